@@ -1,32 +1,173 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
 use std::env;
+use std::fmt;
 use std::fs;
-use std::io::{self, Read};
+use std::io::{self, BufRead, BufWriter, Read, Write};
+use std::path::PathBuf;
 
 use genson_core::{
+    avro::{
+        avro_to_json_schema, field_renames, json_schema_to_avro, normalisation_schema_from_avro,
+        AvroNameStrategy, AvroOptions,
+    },
+    compression::{decompress_reader, open_reader, read_to_string, Compression},
+    coverage::{CoverageTracker, DEFAULT_MAX_EXAMPLES},
+    csv::{parse_csv_char, CsvOptions},
+    export::arrow,
+    generate::{generate_rows, GenerateConfig},
     infer_json_schema,
-    normalise::{normalise_values, MapEncoding, NormaliseConfig},
-    DebugVerbosity, SchemaInferenceConfig,
+    lint::{is_avro, lint_schema, LintConfig, LintRule},
+    nonfinite::{scrub_nonfinite, NonFinitePolicy},
+    normalise::{
+        encode_map_schemas, normalise_values_with_report, try_normalise_values, Coercion,
+        MapEncoding, NormaliseConfig, UnknownFieldPolicy, ValueLimits,
+    },
+    ocf::write_ocf,
+    parquet::write_schema_parquet,
+    registry::{SchemaRegistryClient, SubjectNameStrategy},
+    repair::{repair_json, repair_lines, RowRepairs},
+    schema::{compact, parse_annotations, CompactOptions, EmptyContainerPolicy, DEFAULT_TOP_KEYS},
+    serialise::{self, OutputFormat},
+    variants::{VariantTracker, DEFAULT_TOP_VARIANTS, DEFAULT_VARIANT_EXAMPLES},
+    DebugVerbosity, FieldOrder, FormatDetection, JsonSchemaDraft, KeyFold, MapDetection,
+    OuterScalarPolicy, SampleSpec, SchemaInferenceConfig, SchemaInferenceResult,
+    SchemaInferenceSession, UnionOrder,
 };
+use rayon::prelude::*;
 use serde_json::Value;
 
-fn main() -> Result<(), Box<dyn std::error::Error>> {
-    run_cli()
+/// Schema language to print the inferred schema in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SchemaFormat {
+    JsonSchema,
+    Avro,
+    Delta,
+    Iceberg,
+    Arrow,
+    ArrowIpc,
+    Proto,
+}
+
+impl SchemaFormat {
+    /// Formats converted from the inferred JSON Schema rather than produced by inference.
+    fn is_export(self) -> bool {
+        !matches!(self, SchemaFormat::JsonSchema | SchemaFormat::Avro)
+    }
+}
+
+/// Exit status for input (data or schema) that is not valid JSON.
+const EXIT_PARSE_ERROR: i32 = 2;
+/// Exit status for schema inference failing on valid input, e.g. a resource limit.
+const EXIT_INFERENCE_ERROR: i32 = 3;
+
+/// Lines of NDJSON stdin inferred per batch when streaming.
+const STDIN_BATCH_LINES: usize = 10_000;
+
+/// Exit status for a schema the registry reports as incompatible with the latest version.
+const EXIT_INCOMPATIBLE: i32 = 4;
+/// Exit status for a schema that fails the `--strict` checks.
+const EXIT_STRICT: i32 = 5;
+
+/// An error that ends the process with a specific exit status (1 for anything else).
+#[derive(Debug)]
+struct ExitError {
+    code: i32,
+    message: String,
+}
+
+impl ExitError {
+    fn parse(message: impl Into<String>) -> Self {
+        Self {
+            code: EXIT_PARSE_ERROR,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ExitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for ExitError {}
+
+fn main() {
+    match run_cli() {
+        Ok(()) => {}
+        // Downstream closed the pipe (e.g. `| head`): nothing left to do, exit quietly
+        Err(e) if is_broken_pipe(e.as_ref()) => {}
+        Err(e) => {
+            anstream::eprintln!("Error: {}", e);
+            let code = e.downcast_ref::<ExitError>().map_or(1, |e| e.code);
+            std::process::exit(code);
+        }
+    }
 }
 
 // Extract the main logic into a separate function so we can call it from tests
 fn run_cli() -> Result<(), Box<dyn std::error::Error>> {
     let args: Vec<String> = env::args().collect();
 
+    match args.get(1).map(String::as_str) {
+        Some("lint") => return run_lint(&args[2..]),
+        Some("uncovered") => return run_uncovered(&args[2..]),
+        Some("variants") => return run_variants(&args[2..]),
+        Some("convert") => return run_convert(&args[2..]),
+        Some("generate") => return run_generate(&args[2..]),
+        _ => {}
+    }
+
     // Handle command line options
     let mut config = SchemaInferenceConfig::default();
-    let mut input_file = None;
+    let mut input_patterns: Vec<String> = Vec::new();
+    let mut jobs = 1;
     let mut pq_column: Option<String> = None;
+    let mut pq_batch_size: Option<usize> = None;
+    let mut csv_column: Option<String> = None;
+    let mut csv_delimiter: Option<u8> = None;
+    let mut csv_quote: Option<u8> = None;
+    let mut compression: Option<Compression> = None; // auto-detect from extension
+    let mut stdin_ndjson: Option<bool> = None;
+    let mut progress_every: Option<usize> = None;
+    let mut output_path: Option<String> = None; // stdout by default
+    let mut quiet = false;
+    let mut schema_format = SchemaFormat::JsonSchema;
+    let mut compact_schema = false;
+    let mut encode_map_schema = false; // describe maps as --map-encoding writes them
+    let mut format_sample_rate: Option<f64> = None;
+    let mut output_format = OutputFormat::Json;
+    let mut proto_message = "Root".to_string();
+    let mut proto_package: Option<String> = None;
+    let mut profile_json_path: Option<String> = None;
+    let mut explain_path: Option<String> = None;
+    let mut debug_log_path: Option<String> = None;
+    let mut avro_renames_path: Option<String> = None;
+    let mut presence_report_path: Option<String> = None;
+    let mut map_key_stats_path: Option<String> = None;
+    let mut coercion_report_path: Option<String> = None;
+    let mut repair_report_path: Option<String> = None;
+    let mut parquet_schema_path: Option<String> = None;
+    let mut parquet_rows = 0;
+
+    // Schema registry config
+    let mut registry_url: Option<String> = None;
+    let mut subject: Option<String> = None;
+    let mut topic: Option<String> = None;
+    let mut subject_strategy = SubjectNameStrategy::TopicName;
 
     // Normalisation config
     let mut do_normalise = false;
+    let mut avro_schema_path: Option<String> = None; // normalise against this instead of inferring
+    let mut seed_schema_path: Option<String> = None;
+    let mut output_avro_path: Option<String> = None; // write normalised rows as an Avro container
     let mut empty_as_null = true; // default ON
     let mut coerce_string = false; // default OFF
+    let mut canonical_numeric_keys = false;
     let mut map_encoding = genson_core::normalise::MapEncoding::Mapping; // default
+    let mut unknown_fields = UnknownFieldPolicy::Drop; // default
+    let mut value_limits = ValueLimits::default(); // no truncation
 
     let mut i = 1;
     while i < args.len() {
@@ -41,6 +182,39 @@ fn run_cli() -> Result<(), Box<dyn std::error::Error>> {
             "--ndjson" => {
                 config.delimiter = Some(b'\n');
             }
+            "--stdin-format" => {
+                if i + 1 < args.len() {
+                    stdin_ndjson = match args[i + 1].as_str() {
+                        "json" => Some(false),
+                        "ndjson" => Some(true),
+                        other => {
+                            return Err(format!(
+                                "Invalid value for --stdin-format: {} (expected json|ndjson)",
+                                other
+                            )
+                            .into())
+                        }
+                    };
+                    i += 1;
+                } else {
+                    return Err("Missing value for --stdin-format".into());
+                }
+            }
+            "--progress" => {
+                if i + 1 < args.len() {
+                    progress_every = match args[i + 1].parse::<usize>() {
+                        Ok(n) if n > 0 => Some(n),
+                        _ => {
+                            return Err(
+                                format!("Invalid value for --progress: {}", args[i + 1]).into()
+                            )
+                        }
+                    };
+                    i += 1;
+                } else {
+                    return Err("Missing value for --progress".into());
+                }
+            }
             "--pq-column" => {
                 if i + 1 < args.len() {
                     pq_column = Some(args[i + 1].clone());
@@ -49,13 +223,389 @@ fn run_cli() -> Result<(), Box<dyn std::error::Error>> {
                     return Err("Missing value for --pq-column".into());
                 }
             }
+            "--csv-column" => {
+                if i + 1 < args.len() {
+                    csv_column = Some(args[i + 1].clone());
+                    i += 1;
+                } else {
+                    return Err("Missing value for --csv-column".into());
+                }
+            }
+            "--csv-delimiter" => {
+                if i + 1 < args.len() {
+                    csv_delimiter = Some(
+                        parse_csv_char(&args[i + 1])
+                            .map_err(|e| format!("Invalid value for --csv-delimiter: {}", e))?,
+                    );
+                    i += 1;
+                } else {
+                    return Err("Missing value for --csv-delimiter".into());
+                }
+            }
+            "--csv-quote" => {
+                if i + 1 < args.len() {
+                    csv_quote = Some(
+                        parse_csv_char(&args[i + 1])
+                            .map_err(|e| format!("Invalid value for --csv-quote: {}", e))?,
+                    );
+                    i += 1;
+                } else {
+                    return Err("Missing value for --csv-quote".into());
+                }
+            }
+            "--pq-batch-size" => {
+                if i + 1 < args.len() {
+                    pq_batch_size = match args[i + 1].parse::<usize>() {
+                        Ok(n) if n > 0 => Some(n),
+                        _ => {
+                            return Err(format!(
+                                "Invalid value for --pq-batch-size: {}",
+                                args[i + 1]
+                            )
+                            .into())
+                        }
+                    };
+                    i += 1;
+                } else {
+                    return Err("Missing value for --pq-batch-size".into());
+                }
+            }
+            "--compression" => {
+                if i + 1 < args.len() {
+                    compression = match args[i + 1].as_str() {
+                        "auto" => None,
+                        codec => Some(
+                            codec
+                                .parse::<Compression>()
+                                .map_err(|e| format!("Invalid value for --compression: {}", e))?,
+                        ),
+                    };
+                    i += 1;
+                } else {
+                    return Err("Missing value for --compression".into());
+                }
+            }
+            "--output" | "-o" => {
+                if i + 1 < args.len() {
+                    output_path = Some(args[i + 1].clone());
+                    i += 1;
+                } else {
+                    return Err("Missing value for --output".into());
+                }
+            }
+            "--quiet" | "-q" => {
+                quiet = true;
+            }
             "--avro" => {
                 config.avro = true;
+                schema_format = SchemaFormat::Avro;
+            }
+            "--avro-namespace" => {
+                if i + 1 < args.len() {
+                    config.avro_namespace = Some(args[i + 1].clone());
+                    i += 1;
+                } else {
+                    return Err("Missing value for --avro-namespace".into());
+                }
+            }
+            "--avro-name" => {
+                if i + 1 < args.len() {
+                    config.avro_root_name = Some(args[i + 1].clone());
+                    i += 1;
+                } else {
+                    return Err("Missing value for --avro-name".into());
+                }
+            }
+            "--avro-field-names" => {
+                if i + 1 < args.len() {
+                    config.avro_field_names = match args[i + 1].as_str() {
+                        "keep" => AvroNameStrategy::Keep,
+                        "replace" => AvroNameStrategy::Replace,
+                        "escape" => AvroNameStrategy::Escape,
+                        other => {
+                            return Err(format!(
+                                "Invalid value for --avro-field-names: {} (expected keep|replace|escape)",
+                                other
+                            )
+                            .into());
+                        }
+                    };
+                    i += 1;
+                } else {
+                    return Err("Missing value for --avro-field-names".into());
+                }
+            }
+            "--no-avro-defaults" => {
+                config.avro_defaults = false;
+            }
+            "--avro-renames" => {
+                if i + 1 < args.len() {
+                    avro_renames_path = Some(args[i + 1].clone());
+                    i += 1;
+                } else {
+                    return Err("Missing value for --avro-renames".into());
+                }
+            }
+            "--annotate" => {
+                if i + 1 < args.len() {
+                    let path = &args[i + 1];
+                    let text = fs::read_to_string(path)
+                        .map_err(|e| format!("Failed to read {}: {}", path, e))?;
+                    config.annotations = parse_annotations(&text)?;
+                    i += 1;
+                } else {
+                    return Err("Missing value for --annotate".into());
+                }
+            }
+            "--avro-doc-file" => {
+                if i + 1 < args.len() {
+                    config.avro_docs = read_doc_file(&args[i + 1])?;
+                    i += 1;
+                } else {
+                    return Err("Missing value for --avro-doc-file".into());
+                }
+            }
+            "--draft" => {
+                if i + 1 < args.len() {
+                    config.json_schema_draft = Some(match args[i + 1].as_str() {
+                        "draft-07" | "07" | "7" => JsonSchemaDraft::Draft07,
+                        "2019-09" => JsonSchemaDraft::Draft201909,
+                        "2020-12" => JsonSchemaDraft::Draft202012,
+                        other => {
+                            return Err(format!(
+                                "Invalid value for --draft: {} (expected draft-07|2019-09|2020-12)",
+                                other
+                            )
+                            .into())
+                        }
+                    });
+                    i += 1;
+                } else {
+                    return Err("Missing value for --draft".into());
+                }
+            }
+            "--no-schema-uri" => {
+                config.schema_uri = None;
+            }
+            "--compact" => {
+                compact_schema = true;
+            }
+            "--sort-fields" => {
+                if i + 1 < args.len() {
+                    config.sort_fields = match args[i + 1].as_str() {
+                        "preserve" => FieldOrder::Preserve,
+                        "alphabetical" | "alpha" => FieldOrder::Alphabetical,
+                        "frequency" | "by-frequency" => FieldOrder::ByFrequency,
+                        other => {
+                            return Err(format!(
+                                "Invalid value for --sort-fields: {} (expected preserve|alphabetical|frequency)",
+                                other
+                            )
+                            .into())
+                        }
+                    };
+                    i += 1;
+                } else {
+                    return Err("Missing value for --sort-fields".into());
+                }
+            }
+            "--union-order" => {
+                if i + 1 < args.len() {
+                    config.union_order = match args[i + 1].as_str() {
+                        "canonical" => UnionOrder::Canonical,
+                        "null-first" => UnionOrder::NullFirst,
+                        "frequency" | "by-frequency" => UnionOrder::ByFrequency,
+                        "default-first" => UnionOrder::DefaultFirst,
+                        other => {
+                            return Err(format!(
+                                "Invalid value for --union-order: {} (expected canonical|null-first|frequency|default-first)",
+                                other
+                            )
+                            .into())
+                        }
+                    };
+                    i += 1;
+                } else {
+                    return Err("Missing value for --union-order".into());
+                }
+            }
+            "--repair" => {
+                config.repair = true;
+            }
+            "--repair-report" => {
+                if i + 1 < args.len() {
+                    config.repair = true;
+                    repair_report_path = Some(args[i + 1].clone());
+                    i += 1;
+                } else {
+                    return Err("Missing value for --repair-report".into());
+                }
+            }
+            "--nonfinite" => {
+                if i + 1 < args.len() {
+                    config.nonfinite_policy = match args[i + 1].as_str() {
+                        "null" => Some(NonFinitePolicy::Null),
+                        "string" => Some(NonFinitePolicy::String),
+                        other => {
+                            return Err(format!(
+                                "Invalid value for --nonfinite: {} (expected null|string)",
+                                other
+                            )
+                            .into())
+                        }
+                    };
+                    i += 1;
+                } else {
+                    return Err("Missing value for --nonfinite".into());
+                }
+            }
+            "--null-fallback" => {
+                if i + 1 < args.len() {
+                    match args[i + 1].as_str() {
+                        typ @ ("string" | "integer" | "number" | "boolean") => {
+                            config.null_fallback = Some(typ.to_string());
+                        }
+                        other => {
+                            return Err(format!(
+                                "Invalid value for --null-fallback: {} (expected string|integer|number|boolean)",
+                                other
+                            )
+                            .into())
+                        }
+                    }
+                    i += 1;
+                } else {
+                    return Err("Missing value for --null-fallback".into());
+                }
+            }
+            "--required-threshold" => {
+                if i + 1 < args.len() {
+                    let threshold = args[i + 1]
+                        .parse::<f64>()
+                        .ok()
+                        .filter(|t| (0.0..=1.0).contains(t))
+                        .ok_or_else(|| {
+                            format!(
+                                "Invalid value for --required-threshold: {} (expected a ratio between 0 and 1)",
+                                args[i + 1]
+                            )
+                        })?;
+                    config.required_threshold = Some(threshold);
+                    i += 1;
+                } else {
+                    return Err("Missing value for --required-threshold".into());
+                }
+            }
+            "--presence-report" => {
+                if i + 1 < args.len() {
+                    presence_report_path = Some(args[i + 1].clone());
+                    i += 1;
+                } else {
+                    return Err("Missing value for --presence-report".into());
+                }
+            }
+            "--map-key-stats" => {
+                if i + 1 < args.len() {
+                    map_key_stats_path = Some(args[i + 1].clone());
+                    config.map_key_stats = Some(DEFAULT_TOP_KEYS);
+                    i += 1;
+                } else {
+                    return Err("Missing value for --map-key-stats".into());
+                }
+            }
+            "--arrow" => {
+                config.avro = false;
+                schema_format = SchemaFormat::Arrow;
+            }
+            "--proto" => {
+                config.avro = false;
+                schema_format = SchemaFormat::Proto;
+            }
+            "--proto-message" => {
+                if i + 1 < args.len() {
+                    proto_message = args[i + 1].clone();
+                    i += 1;
+                } else {
+                    return Err("Missing value for --proto-message".into());
+                }
+            }
+            "--proto-package" => {
+                if i + 1 < args.len() {
+                    proto_package = Some(args[i + 1].clone());
+                    i += 1;
+                } else {
+                    return Err("Missing value for --proto-package".into());
+                }
+            }
+            "--output-format" => {
+                if i + 1 < args.len() {
+                    output_format = args[i + 1].parse().map_err(|_| {
+                        format!(
+                            "Invalid value for --output-format: {} (expected json|yaml|toml)",
+                            args[i + 1]
+                        )
+                    })?;
+                    i += 1;
+                } else {
+                    return Err("Missing value for --output-format".into());
+                }
+            }
+            "--format" => {
+                if i + 1 < args.len() {
+                    schema_format = match args[i + 1].as_str() {
+                        "json-schema" | "jsonschema" => SchemaFormat::JsonSchema,
+                        "avro" => SchemaFormat::Avro,
+                        "delta" => SchemaFormat::Delta,
+                        "iceberg" => SchemaFormat::Iceberg,
+                        "arrow" => SchemaFormat::Arrow,
+                        "arrow-ipc" => SchemaFormat::ArrowIpc,
+                        "proto" => SchemaFormat::Proto,
+                        other => {
+                            return Err(format!(
+                                "Invalid value for --format: {} (expected json-schema|avro|delta|iceberg|arrow|arrow-ipc|proto)",
+                                other
+                            )
+                            .into())
+                        }
+                    };
+                    config.avro = schema_format == SchemaFormat::Avro;
+                    i += 1;
+                } else {
+                    return Err("Missing value for --format".into());
+                }
             }
             "--normalise" => {
                 do_normalise = true;
                 config.avro = true;
             }
+            "--seed-schema" => {
+                if i + 1 < args.len() {
+                    seed_schema_path = Some(args[i + 1].clone());
+                    i += 1;
+                } else {
+                    return Err("Missing value for --seed-schema".into());
+                }
+            }
+            "--avro-schema" => {
+                if i + 1 < args.len() {
+                    avro_schema_path = Some(args[i + 1].clone());
+                    do_normalise = true;
+                    config.avro = true;
+                    i += 1;
+                } else {
+                    return Err("Missing value for --avro-schema".into());
+                }
+            }
+            "--output-avro" => {
+                if i + 1 < args.len() {
+                    output_avro_path = Some(args[i + 1].clone());
+                    do_normalise = true;
+                    config.avro = true;
+                    i += 1;
+                } else {
+                    return Err("Missing value for --output-avro".into());
+                }
+            }
             "--coerce-strings" => {
                 coerce_string = true;
             }
@@ -83,9 +633,39 @@ fn run_cli() -> Result<(), Box<dyn std::error::Error>> {
                     return Err("Missing value for --map-max-required-keys".into());
                 }
             }
+            "--map-detection" => {
+                if i + 1 < args.len() {
+                    config.map_detection = match args[i + 1].as_str() {
+                        "merged" => MapDetection::Merged,
+                        "variance" => MapDetection::PerDocumentVariance,
+                        other => {
+                            return Err(format!(
+                                "Invalid value for --map-detection: {} (expected merged|variance)",
+                                other
+                            )
+                            .into());
+                        }
+                    };
+                    i += 1;
+                } else {
+                    return Err("Missing value for --map-detection".into());
+                }
+            }
             "--unify-maps" => {
                 config.unify_maps = true;
             }
+            "--detect-tuples" => {
+                config.detect_tuples = true;
+            }
+            "--coerce-map-array" => {
+                config.coerce_map_array = true;
+            }
+            "--label-maps" => {
+                config.label_maps = true;
+            }
+            "--simplify-labels" => {
+                config.simplify_labels = true;
+            }
             "--no-unify" => {
                 if i + 1 < args.len() {
                     for field in args[i + 1].split(',') {
@@ -99,7 +679,8 @@ fn run_cli() -> Result<(), Box<dyn std::error::Error>> {
             "--force-type" => {
                 if i + 1 < args.len() {
                     for pair in args[i + 1].split(',') {
-                        if let Some((field, typ)) = pair.split_once(':') {
+                        // Split on the last ':' so path keys may contain colons
+                        if let Some((field, typ)) = pair.rsplit_once(':') {
                             config
                                 .force_field_types
                                 .insert(field.to_string(), typ.to_string());
@@ -134,57 +715,1721 @@ fn run_cli() -> Result<(), Box<dyn std::error::Error>> {
                     return Err("Missing value for --force-scalar-promotion".into());
                 }
             }
-            "--map-encoding" => {
+            "--ignore-paths" => {
                 if i + 1 < args.len() {
-                    map_encoding = match args[i + 1].as_str() {
-                        "mapping" => MapEncoding::Mapping,
-                        "entries" => MapEncoding::Entries,
-                        "kv" => MapEncoding::KeyValueEntries,
-                        other => {
-                            return Err(format!(
-                            "Invalid value for --map-encoding: {} (expected mapping|entries|kv)",
-                            other
-                        )
-                            .into())
-                        }
-                    };
+                    for field in args[i + 1].split(',') {
+                        config.ignore_paths.push(field.to_string());
+                    }
+                    i += 1;
+                } else {
+                    return Err("Missing value for --ignore-paths".into());
+                }
+            }
+            "--discriminator" => {
+                if i + 1 < args.len() {
+                    for pair in args[i + 1].split(',') {
+                        // Split on the last ':' so path keys may contain colons
+                        match pair.rsplit_once(':') {
+                            Some((path, field)) if !field.is_empty() => {
+                                config
+                                    .discriminators
+                                    .insert(path.to_string(), field.to_string());
+                            }
+                            _ => {
+                                return Err(format!(
+                                    "Invalid value for --discriminator: {} (expected path:field)",
+                                    pair
+                                )
+                                .into())
+                            }
+                        }
+                    }
+                    i += 1;
+                } else {
+                    return Err("Missing value for --discriminator".into());
+                }
+            }
+            "--select-paths" => {
+                if i + 1 < args.len() {
+                    config.select_paths = read_paths_file(&args[i + 1])?;
+                    i += 1;
+                } else {
+                    return Err("Missing value for --select-paths".into());
+                }
+            }
+            "--max-depth" => {
+                if i + 1 < args.len() {
+                    config.max_depth =
+                        Some(args[i + 1].parse::<usize>().map_err(|_| {
+                            format!("Invalid value for --max-depth: {}", args[i + 1])
+                        })?);
+                    i += 1;
+                } else {
+                    return Err("Missing value for --max-depth".into());
+                }
+            }
+            "--max-object-keys" => {
+                if i + 1 < args.len() {
+                    config.max_object_keys = Some(args[i + 1].parse::<usize>().map_err(|_| {
+                        format!("Invalid value for --max-object-keys: {}", args[i + 1])
+                    })?);
+                    i += 1;
+                } else {
+                    return Err("Missing value for --max-object-keys".into());
+                }
+            }
+            "--empty-containers" => {
+                if i + 1 < args.len() {
+                    config.empty_container_policy = Some(match args[i + 1].as_str() {
+                        "ignore" => EmptyContainerPolicy::Ignore,
+                        "map-of-string" => EmptyContainerPolicy::TypeAsMapOfString,
+                        "nullable" => EmptyContainerPolicy::TypeAsNullable,
+                        other => {
+                            return Err(format!(
+                                "Invalid value for --empty-containers: {} (expected ignore|map-of-string|nullable)",
+                                other
+                            )
+                            .into())
+                        }
+                    });
+                    i += 1;
+                } else {
+                    return Err("Missing value for --empty-containers".into());
+                }
+            }
+            "--map-encoding" => {
+                if i + 1 < args.len() {
+                    map_encoding = match args[i + 1].as_str() {
+                        "mapping" => MapEncoding::Mapping,
+                        "entries" => MapEncoding::Entries,
+                        "kv" => MapEncoding::KeyValueEntries,
+                        other => {
+                            return Err(format!(
+                            "Invalid value for --map-encoding: {} (expected mapping|entries|kv)",
+                            other
+                        )
+                            .into())
+                        }
+                    };
+                    i += 1;
+                } else {
+                    return Err("Missing value for --map-encoding".into());
+                }
+            }
+            "--max-string-len" => {
+                if i + 1 < args.len() {
+                    value_limits.max_string_len =
+                        Some(args[i + 1].parse::<usize>().map_err(|_| {
+                            format!("Invalid value for --max-string-len: {}", args[i + 1])
+                        })?);
+                    i += 1;
+                } else {
+                    return Err("Missing value for --max-string-len".into());
+                }
+            }
+            "--max-array-items" => {
+                if i + 1 < args.len() {
+                    value_limits.max_array_items =
+                        Some(args[i + 1].parse::<usize>().map_err(|_| {
+                            format!("Invalid value for --max-array-items: {}", args[i + 1])
+                        })?);
+                    i += 1;
+                } else {
+                    return Err("Missing value for --max-array-items".into());
+                }
+            }
+            "--max-value-depth" => {
+                if i + 1 < args.len() {
+                    value_limits.max_depth = Some(args[i + 1].parse::<usize>().map_err(|_| {
+                        format!("Invalid value for --max-value-depth: {}", args[i + 1])
+                    })?);
+                    i += 1;
+                } else {
+                    return Err("Missing value for --max-value-depth".into());
+                }
+            }
+            "--truncation-marker" => {
+                if i + 1 < args.len() {
+                    value_limits.marker = args[i + 1].clone();
+                    i += 1;
+                } else {
+                    return Err("Missing value for --truncation-marker".into());
+                }
+            }
+            "--encode-map-schema" => {
+                encode_map_schema = true;
+            }
+            "--canonical-numeric-keys" => {
+                canonical_numeric_keys = true;
+            }
+            "--unknown-fields" => {
+                if i + 1 < args.len() {
+                    unknown_fields = match args[i + 1].as_str() {
+                        "drop" => UnknownFieldPolicy::Drop,
+                        "keep" => UnknownFieldPolicy::Keep,
+                        "error" => UnknownFieldPolicy::Error,
+                        other => {
+                            return Err(format!(
+                                "Invalid value for --unknown-fields: {} (expected drop|keep|error)",
+                                other
+                            )
+                            .into())
+                        }
+                    };
+                    i += 1;
+                } else {
+                    return Err("Missing value for --unknown-fields".into());
+                }
+            }
+            "--no-wrap-scalars" => {
+                config.wrap_scalars = false;
+            }
+            "--wrap-root" => {
+                if i + 1 < args.len() {
+                    config.wrap_root = Some(args[i + 1].clone());
+                    i += 1;
+                } else {
+                    return Err("Missing value for --wrap-root".into());
+                }
+            }
+            "--wrap-root-strict" => {
+                config.wrap_root_strict = true;
+            }
+            "--root-value-field" => {
+                if i + 1 < args.len() {
+                    config.root_value_field = args[i + 1].clone();
+                    i += 1;
+                } else {
+                    return Err("Missing value for --root-value-field".into());
+                }
+            }
+            "--key-fold" => {
+                if i + 1 < args.len() {
+                    config.key_fold = Some(match args[i + 1].as_str() {
+                        "lower" => KeyFold::Lower,
+                        "upper" => KeyFold::Upper,
+                        "preserve" => KeyFold::Preserve,
+                        other => {
+                            return Err(format!(
+                                "Invalid value for --key-fold: {} (expected lower|upper|preserve)",
+                                other
+                            )
+                            .into());
+                        }
+                    });
+                    i += 1;
+                } else {
+                    return Err("Missing value for --key-fold".into());
+                }
+            }
+            "--detect-formats" => {
+                if i + 1 < args.len() {
+                    let mut detection = FormatDetection {
+                        uuid: false,
+                        uri: false,
+                        email: false,
+                        base64: false,
+                        ip: false,
+                        ..Default::default()
+                    };
+                    for format in args[i + 1].split(',') {
+                        match format {
+                            "all" => {
+                                detection = FormatDetection {
+                                    sample_rate: detection.sample_rate,
+                                    ..Default::default()
+                                }
+                            }
+                            "uuid" => detection.uuid = true,
+                            "uri" => detection.uri = true,
+                            "email" => detection.email = true,
+                            "base64" => detection.base64 = true,
+                            "ip" => detection.ip = true,
+                            other => {
+                                return Err(format!(
+                                    "Invalid value for --detect-formats: {} (expected all|uuid|uri|email|base64|ip)",
+                                    other
+                                )
+                                .into());
+                            }
+                        }
+                    }
+                    config.detect_formats = Some(detection);
+                    i += 1;
+                } else {
+                    return Err("Missing value for --detect-formats".into());
+                }
+            }
+            "--detect-numeric-keys" => {
+                config.detect_numeric_keys = true;
+            }
+            "--detect-sparse-arrays" => {
+                config.sparse_array_detection = true;
+            }
+            "--format-sample-rate" => {
+                if i + 1 < args.len() {
+                    format_sample_rate = Some(args[i + 1].parse::<f64>().map_err(|_| {
+                        format!("Invalid value for --format-sample-rate: {}", args[i + 1])
+                    })?);
+                    i += 1;
+                } else {
+                    return Err("Missing value for --format-sample-rate".into());
+                }
+            }
+            "--outer-scalars" => {
+                if i + 1 < args.len() {
+                    config.outer_scalar_policy = match args[i + 1].as_str() {
+                        "array" => OuterScalarPolicy::Array,
+                        "rows" => OuterScalarPolicy::Rows,
+                        other => {
+                            return Err(format!(
+                                "Invalid value for --outer-scalars: {} (expected array|rows)",
+                                other
+                            )
+                            .into());
+                        }
+                    };
+                    i += 1;
+                } else {
+                    return Err("Missing value for --outer-scalars".into());
+                }
+            }
+            "--root-map" => {
+                config.no_root_map = false;
+            }
+            "--embed-config" => {
+                config.embed_config = true;
+            }
+            "--strict" => {
+                config.strict = true;
+            }
+            "--max-builders" => {
+                if i + 1 < args.len() {
+                    config.max_builders = Some(args[i + 1].parse::<usize>().map_err(|_| {
+                        format!("Invalid value for --max-builders: {}", args[i + 1])
+                    })?);
+                    i += 1;
+                } else {
+                    return Err("Missing value for --max-builders".into());
+                }
+            }
+            "--sample" => {
+                if i + 1 < args.len() {
+                    config.sample =
+                        Some(SampleSpec::First(args[i + 1].parse().map_err(|_| {
+                            format!("Invalid value for --sample: {}", args[i + 1])
+                        })?));
+                    i += 1;
+                } else {
+                    return Err("Missing value for --sample".into());
+                }
+            }
+            "--sample-random" => {
+                if i + 1 < args.len() {
+                    config.sample = Some(
+                        args[i + 1]
+                            .parse::<SampleSpec>()
+                            .map_err(|e| format!("Invalid value for --sample-random: {}", e))?,
+                    );
+                    i += 1;
+                } else {
+                    return Err("Missing value for --sample-random".into());
+                }
+            }
+            "--sample-every" => {
+                if i + 1 < args.len() {
+                    config.sample = match args[i + 1].parse::<usize>() {
+                        Ok(k) if k > 0 => Some(SampleSpec::EveryNth(k)),
+                        _ => {
+                            return Err(format!(
+                                "Invalid value for --sample-every: {}",
+                                args[i + 1]
+                            )
+                            .into())
+                        }
+                    };
+                    i += 1;
+                } else {
+                    return Err("Missing value for --sample-every".into());
+                }
+            }
+            "--chunk-size" => {
+                if i + 1 < args.len() {
+                    config.chunk_size = match args[i + 1].parse::<usize>() {
+                        Ok(n) if n > 0 => Some(n),
+                        _ => {
+                            return Err(
+                                format!("Invalid value for --chunk-size: {}", args[i + 1]).into()
+                            )
+                        }
+                    };
+                    i += 1;
+                } else {
+                    return Err("Missing value for --chunk-size".into());
+                }
+            }
+            "--error-context" => {
+                if i + 1 < args.len() {
+                    config.error_context_chars = args[i + 1].parse::<usize>().map_err(|_| {
+                        format!("Invalid value for --error-context: {}", args[i + 1])
+                    })?;
+                    i += 1;
+                } else {
+                    return Err("Missing value for --error-context".into());
+                }
+            }
+            "--error-dump-dir" => {
+                if i + 1 < args.len() {
+                    config.error_dump_dir = Some(args[i + 1].clone().into());
+                    i += 1;
+                } else {
+                    return Err("Missing value for --error-dump-dir".into());
+                }
+            }
+            "--debug" => {
+                config.debug = true;
+            }
+            "--profile" => {
+                config.profile = true;
+            }
+            "--profile-json" => {
+                if i + 1 < args.len() {
+                    profile_json_path = Some(args[i + 1].clone());
+                    config.collect_profile = true;
+                    i += 1;
+                } else {
+                    return Err("Missing value for --profile-json".into());
+                }
+            }
+            "--explain" => {
+                if i + 1 < args.len() {
+                    explain_path = Some(args[i + 1].clone());
+                    config.explain = true;
+                    i += 1;
+                } else {
+                    return Err("Missing value for --explain".into());
+                }
+            }
+            "--debug-log" => {
+                if i + 1 < args.len() {
+                    debug_log_path = Some(args[i + 1].clone());
+                    config.decision_log = true;
+                    i += 1;
+                } else {
+                    return Err("Missing value for --debug-log".into());
+                }
+            }
+            "--parquet-schema" => {
+                if i + 1 < args.len() {
+                    parquet_schema_path = Some(args[i + 1].clone());
+                    i += 1;
+                } else {
+                    return Err("Missing value for --parquet-schema".into());
+                }
+            }
+            "--parquet-rows" => {
+                if i + 1 < args.len() {
+                    parquet_rows = args[i + 1].parse::<usize>().map_err(|_| {
+                        format!("Invalid value for --parquet-rows: {}", args[i + 1])
+                    })?;
+                    i += 1;
+                } else {
+                    return Err("Missing value for --parquet-rows".into());
+                }
+            }
+            "--coercion-report" => {
+                if i + 1 < args.len() {
+                    coercion_report_path = Some(args[i + 1].clone());
+                    i += 1;
+                } else {
+                    return Err("Missing value for --coercion-report".into());
+                }
+            }
+            "--register-schema" => {
+                if i + 1 < args.len() {
+                    registry_url = Some(args[i + 1].clone());
+                    i += 1;
+                } else {
+                    return Err("Missing value for --register-schema".into());
+                }
+            }
+            "--subject" => {
+                if i + 1 < args.len() {
+                    subject = Some(args[i + 1].clone());
+                    i += 1;
+                } else {
+                    return Err("Missing value for --subject".into());
+                }
+            }
+            "--topic" => {
+                if i + 1 < args.len() {
+                    topic = Some(args[i + 1].clone());
+                    i += 1;
+                } else {
+                    return Err("Missing value for --topic".into());
+                }
+            }
+            "--subject-strategy" => {
+                if i + 1 < args.len() {
+                    subject_strategy = match args[i + 1].as_str() {
+                        "topic" => SubjectNameStrategy::TopicName,
+                        "record" => SubjectNameStrategy::RecordName,
+                        "topic-record" => SubjectNameStrategy::TopicRecordName,
+                        other => {
+                            return Err(format!(
+                                "Invalid value for --subject-strategy: {} (expected topic|record|topic-record)",
+                                other
+                            )
+                            .into())
+                        }
+                    };
+                    i += 1;
+                } else {
+                    return Err("Missing value for --subject-strategy".into());
+                }
+            }
+            "--verbose" => {
+                config.verbosity = DebugVerbosity::Verbose;
+            }
+            "--jobs" | "-j" => {
+                if i + 1 < args.len() {
+                    jobs = match args[i + 1].parse::<usize>() {
+                        Ok(n) if n > 0 => n,
+                        _ => {
+                            return Err(format!("Invalid value for --jobs: {}", args[i + 1]).into())
+                        }
+                    };
+                    i += 1;
+                } else {
+                    return Err("Missing value for --jobs".into());
+                }
+            }
+            _ => {
+                if !args[i].starts_with('-') {
+                    input_patterns.push(args[i].clone());
+                }
+            }
+        }
+        i += 1;
+    }
+
+    let mut input_files = expand_inputs(&input_patterns)?;
+    // Several files are inferred separately and merged; one file is read as before
+    let multi_file = input_files.len() > 1;
+    if let Some(ndjson) = stdin_ndjson {
+        if !input_files.is_empty() || pq_column.is_some() || csv_column.is_some() {
+            return Err("--stdin-format only applies to JSON read from stdin".into());
+        }
+        config.delimiter = ndjson.then_some(b'\n');
+    }
+    let input_file = if multi_file { None } else { input_files.pop() };
+    if multi_file {
+        if pq_column.is_some() {
+            return Err("--pq-column takes a single input file".into());
+        }
+        if csv_column.is_some() {
+            return Err("--csv-column takes a single input file".into());
+        }
+        if config.sample.is_some() {
+            return Err("--sample cannot be combined with multiple input files".into());
+        }
+        if profile_json_path.is_some() {
+            return Err("--profile-json cannot be combined with multiple input files".into());
+        }
+    }
+
+    if config.json_schema_draft.is_some() && schema_format != SchemaFormat::JsonSchema {
+        return Err("--draft only applies to JSON Schema output".into());
+    }
+    if compact_schema && schema_format != SchemaFormat::JsonSchema {
+        return Err("--compact only applies to JSON Schema output".into());
+    }
+    if encode_map_schema {
+        if map_encoding == MapEncoding::Mapping {
+            return Err("--encode-map-schema requires --map-encoding entries|kv".into());
+        }
+        if !matches!(schema_format, SchemaFormat::JsonSchema | SchemaFormat::Avro) {
+            return Err("--encode-map-schema only applies to JSON Schema and Avro output".into());
+        }
+    }
+    if let Some(rate) = format_sample_rate {
+        match config.detect_formats.as_mut() {
+            Some(detection) => detection.sample_rate = rate,
+            None => return Err("--format-sample-rate requires --detect-formats".into()),
+        }
+    }
+
+    if registry_url.is_some() {
+        if schema_format.is_export() {
+            return Err(
+                "--register-schema cannot be combined with --format delta|iceberg|arrow|arrow-ipc|proto"
+                    .into(),
+            );
+        }
+        if subject.is_none()
+            && topic.is_none()
+            && subject_strategy != SubjectNameStrategy::RecordName
+        {
+            return Err("--register-schema requires --subject or --topic".into());
+        }
+        // Registries hold Avro schemas
+        config.avro = true;
+        schema_format = SchemaFormat::Avro;
+    } else if subject.is_some() || topic.is_some() {
+        return Err("--subject and --topic require --register-schema".into());
+    }
+
+    if csv_column.is_some() && pq_column.is_some() {
+        return Err("--csv-column cannot be combined with --pq-column".into());
+    }
+    if (csv_delimiter.is_some() || csv_quote.is_some()) && csv_column.is_none() {
+        return Err("--csv-delimiter and --csv-quote require --csv-column".into());
+    }
+
+    if pq_batch_size.is_some() {
+        if pq_column.is_none() {
+            return Err("--pq-batch-size requires --pq-column".into());
+        }
+        if config.sample.is_some() {
+            return Err("--pq-batch-size cannot be combined with --sample".into());
+        }
+        if profile_json_path.is_some() {
+            return Err("--pq-batch-size cannot be combined with --profile-json".into());
+        }
+    }
+    let ndjson = config.delimiter == Some(b'\n');
+
+    // NDJSON is inferred as it arrives, unless the whole input is needed at once (to
+    // sample or profile it)
+    let streamable = ndjson
+        && !multi_file
+        && pq_column.is_none()
+        && csv_column.is_none()
+        && config.sample.is_none()
+        && config.limits.is_unlimited()
+        && profile_json_path.is_none()
+        && repair_report_path.is_none()
+        && parquet_rows == 0;
+    // Stdin can't be read again to normalise the rows
+    let stream_stdin = streamable && input_file.is_none() && !do_normalise;
+    // A compressed file would take more memory decoded than on disk, so it is decoded
+    // as a stream, twice when normalising (once to infer, once to write the rows)
+    let stream_file = input_file.clone().filter(|path| {
+        streamable
+            && compression.unwrap_or_else(|| Compression::from_path(path)) != Compression::None
+    });
+    if progress_every.is_some() && !stream_stdin {
+        return Err(
+            "--progress requires NDJSON on stdin, without --normalise, --sample or --profile-json"
+                .into(),
+        );
+    }
+
+    // Batched Parquet input is read twice (once to infer, once to normalise)
+    // rather than held in memory: (path, column, batch size)
+    let mut pq_batched: Option<(String, String, usize)> = None;
+
+    // Documents processed per input file, when there are several
+    let mut file_counts: Vec<(String, usize)> = Vec::new();
+
+    // For CLI, we treat the entire input as one JSON string
+    let json_strings = if multi_file || stream_stdin || stream_file.is_some() {
+        // Read file by file (or line by line) during inference instead
+        Vec::new()
+    } else if let Some(ref col_name) = pq_column {
+        // Parquet mode
+        let path = input_file.ok_or("--pq-column requires an input file path")?;
+
+        if let Some(batch_size) = pq_batch_size {
+            pq_batched = Some((path, col_name.clone(), batch_size));
+            Vec::new()
+        } else {
+            let strings = genson_core::parquet::read_string_column(&path, col_name)?;
+            split_column_strings(strings, ndjson)
+        }
+    } else if let Some(ref col_name) = csv_column {
+        // CSV mode: parsed record by record, keeping only the JSON column
+        let (reader, mut options) = match input_file {
+            Some(ref path) => (open_reader(path, compression)?, CsvOptions::for_path(path)),
+            None => (
+                decompress_reader(io::stdin(), compression.unwrap_or_default())?,
+                CsvOptions::default(),
+            ),
+        };
+        if let Some(delimiter) = csv_delimiter {
+            options.delimiter = delimiter;
+        }
+        if let Some(quote) = csv_quote {
+            options.quote = quote;
+        }
+        let strings = genson_core::csv::read_string_column(reader, col_name, options)?;
+        split_column_strings(strings, ndjson)
+    } else {
+        // Original JSON/JSONL mode - pass as single string, let core handle delimiter
+        // Compressed input is decoded as a stream, never buffered in compressed form
+        let input = if let Some(path) = input_file {
+            read_to_string(&path, compression)?
+        } else {
+            let mut buffer = String::new();
+            decompress_reader(io::stdin(), compression.unwrap_or_default())?
+                .read_to_string(&mut buffer)?;
+            buffer
+        };
+        vec![input] // Don't clone, just move
+    };
+
+    if let Some(path) = &repair_report_path {
+        if multi_file || pq_batched.is_some() {
+            return Err(
+                "--repair-report cannot be combined with multiple input files or --pq-batch-size"
+                    .into(),
+            );
+        }
+        write_repair_report(path, &json_strings, ndjson)?;
+    }
+
+    if output_format != OutputFormat::Json {
+        if do_normalise {
+            return Err("--output-format only applies to schema output, not --normalise".into());
+        }
+        if matches!(schema_format, SchemaFormat::Proto | SchemaFormat::ArrowIpc) {
+            return Err("--output-format cannot be combined with --format arrow-ipc|proto".into());
+        }
+    }
+
+    if do_normalise && schema_format.is_export() {
+        return Err(
+            "--normalise cannot be combined with --format delta|iceberg|arrow|arrow-ipc|proto"
+                .into(),
+        );
+    }
+
+    if output_avro_path.is_some() && output_path.is_some() {
+        return Err("--output-avro cannot be combined with --output".into());
+    }
+
+    if parquet_rows > 0 {
+        if parquet_schema_path.is_none() {
+            return Err("--parquet-rows requires --parquet-schema".into());
+        }
+        if multi_file || pq_batch_size.is_some() {
+            return Err(
+                "--parquet-rows cannot be combined with multiple input files or --pq-batch-size"
+                    .into(),
+            );
+        }
+    }
+
+    if coercion_report_path.is_some() && !do_normalise {
+        return Err("--coercion-report requires --normalise or --avro-schema".into());
+    }
+
+    if canonical_numeric_keys && !do_normalise {
+        return Err("--canonical-numeric-keys requires --normalise or --avro-schema".into());
+    }
+
+    if value_limits != ValueLimits::default() && !do_normalise {
+        return Err(
+            "--max-string-len, --max-array-items, --max-value-depth and --truncation-marker require --normalise or --avro-schema"
+                .into(),
+        );
+    }
+
+    if profile_json_path.is_some() && avro_schema_path.is_some() {
+        return Err("--profile-json cannot be combined with --avro-schema".into());
+    }
+
+    if explain_path.is_some() && avro_schema_path.is_some() {
+        return Err("--explain cannot be combined with --avro-schema".into());
+    }
+    if map_key_stats_path.is_some() && avro_schema_path.is_some() {
+        return Err("--map-key-stats cannot be combined with --avro-schema".into());
+    }
+    if debug_log_path.is_some() && avro_schema_path.is_some() {
+        return Err("--debug-log cannot be combined with --avro-schema".into());
+    }
+
+    if let Some(ref path) = seed_schema_path {
+        if avro_schema_path.is_some() {
+            return Err("--seed-schema cannot be combined with --avro-schema".into());
+        }
+        let text = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read seed schema {}: {}", path, e))?;
+        config.seed_schema = Some(
+            serde_json::from_str(&text)
+                .map_err(|e| format!("Invalid JSON in seed schema {}: {}", path, e))?,
+        );
+    }
+    if presence_report_path.is_some() {
+        if avro_schema_path.is_some() {
+            return Err("--presence-report cannot be combined with --avro-schema".into());
+        }
+        // Report without changing which fields are required
+        config.required_threshold.get_or_insert(1.0);
+    }
+
+    let result = if let Some(ref path) = avro_schema_path {
+        // Normalising against a supplied Avro schema skips inference entirely
+        let avsc = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read Avro schema {}: {}", path, e))?;
+        let schema = normalisation_schema_from_avro(&avsc)
+            .map_err(|e| format!("Failed to load Avro schema {}: {}", path, e))?;
+        SchemaInferenceResult {
+            schema,
+            processed_count: 0,
+            limit_reached: None,
+            profile: None,
+            explanation: None,
+            decision_log: None,
+            defaulted_fields: Vec::new(),
+            presence: None,
+            key_collisions: Vec::new(),
+            map_key_stats: None,
+        }
+    } else {
+        // Infer schema - genson-core should handle any panics and return proper errors
+        let inference_error = |e: String| ExitError {
+            code: if e.starts_with("Invalid JSON") || e.starts_with("Invalid UTF-8") {
+                EXIT_PARSE_ERROR
+            } else if e.starts_with("Strict mode") {
+                EXIT_STRICT
+            } else {
+                EXIT_INFERENCE_ERROR
+            },
+            message: format!("Schema inference failed: {}", e),
+        };
+        if multi_file {
+            // One session per file, on up to `jobs` threads, merged in input order
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(jobs)
+                .build()
+                .map_err(|e| format!("Failed to start {} jobs: {}", jobs, e))?;
+            let sessions = pool.install(|| {
+                input_files
+                    .par_iter()
+                    .map(|path| infer_file(path, compression, &config, ndjson))
+                    .collect::<Result<Vec<_>, _>>()
+            })?;
+            let mut merged = SchemaInferenceSession::new(config.clone());
+            for (path, session) in input_files.iter().zip(sessions) {
+                file_counts.push((path.clone(), session.processed_count()));
+                merged.merge(session)?;
+            }
+            merged.result().map_err(inference_error)?
+        } else if let Some((ref path, ref col_name, batch_size)) = pq_batched {
+            // Each batch is merged into the session's schema and dropped
+            let mut session = SchemaInferenceSession::new(config.clone());
+            for batch in read_pq_batches(path, col_name, batch_size, ndjson)? {
+                session.add_strings(&batch?).map_err(inference_error)?;
+            }
+            session.result().map_err(inference_error)?
+        } else if let Some(ref path) = stream_file {
+            infer_ndjson_stream(open_reader(path, compression)?, &config, None)
+                .map_err(inference_error)?
+                .result()
+                .map_err(inference_error)?
+        } else if stream_stdin {
+            let reader = decompress_reader(io::stdin(), compression.unwrap_or_default())?;
+            let progress = progress_every.filter(|_| !quiet);
+            infer_ndjson_stream(reader, &config, progress)
+                .map_err(inference_error)?
+                .result()
+                .map_err(inference_error)?
+        } else {
+            infer_json_schema(&json_strings, Some(config.clone())).map_err(inference_error)?
+        }
+    };
+    let mut processed_count = result.processed_count;
+
+    if let (Some(path), Some(profile)) = (&profile_json_path, &result.profile) {
+        let file = fs::File::create(path)
+            .map_err(|e| format!("Failed to create profile file {}: {}", path, e))?;
+        let mut writer = BufWriter::new(file);
+        serde_json::to_writer_pretty(&mut writer, profile)?;
+        writer.flush()?;
+    }
+
+    if let (Some(path), Some(presence)) = (&presence_report_path, &result.presence) {
+        let file = fs::File::create(path)
+            .map_err(|e| format!("Failed to create presence report {}: {}", path, e))?;
+        let mut writer = BufWriter::new(file);
+        serde_json::to_writer_pretty(&mut writer, presence)?;
+        writer.write_all(b"\n")?;
+        writer.flush()?;
+    }
+
+    if let (Some(path), Some(stats)) = (&map_key_stats_path, &result.map_key_stats) {
+        let file = fs::File::create(path)
+            .map_err(|e| format!("Failed to create map key stats {}: {}", path, e))?;
+        let mut writer = BufWriter::new(file);
+        serde_json::to_writer_pretty(&mut writer, stats)?;
+        writer.write_all(b"\n")?;
+        writer.flush()?;
+    }
+
+    if let (Some(path), Some(explanation)) = (&explain_path, &result.explanation) {
+        let file = fs::File::create(path)
+            .map_err(|e| format!("Failed to create explanation file {}: {}", path, e))?;
+        let mut writer = BufWriter::new(file);
+        serde_json::to_writer_pretty(&mut writer, explanation)?;
+        writer.write_all(b"\n")?;
+        writer.flush()?;
+    }
+
+    if let (Some(path), Some(events)) = (&debug_log_path, &result.decision_log) {
+        let file = fs::File::create(path)
+            .map_err(|e| format!("Failed to create debug log {}: {}", path, e))?;
+        let mut writer = BufWriter::new(file);
+        for event in events {
+            serde_json::to_writer(&mut writer, event)?;
+            writer.write_all(b"\n")?;
+        }
+        writer.flush()?;
+    }
+
+    if let Some(path) = &avro_renames_path {
+        let renames = if is_avro(&result.schema) {
+            field_renames(&result.schema)
+        } else {
+            field_renames(&json_schema_to_avro(
+                &result.schema,
+                &AvroOptions::from_config(&config),
+            )?)
+        };
+        let file = fs::File::create(path)
+            .map_err(|e| format!("Failed to create renames file {}: {}", path, e))?;
+        let mut writer = BufWriter::new(file);
+        serde_json::to_writer_pretty(&mut writer, &renames)?;
+        writer.write_all(b"\n")?;
+        writer.flush()?;
+    }
+
+    if let Some(path) = &parquet_schema_path {
+        let (json_schema, avro_schema) = if is_avro(&result.schema) {
+            (avro_to_json_schema(&result.schema)?, result.schema.clone())
+        } else {
+            let avro = json_schema_to_avro(&result.schema, &AvroOptions::from_config(&config))?;
+            (result.schema.clone(), avro)
+        };
+        let rows = if parquet_rows > 0 {
+            // Arrow maps are read from objects, whatever --map-encoding says
+            let cfg = NormaliseConfig {
+                empty_as_null,
+                coerce_string,
+                wrap_root: config.wrap_root.clone(),
+                root_value_field: config.root_value_field.clone(),
+                simplify_labels: config.simplify_labels,
+                coerce_map_array: config.coerce_map_array,
+                sparse_array_detection: config.sparse_array_detection,
+                key_fold: config.key_fold,
+                nonfinite_policy: config.nonfinite_policy,
+                select_paths: config.select_paths.clone(),
+                ..NormaliseConfig::default()
+            };
+            // Arrow columns keep the JSON keys, so rows aren't renamed to Avro names
+            let row_schema = json_schema_to_avro(
+                &json_schema,
+                &AvroOptions {
+                    field_names: AvroNameStrategy::Keep,
+                    ..AvroOptions::from_config(&config)
+                },
+            )?;
+            let documents = leading_documents(&json_strings, ndjson, &config, parquet_rows)?;
+            try_normalise_values(documents, &row_schema, &cfg)?
+        } else {
+            Vec::new()
+        };
+        write_schema_parquet(path, &json_schema, Some(&avro_schema), &rows)?;
+    }
+
+    if !quiet && !result.key_collisions.is_empty() {
+        let merged: Vec<String> = result
+            .key_collisions
+            .iter()
+            .map(|c| format!("{} ({})", c.name, c.keys.join(", ")))
+            .collect();
+        anstream::eprintln!(
+            "Merged keys that differ only in case: {}",
+            merged.join("; ")
+        );
+    }
+
+    if !quiet && !result.defaulted_fields.is_empty() {
+        anstream::eprintln!(
+            "Typed null-only field(s) as {}: {}",
+            config.null_fallback.as_deref().unwrap_or_default(),
+            result.defaulted_fields.join(", ")
+        );
+    }
+
+    if let Some(url) = &registry_url {
+        let subject = match subject {
+            Some(subject) => subject,
+            None => subject_strategy.subject(topic.as_deref(), &result.schema)?,
+        };
+        let client = SchemaRegistryClient::new(url)?;
+        if !client.check_compatibility(&subject, &result.schema)? {
+            return Err(ExitError {
+                code: EXIT_INCOMPATIBLE,
+                message: format!(
+                    "Schema is not compatible with the latest version of subject {}",
+                    subject
+                ),
+            }
+            .into());
+        }
+        let id = client.register(&subject, &result.schema)?;
+        if !quiet {
+            anstream::eprintln!("Registered schema under subject {} with id {}", subject, id);
+        }
+    }
+
+    let mut out = open_output(output_path.as_deref())?;
+
+    if do_normalise {
+        let schema = &result.schema;
+
+        let cfg = NormaliseConfig {
+            empty_as_null,
+            coerce_string,
+            map_encoding,
+            wrap_root: config.wrap_root.clone(),
+            root_value_field: config.root_value_field.clone(),
+            simplify_labels: config.simplify_labels,
+            coerce_map_array: config.coerce_map_array,
+            sparse_array_detection: config.sparse_array_detection,
+            unknown_fields,
+            threads: None,
+            key_fold: config.key_fold,
+            value_limits,
+            canonical_numeric_keys,
+            nonfinite_policy: config.nonfinite_policy,
+            select_paths: config.select_paths.clone(),
+        };
+
+        // Each row is parsed, normalised and written before the next is touched
+        let rows: Box<dyn Iterator<Item = Result<Cow<str>, String>>> =
+            if let Some((ref path, ref col_name, batch_size)) = pq_batched {
+                // Batched Parquet mode: read the column again, one batch at a time
+                Box::new(
+                    read_pq_batches(path, col_name, batch_size, ndjson)?.flat_map(|batch| {
+                        match batch {
+                            Ok(rows) => rows.into_iter().map(|r| Ok(Cow::Owned(r))).collect(),
+                            Err(e) => vec![Err(e)],
+                        }
+                    }),
+                )
+            } else if let Some(ref path) = stream_file {
+                // Compressed NDJSON: decode the file again, a line at a time
+                Box::new(ndjson_lines(path, compression)?.map(|line| line.map(Cow::Owned)))
+            } else if multi_file {
+                // Several files: read each again in turn, NDJSON a line at a time
+                Box::new(input_files.iter().flat_map(
+                    |path| -> Box<dyn Iterator<Item = Result<Cow<str>, String>>> {
+                        if ndjson {
+                            match ndjson_lines(path, compression) {
+                                Ok(lines) => Box::new(lines.map(|line| line.map(Cow::Owned))),
+                                Err(e) => Box::new(std::iter::once(Err(e))),
+                            }
+                        } else {
+                            Box::new(std::iter::once(
+                                read_to_string(path, compression).map(Cow::Owned),
+                            ))
+                        }
+                    },
+                ))
+            } else if pq_column.is_some() || csv_column.is_some() {
+                // Parquet/CSV mode: json_strings is already split correctly
+                Box::new(json_strings.iter().map(|s| Ok(Cow::Borrowed(s.as_str()))))
+            } else if config.delimiter == Some(b'\n') {
+                // NDJSON mode: split the single string by lines
+                Box::new(
+                    json_strings[0]
+                        .lines()
+                        .filter(|l| !l.trim().is_empty())
+                        .map(|l| Ok(Cow::Borrowed(l))),
+                )
+            } else {
+                // Regular JSON: one document, or the items of an outer array
+                match serde_json::from_str::<Value>(&scrub(
+                    &json_strings[0],
+                    config.repair,
+                    cfg.nonfinite_policy,
+                )) {
+                    Ok(Value::Array(items)) if is_outer_array(&items, &config) => Box::new(
+                        items
+                            .into_iter()
+                            .map(|item| Ok(Cow::Owned(item.to_string()))),
+                    ),
+                    _ => Box::new(std::iter::once(Ok(Cow::Borrowed(json_strings[0].as_str())))),
+                }
+            };
+        let mut row_count = 0;
+        let mut coercions: Vec<Coercion> = Vec::new();
+        let normalised_rows = rows.map(|row| {
+            let row = row?;
+            row_count += 1;
+            let value =
+                serde_json::from_str::<Value>(&scrub(&row, config.repair, cfg.nonfinite_policy))
+                    .unwrap_or(Value::Null);
+            let normed = if coercion_report_path.is_some() {
+                normalise_values_with_report(vec![value], schema, &cfg).map(|(normed, report)| {
+                    // Rows are normalised one at a time, so renumber from the row count
+                    coercions.extend(report.into_iter().map(|c| Coercion {
+                        row: row_count - 1,
+                        ..c
+                    }));
+                    normed
+                })
+            } else {
+                try_normalise_values(vec![value], schema, &cfg)
+            };
+            normed
+                .map(|mut normed| normed.pop().unwrap_or(Value::Null))
+                .map_err(|e| format!("Row {}: {}", row_count, e))
+        });
+
+        if let Some(path) = &output_avro_path {
+            // Rows are encoded into the container file, so nothing goes to stdout
+            let file = fs::File::create(path)
+                .map_err(|e| format!("Failed to create Avro file {}: {}", path, e))?;
+            let written = if encode_map_schema {
+                write_ocf(
+                    &encode_map_schemas(schema, map_encoding),
+                    normalised_rows,
+                    BufWriter::new(file),
+                )
+            } else {
+                write_ocf(schema, normalised_rows, BufWriter::new(file))
+            };
+            if let Err(e) = written {
+                // Don't leave a truncated container behind
+                let _ = fs::remove_file(path);
+                return Err(e.into());
+            }
+        } else if config.delimiter == Some(b'\n') {
+            // print one line per row
+            for v in normalised_rows {
+                serde_json::to_writer(&mut out, &v?)?;
+                out.write_all(b"\n")?;
+            }
+        } else {
+            let normalised = normalised_rows.collect::<Result<Vec<Value>, _>>()?;
+            serde_json::to_writer_pretty(&mut out, &normalised)?;
+            out.write_all(b"\n")?;
+        }
+        if avro_schema_path.is_some() {
+            processed_count = row_count;
+        }
+
+        if let Some(path) = &coercion_report_path {
+            let file = fs::File::create(path)
+                .map_err(|e| format!("Failed to create coercion report {}: {}", path, e))?;
+            let mut writer = BufWriter::new(file);
+            serde_json::to_writer_pretty(&mut writer, &coercions)?;
+            writer.write_all(b"\n")?;
+            writer.flush()?;
+        }
+    } else {
+        // Write the schema out, converting it if another schema language was requested
+        let schema = if encode_map_schema {
+            encode_map_schemas(&result.schema, map_encoding)
+        } else {
+            result.schema.clone()
+        };
+        let document = match schema_format {
+            SchemaFormat::JsonSchema if compact_schema => {
+                let mut schema = schema;
+                compact(&mut schema, CompactOptions::default());
+                schema
+            }
+            SchemaFormat::JsonSchema | SchemaFormat::Avro => schema,
+            SchemaFormat::Delta => result.to_delta_schema()?,
+            SchemaFormat::Iceberg => result.to_iceberg_schema()?,
+            SchemaFormat::Arrow => arrow::schema_to_json(&result.to_arrow_schema()?)?,
+            SchemaFormat::Proto => {
+                // .proto text already ends with a newline
+                let proto = result.to_proto_schema(&proto_message, proto_package.as_deref())?;
+                out.write_all(proto.as_bytes())?;
+                out.finish()?;
+                if !quiet {
+                    print_summary(result.processed_count, &file_counts);
+                }
+                return Ok(());
+            }
+            SchemaFormat::ArrowIpc => {
+                // Binary output: no trailing newline
+                out.write_all(&arrow::schema_to_ipc(&result.to_arrow_schema()?)?)?;
+                out.finish()?;
+                if !quiet {
+                    print_summary(result.processed_count, &file_counts);
+                }
+                return Ok(());
+            }
+        };
+        out.write_all(serialise::to_string(&document, output_format)?.as_bytes())?;
+    }
+    out.finish()?;
+
+    if !quiet {
+        print_summary(processed_count, &file_counts);
+    }
+    Ok(())
+}
+
+/// Print the processed document count to stderr, with a line per input file when
+/// there were several.
+fn print_summary(processed_count: usize, file_counts: &[(String, usize)]) {
+    if file_counts.is_empty() {
+        anstream::eprintln!("Processed {} JSON object(s)", processed_count);
+        return;
+    }
+    anstream::eprintln!(
+        "Processed {} JSON object(s) from {} files",
+        processed_count,
+        file_counts.len()
+    );
+    for (path, count) in file_counts {
+        anstream::eprintln!("  {}: {}", path, count);
+    }
+}
+
+/// Expand glob patterns among the input arguments into file paths, in order.
+///
+/// Arguments naming an existing file are taken literally, so paths containing
+/// `*`, `?` or `[` still work. A pattern that matches nothing is an error.
+fn expand_inputs(patterns: &[String]) -> Result<Vec<String>, String> {
+    let mut files = Vec::new();
+    for pattern in patterns {
+        if std::path::Path::new(pattern).exists() || !pattern.contains(['*', '?', '[']) {
+            files.push(pattern.clone());
+            continue;
+        }
+        let paths =
+            glob::glob(pattern).map_err(|e| format!("Invalid glob pattern {}: {}", pattern, e))?;
+        let before = files.len();
+        for path in paths {
+            let path = path.map_err(|e| format!("Failed to read {}: {}", pattern, e))?;
+            files.push(path.to_string_lossy().into_owned());
+        }
+        if files.len() == before {
+            return Err(format!("No files match {}", pattern));
+        }
+    }
+    Ok(files)
+}
+
+/// Infer one input file into its own session. NDJSON is split into lines so
+/// the session counts each line as a document.
+fn infer_file(
+    path: &str,
+    compression: Option<Compression>,
+    config: &SchemaInferenceConfig,
+    ndjson: bool,
+) -> Result<SchemaInferenceSession, ExitError> {
+    let read_error = |message| ExitError { code: 1, message };
+    let inferred = if ndjson {
+        let reader = open_reader(path, compression).map_err(read_error)?;
+        infer_ndjson_stream(reader, config, None)
+    } else {
+        let content = read_to_string(path, compression).map_err(read_error)?;
+        let mut session = SchemaInferenceSession::new(config.clone());
+        session.add_strings(&[content]).map(|_| session)
+    };
+    inferred.map_err(|e| ExitError {
+        code: if e.starts_with("Invalid JSON") || e.starts_with("Invalid UTF-8") {
+            EXIT_PARSE_ERROR
+        } else {
+            EXIT_INFERENCE_ERROR
+        },
+        message: format!("Schema inference failed for {}: {}", path, e),
+    })
+}
+
+/// The non-blank lines of a (possibly compressed) NDJSON file, decoded as they are read.
+fn ndjson_lines(
+    path: &str,
+    compression: Option<Compression>,
+) -> Result<impl Iterator<Item = Result<String, String>>, String> {
+    let path = path.to_string();
+    Ok(open_reader(&path, compression)?
+        .lines()
+        .map(move |line| line.map_err(|e| format!("Failed to read '{}': {}", path, e)))
+        .filter(|line| !matches!(line, Ok(l) if l.trim().is_empty())))
+}
+
+/// Infer NDJSON from `reader` as it arrives, [`STDIN_BATCH_LINES`] lines at a
+/// time, so only the session's merged schema and one batch are held in memory.
+/// With `progress`, the number of lines read is printed to stderr every that
+/// many lines.
+fn infer_ndjson_stream(
+    reader: impl BufRead,
+    config: &SchemaInferenceConfig,
+    progress: Option<usize>,
+) -> Result<SchemaInferenceSession, String> {
+    let mut session = SchemaInferenceSession::new(config.clone());
+    let mut batch: Vec<String> = Vec::with_capacity(STDIN_BATCH_LINES);
+    let mut first_line = 1;
+    let mut add_batch = |batch: &mut Vec<String>, first_line: usize| -> Result<(), String> {
+        if !batch.is_empty() {
+            session
+                .add_strings(batch)
+                .map_err(|e| format!("{} (in the batch from line {})", e, first_line))?;
+            batch.clear();
+        }
+        Ok(())
+    };
+
+    let mut lines_read = 0;
+    for (i, line) in reader.lines().enumerate() {
+        let line = line.map_err(|e| format!("Failed to read input: {}", e))?;
+        lines_read = i + 1;
+        if progress.is_some_and(|every| lines_read % every == 0) {
+            anstream::eprintln!("Read {} lines", lines_read);
+        }
+        if line.trim().is_empty() {
+            continue;
+        }
+        if batch.is_empty() {
+            first_line = lines_read;
+        }
+        batch.push(line);
+        if batch.len() == STDIN_BATCH_LINES {
+            add_batch(&mut batch, first_line)?;
+        }
+    }
+    add_batch(&mut batch, first_line)?;
+    if progress.is_some_and(|every| lines_read % every != 0) {
+        anstream::eprintln!("Read {} lines", lines_read);
+    }
+    Ok(session)
+}
+
+/// `genson-cli lint [OPTIONS] <SCHEMA>`: check a schema file against the lint rules.
+///
+/// Findings are written as JSON (or one line each with `--format text`), and the
+/// process exits with status 1 if there were any.
+fn run_lint(args: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+    let mut cfg = LintConfig::default();
+    let mut schema_file = None;
+    let mut output_path: Option<String> = None;
+    let mut text_format = false;
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--help" | "-h" => {
+                print_lint_help();
+                return Ok(());
+            }
+            "--max-union-depth" => {
+                if i + 1 < args.len() {
+                    cfg.max_union_depth = Some(args[i + 1].parse::<usize>().map_err(|_| {
+                        format!("Invalid value for --max-union-depth: {}", args[i + 1])
+                    })?);
+                    i += 1;
+                } else {
+                    return Err("Missing value for --max-union-depth".into());
+                }
+            }
+            "--disable" => {
+                if i + 1 < args.len() {
+                    for rule in args[i + 1].split(',') {
+                        cfg.disable(rule.parse::<LintRule>()?);
+                    }
+                    i += 1;
+                } else {
+                    return Err("Missing value for --disable".into());
+                }
+            }
+            "--format" => {
+                if i + 1 < args.len() {
+                    text_format = match args[i + 1].as_str() {
+                        "json" => false,
+                        "text" => true,
+                        other => {
+                            return Err(format!(
+                                "Invalid value for --format: {} (expected json|text)",
+                                other
+                            )
+                            .into())
+                        }
+                    };
+                    i += 1;
+                } else {
+                    return Err("Missing value for --format".into());
+                }
+            }
+            "--output" | "-o" => {
+                if i + 1 < args.len() {
+                    output_path = Some(args[i + 1].clone());
+                    i += 1;
+                } else {
+                    return Err("Missing value for --output".into());
+                }
+            }
+            _ => {
+                if !args[i].starts_with('-') && schema_file.is_none() {
+                    schema_file = Some(args[i].clone());
+                }
+            }
+        }
+        i += 1;
+    }
+
+    let input = if let Some(path) = schema_file {
+        fs::read_to_string(path)?
+    } else {
+        let mut buffer = String::new();
+        io::stdin().read_to_string(&mut buffer)?;
+        buffer
+    };
+    let schema: Value = serde_json::from_str(&input)
+        .map_err(|e| ExitError::parse(format!("Invalid schema JSON: {}", e)))?;
+
+    let findings = lint_schema(&schema, &cfg);
+
+    let mut out = open_output(output_path.as_deref())?;
+    if text_format {
+        for finding in &findings {
+            writeln!(
+                out,
+                "{}: {}: {}",
+                finding.rule.name(),
+                finding.path,
+                finding.message
+            )?;
+        }
+    } else {
+        serde_json::to_writer_pretty(&mut out, &findings)?;
+        out.write_all(b"\n")?;
+    }
+    out.finish()?;
+
+    anstream::eprintln!("Found {} lint finding(s)", findings.len());
+    if !findings.is_empty() {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// Read a JSON object of field names or paths to descriptions, for Avro `doc`s.
+fn read_doc_file(path: &str) -> Result<HashMap<String, String>, String> {
+    let text = fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    serde_json::from_str(&text).map_err(|e| {
+        format!(
+            "Invalid doc file {}: {} (expected a JSON object of field paths to descriptions)",
+            path, e
+        )
+    })
+}
+
+/// Read field names or paths, one per line, skipping blank lines and `#` comments.
+fn read_paths_file(path: &str) -> Result<Vec<String>, String> {
+    let text = fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    let paths: Vec<String> = text
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_string)
+        .collect();
+    if paths.is_empty() {
+        return Err(format!("No field paths in {}", path));
+    }
+    Ok(paths)
+}
+
+/// `genson-cli convert [OPTIONS] [SCHEMA]`: convert a saved JSON Schema to Avro,
+/// without inferring it again, or (`--from-avro`) an Avro schema to JSON Schema.
+fn run_convert(args: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+    let mut opts = AvroOptions::default();
+    let mut schema_file = None;
+    let mut output_path: Option<String> = None;
+    let mut from_avro = false;
+    let mut to_json_schema = None;
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--help" | "-h" => {
+                print_convert_help();
+                return Ok(());
+            }
+            "--to" => {
+                if i + 1 < args.len() {
+                    to_json_schema = match args[i + 1].as_str() {
+                        "avro" => Some(false),
+                        "jsonschema" | "json-schema" => Some(true),
+                        other => {
+                            return Err(format!(
+                                "Invalid value for --to: {} (expected avro|jsonschema)",
+                                other
+                            )
+                            .into())
+                        }
+                    };
+                    i += 1;
+                } else {
+                    return Err("Missing value for --to".into());
+                }
+            }
+            "--to-jsonschema" => {
+                to_json_schema = Some(true);
+            }
+            "--from-avro" => {
+                from_avro = true;
+            }
+            "--namespace" => {
+                if i + 1 < args.len() {
+                    opts.namespace = args[i + 1].clone();
+                    i += 1;
+                } else {
+                    return Err("Missing value for --namespace".into());
+                }
+            }
+            "--name" => {
+                if i + 1 < args.len() {
+                    opts.name = args[i + 1].clone();
+                    i += 1;
+                } else {
+                    return Err("Missing value for --name".into());
+                }
+            }
+            "--doc-file" => {
+                if i + 1 < args.len() {
+                    opts.docs = read_doc_file(&args[i + 1])?;
+                    i += 1;
+                } else {
+                    return Err("Missing value for --doc-file".into());
+                }
+            }
+            "--output" | "-o" => {
+                if i + 1 < args.len() {
+                    output_path = Some(args[i + 1].clone());
+                    i += 1;
+                } else {
+                    return Err("Missing value for --output".into());
+                }
+            }
+            _ => {
+                if !args[i].starts_with('-') && schema_file.is_none() {
+                    schema_file = Some(args[i].clone());
+                }
+            }
+        }
+        i += 1;
+    }
+
+    let input = if let Some(path) = schema_file {
+        fs::read_to_string(path)?
+    } else {
+        let mut buffer = String::new();
+        io::stdin().read_to_string(&mut buffer)?;
+        buffer
+    };
+    let converted = match (from_avro, to_json_schema.unwrap_or(from_avro)) {
+        (true, true) => {
+            let avro: Value = serde_json::from_str(&input)
+                .map_err(|e| ExitError::parse(format!("Invalid Avro schema JSON: {}", e)))?;
+            avro_to_json_schema(&avro)?
+        }
+        (false, false) => {
+            let schema: Value = serde_json::from_str(&input)
+                .map_err(|e| ExitError::parse(format!("Invalid schema JSON: {}", e)))?;
+            json_schema_to_avro(&schema, &opts)?
+        }
+        (true, false) => return Err("An Avro schema (--from-avro) converts to jsonschema".into()),
+        (false, true) => return Err("Converting to jsonschema needs --from-avro".into()),
+    };
+
+    let mut out = open_output(output_path.as_deref())?;
+    serde_json::to_writer_pretty(&mut out, &converted)?;
+    out.write_all(b"\n")?;
+    out.finish()?;
+    Ok(())
+}
+
+/// `genson-cli generate --schema <SCHEMA> [OPTIONS]`: write synthetic NDJSON rows
+/// that conform to a JSON Schema or Avro schema.
+fn run_generate(args: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+    let mut cfg = GenerateConfig::default();
+    let mut schema_file: Option<String> = None;
+    let mut output_path: Option<String> = None;
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--help" | "-h" => {
+                print_generate_help();
+                return Ok(());
+            }
+            "--schema" => {
+                if i + 1 < args.len() {
+                    schema_file = Some(args[i + 1].clone());
                     i += 1;
                 } else {
-                    return Err("Missing value for --map-encoding".into());
+                    return Err("Missing value for --schema".into());
                 }
             }
-            "--no-wrap-scalars" => {
-                config.wrap_scalars = false;
+            "--rows" => {
+                if i + 1 < args.len() {
+                    cfg.rows = args[i + 1]
+                        .parse::<usize>()
+                        .map_err(|_| format!("Invalid value for --rows: {}", args[i + 1]))?;
+                    i += 1;
+                } else {
+                    return Err("Missing value for --rows".into());
+                }
             }
-            "--wrap-root" => {
+            "--seed" => {
                 if i + 1 < args.len() {
-                    config.wrap_root = Some(args[i + 1].clone());
+                    cfg.seed = args[i + 1]
+                        .parse::<u64>()
+                        .map_err(|_| format!("Invalid value for --seed: {}", args[i + 1]))?;
                     i += 1;
                 } else {
-                    return Err("Missing value for --wrap-root".into());
+                    return Err("Missing value for --seed".into());
                 }
             }
-            "--root-map" => {
-                config.no_root_map = false;
+            "--null-probability" => {
+                if i + 1 < args.len() {
+                    cfg.null_probability = args[i + 1].parse::<f64>().map_err(|_| {
+                        format!("Invalid value for --null-probability: {}", args[i + 1])
+                    })?;
+                    i += 1;
+                } else {
+                    return Err("Missing value for --null-probability".into());
+                }
             }
-            "--max-builders" => {
+            "--map-keys" => {
                 if i + 1 < args.len() {
-                    config.max_builders = Some(args[i + 1].parse::<usize>().map_err(|_| {
-                        format!("Invalid value for --max-builders: {}", args[i + 1])
-                    })?);
+                    cfg.map_key_cardinality = args[i + 1]
+                        .parse::<usize>()
+                        .map_err(|_| format!("Invalid value for --map-keys: {}", args[i + 1]))?;
                     i += 1;
                 } else {
-                    return Err("Missing value for --max-builders".into());
+                    return Err("Missing value for --map-keys".into());
                 }
             }
-            "--debug" => {
-                config.debug = true;
+            "--max-items" => {
+                if i + 1 < args.len() {
+                    cfg.max_items = args[i + 1]
+                        .parse::<usize>()
+                        .map_err(|_| format!("Invalid value for --max-items: {}", args[i + 1]))?;
+                    i += 1;
+                } else {
+                    return Err("Missing value for --max-items".into());
+                }
             }
-            "--profile" => {
-                config.profile = true;
+            "--output" | "-o" => {
+                if i + 1 < args.len() {
+                    output_path = Some(args[i + 1].clone());
+                    i += 1;
+                } else {
+                    return Err("Missing value for --output".into());
+                }
             }
-            "--verbose" => {
-                config.verbosity = DebugVerbosity::Verbose;
+            other => return Err(format!("Unknown option for generate: {}", other).into()),
+        }
+        i += 1;
+    }
+
+    let schema_file = schema_file.ok_or("generate requires --schema <SCHEMA>")?;
+    let schema: Value = serde_json::from_str(&fs::read_to_string(&schema_file)?)
+        .map_err(|e| ExitError::parse(format!("Invalid schema JSON: {}", e)))?;
+    let rows = generate_rows(&schema, &cfg)?;
+
+    let mut out = open_output(output_path.as_deref())?;
+    for row in &rows {
+        serde_json::to_writer(&mut out, row)?;
+        out.write_all(b"\n")?;
+    }
+    out.finish()?;
+    Ok(())
+}
+
+/// `genson-cli uncovered --schema <SCHEMA> [OPTIONS] [FILE]`: report fields in the
+/// data that the reference schema doesn't declare.
+///
+/// Exits with status 1 when any uncovered field is found, so it can gate a
+/// data-contract check.
+fn run_uncovered(args: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+    let mut schema_file: Option<String> = None;
+    let mut input_file = None;
+    let mut compression: Option<Compression> = None;
+    let mut output_path: Option<String> = None;
+    let mut max_examples = DEFAULT_MAX_EXAMPLES;
+    let mut ignore_outer_array = true;
+    let mut text_format = false;
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--help" | "-h" => {
+                print_uncovered_help();
+                return Ok(());
+            }
+            "--schema" => {
+                if i + 1 < args.len() {
+                    schema_file = Some(args[i + 1].clone());
+                    i += 1;
+                } else {
+                    return Err("Missing value for --schema".into());
+                }
+            }
+            "--max-examples" => {
+                if i + 1 < args.len() {
+                    max_examples = args[i + 1].parse::<usize>().map_err(|_| {
+                        format!("Invalid value for --max-examples: {}", args[i + 1])
+                    })?;
+                    i += 1;
+                } else {
+                    return Err("Missing value for --max-examples".into());
+                }
+            }
+            "--no-ignore-array" => {
+                ignore_outer_array = false;
+            }
+            "--compression" => {
+                if i + 1 < args.len() {
+                    compression = match args[i + 1].as_str() {
+                        "auto" => None,
+                        codec => Some(
+                            codec
+                                .parse::<Compression>()
+                                .map_err(|e| format!("Invalid value for --compression: {}", e))?,
+                        ),
+                    };
+                    i += 1;
+                } else {
+                    return Err("Missing value for --compression".into());
+                }
+            }
+            "--format" => {
+                if i + 1 < args.len() {
+                    text_format = match args[i + 1].as_str() {
+                        "json" => false,
+                        "text" => true,
+                        other => {
+                            return Err(format!(
+                                "Invalid value for --format: {} (expected json|text)",
+                                other
+                            )
+                            .into())
+                        }
+                    };
+                    i += 1;
+                } else {
+                    return Err("Missing value for --format".into());
+                }
+            }
+            "--output" | "-o" => {
+                if i + 1 < args.len() {
+                    output_path = Some(args[i + 1].clone());
+                    i += 1;
+                } else {
+                    return Err("Missing value for --output".into());
+                }
             }
             _ => {
                 if !args[i].starts_with('-') && input_file.is_none() {
@@ -195,101 +2440,563 @@ fn run_cli() -> Result<(), Box<dyn std::error::Error>> {
         i += 1;
     }
 
-    // For CLI, we treat the entire input as one JSON string
-    let json_strings = if let Some(ref col_name) = pq_column {
-        // Parquet mode
-        let path = input_file.ok_or("--pq-column requires an input file path")?;
+    let schema_file = schema_file.ok_or("uncovered requires --schema <SCHEMA>")?;
+    let schema: Value = serde_json::from_str(&fs::read_to_string(&schema_file)?)
+        .map_err(|e| format!("Invalid schema JSON: {}", e))?;
+    // Avro references are compared in their JSON Schema form
+    let schema = if is_avro(&schema) {
+        avro_to_json_schema(&schema)?
+    } else {
+        schema
+    };
 
-        let strings = genson_core::parquet::read_string_column(&path, col_name)?;
+    let input = if let Some(path) = input_file {
+        read_to_string(&path, compression)?
+    } else {
+        let mut buffer = String::new();
+        decompress_reader(io::stdin(), compression.unwrap_or_default())?
+            .read_to_string(&mut buffer)?;
+        buffer
+    };
 
-        // If --ndjson, split each string by newlines
-        if config.delimiter == Some(b'\n') {
-            strings
-                .into_iter()
-                .flat_map(|s| s.lines().map(|l| l.to_string()).collect::<Vec<_>>())
-                .collect()
-        } else {
-            strings
+    // Accepts NDJSON as well as a single document or concatenated documents
+    let mut tracker = CoverageTracker::new(&schema, max_examples);
+    for value in serde_json::Deserializer::from_str(&input).into_iter::<Value>() {
+        match value.map_err(|e| ExitError::parse(format!("Invalid JSON input: {}", e)))? {
+            Value::Array(items) if ignore_outer_array => {
+                items.iter().for_each(|item| tracker.observe(item))
+            }
+            value => tracker.observe(&value),
+        }
+    }
+    let rows = tracker.rows();
+    let uncovered = tracker.finish();
+
+    let mut out = open_output(output_path.as_deref())?;
+    if text_format {
+        for field in &uncovered {
+            let examples: Vec<String> = field.examples.iter().map(Value::to_string).collect();
+            writeln!(
+                out,
+                "{}: seen {} time(s), e.g. {}",
+                field.path,
+                field.count,
+                examples.join(", ")
+            )?;
         }
     } else {
-        // Original JSON/JSONL mode - pass as single string, let core handle delimiter
-        let input = if let Some(path) = input_file {
-            fs::read_to_string(path)?
-        } else {
-            let mut buffer = String::new();
-            io::stdin().read_to_string(&mut buffer)?;
-            buffer
-        };
-        vec![input] // Don't clone, just move
+        serde_json::to_writer_pretty(&mut out, &uncovered)?;
+        out.write_all(b"\n")?;
+    }
+    out.finish()?;
+
+    anstream::eprintln!(
+        "Found {} uncovered field(s) in {} JSON object(s)",
+        uncovered.len(),
+        rows
+    );
+    if !uncovered.is_empty() {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+fn run_variants(args: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+    let mut input_file = None;
+    let mut compression: Option<Compression> = None;
+    let mut output_path: Option<String> = None;
+    let mut top = DEFAULT_TOP_VARIANTS;
+    let mut max_examples = DEFAULT_VARIANT_EXAMPLES;
+    let mut ignore_outer_array = true;
+    let mut text_format = false;
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--help" | "-h" => {
+                print_variants_help();
+                return Ok(());
+            }
+            "--top" => {
+                if i + 1 < args.len() {
+                    top = args[i + 1]
+                        .parse::<usize>()
+                        .map_err(|_| format!("Invalid value for --top: {}", args[i + 1]))?;
+                    i += 1;
+                } else {
+                    return Err("Missing value for --top".into());
+                }
+            }
+            "--max-examples" => {
+                if i + 1 < args.len() {
+                    max_examples = args[i + 1].parse::<usize>().map_err(|_| {
+                        format!("Invalid value for --max-examples: {}", args[i + 1])
+                    })?;
+                    i += 1;
+                } else {
+                    return Err("Missing value for --max-examples".into());
+                }
+            }
+            "--no-ignore-array" => {
+                ignore_outer_array = false;
+            }
+            "--compression" => {
+                if i + 1 < args.len() {
+                    compression = match args[i + 1].as_str() {
+                        "auto" => None,
+                        codec => Some(
+                            codec
+                                .parse::<Compression>()
+                                .map_err(|e| format!("Invalid value for --compression: {}", e))?,
+                        ),
+                    };
+                    i += 1;
+                } else {
+                    return Err("Missing value for --compression".into());
+                }
+            }
+            "--format" => {
+                if i + 1 < args.len() {
+                    text_format = match args[i + 1].as_str() {
+                        "json" => false,
+                        "text" => true,
+                        other => {
+                            return Err(format!(
+                                "Invalid value for --format: {} (expected json|text)",
+                                other
+                            )
+                            .into())
+                        }
+                    };
+                    i += 1;
+                } else {
+                    return Err("Missing value for --format".into());
+                }
+            }
+            "--output" | "-o" => {
+                if i + 1 < args.len() {
+                    output_path = Some(args[i + 1].clone());
+                    i += 1;
+                } else {
+                    return Err("Missing value for --output".into());
+                }
+            }
+            _ => {
+                if !args[i].starts_with('-') && input_file.is_none() {
+                    input_file = Some(args[i].clone());
+                }
+            }
+        }
+        i += 1;
+    }
+
+    let input = if let Some(path) = input_file {
+        read_to_string(&path, compression)?
+    } else {
+        let mut buffer = String::new();
+        decompress_reader(io::stdin(), compression.unwrap_or_default())?
+            .read_to_string(&mut buffer)?;
+        buffer
     };
 
-    // Infer schema - genson-core should handle any panics and return proper errors
-    let result = infer_json_schema(&json_strings, Some(config.clone()))
-        .map_err(|e| format!("Schema inference failed: {}", e))?;
+    // Accepts NDJSON as well as a single document or concatenated documents
+    let mut tracker = VariantTracker::new(max_examples);
+    for value in serde_json::Deserializer::from_str(&input).into_iter::<Value>() {
+        match value.map_err(|e| ExitError::parse(format!("Invalid JSON input: {}", e)))? {
+            Value::Array(items) if ignore_outer_array => {
+                items.iter().for_each(|item| tracker.observe(item))
+            }
+            value => tracker.observe(&value),
+        }
+    }
+    let rows = tracker.rows();
+    let total = tracker.len();
+    let variants = tracker.finish(Some(top));
 
-    if do_normalise {
-        let schema = &result.schema;
+    let mut out = open_output(output_path.as_deref())?;
+    if text_format {
+        for (n, variant) in variants.iter().enumerate() {
+            writeln!(
+                out,
+                "#{}: {} row(s) ({:.1}%)",
+                n + 1,
+                variant.count,
+                100.0 * variant.count as f64 / rows as f64
+            )?;
+            for (path, types) in &variant.paths {
+                writeln!(out, "    {}: {}", path, types)?;
+            }
+            for example in &variant.examples {
+                writeln!(out, "    e.g. {}", example)?;
+            }
+        }
+    } else {
+        serde_json::to_writer_pretty(&mut out, &variants)?;
+        out.write_all(b"\n")?;
+    }
+    out.finish()?;
 
-        let values: Vec<Value> = if pq_column.is_some() {
-            // Parquet mode: json_strings is already split correctly
-            json_strings
-                .iter()
-                .map(|s| serde_json::from_str::<Value>(s).unwrap_or(Value::Null))
-                .collect()
-        } else if config.delimiter == Some(b'\n') {
-            // NDJSON mode: split the single string by lines
-            json_strings[0]
-                .lines()
+    anstream::eprintln!(
+        "Found {} variant(s) in {} JSON object(s), showing {}",
+        total,
+        rows,
+        variants.len()
+    );
+    Ok(())
+}
+
+/// Output destination: stdout, or a file written atomically.
+///
+/// A file is written to a temporary sibling and only renamed into place by
+/// [`Output::finish`], so readers never see partial output and a failed run leaves
+/// any existing file untouched.
+struct Output {
+    writer: BufWriter<Box<dyn Write>>,
+    /// Temporary and final paths when writing to a file
+    rename: Option<(PathBuf, PathBuf)>,
+}
+
+impl Output {
+    /// Flush the output and move a file into place.
+    fn finish(mut self) -> io::Result<()> {
+        self.writer.flush()?;
+        // Close the temporary file before renaming it
+        drop(std::mem::replace(
+            &mut self.writer,
+            BufWriter::new(Box::new(io::sink())),
+        ));
+        match self.rename.take() {
+            Some((tmp, path)) => fs::rename(tmp, path),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Write for Output {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.writer.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+impl Drop for Output {
+    fn drop(&mut self) {
+        // Never finished: discard the partial file
+        if let Some((tmp, _)) = self.rename.take() {
+            let _ = fs::remove_file(tmp);
+        }
+    }
+}
+
+/// The first `n` documents of the input strings: their NDJSON lines, or the items
+/// of an outer array that inference reads as documents.
+fn leading_documents(
+    json_strings: &[String],
+    ndjson: bool,
+    config: &SchemaInferenceConfig,
+    n: usize,
+) -> Result<Vec<Value>, String> {
+    let mut documents = Vec::new();
+    for s in json_strings {
+        let s = scrub(s, config.repair, config.nonfinite_policy);
+        let values: Vec<Value> = if ndjson {
+            s.lines()
                 .filter(|l| !l.trim().is_empty())
-                .map(|l| serde_json::from_str::<Value>(l).unwrap_or(Value::Null))
-                .collect()
+                .map(serde_json::from_str)
+                .collect::<Result<_, _>>()
         } else {
-            // Regular JSON: parse the single string
-            vec![serde_json::from_str::<Value>(&json_strings[0]).unwrap_or(Value::Null)]
-        };
+            serde_json::from_str(&s).map(|v| match v {
+                Value::Array(items) if is_outer_array(&items, config) => items,
+                other => vec![other],
+            })
+        }
+        .map_err(|e| format!("Invalid JSON input: {}", e))?;
+        documents.extend(values.into_iter().take(n - documents.len()));
+        if documents.len() == n {
+            break;
+        }
+    }
+    Ok(documents)
+}
 
-        let cfg = NormaliseConfig {
-            empty_as_null,
-            coerce_string,
-            map_encoding,
-            wrap_root: config.wrap_root,
-        };
-        let normalised = normalise_values(values, schema, &cfg);
+/// `text` with its syntax errors repaired (with `repair`), and its non-finite
+/// literals written as `policy` says, when there is one.
+fn scrub(text: &str, repair: bool, policy: Option<NonFinitePolicy>) -> Cow<'_, str> {
+    let text = if repair {
+        repair_json(text).0
+    } else {
+        Cow::Borrowed(text)
+    };
+    match policy {
+        Some(policy) => match scrub_nonfinite(&text, policy) {
+            Cow::Borrowed(_) => text,
+            Cow::Owned(scrubbed) => Cow::Owned(scrubbed),
+        },
+        None => text,
+    }
+}
 
-        if config.delimiter == Some(b'\n') {
-            // print one line per row
-            for v in normalised {
-                anstream::println!("{}", serde_json::to_string(&v)?);
-            }
+/// Write the repairs made to each row of the input strings to `path` as JSON. Rows
+/// are NDJSON lines, or else the strings themselves.
+fn write_repair_report(path: &str, json_strings: &[String], ndjson: bool) -> Result<(), String> {
+    let mut report: Vec<RowRepairs> = Vec::new();
+    let mut offset = 0;
+    for s in json_strings {
+        if ndjson {
+            let (_, rows) = repair_lines(s);
+            report.extend(rows.into_iter().map(|r| RowRepairs {
+                row: r.row + offset,
+                ..r
+            }));
+            offset += s.lines().filter(|l| !l.trim().is_empty()).count();
         } else {
-            anstream::println!("{}", serde_json::to_string_pretty(&normalised)?);
+            let (_, repairs) = repair_json(s);
+            if !repairs.is_empty() {
+                report.push(RowRepairs {
+                    row: offset,
+                    repairs,
+                });
+            }
+            offset += 1;
         }
+    }
+    let file = fs::File::create(path)
+        .map_err(|e| format!("Failed to create repair report {}: {}", path, e))?;
+    let mut writer = BufWriter::new(file);
+    serde_json::to_writer_pretty(&mut writer, &report).map_err(|e| e.to_string())?;
+    writer
+        .write_all(b"\n")
+        .and_then(|_| writer.flush())
+        .map_err(|e| format!("Failed to write repair report {}: {}", path, e))
+}
+
+/// Whether the items of a top-level array are documents in their own right, as
+/// inference reads them, rather than the array being one document.
+fn is_outer_array(items: &[Value], config: &SchemaInferenceConfig) -> bool {
+    config.ignore_outer_array
+        && (config.outer_scalar_policy == OuterScalarPolicy::Rows
+            || items.iter().all(Value::is_object))
+}
+
+/// Strings read from a Parquet or CSV column, split into lines with `--ndjson`.
+fn split_column_strings(strings: Vec<String>, ndjson: bool) -> Vec<String> {
+    if ndjson {
+        strings
+            .into_iter()
+            .flat_map(|s| s.lines().map(|l| l.to_string()).collect::<Vec<_>>())
+            .collect()
     } else {
-        // Pretty-print the schema
-        anstream::println!("{}", serde_json::to_string_pretty(&result.schema)?);
+        strings
     }
+}
 
-    anstream::eprintln!("Processed {} JSON object(s)", result.processed_count);
-    Ok(())
+/// Stream a Parquet string column in batches of `batch_size` rows, split as by
+/// [`split_column_strings`].
+fn read_pq_batches(
+    path: &str,
+    column: &str,
+    batch_size: usize,
+    ndjson: bool,
+) -> Result<impl Iterator<Item = Result<Vec<String>, String>>, String> {
+    let batches = genson_core::parquet::read_string_column_batches(path, column, batch_size)?;
+    Ok(batches.map(move |batch| batch.map(|strings| split_column_strings(strings, ndjson))))
+}
+
+/// Open the output destination: a file path, or stdout when unset or `-`.
+fn open_output(path: Option<&str>) -> io::Result<Output> {
+    match path {
+        None | Some("-") => Ok(Output {
+            writer: BufWriter::new(Box::new(io::stdout().lock())),
+            rename: None,
+        }),
+        Some(path) => {
+            let path = PathBuf::from(path);
+            let file_name = path
+                .file_name()
+                .ok_or_else(|| io::Error::other(format!("Not a file path: {}", path.display())))?;
+            let tmp = path.with_file_name(format!(
+                ".{}.{}.tmp",
+                file_name.to_string_lossy(),
+                std::process::id()
+            ));
+            let file = fs::File::create(&tmp)?;
+            Ok(Output {
+                writer: BufWriter::new(Box::new(file)),
+                rename: Some((tmp, path)),
+            })
+        }
+    }
+}
+
+/// Whether an error was caused by the reader on the other end of stdout going away.
+fn is_broken_pipe(err: &(dyn std::error::Error + 'static)) -> bool {
+    if let Some(io_err) = err.downcast_ref::<io::Error>() {
+        return io_err.kind() == io::ErrorKind::BrokenPipe;
+    }
+    if let Some(json_err) = err.downcast_ref::<serde_json::Error>() {
+        return json_err.io_error_kind() == Some(io::ErrorKind::BrokenPipe);
+    }
+    false
 }
 
 fn print_help() {
     anstream::println!("genson-cli - JSON schema inference tool");
     anstream::println!();
     anstream::println!("USAGE:");
-    anstream::println!("    genson-cli [OPTIONS] [FILE]");
+    anstream::println!("    genson-cli [OPTIONS] [FILE]...");
+    anstream::println!("    genson-cli lint [OPTIONS] [SCHEMA]");
+    anstream::println!("    genson-cli uncovered --schema <SCHEMA> [OPTIONS] [FILE]");
+    anstream::println!("    genson-cli variants [OPTIONS] [FILE]");
+    anstream::println!("    genson-cli convert [OPTIONS] [SCHEMA]");
+    anstream::println!("    genson-cli generate --schema <SCHEMA> [OPTIONS]");
     anstream::println!();
     anstream::println!("ARGS:");
-    anstream::println!("    <FILE>    Input JSON file (reads from stdin if not provided)");
+    anstream::println!(
+        "    <FILE>... Input JSON file(s) or glob patterns, inferred into one schema (reads from stdin if not provided)"
+    );
     anstream::println!();
     anstream::println!("OPTIONS:");
     anstream::println!("    -h, --help            Print this help message");
     anstream::println!("    --no-ignore-array     Don't treat top-level arrays as object streams");
     anstream::println!("    --ndjson              Treat input as newline-delimited JSON");
+    anstream::println!(
+        "    --stdin-format <fmt>  Format of JSON read from stdin (json|ndjson); ndjson is streamed"
+    );
+    anstream::println!(
+        "    --progress <N>        Print the number of lines read to stderr every N lines of stdin"
+    );
+    anstream::println!(
+        "    -j, --jobs <N>        Infer up to N input files in parallel (default 1)"
+    );
+    anstream::println!(
+        "    --pq-column <name>    Read JSON strings from this column of a Parquet input file"
+    );
+    anstream::println!(
+        "    --pq-batch-size <N>   Stream the Parquet column N rows at a time, in constant memory"
+    );
+    anstream::println!(
+        "    --csv-column <name>   Read JSON strings from this column of CSV input (with a header row)"
+    );
+    anstream::println!(
+        "    --csv-delimiter <c>   CSV field delimiter (default ',', or \\t for .tsv/.tab files)"
+    );
+    anstream::println!("    --csv-quote <c>       CSV quote character (default '\"')");
+    anstream::println!(
+        "    --compression <codec> Input compression (auto|none|gzip|bzip2|zstd, default auto)"
+    );
+    anstream::println!(
+        "                          auto = detect from file extension (.gz, .bz2, .zst)"
+    );
+    anstream::println!(
+        "    -o, --output <path>   Write output to this file instead of stdout ('-' for stdout)"
+    );
+    anstream::println!(
+        "                          The file is replaced atomically, only once output is complete"
+    );
+    anstream::println!(
+        "    -q, --quiet           Don't print the processed object count to stderr"
+    );
     anstream::println!("    --avro                Output Avro schema instead of JSON Schema");
+    anstream::println!(
+        "    --avro-namespace <ns> Namespace of the Avro root record (default genson)"
+    );
+    anstream::println!("    --avro-name <name>    Name of the Avro root record (default document)");
+    anstream::println!("    --avro-doc-file <FILE> JSON object of field paths to Avro field docs");
+    anstream::println!(
+        "    --annotate <FILE>     YAML/JSON of field paths to titles, descriptions and examples"
+    );
+    anstream::println!(
+        "    --avro-field-names <s>  Invalid Avro field names: keep (default), replace or escape"
+    );
+    anstream::println!(
+        "    --avro-renames <FILE> Write the fields renamed to valid Avro names to FILE as JSON"
+    );
+    anstream::println!(
+        "    --no-avro-defaults    Don't give nullable Avro fields \"default\": null"
+    );
+    anstream::println!(
+        "    --arrow               Output Arrow schema (JSON) instead of JSON Schema"
+    );
+    anstream::println!(
+        "    --proto               Output a protobuf (.proto) message instead of JSON Schema"
+    );
+    anstream::println!(
+        "    --proto-message <name> Name of the top-level protobuf message (default: Root)"
+    );
+    anstream::println!("    --proto-package <pkg> Package declared in the .proto output");
+    anstream::println!(
+        "    --format <fmt>        Schema output format (json-schema|avro|delta|iceberg|arrow|arrow-ipc|proto)"
+    );
+    anstream::println!(
+        "                          delta/iceberg = lakehouse table schema JSON (default json-schema)"
+    );
+    anstream::println!(
+        "                          arrow-ipc = Arrow IPC stream bytes holding only the schema"
+    );
+    anstream::println!(
+        "    --output-format <fmt> Text format to write the schema in (json|yaml|toml, default json)"
+    );
+    anstream::println!(
+        "    --draft <draft>       JSON Schema draft to write (draft-07|2019-09|2020-12)"
+    );
+    anstream::println!("    --no-schema-uri       Leave out the $schema keyword");
+    anstream::println!(
+        "    --compact             Strip redundant keywords (nested $schema, empty required, ...)"
+    );
+    anstream::println!(
+        "    --sort-fields <order> Order of properties and Avro fields (preserve|alphabetical|frequency)"
+    );
+    anstream::println!(
+        "                          preserve = order first seen in the input (default)"
+    );
+    anstream::println!("                          frequency = most often seen first, ties by name");
+    anstream::println!(
+        "    --union-order <order> Order of union branches (canonical|null-first|frequency|default-first)"
+    );
+    anstream::println!(
+        "                          default-first = branch matching the Avro default first"
+    );
+    anstream::println!(
+        "    --repair              Repair trailing commas, single quotes, unquoted keys, a BOM"
+    );
+    anstream::println!("                          and raw control characters before parsing");
+    anstream::println!(
+        "    --repair-report <FILE> Repair (as --repair) and write the repairs made to each row"
+    );
+    anstream::println!("                          to FILE as JSON");
+    anstream::println!(
+        "    --nonfinite <policy>  Accept NaN/Infinity literals, typed as number (null|string)"
+    );
+    anstream::println!("                          and normalised to null or to strings");
+    anstream::println!(
+        "    --null-fallback <type> Type for fields that are null in every row (string|integer|number|boolean)"
+    );
+    anstream::println!(
+        "                          They become nullable fields of that type instead of null"
+    );
+    anstream::println!(
+        "    --required-threshold <ratio>  Keep fields present in at least this fraction of rows required (0-1)"
+    );
+    anstream::println!(
+        "    --presence-report <FILE>  Write the presence ratio of every field to FILE as JSON"
+    );
+    anstream::println!(
+        "    --seed-schema <file>  Start from this JSON Schema and widen it with the input"
+    );
     anstream::println!(
         "    --normalise           Normalise the input data against the inferred schema"
     );
+    anstream::println!(
+        "    --avro-schema <file>  Normalise against this Avro schema (.avsc) instead of inferring one"
+    );
+    anstream::println!("                          Implies --normalise");
+    anstream::println!(
+        "    --output-avro <file>  Write the normalised rows to an Avro container file (.avro)"
+    );
+    anstream::println!("                          Implies --normalise");
     anstream::println!("    --coerce-strings      Coerce numeric/boolean strings to schema type during normalisation");
     anstream::println!(
         "    --keep-empty          Keep empty arrays/maps instead of turning them into nulls"
@@ -297,6 +3004,12 @@ fn print_help() {
     anstream::println!(
         "    --map-threshold <N>   Treat objects with >N keys as map candidates (default 20)"
     );
+    anstream::println!(
+        "    --map-detection <mode>  Count keys merged across documents (merged, default) or"
+    );
+    anstream::println!(
+        "                          also require them to vary between documents (variance)"
+    );
     anstream::println!(
         "    --map-max-rk <N>      Maximum required keys for Map inference (default: no limit)"
     );
@@ -305,12 +3018,27 @@ fn print_help() {
         "    --unify-maps          Enable unification of compatible record schemas into maps"
     );
     anstream::println!("                          Same as --map-max-rk");
+    anstream::println!(
+        "    --label-maps          Make {{lang: {{language, value}}}} label objects maps regardless of thresholds"
+    );
+    anstream::println!(
+        "    --simplify-labels     Like --label-maps, with map values reduced to the label text"
+    );
+    anstream::println!(
+        "    --detect-tuples       Keep a schema per position for fixed-length arrays (prefixItems)"
+    );
+    anstream::println!(
+        "    --coerce-map-array    Merge a field seen as {{k: [v]}} and as [{{k: v}}] into {{k: [v]}}"
+    );
     anstream::println!(
         "    --no-unify <fields>   Exclude fields from record unification (comma-separated)"
     );
     anstream::println!("                          Example: --no-unify qualifiers,references");
-    anstream::println!("    --force-type k:v,...  Force field(s) to 'map' or 'record'");
-    anstream::println!("                          Example: --force-type labels:map,claims:record");
+    anstream::println!(
+        "    --force-type k:v,...  Force field(s) to 'map', 'record' or a scalar type"
+    );
+    anstream::println!("                          (string|integer|number|boolean|date|date-time)");
+    anstream::println!("                          Example: --force-type labels:map,price:number");
     anstream::println!("    --force-parent-type k:v,...  Force parent objects containing field(s) to 'map' or 'record'");
     anstream::println!(
         "                                 Example: --force-parent-type mainsnak:record"
@@ -320,35 +3048,332 @@ fn print_help() {
     anstream::println!(
         "                          Example: --force-scalar-promotion precision,datavalue"
     );
+    anstream::println!("    --ignore-paths <fields>");
+    anstream::println!(
+        "                          Treat these subtrees as opaque strings during inference (comma-separated)"
+    );
+    anstream::println!("                          Example: --ignore-paths metadata,/items/extra");
+    anstream::println!("    --select-paths <FILE> Keep only these fields (one name or path per line) in the schema");
+    anstream::println!(
+        "                          and normalised output, with the fields leading to them"
+    );
+    anstream::println!("    --discriminator path:field,...");
+    anstream::println!(
+        "                          Infer the objects at path as a oneOf, one branch per value of their"
+    );
+    anstream::println!("                          tag field (/ for the document root)");
+    anstream::println!("                          Example: --discriminator mainsnak:snaktype");
+    anstream::println!(
+        "                          Fields for --no-unify, --force-type, --force-scalar-promotion,"
+    );
+    anstream::println!(
+        "                          --ignore-paths and --select-paths may be names (any depth) or paths: /claims/*/mainsnak, claims.*.mainsnak"
+    );
+    anstream::println!(
+        "    --max-depth <N>       Treat objects/arrays nested deeper than N levels as opaque strings"
+    );
+    anstream::println!(
+        "    --max-object-keys <N> Treat objects with more than N keys (below the root) as opaque strings"
+    );
+    anstream::println!(
+        "    --empty-containers <policy> Treatment of empty objects and arrays (ignore|map-of-string|nullable)"
+    );
+    anstream::println!(
+        "                          ignore = left out, as if missing; nullable = read as null"
+    );
+    anstream::println!(
+        "                          map-of-string = typed as a map/array of strings if only ever empty"
+    );
     anstream::println!("    --map-encoding <mode> Choose map encoding (mapping|entries|kv)");
     anstream::println!("                          mapping = Avro/JSON object (shared dict)");
     anstream::println!(
         "                          entries = list of single-entry objects (individual dicts)"
     );
     anstream::println!("                          kv      = list of {{key,value}} objects");
+    anstream::println!(
+        "    --encode-map-schema   Describe maps in the output schema as --map-encoding writes them"
+    );
+    anstream::println!(
+        "    --canonical-numeric-keys  Rewrite integer map keys in canonical form (007 -> 7)"
+    );
+    anstream::println!(
+        "    --unknown-fields <policy>  Fields missing from the schema: drop|keep|error (default drop)"
+    );
+    anstream::println!(
+        "    --max-string-len <N>  Cut normalised strings to N characters, ending them with the marker"
+    );
+    anstream::println!("    --max-array-items <N> Keep the first N items of normalised arrays");
+    anstream::println!(
+        "    --max-value-depth <N> Replace normalised values nested deeper than N levels with null"
+    );
+    anstream::println!(
+        "    --truncation-marker <s>  Marker ending cut strings (default ...[truncated])"
+    );
     anstream::println!(
         "    --no-wrap-scalars     Disable scalar promotion (keep raw scalar types)"
     );
-    anstream::println!("    --wrap-root <field>   Wrap top-level schema under this required field");
+    anstream::println!(
+        "    --wrap-root <path>    Wrap top-level schema under this required field (a.b nests)"
+    );
+    anstream::println!(
+        "    --wrap-root-strict    Error if some rows are already wrapped under --wrap-root and others aren't"
+    );
+    anstream::println!(
+        "    --root-value-field <path>  Field to wrap scalar and array documents under (default value)"
+    );
+    anstream::println!(
+        "    --key-fold <case>     Merge keys that differ only in case: lower, upper or preserve"
+    );
+    anstream::println!(
+        "    --detect-formats <list>  Annotate string formats: all, or any of uuid,uri,email,base64,ip"
+    );
+    anstream::println!(
+        "    --format-sample-rate <r>  Fraction of strings to check for formats (default 1)"
+    );
+    anstream::println!(
+        "    --detect-numeric-keys Annotate maps whose keys are all integers (x-key-type)"
+    );
+    anstream::println!(
+        "    --detect-sparse-arrays  Infer objects keyed \"0\" to \"n-1\" as arrays"
+    );
+    anstream::println!(
+        "    --outer-scalars <policy>  Top-level array of scalars: array (one document, default)"
+    );
+    anstream::println!("                          or rows (each item is a document)");
     anstream::println!("    --root-map            Allow document root to become a map");
+    anstream::println!("    --embed-config        Record non-default settings in the schema under \"x-genson-config\"");
+    anstream::println!(
+        "    --strict              Fail (exit status 5) if the schema has non-null unions, fields"
+    );
+    anstream::println!(
+        "                          both map and record, or promoted scalars, listing their paths"
+    );
     anstream::println!(
         "    --max-builders <N>    Maximum schema builders to create in parallel at once"
     );
     anstream::println!(
         "                          Lower values reduce peak memory (default: unlimited)"
     );
+    anstream::println!(
+        "    --sample <N>          Infer from the first N documents (NDJSON lines) only"
+    );
+    anstream::println!("    --sample-random <N:SEED>");
+    anstream::println!(
+        "                          Infer from N documents chosen at random with SEED"
+    );
+    anstream::println!("    --sample-every <K>    Infer from every Kth document");
+    anstream::println!(
+        "    --chunk-size <N>      Infer in batches of N documents (NDJSON lines), merging between"
+    );
+    anstream::println!(
+        "                          batches so memory stays bounded (default: no batching)"
+    );
+    anstream::println!(
+        "    --error-context <N>   Characters of an invalid document to quote in its error, around"
+    );
+    anstream::println!("                          where parsing failed (default: 100)");
+    anstream::println!(
+        "    --error-dump-dir <DIR>  Write invalid documents in full to DIR, named in their errors"
+    );
     anstream::println!("    --debug               Enable debug output during schema inference");
     anstream::println!("    --profile             Enable profiling output during schema inference");
+    anstream::println!(
+        "    --coercion-report <FILE>  Write every value changed by normalisation to FILE as JSON"
+    );
+    anstream::println!("    --profile-json <FILE> Write stage timings (parse, build, rewrite, Avro, per-document p50/p99) to FILE as JSON");
+    anstream::println!(
+        "    --explain <FILE>      Write why each object became a map or a record to FILE as JSON"
+    );
+    anstream::println!(
+        "    --map-key-stats <FILE> Write the key counts and most frequent keys of every map to"
+    );
+    anstream::println!("                          FILE as JSON");
+    anstream::println!(
+        "    --debug-log <FILE>    Write each map/record decision to FILE as NDJSON events"
+    );
+    anstream::println!(
+        "    --parquet-schema <FILE>  Write an empty Parquet file with the schema's Arrow types,"
+    );
+    anstream::println!(
+        "                          embedding the JSON Schema and Avro schema as metadata"
+    );
+    anstream::println!(
+        "    --parquet-rows <N>    Include the first N input documents, normalised, in that file"
+    );
     anstream::println!();
     anstream::println!("EXAMPLES:");
     anstream::println!("    genson-cli data.json");
     anstream::println!("    echo '{{\"name\": \"test\"}}' | genson-cli");
     anstream::println!("    genson-cli --ndjson multi-line.jsonl");
+    anstream::println!("    genson-cli --ndjson dump.jsonl.zst");
+    anstream::println!();
+    anstream::println!("EXIT STATUS:");
+    anstream::println!("    0    Success");
+    anstream::println!(
+        "    1    Invalid arguments or I/O error (lint/uncovered: findings reported)"
+    );
+    anstream::println!("    2    Input is not valid JSON");
+    anstream::println!("    3    Schema inference failed, e.g. a resource limit was exceeded");
+    anstream::println!("    4    The schema registry reported the schema as incompatible");
+    anstream::println!("    5    The schema failed the --strict checks");
+}
+
+fn print_lint_help() {
+    anstream::println!("genson-cli lint - check a generated schema against lint rules");
+    anstream::println!();
+    anstream::println!("USAGE:");
+    anstream::println!("    genson-cli lint [OPTIONS] [SCHEMA]");
+    anstream::println!();
+    anstream::println!("ARGS:");
+    anstream::println!(
+        "    <SCHEMA>    Avro or JSON Schema file (reads from stdin if not provided)"
+    );
+    anstream::println!();
+    anstream::println!("RULES:");
+    anstream::println!("    union_depth        Unions of several non-null types nested too deep");
+    anstream::println!("    field_name         Field names that are not valid Avro names");
+    anstream::println!("    optional_default   Optional fields without a default");
+    anstream::println!("    map_value_record   Maps whose values are not records");
+    anstream::println!();
+    anstream::println!("OPTIONS:");
+    anstream::println!("    -h, --help              Print this help message");
+    anstream::println!(
+        "    --max-union-depth <N>   Deepest level a union may appear at (default 2)"
+    );
+    anstream::println!("    --disable <rules>       Disable rules (comma-separated)");
+    anstream::println!("    --format <fmt>          Findings format (json|text, default json)");
+    anstream::println!("    -o, --output <path>     Write findings to this file instead of stdout");
+    anstream::println!();
+    anstream::println!("Exits with status 1 if any findings are reported.");
+}
+
+fn print_convert_help() {
+    anstream::println!(
+        "genson-cli convert - convert a saved JSON Schema to Avro, or an Avro schema to JSON Schema"
+    );
+    anstream::println!();
+    anstream::println!("USAGE:");
+    anstream::println!("    genson-cli convert [OPTIONS] [SCHEMA]");
+    anstream::println!("    genson-cli convert --from-avro [--to-jsonschema] [SCHEMA]");
+    anstream::println!();
+    anstream::println!("ARGS:");
+    anstream::println!(
+        "    <SCHEMA>    JSON Schema (or Avro schema) file (reads from stdin if not provided)"
+    );
+    anstream::println!();
+    anstream::println!("OPTIONS:");
+    anstream::println!("    -h, --help              Print this help message");
+    anstream::println!("    --to <format>           Schema format to convert to (avro|jsonschema)");
+    anstream::println!(
+        "                            avro for a JSON Schema, jsonschema with --from-avro"
+    );
+    anstream::println!("    --from-avro             The input is an Avro schema (.avsc)");
+    anstream::println!("    --to-jsonschema         Same as --to jsonschema");
+    anstream::println!("    --namespace <ns>        Avro namespace (default genson)");
+    anstream::println!("    --name <name>           Name of the root record (default document)");
+    anstream::println!("    --doc-file <path>       JSON object of field paths to field docs");
+    anstream::println!(
+        "    -o, --output <path>     Write the schema to this file instead of stdout"
+    );
+}
+
+fn print_generate_help() {
+    anstream::println!("genson-cli generate - write synthetic NDJSON rows that fit a schema");
+    anstream::println!();
+    anstream::println!("USAGE:");
+    anstream::println!("    genson-cli generate --schema <SCHEMA> [OPTIONS]");
+    anstream::println!();
+    anstream::println!("OPTIONS:");
+    anstream::println!("    -h, --help                  Print this help message");
+    anstream::println!(
+        "    --schema <SCHEMA>           JSON Schema or Avro schema to generate rows for (required)"
+    );
+    anstream::println!("    --rows <N>                  Number of rows (default 100)");
+    anstream::println!(
+        "    --seed <N>                  Seed; the same seed gives the same rows (default 0)"
+    );
+    anstream::println!(
+        "    --null-probability <P>      Chance a nullable value is null or an optional field absent (default 0.1)"
+    );
+    anstream::println!(
+        "    --map-keys <N>              Distinct keys the maps draw their keys from (default 8)"
+    );
+    anstream::println!(
+        "    --max-items <N>             Most items per array or entries per map (default 4)"
+    );
+    anstream::println!(
+        "    -o, --output <path>         Write the rows to this file instead of stdout"
+    );
+}
+
+fn print_uncovered_help() {
+    anstream::println!("genson-cli uncovered - report data fields missing from a reference schema");
+    anstream::println!();
+    anstream::println!("USAGE:");
+    anstream::println!("    genson-cli uncovered --schema <SCHEMA> [OPTIONS] [FILE]");
+    anstream::println!();
+    anstream::println!("ARGS:");
+    anstream::println!("    <FILE>    JSON or NDJSON data file (reads from stdin if not provided)");
+    anstream::println!();
+    anstream::println!("OPTIONS:");
+    anstream::println!("    -h, --help              Print this help message");
+    anstream::println!(
+        "    --schema <SCHEMA>       Reference JSON Schema or Avro schema (required)"
+    );
+    anstream::println!(
+        "    --max-examples <N>      Distinct example values kept per field (default 3)"
+    );
+    anstream::println!(
+        "    --no-ignore-array       Don't treat top-level arrays as object streams"
+    );
+    anstream::println!(
+        "    --compression <codec>   Input compression (auto|none|gzip|bzip2|zstd, default auto)"
+    );
+    anstream::println!("    --format <fmt>          Report format (json|text, default json)");
+    anstream::println!(
+        "    -o, --output <path>     Write the report to this file instead of stdout"
+    );
+    anstream::println!();
+    anstream::println!("Paths are dotted; [] marks array items and {{}} map values.");
+    anstream::println!("Exits with status 1 if any uncovered fields are found.");
+}
+
+fn print_variants_help() {
+    anstream::println!("genson-cli variants - group documents by their structure");
+    anstream::println!();
+    anstream::println!("USAGE:");
+    anstream::println!("    genson-cli variants [OPTIONS] [FILE]");
+    anstream::println!();
+    anstream::println!("ARGS:");
+    anstream::println!("    <FILE>    JSON or NDJSON data file (reads from stdin if not provided)");
+    anstream::println!();
+    anstream::println!("OPTIONS:");
+    anstream::println!("    -h, --help              Print this help message");
+    anstream::println!(
+        "    --top <N>               Report the N most common variants (default 10)"
+    );
+    anstream::println!(
+        "    --max-examples <N>      Example documents kept per variant (default 1)"
+    );
+    anstream::println!(
+        "    --no-ignore-array       Don't treat top-level arrays as object streams"
+    );
+    anstream::println!(
+        "    --compression <codec>   Input compression (auto|none|gzip|bzip2|zstd, default auto)"
+    );
+    anstream::println!("    --format <fmt>          Report format (json|text, default json)");
+    anstream::println!(
+        "    -o, --output <path>     Write the report to this file instead of stdout"
+    );
+    anstream::println!();
+    anstream::println!("A variant is the set of paths in a document and the type at each.");
+    anstream::println!("Paths are dotted; [] marks array items.");
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use genson_core::normalise::normalise_values;
     use std::io::Write;
     use tempfile::NamedTempFile;
 
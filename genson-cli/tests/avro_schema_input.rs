@@ -0,0 +1,113 @@
+// genson-cli/tests/avro_schema_input.rs
+use predicates::prelude::*;
+use std::io::Write;
+use tempfile::NamedTempFile;
+
+const AVSC: &str = r#"{
+    "type": "record", "name": "Item", "namespace": "org.example",
+    "fields": [
+        {"name": "id", "type": "long"},
+        {"name": "name", "type": ["null", "string"], "default": null},
+        {"name": "tags", "type": {"type": "array", "items": "string"}},
+        {"name": "labels", "type": {"type": "map", "values": "string"}}
+    ]
+}"#;
+
+fn avsc_file(content: &str) -> NamedTempFile {
+    let mut file = NamedTempFile::new().unwrap();
+    file.write_all(content.as_bytes()).unwrap();
+    file
+}
+
+#[test]
+fn test_normalise_against_avro_schema() {
+    let avsc = avsc_file(AVSC);
+
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["--ndjson", "--avro-schema", avsc.path().to_str().unwrap()])
+        .write_stdin("{\"id\": 1, \"tags\": \"x\", \"extra\": true}\n{\"id\": 2, \"name\": \"b\", \"labels\": {\"en\": \"B\"}}\n");
+
+    let output = cmd
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Processed 2 JSON object(s)"))
+        .get_output()
+        .stdout
+        .clone();
+    let rows: Vec<serde_json::Value> = String::from_utf8(output)
+        .unwrap()
+        .lines()
+        .map(|l| serde_json::from_str(l).unwrap())
+        .collect();
+
+    assert_eq!(
+        rows[0],
+        serde_json::json!({"id": 1, "name": null, "tags": ["x"], "labels": null})
+    );
+    assert_eq!(
+        rows[1],
+        serde_json::json!({"id": 2, "name": "b", "tags": null, "labels": {"en": "B"}})
+    );
+}
+
+#[test]
+fn test_avro_schema_keeps_schema_fields_not_in_data() {
+    // The schema, not the data, decides the output shape
+    let avsc = avsc_file(AVSC);
+
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["--avro-schema", avsc.path().to_str().unwrap()])
+        .write_stdin(r#"{"other": 1}"#);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(r#""labels": null"#));
+}
+
+#[test]
+fn test_avro_schema_invalid() {
+    let avsc = avsc_file(r#"{"type": "array", "items": "Missing"}"#);
+
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["--avro-schema", avsc.path().to_str().unwrap()])
+        .write_stdin(r#"{"a": 1}"#);
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("Unknown Avro type: Missing"));
+}
+
+#[test]
+fn test_avro_schema_missing_value() {
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.arg("--avro-schema");
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("Missing value for --avro-schema"));
+}
+
+#[test]
+fn test_output_avro_container() {
+    let out = tempfile::Builder::new().suffix(".avro").tempfile().unwrap();
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["--ndjson", "--keep-empty", "--output-avro"])
+        .arg(out.path())
+        .write_stdin("{\"id\": 1, \"tags\": [\"a\"]}\n{\"id\": 2, \"tags\": []}\n");
+    cmd.assert().success().stdout(predicate::str::is_empty());
+
+    let bytes = std::fs::read(out.path()).unwrap();
+    assert!(bytes.starts_with(b"Obj\x01"));
+}
+
+#[test]
+fn test_output_avro_row_mismatch() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("data.avro");
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    // The empty array becomes null, which the non-nullable `tags` can't hold
+    cmd.args(["--ndjson", "--output-avro"])
+        .arg(&path)
+        .write_stdin("{\"id\": 1, \"tags\": [\"a\"]}\n{\"id\": 2, \"tags\": []}\n");
+    cmd.assert().failure().stderr(predicate::str::contains(
+        "Row 2 does not match the Avro schema",
+    ));
+    assert!(!path.exists());
+}
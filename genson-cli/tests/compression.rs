@@ -0,0 +1,88 @@
+// genson-cli/tests/compression.rs
+use predicates::prelude::*;
+use std::io::Write;
+use tempfile::Builder;
+
+const NDJSON: &str = r#"{"name": "Alice", "age": 30}
+{"name": "Bob", "age": 25, "city": "NYC"}
+"#;
+
+fn write_temp(suffix: &str, bytes: &[u8]) -> tempfile::NamedTempFile {
+    let mut file = Builder::new().suffix(suffix).tempfile().unwrap();
+    file.write_all(bytes).unwrap();
+    file
+}
+
+fn gzip(text: &str) -> Vec<u8> {
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(text.as_bytes()).unwrap();
+    encoder.finish().unwrap()
+}
+
+#[test]
+fn test_gzip_detected_from_extension() {
+    let file = write_temp(".jsonl.gz", &gzip(NDJSON));
+
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.arg("--ndjson").arg(file.path());
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("\"city\""))
+        .stderr(predicate::str::contains("Processed 2 JSON object(s)"));
+}
+
+#[test]
+fn test_gzip_ndjson_normalised() {
+    let file = write_temp(".jsonl.gz", &gzip(NDJSON));
+
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["--ndjson", "--normalise"]).arg(file.path());
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(r#""name":"Alice""#))
+        .stdout(predicate::str::contains(r#""city":"NYC""#));
+}
+
+#[test]
+fn test_zstd_detected_from_extension() {
+    let compressed = zstd::stream::encode_all(NDJSON.as_bytes(), 0).unwrap();
+    let file = write_temp(".jsonl.zst", &compressed);
+
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.arg("--ndjson").arg(file.path());
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("\"age\""))
+        .stdout(predicate::str::contains("\"city\""));
+}
+
+#[test]
+fn test_compression_flag_on_stdin() {
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["--ndjson", "--compression", "gzip"])
+        .write_stdin(gzip(NDJSON));
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("\"city\""));
+}
+
+#[test]
+fn test_compression_flag_overrides_extension() {
+    let file = write_temp(".data", &gzip(NDJSON));
+
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["--ndjson", "--compression", "gz"])
+        .arg(file.path());
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("\"name\""));
+}
+
+#[test]
+fn test_invalid_compression_value() {
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["--compression", "lzma"]).write_stdin("{}");
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid value for --compression"));
+}
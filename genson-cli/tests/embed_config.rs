@@ -0,0 +1,25 @@
+// genson-cli/tests/embed_config.rs
+use predicates::prelude::*;
+
+#[test]
+fn test_embed_config_records_non_defaults() {
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["--embed-config", "--map-threshold", "3", "--debug"])
+        .write_stdin(r#"{"a": 1}"#);
+    let output = cmd.assert().success().get_output().stdout.clone();
+
+    let schema: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(
+        schema["x-genson-config"],
+        serde_json::json!({"map_threshold": 3})
+    );
+}
+
+#[test]
+fn test_no_config_without_flag() {
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.write_stdin(r#"{"a": 1}"#);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("x-genson-config").not());
+}
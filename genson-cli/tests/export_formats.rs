@@ -0,0 +1,118 @@
+// genson-cli/tests/export_formats.rs
+use predicates::prelude::*;
+
+const INPUT: &str = r#"{"id": "Q1", "count": 3, "labels": {"en": "one"}, "tags": ["a"]}
+{"id": "Q2", "labels": {"fr": "deux"}, "tags": []}"#;
+
+fn run(format: &str) -> serde_json::Value {
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["--ndjson", "--map-threshold", "1", "--format", format])
+        .write_stdin(INPUT);
+    let output = cmd.assert().success().get_output().stdout.clone();
+    serde_json::from_slice(&output).unwrap()
+}
+
+#[test]
+fn test_format_delta() {
+    let schema = run("delta");
+    assert_eq!(schema["type"], "struct");
+    let fields = schema["fields"].as_array().unwrap();
+    let count = fields.iter().find(|f| f["name"] == "count").unwrap();
+    assert_eq!(count["type"], "long");
+    assert_eq!(count["nullable"], true);
+    let labels = fields.iter().find(|f| f["name"] == "labels").unwrap();
+    assert_eq!(labels["type"]["type"], "map");
+    assert_eq!(labels["type"]["valueType"], "string");
+}
+
+#[test]
+fn test_format_iceberg() {
+    let schema = run("iceberg");
+    assert_eq!(schema["schema-id"], 0);
+    let fields = schema["fields"].as_array().unwrap();
+    assert!(fields.iter().all(|f| f["id"].is_u64()));
+    let tags = fields.iter().find(|f| f["name"] == "tags").unwrap();
+    assert_eq!(tags["required"], true);
+    assert_eq!(tags["type"]["type"], "list");
+}
+
+#[test]
+fn test_format_avro_matches_flag() {
+    let schema = run("avro");
+    assert_eq!(schema["type"], "record");
+}
+
+#[test]
+fn test_format_rejects_unknown() {
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["--format", "orc"]).write_stdin("{}");
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid value for --format: orc"));
+}
+
+#[test]
+fn test_format_delta_rejects_normalise() {
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["--format", "delta", "--normalise"])
+        .write_stdin(r#"{"a": 1}"#);
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("--normalise cannot be combined"));
+}
+
+#[test]
+fn test_arrow_flag() {
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["--ndjson", "--map-threshold", "1", "--arrow"])
+        .write_stdin(INPUT);
+    let output = cmd.assert().success().get_output().stdout.clone();
+    let schema: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    let fields = schema["fields"].as_array().unwrap();
+    let count = fields.iter().find(|f| f["name"] == "count").unwrap();
+    assert_eq!(
+        count["type"],
+        serde_json::json!({"name": "int", "bitWidth": 64, "isSigned": true})
+    );
+    let labels = fields.iter().find(|f| f["name"] == "labels").unwrap();
+    assert_eq!(labels["type"]["name"], "map");
+}
+
+#[test]
+fn test_format_arrow_ipc() {
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["--ndjson", "--format", "arrow-ipc"])
+        .write_stdin(INPUT);
+    let output = cmd.assert().success().get_output().stdout.clone();
+    // IPC stream messages start with the 0xFFFFFFFF continuation marker
+    assert_eq!(&output[..4], &[0xFF, 0xFF, 0xFF, 0xFF]);
+    // and end with the end-of-stream marker
+    assert_eq!(
+        &output[output.len() - 8..],
+        &[0xFF, 0xFF, 0xFF, 0xFF, 0, 0, 0, 0]
+    );
+}
+
+#[test]
+fn test_proto_flag() {
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args([
+        "--ndjson",
+        "--map-threshold",
+        "1",
+        "--proto",
+        "--proto-message",
+        "Entity",
+    ])
+    .write_stdin(INPUT);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::starts_with(
+            "syntax = \"proto3\";\n\nmessage Entity {\n",
+        ))
+        .stdout(predicate::str::contains("  optional int64 count = 2;\n"))
+        .stdout(predicate::str::contains(
+            "  map<string, string> labels = 3;\n",
+        ))
+        .stdout(predicate::str::contains("  repeated string tags = 4;\n"));
+}
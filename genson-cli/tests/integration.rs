@@ -226,3 +226,1234 @@ fn test_force_scalar_promotion_cli() {
 
     println!("✅ Force scalar promotion CLI test passed");
 }
+
+#[test]
+fn test_chunk_size_ndjson() {
+    let ndjson = (0..10)
+        .map(|i| format!(r#"{{"id": {}, "k{}": true}}"#, i, i % 3))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let mut unchunked = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    unchunked.arg("--ndjson").write_stdin(ndjson.clone());
+    let expected = unchunked.assert().success().get_output().stdout.clone();
+
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["--ndjson", "--chunk-size", "3"])
+        .write_stdin(ndjson);
+    cmd.assert()
+        .success()
+        .stdout(expected)
+        .stderr(predicate::str::contains("Processed 10 JSON object(s)"));
+}
+
+#[test]
+fn test_error_context_and_dump_dir() {
+    let dir = tempfile::tempdir().unwrap();
+    let bad_line = format!(r#"{{"padding": "{}", "broken": }}"#, "p".repeat(200));
+    let ndjson = format!("{{\"ok\": 1}}\n{}\n", bad_line);
+
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["--ndjson", "--error-context", "30", "--error-dump-dir"])
+        .arg(dir.path())
+        .write_stdin(ndjson);
+    let output = cmd.assert().failure().get_output().stderr.clone();
+    let stderr = String::from_utf8(output).unwrap();
+    assert!(
+        stderr.contains("Invalid JSON input at index 2:"),
+        "{}",
+        stderr
+    );
+    assert!(stderr.contains(r#""broken": }"#), "{}", stderr);
+
+    let dumps: Vec<_> = std::fs::read_dir(dir.path()).unwrap().collect();
+    assert_eq!(dumps.len(), 1);
+    let dump = dumps[0].as_ref().unwrap().path();
+    assert!(stderr.contains(&dump.display().to_string()), "{}", stderr);
+    assert_eq!(std::fs::read_to_string(dump).unwrap(), bad_line);
+}
+
+#[test]
+fn test_sample_options() {
+    let ndjson = (0..10)
+        .map(|i| format!(r#"{{"id": {}}}"#, i))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    for (args, processed) in [
+        (vec!["--sample", "3"], 3),
+        (vec!["--sample-random", "4:42"], 4),
+        (vec!["--sample-every", "5"], 2),
+    ] {
+        let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+        cmd.arg("--ndjson").args(&args).write_stdin(ndjson.clone());
+        cmd.assert()
+            .success()
+            .stderr(predicate::str::contains(format!(
+                "Processed {} JSON object(s)",
+                processed
+            )));
+    }
+
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["--sample-random", "4"]).write_stdin("{}");
+    cmd.assert().failure().stderr(predicate::str::contains(
+        "Invalid value for --sample-random: expected N:SEED",
+    ));
+}
+
+#[test]
+fn test_zero_counts_rejected() {
+    for flag in ["--sample-every", "--chunk-size", "--jobs"] {
+        let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+        cmd.args(["--ndjson", flag, "0"]).write_stdin("{}");
+        cmd.assert()
+            .failure()
+            .stderr(predicate::str::contains(format!(
+                "Invalid value for {}: 0",
+                flag
+            )));
+    }
+}
+
+#[test]
+fn test_ignore_paths_cli() {
+    let ndjson = r#"{"id": 1, "metadata": {"a": 1, "b": 2}}
+{"id": 2, "metadata": {"c": {"d": true}}}"#;
+
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["--ndjson", "--ignore-paths", "metadata"])
+        .write_stdin(ndjson);
+    let output = cmd.assert().success().get_output().stdout.clone();
+    let schema: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(schema["properties"]["metadata"]["type"], "string");
+    assert_eq!(schema["properties"]["id"]["type"], "integer");
+}
+
+#[test]
+fn test_select_paths_cli() {
+    let ndjson = r#"{"id": 1, "meta": {"lang": "en", "n": 2}, "blob": {"a": 1}}
+{"id": 2, "meta": {"lang": "fr"}, "blob": [1]}"#;
+    let mut paths = NamedTempFile::new().unwrap();
+    writeln!(paths, "# wanted fields\nid\n\n/meta/lang").unwrap();
+    let paths_arg = paths.path().to_str().unwrap();
+
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["--ndjson", "--select-paths", paths_arg])
+        .write_stdin(ndjson);
+    let output = cmd.assert().success().get_output().stdout.clone();
+    let schema: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    let props = schema["properties"].as_object().unwrap();
+    assert_eq!(props.keys().collect::<Vec<_>>(), ["id", "meta"]);
+    assert_eq!(
+        schema["properties"]["meta"]["properties"],
+        serde_json::json!({"lang": {"type": "string"}})
+    );
+
+    // Pruned fields aren't unknown to normalisation
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["--ndjson", "--normalise", "--unknown-fields", "error"])
+        .args(["--select-paths", paths_arg])
+        .write_stdin(ndjson);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(r#"{"id":1,"meta":{"lang":"en"}}"#));
+
+    let empty = NamedTempFile::new().unwrap();
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["--select-paths", empty.path().to_str().unwrap()])
+        .write_stdin("{}");
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("No field paths in"));
+}
+
+#[test]
+fn test_discriminator_cli() {
+    let ndjson = r#"{"snak": {"type": "value", "value": "Q5"}}
+{"snak": {"type": "novalue"}}"#;
+
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["--ndjson", "--discriminator", "snak:type"])
+        .write_stdin(ndjson);
+    let output = cmd.assert().success().get_output().stdout.clone();
+    let schema: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    let branches = schema["properties"]["snak"]["oneOf"].as_array().unwrap();
+    assert_eq!(branches.len(), 2);
+    assert_eq!(branches[1]["properties"]["type"]["const"], "value");
+    assert_eq!(
+        branches[1]["required"],
+        serde_json::json!(["type", "value"])
+    );
+
+    // Avro gets the branches as one record
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["--ndjson", "--avro", "--discriminator", "snak:type"])
+        .write_stdin(ndjson);
+    let output = cmd.assert().success().get_output().stdout.clone();
+    let avro: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(avro["fields"][0]["type"]["type"], "record");
+
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["--discriminator", "snak"]).write_stdin("{}");
+    cmd.assert().failure().stderr(predicate::str::contains(
+        "Invalid value for --discriminator: snak (expected path:field)",
+    ));
+}
+
+#[test]
+fn test_max_depth_and_object_keys_cli() {
+    let json = r#"{"nested": {"a": {"b": 1}}, "wide": {"a": 1, "b": 2, "c": 3}}"#;
+
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["--max-depth", "2", "--max-object-keys", "2"])
+        .write_stdin(json);
+    let output = cmd.assert().success().get_output().stdout.clone();
+    let schema: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    let props = &schema["properties"];
+    assert_eq!(props["nested"]["properties"]["a"]["type"], "string");
+    assert_eq!(props["wide"]["type"], "string");
+
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["--max-depth", "deep"]).write_stdin(json);
+    cmd.assert().failure().stderr(predicate::str::contains(
+        "Invalid value for --max-depth: deep",
+    ));
+}
+
+#[test]
+fn test_pq_batch_size_matches_unbatched() {
+    let temp_file = NamedTempFile::new().expect("Failed to create temp file");
+    let path = temp_file.path().to_str().unwrap();
+    let rows: Vec<String> = (0..20)
+        .map(|i| format!(r#"{{"id": {}, "k{}": "x"}}"#, i, i % 4))
+        .collect();
+    genson_core::parquet::write_string_rows(path, "json", rows, None, 6).unwrap();
+
+    let mut unbatched = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    unbatched.args(["--pq-column", "json", path]);
+    let expected = unbatched.assert().success().get_output().stdout.clone();
+
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["--pq-column", "json", "--pq-batch-size", "5", path]);
+    cmd.assert()
+        .success()
+        .stdout(expected)
+        .stderr(predicate::str::contains("Processed 20 JSON object(s)"));
+
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args([
+        "--pq-column",
+        "json",
+        "--pq-batch-size",
+        "5",
+        "--normalise",
+        "--ndjson",
+        path,
+    ]);
+    let output = cmd.assert().success().get_output().stdout.clone();
+    assert_eq!(String::from_utf8(output).unwrap().lines().count(), 20);
+
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["--pq-batch-size", "5"]).write_stdin("{}");
+    cmd.assert().failure().stderr(predicate::str::contains(
+        "--pq-batch-size requires --pq-column",
+    ));
+}
+
+#[test]
+fn test_multiple_input_files_and_globs() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("part-1.jsonl"),
+        "{\"id\": 1}\n{\"id\": 2}\n",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.path().join("part-2.jsonl"),
+        "{\"id\": 3, \"name\": \"c\"}\n",
+    )
+    .unwrap();
+    std::fs::write(dir.path().join("other.jsonl"), "{\"other\": true}\n").unwrap();
+    let pattern = dir.path().join("part-*.jsonl");
+    let pattern = pattern.to_str().unwrap();
+
+    for jobs in ["1", "2"] {
+        let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+        cmd.args(["--ndjson", "--jobs", jobs, pattern]);
+        let assert = cmd
+            .assert()
+            .success()
+            .stderr(predicate::str::contains(
+                "Processed 3 JSON object(s) from 2 files",
+            ))
+            .stderr(predicate::str::contains("part-1.jsonl: 2"))
+            .stderr(predicate::str::contains("part-2.jsonl: 1"));
+        let schema: serde_json::Value =
+            serde_json::from_slice(&assert.get_output().stdout).unwrap();
+        assert_eq!(schema["required"], serde_json::json!(["id"]));
+        assert_eq!(schema["properties"]["name"]["type"], "string");
+        assert!(schema["properties"].get("other").is_none());
+    }
+
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["--ndjson", "--normalise", pattern]);
+    let output = cmd.assert().success().get_output().stdout.clone();
+    assert_eq!(
+        String::from_utf8(output).unwrap(),
+        "{\"id\":1,\"name\":null}\n{\"id\":2,\"name\":null}\n{\"id\":3,\"name\":\"c\"}\n"
+    );
+
+    let missing = dir.path().join("nothing-*.jsonl");
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.arg(missing.to_str().unwrap());
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("No files match"));
+}
+
+#[test]
+fn test_draft_and_no_schema_uri() {
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["--draft", "2020-12"]).write_stdin(r#"{"id": 1}"#);
+    cmd.assert().success().stdout(predicate::str::contains(
+        r#""$schema": "https://json-schema.org/draft/2020-12/schema""#,
+    ));
+
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["--draft", "draft-07", "--no-schema-uri"])
+        .write_stdin(r#"{"id": 1}"#);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("$schema").not());
+
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["--draft", "4"]).write_stdin(r#"{"id": 1}"#);
+    cmd.assert().failure().stderr(predicate::str::contains(
+        "Invalid value for --draft: 4 (expected draft-07|2019-09|2020-12)",
+    ));
+
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["--draft", "2019-09", "--avro"])
+        .write_stdin(r#"{"id": 1}"#);
+    cmd.assert().failure().stderr(predicate::str::contains(
+        "--draft only applies to JSON Schema output",
+    ));
+}
+
+#[test]
+fn test_compact_schema() {
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    let output = cmd
+        .args(["--compact", "--ndjson", "-q"])
+        .write_stdin("{\"id\": 1, \"tags\": [\"a\"]}\n{\"id\": 2, \"tags\": []}\n")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let schema: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(schema["$schema"], "http://json-schema.org/schema#");
+    assert_eq!(
+        schema["properties"]["tags"],
+        serde_json::json!({"type": "array", "items": {"type": "string"}})
+    );
+
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["--compact", "--avro"])
+        .write_stdin(r#"{"id": 1}"#);
+    cmd.assert().failure().stderr(predicate::str::contains(
+        "--compact only applies to JSON Schema output",
+    ));
+}
+
+#[test]
+fn test_value_limits() {
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    let output = cmd
+        .args(["-q", "--normalise", "--max-string-len", "4"])
+        .args(["--max-array-items", "2", "--truncation-marker", "~"])
+        .write_stdin(r#"{"s": "abcdefghij", "a": [1, 2, 3, 4]}"#)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let rows: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(rows, serde_json::json!([{"s": "abcd~", "a": [1, 2]}]));
+
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["--max-array-items", "2"]).write_stdin("{}");
+    cmd.assert().failure().stderr(predicate::str::contains(
+        "require --normalise or --avro-schema",
+    ));
+}
+
+#[test]
+fn test_encode_map_schema() {
+    let input = r#"{"labels": {"en": "Hello", "fr": "Bonjour", "de": "Hallo"}}"#;
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    let output = cmd
+        .args(["-q", "--map-threshold", "2", "--map-encoding", "kv"])
+        .arg("--encode-map-schema")
+        .write_stdin(input)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let schema: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let labels = &schema["properties"]["labels"];
+    assert_eq!(labels["type"], "array");
+    assert_eq!(
+        labels["items"]["properties"]["value"],
+        serde_json::json!({"type": "string"})
+    );
+
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.arg("--encode-map-schema").write_stdin(input);
+    cmd.assert().failure().stderr(predicate::str::contains(
+        "--encode-map-schema requires --map-encoding entries|kv",
+    ));
+}
+
+#[test]
+fn test_numeric_map_keys() {
+    let input = r#"{"scores": {"1": 10, "007": 7}}"#;
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    let output = cmd
+        .args(["-q", "--map-threshold", "2", "--detect-numeric-keys"])
+        .write_stdin(input)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let schema: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(schema["properties"]["scores"]["x-key-type"], "integer");
+
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args([
+        "-q",
+        "--ndjson",
+        "--map-threshold",
+        "2",
+        "--detect-numeric-keys",
+    ])
+    .args([
+        "--normalise",
+        "--map-encoding",
+        "kv",
+        "--canonical-numeric-keys",
+    ])
+    .write_stdin(input);
+    cmd.assert().success().stdout(predicate::str::contains(
+        r#"{"scores":[{"key":1,"value":10},{"key":7,"value":7}]}"#,
+    ));
+
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.arg("--canonical-numeric-keys").write_stdin(input);
+    cmd.assert().failure().stderr(predicate::str::contains(
+        "--canonical-numeric-keys requires --normalise or --avro-schema",
+    ));
+}
+
+#[test]
+fn test_detect_formats() {
+    let input = r#"{"id": "6f1c1d52-8e0a-4a3b-9d55-2f1f9e6a7b10", "site": "https://example.com"}"#;
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    let output = cmd
+        .args(["--detect-formats", "uuid", "-q"])
+        .write_stdin(input)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let schema: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(schema["properties"]["id"]["format"], "uuid");
+    assert!(schema["properties"]["site"].get("format").is_none());
+
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    let output = cmd
+        .args(["--detect-formats", "all", "--avro", "-q"])
+        .write_stdin(input)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let schema: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(schema["fields"][0]["type"]["logicalType"], "uuid");
+
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["--format-sample-rate", "0.5"]).write_stdin(input);
+    cmd.assert().failure().stderr(predicate::str::contains(
+        "--format-sample-rate requires --detect-formats",
+    ));
+}
+
+#[test]
+fn test_seed_schema() {
+    let dir = tempfile::tempdir().unwrap();
+    let seed = dir.path().join("seed.json");
+    std::fs::write(
+        &seed,
+        r#"{"type": "object", "properties": {"id": {"type": "integer", "description": "Row id"}}, "required": ["id"]}"#,
+    )
+    .unwrap();
+
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    let output = cmd
+        .args(["-q", "--seed-schema"])
+        .arg(&seed)
+        .write_stdin(r#"{"id": 2, "name": "a"}"#)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let schema: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(schema["properties"]["id"]["description"], "Row id");
+    assert_eq!(schema["properties"]["name"]["type"], "string");
+
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["--seed-schema", "missing.json"])
+        .write_stdin("{}");
+    cmd.assert().failure().stderr(predicate::str::contains(
+        "Failed to read seed schema missing.json",
+    ));
+}
+
+#[test]
+fn test_explain_report() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("explain.json");
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["--map-threshold", "2", "--explain"])
+        .arg(&path)
+        .write_stdin(r#"{"labels": {"en": "a", "fr": "b"}}"#);
+    cmd.assert().success();
+
+    let report: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    let labels = report
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|e| e["path"] == "/labels")
+        .unwrap();
+    assert_eq!(labels["decision"], "map");
+    assert_eq!(labels["key_count"], 2);
+    assert_eq!(labels["map_threshold"], 2);
+}
+
+#[test]
+fn test_debug_log() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("decisions.ndjson");
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["--map-threshold", "2", "--debug-log"])
+        .arg(&path)
+        .write_stdin(r#"{"labels": {"en": "a", "fr": "b"}, "id": 1}"#);
+    cmd.assert().success();
+
+    let events: Vec<serde_json::Value> = std::fs::read_to_string(&path)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    let labels = events.iter().find(|e| e["path"] == "/labels").unwrap();
+    assert_eq!(labels["event"], "map_rewrite");
+    assert_eq!(labels["outcome"], "map");
+    assert_eq!(labels["inputs"]["key_count"], 2);
+    assert!(events
+        .iter()
+        .any(|e| e["path"] == "" && e["event"] == "record_kept"));
+}
+
+#[test]
+fn test_annotate() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("metadata.yaml");
+    std::fs::write(
+        &path,
+        "id:\n  description: Primary key\nowner.name:\n  title: Owner name\n",
+    )
+    .unwrap();
+    let input = r#"{"id": 1, "owner": {"name": "a"}}"#;
+
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    let output = cmd
+        .args(["-q", "--annotate"])
+        .arg(&path)
+        .write_stdin(input)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let schema: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(schema["properties"]["id"]["description"], "Primary key");
+    assert_eq!(
+        schema["properties"]["owner"]["properties"]["name"]["title"],
+        "Owner name"
+    );
+
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    let output = cmd
+        .args(["-q", "--avro", "--annotate"])
+        .arg(&path)
+        .write_stdin(input)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let avro: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(avro["fields"][0]["doc"], "Primary key");
+}
+
+#[test]
+fn test_map_detection_variance() {
+    let input = "{\"tags\": {\"a\": 1, \"b\": 2}, \"meta\": {\"x\": 1, \"y\": 2}}\n{\"tags\": {\"c\": 3, \"d\": 4}, \"meta\": {\"x\": 3, \"y\": 4}}\n";
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    let output = cmd
+        .args(["--ndjson", "-q", "--map-threshold", "2"])
+        .args(["--map-detection", "variance"])
+        .write_stdin(input)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let schema: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(schema["properties"]["tags"]["additionalProperties"].is_object());
+    assert!(schema["properties"]["meta"]["properties"].is_object());
+
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["--map-detection", "sometimes"]).write_stdin("{}");
+    cmd.assert().failure().stderr(predicate::str::contains(
+        "Invalid value for --map-detection: sometimes (expected merged|variance)",
+    ));
+}
+
+#[test]
+fn test_sort_fields() {
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["--sort-fields", "alphabetical"])
+        .write_stdin(r#"{"zebra": 1, "apple": 2}"#);
+    let output = cmd.assert().success().get_output().stdout.clone();
+    let stdout = String::from_utf8(output).unwrap();
+    assert!(stdout.find("apple").unwrap() < stdout.find("zebra").unwrap());
+
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["--sort-fields", "random"]).write_stdin("{}");
+    cmd.assert().failure().stderr(predicate::str::contains(
+        "Invalid value for --sort-fields: random (expected preserve|alphabetical|frequency)",
+    ));
+}
+
+#[test]
+fn test_union_order() {
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["--avro", "--union-order", "frequency", "--ndjson"])
+        .write_stdin("{\"a\": 1}\n{\"a\": \"x\"}\n{\"a\": \"y\"}\n");
+    let assert = cmd.assert().success();
+    let schema: serde_json::Value = serde_json::from_slice(&assert.get_output().stdout).unwrap();
+    assert_eq!(
+        schema["fields"][0]["type"],
+        serde_json::json!(["string", "int"])
+    );
+
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["--union-order", "random"]).write_stdin("{}");
+    cmd.assert().failure().stderr(predicate::str::contains(
+        "Invalid value for --union-order: random (expected canonical|null-first|frequency|default-first)",
+    ));
+}
+
+#[test]
+fn test_nonfinite() {
+    let input = "{\"a\": 1.5}\n{\"a\": NaN}\n{\"a\": -Infinity}\n";
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["--ndjson"]).write_stdin(input);
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid JSON input"));
+
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["--ndjson", "--nonfinite", "string", "--normalise"])
+        .write_stdin(input);
+    cmd.assert().success().stdout(predicate::str::contains(
+        "{\"a\":1.5}\n{\"a\":\"NaN\"}\n{\"a\":\"-Infinity\"}\n",
+    ));
+
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["--ndjson", "--nonfinite", "null", "--normalise"])
+        .write_stdin(input);
+    cmd.assert().success().stdout(predicate::str::contains(
+        "{\"a\":1.5}\n{\"a\":null}\n{\"a\":null}\n",
+    ));
+
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["--nonfinite", "zero"]).write_stdin("{}");
+    cmd.assert().failure().stderr(predicate::str::contains(
+        "Invalid value for --nonfinite: zero (expected null|string)",
+    ));
+}
+
+#[test]
+fn test_repair() {
+    let input = "\u{FEFF}{\"a\": 1}\n{a: 2,}\n{'a': 3}\n";
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["--ndjson"]).write_stdin(input);
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid JSON input"));
+
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["-q", "--ndjson", "--repair", "--normalise"])
+        .write_stdin(input);
+    cmd.assert()
+        .success()
+        .stdout("{\"a\":1}\n{\"a\":2}\n{\"a\":3}\n");
+
+    let report = NamedTempFile::new().unwrap();
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["-q", "--ndjson", "--repair-report"])
+        .arg(report.path())
+        .write_stdin(input);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("\"integer\""));
+    let report: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(report.path()).unwrap()).unwrap();
+    assert_eq!(
+        report,
+        serde_json::json!([
+            {"row": 0, "repairs": ["byte_order_mark"]},
+            {"row": 1, "repairs": ["unquoted_key", "trailing_comma"]},
+            {"row": 2, "repairs": ["single_quotes"]}
+        ])
+    );
+}
+
+#[test]
+fn test_map_key_stats() {
+    let stats = NamedTempFile::new().unwrap();
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["-q", "--ndjson", "--map-threshold", "2", "--map-key-stats"])
+        .arg(stats.path())
+        .write_stdin(
+            "{\"labels\": {\"en\": \"a\", \"fr\": \"b\"}}\n{\"labels\": {\"en\": \"c\"}}\n",
+        );
+    cmd.assert().success();
+    let stats: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(stats.path()).unwrap()).unwrap();
+    assert_eq!(
+        stats,
+        serde_json::json!([{
+            "path": "/labels",
+            "objects": 2,
+            "distinct_keys": 2,
+            "min_keys": 1,
+            "max_keys": 2,
+            "top_keys": [{"key": "en", "count": 2}, {"key": "fr", "count": 1}]
+        }])
+    );
+}
+
+#[test]
+fn test_empty_containers() {
+    let input = "{\"meta\": {\"x\": 1}}\n{\"meta\": {}}\n";
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["-q", "--ndjson", "--empty-containers", "ignore"])
+        .write_stdin(input);
+    let output = cmd.assert().success().get_output().stdout.clone();
+    let schema: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(
+        schema["properties"]["meta"]["required"],
+        serde_json::json!(["x"])
+    );
+
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["--empty-containers", "drop"]).write_stdin("{}");
+    cmd.assert().failure().stderr(predicate::str::contains(
+        "Invalid value for --empty-containers: drop (expected ignore|map-of-string|nullable)",
+    ));
+}
+
+#[test]
+fn test_detect_sparse_arrays() {
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["-q", "--ndjson", "--detect-sparse-arrays", "--normalise"])
+        .write_stdin("{\"tags\": {\"1\": \"b\", \"0\": \"a\"}}\n{\"tags\": [\"c\"]}\n");
+    cmd.assert()
+        .success()
+        .stdout("{\"tags\":[\"a\",\"b\"]}\n{\"tags\":[\"c\"]}\n");
+}
+
+#[test]
+fn test_force_scalar_type() {
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args([
+        "-q",
+        "--ndjson",
+        "--force-type",
+        "price:number",
+        "--normalise",
+    ])
+    .write_stdin("{\"price\": 12}\n{\"price\": \"12.5\"}\n");
+    cmd.assert()
+        .success()
+        .stdout("{\"price\":12.0}\n{\"price\":12.5}\n");
+
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["--force-type", "price:float"])
+        .write_stdin("{\"price\": 1}");
+    cmd.assert().failure().stderr(predicate::str::contains(
+        "Invalid force_field_types value for price: float",
+    ));
+}
+
+#[test]
+fn test_null_fallback() {
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["--avro", "--null-fallback", "string"])
+        .write_stdin(r#"{"id": 1, "note": null}"#);
+    let assert = cmd.assert().success().stderr(predicate::str::contains(
+        "Typed null-only field(s) as string: /note",
+    ));
+    let schema: serde_json::Value = serde_json::from_slice(&assert.get_output().stdout).unwrap();
+    assert_eq!(
+        schema["fields"][1]["type"],
+        serde_json::json!(["null", "string"])
+    );
+
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["--null-fallback", "object"]).write_stdin("{}");
+    cmd.assert().failure().stderr(predicate::str::contains(
+        "Invalid value for --null-fallback: object (expected string|integer|number|boolean)",
+    ));
+}
+
+#[test]
+fn test_bare_root_documents() {
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["--ndjson", "--normalise"])
+        .write_stdin("[1, 2, 3]\n[4]\n");
+    cmd.assert()
+        .success()
+        .stdout("{\"value\":[1,2,3]}\n{\"value\":[4]}\n");
+
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["--ndjson", "--normalise", "--root-value-field", "text"])
+        .write_stdin("\"string\"\n\"another\"\n");
+    cmd.assert()
+        .success()
+        .stdout("{\"text\":\"string\"}\n{\"text\":\"another\"}\n");
+}
+
+#[test]
+fn test_output_format_yaml_and_toml() {
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["--output-format", "yaml"])
+        .write_stdin(r#"{"id": 1}"#);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::starts_with("$schema:"))
+        .stdout(predicate::str::contains("type: integer"));
+
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["--output-format", "toml"])
+        .write_stdin(r#"{"id": 1}"#);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("[properties.id]"));
+
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["--output-format", "xml"]).write_stdin("{}");
+    cmd.assert().failure().stderr(predicate::str::contains(
+        "Invalid value for --output-format: xml (expected json|yaml|toml)",
+    ));
+}
+
+#[test]
+fn test_detect_tuples() {
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["--ndjson", "--detect-tuples"])
+        .write_stdin("{\"pair\": [\"a\", 1]}\n{\"pair\": [\"b\", 2]}\n");
+    let assert = cmd.assert().success();
+    let schema: serde_json::Value = serde_json::from_slice(&assert.get_output().stdout).unwrap();
+    assert_eq!(
+        schema["properties"]["pair"]["prefixItems"],
+        serde_json::json!([{"type": "string"}, {"type": "integer"}])
+    );
+}
+
+#[test]
+fn test_coerce_map_array_normalise() {
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["--ndjson", "--coerce-map-array", "--normalise"])
+        .write_stdin("{\"l\": {\"en\": [\"a\"]}}\n{\"l\": [{\"en\": \"b\"}, {\"en\": \"c\"}]}\n");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(r#"{"l":{"en":["a"]}}"#))
+        .stdout(predicate::str::contains(r#"{"l":{"en":["b","c"]}}"#));
+}
+
+#[test]
+fn test_required_threshold_and_presence_report() {
+    let dir = tempfile::tempdir().unwrap();
+    let report = dir.path().join("presence.json");
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args([
+        "--ndjson",
+        "--required-threshold",
+        "0.6",
+        "--presence-report",
+    ])
+    .arg(&report)
+    .write_stdin("{\"id\": 1, \"name\": \"a\"}\n{\"id\": 2, \"name\": \"b\"}\n{\"id\": 3}\n");
+    let assert = cmd.assert().success();
+    let schema: serde_json::Value = serde_json::from_slice(&assert.get_output().stdout).unwrap();
+    assert_eq!(schema["required"], serde_json::json!(["id", "name"]));
+
+    let presence: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&report).unwrap()).unwrap();
+    assert_eq!(presence[1]["path"], "/name");
+    assert_eq!(presence[1]["present"], 2);
+    assert_eq!(presence[1]["total"], 3);
+}
+
+#[test]
+fn test_invalid_required_threshold() {
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["--required-threshold", "2"])
+        .write_stdin("{}")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Invalid value for --required-threshold: 2",
+        ));
+}
+
+#[test]
+fn test_csv_column() {
+    let mut csv = tempfile::Builder::new().suffix(".tsv").tempfile().unwrap();
+    writeln!(
+        csv,
+        "id\tpayload\n1\t{{\"a\": 1}}\n2\t{{\"a\": 2, \"b\": \"x\"}}"
+    )
+    .unwrap();
+
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    let assert = cmd
+        .args(["--csv-column", "payload"])
+        .arg(csv.path())
+        .assert()
+        .success();
+    let schema: serde_json::Value = serde_json::from_slice(&assert.get_output().stdout).unwrap();
+    assert_eq!(schema["required"], serde_json::json!(["a"]));
+    assert_eq!(schema["properties"]["b"]["type"], "string");
+
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args([
+        "--csv-column",
+        "payload",
+        "--csv-delimiter",
+        ";",
+        "--normalise",
+        "--ndjson",
+    ])
+    .write_stdin("payload;id\n\"{\"\"a\"\": 1}\";1\n")
+    .assert()
+    .success()
+    .stdout(predicate::str::contains("{\"a\":1}"));
+}
+
+#[test]
+fn test_csv_column_missing() {
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["--csv-column", "payload"])
+        .write_stdin("a,b\n1,2\n")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Column 'payload' not found in CSV header",
+        ));
+}
+
+#[test]
+fn test_stdin_ndjson_stream_with_progress() {
+    let input: String = (0..25)
+        .map(|i| format!("{{\"id\": {}}}\n", i))
+        .chain(std::iter::once(
+            "{\"id\": 25, \"extra\": true}\n".to_string(),
+        ))
+        .collect();
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["--stdin-format", "ndjson", "--progress", "10"])
+        .write_stdin(input)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"extra\""))
+        .stderr(predicate::str::contains("Read 10 lines"))
+        .stderr(predicate::str::contains("Read 26 lines"));
+}
+
+#[test]
+fn test_stdin_format_rejects_files_and_progress_needs_stream() {
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["--stdin-format", "ndjson", "data.json"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--stdin-format only applies"));
+
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["--ndjson", "--normalise", "--progress", "10"])
+        .write_stdin("{}\n")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "--progress requires NDJSON on stdin",
+        ));
+}
+
+#[test]
+fn test_convert_json_schema_to_avro() {
+    let dir = tempfile::tempdir().unwrap();
+    let schema_path = dir.path().join("schema.json");
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["--ndjson", "-o"])
+        .arg(&schema_path)
+        .write_stdin("{\"id\": 1, \"owner\": {\"name\": \"a\"}}\n");
+    cmd.assert().success();
+
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["convert", "--namespace", "org.example", "--name", "Item"])
+        .arg(&schema_path);
+    let assert = cmd.assert().success();
+    let avro: serde_json::Value = serde_json::from_slice(&assert.get_output().stdout).unwrap();
+    assert_eq!(avro["type"], "record");
+    assert_eq!(avro["name"], "Item");
+    assert_eq!(avro["namespace"], "org.example");
+    assert_eq!(
+        avro["fields"][0],
+        serde_json::json!({"name": "id", "type": "int"})
+    );
+}
+
+#[test]
+fn test_convert_avro_to_json_schema() {
+    let avsc = r#"{"type": "record", "name": "Item", "fields": [
+        {"name": "id", "type": "long"},
+        {"name": "label", "type": ["null", "string"], "default": null}
+    ]}"#;
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["convert", "--from-avro", "--to-jsonschema"])
+        .write_stdin(avsc);
+    let assert = cmd.assert().success();
+    let schema: serde_json::Value = serde_json::from_slice(&assert.get_output().stdout).unwrap();
+    assert_eq!(schema["type"], "object");
+    assert_eq!(schema["properties"]["id"]["type"], "integer");
+    assert_eq!(
+        schema["properties"]["label"]["type"],
+        serde_json::json!(["null", "string"])
+    );
+    assert_eq!(schema["required"], serde_json::json!(["id"]));
+
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["convert", "--to", "jsonschema"])
+        .write_stdin(avsc);
+    cmd.assert().failure().stderr(predicate::str::contains(
+        "Converting to jsonschema needs --from-avro",
+    ));
+}
+
+#[test]
+fn test_convert_invalid_name() {
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["convert", "--name", "my-record"])
+        .write_stdin("{\"type\": \"object\"}");
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid Avro name: my-record"));
+}
+
+#[test]
+fn test_generate_rows_from_schema() {
+    let dir = tempfile::tempdir().unwrap();
+    let schema_path = dir.path().join("schema.json");
+    std::fs::write(
+        &schema_path,
+        r#"{"type": "object", "properties": {"id": {"type": "integer"}}, "required": ["id"]}"#,
+    )
+    .unwrap();
+
+    let run = || {
+        let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+        cmd.args(["generate", "--rows", "5", "--seed", "3", "--schema"])
+            .arg(&schema_path);
+        cmd.assert().success().get_output().stdout.clone()
+    };
+    let stdout = run();
+    let rows: Vec<serde_json::Value> = String::from_utf8(stdout.clone())
+        .unwrap()
+        .lines()
+        .map(|l| serde_json::from_str(l).unwrap())
+        .collect();
+    assert_eq!(rows.len(), 5);
+    assert!(rows.iter().all(|r| r["id"].is_i64()));
+    assert_eq!(run(), stdout);
+}
+
+#[test]
+fn test_generate_requires_schema() {
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["generate", "--rows", "5"]);
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("generate requires --schema"));
+}
+
+#[test]
+fn test_no_avro_defaults() {
+    let input = r#"{"id": 1, "note": null}"#;
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.arg("--avro").write_stdin(input);
+    let assert = cmd.assert().success();
+    let schema: serde_json::Value = serde_json::from_slice(&assert.get_output().stdout).unwrap();
+    assert_eq!(schema["fields"][1]["default"], serde_json::Value::Null);
+
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["--avro", "--no-avro-defaults"])
+        .write_stdin(input);
+    let assert = cmd.assert().success();
+    let schema: serde_json::Value = serde_json::from_slice(&assert.get_output().stdout).unwrap();
+    assert!(schema["fields"][1].get("default").is_none());
+}
+
+#[test]
+fn test_avro_naming_and_docs() {
+    let dir = tempfile::tempdir().unwrap();
+    let docs = dir.path().join("docs.json");
+    std::fs::write(&docs, r#"{"owner.name": "Legal name of the owner"}"#).unwrap();
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args([
+        "--avro",
+        "--avro-namespace",
+        "org.example",
+        "--avro-name",
+        "Item",
+        "--avro-doc-file",
+    ])
+    .arg(&docs)
+    .write_stdin("{\"id\": 1, \"owner\": {\"name\": \"a\"}}");
+    let assert = cmd.assert().success();
+    let avro: serde_json::Value = serde_json::from_slice(&assert.get_output().stdout).unwrap();
+    assert_eq!(avro["name"], "Item");
+    assert_eq!(avro["namespace"], "org.example");
+    assert_eq!(
+        avro["fields"][1]["type"]["fields"][0]["doc"],
+        "Legal name of the owner"
+    );
+}
+
+#[test]
+fn test_parquet_schema_artifact() {
+    let dir = tempfile::tempdir().unwrap();
+    let out = dir.path().join("schema.parquet");
+    let out = out.to_str().unwrap();
+
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["--ndjson", "--parquet-schema", out, "--parquet-rows", "2"])
+        .write_stdin("{\"name\": \"a\", \"n\": 1}\n{\"name\": \"b\"}\n{\"name\": \"c\"}\n")
+        .assert()
+        .success();
+
+    let metadata = genson_core::parquet::read_parquet_metadata(out).unwrap();
+    let json_schema: serde_json::Value =
+        serde_json::from_str(&metadata["genson_json_schema"]).unwrap();
+    assert!(json_schema["properties"]["name"].is_object());
+    assert!(metadata.contains_key("genson_avro_schema"));
+    assert_eq!(
+        genson_core::parquet::read_string_column(out, "name").unwrap(),
+        ["a", "b"]
+    );
+}
+
+#[test]
+fn test_parquet_rows_requires_parquet_schema() {
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["--ndjson", "--parquet-rows", "2"])
+        .write_stdin("{\"a\": 1}\n")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "--parquet-rows requires --parquet-schema",
+        ));
+}
+
+#[test]
+fn test_outer_scalars_rows_normalises_each_item() {
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    let output = cmd
+        .args(["--outer-scalars", "rows", "--normalise"])
+        .write_stdin("[1, 2, null]")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let rows: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(
+        rows,
+        serde_json::json!([{"value": 1}, {"value": 2}, {"value": null}])
+    );
+}
+
+#[test]
+fn test_outer_scalars_rejects_unknown_policy() {
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["--outer-scalars", "columns"])
+        .write_stdin("[1]")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Invalid value for --outer-scalars: columns (expected array|rows)",
+        ));
+}
+
+#[test]
+fn test_avro_field_names_rename_schema_and_rows() {
+    let dir = tempfile::tempdir().unwrap();
+    let renames_path = dir.path().join("renames.json");
+
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    let output = cmd
+        .args([
+            "--normalise",
+            "--avro-field-names",
+            "replace",
+            "--avro-renames",
+        ])
+        .arg(&renames_path)
+        .write_stdin(r#"{"foo-bar": 1, "2023": "x"}"#)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let rows: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(rows, serde_json::json!([{"foo_bar": 1, "_2023": "x"}]));
+
+    let renames: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&renames_path).unwrap()).unwrap();
+    assert_eq!(
+        renames,
+        serde_json::json!([
+            {"path": "/foo-bar", "original": "foo-bar", "name": "foo_bar"},
+            {"path": "/2023", "original": "2023", "name": "_2023"}
+        ])
+    );
+}
+
+#[test]
+fn test_key_fold_merges_fields_and_reports_collisions() {
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    let output = cmd
+        .args(["--ndjson", "--key-fold", "preserve"])
+        .write_stdin("{\"ID\": 1}\n{\"id\": 2, \"Id\": 3}\n")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let schema: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(
+        schema["properties"],
+        serde_json::json!({"ID": {"type": "integer"}})
+    );
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        stderr.contains("Merged keys that differ only in case: ID (ID, id, Id)"),
+        "{}",
+        stderr
+    );
+}
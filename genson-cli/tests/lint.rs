@@ -0,0 +1,84 @@
+// genson-cli/tests/lint.rs
+use predicates::prelude::*;
+use serde_json::Value;
+use std::io::Write;
+use tempfile::NamedTempFile;
+
+const AVRO_SCHEMA: &str = r#"{
+  "type": "record",
+  "name": "document",
+  "fields": [
+    {"name": "id", "type": "string"},
+    {"name": "note", "type": ["null", "string"]},
+    {"name": "labels", "type": {"type": "map", "values": "string"}}
+  ]
+}"#;
+
+fn schema_file(contents: &str) -> NamedTempFile {
+    let mut file = NamedTempFile::new().unwrap();
+    file.write_all(contents.as_bytes()).unwrap();
+    file
+}
+
+#[test]
+fn test_lint_reports_structured_findings() {
+    let file = schema_file(AVRO_SCHEMA);
+
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.arg("lint").arg(file.path());
+    let output = cmd.assert().code(1).get_output().stdout.clone();
+
+    let findings: Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(
+        findings,
+        serde_json::json!([
+            {"rule": "optional_default", "path": "note", "message": "nullable field has no default"},
+            {"rule": "map_value_record", "path": "labels", "message": "map values are 'string', not a record"}
+        ])
+    );
+}
+
+#[test]
+fn test_lint_disable_rules_passes() {
+    let file = schema_file(AVRO_SCHEMA);
+
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["lint", "--disable", "optional_default,map_value_record"])
+        .arg(file.path());
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("[]"))
+        .stderr(predicate::str::contains("Found 0 lint finding(s)"));
+}
+
+#[test]
+fn test_lint_text_format_from_stdin() {
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["lint", "--format", "text"])
+        .write_stdin(AVRO_SCHEMA);
+    cmd.assert().code(1).stdout(predicate::str::contains(
+        "map_value_record: labels: map values are 'string', not a record",
+    ));
+}
+
+#[test]
+fn test_lint_inferred_schema() {
+    let data = schema_file(r#"{"id": "Q1", "count": 3}"#);
+    let mut infer = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    infer.arg("--avro").arg(data.path());
+    let schema = infer.assert().success().get_output().stdout.clone();
+
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.arg("lint").write_stdin(schema);
+    cmd.assert().success();
+}
+
+#[test]
+fn test_lint_unknown_rule() {
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["lint", "--disable", "nonsense"])
+        .write_stdin("{}");
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("Unknown lint rule 'nonsense'"));
+}
@@ -0,0 +1,220 @@
+// genson-cli/tests/output.rs
+use predicates::prelude::*;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::process::{Command, Stdio};
+use tempfile::NamedTempFile;
+
+fn ndjson_rows(n: usize) -> String {
+    (0..n)
+        .map(|i| {
+            format!(
+                r#"{{"id": {}, "name": "row-{}", "tags": ["a", "b"]}}"#,
+                i, i
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[test]
+fn test_output_to_file() {
+    let out = NamedTempFile::new().unwrap();
+
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["--output", out.path().to_str().unwrap()])
+        .write_stdin(r#"{"name": "Alice", "age": 30}"#);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::is_empty())
+        .stderr(predicate::str::contains("Processed 1 JSON object(s)"));
+
+    let written = std::fs::read_to_string(out.path()).unwrap();
+    let schema: serde_json::Value = serde_json::from_str(&written).unwrap();
+    assert_eq!(schema["properties"]["age"]["type"], "integer");
+}
+
+#[test]
+fn test_output_dash_is_stdout() {
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["--normalise", "--ndjson", "--output", "-"])
+        .write_stdin("{\"a\": 1}\n{\"a\": 2}\n");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(r#"{"a":1}"#))
+        .stdout(predicate::str::contains(r#"{"a":2}"#));
+}
+
+#[test]
+fn test_normalise_into_closed_pipe_exits_quietly() {
+    let mut input = NamedTempFile::new().unwrap();
+    input.write_all(ndjson_rows(20_000).as_bytes()).unwrap();
+
+    let mut child = Command::new(assert_cmd::cargo::cargo_bin("genson-cli"))
+        .args(["--normalise", "--ndjson"])
+        .arg(input.path())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    // Behave like `| head -n 1`: read one line, then hang up
+    let mut stdout = BufReader::new(child.stdout.take().unwrap());
+    let mut first = String::new();
+    stdout.read_line(&mut first).unwrap();
+    assert!(first.contains("\"row-0\""));
+    drop(stdout);
+
+    let mut stderr = String::new();
+    child
+        .stderr
+        .take()
+        .unwrap()
+        .read_to_string(&mut stderr)
+        .unwrap();
+    let status = child.wait().unwrap();
+
+    assert!(
+        status.success(),
+        "exit status: {:?}, stderr: {}",
+        status,
+        stderr
+    );
+    assert!(!stderr.contains("panicked"), "stderr: {}", stderr);
+    assert!(!stderr.contains("Broken pipe"), "stderr: {}", stderr);
+}
+
+#[test]
+fn test_output_leaves_no_temporary_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("schema.json");
+
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["-o", path.to_str().unwrap()])
+        .write_stdin(r#"{"name": "Alice"}"#);
+    cmd.assert().success();
+
+    let entries: Vec<_> = std::fs::read_dir(dir.path())
+        .unwrap()
+        .map(|e| e.unwrap().file_name())
+        .collect();
+    assert_eq!(entries, vec!["schema.json"]);
+}
+
+#[test]
+fn test_failed_run_keeps_existing_output() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("schema.json");
+    std::fs::write(&path, "previous").unwrap();
+
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["-o", path.to_str().unwrap()])
+        .write_stdin(r#"{"hello":"world}"#);
+    cmd.assert().code(2);
+
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "previous");
+    assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+}
+
+#[test]
+fn test_quiet_suppresses_processed_count() {
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.arg("--quiet").write_stdin(r#"{"name": "Alice"}"#);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("\"properties\""))
+        .stderr(predicate::str::is_empty());
+}
+
+#[test]
+fn test_exit_codes() {
+    // Invalid input JSON
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.write_stdin(r#"{"hello":"world}"#);
+    cmd.assert()
+        .code(2)
+        .stderr(predicate::str::contains("Invalid JSON input"));
+
+    // Invalid arguments
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.arg("--output");
+    cmd.assert()
+        .code(1)
+        .stderr(predicate::str::contains("Missing value for --output"));
+
+    // A union under --strict
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["--ndjson", "--strict"])
+        .write_stdin("{\"id\": 1}\n{\"id\": \"2\"}\n");
+    cmd.assert()
+        .code(5)
+        .stderr(predicate::str::contains("/id: union of integer and string"));
+}
+
+#[test]
+fn test_unknown_fields_policy() {
+    let mut schema = NamedTempFile::new().unwrap();
+    schema
+        .write_all(
+            br#"{"type": "record", "name": "item", "fields": [{"name": "id", "type": "string"}]}"#,
+        )
+        .unwrap();
+    let schema_path = schema.path().to_str().unwrap();
+    let input = "{\"id\": \"a\"}\n{\"id\": \"b\", \"extra\": 1}\n";
+
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["--ndjson", "--avro-schema", schema_path])
+        .write_stdin(input);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(r#"{"id":"b"}"#));
+
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["--ndjson", "--avro-schema", schema_path])
+        .args(["--unknown-fields", "keep"])
+        .write_stdin(input);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(r#"{"id":"b","extra":1}"#));
+
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["--ndjson", "--avro-schema", schema_path])
+        .args(["--unknown-fields", "error"])
+        .write_stdin(input);
+    cmd.assert().code(1).stderr(predicate::str::contains(
+        "Row 2: Unknown field 'extra' is not in the schema",
+    ));
+}
+
+#[test]
+fn test_coercion_report() {
+    let dir = tempfile::tempdir().unwrap();
+    let report_path = dir.path().join("report.json");
+
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args([
+        "--normalise",
+        "--ndjson",
+        "--coerce-strings",
+        "--keep-empty",
+    ])
+    .args(["--coercion-report", report_path.to_str().unwrap()])
+    .write_stdin("{\"id\": 1, \"tags\": [\"a\"]}\n{\"id\": 2, \"tags\": \"b\"}\n");
+    cmd.assert().success();
+
+    let report: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&report_path).unwrap()).unwrap();
+    assert_eq!(
+        report,
+        serde_json::json!([
+            {"row": 1, "path": "tags", "original": "string", "action": "wrapped_in_array"}
+        ])
+    );
+
+    // Only meaningful when normalising
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["--coercion-report", report_path.to_str().unwrap()])
+        .write_stdin(r#"{"id": 1}"#);
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("--coercion-report requires"));
+}
@@ -0,0 +1,44 @@
+// genson-cli/tests/profile_json.rs
+use predicates::prelude::*;
+use tempfile::NamedTempFile;
+
+#[test]
+fn test_profile_json_written() {
+    let profile = NamedTempFile::new().unwrap();
+
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args([
+        "--ndjson",
+        "--avro",
+        "--profile-json",
+        profile.path().to_str().unwrap(),
+    ])
+    .write_stdin("{\"a\": 1}\n{\"a\": 2}\n{\"b\": \"x\"}\n");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("\"fields\""));
+
+    let written = std::fs::read_to_string(profile.path()).unwrap();
+    let profile: serde_json::Value = serde_json::from_str(&written).unwrap();
+    for key in [
+        "parse_ms",
+        "build_ms",
+        "rewrite_objects_ms",
+        "avro_ms",
+        "total_ms",
+        "document_p50_ms",
+        "document_p99_ms",
+    ] {
+        assert!(profile[key].as_f64().unwrap() >= 0.0, "{key}");
+    }
+    assert_eq!(profile["documents"], 1);
+}
+
+#[test]
+fn test_profile_json_missing_value() {
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.arg("--profile-json").write_stdin("{}");
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("Missing value for --profile-json"));
+}
@@ -0,0 +1,95 @@
+// genson-cli/tests/registry.rs
+use predicates::prelude::*;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::thread::{self, JoinHandle};
+
+/// Serve one canned `(status, body)` response per connection, returning the
+/// request lines received.
+fn serve(responses: Vec<(u16, &'static str)>) -> (String, JoinHandle<Vec<String>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let handle = thread::spawn(move || {
+        responses
+            .into_iter()
+            .map(|(status, body)| {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                let mut content_length = 0;
+                loop {
+                    let mut header = String::new();
+                    reader.read_line(&mut header).unwrap();
+                    if header == "\r\n" {
+                        break;
+                    }
+                    if let Some(len) = header.to_ascii_lowercase().strip_prefix("content-length:")
+                    {
+                        content_length = len.trim().parse().unwrap();
+                    }
+                }
+                let mut payload = vec![0; content_length];
+                reader.read_exact(&mut payload).unwrap();
+                write!(
+                    stream,
+                    "HTTP/1.1 {} Status\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                )
+                .unwrap();
+                request_line.trim_end().to_string()
+            })
+            .collect()
+    });
+    (url, handle)
+}
+
+#[test]
+fn test_register_schema() {
+    let (url, server) = serve(vec![
+        (200, r#"{"is_compatible": true}"#),
+        (200, r#"{"id": 3}"#),
+    ]);
+
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["--register-schema", &url, "--topic", "orders"])
+        .write_stdin(r#"{"id": 1}"#);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(r#""type": "record""#))
+        .stderr(predicate::str::contains(
+            "Registered schema under subject orders-value with id 3",
+        ));
+
+    assert_eq!(
+        server.join().unwrap(),
+        [
+            "POST /compatibility/subjects/orders-value/versions/latest HTTP/1.1",
+            "POST /subjects/orders-value/versions HTTP/1.1",
+        ]
+    );
+}
+
+#[test]
+fn test_register_incompatible_schema() {
+    let (url, _server) = serve(vec![(200, r#"{"is_compatible": false}"#)]);
+
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["--register-schema", &url, "--subject", "orders-value"])
+        .write_stdin(r#"{"id": 1}"#);
+    cmd.assert()
+        .code(4)
+        .stderr(predicate::str::contains("not compatible"));
+}
+
+#[test]
+fn test_register_requires_subject() {
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["--register-schema", "http://localhost:8081"])
+        .write_stdin(r#"{"id": 1}"#);
+    cmd.assert()
+        .code(1)
+        .stderr(predicate::str::contains("requires --subject or --topic"));
+}
@@ -0,0 +1,104 @@
+// genson-cli/tests/uncovered.rs
+use predicates::prelude::*;
+use std::io::Write;
+use tempfile::NamedTempFile;
+
+const REFERENCE: &str = r#"{
+    "type": "object",
+    "properties": {
+        "id": {"type": "integer"},
+        "owner": {"type": "object", "properties": {"name": {"type": "string"}}},
+        "labels": {"type": "object", "additionalProperties": {"type": "string"}}
+    }
+}"#;
+
+fn file_with(content: &str) -> NamedTempFile {
+    let mut file = NamedTempFile::new().unwrap();
+    file.write_all(content.as_bytes()).unwrap();
+    file
+}
+
+fn uncovered_cmd(schema: &NamedTempFile) -> assert_cmd::Command {
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.args(["uncovered", "--schema", schema.path().to_str().unwrap()]);
+    cmd
+}
+
+#[test]
+fn test_uncovered_reports_new_fields() {
+    let schema = file_with(REFERENCE);
+    let data = file_with(concat!(
+        r#"{"id": 1, "owner": {"name": "a", "email": "a@x"}, "labels": {"en": "A"}}"#,
+        "\n",
+        r#"{"id": 2, "status": "new", "owner": {"email": "b@x"}}"#,
+        "\n",
+    ));
+
+    let output = uncovered_cmd(&schema)
+        .arg(data.path())
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains(
+            "Found 2 uncovered field(s) in 2 JSON object(s)",
+        ))
+        .get_output()
+        .stdout
+        .clone();
+
+    let report: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(
+        report,
+        serde_json::json!([
+            {"path": "owner.email", "count": 2, "examples": ["a@x", "b@x"]},
+            {"path": "status", "count": 1, "examples": ["new"]}
+        ])
+    );
+}
+
+#[test]
+fn test_uncovered_clean_data_succeeds() {
+    let schema = file_with(REFERENCE);
+    uncovered_cmd(&schema)
+        .write_stdin(r#"[{"id": 1}, {"labels": {"fr": "B"}}]"#)
+        .assert()
+        .success()
+        .stdout("[]\n")
+        .stderr(predicate::str::contains(
+            "Found 0 uncovered field(s) in 2 JSON object(s)",
+        ));
+}
+
+#[test]
+fn test_uncovered_text_format_and_max_examples() {
+    let schema = file_with(REFERENCE);
+    uncovered_cmd(&schema)
+        .args(["--format", "text", "--max-examples", "1"])
+        .write_stdin("{\"x\": 1}\n{\"x\": 2}\n")
+        .assert()
+        .code(1)
+        .stdout("x: seen 2 time(s), e.g. 1\n");
+}
+
+#[test]
+fn test_uncovered_against_avro_schema() {
+    let schema = file_with(
+        r#"{"type": "record", "name": "doc", "fields": [{"name": "id", "type": "long"}]}"#,
+    );
+    uncovered_cmd(&schema)
+        .write_stdin(r#"{"id": 1, "extra": true}"#)
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains(r#""path": "extra""#));
+}
+
+#[test]
+fn test_uncovered_requires_schema() {
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.arg("uncovered")
+        .write_stdin("{}")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "uncovered requires --schema <SCHEMA>",
+        ));
+}
@@ -0,0 +1,88 @@
+// genson-cli/tests/variants.rs
+use predicates::prelude::*;
+
+const MIXED: &str = concat!(
+    r#"{"user": {"name": "a"}}"#,
+    "\n",
+    r#"{"order": {"id": 1, "items": ["x"]}}"#,
+    "\n",
+    r#"{"user": {"name": "b"}}"#,
+    "\n",
+    r#"{"metric": 0.5}"#,
+    "\n",
+    r#"{"user": {"name": "c"}}"#,
+    "\n",
+);
+
+fn variants_cmd() -> assert_cmd::Command {
+    let mut cmd = assert_cmd::Command::cargo_bin("genson-cli").unwrap();
+    cmd.arg("variants");
+    cmd
+}
+
+#[test]
+fn test_variants_reports_most_common_first() {
+    let output = variants_cmd()
+        .args(["--top", "2"])
+        .write_stdin(MIXED)
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "Found 3 variant(s) in 5 JSON object(s), showing 2",
+        ))
+        .get_output()
+        .stdout
+        .clone();
+
+    let report: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(
+        report,
+        serde_json::json!([
+            {
+                "paths": {"user": "object", "user.name": "string"},
+                "count": 3,
+                "examples": [{"user": {"name": "a"}}]
+            },
+            {
+                "paths": {
+                    "order": "object",
+                    "order.id": "integer",
+                    "order.items": "array",
+                    "order.items[]": "string"
+                },
+                "count": 1,
+                "examples": [{"order": {"id": 1, "items": ["x"]}}]
+            }
+        ])
+    );
+}
+
+#[test]
+fn test_variants_text_format() {
+    variants_cmd()
+        .args(["--format", "text", "--top", "1", "--max-examples", "0"])
+        .write_stdin(MIXED)
+        .assert()
+        .success()
+        .stdout("#1: 3 row(s) (60.0%)\n    user: object\n    user.name: string\n");
+}
+
+#[test]
+fn test_variants_outer_array_is_a_stream() {
+    variants_cmd()
+        .args(["--format", "text"])
+        .write_stdin(r#"[{"a": 1}, {"a": 2}]"#)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("#1: 2 row(s) (100.0%)"));
+}
+
+#[test]
+fn test_variants_invalid_top() {
+    variants_cmd()
+        .args(["--top", "many"])
+        .write_stdin("{}")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid value for --top: many"));
+}
@@ -0,0 +1,106 @@
+//! Peak memory of inferring from a wide string column, copied into owned
+//! `String`s (as the Python plugin used to) versus borrowed as `&str` slices of
+//! the column's buffer (as it does now).
+//!
+//! Peak RSS can't be reset within a process, so each variant runs in a child
+//! process of this binary and reports its own high-water mark:
+//!
+//! ```text
+//! cargo bench -p genson-core --bench column_memory
+//! ```
+use genson_core::{infer_json_schema_from_strings, SchemaInferenceConfig};
+use std::hint::black_box;
+use std::process::Command;
+
+const VARIANT_VAR: &str = "GENSON_BENCH_COLUMN_VARIANT";
+const VARIANTS: [&str; 2] = ["copied", "borrowed"];
+
+/// One contiguous buffer with row offsets, laid out like an Arrow string array.
+struct StringColumn {
+    values: String,
+    offsets: Vec<usize>,
+}
+
+impl StringColumn {
+    fn wide_rows(rows: usize, payload_len: usize) -> Self {
+        let payload = "x".repeat(payload_len);
+        let mut values = String::new();
+        let mut offsets = vec![0];
+        for i in 0..rows {
+            values.push_str(&format!(
+                r#"{{"id": {}, "payload": "{}", "tags": ["a", "b"], "score": {}.5}}"#,
+                i, payload, i
+            ));
+            offsets.push(values.len());
+        }
+        Self { values, offsets }
+    }
+
+    fn rows(&self) -> impl Iterator<Item = &str> {
+        self.offsets.windows(2).map(|w| &self.values[w[0]..w[1]])
+    }
+}
+
+/// Peak resident set size of this process, in bytes.
+fn peak_rss_bytes() -> Option<usize> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    let line = status.lines().find(|line| line.starts_with("VmHWM:"))?;
+    let kb: usize = line.split_whitespace().nth(1)?.parse().ok()?;
+    Some(kb * 1024)
+}
+
+fn run_variant(variant: &str, quick: bool) {
+    let (rows, payload_len) = if quick { (200, 256) } else { (20_000, 2048) };
+    let column = StringColumn::wide_rows(rows, payload_len);
+    let config = SchemaInferenceConfig::default();
+    let result = match variant {
+        "copied" => {
+            let strings: Vec<String> = column.rows().map(str::to_string).collect();
+            infer_json_schema_from_strings(black_box(&strings), config)
+        }
+        "borrowed" => {
+            let strs: Vec<&str> = column.rows().collect();
+            infer_json_schema_from_strings(black_box(&strs), config)
+        }
+        other => panic!("Unknown variant {}", other),
+    };
+    black_box(result.unwrap());
+    println!(
+        "{} {} {}",
+        variant,
+        column.values.len(),
+        peak_rss_bytes().unwrap_or(0)
+    );
+}
+
+fn main() {
+    let quick = std::env::args().any(|arg| arg == "--test");
+    if let Ok(variant) = std::env::var(VARIANT_VAR) {
+        run_variant(&variant, quick);
+        return;
+    }
+
+    let exe = std::env::current_exe().expect("Failed to locate the bench binary");
+    for variant in VARIANTS {
+        let output = Command::new(&exe)
+            .args(std::env::args().skip(1))
+            .env(VARIANT_VAR, variant)
+            .output()
+            .expect("Failed to run the bench variant");
+        assert!(
+            output.status.success(),
+            "{} variant failed: {}",
+            variant,
+            String::from_utf8_lossy(&output.stderr)
+        );
+        let report = String::from_utf8_lossy(&output.stdout);
+        let fields: Vec<&str> = report.split_whitespace().collect();
+        let mb = |bytes: &str| bytes.parse::<f64>().unwrap_or(0.0) / (1024.0 * 1024.0);
+        println!(
+            "column_memory/{:<8} column {:>7.1} MB, peak RSS {:>7.1} MB",
+            variant,
+            mb(fields[1]),
+            mb(fields[2])
+        );
+    }
+}
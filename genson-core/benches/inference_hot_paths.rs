@@ -0,0 +1,98 @@
+//! End-to-end inference over the checked-in fixtures in `benches/fixtures`, so
+//! regressions in map detection (`rewrite_objects`) and record unification show
+//! up as numbers before a release:
+//!
+//! - `flat_objects`: 20 rows of ~250 mixed scalar keys, some optional
+//! - `deep_nesting`: 60 rows nested 40 levels deep, with arrays every 5 levels
+//! - `map_heavy`: Wikidata-style label, alias and claim maps
+//! - `unification`: maps of records with overlapping, differently typed fields,
+//!   inferred with `unify_maps`
+//! - `string_column`: `flat_objects` read from one buffer, as owned copies of the
+//!   rows versus borrowed slices (see the `column_memory` bench for peak memory)
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use genson_core::{infer_json_schema_from_strings, SchemaInferenceConfig};
+use std::hint::black_box;
+
+fn fixture(name: &str) -> Vec<String> {
+    let path = format!("{}/benches/fixtures/{}", env!("CARGO_MANIFEST_DIR"), name);
+    std::fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("Failed to read {}: {}", path, e))
+        .lines()
+        .map(str::to_string)
+        .collect()
+}
+
+fn bench_fixture(c: &mut Criterion, name: &str, config: SchemaInferenceConfig) {
+    let rows = fixture(&format!("{}.ndjson", name));
+    let bytes: usize = rows.iter().map(String::len).sum();
+
+    let mut group = c.benchmark_group(name);
+    group.throughput(Throughput::Bytes(bytes as u64));
+    group.bench_function("infer_schema", |b| {
+        b.iter(|| infer_json_schema_from_strings(black_box(&rows), config.clone()).unwrap())
+    });
+    group.finish();
+}
+
+fn bench_flat_objects(c: &mut Criterion) {
+    bench_fixture(c, "flat_objects", SchemaInferenceConfig::default());
+}
+
+fn bench_deep_nesting(c: &mut Criterion) {
+    bench_fixture(c, "deep_nesting", SchemaInferenceConfig::default());
+}
+
+fn bench_map_heavy(c: &mut Criterion) {
+    let config = SchemaInferenceConfig {
+        map_threshold: 5,
+        ..Default::default()
+    };
+    bench_fixture(c, "map_heavy", config);
+}
+
+fn bench_unification(c: &mut Criterion) {
+    let config = SchemaInferenceConfig {
+        map_threshold: 5,
+        unify_maps: true,
+        ..Default::default()
+    };
+    bench_fixture(c, "unification", config);
+}
+
+fn bench_string_column(c: &mut Criterion) {
+    let rows = fixture("flat_objects.ndjson");
+    let column = rows.concat();
+    let mut offsets = vec![0];
+    offsets.extend(rows.iter().scan(0, |end, row| {
+        *end += row.len();
+        Some(*end)
+    }));
+    let slices = || offsets.windows(2).map(|w| &column[w[0]..w[1]]);
+
+    let mut group = c.benchmark_group("string_column");
+    group.throughput(Throughput::Bytes(column.len() as u64));
+    group.bench_function("copied", |b| {
+        b.iter(|| {
+            let strings: Vec<String> = slices().map(str::to_string).collect();
+            infer_json_schema_from_strings(black_box(&strings), Default::default()).unwrap()
+        })
+    });
+    group.bench_function("borrowed", |b| {
+        b.iter(|| {
+            let strs: Vec<&str> = slices().collect();
+            infer_json_schema_from_strings(black_box(&strs), Default::default()).unwrap()
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_flat_objects,
+    bench_deep_nesting,
+    bench_map_heavy,
+    bench_unification,
+    bench_string_column,
+);
+
+criterion_main!(benches);
@@ -0,0 +1,806 @@
+//! Conversion between Avro schemas and JSON Schema.
+//!
+//! Converts an existing Avro schema (e.g. a registry-managed `.avsc`) into the
+//! JSON Schema shape produced by inference, so externally-owned schemas can drive
+//! the same downstream steps as inferred ones, such as normalisation. The other
+//! way, a JSON Schema saved from an earlier inference run can be converted to
+//! Avro without inferring it again.
+
+use crate::schema::annotate::annotation_docs;
+use crate::schema::discriminators::merge_tagged_unions;
+use crate::schema::draft::{apply_draft, JsonSchemaDraft};
+use crate::schema::field_path::{is_path_pattern, key_matches, pointer, MAP_VALUES_SEGMENT};
+use crate::schema::tuples::tuples_to_records;
+use crate::schema::{
+    SchemaInferenceConfig, SchemaInferenceResult, FORCED_TYPE_KEY, MAP_KEY_TYPE_KEY,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::collections::{HashMap, HashSet};
+
+/// Parse an Avro schema document and convert it to JSON Schema.
+pub fn avro_str_to_json_schema(avsc: &str) -> Result<Value, String> {
+    let avro: Value =
+        serde_json::from_str(avsc).map_err(|e| format!("Invalid Avro schema JSON: {}", e))?;
+    avro_to_json_schema(&avro)
+}
+
+/// Convert an Avro schema to the JSON Schema representation used internally.
+///
+/// - records become objects whose non-nullable fields are `required`
+/// - maps become objects with `additionalProperties`, arrays keep their `items`
+/// - `int`/`long` become `integer`, `float`/`double` become `number`
+/// - `bytes`, `fixed` and `enum` become `string`; logical types use their underlying type
+/// - `["null", T]` unions become nullable types, other unions become `anyOf`
+///
+/// Named types may be referenced by short or fully-qualified name after their
+/// definition. Recursive types cannot be represented and are rejected.
+pub fn avro_to_json_schema(avro: &Value) -> Result<Value, String> {
+    let mut converter = Converter::default();
+    converter.convert(avro, "")
+}
+
+/// Load an Avro schema for normalisation.
+///
+/// The schema goes through the internal JSON Schema representation and back, so
+/// the normaliser sees the same Avro shape it gets from an inferred schema.
+pub fn normalisation_schema_from_avro(avsc: &str) -> Result<Value, String> {
+    let avro: Value =
+        serde_json::from_str(avsc).map_err(|e| format!("Invalid Avro schema JSON: {}", e))?;
+    let schema = avro_to_json_schema(&avro)?;
+    let mut normalisation_schema = SchemaInferenceResult {
+        schema,
+        processed_count: 0,
+        limit_reached: None,
+        profile: None,
+        explanation: None,
+        decision_log: None,
+        defaulted_fields: Vec::new(),
+        presence: None,
+        key_collisions: Vec::new(),
+        map_key_stats: None,
+    }
+    .to_avro_schema("genson", Some(""), Some(""), false);
+    // The JSON Schema is keyed by the JSON keys, so put the Avro names back
+    let renames = field_renames(&avro);
+    if !renames.is_empty() {
+        restore_renames(&mut normalisation_schema, &renames, &mut Vec::new());
+    }
+    Ok(normalisation_schema)
+}
+
+/// Root record name given to Avro schemas converted from JSON Schema.
+pub const DEFAULT_AVRO_NAME: &str = "document";
+
+/// Namespace given to Avro schemas converted from JSON Schema.
+pub const DEFAULT_AVRO_NAMESPACE: &str = "genson";
+
+/// Naming and documentation of an Avro schema converted from JSON Schema.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AvroOptions {
+    /// Namespace of the root record, and the prefix of its nested records'
+    /// namespaces (default: `genson`)
+    pub namespace: String,
+    /// Name of the root record (default: `document`)
+    pub name: String,
+    /// Descriptions to add as the `doc` of fields, keyed by bare field name or
+    /// path pattern (see `SchemaInferenceConfig::force_field_types`)
+    pub docs: HashMap<String, String>,
+    /// How to write field names that aren't valid Avro names (default: keep them)
+    pub field_names: AvroNameStrategy,
+    /// Give nullable fields `"default": null`, so records written without them can
+    /// be read (default: true)
+    pub null_defaults: bool,
+}
+
+impl Default for AvroOptions {
+    fn default() -> Self {
+        Self {
+            namespace: DEFAULT_AVRO_NAMESPACE.to_string(),
+            name: DEFAULT_AVRO_NAME.to_string(),
+            docs: HashMap::new(),
+            field_names: AvroNameStrategy::Keep,
+            null_defaults: true,
+        }
+    }
+}
+
+impl AvroOptions {
+    /// The Avro naming and docs set on an inference config. Annotated descriptions
+    /// are docs too, though `avro_docs` wins for the same key.
+    pub fn from_config(config: &SchemaInferenceConfig) -> Self {
+        let defaults = Self::default();
+        Self {
+            namespace: config.avro_namespace.clone().unwrap_or(defaults.namespace),
+            name: config.avro_root_name.clone().unwrap_or(defaults.name),
+            docs: annotation_docs(&config.annotations)
+                .chain(&config.avro_docs)
+                .map(|(key, doc)| (key.clone(), doc.clone()))
+                .collect(),
+            field_names: config.avro_field_names,
+            null_defaults: config.avro_defaults,
+        }
+    }
+
+    /// Check that the name and namespace are valid Avro names.
+    pub fn validate(&self) -> Result<(), String> {
+        if !is_avro_name(&self.name) {
+            return Err(format!("Invalid Avro name: {}", self.name));
+        }
+        if !self.namespace.is_empty() && !self.namespace.split('.').all(is_avro_name) {
+            return Err(format!("Invalid Avro namespace: {}", self.namespace));
+        }
+        Ok(())
+    }
+}
+
+/// Convert a JSON Schema, such as one saved from an earlier inference run, to Avro.
+///
+/// This is the conversion inference does with `avro: true`, so a schema saved
+/// with any draft's keywords converts as it would have been inferred. Nested
+/// records are namespaced `{namespace}.{name}_types`.
+///
+/// # Errors
+/// Returns error if the schema isn't a JSON object, or the name or namespace
+/// isn't a valid Avro name.
+pub fn json_schema_to_avro(schema: &Value, opts: &AvroOptions) -> Result<Value, String> {
+    opts.validate()?;
+    if !schema.is_object() {
+        return Err("Expected a JSON Schema object".to_string());
+    }
+    let mut schema = schema.clone();
+    // Tuples as `items` arrays, whichever draft the schema was written with
+    apply_draft(&mut schema, JsonSchemaDraft::Draft07);
+    Ok(convert_json_schema(schema, opts))
+}
+
+/// Convert an inferred JSON Schema (with tuples as `items` arrays) to Avro, with
+/// options that have been validated.
+pub(crate) fn convert_json_schema(mut schema: Value, opts: &AvroOptions) -> Value {
+    tuples_to_records(&mut schema);
+    merge_tagged_unions(&mut schema);
+    strip_forced_formats(&mut schema);
+    let empty_root = schema.get("type") == Some(&json!("object"))
+        && ["properties", "additionalProperties", "anyOf"]
+            .iter()
+            .all(|key| schema.get(key).is_none());
+    let mut avro = if empty_root {
+        // The converter gives an empty root a field referring to the record itself
+        let mut record = json!({
+            "type": "record",
+            "name": DEFAULT_AVRO_NAME,
+            "namespace": opts.namespace,
+            "fields": [],
+        });
+        if opts.namespace.is_empty() {
+            record.as_object_mut().unwrap().remove("namespace");
+        }
+        record
+    } else {
+        avrotize::converter::jsons_to_avro(&schema, &opts.namespace, "", "", false)
+    };
+    restore_annotations(&schema, &mut avro);
+    if opts.null_defaults {
+        add_null_defaults(&mut avro);
+    }
+
+    if opts.name != DEFAULT_AVRO_NAME && avro.get("type") == Some(&json!("record")) {
+        avro["name"] = json!(opts.name);
+        let from = full_name(&format!("{}_types", DEFAULT_AVRO_NAME), &opts.namespace);
+        let to = full_name(&format!("{}_types", opts.name), &opts.namespace);
+        rename_namespaces(&mut avro, &from, &to);
+    }
+    if !opts.docs.is_empty() {
+        add_docs(&mut avro, &opts.docs, &mut Vec::new());
+    }
+    if opts.field_names != AvroNameStrategy::Keep {
+        sanitise_names(&mut avro, opts.field_names);
+    }
+    avro
+}
+
+/// How field names that aren't valid Avro names (`foo-bar`, `2023`, `café`) are
+/// written to an Avro schema.
+///
+/// A renamed field keeps its JSON key under [`ORIGINAL_NAME_KEY`], which
+/// normalisation reads the field's value from, so rows come out with the new name.
+/// A name that would clash with another field of its record gets a `_2`, `_3`...
+/// suffix. The names of nested records, taken from their fields, are renamed too.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AvroNameStrategy {
+    /// Leave names as they are, which makes an invalid Avro schema
+    #[default]
+    Keep,
+    /// Replace each invalid character with `_`: `foo-bar` becomes `foo_bar`
+    Replace,
+    /// Replace each invalid character with its code point in hex, so different
+    /// names stay different: `foo-bar` becomes `foo_x2D_bar`
+    Escape,
+}
+
+/// Field attribute holding the JSON key of a field renamed to a valid Avro name.
+pub const ORIGINAL_NAME_KEY: &str = "genson_original_name";
+
+/// A field renamed to be a valid Avro name.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldRename {
+    /// JSON Pointer to the field, by its JSON keys (array items add no segment
+    /// and map values add `*`)
+    pub path: String,
+    /// The field's JSON key
+    pub original: String,
+    /// The field's Avro name
+    pub name: String,
+}
+
+/// A valid Avro name for `name`; names that start with a digit get a `_` prefix.
+pub fn sanitise_avro_name(name: &str, strategy: AvroNameStrategy) -> String {
+    if strategy == AvroNameStrategy::Keep || is_avro_name(name) {
+        return name.to_string();
+    }
+    let mut sanitised = String::new();
+    for c in name.chars() {
+        match (c.is_ascii_alphanumeric() || c == '_', strategy) {
+            (true, _) => sanitised.push(c),
+            (false, AvroNameStrategy::Escape) => sanitised.push_str(&format!("_x{:X}_", c as u32)),
+            (false, _) => sanitised.push('_'),
+        }
+    }
+    if !sanitised.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_') {
+        sanitised.insert(0, '_');
+    }
+    sanitised
+}
+
+/// Rename the invalid field and record names throughout `avro`.
+fn sanitise_names(avro: &mut Value, strategy: AvroNameStrategy) {
+    match avro {
+        Value::Array(branches) => {
+            for branch in branches {
+                sanitise_names(branch, strategy);
+            }
+        }
+        Value::Object(obj) => {
+            if let Some(Value::String(name)) = obj.get_mut("name") {
+                *name = sanitise_avro_name(name, strategy);
+            }
+            if let Some(Value::Array(fields)) = obj.get_mut("fields") {
+                let names = |field: &Value| field["name"].as_str().unwrap_or_default().to_string();
+                let mut taken: HashSet<String> = fields
+                    .iter()
+                    .map(names)
+                    .filter(|name| is_avro_name(name))
+                    .collect();
+                for field in fields.iter_mut() {
+                    let original = names(field);
+                    if !is_avro_name(&original) {
+                        let sanitised = sanitise_avro_name(&original, strategy);
+                        let name = (1..)
+                            .map(|n| match n {
+                                1 => sanitised.clone(),
+                                n => format!("{}_{}", sanitised, n),
+                            })
+                            .find(|name| !taken.contains(name))
+                            .expect("a free suffix");
+                        taken.insert(name.clone());
+                        field["name"] = json!(name);
+                        field[ORIGINAL_NAME_KEY] = json!(original);
+                    }
+                    if let Some(field_type) = field.get_mut("type") {
+                        sanitise_names(field_type, strategy);
+                    }
+                }
+            }
+            for key in ["items", "values"] {
+                if let Some(child) = obj.get_mut(key) {
+                    sanitise_names(child, strategy);
+                }
+            }
+            if let Some(inner @ (Value::Object(_) | Value::Array(_))) = obj.get_mut("type") {
+                sanitise_names(inner, strategy);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Rename the fields of `avro` at the paths of `renames` back to their Avro names.
+fn restore_renames(avro: &mut Value, renames: &[FieldRename], path: &mut Vec<String>) {
+    match avro {
+        Value::Array(branches) => branches
+            .iter_mut()
+            .for_each(|b| restore_renames(b, renames, path)),
+        Value::Object(obj) => {
+            if let Some(Value::Array(fields)) = obj.get_mut("fields") {
+                for field in fields {
+                    let original = field["name"].as_str().unwrap_or_default().to_string();
+                    path.push(original.clone());
+                    let field_path = pointer(path);
+                    if let Some(rename) = renames.iter().find(|r| r.path == field_path) {
+                        field["name"] = json!(rename.name);
+                        field[ORIGINAL_NAME_KEY] = json!(original);
+                    }
+                    if let Some(field_type) = field.get_mut("type") {
+                        restore_renames(field_type, renames, path);
+                    }
+                    path.pop();
+                }
+            }
+            if let Some(items) = obj.get_mut("items") {
+                restore_renames(items, renames, path);
+            }
+            if let Some(values) = obj.get_mut("values") {
+                path.push(MAP_VALUES_SEGMENT.to_string());
+                restore_renames(values, renames, path);
+                path.pop();
+            }
+            if let Some(inner @ (Value::Object(_) | Value::Array(_))) = obj.get_mut("type") {
+                restore_renames(inner, renames, path);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Every field of `avro` renamed to be a valid Avro name, as a sidecar mapping for
+/// consumers that need to find a JSON key from its Avro name.
+pub fn field_renames(avro: &Value) -> Vec<FieldRename> {
+    fn walk(avro: &Value, path: &mut Vec<String>, renames: &mut Vec<FieldRename>) {
+        match avro {
+            Value::Array(branches) => branches.iter().for_each(|b| walk(b, path, renames)),
+            Value::Object(obj) => {
+                for field in obj
+                    .get("fields")
+                    .and_then(Value::as_array)
+                    .into_iter()
+                    .flatten()
+                {
+                    let name = field["name"].as_str().unwrap_or_default();
+                    let original = field[ORIGINAL_NAME_KEY].as_str();
+                    path.push(original.unwrap_or(name).to_string());
+                    if let Some(original) = original {
+                        renames.push(FieldRename {
+                            path: pointer(path),
+                            original: original.to_string(),
+                            name: name.to_string(),
+                        });
+                    }
+                    walk(&field["type"], path, renames);
+                    path.pop();
+                }
+                if let Some(items) = obj.get("items") {
+                    walk(items, path, renames);
+                }
+                if let Some(values) = obj.get("values") {
+                    path.push(MAP_VALUES_SEGMENT.to_string());
+                    walk(values, path, renames);
+                    path.pop();
+                }
+                if let Some(inner @ (Value::Object(_) | Value::Array(_))) = obj.get("type") {
+                    walk(inner, path, renames);
+                }
+            }
+            _ => {}
+        }
+    }
+    let mut renames = Vec::new();
+    walk(avro, &mut Vec::new(), &mut renames);
+    renames
+}
+
+/// Set the `doc` of every record field below `avro` that a key of `docs` targets.
+///
+/// Paths follow the field path conventions: array items add no segment and map
+/// values add `*`.
+fn add_docs(avro: &mut Value, docs: &HashMap<String, String>, path: &mut Vec<String>) {
+    match avro {
+        Value::Array(branches) => {
+            for branch in branches {
+                add_docs(branch, docs, path);
+            }
+        }
+        Value::Object(obj) => match obj.get("type").and_then(Value::as_str) {
+            Some("record") => {
+                let Some(Value::Array(fields)) = obj.get_mut("fields") else {
+                    return;
+                };
+                for field in fields {
+                    let Some(name) = field.get("name").and_then(Value::as_str) else {
+                        continue;
+                    };
+                    let name = name.to_string();
+                    path.push(name.clone());
+                    if let Some(doc) = field_doc(docs, &name, path) {
+                        field["doc"] = json!(doc);
+                    }
+                    if let Some(field_type) = field.get_mut("type") {
+                        add_docs(field_type, docs, path);
+                    }
+                    path.pop();
+                }
+            }
+            Some("array") => {
+                if let Some(items) = obj.get_mut("items") {
+                    add_docs(items, docs, path);
+                }
+            }
+            Some("map") => {
+                if let Some(values) = obj.get_mut("values") {
+                    path.push(MAP_VALUES_SEGMENT.to_string());
+                    add_docs(values, docs, path);
+                    path.pop();
+                }
+            }
+            _ => {}
+        },
+        _ => {}
+    }
+}
+
+/// Drop the `format` of fields forced to `date` or `date-time`, which the converter
+/// would make logical types over integers, so they stay the strings normalisation
+/// writes.
+fn strip_forced_formats(schema: &mut Value) {
+    match schema {
+        Value::Object(obj) => {
+            if obj.contains_key(FORCED_TYPE_KEY) {
+                obj.shift_remove("format");
+            }
+            obj.values_mut().for_each(strip_forced_formats);
+        }
+        Value::Array(items) => items.iter_mut().for_each(strip_forced_formats),
+        _ => {}
+    }
+}
+
+/// Give every record field whose type is a union with `null` first (the branch
+/// Avro takes a default from) `"default": null`, unless it has a default already.
+fn add_null_defaults(avro: &mut Value) {
+    match avro {
+        Value::Array(branches) => {
+            for branch in branches {
+                add_null_defaults(branch);
+            }
+        }
+        Value::Object(obj) => match obj.get("type").and_then(Value::as_str) {
+            Some("record") => {
+                let Some(Value::Array(fields)) = obj.get_mut("fields") else {
+                    return;
+                };
+                for field in fields.iter_mut().filter_map(Value::as_object_mut) {
+                    let nullable = field
+                        .get("type")
+                        .and_then(Value::as_array)
+                        .is_some_and(|branches| branches.first() == Some(&json!("null")));
+                    if nullable && !field.contains_key("default") {
+                        field.insert("default".to_string(), Value::Null);
+                    }
+                    if let Some(field_type) = field.get_mut("type") {
+                        add_null_defaults(field_type);
+                    }
+                }
+            }
+            Some("array") => {
+                if let Some(items) = obj.get_mut("items") {
+                    add_null_defaults(items);
+                }
+            }
+            Some("map") => {
+                if let Some(values) = obj.get_mut("values") {
+                    add_null_defaults(values);
+                }
+            }
+            _ => {}
+        },
+        _ => {}
+    }
+}
+
+/// The doc for the field `name` at `path`: an exact bare-name key first, then the
+/// first matching path pattern in sorted order.
+fn field_doc<'a>(
+    docs: &'a HashMap<String, String>,
+    name: &str,
+    path: &[String],
+) -> Option<&'a str> {
+    if let Some(doc) = docs.get(name) {
+        return Some(doc);
+    }
+    let mut patterns: Vec<_> = docs
+        .iter()
+        .filter(|(key, _)| is_path_pattern(key) && key_matches(key, Some(name), path))
+        .collect();
+    patterns.sort();
+    patterns.first().map(|(_, doc)| doc.as_str())
+}
+
+/// Whether `name` is a valid Avro name: `[A-Za-z_][A-Za-z0-9_]*`.
+fn is_avro_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Replace the namespace `from` (or a prefix of it) with `to` throughout `avro`.
+fn rename_namespaces(avro: &mut Value, from: &str, to: &str) {
+    match avro {
+        Value::Object(obj) => {
+            if let Some(Value::String(namespace)) = obj.get_mut("namespace") {
+                if namespace == from {
+                    *namespace = to.to_string();
+                } else if let Some(rest) = namespace.strip_prefix(&format!("{}.", from)) {
+                    *namespace = format!("{}.{}", to, rest);
+                }
+            }
+            obj.values_mut()
+                .for_each(|child| rename_namespaces(child, from, to));
+        }
+        Value::Array(items) => items
+            .iter_mut()
+            .for_each(|child| rename_namespaces(child, from, to)),
+        _ => {}
+    }
+}
+
+#[derive(Default)]
+struct Converter {
+    /// Converted named types, keyed by both short and full name
+    named: HashMap<String, Value>,
+    /// Full names of the named types currently being converted
+    in_progress: Vec<String>,
+}
+
+impl Converter {
+    fn convert(&mut self, avro: &Value, namespace: &str) -> Result<Value, String> {
+        match avro {
+            Value::String(name) => self.convert_name(name, namespace),
+            Value::Array(branches) => self.convert_union(branches, namespace),
+            Value::Object(obj) => match obj.get("type") {
+                Some(Value::String(t)) => match t.as_str() {
+                    "record" | "error" => self.convert_record(obj, namespace),
+                    "enum" | "fixed" => {
+                        self.define(obj, namespace, |_| Ok(json!({"type": "string"})))
+                    }
+                    "array" => {
+                        let items = obj.get("items").ok_or("Avro array is missing \"items\"")?;
+                        Ok(json!({"type": "array", "items": self.convert(items, namespace)?}))
+                    }
+                    "map" => {
+                        let values = obj.get("values").ok_or("Avro map is missing \"values\"")?;
+                        let mut map = json!({
+                            "type": "object",
+                            "additionalProperties": self.convert(values, namespace)?
+                        });
+                        if let Some(key_type) = obj.get(MAP_KEY_TYPE_KEY) {
+                            map[MAP_KEY_TYPE_KEY] = key_type.clone();
+                        }
+                        Ok(map)
+                    }
+                    // Primitive with attributes, e.g. a logical type
+                    other => self.convert_name(other, namespace),
+                },
+                // {"type": {...}} or {"type": [...]} wraps another schema
+                Some(inner) => self.convert(inner, namespace),
+                None => Err("Avro schema object is missing \"type\"".to_string()),
+            },
+            other => Err(format!("Invalid Avro schema: {}", other)),
+        }
+    }
+
+    fn convert_name(&self, name: &str, namespace: &str) -> Result<Value, String> {
+        let json_type = match name {
+            "null" => "null",
+            "boolean" => "boolean",
+            "int" | "long" => "integer",
+            "float" | "double" => "number",
+            "string" | "bytes" => "string",
+            _ => {
+                let full = full_name(name, namespace);
+                if self.in_progress.contains(&full) {
+                    return Err(format!("Recursive Avro type {} is not supported", full));
+                }
+                return self
+                    .named
+                    .get(&full)
+                    .or_else(|| self.named.get(name))
+                    .cloned()
+                    .ok_or_else(|| format!("Unknown Avro type: {}", name));
+            }
+        };
+        Ok(json!({ "type": json_type }))
+    }
+
+    fn convert_union(&mut self, branches: &[Value], namespace: &str) -> Result<Value, String> {
+        let mut nullable = false;
+        let mut converted = Vec::new();
+        for branch in branches {
+            if branch == "null" {
+                nullable = true;
+            } else {
+                converted.push(self.convert(branch, namespace)?);
+            }
+        }
+        match converted.len() {
+            0 => Ok(json!({"type": "null"})),
+            1 if nullable => Ok(make_nullable(converted.pop().unwrap())),
+            1 => Ok(converted.pop().unwrap()),
+            _ => {
+                if nullable {
+                    converted.insert(0, json!({"type": "null"}));
+                }
+                Ok(json!({ "anyOf": converted }))
+            }
+        }
+    }
+
+    fn convert_record(
+        &mut self,
+        obj: &Map<String, Value>,
+        namespace: &str,
+    ) -> Result<Value, String> {
+        self.define(obj, namespace, |converter| {
+            let record_namespace = obj
+                .get("namespace")
+                .and_then(Value::as_str)
+                .unwrap_or(namespace)
+                .to_string();
+            let fields = obj
+                .get("fields")
+                .and_then(Value::as_array)
+                .ok_or("Avro record is missing \"fields\"")?;
+
+            let mut properties = Map::new();
+            let mut required = Vec::new();
+            for field in fields {
+                let name = field
+                    .get("name")
+                    .and_then(Value::as_str)
+                    .ok_or("Avro record field is missing \"name\"")?;
+                // Properties are keyed by the field's JSON key
+                let key = field
+                    .get(ORIGINAL_NAME_KEY)
+                    .and_then(Value::as_str)
+                    .unwrap_or(name);
+                let field_type = field
+                    .get("type")
+                    .ok_or_else(|| format!("Avro field {} is missing \"type\"", name))?;
+                let converted = converter.convert(field_type, &record_namespace)?;
+                if !is_nullable(&converted) {
+                    required.push(json!(key));
+                }
+                properties.insert(key.to_string(), converted);
+            }
+
+            let mut schema = json!({"type": "object", "properties": properties});
+            if !required.is_empty() {
+                schema["required"] = Value::Array(required);
+            }
+            Ok(schema)
+        })
+    }
+
+    /// Convert a named type and register it for later references.
+    fn define(
+        &mut self,
+        obj: &Map<String, Value>,
+        namespace: &str,
+        body: impl FnOnce(&mut Self) -> Result<Value, String>,
+    ) -> Result<Value, String> {
+        let name = obj
+            .get("name")
+            .and_then(Value::as_str)
+            .ok_or("Avro named type is missing \"name\"")?;
+        let type_namespace = obj
+            .get("namespace")
+            .and_then(Value::as_str)
+            .unwrap_or(namespace);
+        let full = full_name(name, type_namespace);
+
+        self.in_progress.push(full.clone());
+        let converted = body(self);
+        self.in_progress.pop();
+        let converted = converted?;
+
+        let short = name.rsplit('.').next().unwrap_or(name).to_string();
+        self.named.insert(full, converted.clone());
+        self.named.entry(short).or_insert_with(|| converted.clone());
+        Ok(converted)
+    }
+}
+
+/// Put back the annotations the converter drops: the forced type of fields and the
+/// `x-key-type` of maps.
+fn restore_annotations(schema: &Value, avro: &mut Value) {
+    match avro {
+        Value::Array(branches) => {
+            for branch in branches {
+                let kind = match branch.get("type").and_then(Value::as_str) {
+                    Some("record" | "map") => "object",
+                    Some("array") => "array",
+                    _ => continue,
+                };
+                let json_branch = schema
+                    .get("anyOf")
+                    .and_then(Value::as_array)
+                    .and_then(|bs| bs.iter().find(|b| b["type"] == kind))
+                    .unwrap_or(schema);
+                restore_annotations(json_branch, branch);
+            }
+        }
+        Value::Object(obj) => match obj.get("type").and_then(Value::as_str) {
+            Some("record") => {
+                let Some(Value::Array(fields)) = obj.get_mut("fields") else {
+                    return;
+                };
+                for field in fields {
+                    let Some(name) = field.get("name").and_then(Value::as_str) else {
+                        continue;
+                    };
+                    if let Some(prop) = schema.get("properties").and_then(|p| p.get(name)) {
+                        // The converter drops the forced type of a field
+                        if let Some(forced) = prop.get(FORCED_TYPE_KEY) {
+                            field[FORCED_TYPE_KEY] = forced.clone();
+                        }
+                        restore_annotations(prop, &mut field["type"]);
+                    }
+                }
+            }
+            Some("array") => {
+                if let (Some(items), Some(child)) = (schema.get("items"), obj.get_mut("items")) {
+                    restore_annotations(items, child);
+                }
+            }
+            Some("map") => {
+                // The converter drops the key type annotation too
+                if let Some(key_type) = schema.get(MAP_KEY_TYPE_KEY) {
+                    obj.insert(MAP_KEY_TYPE_KEY.to_string(), key_type.clone());
+                }
+                let json_values = schema.get("additionalProperties");
+                if let (Some(values), Some(child)) = (json_values, obj.get_mut("values")) {
+                    restore_annotations(values, child);
+                }
+            }
+            _ => {}
+        },
+        _ => {}
+    }
+}
+
+fn full_name(name: &str, namespace: &str) -> String {
+    if name.contains('.') || namespace.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}.{}", namespace, name)
+    }
+}
+
+fn is_nullable(schema: &Value) -> bool {
+    match schema.get("type") {
+        Some(Value::String(t)) => t == "null",
+        Some(Value::Array(types)) => types.iter().any(|t| t == "null"),
+        _ => schema
+            .get("anyOf")
+            .and_then(Value::as_array)
+            .is_some_and(|branches| branches.iter().any(|b| b["type"] == "null")),
+    }
+}
+
+/// Allow `null` alongside a converted schema, in the shape inference produces.
+fn make_nullable(schema: Value) -> Value {
+    match schema.get("type") {
+        Some(Value::String(t)) if t != "object" && t != "array" => {
+            let mut schema = schema.clone();
+            schema["type"] = json!(["null", t]);
+            schema
+        }
+        _ => json!({"anyOf": [{"type": "null"}, schema]}),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    include!("tests/avro.rs");
+}
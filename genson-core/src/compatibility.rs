@@ -0,0 +1,258 @@
+//! Schema evolution checks: whether data written with one schema can be read with
+//! another, following Avro's resolution rules.
+//!
+//! Schemas are compared in the JSON Schema shape inference produces; Avro schemas
+//! (with the `avro` feature) are converted to it first. A field that isn't
+//! `required`, or whose type includes `null`, is nullable, which is what gives it
+//! `"default": null` in Avro. Integers can be read as numbers (Avro's `int` to
+//! `double` promotion), and fields the reader doesn't know are skipped.
+
+use crate::conformity::{display, join_path};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::borrow::Cow;
+
+/// Which way data must be readable between an old and a new schema.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CompatibilityMode {
+    /// The new schema reads data written with the old one
+    #[default]
+    Backward,
+    /// The old schema reads data written with the new one
+    Forward,
+    /// Both backward and forward
+    Full,
+}
+
+/// A place where data written with one schema can't be read with the other.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CompatibilityIssue {
+    /// Dotted field path (`$` for the root), `*` for map values and `[]` for
+    /// array items
+    pub path: String,
+    /// The check that failed: `Backward` (new reads old) or `Forward` (old reads new)
+    pub direction: CompatibilityMode,
+    pub message: String,
+}
+
+/// The incompatibilities between `old` and `new` under `mode`; empty when they
+/// are compatible. Either schema may be JSON Schema or (with the `avro` feature)
+/// Avro.
+pub fn check_compatibility(
+    old: &Value,
+    new: &Value,
+    mode: CompatibilityMode,
+) -> Result<Vec<CompatibilityIssue>, String> {
+    let old = json_schema(old)?;
+    let new = json_schema(new)?;
+    let mut issues = Vec::new();
+    if mode != CompatibilityMode::Forward {
+        let mut check = Check::new(CompatibilityMode::Backward, &mut issues);
+        check.read(&new, false, &old, false, "");
+    }
+    if mode != CompatibilityMode::Backward {
+        let mut check = Check::new(CompatibilityMode::Forward, &mut issues);
+        check.read(&old, false, &new, false, "");
+    }
+    Ok(issues)
+}
+
+/// `schema` as JSON Schema, converting it if it's Avro.
+fn json_schema(schema: &Value) -> Result<Cow<'_, Value>, String> {
+    #[cfg(feature = "avro")]
+    if crate::lint::is_avro(schema) {
+        return crate::avro::avro_to_json_schema(schema).map(Cow::Owned);
+    }
+    Ok(Cow::Borrowed(schema))
+}
+
+/// One branch of a (possibly union) schema: its type and the schema holding the
+/// type's keywords.
+struct Branch<'a> {
+    kind: &'static str,
+    schema: &'a Value,
+}
+
+/// The branches of `schema`, with a `null` one when it's `optional`. Objects are
+/// `record`s, or `map`s when they only have `additionalProperties`. A schema
+/// without a type has no branches and accepts anything.
+fn branches(schema: &Value, optional: bool) -> Vec<Branch<'_>> {
+    let mut out = Vec::new();
+    collect_branches(schema, &mut out);
+    if optional && !out.iter().any(|b| b.kind == "null") {
+        out.push(Branch {
+            kind: "null",
+            schema,
+        });
+    }
+    out
+}
+
+fn collect_branches<'a>(schema: &'a Value, out: &mut Vec<Branch<'a>>) {
+    if let Some(Value::Array(any_of)) = schema.get("anyOf") {
+        for branch in any_of {
+            collect_branches(branch, out);
+        }
+    }
+    let names: Vec<&str> = match schema.get("type") {
+        Some(Value::String(name)) => vec![name],
+        Some(Value::Array(names)) => names.iter().filter_map(Value::as_str).collect(),
+        _ => Vec::new(),
+    };
+    for name in names {
+        let kind = match name {
+            "null" => "null",
+            "boolean" => "boolean",
+            "integer" => "integer",
+            "number" => "number",
+            "string" => "string",
+            "array" => "array",
+            "object" if is_map(schema) => "map",
+            "object" => "record",
+            _ => continue,
+        };
+        out.push(Branch { kind, schema });
+    }
+}
+
+fn is_map(schema: &Value) -> bool {
+    schema.get("properties").is_none()
+        && matches!(schema.get("additionalProperties"), Some(Value::Object(_)))
+}
+
+/// Whether a writer's values of type `writer` can be read as type `reader`.
+fn readable_as(writer: &str, reader: &str) -> bool {
+    writer == reader || (writer == "integer" && reader == "number")
+}
+
+/// Whether `schema` accepts null: a `null` type, or no type at all.
+fn nullable(schema: &Value) -> bool {
+    let branches = branches(schema, false);
+    branches.is_empty() || branches.iter().any(|b| b.kind == "null")
+}
+
+struct Check<'i> {
+    direction: CompatibilityMode,
+    issues: &'i mut Vec<CompatibilityIssue>,
+}
+
+impl<'i> Check<'i> {
+    fn new(direction: CompatibilityMode, issues: &'i mut Vec<CompatibilityIssue>) -> Self {
+        Self { direction, issues }
+    }
+
+    fn issue(&mut self, path: &str, message: String) {
+        self.issues.push(CompatibilityIssue {
+            path: display(path),
+            direction: self.direction,
+            message,
+        });
+    }
+
+    /// Check that everything `writer` describes can be read with `reader`. An
+    /// optional field may be missing, which reads and writes as null.
+    fn read(
+        &mut self,
+        reader: &Value,
+        reader_optional: bool,
+        writer: &Value,
+        writer_optional: bool,
+        path: &str,
+    ) {
+        if branches(reader, false).is_empty() {
+            return;
+        }
+        let reader_branches = branches(reader, reader_optional);
+        for written in branches(writer, writer_optional) {
+            let matching = reader_branches
+                .iter()
+                .find(|r| r.kind == written.kind)
+                .or_else(|| {
+                    reader_branches
+                        .iter()
+                        .find(|r| readable_as(written.kind, r.kind))
+                });
+            match matching {
+                Some(read) if read.kind == written.kind => {
+                    self.read_branch(read.schema, written.schema, written.kind, path)
+                }
+                Some(_) => {}
+                None => {
+                    let kinds: Vec<&str> = reader_branches.iter().map(|b| b.kind).collect();
+                    self.issue(
+                        path,
+                        format!(
+                            "{} values can't be read as {}",
+                            written.kind,
+                            kinds.join(" | ")
+                        ),
+                    );
+                }
+            }
+        }
+    }
+
+    fn read_branch(&mut self, reader: &Value, writer: &Value, kind: &str, path: &str) {
+        match kind {
+            "record" => self.read_record(reader, writer, path),
+            "map" => {
+                if let (Some(r), Some(w)) = (
+                    reader.get("additionalProperties"),
+                    writer.get("additionalProperties"),
+                ) {
+                    self.read(r, false, w, false, &join_path(path, "*"));
+                }
+            }
+            "array" => {
+                if let (Some(r @ Value::Object(_)), Some(w @ Value::Object(_))) =
+                    (reader.get("items"), writer.get("items"))
+                {
+                    self.read(r, false, w, false, &format!("{}[]", path));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn read_record(&mut self, reader: &Value, writer: &Value, path: &str) {
+        let no_fields = serde_json::Map::new();
+        let reader_props = reader
+            .get("properties")
+            .and_then(Value::as_object)
+            .unwrap_or(&no_fields);
+        let writer_props = writer
+            .get("properties")
+            .and_then(Value::as_object)
+            .unwrap_or(&no_fields);
+        let required = |schema: &Value, name: &str| {
+            schema
+                .get("required")
+                .and_then(Value::as_array)
+                .is_some_and(|names| names.iter().any(|n| n == name))
+        };
+
+        for (name, read) in reader_props {
+            let field_path = join_path(path, name);
+            match writer_props.get(name) {
+                Some(written) => self.read(
+                    read,
+                    !required(reader, name),
+                    written,
+                    !required(writer, name),
+                    &field_path,
+                ),
+                None if required(reader, name) && !nullable(read) => self.issue(
+                    &field_path,
+                    "field is missing from the writer's schema and has no default".to_string(),
+                ),
+                None => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    include!("tests/compatibility.rs");
+}
@@ -0,0 +1,109 @@
+//! Transparent decompression of compressed JSON input files
+
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read};
+use std::path::Path;
+use std::str::FromStr;
+
+/// Compression codec applied to an input stream.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Compression {
+    /// Plain, uncompressed input
+    #[default]
+    None,
+    /// gzip (`.gz`)
+    Gzip,
+    /// bzip2 (`.bz2`)
+    Bzip2,
+    /// Zstandard (`.zst`)
+    Zstd,
+}
+
+impl Compression {
+    /// Detect the compression codec from a file's extension.
+    ///
+    /// Unrecognised (or missing) extensions are treated as uncompressed.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Self {
+        match path
+            .as_ref()
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext.to_ascii_lowercase())
+            .as_deref()
+        {
+            Some("gz" | "gzip") => Compression::Gzip,
+            Some("bz2" | "bzip2") => Compression::Bzip2,
+            Some("zst" | "zstd") => Compression::Zstd,
+            _ => Compression::None,
+        }
+    }
+}
+
+impl FromStr for Compression {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "none" => Ok(Compression::None),
+            "gzip" | "gz" => Ok(Compression::Gzip),
+            "bzip2" | "bz2" => Ok(Compression::Bzip2),
+            "zstd" | "zst" => Ok(Compression::Zstd),
+            other => Err(format!(
+                "Unknown compression '{}' (expected none|gzip|bzip2|zstd)",
+                other
+            )),
+        }
+    }
+}
+
+/// Wrap a reader in a streaming decoder for the given compression codec.
+///
+/// Decompression happens incrementally as the returned reader is consumed,
+/// so the compressed input is never held in memory in full.
+pub fn decompress_reader<R: Read + 'static>(
+    reader: R,
+    compression: Compression,
+) -> Result<Box<dyn BufRead>, String> {
+    let reader: Box<dyn BufRead> = match compression {
+        Compression::None => Box::new(BufReader::new(reader)),
+        Compression::Gzip => Box::new(BufReader::new(flate2::read::MultiGzDecoder::new(reader))),
+        Compression::Bzip2 => Box::new(BufReader::new(bzip2::read::MultiBzDecoder::new(reader))),
+        Compression::Zstd => Box::new(BufReader::new(
+            zstd::stream::read::Decoder::new(reader)
+                .map_err(|e| format!("Failed to create zstd decoder: {}", e))?,
+        )),
+    };
+    Ok(reader)
+}
+
+/// Open a file for reading, decompressing it on the fly.
+///
+/// # Arguments
+/// * `path` - Path to the (possibly compressed) input file
+/// * `compression` - Codec to use, or `None` to detect it from the file extension
+pub fn open_reader(
+    path: &str,
+    compression: Option<Compression>,
+) -> Result<Box<dyn BufRead>, String> {
+    let file = File::open(path).map_err(|e| format!("Failed to open '{}': {}", path, e))?;
+    let compression = compression.unwrap_or_else(|| Compression::from_path(path));
+    decompress_reader(file, compression)
+}
+
+/// Read a (possibly compressed) file into a string.
+///
+/// The whole decoded text is held in memory, which for a compressed file can be many times
+/// its size on disk: use [`open_reader`] to read it a line at a time instead.
+pub fn read_to_string(path: &str, compression: Option<Compression>) -> Result<String, String> {
+    let mut reader = open_reader(path, compression)?;
+    let mut buffer = String::new();
+    reader
+        .read_to_string(&mut buffer)
+        .map_err(|e| format!("Failed to read '{}': {}", path, e))?;
+    Ok(buffer)
+}
+
+#[cfg(test)]
+mod tests {
+    include!("tests/compression.rs");
+}
@@ -0,0 +1,121 @@
+//! Schema conformity: whether a JSON value validates against a JSON Schema, and
+//! where it doesn't.
+//!
+//! Covers the keywords inference produces: `type` (a name or a list of names),
+//! `properties`, `required`, `additionalProperties`, `items`, `enum`, `anyOf` and
+//! `oneOf` (treated like `anyOf`). Other keywords are ignored.
+
+use serde_json::Value;
+
+/// Path reported for a violation at the document root.
+pub const ROOT_PATH: &str = "$";
+
+/// Paths in `value` that don't conform to `schema`, in document order; empty when
+/// the value is valid.
+///
+/// Paths are dotted field names and map keys, with `[i]` for array elements. A
+/// missing required field is reported at the path it should have had.
+pub fn violations(value: &Value, schema: &Value) -> Vec<String> {
+    let mut out = Vec::new();
+    check(value, schema, "", &mut out);
+    out
+}
+
+fn check(value: &Value, schema: &Value, path: &str, out: &mut Vec<String>) {
+    let obj = match schema {
+        Value::Object(obj) => obj,
+        // Boolean schemas: `true` accepts anything, `false` nothing
+        Value::Bool(false) => return out.push(display(path)),
+        _ => return,
+    };
+
+    for key in ["anyOf", "oneOf"] {
+        if let Some(Value::Array(branches)) = obj.get(key) {
+            if !branches.iter().any(|b| violations(value, b).is_empty()) {
+                return out.push(display(path));
+            }
+        }
+    }
+    if let Some(Value::Array(allowed)) = obj.get("enum") {
+        if !allowed.contains(value) {
+            return out.push(display(path));
+        }
+    }
+    if let Some(t) = obj.get("type") {
+        if !type_matches(value, t) {
+            return out.push(display(path));
+        }
+    }
+
+    match value {
+        Value::Object(m) => {
+            if let Some(Value::Array(required)) = obj.get("required") {
+                for name in required.iter().filter_map(Value::as_str) {
+                    if !m.contains_key(name) {
+                        out.push(join_path(path, name));
+                    }
+                }
+            }
+            let properties = obj.get("properties").and_then(Value::as_object);
+            for (key, child) in m {
+                let child_path = join_path(path, key);
+                match properties.and_then(|p| p.get(key)) {
+                    Some(prop) => check(child, prop, &child_path, out),
+                    None => match obj.get("additionalProperties") {
+                        Some(Value::Bool(false)) => out.push(child_path),
+                        Some(values @ Value::Object(_)) => check(child, values, &child_path, out),
+                        _ => {}
+                    },
+                }
+            }
+        }
+        Value::Array(items) => {
+            if let Some(item_schema) = obj.get("items") {
+                for (i, item) in items.iter().enumerate() {
+                    check(item, item_schema, &format!("{}[{}]", path, i), out);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn type_matches(value: &Value, t: &Value) -> bool {
+    match t {
+        Value::String(name) => match (name.as_str(), value) {
+            ("null", Value::Null)
+            | ("boolean", Value::Bool(_))
+            | ("number", Value::Number(_))
+            | ("string", Value::String(_))
+            | ("array", Value::Array(_))
+            | ("object", Value::Object(_)) => true,
+            ("integer", Value::Number(n)) => {
+                n.is_i64() || n.is_u64() || n.as_f64().is_some_and(|f| f.fract() == 0.0)
+            }
+            _ => false,
+        },
+        Value::Array(names) => names.iter().any(|n| type_matches(value, n)),
+        _ => true,
+    }
+}
+
+pub(crate) fn display(path: &str) -> String {
+    if path.is_empty() {
+        ROOT_PATH.to_string()
+    } else {
+        path.to_string()
+    }
+}
+
+pub(crate) fn join_path(parent: &str, child: &str) -> String {
+    if parent.is_empty() {
+        child.to_string()
+    } else {
+        format!("{}.{}", parent, child)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    include!("tests/conformity.rs");
+}
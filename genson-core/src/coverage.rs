@@ -0,0 +1,171 @@
+//! Schema coverage: fields observed in data that a reference schema doesn't declare.
+//!
+//! Useful for data-contract monitoring, where a known schema is checked in and
+//! new, unexpected fields in incoming data should raise an alert.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Default number of distinct example values kept per uncovered field.
+pub const DEFAULT_MAX_EXAMPLES: usize = 3;
+
+/// A field present in the data but absent from the reference schema.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UncoveredField {
+    /// Dotted path to the field; `[]` marks array items and `{}` map values.
+    pub path: String,
+    /// Number of times the field was seen (array elements count individually)
+    pub count: usize,
+    /// Distinct example values, in the order first seen
+    pub examples: Vec<Value>,
+}
+
+/// Accumulates uncovered fields over a stream of JSON values.
+///
+/// The reference is a JSON Schema. An object key is covered when it is listed in
+/// `properties`, or when `additionalProperties` is a schema (a map) or `true`.
+/// Only the outermost uncovered field of a subtree is reported.
+pub struct CoverageTracker<'a> {
+    schema: &'a Value,
+    max_examples: usize,
+    fields: Vec<UncoveredField>,
+    index: HashMap<String, usize>,
+    rows: usize,
+}
+
+impl<'a> CoverageTracker<'a> {
+    pub fn new(schema: &'a Value, max_examples: usize) -> Self {
+        Self {
+            schema,
+            max_examples,
+            fields: Vec::new(),
+            index: HashMap::new(),
+            rows: 0,
+        }
+    }
+
+    /// Check one document against the schema.
+    pub fn observe(&mut self, value: &Value) {
+        self.rows += 1;
+        self.visit(value, vec![self.schema], "");
+    }
+
+    /// Number of documents observed.
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// The uncovered fields, in the order first seen.
+    pub fn finish(self) -> Vec<UncoveredField> {
+        self.fields
+    }
+
+    fn visit(&mut self, value: &Value, schemas: Vec<&'a Value>, path: &str) {
+        let schemas = expand_branches(schemas);
+        match value {
+            Value::Object(obj) => {
+                for (key, child) in obj {
+                    let mut child_schemas = Vec::new();
+                    let mut covered = false;
+                    let mut is_property = false;
+                    for schema in &schemas {
+                        if let Some(prop) = schema.get("properties").and_then(|p| p.get(key)) {
+                            covered = true;
+                            is_property = true;
+                            child_schemas.push(prop);
+                        } else {
+                            match schema.get("additionalProperties") {
+                                Some(Value::Bool(true)) => covered = true,
+                                Some(values @ Value::Object(_)) => {
+                                    covered = true;
+                                    child_schemas.push(values);
+                                }
+                                _ => {}
+                            }
+                        }
+                    }
+                    if !covered {
+                        self.record(join_path(path, key), child);
+                    } else if !child_schemas.is_empty() {
+                        // Map keys are data, so map values share one `{}` path
+                        let child_path = join_path(path, if is_property { key } else { "{}" });
+                        self.visit(child, child_schemas, &child_path);
+                    }
+                }
+            }
+            Value::Array(items) => {
+                let item_schemas: Vec<&Value> =
+                    schemas.iter().filter_map(|s| s.get("items")).collect();
+                if item_schemas.is_empty() {
+                    return;
+                }
+                let item_path = join_path(path, "[]");
+                for item in items {
+                    self.visit(item, item_schemas.clone(), &item_path);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn record(&mut self, path: String, value: &Value) {
+        let idx = *self.index.entry(path.clone()).or_insert_with(|| {
+            self.fields.push(UncoveredField {
+                path,
+                count: 0,
+                examples: Vec::new(),
+            });
+            self.fields.len() - 1
+        });
+        let field = &mut self.fields[idx];
+        field.count += 1;
+        if field.examples.len() < self.max_examples && !field.examples.contains(value) {
+            field.examples.push(value.clone());
+        }
+    }
+}
+
+/// Report the fields in `values` that `schema` doesn't cover.
+pub fn find_uncovered<'v>(
+    schema: &Value,
+    values: impl IntoIterator<Item = &'v Value>,
+    max_examples: usize,
+) -> Vec<UncoveredField> {
+    let mut tracker = CoverageTracker::new(schema, max_examples);
+    for value in values {
+        tracker.observe(value);
+    }
+    tracker.finish()
+}
+
+/// Add the branches of union nodes (`anyOf` / `oneOf`) to the candidate schemas.
+fn expand_branches(schemas: Vec<&Value>) -> Vec<&Value> {
+    let mut out = Vec::with_capacity(schemas.len());
+    let mut stack = schemas;
+    stack.reverse();
+    while let Some(schema) = stack.pop() {
+        out.push(schema);
+        for key in ["oneOf", "anyOf"] {
+            if let Some(Value::Array(branches)) = schema.get(key) {
+                stack.extend(branches.iter().rev());
+            }
+        }
+    }
+    out
+}
+
+fn join_path(parent: &str, child: &str) -> String {
+    if parent.is_empty() {
+        child.to_string()
+    } else if child.starts_with('[') || child.starts_with('{') {
+        format!("{}{}", parent, child)
+    } else {
+        format!("{}.{}", parent, child)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    include!("tests/coverage.rs");
+}
@@ -0,0 +1,113 @@
+//! CSV/TSV input where one column holds JSON documents
+
+use std::io::Read;
+use std::path::Path;
+
+/// How a CSV file is delimited and quoted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CsvOptions {
+    /// Field delimiter, `,` by default (`\t` for TSV)
+    pub delimiter: u8,
+    /// Quote character, `"` by default
+    pub quote: u8,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        Self {
+            delimiter: b',',
+            quote: b'"',
+        }
+    }
+}
+
+impl CsvOptions {
+    /// Default options for a file, with a tab delimiter for `.tsv` and `.tab`
+    /// files (also when compressed, e.g. `.tsv.gz`).
+    pub fn for_path<P: AsRef<Path>>(path: P) -> Self {
+        let name = path
+            .as_ref()
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        let tabbed = name
+            .split('.')
+            .skip(1)
+            .any(|ext| ext == "tsv" || ext == "tab");
+        Self {
+            delimiter: if tabbed { b'\t' } else { b',' },
+            ..Default::default()
+        }
+    }
+}
+
+/// Parse a delimiter or quote option: a single ASCII character, or `\t` / `tab`.
+pub fn parse_csv_char(value: &str) -> Result<u8, String> {
+    match value {
+        "\\t" | "tab" => Ok(b'\t'),
+        _ if value.len() == 1 && value.is_ascii() => Ok(value.as_bytes()[0]),
+        _ => Err(format!(
+            "Invalid CSV character: {} (expected a single ASCII character or \\t)",
+            value
+        )),
+    }
+}
+
+/// Read a column of JSON strings from CSV input with a header row
+///
+/// Records are parsed one at a time, so only the column's values are held in
+/// memory, not the rest of the file.
+///
+/// # Returns
+/// Vector of strings from the specified column (empty cells are skipped)
+///
+/// # Errors
+/// Returns error if:
+/// - The header can't be read or doesn't have the column
+/// - A record is malformed (e.g. an unterminated quote or a ragged row)
+pub fn read_string_column<R: Read>(
+    reader: R,
+    column_name: &str,
+    options: CsvOptions,
+) -> Result<Vec<String>, String> {
+    let mut reader = ::csv::ReaderBuilder::new()
+        .delimiter(options.delimiter)
+        .quote(options.quote)
+        .from_reader(reader);
+
+    let headers = reader
+        .headers()
+        .map_err(|e| format!("Failed to read CSV header: {}", e))?;
+    let column_index = headers
+        .iter()
+        .position(|name| name == column_name)
+        .ok_or_else(|| {
+            let available: Vec<_> = headers.iter().collect();
+            format!(
+                "Column '{}' not found in CSV header. Available columns: {}",
+                column_name,
+                available.join(", ")
+            )
+        })?;
+
+    let mut strings = Vec::new();
+    let mut record = ::csv::StringRecord::new();
+    loop {
+        match reader.read_record(&mut record) {
+            Ok(true) => {
+                if let Some(value) = record.get(column_index).filter(|v| !v.is_empty()) {
+                    strings.push(value.to_string());
+                }
+            }
+            Ok(false) => break,
+            Err(e) => return Err(format!("Failed to read CSV record: {}", e)),
+        }
+    }
+    Ok(strings)
+}
+
+#[cfg(test)]
+mod tests {
+    include!("tests/csv.rs");
+}
@@ -0,0 +1,204 @@
+//! Export inferred JSON Schemas to other schema languages.
+//!
+//! Every exporter works from a small logical type model ([`LogicalType`]) built
+//! from the inferred JSON Schema, so they agree on nullability, map detection
+//! and how unrepresentable unions are widened.
+
+use serde_json::Value;
+
+#[cfg(feature = "arrow")]
+pub mod arrow;
+pub mod delta;
+pub mod iceberg;
+#[cfg(feature = "proto")]
+pub mod proto;
+
+/// A field of a [`LogicalType::Struct`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogicalField {
+    pub name: String,
+    pub data_type: LogicalType,
+    pub nullable: bool,
+}
+
+/// Format-neutral data type derived from a JSON Schema node.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LogicalType {
+    String,
+    Long,
+    Double,
+    Boolean,
+    Struct(Vec<LogicalField>),
+    List {
+        element: Box<LogicalType>,
+        element_nullable: bool,
+    },
+    /// String-keyed map (`additionalProperties`)
+    Map {
+        value: Box<LogicalType>,
+        value_nullable: bool,
+    },
+}
+
+impl LogicalType {
+    /// Short name of the type's kind, for messages.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            LogicalType::String => "string",
+            LogicalType::Long => "long",
+            LogicalType::Double => "double",
+            LogicalType::Boolean => "boolean",
+            LogicalType::Struct(_) => "struct",
+            LogicalType::List { .. } => "list",
+            LogicalType::Map { .. } => "map",
+        }
+    }
+}
+
+/// Convert a JSON Schema node to its logical type and nullability.
+///
+/// - `["null", T]` types and `anyOf` branches of `{"type": "null"}` make the type nullable.
+/// - Unions of `integer` and `number` widen to `Double`; any other multi-type
+///   union widens to `String` (values are kept as their JSON text).
+/// - Objects with `additionalProperties` become maps; objects with neither
+///   properties nor `additionalProperties` become `Map<String, String>`.
+pub fn logical_type(schema: &Value) -> (LogicalType, bool) {
+    let Value::Object(obj) = schema else {
+        return (LogicalType::String, true);
+    };
+
+    if let Some(Value::Array(branches)) = obj.get("anyOf").or_else(|| obj.get("oneOf")) {
+        let (non_null, nullable) = split_null_branches(branches);
+        return match non_null.as_slice() {
+            [only] => {
+                let (data_type, inner_nullable) = logical_type(only);
+                (data_type, nullable || inner_nullable)
+            }
+            _ => (
+                widen(non_null.iter().filter_map(|b| b.get("type"))),
+                nullable,
+            ),
+        };
+    }
+
+    match obj.get("type") {
+        Some(Value::String(t)) => (logical_type_named(t, obj), t == "null"),
+        Some(Value::Array(types)) => {
+            let nullable = types.iter().any(|t| t == "null");
+            let non_null: Vec<&str> = types
+                .iter()
+                .filter_map(|t| t.as_str())
+                .filter(|t| *t != "null")
+                .collect();
+            match non_null.as_slice() {
+                [only] => (logical_type_named(only, obj), nullable),
+                [] => (LogicalType::String, true),
+                _ => (widen(types.iter()), nullable),
+            }
+        }
+        // Untyped nodes still describe objects if they carry properties
+        _ if obj.contains_key("properties") || obj.contains_key("additionalProperties") => {
+            (logical_type_named("object", obj), false)
+        }
+        _ => (LogicalType::String, true),
+    }
+}
+
+fn split_null_branches(branches: &[Value]) -> (Vec<&Value>, bool) {
+    let is_null = |b: &Value| b == "null" || b.get("type").is_some_and(|t| t == "null");
+    let non_null: Vec<&Value> = branches.iter().filter(|b| !is_null(b)).collect();
+    let nullable = non_null.len() < branches.len();
+    (non_null, nullable)
+}
+
+/// Widest logical type able to hold every member of a type union.
+fn widen<'a>(types: impl Iterator<Item = &'a Value>) -> LogicalType {
+    let mut numeric = true;
+    for t in types {
+        match t.as_str() {
+            Some("null" | "integer" | "number") => {}
+            _ => numeric = false,
+        }
+    }
+    if numeric {
+        LogicalType::Double
+    } else {
+        LogicalType::String
+    }
+}
+
+fn logical_type_named(type_name: &str, obj: &serde_json::Map<String, Value>) -> LogicalType {
+    match type_name {
+        "integer" => LogicalType::Long,
+        "number" => LogicalType::Double,
+        "boolean" => LogicalType::Boolean,
+        "array" => match obj.get("items") {
+            Some(items) => {
+                let (element, element_nullable) = logical_type(items);
+                LogicalType::List {
+                    element: Box::new(element),
+                    element_nullable,
+                }
+            }
+            None => LogicalType::List {
+                element: Box::new(LogicalType::String),
+                element_nullable: true,
+            },
+        },
+        "object" => {
+            if let Some(values @ Value::Object(_)) = obj.get("additionalProperties") {
+                let (value, value_nullable) = logical_type(values);
+                return LogicalType::Map {
+                    value: Box::new(value),
+                    value_nullable,
+                };
+            }
+            match obj.get("properties").and_then(|p| p.as_object()) {
+                Some(props) if !props.is_empty() => {
+                    let required = required_fields(obj);
+                    LogicalType::Struct(
+                        props
+                            .iter()
+                            .map(|(name, field_schema)| {
+                                let (data_type, nullable) = logical_type(field_schema);
+                                LogicalField {
+                                    name: name.clone(),
+                                    data_type,
+                                    nullable: nullable || !required.contains(&name.as_str()),
+                                }
+                            })
+                            .collect(),
+                    )
+                }
+                _ => LogicalType::Map {
+                    value: Box::new(LogicalType::String),
+                    value_nullable: true,
+                },
+            }
+        }
+        _ => LogicalType::String,
+    }
+}
+
+fn required_fields(obj: &serde_json::Map<String, Value>) -> Vec<&str> {
+    obj.get("required")
+        .and_then(|r| r.as_array())
+        .map(|r| r.iter().filter_map(|v| v.as_str()).collect())
+        .unwrap_or_default()
+}
+
+/// Fields of the root record, as every exported table schema needs a struct at the top.
+pub fn root_fields(schema: &Value) -> Result<Vec<LogicalField>, String> {
+    match logical_type(schema).0 {
+        LogicalType::Struct(fields) => Ok(fields),
+        other => Err(format!(
+            "Schema root must be a record (object with properties) to export, found {}",
+            other.kind()
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    include!("tests/export.rs");
+}
@@ -0,0 +1,113 @@
+//! Apache Arrow schemas, serialised as Arrow JSON or IPC schema bytes.
+
+use super::{root_fields, LogicalField, LogicalType};
+use arrow::datatypes::{DataType, Field, Fields, Schema};
+use arrow::ipc::writer::StreamWriter;
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+/// Convert an inferred JSON Schema to an Arrow [`Schema`].
+///
+/// Strings map to `Utf8`, integers to `Int64`, numbers to `Float64`, and
+/// string-keyed maps to `Map<Utf8, T>` with the standard `entries`/`key`/`value`
+/// child names.
+pub fn to_arrow_schema(schema: &Value) -> Result<Schema, String> {
+    Ok(Schema::new(arrow_fields(&root_fields(schema)?)))
+}
+
+fn arrow_fields(fields: &[LogicalField]) -> Fields {
+    fields
+        .iter()
+        .map(|field| Field::new(&field.name, arrow_type(&field.data_type), field.nullable))
+        .collect()
+}
+
+fn arrow_type(data_type: &LogicalType) -> DataType {
+    match data_type {
+        LogicalType::String => DataType::Utf8,
+        LogicalType::Long => DataType::Int64,
+        LogicalType::Double => DataType::Float64,
+        LogicalType::Boolean => DataType::Boolean,
+        LogicalType::Struct(fields) => DataType::Struct(arrow_fields(fields)),
+        LogicalType::List {
+            element,
+            element_nullable,
+        } => DataType::List(Arc::new(Field::new(
+            "item",
+            arrow_type(element),
+            *element_nullable,
+        ))),
+        LogicalType::Map {
+            value,
+            value_nullable,
+        } => {
+            let entries = Fields::from(vec![
+                Field::new("key", DataType::Utf8, false),
+                Field::new("value", arrow_type(value), *value_nullable),
+            ]);
+            DataType::Map(
+                Arc::new(Field::new("entries", DataType::Struct(entries), false)),
+                false,
+            )
+        }
+    }
+}
+
+/// Serialise an Arrow schema as Arrow JSON (the `schema` object of the Arrow
+/// integration-test JSON format).
+pub fn schema_to_json(schema: &Schema) -> Result<Value, String> {
+    let fields = schema
+        .fields()
+        .iter()
+        .map(|field| field_to_json(field))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(json!({ "fields": fields }))
+}
+
+fn field_to_json(field: &Field) -> Result<Value, String> {
+    let (type_json, children): (Value, Vec<&Field>) = match field.data_type() {
+        DataType::Utf8 => (json!({"name": "utf8"}), vec![]),
+        DataType::Int64 => (
+            json!({"name": "int", "bitWidth": 64, "isSigned": true}),
+            vec![],
+        ),
+        DataType::Float64 => (
+            json!({"name": "floatingpoint", "precision": "DOUBLE"}),
+            vec![],
+        ),
+        DataType::Boolean => (json!({"name": "bool"}), vec![]),
+        DataType::Struct(fields) => (
+            json!({"name": "struct"}),
+            fields.iter().map(|f| f.as_ref()).collect(),
+        ),
+        DataType::List(item) => (json!({"name": "list"}), vec![item.as_ref()]),
+        DataType::Map(entries, keys_sorted) => (
+            json!({"name": "map", "keysSorted": keys_sorted}),
+            vec![entries.as_ref()],
+        ),
+        other => return Err(format!("Unsupported Arrow type in schema: {}", other)),
+    };
+    let children = children
+        .into_iter()
+        .map(field_to_json)
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(json!({
+        "name": field.name(),
+        "nullable": field.is_nullable(),
+        "type": type_json,
+        "children": children,
+    }))
+}
+
+/// Serialise an Arrow schema as an Arrow IPC stream containing only the schema
+/// message, readable by e.g. `pyarrow.ipc.open_stream(...).schema`.
+pub fn schema_to_ipc(schema: &Schema) -> Result<Vec<u8>, String> {
+    let mut writer = StreamWriter::try_new(Vec::new(), schema)
+        .map_err(|e| format!("Failed to encode Arrow IPC schema: {}", e))?;
+    writer
+        .finish()
+        .map_err(|e| format!("Failed to encode Arrow IPC schema: {}", e))?;
+    writer
+        .into_inner()
+        .map_err(|e| format!("Failed to encode Arrow IPC schema: {}", e))
+}
@@ -0,0 +1,55 @@
+//! Delta Lake schema JSON (the `schemaString` of a Delta table's metadata action).
+
+use super::{root_fields, LogicalField, LogicalType};
+use serde_json::{json, Value};
+
+/// Convert an inferred JSON Schema to a Delta Lake struct schema.
+///
+/// Integers map to `long`, numbers to `double`, and string-keyed maps to
+/// `map<string, T>`. Unions that cannot be represented are widened as described
+/// in [`super::logical_type`].
+pub fn to_delta_schema(schema: &Value) -> Result<Value, String> {
+    Ok(delta_struct(&root_fields(schema)?))
+}
+
+fn delta_struct(fields: &[LogicalField]) -> Value {
+    json!({
+        "type": "struct",
+        "fields": fields
+            .iter()
+            .map(|field| json!({
+                "name": field.name,
+                "type": delta_type(&field.data_type),
+                "nullable": field.nullable,
+                "metadata": {},
+            }))
+            .collect::<Vec<_>>(),
+    })
+}
+
+fn delta_type(data_type: &LogicalType) -> Value {
+    match data_type {
+        LogicalType::String => json!("string"),
+        LogicalType::Long => json!("long"),
+        LogicalType::Double => json!("double"),
+        LogicalType::Boolean => json!("boolean"),
+        LogicalType::Struct(fields) => delta_struct(fields),
+        LogicalType::List {
+            element,
+            element_nullable,
+        } => json!({
+            "type": "array",
+            "elementType": delta_type(element),
+            "containsNull": element_nullable,
+        }),
+        LogicalType::Map {
+            value,
+            value_nullable,
+        } => json!({
+            "type": "map",
+            "keyType": "string",
+            "valueType": delta_type(value),
+            "valueContainsNull": value_nullable,
+        }),
+    }
+}
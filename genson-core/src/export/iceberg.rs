@@ -0,0 +1,79 @@
+//! Apache Iceberg table schema JSON.
+
+use super::{root_fields, LogicalField, LogicalType};
+use serde_json::{json, Value};
+
+/// Convert an inferred JSON Schema to an Iceberg schema (`schema-id` 0).
+///
+/// Field, list element and map key/value IDs are assigned sequentially
+/// starting at 1, with each struct's own fields numbered before any of their
+/// nested children, matching how Iceberg assigns IDs to a new table.
+pub fn to_iceberg_schema(schema: &Value) -> Result<Value, String> {
+    let fields = root_fields(schema)?;
+    let mut next_id = 1;
+    let Value::Object(mut root) = iceberg_struct(&fields, &mut next_id) else {
+        unreachable!("iceberg_struct always returns an object");
+    };
+    root.insert("schema-id".to_string(), json!(0));
+    Ok(Value::Object(root))
+}
+
+fn take_id(next_id: &mut u64) -> u64 {
+    let id = *next_id;
+    *next_id += 1;
+    id
+}
+
+fn iceberg_struct(fields: &[LogicalField], next_id: &mut u64) -> Value {
+    let ids: Vec<u64> = fields.iter().map(|_| take_id(next_id)).collect();
+    json!({
+        "type": "struct",
+        "fields": fields
+            .iter()
+            .zip(ids)
+            .map(|(field, id)| json!({
+                "id": id,
+                "name": field.name,
+                "required": !field.nullable,
+                "type": iceberg_type(&field.data_type, next_id),
+            }))
+            .collect::<Vec<_>>(),
+    })
+}
+
+fn iceberg_type(data_type: &LogicalType, next_id: &mut u64) -> Value {
+    match data_type {
+        LogicalType::String => json!("string"),
+        LogicalType::Long => json!("long"),
+        LogicalType::Double => json!("double"),
+        LogicalType::Boolean => json!("boolean"),
+        LogicalType::Struct(fields) => iceberg_struct(fields, next_id),
+        LogicalType::List {
+            element,
+            element_nullable,
+        } => {
+            let element_id = take_id(next_id);
+            json!({
+                "type": "list",
+                "element-id": element_id,
+                "element-required": !element_nullable,
+                "element": iceberg_type(element, next_id),
+            })
+        }
+        LogicalType::Map {
+            value,
+            value_nullable,
+        } => {
+            let key_id = take_id(next_id);
+            let value_id = take_id(next_id);
+            json!({
+                "type": "map",
+                "key-id": key_id,
+                "key": "string",
+                "value-id": value_id,
+                "value-required": !value_nullable,
+                "value": iceberg_type(value, next_id),
+            })
+        }
+    }
+}
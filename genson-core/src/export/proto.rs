@@ -0,0 +1,206 @@
+//! Protocol Buffers (proto3) message definitions.
+
+use super::{root_fields, LogicalField, LogicalType};
+use serde_json::Value;
+use std::collections::HashSet;
+use std::fmt::Write;
+
+/// Convert an inferred JSON Schema to a proto3 file defining `message_name`.
+///
+/// - Records become messages, nested inside the message that uses them.
+/// - Arrays become `repeated` fields and string-keyed maps become `map<string, T>`.
+/// - Nullable or non-required scalars are marked `optional`.
+/// - Arrays of arrays, and maps of arrays or maps, which protobuf cannot express
+///   directly, are wrapped in a nested message with a single `values` field.
+/// - Field names that are not valid identifiers are sanitised, keeping the
+///   original as `json_name`.
+pub fn to_proto_schema(
+    schema: &Value,
+    message_name: &str,
+    package: Option<&str>,
+) -> Result<String, String> {
+    let fields = root_fields(schema)?;
+    let mut out = String::from("syntax = \"proto3\";\n\n");
+    if let Some(package) = package {
+        writeln!(out, "package {};\n", package).unwrap();
+    }
+    write_message(&mut out, &sanitise_identifier(message_name), &fields, 0);
+    Ok(out)
+}
+
+/// A nested message to emit after the fields of its parent.
+enum Nested<'a> {
+    Record(String, &'a [LogicalField]),
+    /// Wrapper holding a single `repeated`/`map` field of this type
+    Wrapper(String, &'a LogicalType),
+}
+
+fn write_message(out: &mut String, name: &str, fields: &[LogicalField], depth: usize) {
+    let indent = "  ".repeat(depth);
+    writeln!(out, "{}message {} {{", indent, name).unwrap();
+
+    let mut nested = Vec::new();
+    let mut taken_names = HashSet::new();
+    for (number, field) in fields.iter().enumerate() {
+        let ident = sanitise_identifier(&field.name);
+        let type_hint = pascal_case(&ident);
+        let (label, type_name) =
+            field_type(&field.data_type, &type_hint, &mut nested, &mut taken_names);
+        let label = match label {
+            Label::Singular if field.nullable && is_scalar(&field.data_type) => "optional ",
+            Label::Singular => "",
+            Label::Repeated => "repeated ",
+        };
+        let json_name = if ident != field.name {
+            format!(" [json_name = \"{}\"]", field.name.escape_default())
+        } else {
+            String::new()
+        };
+        writeln!(
+            out,
+            "{}  {}{} {} = {}{};",
+            indent,
+            label,
+            type_name,
+            ident,
+            number + 1,
+            json_name
+        )
+        .unwrap();
+    }
+
+    for message in nested {
+        out.push('\n');
+        match message {
+            Nested::Record(name, fields) => write_message(out, &name, fields, depth + 1),
+            Nested::Wrapper(name, data_type) => {
+                let values = [LogicalField {
+                    name: "values".to_string(),
+                    data_type: data_type.clone(),
+                    nullable: false,
+                }];
+                write_message(out, &name, &values, depth + 1)
+            }
+        }
+    }
+
+    writeln!(out, "{}}}", indent).unwrap();
+}
+
+enum Label {
+    Singular,
+    Repeated,
+}
+
+fn is_scalar(data_type: &LogicalType) -> bool {
+    matches!(
+        data_type,
+        LogicalType::String | LogicalType::Long | LogicalType::Double | LogicalType::Boolean
+    )
+}
+
+/// Reserve a unique nested message name within the current message.
+fn claim_name(hint: &str, taken_names: &mut HashSet<String>) -> String {
+    let mut name = hint.to_string();
+    let mut suffix = 2;
+    while !taken_names.insert(name.clone()) {
+        name = format!("{}{}", hint, suffix);
+        suffix += 1;
+    }
+    name
+}
+
+/// The label and type of a field, registering any nested messages it needs.
+fn field_type<'a>(
+    data_type: &'a LogicalType,
+    hint: &str,
+    nested: &mut Vec<Nested<'a>>,
+    taken_names: &mut HashSet<String>,
+) -> (Label, String) {
+    match data_type {
+        LogicalType::List { element, .. } => {
+            let element_type = match element.as_ref() {
+                collection @ (LogicalType::List { .. } | LogicalType::Map { .. }) => {
+                    wrapper(collection, hint, nested, taken_names)
+                }
+                other => singular_type(other, hint, nested, taken_names),
+            };
+            (Label::Repeated, element_type)
+        }
+        LogicalType::Map { value, .. } => {
+            let value_type = match value.as_ref() {
+                collection @ (LogicalType::List { .. } | LogicalType::Map { .. }) => {
+                    wrapper(collection, hint, nested, taken_names)
+                }
+                other => singular_type(other, hint, nested, taken_names),
+            };
+            (Label::Singular, format!("map<string, {}>", value_type))
+        }
+        other => (
+            Label::Singular,
+            singular_type(other, hint, nested, taken_names),
+        ),
+    }
+}
+
+fn singular_type<'a>(
+    data_type: &'a LogicalType,
+    hint: &str,
+    nested: &mut Vec<Nested<'a>>,
+    taken_names: &mut HashSet<String>,
+) -> String {
+    match data_type {
+        LogicalType::String => "string".to_string(),
+        LogicalType::Long => "int64".to_string(),
+        LogicalType::Double => "double".to_string(),
+        LogicalType::Boolean => "bool".to_string(),
+        LogicalType::Struct(fields) => {
+            let name = claim_name(hint, taken_names);
+            nested.push(Nested::Record(name.clone(), fields));
+            name
+        }
+        collection => wrapper(collection, hint, nested, taken_names),
+    }
+}
+
+fn wrapper<'a>(
+    data_type: &'a LogicalType,
+    hint: &str,
+    nested: &mut Vec<Nested<'a>>,
+    taken_names: &mut HashSet<String>,
+) -> String {
+    let name = claim_name(&format!("{}Values", hint), taken_names);
+    nested.push(Nested::Wrapper(name.clone(), data_type));
+    name
+}
+
+/// Replace characters not allowed in a protobuf identifier (`[A-Za-z_][A-Za-z0-9_]*`).
+fn sanitise_identifier(name: &str) -> String {
+    let mut ident: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    if !ident.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_') {
+        ident.insert_str(0, "f_");
+    }
+    ident
+}
+
+fn pascal_case(ident: &str) -> String {
+    let pascal: String = ident
+        .split('_')
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            let first = chars.next().unwrap().to_ascii_uppercase();
+            std::iter::once(first).chain(chars).collect::<String>()
+        })
+        .collect();
+    if pascal.is_empty() {
+        "Field".to_string()
+    } else if pascal.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("M{}", pascal)
+    } else {
+        pascal
+    }
+}
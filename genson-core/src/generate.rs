@@ -0,0 +1,375 @@
+//! Synthetic JSON rows generated from a schema, for test fixtures and fuzzing.
+//!
+//! The schema can be a JSON Schema or an Avro schema (detected from the root, as
+//! for linting). Rows are plain JSON, the form inference and normalisation take
+//! as input, so Avro union values are not wrapped in their branch name.
+
+use crate::lint::is_avro;
+use crate::sample::SplitMix64;
+use serde_json::{Map, Number, Value};
+use std::collections::HashMap;
+
+/// Nesting depth past which nullable values are always null and arrays and maps
+/// empty, so recursive schemas produce finite rows.
+const MAX_GENERATE_DEPTH: usize = 16;
+
+/// How many rows to generate, and what they look like.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GenerateConfig {
+    /// Number of rows (default 100)
+    pub rows: usize,
+    /// Seed for the generator; the same seed gives the same rows (default 0)
+    pub seed: u64,
+    /// Chance that a nullable value is null, and that an optional property is
+    /// left out (default 0.1)
+    pub null_probability: f64,
+    /// Number of distinct keys the maps draw their keys from (default 8)
+    pub map_key_cardinality: usize,
+    /// Most items in an array or entries in a map (default 4)
+    pub max_items: usize,
+}
+
+impl Default for GenerateConfig {
+    fn default() -> Self {
+        Self {
+            rows: 100,
+            seed: 0,
+            null_probability: 0.1,
+            map_key_cardinality: 8,
+            max_items: 4,
+        }
+    }
+}
+
+/// Generate `cfg.rows` rows that conform to `schema`.
+pub fn generate_rows(schema: &Value, cfg: &GenerateConfig) -> Result<Vec<Value>, String> {
+    if !(0.0..=1.0).contains(&cfg.null_probability) {
+        return Err(format!(
+            "Null probability must be between 0 and 1, got {}",
+            cfg.null_probability
+        ));
+    }
+    if !schema.is_object() && !schema.is_array() && !schema.is_boolean() {
+        return Err("Expected a JSON Schema or Avro schema".to_string());
+    }
+
+    let mut gen = Generator {
+        cfg,
+        root: schema,
+        rng: SplitMix64(cfg.seed),
+        named: HashMap::new(),
+    };
+    let avro = is_avro(schema);
+    let rows = (0..cfg.rows)
+        .map(|_| {
+            if avro {
+                gen.avro(schema, "", 0)
+            } else {
+                gen.json_schema(schema, 0)
+            }
+        })
+        .collect::<Result<_, _>>()?;
+    Ok(rows)
+}
+
+struct Generator<'a> {
+    cfg: &'a GenerateConfig,
+    root: &'a Value,
+    rng: SplitMix64,
+    /// Avro named types seen so far, by full and short name
+    named: HashMap<String, Value>,
+}
+
+impl Generator<'_> {
+    /// A uniform index below `n` (which must be non-zero).
+    fn index(&mut self, n: usize) -> usize {
+        (self.rng.next() % n as u64) as usize
+    }
+
+    /// True with probability `p`.
+    fn chance(&mut self, p: f64) -> bool {
+        ((self.rng.next() >> 11) as f64 / (1u64 << 53) as f64) < p
+    }
+
+    fn null(&mut self, depth: usize) -> bool {
+        depth >= MAX_GENERATE_DEPTH || self.chance(self.cfg.null_probability)
+    }
+
+    fn length(&mut self, min: usize, depth: usize) -> usize {
+        if depth >= MAX_GENERATE_DEPTH {
+            return min;
+        }
+        let max = self.cfg.max_items.max(min);
+        min + self.index(max - min + 1)
+    }
+
+    /// Distinct keys from the pool of `map_key_cardinality` keys.
+    fn map_keys(&mut self, depth: usize) -> Vec<String> {
+        let mut pool: Vec<usize> = (0..self.cfg.map_key_cardinality).collect();
+        let n = self.length(0, depth).min(pool.len());
+        (0..n)
+            .map(|i| {
+                let j = i + self.index(pool.len() - i);
+                pool.swap(i, j);
+                format!("key_{}", pool[i])
+            })
+            .collect()
+    }
+
+    fn string(&mut self, format: Option<&str>) -> Value {
+        let day = 1 + self.index(28);
+        let s = match format {
+            Some("date") => format!("2024-01-{:02}", day),
+            Some("date-time") => format!("2024-01-{:02}T{:02}:00:00Z", day, self.index(24)),
+            Some("time") => format!("{:02}:{:02}:00", self.index(24), self.index(60)),
+            _ => {
+                let len = 1 + self.index(8);
+                (0..len)
+                    .map(|_| (b'a' + self.index(26) as u8) as char)
+                    .collect()
+            }
+        };
+        Value::String(s)
+    }
+
+    fn integer(&mut self) -> Value {
+        Value::from(self.index(1000) as i64)
+    }
+
+    fn number(&mut self) -> Value {
+        let x = self.index(100_000) as f64 / 100.0;
+        Number::from_f64(x).map_or(Value::Null, Value::Number)
+    }
+
+    fn json_schema(&mut self, schema: &Value, depth: usize) -> Result<Value, String> {
+        let obj = match schema {
+            Value::Object(obj) => obj,
+            Value::Bool(false) => return Ok(Value::Null),
+            _ => return Ok(self.string(None)),
+        };
+
+        if let Some(Value::String(reference)) = obj.get("$ref") {
+            let target = reference
+                .strip_prefix('#')
+                .and_then(|pointer| self.root.pointer(pointer))
+                .ok_or_else(|| format!("Unresolved $ref: {}", reference))?;
+            return self.json_schema(target, depth + 1);
+        }
+        if let Some(value) = obj.get("const") {
+            return Ok(value.clone());
+        }
+        if let Some(Value::Array(allowed)) = obj.get("enum") {
+            if !allowed.is_empty() {
+                return Ok(allowed[self.index(allowed.len())].clone());
+            }
+        }
+        for key in ["anyOf", "oneOf"] {
+            if let Some(Value::Array(branches)) = obj.get(key) {
+                if !branches.is_empty() {
+                    let branch = &branches[self.index(branches.len())];
+                    return self.json_schema(branch, depth);
+                }
+            }
+        }
+
+        let type_name = match obj.get("type") {
+            Some(Value::String(t)) => t.as_str(),
+            Some(Value::Array(types)) => {
+                let names: Vec<&str> = types.iter().filter_map(Value::as_str).collect();
+                let non_null: Vec<&str> = names.iter().copied().filter(|t| *t != "null").collect();
+                if non_null.is_empty() || (non_null.len() < names.len() && self.null(depth)) {
+                    return Ok(Value::Null);
+                }
+                non_null[self.index(non_null.len())]
+            }
+            _ if obj.contains_key("properties") || obj.contains_key("additionalProperties") => {
+                "object"
+            }
+            _ if obj.contains_key("items") || obj.contains_key("prefixItems") => "array",
+            _ => "string",
+        };
+
+        match type_name {
+            "null" => Ok(Value::Null),
+            "boolean" => Ok(Value::Bool(self.chance(0.5))),
+            "integer" => Ok(self.integer()),
+            "number" => Ok(self.number()),
+            "string" => Ok(self.string(obj.get("format").and_then(Value::as_str))),
+            "array" => self.json_array(obj, depth),
+            "object" => self.json_object(obj, depth),
+            other => Err(format!("Unsupported JSON Schema type: {}", other)),
+        }
+    }
+
+    fn json_array(&mut self, obj: &Map<String, Value>, depth: usize) -> Result<Value, String> {
+        let tuple = match (obj.get("prefixItems"), obj.get("items")) {
+            (Some(Value::Array(items)), _) | (None, Some(Value::Array(items))) => Some(items),
+            _ => None,
+        };
+        if let Some(items) = tuple {
+            let values = items
+                .iter()
+                .map(|item| self.json_schema(item, depth + 1))
+                .collect::<Result<_, _>>()?;
+            return Ok(Value::Array(values));
+        }
+
+        let min = obj.get("minItems").and_then(Value::as_u64).unwrap_or(0) as usize;
+        let items = obj.get("items").unwrap_or(&Value::Bool(true));
+        let len = self.length(min, depth);
+        let values = (0..len)
+            .map(|_| self.json_schema(items, depth + 1))
+            .collect::<Result<_, _>>()?;
+        Ok(Value::Array(values))
+    }
+
+    fn json_object(&mut self, obj: &Map<String, Value>, depth: usize) -> Result<Value, String> {
+        let required: Vec<&str> = obj
+            .get("required")
+            .and_then(Value::as_array)
+            .map(|names| names.iter().filter_map(Value::as_str).collect())
+            .unwrap_or_default();
+
+        let mut out = Map::new();
+        if let Some(Value::Object(props)) = obj.get("properties") {
+            for (name, prop) in props {
+                if !required.contains(&name.as_str()) && self.null(depth) {
+                    continue;
+                }
+                out.insert(name.clone(), self.json_schema(prop, depth + 1)?);
+            }
+        }
+        if let Some(values @ Value::Object(_)) = obj.get("additionalProperties") {
+            for key in self.map_keys(depth) {
+                if !out.contains_key(&key) {
+                    let value = self.json_schema(values, depth + 1)?;
+                    out.insert(key, value);
+                }
+            }
+        }
+        Ok(Value::Object(out))
+    }
+
+    fn avro(&mut self, schema: &Value, namespace: &str, depth: usize) -> Result<Value, String> {
+        match schema {
+            Value::String(name) => self.avro_named(name, namespace, depth),
+            Value::Array(branches) => {
+                let non_null: Vec<&Value> = branches
+                    .iter()
+                    .filter(|b| b.as_str() != Some("null"))
+                    .collect();
+                if non_null.is_empty() || (non_null.len() < branches.len() && self.null(depth)) {
+                    return Ok(Value::Null);
+                }
+                let branch = non_null[self.index(non_null.len())];
+                self.avro(branch, namespace, depth)
+            }
+            Value::Object(obj) => self.avro_complex(obj, namespace, depth),
+            _ => Err(format!("Invalid Avro schema: {}", schema)),
+        }
+    }
+
+    fn avro_named(&mut self, name: &str, namespace: &str, depth: usize) -> Result<Value, String> {
+        match name {
+            "null" => Ok(Value::Null),
+            "boolean" => Ok(Value::Bool(self.chance(0.5))),
+            "int" | "long" => Ok(self.integer()),
+            "float" | "double" => Ok(self.number()),
+            "string" | "bytes" => Ok(self.string(None)),
+            _ => {
+                let full = format!("{}.{}", namespace, name);
+                let schema = self
+                    .named
+                    .get(&full)
+                    .or_else(|| self.named.get(name))
+                    .cloned()
+                    .ok_or_else(|| format!("Unknown Avro type: {}", name))?;
+                self.avro(&schema, namespace, depth + 1)
+            }
+        }
+    }
+
+    fn avro_complex(
+        &mut self,
+        obj: &Map<String, Value>,
+        namespace: &str,
+        depth: usize,
+    ) -> Result<Value, String> {
+        let type_name = obj.get("type").and_then(Value::as_str).unwrap_or("");
+        let namespace = obj
+            .get("namespace")
+            .and_then(Value::as_str)
+            .unwrap_or(namespace);
+        if let Some(name) = obj.get("name").and_then(Value::as_str) {
+            if matches!(type_name, "record" | "enum" | "fixed") {
+                let schema = Value::Object(obj.clone());
+                if !namespace.is_empty() {
+                    self.named
+                        .insert(format!("{}.{}", namespace, name), schema.clone());
+                }
+                self.named.insert(name.to_string(), schema);
+            }
+        }
+
+        match type_name {
+            "record" => {
+                let mut out = Map::new();
+                if let Some(Value::Array(fields)) = obj.get("fields") {
+                    for field in fields {
+                        let name = field
+                            .get("name")
+                            .and_then(Value::as_str)
+                            .ok_or("Avro field without a name")?;
+                        let field_type = field.get("type").ok_or("Avro field without a type")?;
+                        let value = self.avro(field_type, namespace, depth + 1)?;
+                        out.insert(name.to_string(), value);
+                    }
+                }
+                Ok(Value::Object(out))
+            }
+            "enum" => {
+                let symbols = obj
+                    .get("symbols")
+                    .and_then(Value::as_array)
+                    .filter(|s| !s.is_empty())
+                    .ok_or("Avro enum without symbols")?;
+                Ok(symbols[self.index(symbols.len())].clone())
+            }
+            "fixed" => {
+                let size = obj.get("size").and_then(Value::as_u64).unwrap_or(0) as usize;
+                Ok(Value::String(
+                    (0..size)
+                        .map(|_| (b'a' + self.index(26) as u8) as char)
+                        .collect(),
+                ))
+            }
+            "array" => {
+                let items = obj.get("items").ok_or("Avro array without items")?;
+                let len = self.length(0, depth);
+                let values = (0..len)
+                    .map(|_| self.avro(items, namespace, depth + 1))
+                    .collect::<Result<_, _>>()?;
+                Ok(Value::Array(values))
+            }
+            "map" => {
+                let values = obj.get("values").ok_or("Avro map without values")?;
+                let mut out = Map::new();
+                for key in self.map_keys(depth) {
+                    let value = self.avro(values, namespace, depth + 1)?;
+                    out.insert(key, value);
+                }
+                Ok(Value::Object(out))
+            }
+            // A primitive with attributes, e.g. a logical type
+            _ => match obj.get("type") {
+                Some(inner) => self.avro(inner, namespace, depth),
+                None => Err("Avro schema without a type".to_string()),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    include!("tests/generate.rs");
+}
@@ -3,11 +3,13 @@ mod builder;
 mod node;
 mod strategy;
 
+#[cfg(not(target_arch = "wasm32"))]
 use mimalloc::MiMalloc;
 use rayon::prelude::*;
 use serde_json::json;
 
 // Setting the global allocator to mimalloc for more efficient memory allocation
+#[cfg(not(target_arch = "wasm32"))]
 #[global_allocator]
 static GLOBAL: MiMalloc = MiMalloc;
 
@@ -144,9 +146,10 @@ fn trim_to_object(data: &mut [u8]) -> &mut [u8] {
 }
 
 /// Check if the data is a JSON object array, this function assumes that the data is trimmed
-/// with `trim_to_object` before calling it
+/// with `trim_to_object` before calling it. Data with no object or array in it (a bare
+/// scalar) trims to nothing and is not an array.
 fn is_json_object_array(data: &[u8]) -> bool {
-    data[0] == b'[' && data[data.len() - 1] == b']'
+    data.first() == Some(&b'[') && data.last() == Some(&b']')
 }
 
 /// Get only the JSON array elements from the JSON array data slice, excluding the square brackets.
@@ -193,6 +196,11 @@ mod tests {
         assert_eq!(trimmed_data, expected_data);
     }
 
+    #[test]
+    fn test_is_json_object_array_empty() {
+        assert!(!is_json_object_array(b""));
+    }
+
     #[test]
     fn test_is_json_object_array() {
         let data = br#"[{"name": "John", "age": 30}]"#.to_vec();
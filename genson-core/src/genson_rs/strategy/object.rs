@@ -79,14 +79,10 @@ impl SchemaStrategy for ObjectStrategy {
             });
         }
 
-        if self.required_properties.is_none() {
-            self.required_properties = Some(properties);
-        } else {
+        match self.required_properties.as_mut() {
+            None => self.required_properties = Some(properties),
             // take the intersection
-            self.required_properties
-                .as_mut()
-                .unwrap()
-                .retain(|p| properties.contains(p));
+            Some(required) => required.retain(|p| properties.contains(p)),
         }
     }
 
@@ -125,18 +121,17 @@ impl SchemaStrategy for ObjectStrategy {
                         // and should be followed
                         self.include_empty_required = true;
                     }
-                    if self.required_properties.is_none() {
-                        let required_fields_set: HashSet<String> = required_fields
-                            .iter()
-                            .map(|v| v.as_str().unwrap().to_string())
-                            .collect();
-                        self.required_properties = Some(required_fields_set);
-                    } else {
+                    match self.required_properties.as_mut() {
+                        None => {
+                            let required_fields_set: HashSet<String> = required_fields
+                                .iter()
+                                .map(|v| v.as_str().unwrap().to_string())
+                                .collect();
+                            self.required_properties = Some(required_fields_set);
+                        }
                         // take the intersection
-                        self.required_properties
-                            .as_mut()
-                            .unwrap()
-                            .retain(|p| required_fields.contains(&Value::String(p.to_string())));
+                        Some(required) => required
+                            .retain(|p| required_fields.contains(&Value::String(p.to_string()))),
                     }
                 }
             }
@@ -234,13 +229,9 @@ impl SchemaStrategy for ObjectStrategy {
                 .reduce(|acc, set| acc.intersection(&set).cloned().collect())
                 .unwrap_or_default();
 
-            if self.required_properties.is_none() {
-                self.required_properties = Some(final_required);
-            } else {
-                self.required_properties
-                    .as_mut()
-                    .unwrap()
-                    .retain(|p| final_required.contains(p));
+            match self.required_properties.as_mut() {
+                None => self.required_properties = Some(final_required),
+                Some(required) => required.retain(|p| final_required.contains(p)),
             }
         }
     }
@@ -1,44 +1,99 @@
-#[cfg(not(panic = "unwind"))]
+#[cfg(all(not(panic = "unwind"), not(target_arch = "wasm32")))]
 compile_error!("genson-core requires panic=unwind to catch genson-rs panics. Set [profile.*].panic = \"unwind\" in Cargo.toml.");
 
+#[cfg(feature = "avro")]
+pub mod avro;
+pub mod compatibility;
+#[cfg(feature = "compression")]
+pub mod compression;
+pub mod conformity;
+pub mod coverage;
+#[cfg(feature = "csv")]
+pub mod csv;
+pub mod export;
+pub mod generate;
 pub mod genson_rs;
+pub mod limits;
+pub mod lint;
+pub mod nonfinite;
 #[cfg(feature = "avro")]
 pub mod normalise;
+#[cfg(feature = "ocf")]
+pub mod ocf;
 #[cfg(feature = "parquet")]
 pub mod parquet;
+#[cfg(feature = "schema-registry")]
+pub mod registry;
+pub mod repair;
+pub mod sample;
 pub mod schema;
+pub mod serialise;
+pub mod variants;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+/// The clock: `std::time`, or its browser equivalent with the `wasm` feature, as the
+/// standard clock panics on `wasm32-unknown-unknown`.
+#[cfg(not(feature = "wasm"))]
+pub(crate) use std::time;
+#[cfg(feature = "wasm")]
+pub(crate) use web_time as time;
 
 // Re-export commonly used items
+pub use limits::{LimitAction, ResourceLimits};
+pub use sample::SampleSpec;
+
 pub use schema::{
-    infer_json_schema_from_strings, DebugVerbosity, SchemaInferenceConfig, SchemaInferenceResult,
+    infer_json_schema_from_strings, DebugVerbosity, FieldExplanation, FieldOrder, FieldPresence,
+    FormatDetection, JsonSchemaDraft, KeyCollision, KeyFold, MapDetection, OuterScalarPolicy,
+    SchemaInferenceConfig, SchemaInferenceResult, SchemaInferenceSession, UnionOrder,
+    CONFIG_SNAPSHOT_KEY, DEFAULT_ERROR_CONTEXT_CHARS, DEFAULT_ROOT_VALUE_FIELD, MAX_NESTING_DEPTH,
 };
+// Used by the debug/profile macros to emit tracing events
+#[cfg(feature = "trace")]
+#[doc(hidden)]
+pub use tracing as __tracing;
 
 /// Helper function to infer JSON schema from a collection of JSON strings
-pub fn infer_json_schema(
-    json_strings: &[String],
+pub fn infer_json_schema<S: AsRef<str>>(
+    json_strings: &[S],
     config: Option<SchemaInferenceConfig>,
 ) -> Result<SchemaInferenceResult, String> {
     #[cfg(feature = "trace")]
     {
         use crustrace_mermaid::{GroupingMode, MermaidLayer};
-        use tracing_subscriber::filter::LevelFilter;
+        use tracing_subscriber::fmt::format::FmtSpan;
         use tracing_subscriber::prelude::*;
+        use tracing_subscriber::EnvFilter;
 
         let mmd_layer = MermaidLayer::new()
             .with_mode(GroupingMode::MergeByName)
             .with_params_mode(crustrace_mermaid::ParamRenderMode::SingleNodeGrouped);
 
-        tracing_subscriber::registry()
-            .with(
-                tracing_subscriber::fmt::layer()
-                    .with_span_events(
-                        tracing_subscriber::fmt::format::FmtSpan::ENTER
-                            | tracing_subscriber::fmt::format::FmtSpan::EXIT,
-                    )
-                    .with_filter(LevelFilter::INFO),
-            )
+        // RUST_LOG filters by target, e.g. `genson_core::schema=debug`
+        let filter =
+            || EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+        let json = std::env::var("GENSON_LOG_FORMAT").is_ok_and(|f| f == "json");
+        let json_layer = json.then(|| {
+            tracing_subscriber::fmt::layer()
+                .json()
+                .with_span_list(true)
+                .with_writer(std::io::stderr)
+                .with_filter(filter())
+        });
+        let text_layer = (!json).then(|| {
+            tracing_subscriber::fmt::layer()
+                .with_span_events(FmtSpan::ENTER | FmtSpan::EXIT)
+                .with_writer(std::io::stderr)
+                .with_filter(filter())
+        });
+
+        // Ignore the error when a subscriber is already set (e.g. on a repeat call)
+        let _ = tracing_subscriber::registry()
+            .with(json_layer)
+            .with(text_layer)
             .with(mmd_layer)
-            .init();
+            .try_init();
     }
 
     infer_json_schema_from_strings(json_strings, config.unwrap_or_default())
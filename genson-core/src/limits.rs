@@ -0,0 +1,133 @@
+//! Per-run resource limits for inference and normalisation.
+//!
+//! Embedding services can cap how many rows, how many input bytes and how much
+//! wall-clock time a single run may consume. When a limit is hit the run either
+//! fails with an error or stops early and returns what it has processed so far,
+//! depending on [`LimitAction`].
+
+use crate::time::{Duration, Instant};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// What to do when a [`ResourceLimits`] limit is reached.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LimitAction {
+    /// Fail the whole run with an error.
+    #[default]
+    Error,
+    /// Stop consuming input and return a partial result built from the rows
+    /// admitted so far, recording which limit was hit.
+    Partial,
+}
+
+/// Limits enforced over a single inference or normalisation run. `None` means unlimited.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResourceLimits {
+    /// Maximum number of rows (JSON documents; NDJSON lines count individually)
+    pub max_rows: Option<usize>,
+    /// Maximum total size of the input rows in bytes
+    pub max_bytes: Option<usize>,
+    /// Maximum wall-clock time for the run
+    pub max_runtime: Option<Duration>,
+    /// Whether hitting a limit is an error or yields a partial result
+    pub on_limit: LimitAction,
+}
+
+impl ResourceLimits {
+    /// Whether no limit is set.
+    pub fn is_unlimited(&self) -> bool {
+        self.max_rows.is_none() && self.max_bytes.is_none() && self.max_runtime.is_none()
+    }
+}
+
+/// The limit that stopped a run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LimitExceeded {
+    MaxRows(usize),
+    MaxBytes(usize),
+    MaxRuntime(Duration),
+}
+
+impl fmt::Display for LimitExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LimitExceeded::MaxRows(n) => write!(f, "row limit of {} reached", n),
+            LimitExceeded::MaxBytes(n) => write!(f, "byte limit of {} reached", n),
+            LimitExceeded::MaxRuntime(d) => write!(f, "runtime limit of {:?} reached", d),
+        }
+    }
+}
+
+/// Tracks consumption against a [`ResourceLimits`] as rows are admitted.
+///
+/// The clock starts when the tracker is created.
+#[derive(Debug, Clone)]
+pub struct LimitTracker {
+    limits: ResourceLimits,
+    started: Instant,
+    rows: usize,
+    bytes: usize,
+}
+
+impl LimitTracker {
+    pub fn new(limits: &ResourceLimits) -> Self {
+        Self {
+            limits: limits.clone(),
+            started: Instant::now(),
+            rows: 0,
+            bytes: 0,
+        }
+    }
+
+    pub fn limits(&self) -> &ResourceLimits {
+        &self.limits
+    }
+
+    /// Admit a row of `row_bytes` bytes, or report the limit it would break.
+    /// Rejected rows are not counted.
+    pub fn admit(&mut self, row_bytes: usize) -> Result<(), LimitExceeded> {
+        self.check_runtime()?;
+        if let Some(max_rows) = self.limits.max_rows {
+            if self.rows >= max_rows {
+                return Err(LimitExceeded::MaxRows(max_rows));
+            }
+        }
+        if let Some(max_bytes) = self.limits.max_bytes {
+            if self.bytes + row_bytes > max_bytes {
+                return Err(LimitExceeded::MaxBytes(max_bytes));
+            }
+        }
+        self.rows += 1;
+        self.bytes += row_bytes;
+        Ok(())
+    }
+
+    /// Report whether the runtime limit has passed.
+    pub fn check_runtime(&self) -> Result<(), LimitExceeded> {
+        match self.limits.max_runtime {
+            Some(max_runtime) if self.started.elapsed() >= max_runtime => {
+                Err(LimitExceeded::MaxRuntime(max_runtime))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// The instant after which the runtime limit is exceeded, if there is one.
+    pub fn deadline(&self) -> Option<Instant> {
+        self.limits.max_runtime.map(|d| self.started + d)
+    }
+
+    /// Resolve a hit limit according to [`LimitAction`]: an error message, or the
+    /// limit to record on a partial result.
+    pub fn resolve(&self, exceeded: LimitExceeded) -> Result<LimitExceeded, String> {
+        match self.limits.on_limit {
+            LimitAction::Error => Err(format!("Resource limit exceeded: {}", exceeded)),
+            LimitAction::Partial => Ok(exceeded),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    include!("tests/limits.rs");
+}
@@ -0,0 +1,375 @@
+//! Lint rules for generated schemas.
+//!
+//! Checks an Avro or JSON Schema document against a configurable set of
+//! structural rules and reports every violation as a [`LintFinding`], so
+//! generated schemas can be gated before they are checked in.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// A single lint rule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LintRule {
+    /// Unions with more than one non-null branch nested deeper than `max_union_depth`
+    UnionDepth,
+    /// Field names that are not valid Avro names and would need sanitising
+    FieldName,
+    /// Optional (nullable or not-required) fields without a `default`
+    OptionalDefault,
+    /// Maps whose value type is not a record
+    MapValueRecord,
+}
+
+impl LintRule {
+    /// All rules, in reporting order.
+    pub const ALL: [LintRule; 4] = [
+        LintRule::UnionDepth,
+        LintRule::FieldName,
+        LintRule::OptionalDefault,
+        LintRule::MapValueRecord,
+    ];
+
+    /// The rule's name as used in config and output (e.g. `"union_depth"`).
+    pub fn name(&self) -> &'static str {
+        match self {
+            LintRule::UnionDepth => "union_depth",
+            LintRule::FieldName => "field_name",
+            LintRule::OptionalDefault => "optional_default",
+            LintRule::MapValueRecord => "map_value_record",
+        }
+    }
+}
+
+impl std::str::FromStr for LintRule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        LintRule::ALL
+            .into_iter()
+            .find(|rule| rule.name() == s)
+            .ok_or_else(|| {
+                let names: Vec<_> = LintRule::ALL.iter().map(|r| r.name()).collect();
+                format!("Unknown lint rule '{}' (expected {})", s, names.join("|"))
+            })
+    }
+}
+
+/// Configuration for [`lint_schema`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LintConfig {
+    /// Maximum nesting depth (root fields are depth 1) at which a multi-branch union
+    /// may appear. `None` disables the `union_depth` rule.
+    pub max_union_depth: Option<usize>,
+    /// Flag field names that need sanitising to be valid Avro names.
+    pub check_field_names: bool,
+    /// Flag optional fields that have no `default`.
+    pub require_optional_defaults: bool,
+    /// Flag maps whose values are not records.
+    pub require_record_map_values: bool,
+}
+
+impl Default for LintConfig {
+    fn default() -> Self {
+        Self {
+            max_union_depth: Some(2),
+            check_field_names: true,
+            require_optional_defaults: true,
+            require_record_map_values: true,
+        }
+    }
+}
+
+impl LintConfig {
+    /// Turn off a single rule.
+    pub fn disable(&mut self, rule: LintRule) {
+        match rule {
+            LintRule::UnionDepth => self.max_union_depth = None,
+            LintRule::FieldName => self.check_field_names = false,
+            LintRule::OptionalDefault => self.require_optional_defaults = false,
+            LintRule::MapValueRecord => self.require_record_map_values = false,
+        }
+    }
+}
+
+/// A rule violation at a location in the schema.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LintFinding {
+    pub rule: LintRule,
+    /// Dotted path to the offending field; `[]` marks array items and `{}` map values.
+    pub path: String,
+    pub message: String,
+}
+
+/// Lint a schema, returning all findings in document order.
+///
+/// Avro schemas (a top-level `record` with `fields`) and JSON Schema documents
+/// (`properties` / `additionalProperties` / `items`) are both supported; the
+/// format is detected from the root.
+pub fn lint_schema(schema: &Value, cfg: &LintConfig) -> Vec<LintFinding> {
+    let mut findings = Vec::new();
+    if is_avro(schema) {
+        lint_avro(schema, "", 0, cfg, &mut findings);
+    } else {
+        lint_json_schema(schema, "", 0, cfg, &mut findings);
+    }
+    findings
+}
+
+/// Whether a schema document is Avro rather than JSON Schema, judged from its root.
+pub fn is_avro(schema: &Value) -> bool {
+    match schema {
+        Value::Object(obj) => matches!(
+            obj.get("type").and_then(|t| t.as_str()),
+            Some("record" | "map" | "enum" | "fixed")
+        ),
+        Value::Array(_) => true,
+        _ => false,
+    }
+}
+
+/// Whether a name is a valid Avro name: `[A-Za-z_][A-Za-z0-9_]*`.
+pub fn is_valid_avro_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(c) if c.is_ascii_alphabetic() || c == '_' => {}
+        _ => return false,
+    }
+    chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn join_path(parent: &str, child: &str) -> String {
+    if parent.is_empty() {
+        child.to_string()
+    } else if child.starts_with('[') || child.starts_with('{') {
+        format!("{}{}", parent, child)
+    } else {
+        format!("{}.{}", parent, child)
+    }
+}
+
+fn display_path(path: &str) -> &str {
+    if path.is_empty() {
+        "<root>"
+    } else {
+        path
+    }
+}
+
+fn push(findings: &mut Vec<LintFinding>, rule: LintRule, path: &str, message: String) {
+    findings.push(LintFinding {
+        rule,
+        path: display_path(path).to_string(),
+        message,
+    });
+}
+
+fn check_union_depth(
+    branch_count: usize,
+    path: &str,
+    depth: usize,
+    cfg: &LintConfig,
+    findings: &mut Vec<LintFinding>,
+) {
+    if let Some(max_depth) = cfg.max_union_depth {
+        if branch_count > 1 && depth > max_depth {
+            push(
+                findings,
+                LintRule::UnionDepth,
+                path,
+                format!(
+                    "union of {} non-null types at depth {} (max {})",
+                    branch_count, depth, max_depth
+                ),
+            );
+        }
+    }
+}
+
+fn check_field_name(name: &str, path: &str, cfg: &LintConfig, findings: &mut Vec<LintFinding>) {
+    if cfg.check_field_names && !is_valid_avro_name(name) {
+        push(
+            findings,
+            LintRule::FieldName,
+            path,
+            format!("field name '{}' is not a valid Avro name", name),
+        );
+    }
+}
+
+/// Strip `null` from an Avro union, returning the sole remaining branch if there is one.
+fn avro_non_null(schema: &Value) -> Option<&Value> {
+    match schema {
+        Value::Array(branches) => {
+            let mut non_null = branches.iter().filter(|b| *b != "null");
+            match (non_null.next(), non_null.next()) {
+                (Some(only), None) => Some(only),
+                _ => None,
+            }
+        }
+        other => Some(other),
+    }
+}
+
+fn lint_avro(
+    schema: &Value,
+    path: &str,
+    depth: usize,
+    cfg: &LintConfig,
+    findings: &mut Vec<LintFinding>,
+) {
+    match schema {
+        Value::Array(branches) => {
+            let non_null = branches.iter().filter(|b| *b != "null").count();
+            check_union_depth(non_null, path, depth, cfg, findings);
+            for branch in branches {
+                lint_avro(branch, path, depth, cfg, findings);
+            }
+        }
+        Value::Object(obj) => match obj.get("type").and_then(|t| t.as_str()) {
+            Some("record") => {
+                let fields = obj.get("fields").and_then(|f| f.as_array());
+                for field in fields.into_iter().flatten() {
+                    let Some(name) = field.get("name").and_then(|n| n.as_str()) else {
+                        continue;
+                    };
+                    let field_path = join_path(path, name);
+                    check_field_name(name, &field_path, cfg, findings);
+                    let Some(field_type) = field.get("type") else {
+                        continue;
+                    };
+                    let nullable =
+                        matches!(field_type, Value::Array(b) if b.iter().any(|t| t == "null"));
+                    if cfg.require_optional_defaults && nullable && field.get("default").is_none() {
+                        push(
+                            findings,
+                            LintRule::OptionalDefault,
+                            &field_path,
+                            "nullable field has no default".to_string(),
+                        );
+                    }
+                    lint_avro(field_type, &field_path, depth + 1, cfg, findings);
+                }
+            }
+            Some("array") => {
+                if let Some(items) = obj.get("items") {
+                    lint_avro(items, &join_path(path, "[]"), depth + 1, cfg, findings);
+                }
+            }
+            Some("map") => {
+                if let Some(values) = obj.get("values") {
+                    let is_record = avro_non_null(values)
+                        .and_then(|v| v.get("type"))
+                        .is_some_and(|t| t == "record");
+                    if cfg.require_record_map_values && !is_record {
+                        push(
+                            findings,
+                            LintRule::MapValueRecord,
+                            path,
+                            format!("map values are {}, not a record", describe(values)),
+                        );
+                    }
+                    lint_avro(values, &join_path(path, "{}"), depth + 1, cfg, findings);
+                }
+            }
+            _ => {}
+        },
+        _ => {}
+    }
+}
+
+/// Non-null branches of a JSON Schema node's union, if it has one.
+fn json_schema_union_width(obj: &serde_json::Map<String, Value>) -> usize {
+    let is_null = |v: &Value| v == "null" || v.get("type").is_some_and(|t| t == "null");
+    let mut width = 0;
+    if let Some(Value::Array(types)) = obj.get("type") {
+        width = types.iter().filter(|t| !is_null(t)).count();
+    }
+    for key in ["anyOf", "oneOf"] {
+        if let Some(Value::Array(branches)) = obj.get(key) {
+            width = width.max(branches.iter().filter(|b| !is_null(b)).count());
+        }
+    }
+    width
+}
+
+fn lint_json_schema(
+    schema: &Value,
+    path: &str,
+    depth: usize,
+    cfg: &LintConfig,
+    findings: &mut Vec<LintFinding>,
+) {
+    let Value::Object(obj) = schema else {
+        return;
+    };
+
+    check_union_depth(json_schema_union_width(obj), path, depth, cfg, findings);
+    for key in ["anyOf", "oneOf"] {
+        if let Some(Value::Array(branches)) = obj.get(key) {
+            for branch in branches {
+                lint_json_schema(branch, path, depth, cfg, findings);
+            }
+        }
+    }
+
+    if let Some(Value::Object(props)) = obj.get("properties") {
+        let required: Vec<&str> = obj
+            .get("required")
+            .and_then(|r| r.as_array())
+            .map(|r| r.iter().filter_map(|v| v.as_str()).collect())
+            .unwrap_or_default();
+        for (name, field_schema) in props {
+            let field_path = join_path(path, name);
+            check_field_name(name, &field_path, cfg, findings);
+            if cfg.require_optional_defaults
+                && !required.contains(&name.as_str())
+                && field_schema.get("default").is_none()
+            {
+                push(
+                    findings,
+                    LintRule::OptionalDefault,
+                    &field_path,
+                    "optional field has no default".to_string(),
+                );
+            }
+            lint_json_schema(field_schema, &field_path, depth + 1, cfg, findings);
+        }
+    }
+
+    if let Some(items) = obj.get("items") {
+        lint_json_schema(items, &join_path(path, "[]"), depth + 1, cfg, findings);
+    }
+
+    if let Some(values @ Value::Object(_)) = obj.get("additionalProperties") {
+        let is_record = values.get("properties").is_some();
+        if cfg.require_record_map_values && !is_record {
+            push(
+                findings,
+                LintRule::MapValueRecord,
+                path,
+                format!("map values are {}, not a record", describe(values)),
+            );
+        }
+        lint_json_schema(values, &join_path(path, "{}"), depth + 1, cfg, findings);
+    }
+}
+
+/// Short human-readable description of a type for messages.
+fn describe(schema: &Value) -> String {
+    match schema {
+        Value::String(s) => format!("'{}'", s),
+        Value::Array(_) => "a union".to_string(),
+        Value::Object(obj) => match obj.get("type") {
+            Some(Value::String(t)) => format!("'{}'", t),
+            Some(other) => other.to_string(),
+            None => "untyped".to_string(),
+        },
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    include!("tests/lint.rs");
+}
@@ -0,0 +1,107 @@
+//! Lenient parsing of the non-finite float literals `NaN`, `Infinity` and
+//! `-Infinity`, which lax serialisers (Python's `json`, JavaScript's `String`)
+//! write but JSON doesn't allow.
+//!
+//! The text is scrubbed before it's parsed: inference reads each literal as a
+//! number, and normalisation writes it as `null` or as a string, per
+//! [`NonFinitePolicy`]. Literals inside strings are left alone.
+
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+
+/// How non-finite float literals are written when normalising.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NonFinitePolicy {
+    /// Write them as `null`
+    #[default]
+    Null,
+    /// Write them as the strings `"NaN"`, `"Infinity"` and `"-Infinity"`
+    String,
+}
+
+/// The non-finite literals, longest first so `-Infinity` isn't read as `-` then
+/// `Infinity`.
+const LITERALS: [&str; 3] = ["-Infinity", "Infinity", "NaN"];
+
+/// Whether `s` is the text of a non-finite literal.
+pub fn is_nonfinite(s: &str) -> bool {
+    LITERALS.contains(&s)
+}
+
+/// `s` with each non-finite literal replaced by `0.0`, so inference types it as a
+/// number.
+pub fn nonfinite_as_number(s: &str) -> Cow<'_, str> {
+    replace_literals(s, |_| Cow::Borrowed("0.0"))
+}
+
+/// `s` with each non-finite literal written as `policy` says.
+pub fn scrub_nonfinite(s: &str, policy: NonFinitePolicy) -> Cow<'_, str> {
+    replace_literals(s, |literal| match policy {
+        NonFinitePolicy::Null => Cow::Borrowed("null"),
+        NonFinitePolicy::String => Cow::Owned(format!("\"{}\"", literal)),
+    })
+}
+
+/// Replace the literals outside strings with `replacement(literal)`, borrowing `s`
+/// when there are none.
+fn replace_literals<'a>(
+    s: &'a str,
+    replacement: impl Fn(&str) -> Cow<'static, str>,
+) -> Cow<'a, str> {
+    let bytes = s.as_bytes();
+    let mut out = String::new();
+    let mut copied = 0;
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut i = 0;
+    while i < bytes.len() {
+        let byte = bytes[i];
+        if in_string {
+            match byte {
+                _ if escaped => escaped = false,
+                b'\\' => escaped = true,
+                b'"' => in_string = false,
+                _ => {}
+            }
+            i += 1;
+            continue;
+        }
+        if byte == b'"' {
+            in_string = true;
+            i += 1;
+            continue;
+        }
+        let at_boundary = i == 0 || !is_word_byte(bytes[i - 1]);
+        let literal = LITERALS.iter().find(|literal| {
+            bytes[i..].starts_with(literal.as_bytes())
+                && bytes
+                    .get(i + literal.len())
+                    .is_none_or(|&next| !is_word_byte(next))
+        });
+        match literal {
+            Some(literal) if at_boundary => {
+                out.push_str(&s[copied..i]);
+                out.push_str(&replacement(literal));
+                i += literal.len();
+                copied = i;
+            }
+            _ => i += 1,
+        }
+    }
+    if copied == 0 {
+        return Cow::Borrowed(s);
+    }
+    out.push_str(&s[copied..]);
+    Cow::Owned(out)
+}
+
+/// Bytes that can continue a number or a bare word, e.g. the `e` of `1e5`.
+fn is_word_byte(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || matches!(byte, b'_' | b'.' | b'+' | b'-')
+}
+
+#[cfg(test)]
+mod tests {
+    include!("tests/nonfinite.rs");
+}
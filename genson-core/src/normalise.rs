@@ -1,5 +1,19 @@
-use crate::schema::core::make_promoted_scalar_key;
+use crate::avro::ORIGINAL_NAME_KEY;
+use crate::limits::{LimitExceeded, LimitTracker, ResourceLimits};
+use crate::nonfinite::{is_nonfinite, NonFinitePolicy};
+use crate::schema::core::{
+    is_bare_root, is_wrapped, make_promoted_scalar_key, wrap_root_value, DEFAULT_ROOT_VALUE_FIELD,
+    FORCED_TYPE_KEY,
+};
+use crate::schema::key_fold::{fold_keys, KeyFold};
+use crate::schema::map_keys::{is_integer_key, MAP_KEY_TYPE_KEY};
+use crate::schema::select::select_fields;
+use crate::schema::sparse_arrays::{is_sparse_array, unpack_sparse_array};
+use crate::schema::tuples::tuple_position;
 use serde_json::{json, Value};
+use std::borrow::Cow;
+use std::collections::HashSet;
+use std::fmt;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -13,6 +27,51 @@ pub enum MapEncoding {
     KeyValueEntries,
 }
 
+/// What normalisation does with input fields that the schema doesn't declare.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UnknownFieldPolicy {
+    /// Leave them out of the output (the default).
+    #[default]
+    Drop,
+    /// Pass them through unchanged after the schema's fields.
+    Keep,
+    /// Fail on the first one, with its dotted path. Only [`try_normalise_values`]
+    /// and the strict functions report this; [`normalise_value`] drops them.
+    Error,
+}
+
+/// Marker appended to strings cut at [`ValueLimits::max_string_len`].
+pub const DEFAULT_TRUNCATION_MARKER: &str = "...[truncated]";
+
+/// Caps on the size of normalised values, so adversarial rows (megabyte strings,
+/// arrays of thousands of items) can't produce values downstream writers choke on.
+/// `None` means unlimited. Every cut is reported as a [`Coercion`].
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct ValueLimits {
+    /// Longest string kept, in characters. Longer strings keep their first
+    /// `max_string_len` characters followed by `marker`
+    pub max_string_len: Option<usize>,
+    /// Most items kept in an array. Later items are dropped
+    pub max_array_items: Option<usize>,
+    /// Deepest level kept, the row being depth 1. Values nested deeper become `null`
+    pub max_depth: Option<usize>,
+    /// Appended to strings cut at `max_string_len` (default: `...[truncated]`)
+    pub marker: String,
+}
+
+impl Default for ValueLimits {
+    fn default() -> Self {
+        Self {
+            max_string_len: None,
+            max_array_items: None,
+            max_depth: None,
+            marker: DEFAULT_TRUNCATION_MARKER.to_string(),
+        }
+    }
+}
+
 /// Configuration options for normalisation.
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct NormaliseConfig {
@@ -22,8 +81,57 @@ pub struct NormaliseConfig {
     pub coerce_string: bool,
     /// Which map encoding to output Map type fields into (default: Mapping).
     pub map_encoding: MapEncoding,
-    /// Optional: wrap input values inside an object with this field name
+    /// Optional: wrap input values inside an object with this field name, or
+    /// nested objects for a dotted path (e.g. `entity.claims`)
     pub wrap_root: Option<String>,
+    /// When `wrap_root` is unset and the schema is a record, wrap values with a
+    /// bare root (a scalar, or an array that isn't an array of objects) under this
+    /// field, as inference does (default: `"value"`).
+    #[serde(default = "default_root_value_field")]
+    pub root_value_field: String,
+    /// Unwrap `{language, value}` label records to their `value` where the schema
+    /// expects a string, to match a schema inferred with `simplify_labels` (default: false).
+    #[serde(default)]
+    pub simplify_labels: bool,
+    /// Fold an array of maps (`[{"en": "a"}]`) into a map of arrays (`{"en": ["a"]}`)
+    /// where the schema expects the latter, to match a schema inferred with
+    /// `coerce_map_array` (default: false).
+    #[serde(default)]
+    pub coerce_map_array: bool,
+    /// Unpack objects keyed `"0"` to `"n-1"` into arrays in key order where the
+    /// schema expects an array, to match a schema inferred with
+    /// `sparse_array_detection` (default: false).
+    #[serde(default)]
+    pub sparse_array_detection: bool,
+    /// Threads to normalise rows on, with the `parallel` feature (default: None,
+    /// rayon's global pool). `Some(1)` normalises one row at a time. The pool is kept
+    /// for later calls with the same count. Rows keep their order either way.
+    #[serde(default)]
+    pub threads: Option<usize>,
+    /// What to do with fields in the input that aren't in the schema (default: Drop).
+    #[serde(default)]
+    pub unknown_fields: UnknownFieldPolicy,
+    /// Fold the keys of every object as inference did with the same `key_fold`,
+    /// so `ID` and `id` both fill the field they were merged into (default: None).
+    #[serde(default)]
+    pub key_fold: Option<KeyFold>,
+    /// Caps on string length, array length and nesting depth (default: none).
+    #[serde(default)]
+    pub value_limits: ValueLimits,
+    /// Rewrite the integer keys of maps annotated `"x-key-type": "integer"` in
+    /// canonical form, so `"007"` and `"7"` are the same key (default: false).
+    #[serde(default)]
+    pub canonical_numeric_keys: bool,
+    /// How the `NaN`, `Infinity` and `-Infinity` literals were scrubbed from the input
+    /// text (with `scrub_nonfinite`, before parsing). With `String`, float fields keep
+    /// those strings rather than nulling them (default: None).
+    #[serde(default)]
+    pub nonfinite_policy: Option<NonFinitePolicy>,
+    /// Prune each row to these fields (and the fields leading to them) before
+    /// normalising, as inference did with the same `select_paths`, so the fields
+    /// left out aren't kept or reported as unknown (default: none, keep every field).
+    #[serde(default)]
+    pub select_paths: Vec<String>,
 }
 
 impl Default for NormaliseConfig {
@@ -33,12 +141,58 @@ impl Default for NormaliseConfig {
             coerce_string: false,
             map_encoding: MapEncoding::Mapping,
             wrap_root: None,
+            root_value_field: DEFAULT_ROOT_VALUE_FIELD.to_string(),
+            simplify_labels: false,
+            coerce_map_array: false,
+            sparse_array_detection: false,
+            threads: None,
+            unknown_fields: UnknownFieldPolicy::Drop,
+            key_fold: None,
+            value_limits: ValueLimits::default(),
+            canonical_numeric_keys: false,
+            nonfinite_policy: None,
+            select_paths: Vec::new(),
+        }
+    }
+}
+
+impl NormaliseConfig {
+    /// The default config with the settings in `overrides` (a JSON object keyed like
+    /// the config's fields, e.g. `{"map_encoding": "kv"}`) applied.
+    ///
+    /// # Errors
+    /// Returns error if `overrides` isn't an object, names an unknown setting, or
+    /// gives a setting a value of the wrong type.
+    pub fn from_overrides(overrides: &Value) -> Result<Self, String> {
+        let Value::Object(overrides) = overrides else {
+            return Err("Expected a JSON object of normalise settings".to_string());
+        };
+        let Ok(Value::Object(mut config)) = serde_json::to_value(Self::default()) else {
+            return Err("Config doesn't serialise to an object".to_string());
+        };
+        for (key, value) in overrides {
+            if !config.contains_key(key) {
+                return Err(format!("Unknown normalise setting: {}", key));
+            }
+            config.insert(key.clone(), value.clone());
         }
+        serde_json::from_value(Value::Object(config)).map_err(|e| format!("Invalid config: {}", e))
     }
 }
 
+fn default_root_value_field() -> String {
+    DEFAULT_ROOT_VALUE_FIELD.to_string()
+}
+
 /// Apply map encoding strategy to a map of already-normalised values.
-fn apply_map_encoding(m: serde_json::Map<String, Value>, encoding: MapEncoding) -> Value {
+///
+/// With `integer_keys`, `KeyValueEntries` writes the keys that are integers as
+/// numbers.
+fn apply_map_encoding(
+    m: serde_json::Map<String, Value>,
+    encoding: MapEncoding,
+    integer_keys: bool,
+) -> Value {
     match encoding {
         MapEncoding::Mapping => Value::Object(m),
         MapEncoding::Entries => {
@@ -48,13 +202,26 @@ fn apply_map_encoding(m: serde_json::Map<String, Value>, encoding: MapEncoding)
         MapEncoding::KeyValueEntries => {
             let arr: Vec<Value> = m
                 .into_iter()
-                .map(|(k, v)| json!({ "key": k, "value": v }))
+                .map(|(k, v)| {
+                    let key = match k.parse::<i64>() {
+                        Ok(i) if integer_keys => json!(i),
+                        _ => Value::String(k),
+                    };
+                    json!({ "key": key, "value": v })
+                })
                 .collect();
             Value::Array(arr)
         }
     }
 }
 
+/// Whether a map schema (Avro or JSON Schema) is annotated as having integer keys.
+fn has_integer_keys(schema: &serde_json::Map<String, Value>) -> bool {
+    schema
+        .get(MAP_KEY_TYPE_KEY)
+        .is_some_and(|key_type| key_type == "integer")
+}
+
 fn get_scalar_type_from_value(value: &Value) -> &'static str {
     match value {
         Value::String(_) => "string",
@@ -89,11 +256,14 @@ fn get_scalar_type_from_value(value: &Value) -> &'static str {
 ///   * If parsing fails, the value becomes `null`.
 ///   * Non-matching values are coerced to string via `.to_string()` for the
 ///     `"string"` type, or dropped to `null` for numeric/boolean types.
+///   * With `simplify_labels`, `{language, value}` label records under a
+///     `"string"` type are unwrapped to their `value`.
 ///
 /// - **Record** (`{"type":"record","fields":[...]}`):
 ///   * Produces a JSON object with exactly the schema’s fields.
 ///   * Missing fields are filled with `null`.
-///   * Extra fields in the input are ignored.
+///   * Extra fields in the input are dropped, or appended unchanged after the
+///     schema's fields with `UnknownFieldPolicy::Keep`.
 ///   * Each field is recursively normalised against its declared type.
 ///
 /// - **Array** (`{"type":"array","items": ...}`):
@@ -101,6 +271,8 @@ fn get_scalar_type_from_value(value: &Value) -> &'static str {
 ///   * Empty arrays become `null` if `cfg.empty_as_null == true`,
 ///     otherwise they remain empty arrays, which can help to avoid row elimination
 ///     when flattened/'exploded'.
+///   * With `sparse_array_detection`, objects keyed `"0"` to `"n-1"` become
+///     arrays of their values in key order.
 ///   * Non-array values are wrapped in a singleton array and normalised
 ///     against the `items` schema.
 ///   * Elements are recursively normalised.
@@ -113,6 +285,9 @@ fn get_scalar_type_from_value(value: &Value) -> &'static str {
 ///   * Each entry’s value is recursively normalised against the `values` schema.
 ///   * Non-object values are coerced into a single-entry object
 ///     (`{"default": value}`).
+///   * Maps annotated `"x-key-type": "integer"` write their keys as numbers in
+///     `kv` entries (dropping any key that isn't an integer), and in canonical
+///     form with `canonical_numeric_keys`.
 ///
 /// - **Union** (`[ ... ]`):
 ///   * If the union contains `"null"`, then `null` inputs are preserved.
@@ -143,55 +318,499 @@ pub fn normalise_value(
     cfg: &NormaliseConfig,
     field_name: Option<&str>,
 ) -> Value {
+    normalise_inner(value, schema, cfg, field_name, 1, None)
+}
+
+/// What normalisation did to a value that didn't already fit the schema.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CoercionAction {
+    /// A string was parsed as the schema's number or boolean (`coerce_string`).
+    CoercedFromString,
+    /// A value was turned into a string.
+    Stringified,
+    /// A value that couldn't be coerced to the schema's type was replaced with `null`.
+    Nulled,
+    /// An empty array or map was replaced with `null` (`empty_as_null`).
+    EmptyToNull,
+    /// A single value was wrapped in an array.
+    WrappedInArray,
+    /// A non-object value was wrapped in a single-entry map.
+    WrappedInMap,
+    /// A scalar was moved into its record's promoted `name__type` field.
+    PromotedScalar,
+    /// A `{language, value}` label record was reduced to its `value` (`simplify_labels`).
+    UnwrappedLabel,
+    /// A field the schema doesn't declare was dropped.
+    DroppedUnknownField,
+    /// An array of maps was folded into a map of arrays (`coerce_map_array`).
+    FoldedArrayOfMaps,
+    /// An object keyed by position was unpacked into an array (`sparse_array_detection`).
+    UnpackedSparseArray,
+    /// A string was cut at `max_string_len` and marked.
+    TruncatedString,
+    /// An array's items past `max_array_items` were dropped.
+    TruncatedArray,
+    /// A value nested deeper than `max_depth` was replaced with `null`.
+    TruncatedDepth,
+    /// An integer map key was rewritten in canonical form (`canonical_numeric_keys`).
+    CanonicalisedKey,
+    /// An entry of a map with integer keys whose key isn't an integer was dropped,
+    /// as `kv` entries can't hold it.
+    DroppedMapEntry,
+}
+
+/// One change made while normalising, as recorded by [`normalise_values_with_report`].
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Coercion {
+    /// Index of the row among the input values.
+    pub row: usize,
+    /// Dotted path to the value: field names and map keys, with no segment for
+    /// array elements (empty for the root).
+    pub path: String,
+    /// JSON kind of the original value: `null`, `boolean`, `number`, `string`,
+    /// `array` or `object`.
+    pub original: String,
+    /// What was done to it.
+    pub action: CoercionAction,
+}
+
+/// Why a value doesn't fit the schema, as reported by [`normalise_value_strict`].
+///
+/// Paths are dotted like those of a [`Coercion`].
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum NormaliseError {
+    /// A value the schema's type can't hold, which was replaced with `null`.
+    TypeMismatch {
+        path: String,
+        /// The schema's type: `int`, `double`, `record`, etc.
+        expected: String,
+        /// JSON kind of the value
+        found: String,
+    },
+    /// A non-nullable field without a default was missing.
+    MissingRequired { path: String },
+    /// A value that the union's first non-null branch, which normalisation uses,
+    /// can't hold.
+    UnionResolution {
+        path: String,
+        /// JSON kind of the value
+        found: String,
+        /// The union's branches, by type name
+        branches: Vec<String>,
+    },
+    /// A field the schema doesn't declare, with [`UnknownFieldPolicy::Error`].
+    UnknownField { path: String },
+}
+
+impl fmt::Display for NormaliseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NormaliseError::TypeMismatch {
+                path,
+                expected,
+                found,
+            } => write!(f, "Expected {} at '{}', found {}", expected, path, found),
+            NormaliseError::MissingRequired { path } => {
+                write!(f, "Required field '{}' is missing", path)
+            }
+            NormaliseError::UnionResolution {
+                path,
+                found,
+                branches,
+            } => write!(
+                f,
+                "Union [{}] at '{}' can't hold a {}",
+                branches.join(", "),
+                path,
+                found
+            ),
+            NormaliseError::UnknownField { path } => {
+                write!(f, "Unknown field '{}' is not in the schema", path)
+            }
+        }
+    }
+}
+
+impl std::error::Error for NormaliseError {}
+
+/// The errors found in one row by [`normalise_values_strict`].
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct RowErrors {
+    /// Index of the row among the input values.
+    pub row: usize,
+    /// Each error in the row, in document order.
+    pub errors: Vec<NormaliseError>,
+}
+
+/// Collects [`Coercion`]s (and, when normalising strictly, [`NormaliseError`]s) for
+/// one row, tracking the path being normalised.
+struct Report<'a> {
+    row: usize,
+    path: Vec<String>,
+    entries: &'a mut Vec<Coercion>,
+    errors: Option<&'a mut Vec<NormaliseError>>,
+}
+
+impl Report<'_> {
+    fn note(&mut self, original: &str, action: CoercionAction) {
+        self.entries.push(Coercion {
+            row: self.row,
+            path: self.path.join("."),
+            original: original.to_string(),
+            action,
+        });
+    }
+
+    /// Record the error `error` makes of the current path, if collecting errors.
+    fn fail(&mut self, error: impl FnOnce(String) -> NormaliseError) {
+        if let Some(errors) = self.errors.as_deref_mut() {
+            errors.push(error(self.path.join(".")));
+        }
+    }
+}
+
+fn value_kind(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn note(report: &mut Option<&mut Report>, original: &str, action: CoercionAction) {
+    if let Some(r) = report.as_deref_mut() {
+        r.note(original, action);
+    }
+}
+
+fn mismatch(report: &mut Option<&mut Report>, expected: &str, found: &str) {
+    if let Some(r) = report.as_deref_mut() {
+        r.fail(|path| NormaliseError::TypeMismatch {
+            path,
+            expected: expected.to_string(),
+            found: found.to_string(),
+        });
+    }
+}
+
+/// Whether `schema` is `"null"` or a union with a `"null"` branch.
+fn admits_null(schema: &Value) -> bool {
     match schema {
-        // Primitive types
-        Value::String(t) if t == "string" => match value {
-            Value::Null => Value::Null,
-            v @ Value::String(_) => v,
-            v => Value::String(v.to_string()),
+        Value::Array(types) => types.iter().any(|t| t == "null"),
+        t => t == "null",
+    }
+}
+
+/// Whether a record schema holds a tuple, its fields being positions.
+fn is_tuple_record(record: &serde_json::Map<String, Value>) -> bool {
+    record
+        .get("fields")
+        .and_then(Value::as_array)
+        .and_then(|fields| fields.first())
+        .and_then(|f| f.get("name").and_then(Value::as_str))
+        .is_some_and(|name| tuple_position(name).is_some())
+}
+
+/// The name of a union branch's type: a primitive's name, or a complex type's `type`.
+fn type_name(schema: &Value) -> String {
+    match schema {
+        Value::String(t) => t.clone(),
+        Value::Object(obj) => obj
+            .get("type")
+            .and_then(Value::as_str)
+            .unwrap_or("unknown")
+            .to_string(),
+        _ => "union".to_string(),
+    }
+}
+
+/// With `coerce_map_array`, turn an array of objects into one object of arrays
+/// (`[{"en": "a"}, {"en": "b"}]` to `{"en": ["a", "b"]}`) where `schema` is a
+/// record or map whose values are arrays. Other values are returned unchanged.
+fn fold_array_of_maps(
+    value: Value,
+    schema: &Value,
+    cfg: &NormaliseConfig,
+    report: &mut Option<&mut Report>,
+) -> Value {
+    let foldable = cfg.coerce_map_array
+        && matches!(&value, Value::Array(items)
+            if !items.is_empty() && items.iter().all(Value::is_object))
+        && expects_array_values(schema);
+    if !foldable {
+        return value;
+    }
+    let Value::Array(items) = value else {
+        unreachable!()
+    };
+    let mut folded = serde_json::Map::new();
+    for item in items {
+        if let Value::Object(entries) = item {
+            for (k, v) in entries {
+                if let Value::Array(values) = folded.entry(k).or_insert_with(|| json!([])) {
+                    values.push(v);
+                }
+            }
+        }
+    }
+    note(report, "array", CoercionAction::FoldedArrayOfMaps);
+    Value::Object(folded)
+}
+
+/// Whether every field of a record schema, or the values of a map schema, are
+/// (nullable) arrays.
+fn expects_array_values(schema: &Value) -> bool {
+    fn is_array(schema: &Value) -> bool {
+        match schema {
+            Value::Object(obj) => obj.get("type") == Some(&Value::String("array".into())),
+            Value::Array(types) => types.iter().any(is_array),
+            _ => false,
+        }
+    }
+    match schema.get("type").and_then(Value::as_str) {
+        Some("map") => schema.get("values").is_some_and(is_array),
+        Some("record") => schema
+            .get("fields")
+            .and_then(Value::as_array)
+            .is_some_and(|fields| {
+                !fields.is_empty() && fields.iter().all(|f| f.get("type").is_some_and(is_array))
+            }),
+        _ => false,
+    }
+}
+
+/// Parse a string into the scalar type a field was forced to (`x-forced-type`),
+/// whatever `coerce_string` says, and widen an integer in a field forced to a
+/// number. Returns whether a string was parsed; other values are left to the
+/// field's type.
+fn coerce_forced(value: Value, forced: &str) -> (Value, bool) {
+    let parsed = match (forced, &value) {
+        ("integer", Value::String(s)) => s.trim().parse::<i64>().ok().map(|i| json!(i)),
+        ("number", Value::String(s)) => s
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .filter(|f| f.is_finite())
+            .map(|f| json!(f)),
+        ("boolean", Value::String(s)) => match s.as_str() {
+            "true" | "1" => Some(Value::Bool(true)),
+            "false" | "0" => Some(Value::Bool(false)),
+            _ => None,
         },
+        ("number", Value::Number(n)) if !n.is_f64() => {
+            return (n.as_f64().map_or(value, |f| json!(f)), false)
+        }
+        _ => None,
+    };
+    match parsed {
+        Some(parsed) => (parsed, true),
+        None => (value, false),
+    }
+}
+
+/// The JSON key a record field is read from: its Avro `name`, unless it was
+/// renamed from an invalid one (see [`AvroNameStrategy`](crate::avro::AvroNameStrategy)).
+fn json_key<'a>(field: &'a Value, name: &'a str) -> &'a str {
+    field
+        .get(ORIGINAL_NAME_KEY)
+        .and_then(Value::as_str)
+        .unwrap_or(name)
+}
+
+/// The key a field is read from in rows folded with `key_fold`: with
+/// [`KeyFold::Preserve`], fields keep the spelling first seen but rows are
+/// lower-cased.
+fn folded_key(key: &str, key_fold: Option<KeyFold>) -> Cow<'_, str> {
+    match key_fold {
+        Some(KeyFold::Preserve) => Cow::Owned(key.to_lowercase()),
+        _ => Cow::Borrowed(key),
+    }
+}
+
+/// Cut a string longer than `max_string_len` and append the marker.
+fn limit_string(value: Value, cfg: &NormaliseConfig, report: &mut Option<&mut Report>) -> Value {
+    let limits = &cfg.value_limits;
+    match (value, limits.max_string_len) {
+        (Value::String(mut s), Some(max)) => {
+            if let Some((cut, _)) = s.char_indices().nth(max) {
+                s.truncate(cut);
+                s.push_str(&limits.marker);
+                note(report, "string", CoercionAction::TruncatedString);
+            }
+            Value::String(s)
+        }
+        (value, _) => value,
+    }
+}
+
+/// Normalise a field or map entry one level down, with `segment` appended to the
+/// reported path.
+fn normalise_child(
+    value: Value,
+    schema: &Value,
+    cfg: &NormaliseConfig,
+    field_name: Option<&str>,
+    segment: &str,
+    depth: usize,
+    report: Option<&mut Report>,
+) -> Value {
+    match report {
+        Some(r) => {
+            r.path.push(segment.to_string());
+            let v = normalise_inner(value, schema, cfg, field_name, depth + 1, Some(&mut *r));
+            r.path.pop();
+            v
+        }
+        None => normalise_inner(value, schema, cfg, field_name, depth + 1, None),
+    }
+}
+
+fn normalise_inner(
+    value: Value,
+    schema: &Value,
+    cfg: &NormaliseConfig,
+    field_name: Option<&str>,
+    depth: usize,
+    mut report: Option<&mut Report>,
+) -> Value {
+    use CoercionAction::*;
+
+    if cfg.value_limits.max_depth.is_some_and(|max| depth > max) && !value.is_null() {
+        note(&mut report, value_kind(&value), TruncatedDepth);
+        return Value::Null;
+    }
+
+    match schema {
+        // Primitive types
+        Value::String(t) if t == "string" => {
+            let value = match value {
+                Value::Null => Value::Null,
+                v @ Value::String(_) => v,
+                Value::Object(mut m)
+                    if cfg.simplify_labels
+                        && m.contains_key("language")
+                        && m.contains_key("value") =>
+                {
+                    note(&mut report, "object", UnwrappedLabel);
+                    match m.remove("value") {
+                        Some(v @ Value::String(_)) => v,
+                        Some(Value::Null) | None => Value::Null,
+                        Some(v) => Value::String(v.to_string()),
+                    }
+                }
+                v => {
+                    note(&mut report, value_kind(&v), Stringified);
+                    Value::String(v.to_string())
+                }
+            };
+            limit_string(value, cfg, &mut report)
+        }
 
         Value::String(t) if t == "int" || t == "long" => match value {
             Value::Null => Value::Null,
             Value::Number(n) if n.is_i64() => Value::Number(n),
-            Value::String(s) if cfg.coerce_string => {
-                s.parse::<i64>().map(|i| json!(i)).unwrap_or(Value::Null)
+            Value::String(s) if cfg.coerce_string => match s.parse::<i64>() {
+                Ok(i) => {
+                    note(&mut report, "string", CoercedFromString);
+                    json!(i)
+                }
+                Err(_) => {
+                    note(&mut report, "string", Nulled);
+                    mismatch(&mut report, t, "string");
+                    Value::Null
+                }
+            },
+            v => {
+                note(&mut report, value_kind(&v), Nulled);
+                mismatch(&mut report, t, value_kind(&v));
+                Value::Null
             }
-            _ => Value::Null,
         },
 
         Value::String(t) if t == "double" || t == "float" => match value {
             Value::Null => Value::Null,
             Value::Number(n) if n.is_f64() => Value::Number(n),
-            Value::String(s) if cfg.coerce_string => {
-                s.parse::<f64>().map(|f| json!(f)).unwrap_or(Value::Null)
+            Value::String(s)
+                if cfg.nonfinite_policy == Some(NonFinitePolicy::String) && is_nonfinite(&s) =>
+            {
+                Value::String(s)
+            }
+            Value::String(s) if cfg.coerce_string => match s.parse::<f64>() {
+                Ok(f) => {
+                    note(&mut report, "string", CoercedFromString);
+                    json!(f)
+                }
+                Err(_) => {
+                    note(&mut report, "string", Nulled);
+                    mismatch(&mut report, t, "string");
+                    Value::Null
+                }
+            },
+            v => {
+                note(&mut report, value_kind(&v), Nulled);
+                mismatch(&mut report, t, value_kind(&v));
+                Value::Null
             }
-            _ => Value::Null,
         },
 
         Value::String(t) if t == "boolean" => match value {
             Value::Null => Value::Null,
             Value::Bool(b) => Value::Bool(b),
-            Value::String(s) if cfg.coerce_string => match s.as_str() {
-                "true" | "1" => Value::Bool(true),
-                "false" | "0" => Value::Bool(false),
-                _ => Value::Null,
-            },
-            _ => Value::Null,
+            Value::String(s) if cfg.coerce_string => {
+                let parsed = match s.as_str() {
+                    "true" | "1" => Some(true),
+                    "false" | "0" => Some(false),
+                    _ => None,
+                };
+                let action = if parsed.is_some() {
+                    CoercedFromString
+                } else {
+                    Nulled
+                };
+                note(&mut report, "string", action);
+                if parsed.is_none() {
+                    mismatch(&mut report, t, "string");
+                }
+                parsed.map_or(Value::Null, Value::Bool)
+            }
+            v => {
+                note(&mut report, value_kind(&v), Nulled);
+                mismatch(&mut report, t, value_kind(&v));
+                Value::Null
+            }
         },
 
         // Record
         Value::Object(obj) if obj.get("type") == Some(&Value::String("record".into())) => {
+            let value = fold_array_of_maps(value, schema, cfg, &mut report);
             let mut out = serde_json::Map::new();
+            let mut promoted = false;
+            // Keys read into fields of another name, which aren't unknown
+            let mut renamed_keys: HashSet<String> = HashSet::new();
             if let Some(Value::Array(fields)) = obj.get("fields") {
                 for f in fields {
                     if let (Some(Value::String(name)), Some(field_schema)) =
                         (f.get("name"), f.get("type"))
                     {
+                        let key = folded_key(json_key(f, name), cfg.key_fold);
+                        if key != *name {
+                            renamed_keys.insert(key.to_string());
+                        }
+                        // `None` where the field is missing from an object or tuple
                         let val = match &value {
-                            Value::Object(m) => m.get(name).cloned().unwrap_or(Value::Null),
+                            Value::Object(m) => m.get(&*key).cloned(),
+                            // A tuple, inferred with `detect_tuples` as positional fields
+                            Value::Array(items) => match tuple_position(name) {
+                                Some(i) => items.get(i).cloned(),
+                                None => Some(Value::Null),
+                            },
                             // Handle scalar promotion case
-                            scalar_value => {
+                            scalar_value => Some({
                                 // If this is a synthetic field that matches the scalar type
                                 if name.contains("__") {
                                     let type_suffix = name.split("__").last().unwrap_or("");
@@ -211,6 +830,7 @@ pub fn normalise_value(
                                     );
 
                                     if matches_type {
+                                        promoted = true;
                                         scalar_value.clone()
                                     } else {
                                         Value::Null
@@ -218,15 +838,75 @@ pub fn normalise_value(
                                 } else {
                                     Value::Null
                                 }
+                            }),
+                        };
+                        let val = val.unwrap_or_else(|| {
+                            if !admits_null(field_schema) && f.get("default").is_none() {
+                                if let Some(r) = report.as_deref_mut() {
+                                    r.path.push(name.clone());
+                                    r.fail(|path| NormaliseError::MissingRequired { path });
+                                    r.path.pop();
+                                }
+                            }
+                            Value::Null
+                        });
+                        let val = match f.get(FORCED_TYPE_KEY).and_then(Value::as_str) {
+                            Some(forced) => {
+                                let (val, parsed) = coerce_forced(val, forced);
+                                if let (true, Some(r)) = (parsed, report.as_deref_mut()) {
+                                    r.path.push(name.clone());
+                                    r.note("string", CoercionAction::CoercedFromString);
+                                    r.path.pop();
+                                }
+                                val
                             }
+                            None => val,
                         };
                         out.insert(
                             name.clone(),
-                            normalise_value(val, field_schema, cfg, Some(name)),
+                            normalise_child(
+                                val,
+                                field_schema,
+                                cfg,
+                                Some(name),
+                                name,
+                                depth,
+                                report.as_deref_mut(),
+                            ),
                         );
                     }
                 }
             }
+            match value {
+                Value::Object(m)
+                    if cfg.unknown_fields == UnknownFieldPolicy::Keep || report.is_some() =>
+                {
+                    for (k, v) in m {
+                        if out.contains_key(&k) || renamed_keys.contains(&k) {
+                            continue;
+                        }
+                        if cfg.unknown_fields == UnknownFieldPolicy::Keep {
+                            out.insert(k, v);
+                        } else if let Some(r) = report.as_deref_mut() {
+                            r.path.push(k);
+                            r.note(value_kind(&v), DroppedUnknownField);
+                            r.path.pop();
+                        }
+                    }
+                }
+                Value::Object(_) | Value::Null => {}
+                scalar => {
+                    note(
+                        &mut report,
+                        value_kind(&scalar),
+                        if promoted { PromotedScalar } else { Nulled },
+                    );
+                    let tuple = scalar.is_array() && is_tuple_record(obj);
+                    if !promoted && !tuple {
+                        mismatch(&mut report, "record", value_kind(&scalar));
+                    }
+                }
+            }
             Value::Object(out)
         }
 
@@ -234,15 +914,54 @@ pub fn normalise_value(
         Value::Object(obj) if obj.get("type") == Some(&Value::String("array".into())) => {
             let default_items = Value::String("string".into());
             let items_schema = obj.get("items").unwrap_or(&default_items);
+            let value = match value {
+                Value::Object(entries)
+                    if cfg.sparse_array_detection && is_sparse_array(&entries) =>
+                {
+                    note(&mut report, "object", UnpackedSparseArray);
+                    Value::Array(unpack_sparse_array(entries))
+                }
+                v => v,
+            };
             match value {
                 Value::Null => Value::Null,
-                Value::Array(arr) if arr.is_empty() && cfg.empty_as_null => Value::Null,
-                Value::Array(arr) => Value::Array(
-                    arr.into_iter()
-                        .map(|v| normalise_value(v, items_schema, cfg, field_name))
-                        .collect(),
-                ),
-                v => Value::Array(vec![normalise_value(v, items_schema, cfg, field_name)]),
+                Value::Array(arr) if arr.is_empty() && cfg.empty_as_null => {
+                    note(&mut report, "array", EmptyToNull);
+                    Value::Null
+                }
+                Value::Array(mut arr) => {
+                    if let Some(max) = cfg.value_limits.max_array_items {
+                        if arr.len() > max {
+                            arr.truncate(max);
+                            note(&mut report, "array", TruncatedArray);
+                        }
+                    }
+                    Value::Array(
+                        arr.into_iter()
+                            .map(|v| {
+                                normalise_inner(
+                                    v,
+                                    items_schema,
+                                    cfg,
+                                    field_name,
+                                    depth + 1,
+                                    report.as_deref_mut(),
+                                )
+                            })
+                            .collect(),
+                    )
+                }
+                v => {
+                    note(&mut report, value_kind(&v), WrappedInArray);
+                    Value::Array(vec![normalise_inner(
+                        v,
+                        items_schema,
+                        cfg,
+                        field_name,
+                        depth + 1,
+                        report,
+                    )])
+                }
             }
         }
 
@@ -251,43 +970,68 @@ pub fn normalise_value(
             let default_values = Value::String("string".into());
             let values_schema = obj.get("values").unwrap_or(&default_values);
 
-            match value {
+            match fold_array_of_maps(value, schema, cfg, &mut report) {
                 Value::Null => Value::Null,
 
-                Value::Object(m) if m.is_empty() && cfg.empty_as_null => Value::Null,
+                Value::Object(m) if m.is_empty() && cfg.empty_as_null => {
+                    note(&mut report, "object", EmptyToNull);
+                    Value::Null
+                }
 
                 Value::Object(m) => {
                     let mut out = serde_json::Map::new();
+                    let integer_keys = has_integer_keys(obj);
 
-                    if values_schema.get("type") == Some(&Value::String("object".into())) {
-                        // --- Map of records ---
-                        for (k, v) in m {
-                            let normalised_record =
-                                normalise_value(v, values_schema, cfg, Some(&k));
-                            out.insert(k, normalised_record);
-                        }
-                    } else {
-                        // --- Map of scalars (existing behaviour) ---
-                        for (k, v) in m {
-                            let normalised_value = normalise_value(v, values_schema, cfg, Some(&k));
-                            out.insert(k, normalised_value);
-                        }
+                    for (k, v) in m {
+                        let k = match integer_keys {
+                            true if !is_integer_key(&k) => {
+                                if cfg.map_encoding == MapEncoding::KeyValueEntries {
+                                    if let Some(r) = report.as_deref_mut() {
+                                        r.path.push(k);
+                                        r.note(value_kind(&v), DroppedMapEntry);
+                                        r.path.pop();
+                                    }
+                                    continue;
+                                }
+                                k
+                            }
+                            true if cfg.canonical_numeric_keys => {
+                                let canonical =
+                                    k.parse::<i64>().map_or(k.clone(), |i| i.to_string());
+                                if canonical != k {
+                                    note(&mut report, "string", CanonicalisedKey);
+                                }
+                                canonical
+                            }
+                            _ => k,
+                        };
+                        let normalised_value = normalise_child(
+                            v,
+                            values_schema,
+                            cfg,
+                            Some(&k),
+                            &k,
+                            depth,
+                            report.as_deref_mut(),
+                        );
+                        out.insert(k, normalised_value);
                     }
 
-                    apply_map_encoding(out, cfg.map_encoding)
+                    apply_map_encoding(out, cfg.map_encoding, integer_keys)
                 }
 
                 v => {
                     // Scalar fallback: wrap as {"default": v}
+                    note(&mut report, value_kind(&v), WrappedInMap);
                     let mut synthetic = serde_json::Map::new();
                     let scalar_type = get_scalar_type_from_value(&v);
                     let wrapped_key =
                         make_promoted_scalar_key(field_name.unwrap_or(""), scalar_type);
                     synthetic.insert(
                         wrapped_key,
-                        normalise_value(v, values_schema, cfg, field_name),
+                        normalise_inner(v, values_schema, cfg, field_name, depth + 1, report),
                     );
-                    apply_map_encoding(synthetic, cfg.map_encoding)
+                    apply_map_encoding(synthetic, cfg.map_encoding, has_integer_keys(obj))
                 }
             }
         }
@@ -295,17 +1039,32 @@ pub fn normalise_value(
         // Union
         Value::Array(types) => {
             // Typical Avro union is ["null", T]
-            if types.iter().any(|t| t == "null") {
-                if value.is_null() {
-                    Value::Null
-                } else {
-                    // normalise against the first non-null branch
-                    let branch = types.iter().find(|t| *t != "null").unwrap();
-                    normalise_value(value, branch, cfg, field_name)
+            let nullable = types.iter().any(|t| t == "null");
+            if nullable && value.is_null() {
+                return Value::Null;
+            }
+            // normalise against the first non-null branch, if there is one
+            let Some(branch) = types.iter().find(|t| !nullable || *t != "null") else {
+                return Value::Null;
+            };
+            let branches = types.iter().filter(|t| *t != "null").count();
+            match report {
+                Some(r) if branches > 1 && r.errors.is_some() => {
+                    // Fail the union as a whole if its first branch can't hold the value
+                    let found = value_kind(&value);
+                    let before = r.errors.as_ref().map_or(0, |errors| errors.len());
+                    let v = normalise_inner(value, branch, cfg, field_name, depth, Some(&mut *r));
+                    if let Some(errors) = r.errors.as_deref_mut().filter(|e| e.len() > before) {
+                        errors.truncate(before);
+                        r.fail(|path| NormaliseError::UnionResolution {
+                            path,
+                            found: found.to_string(),
+                            branches: types.iter().map(type_name).collect(),
+                        });
+                    }
+                    v
                 }
-            } else {
-                // pick first type
-                normalise_value(value, &types[0], cfg, field_name)
+                report => normalise_inner(value, branch, cfg, field_name, depth, report),
             }
         }
 
@@ -314,22 +1073,594 @@ pub fn normalise_value(
     }
 }
 
+/// Change the encoding of the maps in an already normalised value, as located by
+/// its Avro `schema`, from `from` to `to`.
+///
+/// Records, arrays and unions are walked without being changed otherwise, so
+/// normalised rows can be re-encoded for a consumer that expects another map
+/// encoding (e.g. Polars, which decodes `KeyValueEntries`) without normalising
+/// them again.
+pub fn reencode_maps(value: Value, schema: &Value, from: MapEncoding, to: MapEncoding) -> Value {
+    if value.is_null() || from == to {
+        return value;
+    }
+    match schema {
+        Value::Object(obj) if obj.get("type") == Some(&Value::String("record".into())) => {
+            let Value::Object(mut m) = value else {
+                return value;
+            };
+            if let Some(Value::Array(fields)) = obj.get("fields") {
+                for f in fields {
+                    if let (Some(Value::String(name)), Some(field_schema)) =
+                        (f.get("name"), f.get("type"))
+                    {
+                        if let Some(v) = m.get_mut(name) {
+                            *v = reencode_maps(v.take(), field_schema, from, to);
+                        }
+                    }
+                }
+            }
+            Value::Object(m)
+        }
+        Value::Object(obj) if obj.get("type") == Some(&Value::String("array".into())) => {
+            match (value, obj.get("items")) {
+                (Value::Array(items), Some(items_schema)) => Value::Array(
+                    items
+                        .into_iter()
+                        .map(|v| reencode_maps(v, items_schema, from, to))
+                        .collect(),
+                ),
+                (value, _) => value,
+            }
+        }
+        Value::Object(obj) if obj.get("type") == Some(&Value::String("map".into())) => {
+            let Some(entries) = decode_map_encoding(value, from) else {
+                return Value::Null;
+            };
+            let values_schema = obj.get("values").cloned().unwrap_or_default();
+            let entries = entries
+                .into_iter()
+                .map(|(k, v)| (k, reencode_maps(v, &values_schema, from, to)))
+                .collect();
+            apply_map_encoding(entries, to, has_integer_keys(obj))
+        }
+        Value::Array(types) => match types.iter().find(|t| *t != "null") {
+            Some(branch) => reencode_maps(value, branch, from, to),
+            None => value,
+        },
+        _ => value,
+    }
+}
+
+/// The entries of a map written with `encoding`, or `None` if it isn't one.
+fn decode_map_encoding(
+    value: Value,
+    encoding: MapEncoding,
+) -> Option<serde_json::Map<String, Value>> {
+    match (encoding, value) {
+        (MapEncoding::Mapping, Value::Object(m)) => Some(m),
+        (MapEncoding::Entries, Value::Array(items)) => {
+            let mut m = serde_json::Map::new();
+            for item in items {
+                let Value::Object(entry) = item else {
+                    return None;
+                };
+                m.extend(entry);
+            }
+            Some(m)
+        }
+        (MapEncoding::KeyValueEntries, Value::Array(items)) => {
+            let mut m = serde_json::Map::new();
+            for item in items {
+                let Value::Object(mut entry) = item else {
+                    return None;
+                };
+                let key = match entry.remove("key") {
+                    Some(Value::String(key)) => key,
+                    Some(Value::Number(key)) => key.to_string(),
+                    _ => return None,
+                };
+                m.insert(key, entry.remove("value").unwrap_or(Value::Null));
+            }
+            Some(m)
+        }
+        _ => None,
+    }
+}
+
+/// Rewrite the maps of an Avro or JSON Schema `schema` as the lists that
+/// normalising with `encoding` writes them as, so a schema emitted next to the
+/// normalised rows describes them (for engines without a map type).
+///
+/// `Entries` maps become arrays of single-entry maps, and `KeyValueEntries` maps
+/// become arrays of `{key, value}` records, named `<field>_entry` in Avro. Maps
+/// annotated `"x-key-type": "integer"` get a `long` (`integer`) key.
+/// `Mapping` leaves the schema as it is.
+pub fn encode_map_schemas(schema: &Value, encoding: MapEncoding) -> Value {
+    let mut schema = schema.clone();
+    if encoding == MapEncoding::Mapping {
+        return schema;
+    }
+    if crate::lint::is_avro(&schema) {
+        let mut names = HashSet::new();
+        collect_avro_names(&schema, &mut names);
+        encode_avro_maps(&mut schema, "map", encoding, &mut names);
+    } else {
+        encode_json_maps(&mut schema, encoding);
+    }
+    schema
+}
+
+/// The names of the named types (records, enums, fixed) in an Avro schema.
+fn collect_avro_names(schema: &Value, names: &mut HashSet<String>) {
+    match schema {
+        Value::Object(obj) => {
+            if let Some(Value::String(name)) = obj.get("name") {
+                names.insert(name.clone());
+            }
+            for key in ["items", "values"] {
+                if let Some(child) = obj.get(key) {
+                    collect_avro_names(child, names);
+                }
+            }
+            if let Some(Value::Array(fields)) = obj.get("fields") {
+                for f in fields.iter().filter_map(|f| f.get("type")) {
+                    collect_avro_names(f, names);
+                }
+            }
+        }
+        Value::Array(types) => types.iter().for_each(|t| collect_avro_names(t, names)),
+        _ => {}
+    }
+}
+
+fn encode_avro_maps(
+    schema: &mut Value,
+    field_name: &str,
+    encoding: MapEncoding,
+    names: &mut HashSet<String>,
+) {
+    match schema {
+        Value::Object(obj) => match obj.get("type").and_then(Value::as_str) {
+            Some("record") => {
+                if let Some(Value::Array(fields)) = obj.get_mut("fields") {
+                    for f in fields.iter_mut().filter_map(Value::as_object_mut) {
+                        let name = f.get("name").and_then(Value::as_str).unwrap_or("map");
+                        let name = name.to_string();
+                        if let Some(field_schema) = f.get_mut("type") {
+                            encode_avro_maps(field_schema, &name, encoding, names);
+                        }
+                    }
+                }
+            }
+            Some("array") => {
+                if let Some(items) = obj.get_mut("items") {
+                    encode_avro_maps(items, field_name, encoding, names);
+                }
+            }
+            Some("map") => {
+                // Name the outer entry record before any nested in its values
+                let mut name = format!("{}_entry", field_name);
+                let mut n = 1;
+                while names.contains(&name) {
+                    n += 1;
+                    name = format!("{}_entry{}", field_name, n);
+                }
+                if encoding == MapEncoding::KeyValueEntries {
+                    names.insert(name.clone());
+                }
+                let key_type = if has_integer_keys(obj) {
+                    "long"
+                } else {
+                    "string"
+                };
+                let mut values = obj.remove("values").unwrap_or(Value::Null);
+                encode_avro_maps(&mut values, field_name, encoding, names);
+                let items = match encoding {
+                    MapEncoding::Entries => json!({"type": "map", "values": values}),
+                    _ => {
+                        json!({
+                            "type": "record",
+                            "name": name,
+                            "fields": [
+                                {"name": "key", "type": key_type},
+                                {"name": "value", "type": values}
+                            ]
+                        })
+                    }
+                };
+                *schema = json!({"type": "array", "items": items});
+            }
+            _ => {}
+        },
+        Value::Array(types) => types
+            .iter_mut()
+            .for_each(|t| encode_avro_maps(t, field_name, encoding, names)),
+        _ => {}
+    }
+}
+
+fn encode_json_maps(schema: &mut Value, encoding: MapEncoding) {
+    let obj = match schema {
+        Value::Object(obj) => obj,
+        Value::Array(items) => {
+            items
+                .iter_mut()
+                .for_each(|item| encode_json_maps(item, encoding));
+            return;
+        }
+        _ => return,
+    };
+    for (key, child) in obj.iter_mut() {
+        match key.as_str() {
+            "properties" => {
+                if let Value::Object(props) = child {
+                    props
+                        .values_mut()
+                        .for_each(|prop| encode_json_maps(prop, encoding));
+                }
+            }
+            "additionalProperties" | "items" | "anyOf" | "oneOf" | "allOf" => {
+                encode_json_maps(child, encoding)
+            }
+            _ => {}
+        }
+    }
+
+    let is_object = match obj.get("type") {
+        Some(Value::String(t)) => t == "object",
+        Some(Value::Array(types)) => types.iter().any(|t| t == "object"),
+        _ => false,
+    };
+    let is_map = is_object
+        && !obj.contains_key("properties")
+        && matches!(obj.get("additionalProperties"), Some(Value::Object(_)));
+    if !is_map {
+        return;
+    }
+    let key_type = if has_integer_keys(obj) {
+        "integer"
+    } else {
+        "string"
+    };
+    obj.remove(MAP_KEY_TYPE_KEY);
+    let values = obj.remove("additionalProperties").unwrap_or_default();
+    let items = match encoding {
+        MapEncoding::Entries => json!({
+            "type": "object",
+            "additionalProperties": values,
+            "minProperties": 1,
+            "maxProperties": 1
+        }),
+        _ => json!({
+            "type": "object",
+            "properties": {"key": {"type": key_type}, "value": values},
+            "required": ["key", "value"]
+        }),
+    };
+    let array_type = match obj.remove("type") {
+        Some(Value::Array(types)) => Value::Array(
+            types
+                .into_iter()
+                .map(|t| if t == "object" { json!("array") } else { t })
+                .collect(),
+        ),
+        _ => json!("array"),
+    };
+    obj.insert("type".to_string(), array_type);
+    obj.insert("items".to_string(), items);
+}
+
+/// Apply `f` to each row with its index, in order. With the `parallel` feature the
+/// rows are spread over `cfg.threads` threads.
+#[cfg_attr(not(feature = "parallel"), allow(unused_variables))]
+fn map_rows<T, F>(values: Vec<Value>, cfg: &NormaliseConfig, f: F) -> Vec<T>
+where
+    T: Send,
+    F: Fn(usize, Value) -> T + Send + Sync,
+{
+    #[cfg(feature = "parallel")]
+    if values.len() > 1 && cfg.threads != Some(1) {
+        use rayon::prelude::*;
+        let run = || {
+            values
+                .into_par_iter()
+                .enumerate()
+                .map(|(row, v)| f(row, v))
+                .collect()
+        };
+        return match cfg.threads.and_then(thread_pool) {
+            Some(pool) => pool.install(run),
+            None => run(),
+        };
+    }
+    values
+        .into_iter()
+        .enumerate()
+        .map(|(row, v)| f(row, v))
+        .collect()
+}
+
+/// The pool of `threads` threads to normalise rows on. The last pool built is kept
+/// and reused while runs ask for the same thread count, and replaced when one asks
+/// for another. None if it can't be built, in which case rayon's global pool is used.
+#[cfg(feature = "parallel")]
+fn thread_pool(threads: usize) -> Option<std::sync::Arc<rayon::ThreadPool>> {
+    static POOL: CachedPool = std::sync::Mutex::new(None);
+    cached_pool(&POOL, threads)
+}
+
+#[cfg(feature = "parallel")]
+type CachedPool = std::sync::Mutex<Option<std::sync::Arc<rayon::ThreadPool>>>;
+
+/// The pool in `slot` if it has `threads` threads, otherwise a new one put in its place.
+#[cfg(feature = "parallel")]
+fn cached_pool(slot: &CachedPool, threads: usize) -> Option<std::sync::Arc<rayon::ThreadPool>> {
+    use std::sync::Arc;
+    let mut cached = slot.lock().ok()?;
+    if let Some(pool) = cached
+        .as_ref()
+        .filter(|p| p.current_num_threads() == threads)
+    {
+        return Some(Arc::clone(pool));
+    }
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .build()
+        .ok()?;
+    let pool = Arc::new(pool);
+    *cached = Some(Arc::clone(&pool));
+    Some(pool)
+}
+
 /// Normalise a list of JSON values (e.g. a column in Polars).
 pub fn normalise_values(values: Vec<Value>, schema: &Value, cfg: &NormaliseConfig) -> Vec<Value> {
-    values
+    map_rows(values, cfg, |_, v| {
+        let v = wrap_row(v, schema, cfg);
+        normalise_value(v, schema, cfg, None) // Only the root call passes field name as None
+    })
+}
+
+/// Normalise a list of JSON values, failing if `cfg.unknown_fields` is
+/// [`UnknownFieldPolicy::Error`] and any value has a field the schema doesn't declare.
+///
+/// The error is that of the first failing row.
+pub fn try_normalise_values(
+    values: Vec<Value>,
+    schema: &Value,
+    cfg: &NormaliseConfig,
+) -> Result<Vec<Value>, String> {
+    map_rows(values, cfg, |_, v| normalise_row(v, schema, cfg, None))
         .into_iter()
-        .map(|mut v| {
-            // Apply wrap_root if requested
-            if let Some(ref field) = cfg.wrap_root {
-                v = Value::Object(
-                    std::iter::once((field.clone(), v)).collect::<serde_json::Map<String, Value>>(),
-                );
-            }
-            normalise_value(v, schema, cfg, None) // Only the root call passes field name as None
-        })
+        .map(|row| row.map_err(|e| e.to_string()))
         .collect()
 }
 
+/// Normalise like [`try_normalise_values`], also returning a [`Coercion`] for each
+/// value that had to be changed to fit the schema, in row order.
+pub fn normalise_values_with_report(
+    values: Vec<Value>,
+    schema: &Value,
+    cfg: &NormaliseConfig,
+) -> Result<(Vec<Value>, Vec<Coercion>), String> {
+    let rows = map_rows(values, cfg, |row, v| {
+        let mut coercions = Vec::new();
+        let mut report = Report {
+            row,
+            path: Vec::new(),
+            entries: &mut coercions,
+            errors: None,
+        };
+        normalise_row(v, schema, cfg, Some(&mut report))
+            .map(|v| (v, coercions))
+            .map_err(|e| e.to_string())
+    });
+    let mut normalised = Vec::with_capacity(rows.len());
+    let mut coercions = Vec::new();
+    for row in rows {
+        let (v, row_coercions) = row?;
+        normalised.push(v);
+        coercions.extend(row_coercions);
+    }
+    Ok((normalised, coercions))
+}
+
+/// Normalise a row like [`try_normalise_values`] does, failing with the first
+/// [`NormaliseError`] rather than nulling or dropping what doesn't fit the schema.
+///
+/// Coercions that keep the value (parsing strings with `coerce_string`,
+/// stringifying, wrapping in an array or map, etc.) aren't errors.
+pub fn normalise_value_strict(
+    value: Value,
+    schema: &Value,
+    cfg: &NormaliseConfig,
+) -> Result<Value, NormaliseError> {
+    strict_row(0, value, schema, cfg).map_err(|errors| errors.into_iter().next().unwrap())
+}
+
+/// Normalise a list of JSON values strictly, like [`normalise_value_strict`], failing
+/// with every error of every row that has any, in row order.
+pub fn normalise_values_strict(
+    values: Vec<Value>,
+    schema: &Value,
+    cfg: &NormaliseConfig,
+) -> Result<Vec<Value>, Vec<RowErrors>> {
+    let rows = map_rows(values, cfg, |row, v| strict_row(row, v, schema, cfg));
+    let mut normalised = Vec::with_capacity(rows.len());
+    let mut failed = Vec::new();
+    for (row, result) in rows.into_iter().enumerate() {
+        match result {
+            Ok(v) => normalised.push(v),
+            Err(errors) => failed.push(RowErrors { row, errors }),
+        }
+    }
+    match failed.is_empty() {
+        true => Ok(normalised),
+        false => Err(failed),
+    }
+}
+
+/// Normalise a row, collecting its [`NormaliseError`]s (never empty on failure).
+fn strict_row(
+    row: usize,
+    value: Value,
+    schema: &Value,
+    cfg: &NormaliseConfig,
+) -> Result<Value, Vec<NormaliseError>> {
+    let mut coercions = Vec::new();
+    let mut errors = Vec::new();
+    let mut report = Report {
+        row,
+        path: Vec::new(),
+        entries: &mut coercions,
+        errors: Some(&mut errors),
+    };
+    let value = normalise_row(value, schema, cfg, Some(&mut report)).map_err(|e| vec![e])?;
+    match errors.is_empty() {
+        true => Ok(value),
+        false => Err(errors),
+    }
+}
+
+/// Wrap a row under `wrap_root` if set (unless it is already wrapped), otherwise
+/// wrap a bare root under `root_value_field` when the schema expects a record, then
+/// prune it to `select_paths`.
+fn wrap_row(value: Value, schema: &Value, cfg: &NormaliseConfig) -> Value {
+    let mut value = match cfg.wrap_root {
+        Some(ref path) if is_wrapped(path, &value) => value,
+        Some(ref path) => wrap_root_value(path, value),
+        None if is_bare_root(&value) && matches!(schema["type"].as_str(), Some("record")) => {
+            wrap_root_value(&cfg.root_value_field, value)
+        }
+        None => value,
+    };
+    select_fields(&mut value, &cfg.select_paths);
+    value
+}
+
+/// Apply `wrap_root` (or wrap a bare root), check for unknown fields if they're
+/// an error, then normalise.
+fn normalise_row(
+    value: Value,
+    schema: &Value,
+    cfg: &NormaliseConfig,
+    report: Option<&mut Report>,
+) -> Result<Value, NormaliseError> {
+    let mut value = value;
+    if let Some(fold) = cfg.key_fold {
+        fold_keys(&mut value, fold);
+    }
+    let value = wrap_row(value, schema, cfg);
+    if cfg.unknown_fields == UnknownFieldPolicy::Error {
+        if let Some(path) = unknown_field(&value, schema, cfg.key_fold) {
+            return Err(NormaliseError::UnknownField { path });
+        }
+    }
+    // Only the root call passes field name as None
+    Ok(normalise_inner(value, schema, cfg, None, 1, report))
+}
+
+/// Find the dotted path of the first field in `value` that `schema` doesn't declare.
+///
+/// Map keys appear in the path as they do in the input; array elements add no segment.
+pub fn find_unknown_field(value: &Value, schema: &Value) -> Option<String> {
+    unknown_field(value, schema, None)
+}
+
+/// [`find_unknown_field`] for a row whose keys were folded with `key_fold`.
+fn unknown_field(value: &Value, schema: &Value, key_fold: Option<KeyFold>) -> Option<String> {
+    fn walk<'a>(
+        value: &'a Value,
+        schema: &Value,
+        key_fold: Option<KeyFold>,
+        path: &mut Vec<&'a str>,
+    ) -> Option<String> {
+        match schema {
+            Value::Object(obj) => match obj.get("type").and_then(Value::as_str) {
+                Some("record") => {
+                    let Value::Object(m) = value else {
+                        return None;
+                    };
+                    let fields = obj.get("fields").and_then(Value::as_array);
+                    for (k, v) in m {
+                        path.push(k);
+                        let field = fields.and_then(|fs| {
+                            fs.iter().find(|f| {
+                                f.get("name").and_then(Value::as_str).is_some_and(|name| {
+                                    folded_key(json_key(f, name), key_fold) == *k
+                                })
+                            })
+                        });
+                        match field {
+                            None => return Some(path.join(".")),
+                            Some(f) => {
+                                if let Some(found) = walk(v, &f["type"], key_fold, path) {
+                                    return Some(found);
+                                }
+                            }
+                        }
+                        path.pop();
+                    }
+                    None
+                }
+                Some("array") => match value {
+                    Value::Array(items) => items
+                        .iter()
+                        .find_map(|v| walk(v, &obj["items"], key_fold, path)),
+                    v => walk(v, &obj["items"], key_fold, path),
+                },
+                Some("map") => {
+                    let Value::Object(m) = value else {
+                        return None;
+                    };
+                    for (k, v) in m {
+                        path.push(k);
+                        if let Some(found) = walk(v, &obj["values"], key_fold, path) {
+                            return Some(found);
+                        }
+                        path.pop();
+                    }
+                    None
+                }
+                _ => None,
+            },
+            // Unions normalise against the first non-null branch, so check that one
+            Value::Array(types) => types
+                .iter()
+                .find(|t| *t != "null")
+                .and_then(|branch| walk(value, branch, key_fold, path)),
+            _ => None,
+        }
+    }
+    walk(value, schema, key_fold, &mut Vec::new())
+}
+
+/// Normalise values like [`normalise_values`], stopping once a resource limit is hit.
+///
+/// Row sizes for `max_bytes` are measured as the compact JSON length of each input
+/// value. Returns the normalised rows (all of them, or the prefix admitted before
+/// the limit with [`crate::LimitAction::Partial`]) and the limit that was hit.
+pub fn normalise_values_with_limits(
+    values: Vec<Value>,
+    schema: &Value,
+    cfg: &NormaliseConfig,
+    limits: &ResourceLimits,
+) -> Result<(Vec<Value>, Option<LimitExceeded>), String> {
+    let mut tracker = LimitTracker::new(limits);
+    let mut out = Vec::with_capacity(values.len());
+    for value in values {
+        let row_bytes = match limits.max_bytes {
+            Some(_) => serde_json::to_string(&value).map_or(0, |s| s.len()),
+            None => 0,
+        };
+        if let Err(exceeded) = tracker.admit(row_bytes) {
+            return Ok((out, Some(tracker.resolve(exceeded)?)));
+        }
+        out.extend(try_normalise_values(vec![value], schema, cfg)?);
+    }
+    Ok((out, None))
+}
+
 #[cfg(test)]
 mod tests {
     include!("tests/normalise.rs");
@@ -1,15 +1,21 @@
 //! Parquet file I/O for reading and writing string columns
 
-use arrow::array::{Array, StringArray};
+use arrow::array::{Array, GenericStringArray, LargeStringArray, OffsetSizeTrait, StringArray};
 use arrow::datatypes::{DataType, Field, Schema};
+use arrow::json::ReaderBuilder;
 use arrow::record_batch::RecordBatch;
-use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
-use parquet::arrow::ArrowWriter;
+use parquet::arrow::arrow_reader::{ParquetRecordBatchReader, ParquetRecordBatchReaderBuilder};
+use parquet::arrow::{ArrowWriter, ProjectionMask};
 use parquet::file::properties::WriterProperties;
+use rayon::prelude::*;
+use serde_json::Value;
 use std::collections::HashMap;
 use std::fs::File;
 use std::sync::Arc;
 
+/// Rows per record batch when streaming strings with [`read_string_column_batches`].
+pub const DEFAULT_READ_BATCH_SIZE: usize = 8192;
+
 /// Read a string column from a Parquet file
 ///
 /// # Arguments
@@ -25,6 +31,87 @@ use std::sync::Arc;
 /// - Column doesn't exist
 /// - Column is not a string type (Utf8 or LargeUtf8)
 pub fn read_string_column(path: &str, column_name: &str) -> Result<Vec<String>, String> {
+    let mut strings = Vec::new();
+    for batch in read_string_column_batches(path, column_name, DEFAULT_READ_BATCH_SIZE)? {
+        strings.extend(batch?);
+    }
+    Ok(strings)
+}
+
+/// Stream a string column from a Parquet file in batches of at most `batch_size`
+/// rows, so only one batch of the column is held in memory at a time.
+///
+/// Only the requested column is decoded. Batches never span row groups, and nulls
+/// are skipped, so a batch may hold fewer than `batch_size` strings.
+///
+/// # Errors
+/// As for [`read_string_column`], when opening the file. Decoding errors are
+/// returned by the iterator.
+pub fn read_string_column_batches(
+    path: &str,
+    column_name: &str,
+    batch_size: usize,
+) -> Result<StringBatches, String> {
+    let builder = open_string_column(path, column_name)?;
+    let reader = builder
+        .with_batch_size(batch_size.max(1))
+        .build()
+        .map_err(|e| format!("Failed to create Parquet reader: {}", e))?;
+    Ok(StringBatches { reader })
+}
+
+/// Read a string column from a Parquet file, decoding its row groups in parallel.
+///
+/// Returns the same strings in the same order as [`read_string_column`], but
+/// holds the whole column in memory, so suits files with several row groups that
+/// fit in memory.
+pub fn read_string_column_parallel(path: &str, column_name: &str) -> Result<Vec<String>, String> {
+    let row_groups = open_string_column(path, column_name)?
+        .metadata()
+        .num_row_groups();
+
+    let chunks = (0..row_groups)
+        .into_par_iter()
+        .map(|row_group| {
+            let reader = open_string_column(path, column_name)?
+                .with_row_groups(vec![row_group])
+                .with_batch_size(DEFAULT_READ_BATCH_SIZE)
+                .build()
+                .map_err(|e| format!("Failed to create Parquet reader: {}", e))?;
+            let mut strings = Vec::new();
+            for batch in (StringBatches { reader }) {
+                strings.extend(batch?);
+            }
+            Ok(strings)
+        })
+        .collect::<Result<Vec<Vec<String>>, String>>()?;
+
+    Ok(chunks.concat())
+}
+
+/// Batches of strings from a Parquet column, from [`read_string_column_batches`].
+pub struct StringBatches {
+    reader: ParquetRecordBatchReader,
+}
+
+impl Iterator for StringBatches {
+    type Item = Result<Vec<String>, String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let batch = match self.reader.next()? {
+            Ok(batch) => batch,
+            Err(e) => return Some(Err(format!("Failed to read record batch: {}", e))),
+        };
+        // The reader is projected onto the one column
+        Some(non_null_strings(batch.column(0).as_ref()))
+    }
+}
+
+/// Open a Parquet file with a reader projected onto the string column `column_name`.
+fn open_string_column(
+    path: &str,
+    column_name: &str,
+) -> Result<ParquetRecordBatchReaderBuilder<File>, String> {
     let file =
         File::open(path).map_err(|e| format!("Failed to open Parquet file '{}': {}", path, e))?;
 
@@ -42,11 +129,8 @@ pub fn read_string_column(path: &str, column_name: &str) -> Result<Vec<String>,
         )
     })?;
 
-    // Clone the data type so we don't hold a reference to schema/builder
-    let data_type = field.data_type().clone();
-
     // Ensure it's actually a string column
-    match &data_type {
+    match field.data_type() {
         DataType::Utf8 | DataType::LargeUtf8 => {}
         other => {
             return Err(format!(
@@ -56,62 +140,29 @@ pub fn read_string_column(path: &str, column_name: &str) -> Result<Vec<String>,
         }
     }
 
-    let reader = builder
-        .build()
-        .map_err(|e| format!("Failed to create Parquet reader: {}", e))?;
-
-    let mut strings = Vec::new();
-
-    // Process all record batches
-    for batch_result in reader {
-        let batch = batch_result.map_err(|e| format!("Failed to read record batch: {}", e))?;
-
-        // Around line 66-76, replace the downcast logic:
-
-        let column = batch.column(column_index);
-
-        // Handle both StringArray and LargeStringArray
-        let strings_in_batch: Vec<String> = match &data_type {
-            DataType::Utf8 => {
-                let string_array = column
-                    .as_any()
-                    .downcast_ref::<StringArray>()
-                    .ok_or_else(|| "Failed to downcast column to StringArray".to_string())?;
-
-                (0..string_array.len())
-                    .filter_map(|i| {
-                        if !string_array.is_null(i) {
-                            Some(string_array.value(i).to_string())
-                        } else {
-                            None
-                        }
-                    })
-                    .collect()
-            }
-            DataType::LargeUtf8 => {
-                use arrow::array::LargeStringArray;
-                let string_array = column
-                    .as_any()
-                    .downcast_ref::<LargeStringArray>()
-                    .ok_or_else(|| "Failed to downcast column to LargeStringArray".to_string())?;
-
-                (0..string_array.len())
-                    .filter_map(|i| {
-                        if !string_array.is_null(i) {
-                            Some(string_array.value(i).to_string())
-                        } else {
-                            None
-                        }
-                    })
-                    .collect()
-            }
-            _ => unreachable!("Type already validated"),
-        };
+    let mask = ProjectionMask::roots(builder.parquet_schema(), [column_index]);
+    Ok(builder.with_projection(mask))
+}
 
-        strings.extend(strings_in_batch);
+/// The non-null values of a string array, handling both StringArray and LargeStringArray.
+fn non_null_strings(column: &dyn Array) -> Result<Vec<String>, String> {
+    fn collect<O: OffsetSizeTrait>(array: &GenericStringArray<O>) -> Vec<String> {
+        array.iter().flatten().map(str::to_string).collect()
     }
 
-    Ok(strings)
+    match column.data_type() {
+        DataType::Utf8 => column
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .map(collect)
+            .ok_or_else(|| "Failed to downcast column to StringArray".to_string()),
+        DataType::LargeUtf8 => column
+            .as_any()
+            .downcast_ref::<LargeStringArray>()
+            .map(collect)
+            .ok_or_else(|| "Failed to downcast column to LargeStringArray".to_string()),
+        other => Err(format!("Expected a string column, got {:?}", other)),
+    }
 }
 
 /// Write strings to a Parquet file as a single string column
@@ -153,7 +204,6 @@ pub fn write_string_column(
 
     // Create appropriate string array based on size
     let array: Arc<dyn Array> = if use_large {
-        use arrow::array::LargeStringArray;
         Arc::new(LargeStringArray::from(strings))
     } else {
         Arc::new(StringArray::from(strings))
@@ -187,6 +237,149 @@ pub fn write_string_column(
     Ok(())
 }
 
+/// Rows per record batch when streaming strings with [`write_string_rows`].
+pub const DEFAULT_WRITE_BATCH_SIZE: usize = 8192;
+
+/// Stream strings to a Parquet file as a single string column, one record batch
+/// (and row group) per `batch_size` rows, so the full column is never held in memory.
+///
+/// # Arguments
+/// * `path` - Output path for the Parquet file
+/// * `column_name` - Name for the string column
+/// * `rows` - Strings to write, consumed lazily
+/// * `metadata` - Optional key/value metadata stored in the file schema
+/// * `batch_size` - Maximum rows buffered before a batch is flushed
+///
+/// # Returns
+/// The number of rows written
+///
+/// # Errors
+/// Returns error if file cannot be written or Arrow conversion fails
+pub fn write_string_rows<I>(
+    path: &str,
+    column_name: &str,
+    rows: I,
+    metadata: Option<HashMap<String, String>>,
+    batch_size: usize,
+) -> Result<usize, String>
+where
+    I: IntoIterator<Item = String>,
+{
+    // Batches are flushed well before their offsets could overflow i32
+    const MAX_BATCH_BYTES: usize = (i32::MAX / 2) as usize;
+
+    let field = Field::new(column_name, DataType::Utf8, true);
+    let schema = match metadata {
+        Some(meta) => Schema::new_with_metadata(vec![field], meta),
+        None => Schema::new(vec![field]),
+    };
+    let schema_ref = Arc::new(schema);
+
+    let file = File::create(path)
+        .map_err(|e| format!("Failed to create output file '{}': {}", path, e))?;
+    let props = WriterProperties::builder().build();
+    let mut writer = ArrowWriter::try_new(file, schema_ref.clone(), Some(props))
+        .map_err(|e| format!("Failed to create Parquet writer: {}", e))?;
+
+    let batch_size = batch_size.max(1);
+    let mut buffer: Vec<String> = Vec::with_capacity(batch_size);
+    let mut buffered_bytes = 0;
+    let mut written = 0;
+
+    let mut flush = |buffer: &mut Vec<String>| -> Result<(), String> {
+        let array: Arc<dyn Array> = Arc::new(StringArray::from(std::mem::take(buffer)));
+        let batch = RecordBatch::try_new(schema_ref.clone(), vec![array])
+            .map_err(|e| format!("Failed to create RecordBatch: {}", e))?;
+        writer
+            .write(&batch)
+            .map_err(|e| format!("Failed to write RecordBatch: {}", e))?;
+        // One row group per batch keeps the writer's buffered data bounded
+        writer
+            .flush()
+            .map_err(|e| format!("Failed to flush row group: {}", e))
+    };
+
+    for row in rows {
+        buffered_bytes += row.len();
+        buffer.push(row);
+        written += 1;
+        if buffer.len() >= batch_size || buffered_bytes >= MAX_BATCH_BYTES {
+            flush(&mut buffer)?;
+            buffered_bytes = 0;
+        }
+    }
+    if !buffer.is_empty() {
+        flush(&mut buffer)?;
+    }
+
+    writer
+        .close()
+        .map_err(|e| format!("Failed to close Parquet writer: {}", e))?;
+
+    Ok(written)
+}
+
+/// Metadata key of the JSON Schema embedded by [`write_schema_parquet`].
+pub const JSON_SCHEMA_METADATA_KEY: &str = "genson_json_schema";
+/// Metadata key of the Avro schema embedded by [`write_schema_parquet`].
+pub const AVRO_SCHEMA_METADATA_KEY: &str = "genson_avro_schema";
+
+/// Write a Parquet file whose Arrow schema is converted from `json_schema`, as a
+/// schema artifact for readers that take Parquet natively (Spark, Polars).
+///
+/// The JSON Schema, and `avro_schema` if given, are embedded as key/value metadata
+/// under [`JSON_SCHEMA_METADATA_KEY`] and [`AVRO_SCHEMA_METADATA_KEY`]. `rows` are
+/// the file's data: JSON objects shaped like the schema (normalised against its
+/// Avro form, with maps as objects), or none for an empty table.
+///
+/// # Errors
+/// Returns error if the schema has no Arrow equivalent, a row doesn't fit it, or
+/// the file can't be written.
+pub fn write_schema_parquet(
+    path: &str,
+    json_schema: &Value,
+    avro_schema: Option<&Value>,
+    rows: &[Value],
+) -> Result<(), String> {
+    let mut metadata = HashMap::new();
+    metadata.insert(
+        JSON_SCHEMA_METADATA_KEY.to_string(),
+        json_schema.to_string(),
+    );
+    if let Some(avro_schema) = avro_schema {
+        metadata.insert(
+            AVRO_SCHEMA_METADATA_KEY.to_string(),
+            avro_schema.to_string(),
+        );
+    }
+    let schema = crate::export::arrow::to_arrow_schema(json_schema)?.with_metadata(metadata);
+    let schema_ref = Arc::new(schema);
+
+    let mut decoder = ReaderBuilder::new(schema_ref.clone())
+        .build_decoder()
+        .map_err(|e| format!("Failed to create JSON decoder: {}", e))?;
+    decoder
+        .serialize(rows)
+        .map_err(|e| format!("Row doesn't fit the Arrow schema: {}", e))?;
+    let batch = decoder
+        .flush()
+        .map_err(|e| format!("Row doesn't fit the Arrow schema: {}", e))?
+        .unwrap_or_else(|| RecordBatch::new_empty(schema_ref.clone()));
+
+    let file = File::create(path)
+        .map_err(|e| format!("Failed to create output file '{}': {}", path, e))?;
+    let props = WriterProperties::builder().build();
+    let mut writer = ArrowWriter::try_new(file, schema_ref, Some(props))
+        .map_err(|e| format!("Failed to create Parquet writer: {}", e))?;
+    writer
+        .write(&batch)
+        .map_err(|e| format!("Failed to write RecordBatch: {}", e))?;
+    writer
+        .close()
+        .map_err(|e| format!("Failed to close Parquet writer: {}", e))?;
+    Ok(())
+}
+
 pub fn read_parquet_metadata(path: &str) -> Result<HashMap<String, String>, String> {
     let file =
         File::open(path).map_err(|e| format!("Failed to open Parquet file '{}': {}", path, e))?;
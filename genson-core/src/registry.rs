@@ -0,0 +1,160 @@
+//! Confluent-compatible Schema Registry client.
+//!
+//! Registers inferred Avro schemas under a subject and checks them against the
+//! latest registered version first, so a schema that would break consumers is
+//! caught before it reaches a topic.
+
+use serde_json::{json, Value};
+use std::time::Duration;
+
+const CONTENT_TYPE: &str = "application/vnd.schemaregistry.v1+json";
+
+/// Registry error code for a subject that has no versions yet.
+const SUBJECT_NOT_FOUND: i64 = 40401;
+
+/// How the subject is named, following Confluent's serializer strategies.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SubjectNameStrategy {
+    /// `<topic>-value` (the default)
+    #[default]
+    TopicName,
+    /// The fully-qualified name of the root record
+    RecordName,
+    /// `<topic>-<fully-qualified record name>`
+    TopicRecordName,
+}
+
+impl SubjectNameStrategy {
+    /// The subject for `schema` (an Avro schema) on `topic`.
+    pub fn subject(&self, topic: Option<&str>, schema: &Value) -> Result<String, String> {
+        let topic = || topic.ok_or("A topic is required for this subject name strategy");
+        match self {
+            SubjectNameStrategy::TopicName => Ok(format!("{}-value", topic()?)),
+            SubjectNameStrategy::RecordName => record_full_name(schema),
+            SubjectNameStrategy::TopicRecordName => {
+                Ok(format!("{}-{}", topic()?, record_full_name(schema)?))
+            }
+        }
+    }
+}
+
+/// Fully-qualified name of the root record of an Avro schema.
+fn record_full_name(schema: &Value) -> Result<String, String> {
+    let name = schema
+        .get("name")
+        .and_then(Value::as_str)
+        .ok_or("The schema's root has no record name")?;
+    match schema.get("namespace").and_then(Value::as_str) {
+        Some(ns) if !ns.is_empty() && !name.contains('.') => Ok(format!("{}.{}", ns, name)),
+        _ => Ok(name.to_string()),
+    }
+}
+
+/// A blocking client for one Schema Registry.
+pub struct SchemaRegistryClient {
+    base_url: String,
+    http: reqwest::blocking::Client,
+}
+
+impl SchemaRegistryClient {
+    /// A client for the registry at `base_url`, e.g. `http://localhost:8081`.
+    pub fn new(base_url: &str) -> Result<Self, String> {
+        let http = reqwest::blocking::Client::builder()
+            .timeout(Duration::from_secs(30))
+            .build()
+            .map_err(|e| format!("Failed to create schema registry client: {}", e))?;
+        Ok(Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            http,
+        })
+    }
+
+    /// Register `schema` under `subject`, returning its registry id.
+    ///
+    /// Registering a schema identical to an existing version returns that
+    /// version's id rather than creating a new one.
+    pub fn register(&self, subject: &str, schema: &Value) -> Result<i64, String> {
+        let url = format!("{}/subjects/{}/versions", self.base_url, encode(subject));
+        let (status, body) = self.post(&url, schema)?;
+        if !status.is_success() {
+            return Err(error_message(status, &body));
+        }
+        body.get("id")
+            .and_then(Value::as_i64)
+            .ok_or_else(|| format!("Schema registry response has no id: {}", body))
+    }
+
+    /// Whether `schema` is compatible with the latest version under `subject`,
+    /// according to the subject's compatibility level. A subject with no versions
+    /// yet accepts any schema.
+    pub fn check_compatibility(&self, subject: &str, schema: &Value) -> Result<bool, String> {
+        let url = format!(
+            "{}/compatibility/subjects/{}/versions/latest",
+            self.base_url,
+            encode(subject)
+        );
+        let (status, body) = self.post(&url, schema)?;
+        if status == reqwest::StatusCode::NOT_FOUND
+            && body.get("error_code").and_then(Value::as_i64) == Some(SUBJECT_NOT_FOUND)
+        {
+            return Ok(true);
+        }
+        if !status.is_success() {
+            return Err(error_message(status, &body));
+        }
+        body.get("is_compatible")
+            .and_then(Value::as_bool)
+            .ok_or_else(|| format!("Schema registry response has no is_compatible: {}", body))
+    }
+
+    /// POST an Avro schema, returning the response status and JSON body.
+    fn post(&self, url: &str, schema: &Value) -> Result<(reqwest::StatusCode, Value), String> {
+        let payload = json!({ "schema": schema.to_string() });
+        let response = self
+            .http
+            .post(url)
+            .header(reqwest::header::CONTENT_TYPE, CONTENT_TYPE)
+            .body(payload.to_string())
+            .send()
+            .map_err(|e| format!("Schema registry request to {} failed: {}", url, e))?;
+        let status = response.status();
+        let text = response
+            .text()
+            .map_err(|e| format!("Failed to read schema registry response: {}", e))?;
+        let body = serde_json::from_str(&text).unwrap_or(Value::String(text));
+        Ok((status, body))
+    }
+}
+
+/// Describe an unsuccessful response; registry errors are
+/// `{"error_code": 40401, "message": "..."}`.
+fn error_message(status: reqwest::StatusCode, body: &Value) -> String {
+    match (
+        body.get("error_code"),
+        body.get("message").and_then(Value::as_str),
+    ) {
+        (Some(code), Some(message)) => format!(
+            "Schema registry returned {} (error {}): {}",
+            status, code, message
+        ),
+        _ => format!("Schema registry returned {}: {}", status, body),
+    }
+}
+
+/// Percent-encode a subject for use as a URL path segment.
+fn encode(segment: &str) -> String {
+    segment
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    include!("tests/registry.rs");
+}
@@ -0,0 +1,192 @@
+//! Repair of the low-grade syntax errors common in scraped JSON: trailing commas,
+//! single-quoted strings, unquoted keys, a leading byte order mark and raw control
+//! characters.
+//!
+//! The text is repaired before it's parsed, and only ever made valid where it was
+//! invalid: text that already parses is left as it is. What can't be repaired
+//! (unbalanced brackets, a missing comma) still fails.
+
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+
+/// A kind of syntax error that was repaired.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Repair {
+    /// A byte order mark before the document, dropped
+    ByteOrderMark,
+    /// A raw control character: escaped inside strings, dropped outside them
+    ControlCharacter,
+    /// A comma before a closing `}` or `]`, dropped
+    TrailingComma,
+    /// A string in single quotes, requoted with double quotes
+    SingleQuotes,
+    /// An object key without quotes, quoted
+    UnquotedKey,
+}
+
+/// The repairs made to one row of the input.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RowRepairs {
+    /// Index of the row: the document, or the (non-blank) line of NDJSON
+    pub row: usize,
+    /// Each kind of repair made, in the order first made
+    pub repairs: Vec<Repair>,
+}
+
+/// `text` with its syntax errors repaired, and the kinds of repair made. `text` is
+/// borrowed when there was nothing to repair.
+pub fn repair_json(text: &str) -> (Cow<'_, str>, Vec<Repair>) {
+    let mut repairs = Vec::new();
+    let body = match text.strip_prefix('\u{FEFF}') {
+        Some(rest) => {
+            note(&mut repairs, Repair::ByteOrderMark);
+            rest
+        }
+        None => text,
+    };
+    let chars: Vec<char> = body.chars().collect();
+    let mut out = String::with_capacity(body.len());
+    // Byte offset in `out` of the last character outside strings that isn't whitespace
+    let mut last: Option<usize> = None;
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            '"' | '\'' => {
+                if c == '\'' {
+                    note(&mut repairs, Repair::SingleQuotes);
+                }
+                i = copy_string(&chars, i, &mut out, &mut repairs);
+                last = Some(out.len() - 1);
+                continue;
+            }
+            '}' | ']' => {
+                if let Some(pos) = last.filter(|&pos| out.as_bytes()[pos] == b',') {
+                    out.remove(pos);
+                    note(&mut repairs, Repair::TrailingComma);
+                }
+                last = Some(out.len());
+                out.push(c);
+            }
+            _ if is_word_char(c) && !c.is_ascii_digit() => {
+                let end = (i..chars.len())
+                    .find(|&j| !is_word_char(chars[j]))
+                    .unwrap_or(chars.len());
+                let word: String = chars[i..end].iter().collect();
+                let after_key_start =
+                    last.is_some_and(|pos| matches!(out.as_bytes()[pos], b'{' | b','));
+                let before_colon = chars[end..]
+                    .iter()
+                    .find(|c| !c.is_whitespace())
+                    .is_some_and(|&c| c == ':');
+                if after_key_start && before_colon {
+                    out.push('"');
+                    out.push_str(&word);
+                    out.push('"');
+                    note(&mut repairs, Repair::UnquotedKey);
+                } else {
+                    out.push_str(&word);
+                }
+                last = Some(out.len() - 1);
+                i = end;
+                continue;
+            }
+            ' ' | '\t' | '\n' | '\r' => out.push(c),
+            _ if c.is_control() => note(&mut repairs, Repair::ControlCharacter),
+            _ => {
+                last = Some(out.len());
+                out.push(c);
+            }
+        }
+        i += 1;
+    }
+    if repairs.is_empty() {
+        return (Cow::Borrowed(text), repairs);
+    }
+    (Cow::Owned(out), repairs)
+}
+
+/// `text` read as NDJSON, with the syntax errors of each line repaired, and the
+/// repairs made to each row that needed any.
+pub fn repair_lines(text: &str) -> (Cow<'_, str>, Vec<RowRepairs>) {
+    let mut rows = Vec::new();
+    let mut lines = Vec::new();
+    let mut row = 0;
+    for line in text.split('\n') {
+        let (repaired, repairs) = repair_json(line);
+        if !repairs.is_empty() {
+            rows.push(RowRepairs { row, repairs });
+        }
+        if !repaired.trim().is_empty() {
+            row += 1;
+        }
+        lines.push(repaired);
+    }
+    if rows.is_empty() {
+        return (Cow::Borrowed(text), rows);
+    }
+    (Cow::Owned(lines.join("\n")), rows)
+}
+
+/// Characters of a bare word: an unquoted key, or `true`, `false` and `null`.
+fn is_word_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '_' | '$')
+}
+
+fn note(repairs: &mut Vec<Repair>, repair: Repair) {
+    if !repairs.contains(&repair) {
+        repairs.push(repair);
+    }
+}
+
+/// Copy the string starting at `chars[start]` (quoted with `"` or `'`) to `out` as
+/// a JSON string, returning the index after its closing quote.
+fn copy_string(chars: &[char], start: usize, out: &mut String, repairs: &mut Vec<Repair>) -> usize {
+    let quote = chars[start];
+    out.push('"');
+    let mut i = start + 1;
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            '\\' => match chars.get(i + 1) {
+                // `\'` is how single-quoted strings hold a quote, but isn't JSON
+                Some('\'') if quote == '\'' => {
+                    out.push('\'');
+                    i += 2;
+                    continue;
+                }
+                Some(&next) => {
+                    out.push('\\');
+                    out.push(next);
+                    i += 2;
+                    continue;
+                }
+                None => out.push('\\'),
+            },
+            _ if c == quote => {
+                out.push('"');
+                return i + 1;
+            }
+            '"' => out.push_str("\\\""),
+            '\n' => control(out, repairs, "\\n"),
+            '\r' => control(out, repairs, "\\r"),
+            '\t' => control(out, repairs, "\\t"),
+            _ if (c as u32) < 0x20 => control(out, repairs, &format!("\\u{:04x}", c as u32)),
+            _ => out.push(c),
+        }
+        i += 1;
+    }
+    // Unterminated: left for the parser to reject
+    i
+}
+
+fn control(out: &mut String, repairs: &mut Vec<Repair>, escaped: &str) {
+    out.push_str(escaped);
+    note(repairs, Repair::ControlCharacter);
+}
+
+#[cfg(test)]
+mod tests {
+    include!("tests/repair.rs");
+}
@@ -0,0 +1,119 @@
+//! Sampling the input documents before schema inference.
+//!
+//! For quick exploration of a large input, inferring from a subset is often
+//! enough. NDJSON input is sampled by line.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// Which documents to infer the schema from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SampleSpec {
+    /// The first `n` documents
+    First(usize),
+    /// `n` documents chosen uniformly at random, the same ones for the same seed.
+    /// The sampled documents keep their input order.
+    Random { n: usize, seed: u64 },
+    /// Every `k`th document, starting with the first
+    EveryNth(usize),
+}
+
+impl SampleSpec {
+    /// The sampled documents, in input order.
+    pub fn sample<'a>(&self, documents: impl Iterator<Item = &'a str>) -> Vec<&'a str> {
+        match *self {
+            SampleSpec::First(n) => documents.take(n).collect(),
+            SampleSpec::EveryNth(k) => documents.step_by(k.max(1)).collect(),
+            SampleSpec::Random { n, seed } => reservoir_sample(documents, n, seed),
+        }
+    }
+}
+
+impl fmt::Display for SampleSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SampleSpec::First(n) => write!(f, "first {}", n),
+            SampleSpec::Random { n, seed } => write!(f, "random {} (seed {})", n, seed),
+            SampleSpec::EveryNth(k) => write!(f, "one in every {}", k),
+        }
+    }
+}
+
+/// Parses `N:SEED` as a random sample of `N` documents.
+impl FromStr for SampleSpec {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (n, seed) = s
+            .split_once(':')
+            .ok_or_else(|| format!("expected N:SEED, got '{}'", s))?;
+        let n = n
+            .trim()
+            .parse()
+            .map_err(|_| format!("invalid sample size '{}'", n))?;
+        let seed = seed
+            .trim()
+            .parse()
+            .map_err(|_| format!("invalid seed '{}'", seed))?;
+        Ok(SampleSpec::Random { n, seed })
+    }
+}
+
+/// Apply `spec` to the input strings, splitting NDJSON strings into lines.
+pub(crate) fn sample_documents<S: AsRef<str>>(
+    json_strings: &[S],
+    spec: SampleSpec,
+    ndjson: bool,
+) -> Vec<&str> {
+    let documents = json_strings.iter().map(AsRef::as_ref);
+    if ndjson {
+        let lines = documents
+            .flat_map(str::lines)
+            .filter(|line| !line.trim().is_empty());
+        spec.sample(lines)
+    } else {
+        spec.sample(documents)
+    }
+}
+
+/// Algorithm R over the documents, then restored to input order.
+fn reservoir_sample<'a>(
+    documents: impl Iterator<Item = &'a str>,
+    n: usize,
+    seed: u64,
+) -> Vec<&'a str> {
+    let mut rng = SplitMix64(seed);
+    let mut reservoir: Vec<(usize, &str)> = Vec::with_capacity(n);
+    for (i, doc) in documents.enumerate() {
+        if reservoir.len() < n {
+            reservoir.push((i, doc));
+        } else {
+            let j = (rng.next() % (i as u64 + 1)) as usize;
+            if j < n {
+                reservoir[j] = (i, doc);
+            }
+        }
+    }
+    reservoir.sort_unstable_by_key(|&(i, _)| i);
+    reservoir.into_iter().map(|(_, doc)| doc).collect()
+}
+
+/// Small seeded generator, so sampling needs no RNG dependency.
+pub(crate) struct SplitMix64(pub(crate) u64);
+
+impl SplitMix64 {
+    pub(crate) fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    include!("tests/sample.rs");
+}
@@ -1,4 +1,9 @@
 use crate::genson_rs::{build_json_schema, get_builder, BuildConfig};
+use crate::limits::{LimitExceeded, LimitTracker};
+use crate::nonfinite::nonfinite_as_number;
+use crate::repair::repair_json;
+use crate::sample::sample_documents;
+use crate::time::{SystemTime, UNIX_EPOCH};
 use crate::{debug, profile, profile_verbose};
 use rayon::prelude::*;
 use serde::de::Error as DeError;
@@ -7,20 +12,103 @@ use serde_json::{json, Value};
 use std::borrow::Cow;
 use std::collections::HashSet;
 use std::panic::{self, AssertUnwindSafe};
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::sync::atomic::{AtomicBool, Ordering};
 use xxhash_rust::xxh64::xxh64;
 
 use crate::genson_rs::SchemaBuilder;
 
+pub(crate) mod annotate;
+pub use annotate::{annotate_schema, parse_annotations, FieldAnnotation};
+pub(crate) mod compact;
+pub use compact::{compact, CompactOptions};
 pub(crate) mod core;
 pub use core::*;
+pub(crate) mod discriminators;
+pub(crate) mod document_stats;
+use document_stats::DocumentStats;
+pub(crate) mod draft;
+use draft::apply_draft;
+pub use draft::JsonSchemaDraft;
+pub(crate) mod explain;
+use discriminators::apply_discriminators;
+use explain::ExplainRecorder;
+pub use explain::{DecisionEvent, DecisionKind, FieldDecision, FieldExplanation, Unification};
+pub(crate) mod field_order;
+pub use field_order::FieldOrder;
+use field_order::{order_fields, FieldCounts};
+pub(crate) mod field_path;
+pub(crate) mod formats;
+use formats::apply_string_formats;
+pub use formats::FormatDetection;
+pub(crate) mod empty_containers;
+pub(crate) mod invalid_json;
+pub(crate) mod key_fold;
+pub use empty_containers::EmptyContainerPolicy;
+use empty_containers::{rewrite_empty_containers, type_empty_containers};
+use invalid_json::invalid_json_error;
+use key_fold::{fold_keys, key_collisions, restore_spellings};
+pub use key_fold::{KeyCollision, KeyFold};
+pub(crate) mod map_array;
+pub(crate) mod map_keys;
+pub(crate) mod map_stats;
+pub(crate) mod presence;
+pub(crate) mod profile;
+pub(crate) mod seed;
+pub(crate) mod select;
+pub(crate) mod sparse_arrays;
+pub(crate) mod strict;
+pub(crate) mod tuples;
+pub(crate) mod union_order;
+use field_path::{child_path, pointer, MAP_VALUES_SEGMENT};
+use map_array::coerce_map_arrays;
+use map_keys::apply_numeric_keys;
+pub use map_keys::MAP_KEY_TYPE_KEY;
+use map_stats::map_key_stats;
+pub use map_stats::{KeyFrequency, MapKeyStats, DEFAULT_TOP_KEYS};
+pub use presence::FieldPresence;
+use presence::{apply_required_threshold, validate_threshold, PresenceCounts};
+pub use profile::InferenceProfile;
+use profile::ProfileRecorder;
+use seed::{builder_seed, fold_seed_maps, seed_records, validate_seed};
+use select::select_fields;
+use sparse_arrays::apply_sparse_arrays;
+use strict::{strict_error, strict_issues};
+use tuples::apply_tuples;
+use union_order::order_unions;
+pub use union_order::UnionOrder;
+pub mod session;
+pub use session::SchemaInferenceSession;
 mod map_inference;
 use map_inference::*;
 
-/// Maximum length of JSON string to include in error messages before truncating
-const MAX_JSON_ERROR_LENGTH: usize = 100;
 /// Threshold for switching to parallel processing. Below this, use sequential.
 const PARALLEL_THRESHOLD: usize = 10;
+/// Deepest nesting of objects and arrays accepted in a document, the same as
+/// serde_json's recursion limit. Deeper documents are rejected up front, whatever
+/// `max_depth` is.
+pub const MAX_NESTING_DEPTH: usize = 128;
+/// Stack size of the inference threads. The builder and the schema rewrites recurse
+/// once per level of nesting, and [`MAX_NESTING_DEPTH`] levels fit in 8 MiB even in
+/// debug builds with the `trace` feature, which spend the most stack per level.
+const INFERENCE_STACK_SIZE: usize = 64 * 1024 * 1024;
+
+/// Run `f` on the inference thread pool, whose threads have [`INFERENCE_STACK_SIZE`]
+/// stacks however small the caller's is. Parallel work inside `f` runs on the same
+/// pool. Runs `f` on the calling thread if the pool can't be built.
+fn on_inference_stack<R: Send>(f: impl FnOnce() -> R + Send) -> R {
+    static POOL: std::sync::OnceLock<Option<rayon::ThreadPool>> = std::sync::OnceLock::new();
+    let pool = POOL.get_or_init(|| {
+        rayon::ThreadPoolBuilder::new()
+            .stack_size(INFERENCE_STACK_SIZE)
+            .thread_name(|i| format!("genson-inference-{}", i))
+            .build()
+            .ok()
+    });
+    match pool {
+        Some(pool) => pool.install(f),
+        None => f(),
+    }
+}
 
 /// Get current RSS memory usage in bytes
 pub(crate) fn get_rss_bytes() -> Option<usize> {
@@ -68,13 +156,46 @@ fn validate_json(s: &str) -> Result<(), serde_json::Error> {
     de.end()
 }
 
-fn validate_ndjson(s: &str) -> Result<(), serde_json::Error> {
-    for line in s.lines() {
+/// Whether objects and arrays in `s` (one document, or NDJSON lines) nest more than
+/// `limit` levels deep. Scans the bytes without parsing, so it can't overflow itself.
+fn exceeds_nesting_depth(s: &str, limit: usize) -> bool {
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+    for &byte in s.as_bytes() {
+        if in_string {
+            match byte {
+                _ if escaped => escaped = false,
+                b'\\' => escaped = true,
+                b'"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match byte {
+            b'"' => in_string = true,
+            b'{' | b'[' => {
+                depth += 1;
+                if depth > limit {
+                    return true;
+                }
+            }
+            b'}' | b']' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+    }
+    false
+}
+
+/// Validate each line of NDJSON, failing with the (1-based) number and text of the
+/// first invalid line.
+fn validate_ndjson(s: &str) -> Result<(), (usize, &str, serde_json::Error)> {
+    for (i, line) in s.lines().enumerate() {
         let trimmed = line.trim();
         if trimmed.is_empty() {
             continue;
         }
-        validate_json(trimmed)?; // propagate serde_json::Error
+        validate_json(trimmed).map_err(|e| (i + 1, trimmed, e))?;
     }
     Ok(())
 }
@@ -110,6 +231,73 @@ pub fn reorder_unions(schema: &mut Value) {
     }
 }
 
+/// Give null-only schemas (fields that were null in every document) the type
+/// `fallback` as well, so they become `["null", fallback]`. Defaulted properties
+/// are dropped from `required`, which is what makes them nullable in Avro.
+/// Returns the JSON Pointer paths of the fields changed. The root is left alone.
+fn apply_null_fallback(schema: &mut Value, fallback: &str) -> Vec<String> {
+    /// Returns whether `schema` itself was defaulted.
+    fn walk(
+        schema: &mut Value,
+        fallback: &str,
+        path: &[String],
+        defaulted: &mut Vec<String>,
+    ) -> bool {
+        let Value::Object(obj) = schema else {
+            return false;
+        };
+        if !path.is_empty() && obj.get("type").is_some_and(|t| t == "null") {
+            obj.insert("type".to_string(), json!(["null", fallback]));
+            defaulted.push(pointer(path));
+            return true;
+        }
+        let mut optional = Vec::new();
+        for (key, child) in obj.iter_mut() {
+            match key.as_str() {
+                "properties" => {
+                    if let Value::Object(props) = child {
+                        for (name, prop) in props.iter_mut() {
+                            if walk(prop, fallback, &child_path(path, name), defaulted) {
+                                optional.push(name.clone());
+                            }
+                        }
+                    }
+                }
+                "additionalProperties" => {
+                    walk(
+                        child,
+                        fallback,
+                        &child_path(path, MAP_VALUES_SEGMENT),
+                        defaulted,
+                    );
+                }
+                "items" => {
+                    walk(child, fallback, path, defaulted);
+                }
+                // A null branch only makes the union nullable
+                "anyOf" => {
+                    if let Value::Array(branches) = child {
+                        for branch in branches.iter_mut().filter(|b| b["type"] != "null") {
+                            walk(branch, fallback, path, defaulted);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        if let Some(Value::Array(required)) = obj.get_mut("required") {
+            required.retain(|name| !optional.iter().any(|o| name == o));
+        }
+        false
+    }
+
+    let mut defaulted = Vec::new();
+    walk(schema, fallback, &[], &mut defaulted);
+    defaulted.sort();
+    defaulted.dedup();
+    defaulted
+}
+
 /// Assign a numeric precedence rank to a JSON Schema type.
 ///
 /// Used by `reorder_unions` to sort union members deterministically.
@@ -171,78 +359,351 @@ fn prepare_json_bytes<'a>(
     }
 
     // Choose validation strategy based on delimiter
-    let validation_result = if let Some(delim) = config.delimiter {
-        if delim == b'\n' {
-            validate_ndjson(json_str)
-        } else {
-            Err(serde_json::Error::custom(format!(
-                "Unsupported delimiter: {:?}",
-                delim
-            )))
-        }
-    } else {
-        validate_json(json_str)
+    let validation_result = match config.delimiter {
+        Some(b'\n') => validate_ndjson(json_str).map_err(|(line, text, e)| {
+            // The line number only helps if the string holds several
+            let multiline = json_str.trim().contains('\n');
+            (multiline.then_some(line), text, e)
+        }),
+        Some(delim) => Err((
+            None,
+            json_str,
+            serde_json::Error::custom(format!("Unsupported delimiter: {:?}", delim)),
+        )),
+        None => validate_json(json_str).map_err(|e| (None, json_str, e)),
     };
 
-    if let Err(parse_error) = validation_result {
-        let truncated_json = if json_str.len() > MAX_JSON_ERROR_LENGTH {
-            format!(
-                "{}... [truncated {} chars]",
-                &json_str[..MAX_JSON_ERROR_LENGTH],
-                json_str.len() - MAX_JSON_ERROR_LENGTH
-            )
-        } else {
-            json_str.to_string()
-        };
+    if let Err((line, document, parse_error)) = validation_result {
+        return Err(invalid_json_error(
+            index,
+            line,
+            document,
+            &parse_error,
+            config,
+        ));
+    }
 
+    if exceeds_nesting_depth(json_str, MAX_NESTING_DEPTH) {
         return Err(format!(
-            "Invalid JSON input at index {}: {} - JSON: {}",
+            "Invalid JSON input at index {}: nested more than {} levels deep",
             index + 1,
-            parse_error,
-            truncated_json
+            MAX_NESTING_DEPTH
         ));
     }
 
     // Safe: JSON is valid, now hand off to genson-rs
-    if let Some(ref field) = config.wrap_root {
-        if config.delimiter == Some(b'\n') {
-            // NDJSON: wrap each line separately
-            let mut wrapped_bytes = Vec::new();
-            for line in json_str.lines() {
-                let trimmed = line.trim();
-                if trimmed.is_empty() {
-                    continue;
-                }
-                let inner_val: Value = serde_json::from_str(trimmed)
-                    .map_err(|e| format!("Failed to parse NDJSON line before wrap_root: {}", e))?;
+    if !rewrites_documents(config) && !has_bare_root(json_str, config) {
+        // No rewriting needed - just borrow the original bytes
+        return Ok(Cow::Borrowed(json_bytes));
+    }
 
-                if !wrapped_bytes.is_empty() {
-                    wrapped_bytes.push(b'\n');
-                }
-                serde_json::to_writer(&mut wrapped_bytes, &json!({ field: inner_val }))
-                    .map_err(|e| format!("Failed to serialize wrapped NDJSON: {}", e))?;
+    if config.delimiter == Some(b'\n') {
+        // NDJSON: rewrite each line separately
+        let mut rewritten_bytes = Vec::new();
+        for line in json_str.lines() {
+            let trimmed = line.trim();
+            if trimmed.is_empty() {
+                continue;
             }
-            Ok(Cow::Owned(wrapped_bytes))
+            let value: Value = serde_json::from_str(trimmed)
+                .map_err(|e| format!("Failed to parse NDJSON line before rewriting: {}", e))?;
+
+            if !rewritten_bytes.is_empty() {
+                rewritten_bytes.push(b'\n');
+            }
+            serde_json::to_writer(&mut rewritten_bytes, &rewrite_document(value, config))
+                .map_err(|e| format!("Failed to serialize rewritten NDJSON: {}", e))?;
+        }
+        Ok(Cow::Owned(rewritten_bytes))
+    } else {
+        // Single JSON doc
+        let value: Value = serde_json::from_str(json_str)
+            .map_err(|e| format!("Failed to parse JSON before rewriting: {}", e))?;
+        let rewritten_bytes = serde_json::to_vec(&rewrite_document(value, config))
+            .map_err(|e| format!("Failed to serialize rewritten JSON: {}", e))?;
+        Ok(Cow::Owned(rewritten_bytes))
+    }
+}
+
+/// Parse the documents of one input string.
+/// Documents that fail to parse are skipped: inference has already reported them.
+fn parse_documents(json_str: &str, config: &SchemaInferenceConfig) -> Vec<Value> {
+    let documents: Vec<&str> = if config.delimiter == Some(b'\n') {
+        json_str.lines().filter(|l| !l.trim().is_empty()).collect()
+    } else {
+        vec![json_str]
+    };
+    documents
+        .into_iter()
+        .filter_map(|document| serde_json::from_str(document).ok())
+        .collect()
+}
+
+/// A parsed document as the builder sees it (after `wrap_root` and the other
+/// rewrites, and split out of an outer array as `outer_scalar_policy` decides).
+fn builder_documents(mut value: Value, config: &SchemaInferenceConfig) -> Vec<Value> {
+    if rewrites_documents(config) || is_bare_root(&value) {
+        value = rewrite_document(value, config);
+    }
+    match value {
+        Value::Array(items) if is_outer_array(&value, config) => items,
+        value => vec![value],
+    }
+}
+
+/// Check that the documents are either all wrapped under `wrap_root` already or
+/// none are (`wrap_root_strict`). Documents that fail to parse are left for
+/// inference to report.
+fn check_wrapped_rows(
+    json_strings: &[&str],
+    path: &str,
+    config: &SchemaInferenceConfig,
+) -> Result<(), String> {
+    let documents = json_strings.iter().flat_map(|json_str| {
+        if config.delimiter == Some(b'\n') {
+            json_str.lines().collect::<Vec<_>>()
         } else {
-            // Single JSON doc
-            let inner_val: Value = serde_json::from_str(json_str)
-                .map_err(|e| format!("Failed to parse JSON before wrap_root: {}", e))?;
-            let wrapped_bytes = serde_json::to_vec(&json!({ field: inner_val }))
-                .map_err(|e| format!("Failed to serialize wrapped JSON: {}", e))?;
-            Ok(Cow::Owned(wrapped_bytes))
+            vec![*json_str]
+        }
+    });
+    let mut first: Option<(usize, bool)> = None;
+    for (row, document) in documents.filter(|d| !d.trim().is_empty()).enumerate() {
+        let Ok(value) = serde_json::from_str::<Value>(document) else {
+            continue;
+        };
+        let wrapped = is_wrapped(path, &value);
+        match first {
+            None => first = Some((row + 1, wrapped)),
+            Some((first_row, first_wrapped)) if first_wrapped != wrapped => {
+                let (wrapped_row, unwrapped_row) = if first_wrapped {
+                    (first_row, row + 1)
+                } else {
+                    (row + 1, first_row)
+                };
+                return Err(format!(
+                    "Row {} is already wrapped under '{}' but row {} isn't (wrap_root_strict)",
+                    wrapped_row, path, unwrapped_row
+                ));
+            }
+            _ => {}
         }
+    }
+    Ok(())
+}
+
+/// Add the counts of `other` to `counts`.
+pub(crate) fn merge_field_counts(mut counts: FieldCounts, other: FieldCounts) -> FieldCounts {
+    for (path, count) in other {
+        *counts.entry(path).or_default() += count;
+    }
+    counts
+}
+
+/// Whether documents need parsing and rewriting before they reach the builder.
+fn rewrites_documents(config: &SchemaInferenceConfig) -> bool {
+    config.key_fold.is_some()
+        || config.wrap_root.is_some()
+        || !config.ignore_paths.is_empty()
+        || !config.select_paths.is_empty()
+        || config.max_depth.is_some()
+        || config.max_object_keys.is_some()
+        || config
+            .empty_container_policy
+            .is_some_and(EmptyContainerPolicy::rewrites_documents)
+}
+
+/// Whether any document in `json_str` may have a bare root, judged by its leading
+/// bytes so that object documents don't have to be parsed. The builder only
+/// understands objects and outer arrays of objects.
+fn has_bare_root(json_str: &str, config: &SchemaInferenceConfig) -> bool {
+    let is_bare = |document: &str| {
+        let mut bytes = document.bytes().filter(|b| !b.is_ascii_whitespace());
+        match bytes.next() {
+            None | Some(b'{') => false,
+            Some(b'[') => has_bare_items(document),
+            Some(_) => true,
+        }
+    };
+    if config.delimiter == Some(b'\n') {
+        json_str.lines().any(is_bare)
     } else {
-        // No wrapping needed - just borrow the original bytes
-        Ok(Cow::Borrowed(json_bytes))
+        is_bare(json_str)
+    }
+}
+
+/// Whether an outer array holds anything but objects, judged by the first byte of
+/// each of its items (skipping over strings) rather than by parsing it.
+fn has_bare_items(document: &str) -> bool {
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut item_start = false;
+    for b in document.bytes() {
+        if in_string {
+            match b {
+                _ if escaped => escaped = false,
+                b'\\' => escaped = true,
+                b'"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        if b.is_ascii_whitespace() {
+            continue;
+        }
+        if item_start {
+            item_start = false;
+            if b != b'{' && b != b']' {
+                return true;
+            }
+        }
+        match b {
+            b'"' => in_string = true,
+            b'[' | b'{' => {
+                depth += 1;
+                item_start = depth == 1;
+            }
+            b']' | b'}' => depth = depth.saturating_sub(1),
+            b',' => item_start = depth == 1,
+            _ => {}
+        }
+    }
+    false
+}
+
+/// Apply `key_fold`, `wrap_root` (or wrap a bare root under `root_value_field`),
+/// `select_paths`, `ignore_paths`, the depth and width limits and `empty_container_policy` to a
+/// parsed document.
+fn rewrite_document(mut value: Value, config: &SchemaInferenceConfig) -> Value {
+    if let Some(fold) = config.key_fold {
+        fold_keys(&mut value, fold);
+    }
+    if config.outer_scalar_policy == OuterScalarPolicy::Rows && is_outer_array(&value, config) {
+        // Each item is a document, so the scalars among them are wrapped one by one
+        let Value::Array(items) = value else {
+            unreachable!("outer arrays are arrays")
+        };
+        return Value::Array(
+            items
+                .into_iter()
+                .map(|item| rewrite_item(item, config))
+                .collect(),
+        );
+    }
+    let mut value = wrap_document(value, config.wrap_root.as_deref(), &config.root_value_field);
+    match value {
+        // Each item of an outer array is a document in its own right
+        Value::Array(ref mut items) if config.ignore_outer_array => {
+            for item in items {
+                select_fields(item, &config.select_paths);
+                collapse_subtrees(item, config, &mut Vec::new(), 1);
+                rewrite_empty(item, config);
+            }
+        }
+        _ => {
+            select_fields(&mut value, &config.select_paths);
+            collapse_subtrees(&mut value, config, &mut Vec::new(), 1);
+            rewrite_empty(&mut value, config);
+        }
+    }
+    value
+}
+
+/// Drop or null the empty containers in a document, as `empty_container_policy` says.
+fn rewrite_empty(document: &mut Value, config: &SchemaInferenceConfig) {
+    if let Some(policy) = config
+        .empty_container_policy
+        .filter(|policy| policy.rewrites_documents())
+    {
+        rewrite_empty_containers(document, policy);
+    }
+}
+
+/// Rewrite an item of an outer array read as rows. A null item is wrapped like a
+/// scalar, making the field nullable, rather than being dropped as a missing row.
+fn rewrite_item(item: Value, config: &SchemaInferenceConfig) -> Value {
+    let mut item = match (item, config.wrap_root.as_deref()) {
+        (Value::Null, None) => wrap_root_value(&config.root_value_field, Value::Null),
+        (item, wrap_root) => wrap_document(item, wrap_root, &config.root_value_field),
+    };
+    select_fields(&mut item, &config.select_paths);
+    collapse_subtrees(&mut item, config, &mut Vec::new(), 1);
+    rewrite_empty(&mut item, config);
+    item
+}
+
+/// Replace the non-null values at `ignore_paths`, and containers nested deeper than
+/// `max_depth` or with more than `max_object_keys` keys, with an empty string, so the
+/// builder sees a plain string field instead of the whole subtree. `depth` is the
+/// nesting depth of `value`, counting the document root as 1.
+fn collapse_subtrees(
+    value: &mut Value,
+    config: &SchemaInferenceConfig,
+    path: &mut Vec<String>,
+    depth: usize,
+) {
+    match value {
+        Value::Object(obj) => {
+            for (key, child) in obj.iter_mut() {
+                path.push(key.clone());
+                if config.is_ignored_path(key, path) {
+                    if !child.is_null() {
+                        *child = Value::String(String::new());
+                    }
+                } else if exceeds_limits(child, config, path, depth + 1) {
+                    *child = Value::String(String::new());
+                } else {
+                    collapse_subtrees(child, config, path, depth + 1);
+                }
+                path.pop();
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                if exceeds_limits(item, config, path, depth + 1) {
+                    *item = Value::String(String::new());
+                } else {
+                    collapse_subtrees(item, config, path, depth + 1);
+                }
+            }
+        }
+        _ => {}
     }
 }
 
+/// Whether the container `value` at `depth` is too deep or too wide to infer.
+fn exceeds_limits(
+    value: &Value,
+    config: &SchemaInferenceConfig,
+    path: &[String],
+    depth: usize,
+) -> bool {
+    let too_deep = || config.max_depth.is_some_and(|max| depth > max);
+    let exceeded = match value {
+        Value::Object(obj) => {
+            too_deep() || config.max_object_keys.is_some_and(|max| obj.len() > max)
+        }
+        Value::Array(_) => too_deep(),
+        _ => false,
+    };
+    if exceeded {
+        debug!(
+            config,
+            "Collapsing /{} to a string: exceeds max_depth or max_object_keys",
+            path.join("/")
+        );
+    }
+    exceeded
+}
+
 /// Process all JSON strings sequentially and build schemas
 fn process_json_strings_sequential(
-    json_strings: &[String],
+    json_strings: &[&str],
     config: &SchemaInferenceConfig,
     builder: &mut crate::genson_rs::SchemaBuilder,
-) -> Result<usize, String> {
+    tracker: &LimitTracker,
+    mut recorder: Option<&mut ProfileRecorder>,
+) -> Result<(usize, Option<LimitExceeded>), String> {
     let build_config = BuildConfig {
         delimiter: config.delimiter,
         ignore_outer_array: config.ignore_outer_array,
@@ -252,9 +713,12 @@ fn process_json_strings_sequential(
 
     // Process each JSON string
     for (i, json_str) in json_strings.iter().enumerate() {
+        if let Err(exceeded) = tracker.check_runtime() {
+            return Ok((processed_count, Some(exceeded)));
+        }
         profile_verbose!(config, "PROCESSING JSON STRING {}", i);
 
-        let prep_start = std::time::Instant::now();
+        let prep_start = crate::time::Instant::now();
         let prepared_json = prepare_json_bytes(json_str.as_bytes(), i, config)?;
         let prep_elapsed = prep_start.elapsed();
         profile_verbose!(config, "  Preparation took: {:?}", prep_elapsed);
@@ -266,28 +730,34 @@ fn process_json_strings_sequential(
         let mut bytes = prepared_json.into_owned(); // Only allocate if Cow::Owned
 
         // Build schema incrementally - this is where panics happen
-        let build_start = std::time::Instant::now();
+        let build_start = crate::time::Instant::now();
         let _schema = build_json_schema(builder, &mut bytes, &build_config);
         let build_elapsed = build_start.elapsed();
         profile_verbose!(config, "  Schema building took: {:?}", build_elapsed);
+        if let Some(recorder) = recorder.as_deref_mut() {
+            recorder.document(prep_elapsed, build_elapsed);
+        }
 
         processed_count += 1;
     }
 
-    Ok(processed_count)
+    Ok((processed_count, None))
 }
 
 /// Apply force_field_types to a schema before merging.
 /// This ensures structural consistency across schemas that will be unified.
-fn apply_force_field_types(schema: &mut Value, config: &SchemaInferenceConfig) {
+fn apply_force_field_types(schema: &mut Value, config: &SchemaInferenceConfig, path: &[String]) {
     match schema {
         Value::Object(obj) => {
             // Check if this object has properties
             if let Some(props) = obj.get_mut("properties") {
                 if let Some(props_obj) = props.as_object_mut() {
                     for (field_name, field_schema) in props_obj.iter_mut() {
+                        let field_path = child_path(path, field_name);
                         // Apply force_field_types
-                        if let Some(forced) = config.force_field_types.get(field_name) {
+                        if let Some(forced) =
+                            config.forced_field_type(Some(field_name), &field_path)
+                        {
                             if forced == "map" {
                                 if let Some(field_obj) = field_schema.as_object_mut() {
                                     // Convert to map schema
@@ -302,38 +772,154 @@ fn apply_force_field_types(schema: &mut Value, config: &SchemaInferenceConfig) {
                             }
                         }
                         // Recurse
-                        apply_force_field_types(field_schema, config);
+                        apply_force_field_types(field_schema, config, &field_path);
                     }
                 }
             }
             // Also recurse into items, additionalProperties, anyOf, etc.
             if let Some(items) = obj.get_mut("items") {
-                apply_force_field_types(items, config);
+                apply_force_field_types(items, config, path);
             }
             if let Some(additional) = obj.get_mut("additionalProperties") {
-                apply_force_field_types(additional, config);
+                apply_force_field_types(additional, config, &child_path(path, MAP_VALUES_SEGMENT));
             }
             if let Some(Value::Array(any_of)) = obj.get_mut("anyOf") {
                 for item in any_of {
-                    apply_force_field_types(item, config);
+                    apply_force_field_types(item, config, path);
                 }
             }
         }
         Value::Array(arr) => {
             for item in arr {
-                apply_force_field_types(item, config);
+                apply_force_field_types(item, config, path);
             }
         }
         _ => {}
     }
 }
 
+/// Process documents in batches of `chunk_size`, merging each batch into `builder`.
+///
+/// Each batch is built in parallel with one builder per thread, reduced to a schema
+/// and merged before the next batch starts, and NDJSON strings are split into lines,
+/// so peak memory is bounded by the batch rather than the input. The count returned
+/// is of documents (NDJSON lines), not input strings.
+fn process_json_strings_chunked(
+    json_strings: &[&str],
+    chunk_size: usize,
+    config: &SchemaInferenceConfig,
+    builder: &mut SchemaBuilder,
+    tracker: &LimitTracker,
+    mut recorder: Option<&mut ProfileRecorder>,
+) -> Result<(usize, Option<LimitExceeded>), String> {
+    let ndjson = config.delimiter == Some(b'\n');
+    let documents = json_strings
+        .iter()
+        .flat_map(|s| -> Box<dyn Iterator<Item = &str>> {
+            if ndjson {
+                Box::new(s.lines().filter(|line| !line.trim().is_empty()))
+            } else {
+                Box::new(std::iter::once(*s))
+            }
+        });
+
+    let chunk_size = chunk_size.max(1);
+    let mut processed_count = 0;
+    let mut seen = 0;
+    let mut batch = Vec::with_capacity(chunk_size);
+    for document in documents {
+        batch.push(document);
+        if batch.len() < chunk_size {
+            continue;
+        }
+        if let Err(exceeded) = tracker.check_runtime() {
+            return Ok((processed_count, Some(exceeded)));
+        }
+        processed_count += process_batch(&batch, seen, config, builder, recorder.as_deref_mut())?;
+        seen += batch.len();
+        batch.clear();
+        profile!(config, "Merged {} documents ({})", seen, current_time_hms());
+    }
+    if !batch.is_empty() {
+        if let Err(exceeded) = tracker.check_runtime() {
+            return Ok((processed_count, Some(exceeded)));
+        }
+        processed_count += process_batch(&batch, seen, config, builder, recorder)?;
+    }
+
+    Ok((processed_count, None))
+}
+
+/// Build one batch of documents and merge its schema into `builder`, returning the
+/// number of non-empty documents. `offset` is the index of the first document.
+fn process_batch(
+    batch: &[&str],
+    offset: usize,
+    config: &SchemaInferenceConfig,
+    builder: &mut SchemaBuilder,
+    recorder: Option<&mut ProfileRecorder>,
+) -> Result<usize, String> {
+    let build_config = BuildConfig {
+        delimiter: config.delimiter,
+        ignore_outer_array: config.ignore_outer_array,
+    };
+
+    let reduced = batch
+        .par_iter()
+        .enumerate()
+        .try_fold(
+            || (get_builder(config.schema_uri.as_deref()), Vec::new()),
+            |(mut batch_builder, mut timings), (i, document)| -> Result<_, String> {
+                let prep_start = crate::time::Instant::now();
+                let prepared = prepare_json_bytes(document.as_bytes(), offset + i, config)?;
+                let prep_elapsed = prep_start.elapsed();
+                if prepared.is_empty() {
+                    return Ok((batch_builder, timings));
+                }
+
+                let mut bytes = prepared.into_owned();
+                let build_start = crate::time::Instant::now();
+                build_json_schema(&mut batch_builder, &mut bytes, &build_config);
+                timings.push((prep_elapsed, build_start.elapsed()));
+                Ok((batch_builder, timings))
+            },
+        )
+        .try_reduce_with(|(mut left, mut left_timings), (right, right_timings)| {
+            left.add_schema(right.to_schema());
+            left_timings.extend(right_timings);
+            Ok((left, left_timings))
+        });
+    let Some(reduced) = reduced else {
+        return Ok(0);
+    };
+    let (batch_builder, timings): (SchemaBuilder, Vec<DocumentTimings>) = reduced?;
+
+    let merge_start = crate::time::Instant::now();
+    let mut schema = batch_builder.to_schema();
+    drop(batch_builder);
+    apply_force_field_types(&mut schema, config, &[]);
+    builder.add_schema(schema);
+
+    if let Some(recorder) = recorder {
+        for &(prep_elapsed, build_elapsed) in &timings {
+            recorder.document(prep_elapsed, build_elapsed);
+        }
+        recorder.merge(merge_start.elapsed());
+    }
+    Ok(timings.len())
+}
+
+/// Preparation and build time of one document, for profiling
+type DocumentTimings = (crate::time::Duration, crate::time::Duration);
+
 /// Process all JSON strings in parallel while maintaining order
 fn process_json_strings_parallel(
-    json_strings: &[String],
+    json_strings: &[&str],
     config: &SchemaInferenceConfig,
     builder: &mut SchemaBuilder,
-) -> Result<usize, String> {
+    tracker: &LimitTracker,
+    mut recorder: Option<&mut ProfileRecorder>,
+) -> Result<(usize, Option<LimitExceeded>), String> {
     profile!(
         config,
         "Starting parallel preparation and building ({})",
@@ -345,14 +931,24 @@ fn process_json_strings_parallel(
 
     if config.profile {
         if let Some(rss) = get_rss_bytes() {
-            anstream::eprintln!("📊 RSS before parallel processing: {}", format_bytes(rss));
+            profile!(
+                config,
+                "📊 RSS before parallel processing: {}",
+                format_bytes(rss)
+            );
         }
     }
 
     let mut processed_count = 0;
     let mut seen_hashes = HashSet::new();
+    let deadline = tracker.deadline();
+    let out_of_time = AtomicBool::new(false);
 
     for (chunk_idx, chunk) in json_strings.chunks(chunk_size).enumerate() {
+        if let Err(exceeded) = tracker.check_runtime() {
+            return Ok((processed_count, Some(exceeded)));
+        }
+
         profile!(
             config,
             "Processing chunk {} ({} strings)",
@@ -362,18 +958,29 @@ fn process_json_strings_parallel(
 
         if config.profile {
             if let Some(rss) = get_rss_bytes() {
-                anstream::eprintln!("📊 RSS before chunk {}: {}", chunk_idx, format_bytes(rss));
+                profile!(
+                    config,
+                    "📊 RSS before chunk {}: {}",
+                    chunk_idx,
+                    format_bytes(rss)
+                );
             }
         }
 
-        let chunk_builders: Vec<(usize, SchemaBuilder, bool)> = chunk
+        let chunk_builders: Vec<(usize, SchemaBuilder, Option<DocumentTimings>)> = chunk
             .par_iter()
             .enumerate()
             .map(
-                |(i, json_str)| -> Result<(usize, SchemaBuilder, bool), String> {
+                |(i, json_str)| -> Result<(usize, SchemaBuilder, Option<DocumentTimings>), String> {
                     profile_verbose!(config, "Thread processing JSON STRING {}", i);
 
-                    let prep_start = std::time::Instant::now();
+                    // Strings not yet started when the runtime limit passes are skipped
+                    if deadline.is_some_and(|d| crate::time::Instant::now() >= d) {
+                        out_of_time.store(true, Ordering::Relaxed);
+                        return Ok((i, get_builder(config.schema_uri.as_deref()), None));
+                    }
+
+                    let prep_start = crate::time::Instant::now();
                     let prepared = prepare_json_bytes(json_str.as_bytes(), i, config)?;
                     let prep_elapsed = prep_start.elapsed();
                     profile_verbose!(
@@ -384,7 +991,7 @@ fn process_json_strings_parallel(
                     );
 
                     if prepared.is_empty() {
-                        return Ok((i, get_builder(config.schema_uri.as_deref()), false));
+                        return Ok((i, get_builder(config.schema_uri.as_deref()), None));
                     }
 
                     let mut chunk_builder = get_builder(config.schema_uri.as_deref());
@@ -394,7 +1001,7 @@ fn process_json_strings_parallel(
                         ignore_outer_array: config.ignore_outer_array,
                     };
 
-                    let build_start = std::time::Instant::now();
+                    let build_start = crate::time::Instant::now();
                     build_json_schema(&mut chunk_builder, &mut bytes, &chunk_build_config);
                     let build_elapsed = build_start.elapsed();
                     profile_verbose!(
@@ -404,88 +1011,153 @@ fn process_json_strings_parallel(
                         build_elapsed
                     );
 
-                    Ok((i, chunk_builder, true))
+                    Ok((i, chunk_builder, Some((prep_elapsed, build_elapsed))))
                 },
             )
             .collect::<Result<Vec<_>, String>>()?;
 
         if config.profile {
             if let Some(rss) = get_rss_bytes() {
-                anstream::eprintln!("📊 RSS after collecting chunk: {}", format_bytes(rss));
+                profile!(
+                    config,
+                    "📊 RSS after collecting chunk: {}",
+                    format_bytes(rss)
+                );
             }
         }
 
         // Extract and merge schemas from this chunk
-        for (_i, individual_builder, was_non_empty) in chunk_builders {
-            if !was_non_empty {
+        for (_i, individual_builder, timings) in chunk_builders {
+            let Some((prep_elapsed, build_elapsed)) = timings else {
                 continue;
+            };
+            let merge_start = crate::time::Instant::now();
+            if let Some(recorder) = recorder.as_deref_mut() {
+                recorder.document(prep_elapsed, build_elapsed);
             }
 
             let mut schema = individual_builder.to_schema();
 
             // Apply force_field_types BEFORE merging to ensure structural consistency
-            apply_force_field_types(&mut schema, config);
+            apply_force_field_types(&mut schema, config, &[]);
 
             let hash = xxh64(schema.to_string().as_bytes(), 0);
-            if !seen_hashes.insert(hash) {
-                continue;
+            if seen_hashes.insert(hash) {
+                processed_count += 1;
+                builder.add_schema(schema);
+            }
+            if let Some(recorder) = recorder.as_deref_mut() {
+                recorder.merge(merge_start.elapsed());
             }
-
-            processed_count += 1;
-            builder.add_schema(schema);
         }
 
         if config.profile {
             if let Some(rss) = get_rss_bytes() {
-                anstream::eprintln!("📊 RSS after merging chunk: {}", format_bytes(rss));
+                profile!(config, "📊 RSS after merging chunk: {}", format_bytes(rss));
+            }
+        }
+
+        if out_of_time.load(Ordering::Relaxed) {
+            if let Err(exceeded) = tracker.check_runtime() {
+                return Ok((processed_count, Some(exceeded)));
             }
         }
     }
 
     profile!(config, "All chunks processed ({})", current_time_hms());
 
-    Ok(processed_count)
+    Ok((processed_count, None))
+}
+
+/// Apply the row and byte limits to the input, returning the admitted prefix and
+/// the limit that cut it short, if any. NDJSON lines count as individual rows.
+fn apply_input_limits<'a>(
+    json_strings: &'a [&'a str],
+    config: &SchemaInferenceConfig,
+    tracker: &mut LimitTracker,
+) -> (Cow<'a, [&'a str]>, Option<LimitExceeded>) {
+    if config.limits.max_rows.is_none() && config.limits.max_bytes.is_none() {
+        return (Cow::Borrowed(json_strings), None);
+    }
+
+    for (i, json_str) in json_strings.iter().enumerate() {
+        if config.delimiter == Some(b'\n') {
+            let mut admitted_len = 0;
+            for line in json_str.split_inclusive('\n') {
+                let trimmed = line.trim();
+                if !trimmed.is_empty() {
+                    if let Err(exceeded) = tracker.admit(trimmed.len()) {
+                        let mut admitted = json_strings[..i].to_vec();
+                        if admitted_len > 0 {
+                            admitted.push(&json_str[..admitted_len]);
+                        }
+                        return (Cow::Owned(admitted), Some(exceeded));
+                    }
+                }
+                admitted_len += line.len();
+            }
+        } else if !json_str.trim().is_empty() {
+            if let Err(exceeded) = tracker.admit(json_str.len()) {
+                return (Cow::Borrowed(&json_strings[..i]), Some(exceeded));
+            }
+        }
+    }
+
+    (Cow::Borrowed(json_strings), None)
 }
 
 /// Recursively convert fields matching force_field_types BEFORE unification runs.
 /// This ensures structural consistency so that unification can succeed.
-pub(crate) fn preprocess_force_field_types(schema: &mut Value, config: &SchemaInferenceConfig) {
+pub(crate) fn preprocess_force_field_types(
+    schema: &mut Value,
+    config: &SchemaInferenceConfig,
+    path: &[String],
+) {
     match schema {
         Value::Object(obj) => {
             // Process properties
             if let Some(props) = obj.get_mut("properties") {
                 if let Some(props_obj) = props.as_object_mut() {
                     for (field_name, field_schema) in props_obj.iter_mut() {
+                        let field_path = child_path(path, field_name);
                         // Check if this field should be forced to a type
-                        if let Some(forced) = config.force_field_types.get(field_name) {
+                        if let Some(forced) =
+                            config.forced_field_type(Some(field_name), &field_path)
+                        {
                             if forced == "map" {
                                 convert_to_map(field_schema);
+                            } else if FORCED_SCALAR_TYPES.contains(&forced) {
+                                convert_to_scalar(field_schema, forced);
                             }
                         }
                         // Recurse into the field schema
-                        preprocess_force_field_types(field_schema, config);
+                        preprocess_force_field_types(field_schema, config, &field_path);
                     }
                 }
             }
             // Recurse into items (for arrays)
             if let Some(items) = obj.get_mut("items") {
-                preprocess_force_field_types(items, config);
+                preprocess_force_field_types(items, config, path);
             }
             // Recurse into additionalProperties (for maps)
             if let Some(additional) = obj.get_mut("additionalProperties") {
-                preprocess_force_field_types(additional, config);
+                preprocess_force_field_types(
+                    additional,
+                    config,
+                    &child_path(path, MAP_VALUES_SEGMENT),
+                );
             }
             // Recurse into anyOf branches
             if let Some(Value::Array(any_of)) = obj.get_mut("anyOf") {
                 for item in any_of {
-                    preprocess_force_field_types(item, config);
+                    preprocess_force_field_types(item, config, path);
                 }
             }
         }
         Value::Array(arr) => {
             // Handle union types like ["null", {...}]
             for item in arr {
-                preprocess_force_field_types(item, config);
+                preprocess_force_field_types(item, config, path);
             }
         }
         _ => {}
@@ -547,9 +1219,42 @@ fn convert_to_map(schema: &mut Value) {
     }
 }
 
+/// Replace any schema with the scalar type `forced` (one of
+/// [`FORCED_SCALAR_TYPES`]), nullable if the schema accepted null.
+fn convert_to_scalar(schema: &mut Value, forced: &str) {
+    fn accepts_null(schema: &Value) -> bool {
+        match schema {
+            Value::String(t) => t == "null",
+            Value::Array(branches) => branches.iter().any(accepts_null),
+            Value::Object(obj) => {
+                obj.get("type").is_some_and(accepts_null)
+                    || obj.get("anyOf").is_some_and(accepts_null)
+            }
+            _ => false,
+        }
+    }
+    let (kind, format) = match forced {
+        "date" | "date-time" => ("string", Some(forced)),
+        kind => (kind, None),
+    };
+    let mut scalar = if accepts_null(schema) {
+        json!({"type": ["null", kind]})
+    } else {
+        json!({"type": kind})
+    };
+    if let Some(format) = format {
+        scalar["format"] = json!(format);
+    }
+    scalar[FORCED_TYPE_KEY] = json!(forced);
+    *schema = scalar;
+}
+
 /// Infer JSON schema from a collection of JSON strings
-pub fn infer_json_schema_from_strings(
-    json_strings: &[String],
+///
+/// Accepts owned strings or borrowed slices (e.g. the values of an Arrow string
+/// array), which are read in place rather than copied.
+pub fn infer_json_schema_from_strings<S: AsRef<str>>(
+    json_strings: &[S],
     config: SchemaInferenceConfig,
 ) -> Result<SchemaInferenceResult, String> {
     profile!(
@@ -563,66 +1268,366 @@ pub fn infer_json_schema_from_strings(
         return Err("No JSON strings provided".to_string());
     }
 
-    // Wrap the entire genson-rs interaction in panic handling
-    let result = panic::catch_unwind(AssertUnwindSafe(
-        || -> Result<SchemaInferenceResult, String> {
-            // Create schema builder
-            let mut builder = get_builder(config.schema_uri.as_deref());
+    let json_strings: Vec<&str> = json_strings.iter().map(AsRef::as_ref).collect();
+    let mut recorder = (config.profile || config.collect_profile).then(ProfileRecorder::new);
+    let raw = build_raw_schema(&json_strings, &config, recorder.as_mut())?;
+    finalise_schema(raw, &config, recorder)
+}
 
-            profile!(config, "Starting preparation loop ({})", current_time_hms());
+/// The output of the build stage, before any of the rewrites.
+pub(crate) struct RawSchema {
+    pub(crate) schema: Value,
+    /// Number of documents processed
+    pub(crate) processed_count: usize,
+    /// The resource limit that cut the input short, if any
+    pub(crate) limit_reached: Option<LimitExceeded>,
+    /// Statistics of the documents for the rewrites, as the config asks
+    pub(crate) stats: DocumentStats,
+}
 
-            let use_parallel = std::env::var("GENSON_PARALLEL")
-                .map(|v| v == "1" || v.to_lowercase() == "true")
-                .unwrap_or_else(|_| json_strings.len() >= PARALLEL_THRESHOLD);
+/// `text` with its syntax errors repaired and its non-finite literals read as
+/// numbers, as `config` asks.
+fn prepare_text<'a>(text: &'a str, config: &SchemaInferenceConfig) -> Cow<'a, str> {
+    let text = if config.repair {
+        repair_json(text).0
+    } else {
+        Cow::Borrowed(text)
+    };
+    if config.nonfinite_policy.is_none() {
+        return text;
+    }
+    match nonfinite_as_number(&text) {
+        Cow::Borrowed(_) => text,
+        Cow::Owned(scrubbed) => Cow::Owned(scrubbed),
+    }
+}
 
-            let processed_count = if use_parallel {
-                process_json_strings_parallel(json_strings, &config, &mut builder)?
-            } else {
-                process_json_strings_sequential(json_strings, &config, &mut builder)?
-            };
+/// The build stage of inference: sample and limit the input, then merge every
+/// document into one raw genson schema, before any of the rewrites.
+pub(crate) fn build_raw_schema(
+    json_strings: &[&str],
+    config: &SchemaInferenceConfig,
+    recorder: Option<&mut ProfileRecorder>,
+) -> Result<RawSchema, String> {
+    on_inference_stack(|| build_raw_schema_inner(json_strings, config, recorder))
+}
 
-            // Get final schema
-            let mut final_schema = builder.to_schema();
-            profile!(
+fn build_raw_schema_inner(
+    json_strings: &[&str],
+    config: &SchemaInferenceConfig,
+    mut recorder: Option<&mut ProfileRecorder>,
+) -> Result<RawSchema, String> {
+    if let Some(seed) = &config.seed_schema {
+        validate_seed(seed)?;
+    }
+    let scrubbed: Option<Vec<Cow<str>>> = (config.repair || config.nonfinite_policy.is_some())
+        .then(|| {
+            json_strings
+                .iter()
+                .map(|s| prepare_text(s, config))
+                .collect()
+        });
+    let scrubbed: Option<Vec<&str>> = scrubbed
+        .as_ref()
+        .map(|strings| strings.iter().map(|s| s.as_ref()).collect());
+    let json_strings = scrubbed.as_deref().unwrap_or(json_strings);
+
+    let sampled = config.sample.map(|spec| {
+        let sampled = sample_documents(json_strings, spec, config.delimiter == Some(b'\n'));
+        debug!(config, "Sampled {} document(s): {}", sampled.len(), spec);
+        sampled
+    });
+    let json_strings = sampled.as_deref().unwrap_or(json_strings);
+
+    let mut tracker = LimitTracker::new(&config.limits);
+    let (json_strings, input_limit) = apply_input_limits(json_strings, config, &mut tracker);
+    if let (true, Some(path)) = (config.wrap_root_strict, &config.wrap_root) {
+        check_wrapped_rows(&json_strings, path, config)?;
+    }
+    let mut limit_reached = input_limit.map(|e| tracker.resolve(e)).transpose()?;
+    if let Some(exceeded) = limit_reached {
+        debug!(
+            config,
+            "Input truncated to {} string(s): {}",
+            json_strings.len(),
+            exceeded
+        );
+    }
+
+    // Wrap the entire genson-rs interaction in panic handling
+    let result = panic::catch_unwind(AssertUnwindSafe(|| -> Result<(Value, usize), String> {
+        // Create schema builder
+        let mut builder = get_builder(config.schema_uri.as_deref());
+        if let Some(seed) = &config.seed_schema {
+            builder.add_schema(builder_seed(seed));
+        }
+
+        profile!(config, "Starting preparation loop ({})", current_time_hms());
+
+        let use_parallel = std::env::var("GENSON_PARALLEL")
+            .map(|v| v == "1" || v.to_lowercase() == "true")
+            .unwrap_or_else(|_| json_strings.len() >= PARALLEL_THRESHOLD);
+
+        let (processed_count, runtime_limit) = if let Some(chunk_size) = config.chunk_size {
+            process_json_strings_chunked(
+                &json_strings,
+                chunk_size,
                 config,
-                "Applying force field types ({})",
-                current_time_hms()
-            );
-            preprocess_force_field_types(&mut final_schema, &config);
-            profile!(config, "Rewriting objects ({})", current_time_hms());
-            rewrite_objects(&mut final_schema, None, &config, true);
-            profile!(config, "Reordering unions ({})", current_time_hms());
-            reorder_unions(&mut final_schema);
-
-            #[cfg(feature = "avro")]
-            if config.avro {
-                let avro_schema = SchemaInferenceResult {
-                    schema: final_schema.clone(),
-                    processed_count,
-                }
-                .to_avro_schema(
-                    "genson", // namespace
-                    Some(""),
-                    Some(""), // base_uri
-                    false,    // don't split top-level
-                );
-                return Ok(SchemaInferenceResult {
-                    schema: avro_schema,
-                    processed_count,
-                });
-            }
+                &mut builder,
+                &tracker,
+                recorder.as_deref_mut(),
+            )?
+        } else if use_parallel {
+            process_json_strings_parallel(
+                &json_strings,
+                config,
+                &mut builder,
+                &tracker,
+                recorder.as_deref_mut(),
+            )?
+        } else {
+            process_json_strings_sequential(
+                &json_strings,
+                config,
+                &mut builder,
+                &tracker,
+                recorder.as_deref_mut(),
+            )?
+        };
+        if let Some(exceeded) = runtime_limit {
+            limit_reached.get_or_insert(tracker.resolve(exceeded)?);
+        }
+
+        Ok((builder.to_schema(), processed_count))
+    }));
+
+    match result {
+        Ok(Ok((schema, processed_count))) => Ok(RawSchema {
+            schema,
+            processed_count,
+            limit_reached,
+            stats: DocumentStats::collect(&json_strings, config),
+        }),
+        Ok(Err(e)) => Err(e),
+        Err(_panic) => Err("JSON schema inference failed due to invalid JSON input".to_string()),
+    }
+}
 
-            Ok(SchemaInferenceResult {
-                schema: final_schema,
+/// The rewrite stage of inference: turn a raw genson schema into the final schema,
+/// applying forced types, map detection, union ordering and the Avro conversion.
+pub(crate) fn finalise_schema(
+    raw: RawSchema,
+    config: &SchemaInferenceConfig,
+    recorder: Option<ProfileRecorder>,
+) -> Result<SchemaInferenceResult, String> {
+    on_inference_stack(|| finalise_schema_inner(raw, config, recorder))
+}
+
+fn finalise_schema_inner(
+    raw: RawSchema,
+    config: &SchemaInferenceConfig,
+    mut recorder: Option<ProfileRecorder>,
+) -> Result<SchemaInferenceResult, String> {
+    let RawSchema {
+        schema: raw_schema,
+        processed_count,
+        limit_reached,
+        stats:
+            DocumentStats {
+                field_counts,
+                object_counts,
+                tuple_shapes,
+                key_spellings,
+                string_formats,
+                key_kinds,
+                key_cardinality,
+                sparse_arrays,
+                type_counts,
+                tagged_schemas,
+            },
+    } = raw;
+    if let Some(threshold) = config.required_threshold {
+        validate_threshold(threshold)?;
+    }
+    if let Some(detection) = &config.detect_formats {
+        detection.validate()?;
+    }
+    for (key, forced) in &config.force_field_types {
+        if !matches!(forced.as_str(), "map" | "record")
+            && !FORCED_SCALAR_TYPES.contains(&forced.as_str())
+        {
+            return Err(format!(
+                "Invalid force_field_types value for {}: {} (expected map|record|{})",
+                key,
+                forced,
+                FORCED_SCALAR_TYPES.join("|")
+            ));
+        }
+    }
+    if let Some(fallback) = &config.null_fallback {
+        if !matches!(
+            fallback.as_str(),
+            "string" | "integer" | "number" | "boolean"
+        ) {
+            return Err(format!(
+                "Invalid null_fallback: {} (expected string|integer|number|boolean)",
+                fallback
+            ));
+        }
+    }
+    #[cfg(feature = "avro")]
+    let avro_options = if config.avro {
+        let options = crate::avro::AvroOptions::from_config(config);
+        options.validate()?;
+        Some(options)
+    } else {
+        None
+    };
+    let mut issues = Vec::new();
+    let result = panic::catch_unwind(AssertUnwindSafe(|| -> SchemaInferenceResult {
+        let mut final_schema = raw_schema;
+        if let Some(seed) = &config.seed_schema {
+            fold_seed_maps(&mut final_schema, seed);
+        }
+        if config.detect_tuples {
+            apply_tuples(&mut final_schema, &tuple_shapes);
+        }
+        if config.coerce_map_array {
+            coerce_map_arrays(&mut final_schema);
+        }
+        profile!(
+            config,
+            "Applying force field types ({})",
+            current_time_hms()
+        );
+        preprocess_force_field_types(&mut final_schema, config, &[]);
+        profile!(config, "Rewriting objects ({})", current_time_hms());
+        let rewrite_start = crate::time::Instant::now();
+        // Strict mode needs the map/record decisions whether or not they're reported
+        let explain =
+            (config.explain || config.strict || config.decision_log).then(ExplainRecorder::default);
+        let hints = RewriteHints {
+            presence: Some(PresenceCounts {
+                fields: &field_counts,
+                objects: &object_counts,
+            }),
+            seed_records: config
+                .seed_schema
+                .as_ref()
+                .map(seed_records)
+                .unwrap_or_default(),
+        };
+        rewrite_objects(
+            &mut final_schema,
+            None,
+            &[],
+            config,
+            true,
+            &hints,
+            explain.as_ref(),
+        );
+        if !config.discriminators.is_empty() {
+            apply_discriminators(&mut final_schema, &tagged_schemas, config, &hints);
+        }
+        let decision_log = explain
+            .as_ref()
+            .filter(|_| config.decision_log)
+            .map(ExplainRecorder::events);
+        let explanation = explain.map(ExplainRecorder::finish);
+        if let Some(recorder) = recorder.as_mut() {
+            recorder.rewrite_objects(rewrite_start.elapsed());
+        }
+        if config.empty_container_policy == Some(EmptyContainerPolicy::TypeAsMapOfString) {
+            type_empty_containers(&mut final_schema);
+        }
+        let presence = config.required_threshold.map(|threshold| {
+            apply_required_threshold(&mut final_schema, threshold, &field_counts, &object_counts)
+        });
+        if config.detect_formats.is_some() {
+            apply_string_formats(&mut final_schema, &string_formats);
+        }
+        if config.detect_numeric_keys {
+            apply_numeric_keys(&mut final_schema, &key_kinds);
+        }
+        if config.sparse_array_detection {
+            apply_sparse_arrays(&mut final_schema, &sparse_arrays);
+        }
+        let map_key_stats = config
+            .map_key_stats
+            .map(|top_k| map_key_stats(&final_schema, &key_cardinality, top_k));
+        let defaulted_fields = match config.null_fallback {
+            Some(ref fallback) => apply_null_fallback(&mut final_schema, fallback),
+            None => Vec::new(),
+        };
+        profile!(config, "Reordering unions ({})", current_time_hms());
+        order_unions(&mut final_schema, config.union_order, &type_counts);
+        order_fields(&mut final_schema, config.sort_fields, &field_counts);
+        if config.key_fold == Some(KeyFold::Preserve) {
+            restore_spellings(&mut final_schema, &key_spellings);
+        }
+        let key_collisions = config
+            .key_fold
+            .map(|fold| key_collisions(&key_spellings, fold))
+            .unwrap_or_default();
+        annotate_schema(&mut final_schema, &config.annotations);
+        if config.strict {
+            issues = strict_issues(&final_schema, explanation.as_deref().unwrap_or(&[]), config);
+        }
+        let explanation = explanation.filter(|_| config.explain);
+
+        #[cfg(feature = "avro")]
+        if let Some(avro_options) = &avro_options {
+            let avro_start = crate::time::Instant::now();
+            let mut avro_schema =
+                crate::avro::convert_json_schema(std::mem::take(&mut final_schema), avro_options);
+            union_order::order_avro_unions(&mut avro_schema, config.union_order, &type_counts);
+            if let Some(recorder) = recorder.as_mut() {
+                recorder.avro(avro_start.elapsed());
+            }
+            return SchemaInferenceResult {
+                schema: avro_schema,
                 processed_count,
-            })
-        },
-    ));
+                limit_reached,
+                profile: recorder.map(ProfileRecorder::finish),
+                explanation,
+                decision_log,
+                defaulted_fields,
+                presence,
+                key_collisions,
+                map_key_stats,
+            };
+        }
+
+        tuples::tuples_to_prefix_items(&mut final_schema);
+        if let Some(draft) = config.json_schema_draft {
+            apply_draft(&mut final_schema, draft);
+        }
+
+        SchemaInferenceResult {
+            schema: final_schema,
+            processed_count,
+            limit_reached,
+            profile: recorder.map(ProfileRecorder::finish),
+            explanation,
+            decision_log,
+            defaulted_fields,
+            presence,
+            key_collisions,
+            map_key_stats,
+        }
+    }));
 
     // Handle the result of panic::catch_unwind
     match result {
-        Ok(Ok(schema_result)) => Ok(schema_result),
-        Ok(Err(e)) => Err(e),
+        Ok(_) if !issues.is_empty() => Err(strict_error(&issues)),
+        Ok(mut schema_result) => {
+            if config.embed_config {
+                if let Value::Object(schema) = &mut schema_result.schema {
+                    schema.insert(CONFIG_SNAPSHOT_KEY.to_string(), config.config_snapshot());
+                }
+            }
+            Ok(schema_result)
+        }
         Err(_panic) => Err("JSON schema inference failed due to invalid JSON input".to_string()),
     }
 }
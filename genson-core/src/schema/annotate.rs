@@ -0,0 +1,136 @@
+//! Human-authored annotations (`annotations`).
+//!
+//! Titles, descriptions and examples kept in a metadata file, apart from the
+//! inferred schema, are merged into it by field path after inference. A schema
+//! regenerated from new data keeps its curated docs. Avro output takes each
+//! description as the field's `doc`, unless `avro_docs` gives one.
+
+use crate::schema::field_path::{child_path, is_path_pattern, key_matches, MAP_VALUES_SEGMENT};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Docs for one field, e.g. from a YAML metadata file:
+///
+/// ```yaml
+/// owner.name:
+///   title: Owner name
+///   description: Legal name of the account owner
+///   examples: [Ada Lovelace]
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FieldAnnotation {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub examples: Option<Vec<Value>>,
+}
+
+/// Parse annotations keyed by field name or path pattern (see
+/// `force_field_types`), from YAML (with the `yaml` feature) or JSON.
+pub fn parse_annotations(text: &str) -> Result<HashMap<String, FieldAnnotation>, String> {
+    #[cfg(feature = "yaml")]
+    let parsed = serde_yaml::from_str(text).map_err(|e| e.to_string());
+    #[cfg(not(feature = "yaml"))]
+    let parsed = serde_json::from_str(text).map_err(|e| e.to_string());
+    parsed.map_err(|e| format!("Invalid annotations: {}", e))
+}
+
+/// Add the title, description and examples annotated for each field of `schema`.
+///
+/// Paths follow the field path conventions: array items add no segment and map
+/// values add `*`. A field takes the annotation under its bare name if there is
+/// one, and otherwise the first matching path pattern in sorted order.
+pub fn annotate_schema(schema: &mut Value, annotations: &HashMap<String, FieldAnnotation>) {
+    if !annotations.is_empty() {
+        annotate_node(schema, annotations, &[]);
+    }
+}
+
+fn annotate_node(
+    schema: &mut Value,
+    annotations: &HashMap<String, FieldAnnotation>,
+    path: &[String],
+) {
+    let obj = match schema {
+        Value::Object(obj) => obj,
+        Value::Array(items) => {
+            for item in items {
+                annotate_node(item, annotations, path);
+            }
+            return;
+        }
+        _ => return,
+    };
+
+    for (key, child) in obj.iter_mut() {
+        match key.as_str() {
+            "properties" => {
+                if let Value::Object(props) = child {
+                    for (name, prop) in props.iter_mut() {
+                        let prop_path = child_path(path, name);
+                        if let (Some(annotation), Value::Object(prop_obj)) =
+                            (field_annotation(annotations, name, &prop_path), &mut *prop)
+                        {
+                            if let Some(title) = &annotation.title {
+                                prop_obj.insert("title".to_string(), Value::from(title.as_str()));
+                            }
+                            if let Some(description) = &annotation.description {
+                                prop_obj.insert(
+                                    "description".to_string(),
+                                    Value::from(description.as_str()),
+                                );
+                            }
+                            if let Some(examples) = &annotation.examples {
+                                prop_obj
+                                    .insert("examples".to_string(), Value::from(examples.clone()));
+                            }
+                        }
+                        annotate_node(prop, annotations, &prop_path);
+                    }
+                }
+            }
+            "additionalProperties" => {
+                annotate_node(child, annotations, &child_path(path, MAP_VALUES_SEGMENT))
+            }
+            "enum" | "const" | "default" | "examples" | "required" => {}
+            _ => annotate_node(child, annotations, path),
+        }
+    }
+}
+
+/// The annotation for the field `name` at `path`: an exact bare-name key first,
+/// then the first matching path pattern in sorted order.
+fn field_annotation<'a>(
+    annotations: &'a HashMap<String, FieldAnnotation>,
+    name: &str,
+    path: &[String],
+) -> Option<&'a FieldAnnotation> {
+    if let Some(annotation) = annotations.get(name) {
+        return Some(annotation);
+    }
+    let mut patterns: Vec<_> = annotations
+        .iter()
+        .filter(|(key, _)| is_path_pattern(key) && key_matches(key, Some(name), path))
+        .collect();
+    patterns.sort_by_key(|(key, _)| *key);
+    patterns.first().map(|(_, annotation)| *annotation)
+}
+
+/// The annotated descriptions, as Avro field docs keyed the same way.
+#[cfg(feature = "avro")]
+pub(crate) fn annotation_docs(
+    annotations: &HashMap<String, FieldAnnotation>,
+) -> impl Iterator<Item = (&String, &String)> {
+    annotations
+        .iter()
+        .filter_map(|(key, annotation)| Some((key, annotation.description.as_ref()?)))
+}
+
+#[cfg(test)]
+mod tests {
+    include!("../tests/annotate.rs");
+}
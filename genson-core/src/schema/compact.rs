@@ -0,0 +1,128 @@
+//! Schema compaction: strip keywords that don't change what a JSON Schema accepts.
+//!
+//! Inferred schemas carry some noise, like `$schema` on nested nodes, empty
+//! `required` arrays and `additionalProperties: true`, which bloats schema files
+//! committed for review. [`compact`] removes it in place.
+
+use serde_json::{Map, Value};
+
+/// What [`compact`] strips, besides the keywords that are always redundant.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompactOptions {
+    /// Keep `$schema` on the root. Nested copies are removed either way
+    pub keep_schema_uri: bool,
+    /// Collapse an `anyOf` or `oneOf` with a single branch into the node holding it
+    pub collapse_unions: bool,
+}
+
+impl Default for CompactOptions {
+    fn default() -> Self {
+        Self {
+            keep_schema_uri: true,
+            collapse_unions: true,
+        }
+    }
+}
+
+/// Keywords whose values are a single subschema.
+const SUBSCHEMA_KEYWORDS: &[&str] = &["additionalProperties", "items", "not", "contains"];
+
+/// Keywords whose values are a list of subschemas.
+const SUBSCHEMA_LIST_KEYWORDS: &[&str] = &["anyOf", "oneOf", "allOf", "prefixItems", "items"];
+
+/// Keywords whose values map names to subschemas.
+const SUBSCHEMA_MAP_KEYWORDS: &[&str] =
+    &["properties", "patternProperties", "definitions", "$defs"];
+
+/// Remove redundant keywords from a JSON Schema, in place:
+///
+/// - `$schema` below the root (and on the root too without `keep_schema_uri`)
+/// - empty `required` arrays
+/// - `additionalProperties: true`, which is the default
+/// - a one-name `type` array, which becomes the name
+/// - with `collapse_unions`, an `anyOf`/`oneOf` of one branch, merged into its node
+///   when none of their keywords clash
+pub fn compact(schema: &mut Value, options: CompactOptions) {
+    if !options.keep_schema_uri {
+        if let Value::Object(obj) = schema {
+            obj.remove("$schema");
+        }
+    }
+    compact_node(schema, &options, true);
+}
+
+fn compact_node(schema: &mut Value, options: &CompactOptions, root: bool) {
+    let Value::Object(obj) = schema else {
+        return;
+    };
+    if !root {
+        obj.remove("$schema");
+    }
+    if obj
+        .get("required")
+        .and_then(Value::as_array)
+        .is_some_and(Vec::is_empty)
+    {
+        obj.remove("required");
+    }
+    if obj.get("additionalProperties") == Some(&Value::Bool(true)) {
+        obj.remove("additionalProperties");
+    }
+    if let Some(Value::Array(names)) = obj.get_mut("type") {
+        if names.len() == 1 {
+            let name = names.remove(0);
+            obj.insert("type".to_string(), name);
+        }
+    }
+
+    for key in SUBSCHEMA_KEYWORDS {
+        if let Some(child @ Value::Object(_)) = obj.get_mut(*key) {
+            compact_node(child, options, false);
+        }
+    }
+    for key in SUBSCHEMA_LIST_KEYWORDS {
+        if let Some(Value::Array(children)) = obj.get_mut(*key) {
+            children
+                .iter_mut()
+                .for_each(|child| compact_node(child, options, false));
+        }
+    }
+    for key in SUBSCHEMA_MAP_KEYWORDS {
+        if let Some(Value::Object(children)) = obj.get_mut(*key) {
+            children
+                .values_mut()
+                .for_each(|child| compact_node(child, options, false));
+        }
+    }
+
+    if options.collapse_unions {
+        for key in ["anyOf", "oneOf"] {
+            collapse_single_branch(obj, key);
+        }
+    }
+}
+
+/// Merge the only branch of the union under `key` into `obj`, unless they share a
+/// keyword (which merging would lose).
+fn collapse_single_branch(obj: &mut Map<String, Value>, key: &str) {
+    let branch = match obj.get(key) {
+        Some(Value::Array(branches)) if branches.len() == 1 => match &branches[0] {
+            Value::Object(branch) => branch,
+            _ => return,
+        },
+        _ => return,
+    };
+    if branch.keys().any(|k| obj.contains_key(k)) {
+        return;
+    }
+    if let Some(Value::Array(mut branches)) = obj.remove(key) {
+        if let Some(Value::Object(branch)) = branches.pop() {
+            obj.extend(branch);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    include!("../tests/compact.rs");
+}
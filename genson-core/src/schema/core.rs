@@ -1,6 +1,24 @@
+#[cfg(feature = "avro")]
+use crate::avro::AvroNameStrategy;
+use crate::limits::{LimitExceeded, ResourceLimits};
+use crate::nonfinite::NonFinitePolicy;
+use crate::sample::SampleSpec;
+use crate::schema::annotate::FieldAnnotation;
+use crate::schema::draft::JsonSchemaDraft;
+use crate::schema::empty_containers::EmptyContainerPolicy;
+use crate::schema::explain::{DecisionEvent, FieldExplanation};
+use crate::schema::field_order::FieldOrder;
+use crate::schema::field_path::{is_path_pattern, key_matches};
+use crate::schema::formats::FormatDetection;
+use crate::schema::key_fold::{KeyCollision, KeyFold};
+use crate::schema::map_stats::MapKeyStats;
+use crate::schema::presence::FieldPresence;
+use crate::schema::profile::InferenceProfile;
+use crate::schema::union_order::UnionOrder;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
+use std::path::PathBuf;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SchemaInferenceConfig {
@@ -8,18 +26,48 @@ pub struct SchemaInferenceConfig {
     pub ignore_outer_array: bool,
     /// Delimiter for NDJSON format (None for regular JSON)
     pub delimiter: Option<u8>,
-    /// Schema URI to use ("AUTO" for auto-detection)
+    /// Schema URI to use ("AUTO" for auto-detection, None to omit `$schema`)
     pub schema_uri: Option<String>,
+    /// JSON Schema draft to write the schema for: sets the `$schema` URI (unless
+    /// `schema_uri` is None) and the draft's keywords, e.g. `$defs` rather than
+    /// `definitions`. None: genson's default URI and keywords
+    pub json_schema_draft: Option<JsonSchemaDraft>,
     /// Threshold above which non-fixed keys are treated as a map
     pub map_threshold: usize,
     /// Maximum number of required keys a Map can have. If None, no gating based on required keys.
     /// If Some(n), objects with more than n required keys will be forced to Record type.
     pub map_max_required_keys: Option<usize>,
+    /// How an object's keys are counted against `map_threshold`: the keys of the
+    /// merged schema (`Merged`), or also how much the key sets of individual
+    /// documents vary (`PerDocumentVariance`), so stable records stay records
+    pub map_detection: MapDetection,
     /// Enable unification of compatible but non-homogeneous record schemas into maps
     pub unify_maps: bool,
-    /// Fields whose keys should not be merged during record unification
+    /// Recognise multi-language label maps (`{lang: {language, value}}`, or arrays of such
+    /// records as in aliases) and make them `map<string, record{language, value}>`
+    /// regardless of `map_threshold`.
+    pub label_maps: bool,
+    /// Like `label_maps`, but simplify the map values to just the `value` string
+    /// (`map<string, string>`, or `map<string, array<string>>` for aliases). Normalise with
+    /// `NormaliseConfig::simplify_labels` to unwrap the records in the data to match.
+    pub simplify_labels: bool,
+    /// Keep a schema per position for arrays that had the same length in every
+    /// document and different values at each position (e.g. `[string, object]`
+    /// pairs), as `prefixItems` (or a record of fields `_0`, `_1`, ... in Avro),
+    /// instead of one `items` union
+    pub detect_tuples: bool,
+    /// Collapse unions of a map of arrays and an array of maps (`{"en": ["a"]}`
+    /// and `[{"en": "a"}]`) to the map-of-arrays form, which normalisation folds
+    /// the array form into
+    pub coerce_map_array: bool,
+    /// Fields whose keys should not be merged during record unification.
+    /// Keys may be bare field names or path patterns (see `force_field_types`).
     pub no_unify: std::collections::HashSet<String>,
-    /// Force override of field treatment, e.g. {"labels": "map"}
+    /// Force override of field treatment, e.g. {"labels": "map"}, or a scalar type
+    /// (see [`FORCED_SCALAR_TYPES`]) for fields the heuristics guess wrong, e.g.
+    /// {"price": "number"}. Keys are bare field names (matching at any depth) or path patterns such as
+    /// `/claims/*/mainsnak/datavalue` or `claims.*.mainsnak.datavalue`, where `*`
+    /// matches one segment and `**` any number of segments.
     pub force_field_types: HashMap<String, String>,
     /// Force parent objects containing these fields to remain as records, preventing map inference.
     /// e.g. {"mainsnak": "record"} prevents any object containing a "mainsnak" field from being
@@ -28,33 +76,227 @@ pub struct SchemaInferenceConfig {
     /// Set of field names that should always be promoted to wrapped scalars,
     /// even when they appear as simple scalars (not in type unions). This ensures
     /// schema stability for fields known to have heterogeneous types across schematised files.
+    /// Keys may be bare field names or path patterns (see `force_field_types`).
     pub force_scalar_promotion: std::collections::HashSet<String>,
+    /// Subtrees to leave out of inference, e.g. freeform `metadata` blobs. The values
+    /// at these fields are treated as opaque JSON strings, so their contents never
+    /// reach the schema. Keys may be bare field names or path patterns (see
+    /// `force_field_types`), matched after `wrap_root` is applied.
+    pub ignore_paths: Vec<String>,
+    /// Fields to keep, e.g. the 20 of 400 a consumer reads: every other field is
+    /// pruned from the documents before inference, so the schema holds only these
+    /// (with their subtrees) and the fields leading to them. Keys may be bare field
+    /// names or path patterns (see `force_field_types`), matched after `wrap_root` is
+    /// applied. Empty: keep every field
+    pub select_paths: Vec<String>,
+    /// Objects to infer as tagged unions, keyed by their path, with the name of
+    /// their tag field: the objects at the path are grouped by tag value and the
+    /// record becomes a `oneOf` with one branch per value, its tag fixed with
+    /// `const`, instead of one record unifying them all. Objects without a scalar tag
+    /// form a branch of their own. Keys may be bare field names, path patterns (see
+    /// `force_field_types`) or `/` for the document root
+    pub discriminators: HashMap<String, String>,
+    /// Titles, descriptions and examples to add to fields of the final schema, e.g.
+    /// read from a metadata file with `parse_annotations`. Keys may be bare field
+    /// names or path patterns (see `force_field_types`). Avro output takes each
+    /// description as the field's `doc`
+    pub annotations: HashMap<String, FieldAnnotation>,
+    /// Maximum nesting depth of objects and arrays, counting the document root as 1.
+    /// Deeper containers are treated as opaque JSON strings, like `ignore_paths`,
+    /// instead of being recursed into. None: no limit
+    pub max_depth: Option<usize>,
+    /// Maximum number of keys an object may have below the document root. Wider
+    /// objects are treated as opaque JSON strings. None: no limit
+    pub max_object_keys: Option<usize>,
+    /// What to make of empty objects and arrays, which otherwise count as records with
+    /// the fields of the others at their path and leave array items untyped. None:
+    /// they're inferred like any other value
+    pub empty_container_policy: Option<EmptyContainerPolicy>,
     /// Whether to promote scalar values to wrapped objects when they collide with record values
     /// during unification. If `true`, scalars are promoted under a synthetic property name derived from
-    /// the parent field and the scalar type (e.g. "foo__string"). If `false`, don't unify on conflicts.
+    /// the parent field and the scalar type (e.g. "foo__string"). Arrays of scalars that collide with
+    /// arrays of records have their items promoted the same way. If `false`, don't unify on conflicts.
     pub wrap_scalars: bool,
     /// Wrap the inferred top-level schema under a single required field with this name.
     /// Example: wrap_root = Some("labels") turns `{...}` into
     /// `{"type":"object","properties":{"labels":{...}},"required":["labels"]}`.
+    /// A dotted path nests it several levels deep: `"entity.claims"` wraps each
+    /// document as `{"entity":{"claims":{...}}}`.
     pub wrap_root: Option<String>,
+    /// Rows already wrapped under `wrap_root` (e.g. `{"labels": {...}}` for
+    /// `"labels"`) are left as they are. Error if some rows are wrapped already and
+    /// others aren't, rather than inferring from both
+    pub wrap_root_strict: bool,
+    /// Field that documents with a bare root (a scalar, or an array that isn't an
+    /// outer array of objects) are wrapped under when `wrap_root` is unset, so
+    /// `"a"` and `[1, 2]` are inferred as `{"value": "a"}` and `{"value": [1, 2]}`.
+    /// A dotted path nests it like `wrap_root`
+    pub root_value_field: String,
+    /// What an outer array holding scalars (or nested arrays) is, when
+    /// `ignore_outer_array` is set: one document whose root is that array (`Array`),
+    /// or one document per item, with the items that aren't objects wrapped under
+    /// `root_value_field` (`Rows`)
+    pub outer_scalar_policy: OuterScalarPolicy,
+    /// Merge keys that differ only in case (`ID`, `Id`, `id`) into one field, by
+    /// folding every key before inference. Keys seen with several spellings are
+    /// reported in `key_collisions`. None: keys are case-sensitive
+    pub key_fold: Option<KeyFold>,
+    /// Check strings against well-known formats (UUID, URI, email, base64, IP) and
+    /// annotate the paths whose strings all had one with `format` (or
+    /// `contentEncoding` for base64). Avro gives UUIDs the `uuid` logical type.
+    /// None: no detection
+    pub detect_formats: Option<FormatDetection>,
+    /// Annotate maps whose keys were all integers (`"1"`, `"42"`) in every
+    /// document with `"x-key-type": "integer"`, which Avro maps keep and `kv`
+    /// normalisation writes as integer keys
+    pub detect_numeric_keys: bool,
+    /// Infer objects keyed `"0"` to `"n-1"` at a path (PHP's encoding of lists)
+    /// as arrays of their values, when every object there is keyed that way.
+    /// Normalise with the same `sparse_array_detection` on `NormaliseConfig`
+    pub sparse_array_detection: bool,
+    /// JSON Schema (previously inferred or hand-written) that inference starts from
+    /// and only widens: the documents add fields and types and can make fields
+    /// optional, but the seed's types, keywords, maps and records are kept.
+    /// None: infer from the documents alone
+    pub seed_schema: Option<Value>,
     /// Prevent the document root from becoming a map type, even if it meets map inference criteria
     pub no_root_map: bool,
+    /// Type to give fields that were null in every document (`string`, `integer`,
+    /// `number` or `boolean`), which become nullable fields of that type instead of
+    /// `null`-typed ones that Avro consumers can't use. None: leave them as `null`
+    pub null_fallback: Option<String>,
+    /// Fraction of the objects at a path (0 to 1) that must have a field for it to be
+    /// required, rather than all of them, so a few malformed rows don't make the
+    /// field optional. Also reports the presence ratio of every field on the result
+    /// (`presence`). None: required only if always present
+    pub required_threshold: Option<f64>,
+    /// Order of the properties (and Avro fields) in the output schema. `Preserve`
+    /// keeps the order fields were first seen in, which follows the input row order
+    pub sort_fields: FieldOrder,
+    /// Order of union branches. Avro takes a field's default from the first branch
+    /// of its union, so `DefaultFirst` keeps generated defaults valid
+    pub union_order: UnionOrder,
+    /// Accept the `NaN`, `Infinity` and `-Infinity` literals lax serialisers write,
+    /// typing them as `number`. The policy is how `normalise` writes them (see
+    /// `NormaliseConfig::nonfinite_policy`). None: they make the input invalid
+    pub nonfinite_policy: Option<NonFinitePolicy>,
+    /// Repair trailing commas, single-quoted strings, unquoted keys, a leading byte
+    /// order mark and raw control characters before parsing (see `crate::repair`),
+    /// so low-grade syntax errors don't fail the whole input
+    pub repair: bool,
     /// Maximum number of schema builders to create in parallel at once
     /// Lower values reduce peak memory usage during schema inference
     /// None: process all strings at once
     pub max_builders: Option<usize>,
+    /// Process documents in batches of this many, merging each batch's schema and
+    /// dropping its builders before the next, so peak memory stays bounded however
+    /// large the input. NDJSON strings are split into lines, which are counted as
+    /// documents in `processed_count`. None: no batching
+    pub chunk_size: Option<usize>,
+    /// Infer from a sample of the documents (NDJSON lines) rather than all of them.
+    /// `processed_count` then counts the sampled documents. None: use everything
+    pub sample: Option<SampleSpec>,
+    /// Row, byte and runtime limits for this run (unlimited by default)
+    pub limits: ResourceLimits,
+    /// Characters of an invalid document to quote in its parse error, centred on
+    /// where parsing failed, so the broken part of a long row is the part shown
+    pub error_context_chars: usize,
+    /// Directory to write invalid documents to in full, with each file's path given
+    /// in the parse error, for rows too long to quote. None: don't write them
+    pub error_dump_dir: Option<PathBuf>,
     /// Whether to output Avro schema rather than regular JSON Schema.
     #[cfg(feature = "avro")]
     pub avro: bool,
+    /// Namespace of the Avro schema's root record, and the prefix of its nested
+    /// records' namespaces. None: `genson`
+    #[cfg(feature = "avro")]
+    pub avro_namespace: Option<String>,
+    /// Name of the Avro schema's root record. None: `document`
+    #[cfg(feature = "avro")]
+    pub avro_root_name: Option<String>,
+    /// Descriptions to add as the `doc` of Avro fields. Keys may be bare field
+    /// names or path patterns (see `force_field_types`)
+    #[cfg(feature = "avro")]
+    pub avro_docs: HashMap<String, String>,
+    /// How to write field names that aren't valid Avro names, such as `foo-bar`.
+    /// Renamed fields keep their JSON key, which normalisation reads them from
+    #[cfg(feature = "avro")]
+    pub avro_field_names: AvroNameStrategy,
+    /// Give nullable Avro fields `"default": null`, so readers can fill in the
+    /// field for records written without it
+    #[cfg(feature = "avro")]
+    pub avro_defaults: bool,
     /// Enable debug output. When `true`, prints detailed information about schema inference
     /// processes including field unification, map detection, and scalar wrapping decisions.
     pub debug: bool,
     /// Enable profiling output. When `true`, prints detailed information about timing.
     pub profile: bool,
+    /// Record per-stage timings on the result as an [`InferenceProfile`], without
+    /// printing anything. Implied by `profile`.
+    pub collect_profile: bool,
+    /// Record why each object became a map or a record on the result, as
+    /// [`FieldExplanation`]s keyed by field path
+    pub explain: bool,
+    /// Report the key cardinality of every inferred map on the result
+    /// (`map_key_stats`), with this many of each map's most frequent keys (see
+    /// [`crate::schema::DEFAULT_TOP_KEYS`]). None: not reported
+    pub map_key_stats: Option<usize>,
+    /// Record every map/record decision as a [`DecisionEvent`] on the result
+    /// (`decision_log`), in the order they were made, for a machine-readable log
+    /// of what debug output narrates
+    pub decision_log: bool,
     /// Controls the verbosity level of debug output
     pub verbosity: DebugVerbosity,
+    /// Embed the non-default settings used for inference in the schema, under
+    /// [`CONFIG_SNAPSHOT_KEY`], so the schema can be reproduced.
+    pub embed_config: bool,
+    /// Fail inference, listing the field paths, if the schema has a union other
+    /// than with null, an object that was a map in some places and a record in
+    /// others, or a scalar promoted into a record by `wrap_scalars`
+    pub strict: bool,
 }
 
+/// Default for [`SchemaInferenceConfig::error_context_chars`].
+pub const DEFAULT_ERROR_CONTEXT_CHARS: usize = 100;
+
+/// Root key of the config snapshot embedded with `embed_config`.
+pub const CONFIG_SNAPSHOT_KEY: &str = "x-genson-config";
+
+/// The scalar types `force_field_types` accepts, besides `map` and `record`.
+/// `date` and `date-time` are strings with that `format`.
+pub const FORCED_SCALAR_TYPES: &[&str] = &[
+    "string",
+    "integer",
+    "number",
+    "boolean",
+    "date",
+    "date-time",
+];
+
+/// Keyword naming the type of a field forced to a scalar with `force_field_types`,
+/// e.g. `{"type": "number", "x-forced-type": "number"}`. Avro output keeps it on
+/// the record field, where normalisation parses strings into the type.
+pub const FORCED_TYPE_KEY: &str = "x-forced-type";
+
+/// Settings that only affect diagnostics or resource use, not the schema produced,
+/// and the seed schema, whose snapshot would nest every earlier seed.
+const SNAPSHOT_EXCLUDED: &[&str] = &[
+    "seed_schema",
+    "debug",
+    "profile",
+    "collect_profile",
+    "explain",
+    "map_key_stats",
+    "decision_log",
+    "verbosity",
+    "max_builders",
+    "chunk_size",
+    "embed_config",
+    "error_context_chars",
+    "error_dump_dir",
+    "strict",
+];
+
 #[derive(Default, Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
 pub enum DebugVerbosity {
     /// Show important unification decisions and failures  
@@ -64,7 +306,136 @@ pub enum DebugVerbosity {
     Verbose,
 }
 
+/// How an outer array of scalars is read when `ignore_outer_array` is set.
+#[derive(Default, Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum OuterScalarPolicy {
+    /// The array is a single document: `[1, 2, 3]` is inferred as `{"value": [1, 2, 3]}`
+    #[default]
+    Array,
+    /// Each item is a document: `[1, 2, 3]` is three rows, `{"value": 1}` and so on
+    Rows,
+}
+
+/// How map detection counts the keys of an object against `map_threshold`.
+#[derive(Default, Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MapDetection {
+    /// The distinct keys of the merged schema, so two documents with disjoint
+    /// 3-key objects count as 6 keys
+    #[default]
+    Merged,
+    /// Like `Merged`, but an object seen at least twice must also have keys that
+    /// vary between documents: on average each key in at most
+    /// [`MAP_KEY_PRESENCE_RATIO`] of the objects. Objects with the same keys in
+    /// every document stay records, however many keys they have
+    PerDocumentVariance,
+}
+
+/// Mean share of the objects at a path that have each of its keys, at or below
+/// which [`MapDetection::PerDocumentVariance`] treats the keys as dynamic.
+pub const MAP_KEY_PRESENCE_RATIO: f64 = 0.5;
+
 impl SchemaInferenceConfig {
+    /// The `force_field_types` override for the field `name` at `path`, if any.
+    ///
+    /// Exact bare-name keys are checked first, then path patterns in key order.
+    pub(crate) fn forced_field_type(&self, name: Option<&str>, path: &[String]) -> Option<&str> {
+        let name = name?;
+        if let Some(forced) = self.force_field_types.get(name) {
+            return Some(forced.as_str());
+        }
+        self.force_field_types
+            .iter()
+            .find(|(key, _)| is_path_pattern(key) && key_matches(key, Some(name), path))
+            .map(|(_, forced)| forced.as_str())
+    }
+
+    /// The settings that differ from the defaults, as a JSON object.
+    ///
+    /// Diagnostic settings (debug, profiling, `max_builders`) are left out, and
+    /// sets and maps are sorted so the snapshot is stable between runs.
+    pub fn config_snapshot(&self) -> Value {
+        let current = serde_json::to_value(self).unwrap_or_default();
+        let default = serde_json::to_value(Self::default()).unwrap_or_default();
+        let (Value::Object(current), Value::Object(default)) = (current, default) else {
+            return Value::Object(Default::default());
+        };
+        let snapshot = current
+            .into_iter()
+            .filter(|(key, value)| {
+                !SNAPSHOT_EXCLUDED.contains(&key.as_str()) && default.get(key) != Some(value)
+            })
+            .map(|(key, mut value)| {
+                sort_collections(&mut value);
+                (key, value)
+            })
+            .collect();
+        Value::Object(snapshot)
+    }
+
+    /// The default config with the settings in `overrides` (a JSON object keyed like
+    /// the config's fields, e.g. a [`config_snapshot`](Self::config_snapshot)) applied.
+    ///
+    /// # Errors
+    /// Returns error if `overrides` isn't an object, names an unknown setting, or
+    /// gives a setting a value of the wrong type.
+    pub fn from_overrides(overrides: &Value) -> Result<Self, String> {
+        let Value::Object(overrides) = overrides else {
+            return Err("Expected a JSON object of config settings".to_string());
+        };
+        let Ok(Value::Object(mut config)) = serde_json::to_value(Self::default()) else {
+            return Err("Config doesn't serialise to an object".to_string());
+        };
+        for (key, value) in overrides {
+            if !config.contains_key(key) {
+                return Err(format!("Unknown config setting: {}", key));
+            }
+            config.insert(key.clone(), value.clone());
+        }
+        serde_json::from_value(Value::Object(config)).map_err(|e| format!("Invalid config: {}", e))
+    }
+
+    /// Whether the field `name` at `path` is listed in `no_unify`.
+    pub(crate) fn is_no_unify(&self, name: &str, path: &[String]) -> bool {
+        self.no_unify.contains(name)
+            || self
+                .no_unify
+                .iter()
+                .any(|key| is_path_pattern(key) && key_matches(key, Some(name), path))
+    }
+
+    /// Whether the field `name` at `path` is listed in `force_scalar_promotion`.
+    pub(crate) fn is_force_scalar_promotion(&self, name: Option<&str>, path: &[String]) -> bool {
+        let Some(name) = name else {
+            return false;
+        };
+        self.force_scalar_promotion.contains(name)
+            || self
+                .force_scalar_promotion
+                .iter()
+                .any(|key| is_path_pattern(key) && key_matches(key, Some(name), path))
+    }
+
+    /// The tag field of the objects at `path`, if `discriminators` lists it.
+    pub(crate) fn discriminator(&self, path: &[String]) -> Option<&str> {
+        self.discriminators
+            .iter()
+            .find(|(key, _)| match key.as_str() {
+                "/" => path.is_empty(),
+                key => key_matches(key, path.last().map(String::as_str), path),
+            })
+            .map(|(_, field)| field.as_str())
+    }
+
+    /// Whether the field `name` at `path` is listed in `ignore_paths`.
+    pub(crate) fn is_ignored_path(&self, name: &str, path: &[String]) -> bool {
+        self.ignore_paths
+            .iter()
+            .any(|key| key_matches(key, Some(name), path))
+    }
+
+    #[cfg(not(feature = "trace"))]
     pub(crate) fn profile(&self, args: std::fmt::Arguments) {
         if self.profile {
             let message = format!("{}", args);
@@ -72,6 +443,7 @@ impl SchemaInferenceConfig {
         }
     }
 
+    #[cfg(not(feature = "trace"))]
     pub(crate) fn profile_verbose(&self, args: std::fmt::Arguments) {
         if self.profile && matches!(self.verbosity, DebugVerbosity::Verbose) {
             let message = format!("{}", args);
@@ -79,6 +451,17 @@ impl SchemaInferenceConfig {
         }
     }
 
+    /// Whether debug output is wanted: `debug` is set or, with the `trace` feature
+    /// (where debug output goes to the tracing subscriber), the subscriber has debug
+    /// events enabled. Guards debug messages that are costly to format.
+    pub(crate) fn debug_enabled(&self) -> bool {
+        #[cfg(feature = "trace")]
+        return tracing::enabled!(tracing::Level::DEBUG);
+        #[cfg(not(feature = "trace"))]
+        self.debug
+    }
+
+    #[cfg(not(feature = "trace"))]
     pub(crate) fn debug(&self, args: std::fmt::Arguments) {
         if self.debug {
             let message = format!("{}", args);
@@ -86,6 +469,7 @@ impl SchemaInferenceConfig {
         }
     }
 
+    #[cfg(not(feature = "trace"))]
     pub(crate) fn debug_verbose(&self, args: std::fmt::Arguments) {
         if self.debug && matches!(self.verbosity, DebugVerbosity::Verbose) {
             let message = format!("{}", args);
@@ -93,6 +477,7 @@ impl SchemaInferenceConfig {
         }
     }
 
+    #[cfg(not(feature = "trace"))]
     fn maybe_truncate(&self, message: String) -> String {
         let lines: Vec<&str> = message.lines().collect();
 
@@ -126,58 +511,256 @@ impl Default for SchemaInferenceConfig {
             ignore_outer_array: true,
             delimiter: None,
             schema_uri: Some("AUTO".to_string()),
+            json_schema_draft: None,
             map_threshold: 20,
             map_max_required_keys: None,
+            map_detection: MapDetection::Merged,
             unify_maps: false,
+            label_maps: false,
+            simplify_labels: false,
+            detect_tuples: false,
+            coerce_map_array: false,
             no_unify: std::collections::HashSet::new(),
             force_field_types: std::collections::HashMap::new(),
             force_parent_field_types: std::collections::HashMap::new(),
             force_scalar_promotion: std::collections::HashSet::new(),
+            ignore_paths: Vec::new(),
+            select_paths: Vec::new(),
+            discriminators: HashMap::new(),
+            annotations: HashMap::new(),
+            max_depth: None,
+            max_object_keys: None,
+            empty_container_policy: None,
             wrap_scalars: true,
             wrap_root: None,
+            wrap_root_strict: false,
+            root_value_field: DEFAULT_ROOT_VALUE_FIELD.to_string(),
+            outer_scalar_policy: OuterScalarPolicy::Array,
+            key_fold: None,
+            detect_formats: None,
+            detect_numeric_keys: false,
+            sparse_array_detection: false,
+            seed_schema: None,
             no_root_map: true,
+            null_fallback: None,
+            required_threshold: None,
+            sort_fields: FieldOrder::Preserve,
+            union_order: UnionOrder::Canonical,
+            nonfinite_policy: None,
+            repair: false,
             max_builders: None,
+            chunk_size: None,
+            sample: None,
+            limits: ResourceLimits::default(),
+            error_context_chars: DEFAULT_ERROR_CONTEXT_CHARS,
+            error_dump_dir: None,
             #[cfg(feature = "avro")]
             avro: false,
+            #[cfg(feature = "avro")]
+            avro_namespace: None,
+            #[cfg(feature = "avro")]
+            avro_root_name: None,
+            #[cfg(feature = "avro")]
+            avro_docs: HashMap::new(),
+            #[cfg(feature = "avro")]
+            avro_field_names: AvroNameStrategy::Keep,
+            #[cfg(feature = "avro")]
+            avro_defaults: true,
             debug: false,
             profile: false,
+            collect_profile: false,
+            explain: false,
+            map_key_stats: None,
+            decision_log: false,
             verbosity: DebugVerbosity::default(),
+            embed_config: false,
+            strict: false,
         }
     }
 }
 
+/// Field that bare document roots are wrapped under by default.
+pub const DEFAULT_ROOT_VALUE_FIELD: &str = "value";
+
+/// Whether a document root is a bare value rather than a record: a non-null scalar,
+/// or an array with an item that isn't an object. Arrays of objects are outer
+/// arrays, whose items are the documents, and a null root is a missing document.
+pub(crate) fn is_bare_root(value: &Value) -> bool {
+    match value {
+        Value::Object(_) | Value::Null => false,
+        Value::Array(items) => !items.iter().all(Value::is_object),
+        _ => true,
+    }
+}
+
+/// Whether `value` is an outer array whose items are documents in their own right:
+/// always for an array of objects, and for any array under
+/// [`OuterScalarPolicy::Rows`].
+pub(crate) fn is_outer_array(value: &Value, config: &SchemaInferenceConfig) -> bool {
+    match value {
+        Value::Array(items) if config.ignore_outer_array => {
+            config.outer_scalar_policy == OuterScalarPolicy::Rows
+                || items.iter().all(Value::is_object)
+        }
+        _ => false,
+    }
+}
+
+/// Wrap a document under `wrap_root` if set (unless it is already wrapped),
+/// otherwise wrap it under `root_value_field` if its root is bare.
+pub(crate) fn wrap_document(
+    value: Value,
+    wrap_root: Option<&str>,
+    root_value_field: &str,
+) -> Value {
+    match wrap_root {
+        Some(path) if is_wrapped(path, &value) => value,
+        Some(path) => wrap_root_value(path, value),
+        None if is_bare_root(&value) => wrap_root_value(root_value_field, value),
+        None => value,
+    }
+}
+
+/// Wrap `value` under the dotted `path`, innermost segment first:
+/// `"entity.claims"` gives `{"entity":{"claims":value}}`.
+pub(crate) fn wrap_root_value(path: &str, value: Value) -> Value {
+    path.rsplit('.').fold(value, |inner, field| {
+        Value::Object(std::iter::once((field.to_string(), inner)).collect())
+    })
+}
+
+/// Whether `value` is already wrapped under the dotted `path`: an object whose only
+/// key is the path's first segment, holding a value wrapped under the rest.
+pub(crate) fn is_wrapped(path: &str, value: &Value) -> bool {
+    let (field, rest) = match path.split_once('.') {
+        Some((field, rest)) => (field, Some(rest)),
+        None => (path, None),
+    };
+    match value {
+        Value::Object(obj) if obj.len() == 1 => match (obj.get(field), rest) {
+            (Some(_), None) => true,
+            (Some(inner), Some(rest)) => is_wrapped(rest, inner),
+            (None, _) => false,
+        },
+        _ => false,
+    }
+}
+
 #[macro_export]
 macro_rules! profile {
-    ($cfg:expr, $($arg:tt)*) => {
+    ($cfg:expr, $($arg:tt)*) => {{
+        #[cfg(feature = "trace")]
+        {
+            let _ = &$cfg;
+            $crate::__tracing::debug!($($arg)*);
+        }
+        #[cfg(not(feature = "trace"))]
         $cfg.profile(format_args!($($arg)*))
-    };
+    }};
 }
 
 #[macro_export]
 macro_rules! profile_verbose {
-    ($cfg:expr, $($arg:tt)*) => {
+    ($cfg:expr, $($arg:tt)*) => {{
+        #[cfg(feature = "trace")]
+        {
+            let _ = &$cfg;
+            $crate::__tracing::trace!($($arg)*);
+        }
+        #[cfg(not(feature = "trace"))]
         $cfg.profile_verbose(format_args!($($arg)*))
-    };
+    }};
 }
 
 #[macro_export]
 macro_rules! debug {
-    ($cfg:expr, $($arg:tt)*) => {
+    ($cfg:expr, $($arg:tt)*) => {{
+        #[cfg(feature = "trace")]
+        {
+            let _ = &$cfg;
+            $crate::__tracing::debug!($($arg)*);
+        }
+        #[cfg(not(feature = "trace"))]
         $cfg.debug(format_args!($($arg)*))
-    };
+    }};
 }
 
 #[macro_export]
 macro_rules! debug_verbose {
-    ($cfg:expr, $($arg:tt)*) => {
+    ($cfg:expr, $($arg:tt)*) => {{
+        #[cfg(feature = "trace")]
+        {
+            let _ = &$cfg;
+            $crate::__tracing::trace!($($arg)*);
+        }
+        #[cfg(not(feature = "trace"))]
         $cfg.debug_verbose(format_args!($($arg)*))
-    };
+    }};
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SchemaInferenceResult {
     pub schema: Value,
     pub processed_count: usize,
+    /// The resource limit that cut the input short, for partial results
+    #[serde(default)]
+    pub limit_reached: Option<LimitExceeded>,
+    /// Stage timings, when `profile` or `collect_profile` was set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile: Option<InferenceProfile>,
+    /// Map/record decisions by field path, when `explain` was set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub explanation: Option<Vec<FieldExplanation>>,
+    /// Every map/record decision in the order made, when `decision_log` was set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decision_log: Option<Vec<DecisionEvent>>,
+    /// JSON Pointer paths of the null-only fields given the `null_fallback` type
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub defaulted_fields: Vec<String>,
+    /// Presence ratio of every field, when `required_threshold` was set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub presence: Option<Vec<FieldPresence>>,
+    /// Keys seen with more than one spelling and merged, when `key_fold` was set
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub key_collisions: Vec<KeyCollision>,
+    /// Key cardinality of every inferred map, when `map_key_stats` was set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub map_key_stats: Option<Vec<MapKeyStats>>,
+}
+
+impl SchemaInferenceResult {
+    /// Render the inferred JSON Schema as a Delta Lake struct schema.
+    pub fn to_delta_schema(&self) -> Result<Value, String> {
+        crate::export::delta::to_delta_schema(&self.schema)
+    }
+
+    /// Render the inferred JSON Schema as an Iceberg table schema.
+    pub fn to_iceberg_schema(&self) -> Result<Value, String> {
+        crate::export::iceberg::to_iceberg_schema(&self.schema)
+    }
+}
+
+#[cfg(feature = "proto")]
+impl SchemaInferenceResult {
+    /// Render the inferred JSON Schema as a proto3 file with a single top-level message.
+    pub fn to_proto_schema(
+        &self,
+        message_name: &str,
+        package: Option<&str>,
+    ) -> Result<String, String> {
+        crate::export::proto::to_proto_schema(&self.schema, message_name, package)
+    }
+}
+
+#[cfg(feature = "arrow")]
+impl SchemaInferenceResult {
+    /// Convert the inferred JSON Schema to an Arrow schema.
+    ///
+    /// Use [`crate::export::arrow::schema_to_json`] or
+    /// [`crate::export::arrow::schema_to_ipc`] to serialise it.
+    pub fn to_arrow_schema(&self) -> Result<arrow::datatypes::Schema, String> {
+        crate::export::arrow::to_arrow_schema(&self.schema)
+    }
 }
 
 #[cfg(feature = "avro")]
@@ -207,3 +790,18 @@ pub fn make_promoted_scalar_key(field_prefix: &str, scalar_type: &str) -> String
     // Could be parameterised by config in future to make configurable
     format!("{}__{}", field_prefix, scalar_type)
 }
+
+/// Sort the keys of objects and the items of string arrays, which come from hash
+/// collections in arbitrary order.
+fn sort_collections(value: &mut Value) {
+    match value {
+        Value::Object(obj) => {
+            obj.sort_keys();
+            obj.values_mut().for_each(sort_collections);
+        }
+        Value::Array(items) if items.iter().all(Value::is_string) => {
+            items.sort_by(|a, b| a.as_str().cmp(&b.as_str()));
+        }
+        _ => {}
+    }
+}
@@ -0,0 +1,312 @@
+//! Tagged unions for objects that carry a discriminator (`discriminators`).
+//!
+//! Payloads often embed a tag (`"snaktype": "value" | "novalue"`) that decides
+//! which sibling fields are present. Inferred as one record, every sibling becomes
+//! optional and the link between tag and fields is lost. With a discriminator, the
+//! objects at a path are grouped by their tag value while documents are read, each
+//! group is inferred on its own, and the record is replaced with a `oneOf` of one
+//! branch per tag value, the tag fixed with `const`.
+
+use super::map_inference::{rewrite_objects, RewriteHints};
+use super::preprocess_force_field_types;
+use crate::genson_rs::get_builder;
+use crate::schema::core::SchemaInferenceConfig;
+use crate::schema::field_path::{child_path, pointer, pointer_matches, MAP_VALUES_SEGMENT};
+use crate::schema::tuples::schema_of;
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashMap};
+
+/// Group key of the objects whose tag is missing or isn't a scalar. Tag values are
+/// keyed by their JSON text, which is never empty.
+const UNTAGGED: &str = "";
+
+/// Raw genson schemas of the objects at each path (array items add no segment),
+/// keyed by the JSON text of their tag value.
+pub(crate) type TaggedSchemas = HashMap<String, BTreeMap<String, Value>>;
+
+/// Add the schemas of `other` to `tagged`, merging those of the same path and tag.
+pub(crate) fn merge_tagged_schemas(
+    mut tagged: TaggedSchemas,
+    other: TaggedSchemas,
+) -> TaggedSchemas {
+    for (path, groups) in other {
+        merge_groups(tagged.entry(path).or_default(), groups);
+    }
+    tagged
+}
+
+fn merge_groups(groups: &mut BTreeMap<String, Value>, other: BTreeMap<String, Value>) {
+    for (tag, schema) in other {
+        let merged = match groups.remove(&tag) {
+            Some(seen) => merge_raw(seen, schema),
+            None => schema,
+        };
+        groups.insert(tag, merged);
+    }
+}
+
+fn merge_raw(a: Value, b: Value) -> Value {
+    let mut builder = get_builder(None);
+    builder.add_schema(a);
+    builder.add_schema(b);
+    builder.to_schema()
+}
+
+/// The objects seen at each discriminated path, grouped by tag value.
+pub(crate) type TaggedObjects<'a> = HashMap<String, BTreeMap<String, Vec<&'a Value>>>;
+
+/// Group `value`, an object at `path`, by its tag if the path is discriminated.
+pub(crate) fn observe_object<'a>(
+    groups: &mut TaggedObjects<'a>,
+    value: &'a Value,
+    path: &[String],
+    config: &SchemaInferenceConfig,
+) {
+    let (Value::Object(obj), Some(tag_field)) = (value, config.discriminator(path)) else {
+        return;
+    };
+    let tag = match obj.get(tag_field) {
+        Some(tag @ (Value::String(_) | Value::Number(_) | Value::Bool(_))) => tag.to_string(),
+        _ => UNTAGGED.to_string(),
+    };
+    groups
+        .entry(pointer(path))
+        .or_default()
+        .entry(tag)
+        .or_default()
+        .push(value);
+}
+
+/// Infer the raw schema of each group of objects in `groups`.
+pub(crate) fn tagged_schemas(groups: TaggedObjects) -> TaggedSchemas {
+    groups
+        .into_iter()
+        .map(|(path, by_tag)| {
+            let schemas = by_tag
+                .into_iter()
+                .map(|(tag, objects)| (tag, schema_of(&objects)))
+                .collect();
+            (path, schemas)
+        })
+        .collect()
+}
+
+/// Replace the object schemas at discriminated paths with a `oneOf` of the schemas
+/// in `tagged`, each given map detection and forced types at its path as the rest
+/// of the schema was.
+pub(crate) fn apply_discriminators(
+    schema: &mut Value,
+    tagged: &TaggedSchemas,
+    config: &SchemaInferenceConfig,
+    hints: &RewriteHints,
+) {
+    apply_node(schema, tagged, config, hints, &[]);
+}
+
+fn apply_node(
+    schema: &mut Value,
+    tagged: &TaggedSchemas,
+    config: &SchemaInferenceConfig,
+    hints: &RewriteHints,
+    path: &[String],
+) {
+    if is_object_schema(schema) {
+        if let Some(tag_field) = config.discriminator(path) {
+            let mut groups: Vec<_> = groups_at(tagged, path).into_iter().collect();
+            if !groups.is_empty() {
+                // Untagged objects sort first, but make the last branch
+                if groups[0].0 == UNTAGGED {
+                    groups.rotate_left(1);
+                }
+                let branches = groups
+                    .into_iter()
+                    .map(|(tag, raw)| {
+                        let mut branch = branch_schema(raw, tag_field, &tag, config, hints, path);
+                        apply_children(&mut branch, tagged, config, hints, path);
+                        branch
+                    })
+                    .collect();
+                *schema = json!({ "oneOf": Value::Array(branches) });
+                return;
+            }
+        }
+    }
+    apply_children(schema, tagged, config, hints, path);
+}
+
+fn apply_children(
+    schema: &mut Value,
+    tagged: &TaggedSchemas,
+    config: &SchemaInferenceConfig,
+    hints: &RewriteHints,
+    path: &[String],
+) {
+    let Value::Object(obj) = schema else {
+        return;
+    };
+    for (key, child) in obj.iter_mut() {
+        match key.as_str() {
+            "properties" => {
+                if let Value::Object(props) = child {
+                    for (name, prop) in props.iter_mut() {
+                        apply_node(prop, tagged, config, hints, &child_path(path, name));
+                    }
+                }
+            }
+            "additionalProperties" => apply_node(
+                child,
+                tagged,
+                config,
+                hints,
+                &child_path(path, MAP_VALUES_SEGMENT),
+            ),
+            "items" => match child {
+                Value::Array(positions) => positions
+                    .iter_mut()
+                    .for_each(|position| apply_node(position, tagged, config, hints, path)),
+                _ => apply_node(child, tagged, config, hints, path),
+            },
+            "anyOf" | "oneOf" => {
+                if let Value::Array(branches) = child {
+                    for branch in branches {
+                        apply_node(branch, tagged, config, hints, path);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn is_object_schema(schema: &Value) -> bool {
+    schema.get("type").is_some_and(|t| t == "object")
+        || schema.get("properties").is_some()
+        || schema.get("additionalProperties").is_some()
+}
+
+/// The groups seen at `path`, combining those of every key of enclosing maps.
+fn groups_at(tagged: &TaggedSchemas, path: &[String]) -> BTreeMap<String, Value> {
+    let key = pointer(path);
+    let mut combined = BTreeMap::new();
+    for (seen, groups) in tagged {
+        if pointer_matches(&key, seen) {
+            merge_groups(&mut combined, groups.clone());
+        }
+    }
+    combined
+}
+
+/// Finish the raw schema of one tag value's objects as a branch of the union.
+fn branch_schema(
+    mut branch: Value,
+    tag_field: &str,
+    tag: &str,
+    config: &SchemaInferenceConfig,
+    hints: &RewriteHints,
+    path: &[String],
+) -> Value {
+    if let Value::Object(obj) = &mut branch {
+        obj.shift_remove("$schema");
+    }
+    preprocess_force_field_types(&mut branch, config, path);
+    let field_name = path.last().map(String::as_str);
+    rewrite_objects(
+        &mut branch,
+        field_name,
+        path,
+        config,
+        path.is_empty(),
+        hints,
+        None,
+    );
+    // The tag of untagged objects is left as it was inferred
+    let Ok(tag) = serde_json::from_str::<Value>(tag) else {
+        return branch;
+    };
+    let Some(Value::Object(props)) = branch.get_mut("properties") else {
+        return branch;
+    };
+    if let Some(Value::Object(tag_schema)) = props.get_mut(tag_field) {
+        tag_schema.insert("const".to_string(), tag);
+    }
+    branch
+}
+
+/// Merge the branches of each tagged union in `schema` back into one record, for
+/// Avro, whose record names can't tell the branches apart: a field is required if
+/// every branch requires it, and the tag loses its `const`.
+#[cfg(feature = "avro")]
+pub(crate) fn merge_tagged_unions(schema: &mut Value) {
+    match schema {
+        Value::Object(obj) => {
+            obj.values_mut().for_each(merge_tagged_unions);
+            let tagged = obj.len() == 1
+                && matches!(obj.get("oneOf"), Some(Value::Array(branches))
+                    if branches.iter().all(is_object_schema));
+            if tagged {
+                if let Some(Value::Array(branches)) = obj.shift_remove("oneOf") {
+                    *schema = merge_records(branches);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(merge_tagged_unions),
+        _ => {}
+    }
+}
+
+#[cfg(feature = "avro")]
+fn merge_records(records: Vec<Value>) -> Value {
+    let mut fields: Vec<(String, Vec<Value>)> = Vec::new();
+    let mut required: Option<Vec<Value>> = None;
+    for record in &records {
+        if let Some(Value::Object(props)) = record.get("properties") {
+            for (name, prop) in props {
+                let mut prop = prop.clone();
+                if let Value::Object(prop_obj) = &mut prop {
+                    prop_obj.shift_remove("const");
+                }
+                match fields.iter_mut().find(|(seen, _)| seen == name) {
+                    Some((_, schemas)) if !schemas.contains(&prop) => schemas.push(prop),
+                    Some(_) => {}
+                    None => fields.push((name.clone(), vec![prop])),
+                }
+            }
+        }
+        let record_required = match record.get("required") {
+            Some(Value::Array(names)) => names.clone(),
+            _ => Vec::new(),
+        };
+        required = Some(match required {
+            Some(names) => names
+                .into_iter()
+                .filter(|name| record_required.contains(name))
+                .collect(),
+            None => record_required,
+        });
+    }
+
+    let properties: serde_json::Map<String, Value> = fields
+        .into_iter()
+        .map(|(name, mut schemas)| {
+            let merged = if schemas.len() == 1 {
+                schemas.remove(0)
+            } else if schemas.iter().all(|s| s.get("properties").is_some()) {
+                // Nested records of the same name would clash in Avro as well
+                merge_records(schemas)
+            } else {
+                json!({ "anyOf": schemas })
+            };
+            (name, merged)
+        })
+        .collect();
+    let mut merged = json!({ "type": "object", "properties": properties });
+    if let Some(names) = required.filter(|names| !names.is_empty()) {
+        merged["required"] = Value::Array(names);
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    include!("../tests/discriminators.rs");
+}
@@ -0,0 +1,263 @@
+//! Statistics about the input documents, for the rewrites the raw schema can't
+//! decide alone (field frequencies, tuple shapes, string formats, ...).
+//!
+//! Each document is parsed once, rewritten as the builder sees it, and walked
+//! once, with every statistic the config asks for observed at each value. The
+//! statistics of separate inputs merge, so they're kept with a session too.
+
+use super::discriminators::{self, merge_tagged_schemas, TaggedObjects, TaggedSchemas};
+use super::field_order::{FieldCounts, FieldOrder};
+use super::field_path::{pointer, ITEMS_SEGMENT};
+use super::formats::{self, merge_string_formats, StringFormat, StringFormats};
+use super::key_fold::{collect_key_spellings, merge_key_spellings, KeySpellings};
+use super::map_keys::{self, merge_key_kinds, MapKeyKinds};
+use super::map_stats::{self, merge_key_cardinality, KeyCardinality};
+use super::sparse_arrays::{self, merge_sparse_arrays, SparseArrays};
+use super::tuples::{self, merge_tuple_shapes, TupleShapes, TupleValues};
+use super::union_order::{self, merge_type_counts, TypeCounts, UnionOrder};
+use super::{builder_documents, merge_field_counts, parse_documents};
+use crate::schema::core::{MapDetection, SchemaInferenceConfig};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Statistics gathered from the documents, each empty unless the config asks for it.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub(crate) struct DocumentStats {
+    /// How often each field was seen, for [`FieldOrder::ByFrequency`],
+    /// `required_threshold` and [`MapDetection::PerDocumentVariance`]
+    pub(crate) field_counts: FieldCounts,
+    /// How many objects were seen at each path (the root under the empty path), for
+    /// `required_threshold` and [`MapDetection::PerDocumentVariance`]
+    pub(crate) object_counts: FieldCounts,
+    /// Array shapes, for `detect_tuples`
+    pub(crate) tuple_shapes: TupleShapes,
+    /// Spellings of every key before folding, for `key_fold`
+    pub(crate) key_spellings: KeySpellings,
+    /// Formats of the strings at each path, for `detect_formats`
+    pub(crate) string_formats: StringFormats,
+    /// Whether the keys at each path were integers, for `detect_numeric_keys`
+    pub(crate) key_kinds: MapKeyKinds,
+    /// Keys of the objects at each path, for `map_key_stats`
+    pub(crate) key_cardinality: KeyCardinality,
+    /// Whether the objects at each path were keyed by position, for
+    /// `sparse_array_detection`
+    pub(crate) sparse_arrays: SparseArrays,
+    /// Types of the values at each path, for [`UnionOrder::ByFrequency`]
+    pub(crate) type_counts: TypeCounts,
+    /// Raw schemas of the objects at each discriminated path by tag value, for
+    /// `discriminators`
+    pub(crate) tagged_schemas: TaggedSchemas,
+}
+
+impl DocumentStats {
+    /// Gather the statistics `config` asks for from every input string.
+    pub(crate) fn collect(json_strings: &[&str], config: &SchemaInferenceConfig) -> Self {
+        let wants = Wants::new(config);
+        if !wants.any() {
+            return Self::default();
+        }
+        json_strings
+            .par_iter()
+            .map(|json_str| {
+                let mut key_spellings = KeySpellings::new();
+                let mut documents = Vec::new();
+                for value in parse_documents(json_str, config) {
+                    // Spellings are recorded before `key_fold` folds them away
+                    if wants.key_spellings {
+                        collect_key_spellings(&value, &mut key_spellings);
+                    }
+                    documents.extend(builder_documents(value, config));
+                }
+                let mut stats = Self::walk(&documents, config, wants);
+                stats.key_spellings = key_spellings;
+                stats
+            })
+            .reduce(Self::default, Self::merge)
+    }
+
+    fn walk(documents: &[Value], config: &SchemaInferenceConfig, wants: Wants) -> Self {
+        let mut walker = Walker {
+            config,
+            wants,
+            formats: config
+                .detect_formats
+                .as_ref()
+                .map(|detection| detection.enabled())
+                .unwrap_or_default(),
+            stats: Self::default(),
+            tuples: TupleValues::new(),
+            tagged: TaggedObjects::new(),
+            path: Vec::new(),
+            items_path: Vec::new(),
+        };
+        for document in documents {
+            walker.visit(document, false);
+        }
+        let mut stats = walker.stats;
+        stats.tuple_shapes = tuples::tuple_shapes(walker.tuples);
+        stats.tagged_schemas = discriminators::tagged_schemas(walker.tagged);
+        stats
+    }
+
+    /// Add the statistics of `other` to these, as if its documents came after.
+    pub(crate) fn merge(self, other: Self) -> Self {
+        Self {
+            field_counts: merge_field_counts(self.field_counts, other.field_counts),
+            object_counts: merge_field_counts(self.object_counts, other.object_counts),
+            tuple_shapes: merge_tuple_shapes(self.tuple_shapes, other.tuple_shapes),
+            key_spellings: merge_key_spellings(self.key_spellings, other.key_spellings),
+            string_formats: merge_string_formats(self.string_formats, other.string_formats),
+            key_kinds: merge_key_kinds(self.key_kinds, other.key_kinds),
+            key_cardinality: merge_key_cardinality(self.key_cardinality, other.key_cardinality),
+            sparse_arrays: merge_sparse_arrays(self.sparse_arrays, other.sparse_arrays),
+            type_counts: merge_type_counts(self.type_counts, other.type_counts),
+            tagged_schemas: merge_tagged_schemas(self.tagged_schemas, other.tagged_schemas),
+        }
+    }
+}
+
+/// Which of the statistics the config asks for.
+#[derive(Clone, Copy)]
+struct Wants {
+    field_counts: bool,
+    object_counts: bool,
+    tuple_shapes: bool,
+    key_spellings: bool,
+    string_formats: bool,
+    key_kinds: bool,
+    key_cardinality: bool,
+    sparse_arrays: bool,
+    type_counts: bool,
+    tagged_schemas: bool,
+}
+
+impl Wants {
+    fn new(config: &SchemaInferenceConfig) -> Self {
+        let per_document_variance = config.map_detection == MapDetection::PerDocumentVariance;
+        Self {
+            field_counts: config.sort_fields == FieldOrder::ByFrequency
+                || config.required_threshold.is_some()
+                || per_document_variance,
+            object_counts: config.required_threshold.is_some() || per_document_variance,
+            tuple_shapes: config.detect_tuples,
+            key_spellings: config.key_fold.is_some(),
+            string_formats: config.detect_formats.is_some(),
+            key_kinds: config.detect_numeric_keys,
+            key_cardinality: config.map_key_stats.is_some(),
+            sparse_arrays: config.sparse_array_detection,
+            type_counts: config.union_order == UnionOrder::ByFrequency,
+            tagged_schemas: !config.discriminators.is_empty(),
+        }
+    }
+
+    fn any(self) -> bool {
+        self.key_spellings || self.field_paths() || self.tuple_shapes || self.sparse_arrays
+    }
+
+    /// Whether any of the statistics keyed by field path is asked for.
+    fn field_paths(self) -> bool {
+        self.field_counts
+            || self.object_counts
+            || self.string_formats
+            || self.key_kinds
+            || self.key_cardinality
+            || self.type_counts
+            || self.tagged_schemas
+    }
+}
+
+/// Walks documents, observing the statistics asked for at every value.
+struct Walker<'a, 'c> {
+    config: &'c SchemaInferenceConfig,
+    wants: Wants,
+    /// The formats `detect_formats` turns on
+    formats: Vec<StringFormat>,
+    stats: DocumentStats,
+    tuples: TupleValues<'a>,
+    tagged: TaggedObjects<'a>,
+    /// Field path of the current value (array items add no segment)
+    path: Vec<String>,
+    /// Path of the current value with an [`ITEMS_SEGMENT`] for array items
+    items_path: Vec<String>,
+}
+
+impl<'a> Walker<'a, '_> {
+    /// Observe `value` and everything below it. `is_field` is set for object fields,
+    /// rather than documents and array items.
+    fn visit(&mut self, value: &'a Value, is_field: bool) {
+        let wants = self.wants;
+        let stats = &mut self.stats;
+        let field = if wants.field_paths() {
+            pointer(&self.path)
+        } else {
+            String::new()
+        };
+        if wants.field_counts && is_field {
+            *stats.field_counts.entry(field.clone()).or_default() += 1;
+        }
+        if wants.type_counts {
+            union_order::observe_value(&mut stats.type_counts, field.clone(), value);
+        }
+        match value {
+            Value::Object(obj) => {
+                if wants.object_counts {
+                    *stats.object_counts.entry(field.clone()).or_default() += 1;
+                }
+                if wants.key_kinds {
+                    map_keys::observe_object(&mut stats.key_kinds, field.clone(), obj);
+                }
+                if wants.key_cardinality {
+                    map_stats::observe_object(&mut stats.key_cardinality, field, obj);
+                }
+                if wants.sparse_arrays {
+                    let items_path = pointer(&self.items_path);
+                    sparse_arrays::observe_object(&mut stats.sparse_arrays, items_path, obj);
+                }
+                if wants.tagged_schemas {
+                    discriminators::observe_object(
+                        &mut self.tagged,
+                        value,
+                        &self.path,
+                        self.config,
+                    );
+                }
+                for (key, child) in obj {
+                    self.path.push(key.clone());
+                    self.items_path.push(key.clone());
+                    self.visit(child, true);
+                    self.path.pop();
+                    self.items_path.pop();
+                }
+            }
+            Value::Array(items) => {
+                if wants.tuple_shapes {
+                    tuples::observe_array(&mut self.tuples, pointer(&self.items_path), items);
+                }
+                self.items_path.push(ITEMS_SEGMENT.to_string());
+                for item in items {
+                    self.visit(item, false);
+                }
+                self.items_path.pop();
+            }
+            Value::String(s) => {
+                if let Some(detection) = &self.config.detect_formats {
+                    formats::observe_string(
+                        &mut stats.string_formats,
+                        field,
+                        s,
+                        detection,
+                        &self.formats,
+                    );
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    include!("../tests/document_stats.rs");
+}
@@ -0,0 +1,125 @@
+// genson-core/src/schema/draft.rs
+//! JSON Schema draft selection for inferred schemas.
+//!
+//! The drafts mostly share the keywords inference emits (`type` arrays for
+//! nullable fields, `properties`, `additionalProperties`, `anyOf`), so choosing a
+//! draft sets the `$schema` URI and rewrites the few keywords that differ:
+//!
+//! - Draft-07 keeps reusable schemas under `definitions`, 2019-09 and 2020-12
+//!   under `$defs` (`$ref` pointers are updated to match).
+//! - 2020-12 writes tuple items as `prefixItems` (with `items` for the rest),
+//!   where earlier drafts use an `items` array (with `additionalItems`).
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// A JSON Schema draft to emit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum JsonSchemaDraft {
+    #[serde(rename = "draft-07")]
+    Draft07,
+    #[serde(rename = "2019-09")]
+    Draft201909,
+    #[serde(rename = "2020-12")]
+    Draft202012,
+}
+
+impl JsonSchemaDraft {
+    /// The draft's meta-schema URI, used as `$schema`.
+    pub fn schema_uri(self) -> &'static str {
+        match self {
+            JsonSchemaDraft::Draft07 => "http://json-schema.org/draft-07/schema#",
+            JsonSchemaDraft::Draft201909 => "https://json-schema.org/draft/2019-09/schema",
+            JsonSchemaDraft::Draft202012 => "https://json-schema.org/draft/2020-12/schema",
+        }
+    }
+
+    /// Keyword holding reusable schemas in this draft.
+    fn defs_keyword(self) -> &'static str {
+        match self {
+            JsonSchemaDraft::Draft07 => "definitions",
+            _ => "$defs",
+        }
+    }
+}
+
+/// Rewrite `schema` to use the keywords of `draft`.
+///
+/// An existing `$schema` is replaced with the draft's URI; a schema without one
+/// (inferred with `schema_uri: None`) is left without.
+pub(crate) fn apply_draft(schema: &mut Value, draft: JsonSchemaDraft) {
+    if let Value::Object(root) = schema {
+        if root.contains_key("$schema") {
+            root.insert("$schema".to_string(), Value::from(draft.schema_uri()));
+        }
+    }
+    rewrite_keywords(schema, draft);
+}
+
+fn rewrite_keywords(value: &mut Value, draft: JsonSchemaDraft) {
+    match value {
+        Value::Object(obj) => {
+            let defs = draft.defs_keyword();
+            let other_defs = if defs == "$defs" {
+                "definitions"
+            } else {
+                "$defs"
+            };
+            if let Some(found) = obj.shift_remove(other_defs) {
+                obj.insert(defs.to_string(), found);
+            }
+
+            if let Some(Value::String(reference)) = obj.get_mut("$ref") {
+                if let Some(name) = reference.strip_prefix(&format!("#/{}/", other_defs)) {
+                    *reference = format!("#/{}/{}", defs, name);
+                }
+            }
+
+            if draft == JsonSchemaDraft::Draft202012 {
+                if matches!(obj.get("items"), Some(Value::Array(_))) {
+                    let prefix = obj.shift_remove("items").unwrap_or_default();
+                    let rest = obj.shift_remove("additionalItems");
+                    obj.insert("prefixItems".to_string(), prefix);
+                    if let Some(rest) = rest {
+                        obj.insert("items".to_string(), rest);
+                    }
+                }
+            } else if matches!(obj.get("prefixItems"), Some(Value::Array(_))) {
+                let prefix = obj.shift_remove("prefixItems").unwrap_or_default();
+                let rest = obj.shift_remove("items");
+                obj.insert("items".to_string(), prefix);
+                if let Some(rest) = rest {
+                    obj.insert("additionalItems".to_string(), rest);
+                }
+            }
+
+            for (key, child) in obj.iter_mut() {
+                match key.as_str() {
+                    // Maps from names to schemas: the names are not keywords
+                    "properties" | "patternProperties" | "dependentSchemas" | "$defs"
+                    | "definitions" => {
+                        if let Value::Object(named) = child {
+                            for schema in named.values_mut() {
+                                rewrite_keywords(schema, draft);
+                            }
+                        }
+                    }
+                    // Instance values, not schemas
+                    "enum" | "const" | "default" | "examples" => {}
+                    _ => rewrite_keywords(child, draft),
+                }
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                rewrite_keywords(item, draft);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    include!("../tests/draft.rs");
+}
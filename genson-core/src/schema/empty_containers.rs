@@ -0,0 +1,118 @@
+//! Treatment of empty objects and arrays (`empty_container_policy`).
+//!
+//! An empty `{}` or `[]` says nothing about the type of its contents, yet genson
+//! still types it: an empty object counts as a record with the fields of the others
+//! at its path, and an array that was only ever empty has items of any type (which
+//! Avro renders as a union of everything). The policy decides whether empty
+//! containers take part in inference at all, and what they are typed as.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+
+/// What inference makes of empty objects and arrays.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EmptyContainerPolicy {
+    /// Leave them out, as if the field (or array item) were missing
+    Ignore,
+    /// Type containers that were only ever empty as a map of strings (objects) or an
+    /// array of strings (arrays)
+    TypeAsMapOfString,
+    /// Read them as `null`, making the field nullable rather than weakening its type
+    TypeAsNullable,
+}
+
+impl EmptyContainerPolicy {
+    /// Whether documents are rewritten before they reach the builder.
+    pub(crate) fn rewrites_documents(self) -> bool {
+        matches!(self, Self::Ignore | Self::TypeAsNullable)
+    }
+}
+
+/// Drop (`Ignore`) or null (`TypeAsNullable`) the empty containers nested in
+/// `value`, innermost first, so a container left empty by dropping its contents is
+/// dropped too. `value` itself is left as it is.
+pub(crate) fn rewrite_empty_containers(value: &mut Value, policy: EmptyContainerPolicy) {
+    match value {
+        Value::Object(obj) => {
+            for child in obj.values_mut() {
+                rewrite_empty_containers(child, policy);
+            }
+            match policy {
+                EmptyContainerPolicy::Ignore => obj.retain(|_, child| !is_empty_container(child)),
+                _ => obj
+                    .values_mut()
+                    .filter(|child| is_empty_container(child))
+                    .for_each(|child| *child = Value::Null),
+            }
+        }
+        Value::Array(items) => {
+            for item in items.iter_mut() {
+                rewrite_empty_containers(item, policy);
+            }
+            match policy {
+                EmptyContainerPolicy::Ignore => items.retain(|item| !is_empty_container(item)),
+                _ => items
+                    .iter_mut()
+                    .filter(|item| is_empty_container(item))
+                    .for_each(|item| *item = Value::Null),
+            }
+        }
+        _ => {}
+    }
+}
+
+fn is_empty_container(value: &Value) -> bool {
+    match value {
+        Value::Object(obj) => obj.is_empty(),
+        Value::Array(items) => items.is_empty(),
+        _ => false,
+    }
+}
+
+/// Give the objects with neither properties nor map values, and the arrays without
+/// item types, `string` contents (`TypeAsMapOfString`).
+pub(crate) fn type_empty_containers(schema: &mut Value) {
+    let obj = match schema {
+        Value::Object(obj) => obj,
+        Value::Array(items) => {
+            items.iter_mut().for_each(type_empty_containers);
+            return;
+        }
+        _ => return,
+    };
+
+    if has_type(obj, "object")
+        && !obj.contains_key("properties")
+        && !obj.contains_key("additionalProperties")
+    {
+        obj.insert(
+            "additionalProperties".to_string(),
+            json!({"type": "string"}),
+        );
+    }
+    if has_type(obj, "array") && obj.get("items").is_none_or(|items| items == &json!({})) {
+        obj.insert("items".to_string(), json!({"type": "string"}));
+    }
+
+    for (key, child) in obj.iter_mut() {
+        match key.as_str() {
+            "enum" | "const" | "default" | "examples" | "required" => {}
+            _ => type_empty_containers(child),
+        }
+    }
+}
+
+/// Whether `obj` has `type` `name`, alone or in a union.
+fn has_type(obj: &Map<String, Value>, name: &str) -> bool {
+    match obj.get("type") {
+        Some(Value::String(t)) => t == name,
+        Some(Value::Array(types)) => types.iter().any(|t| t == name),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    include!("../tests/empty_containers.rs");
+}
@@ -0,0 +1,226 @@
+//! Structured explanations of map/record decisions, collected with `explain`.
+//!
+//! Debug output narrates every step of `rewrite_objects`, which makes it hard to
+//! see why a particular field ended up as a map. An explanation is one entry per
+//! decision, keyed by field path, holding the numbers the decision was made on.
+//! The same decisions can be kept as a log of [`DecisionEvent`]s in the order
+//! they were made (`decision_log`), for post-processing.
+
+use crate::schema::field_path::pointer;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::sync::Mutex;
+
+/// What an object schema was turned into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FieldDecision {
+    /// `additionalProperties` with a single value schema
+    Map,
+    /// Kept as `properties`
+    Record,
+    /// A scalar wrapped in a single-property record (`force_scalar_promotion`)
+    ScalarWrapper,
+    /// Left as an `anyOf` union of its branches
+    Union,
+}
+
+/// How the property (or branch) schemas compared when looking for a map value schema.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Unification {
+    /// All the schemas were the same, ignoring nullability
+    Homogeneous,
+    /// Different schemas were unified into one
+    Unified,
+    /// Unification was attempted and failed
+    Failed,
+    /// Not attempted: a property is listed in `no_unify`
+    NoUnify,
+    /// Not attempted: `unify_maps` is off, or the object is below `map_threshold`
+    /// (or has stable keys, with `MapDetection::PerDocumentVariance`)
+    NotAttempted,
+}
+
+/// Why the object at one field path became a map, a record or a wrapped scalar.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldExplanation {
+    /// Field path as a JSON Pointer (empty for the root); map values are `*`
+    pub path: String,
+    pub decision: FieldDecision,
+    /// The deciding factor, in words
+    pub reason: String,
+    /// Number of distinct keys observed, when the map heuristics ran
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key_count: Option<usize>,
+    /// The `map_threshold` the key count was compared with
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub map_threshold: Option<usize>,
+    /// Mean share of the objects at the path that had each key, with
+    /// `MapDetection::PerDocumentVariance`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key_presence: Option<f64>,
+    /// Number of keys present in every document
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub required_key_count: Option<usize>,
+    /// The `map_max_required_keys` the required key count was compared with
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub map_max_required_keys: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unification: Option<Unification>,
+    /// The setting that overrode the heuristics, e.g. `force_field_types`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub forced_by: Option<String>,
+}
+
+impl FieldExplanation {
+    pub(crate) fn new(path: &[String], decision: FieldDecision, reason: impl Into<String>) -> Self {
+        Self {
+            path: pointer(path),
+            decision,
+            reason: reason.into(),
+            key_count: None,
+            map_threshold: None,
+            key_presence: None,
+            required_key_count: None,
+            map_max_required_keys: None,
+            unification: None,
+            forced_by: None,
+        }
+    }
+
+    /// An explanation for a decision made by a config override rather than the heuristics.
+    pub(crate) fn forced(path: &[String], decision: FieldDecision, setting: &str) -> Self {
+        Self {
+            forced_by: Some(setting.to_string()),
+            ..Self::new(path, decision, format!("forced by {}", setting))
+        }
+    }
+}
+
+impl FieldExplanation {
+    /// The log events of this decision: the unification attempt, if one was made,
+    /// then the decision itself.
+    pub(crate) fn events(&self) -> Vec<DecisionEvent> {
+        let mut inputs = match serde_json::to_value(self) {
+            Ok(Value::Object(obj)) => obj,
+            _ => Map::new(),
+        };
+        for key in ["path", "decision", "reason"] {
+            inputs.shift_remove(key);
+        }
+
+        let mut events = Vec::with_capacity(2);
+        let attempt = match self.unification {
+            Some(Unification::Unified) => Some("unified"),
+            Some(Unification::Failed) => Some("failed"),
+            _ => None,
+        };
+        if let Some(outcome) = attempt {
+            events.push(DecisionEvent {
+                event: DecisionKind::UnificationAttempt,
+                path: self.path.clone(),
+                inputs: inputs.clone(),
+                outcome: outcome.to_string(),
+                reason: None,
+            });
+        }
+        let event = match (self.decision, &self.forced_by) {
+            (_, Some(_)) => DecisionKind::ForcedOverride,
+            (FieldDecision::Map, None) => DecisionKind::MapRewrite,
+            (FieldDecision::Record, None) => DecisionKind::RecordKept,
+            (FieldDecision::ScalarWrapper, None) => DecisionKind::ScalarPromotion,
+            (FieldDecision::Union, None) => DecisionKind::UnionKept,
+        };
+        let outcome = match serde_json::to_value(self.decision) {
+            Ok(Value::String(decision)) => decision,
+            _ => String::new(),
+        };
+        events.push(DecisionEvent {
+            event,
+            path: self.path.clone(),
+            inputs,
+            outcome,
+            reason: Some(self.reason.clone()),
+        });
+        events
+    }
+}
+
+/// The kind of decision a [`DecisionEvent`] records.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DecisionKind {
+    /// The property (or branch) schemas were unified, successfully or not
+    UnificationAttempt,
+    /// An object became a map by the heuristics
+    MapRewrite,
+    /// An object was kept as a record by the heuristics
+    RecordKept,
+    /// A scalar was wrapped in a single-property record
+    ScalarPromotion,
+    /// A setting (e.g. `force_field_types`) decided instead of the heuristics
+    ForcedOverride,
+    /// An `anyOf` union was left as it was
+    UnionKept,
+}
+
+/// One decision of inference, as logged with `decision_log`. Serialised one per
+/// line, the log can be filtered and aggregated with standard JSON tools.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DecisionEvent {
+    pub event: DecisionKind,
+    /// Field path as a JSON Pointer (empty for the root); map values are `*`
+    pub path: String,
+    /// The numbers and settings the decision was made on, named as in
+    /// [`FieldExplanation`]
+    pub inputs: Map<String, Value>,
+    /// What was decided: `map`, `record`, `scalar_wrapper` or `union`, or for a
+    /// unification attempt, `unified` or `failed`
+    pub outcome: String,
+    /// The deciding factor, in words
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// Collects explanations from `rewrite_objects`, which recurses in parallel.
+#[derive(Default)]
+pub(crate) struct ExplainRecorder(Mutex<Vec<FieldExplanation>>);
+
+impl ExplainRecorder {
+    /// Record the explanation built by `explanation`, if explanations are being collected.
+    pub(crate) fn note(recorder: Option<&Self>, explanation: impl FnOnce() -> FieldExplanation) {
+        if let Some(recorder) = recorder {
+            recorder
+                .0
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .push(explanation());
+        }
+    }
+
+    /// The log events of the explanations so far, in the order they were recorded
+    /// (which varies between parallel runs).
+    pub(crate) fn events(&self) -> Vec<DecisionEvent> {
+        self.0
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .flat_map(FieldExplanation::events)
+            .collect()
+    }
+
+    /// The explanations ordered by path, so parallel runs give the same report.
+    pub(crate) fn finish(self) -> Vec<FieldExplanation> {
+        let mut explanations = self.0.into_inner().unwrap_or_else(|e| e.into_inner());
+        explanations.sort_by(|a, b| a.path.cmp(&b.path).then_with(|| a.reason.cmp(&b.reason)));
+        // anyOf branches share their parent's path and can repeat a decision
+        explanations.dedup();
+        explanations
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    include!("../tests/explain.rs");
+}
@@ -0,0 +1,124 @@
+//! Deterministic field ordering for output schemas (`sort_fields`).
+//!
+//! By default properties keep the order fields were first seen in, which changes
+//! when the input rows are reordered and makes committed schema files noisy to
+//! diff. Sorting the properties also fixes the order of Avro record fields, which
+//! are converted from them.
+
+use crate::schema::field_path::{child_path, pointer, pointer_matches, MAP_VALUES_SEGMENT};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+/// Order of the properties (and Avro fields) in an output schema.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FieldOrder {
+    /// The order fields were first seen in the input
+    #[default]
+    Preserve,
+    /// Sorted by field name
+    Alphabetical,
+    /// Most often seen first, ties sorted by field name
+    ByFrequency,
+}
+
+/// How many times each field was seen in the input, keyed by JSON Pointer path.
+pub(crate) type FieldCounts = HashMap<String, usize>;
+
+/// Reorder every `properties` map (and `required` array with it) in `schema`.
+pub(crate) fn order_fields(schema: &mut Value, order: FieldOrder, counts: &FieldCounts) {
+    if order != FieldOrder::Preserve {
+        order_node(schema, order, counts, &[]);
+    }
+}
+
+fn order_node(schema: &mut Value, order: FieldOrder, counts: &FieldCounts, path: &[String]) {
+    let obj = match schema {
+        Value::Object(obj) => obj,
+        Value::Array(items) => {
+            for item in items {
+                order_node(item, order, counts, path);
+            }
+            return;
+        }
+        _ => return,
+    };
+
+    if let Some(Value::Object(props)) = obj.get_mut("properties") {
+        sort_properties(props, order, counts, path);
+        let rank: HashMap<String, usize> = props
+            .keys()
+            .enumerate()
+            .map(|(i, key)| (key.clone(), i))
+            .collect();
+        if let Some(Value::Array(required)) = obj.get_mut("required") {
+            required.sort_by_key(|name| {
+                name.as_str()
+                    .and_then(|name| rank.get(name))
+                    .copied()
+                    .unwrap_or(usize::MAX)
+            });
+        }
+    }
+
+    for (key, child) in obj.iter_mut() {
+        match key.as_str() {
+            "properties" => {
+                if let Value::Object(props) = child {
+                    for (name, prop) in props.iter_mut() {
+                        order_node(prop, order, counts, &child_path(path, name));
+                    }
+                }
+            }
+            "additionalProperties" => {
+                order_node(child, order, counts, &child_path(path, MAP_VALUES_SEGMENT))
+            }
+            // Instance values, not schemas
+            "enum" | "const" | "default" | "examples" | "required" => {}
+            _ => order_node(child, order, counts, path),
+        }
+    }
+}
+
+fn sort_properties(
+    props: &mut Map<String, Value>,
+    order: FieldOrder,
+    counts: &FieldCounts,
+    path: &[String],
+) {
+    let mut entries: Vec<(String, Value)> = std::mem::take(props).into_iter().collect();
+    match order {
+        FieldOrder::Preserve => {}
+        FieldOrder::Alphabetical => entries.sort_by(|(a, _), (b, _)| a.cmp(b)),
+        FieldOrder::ByFrequency => {
+            let mut keyed: Vec<(usize, (String, Value))> = entries
+                .into_iter()
+                .map(|(name, prop)| (frequency(counts, &child_path(path, &name)), (name, prop)))
+                .collect();
+            keyed.sort_by(|(fa, (a, _)), (fb, (b, _))| fb.cmp(fa).then_with(|| a.cmp(b)));
+            entries = keyed.into_iter().map(|(_, entry)| entry).collect();
+        }
+    }
+    props.extend(entries);
+}
+
+/// How often the field at `path` was seen. Below a map, the counts of the field
+/// under every map key are added up.
+pub(crate) fn frequency(counts: &FieldCounts, path: &[String]) -> usize {
+    let key = pointer(path);
+    if path.iter().any(|seg| seg == MAP_VALUES_SEGMENT) {
+        counts
+            .iter()
+            .filter(|(seen, _)| pointer_matches(&key, seen))
+            .map(|(_, count)| count)
+            .sum()
+    } else {
+        counts.get(&key).copied().unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    include!("../tests/field_order.rs");
+}
@@ -0,0 +1,123 @@
+// genson-core/src/schema/field_path.rs
+//! Field targeting for `force_field_types`, `no_unify`, `force_scalar_promotion`,
+//! `ignore_paths` and `select_paths`.
+//!
+//! Keys in those settings are either bare field names, which match a field of
+//! that name at any depth, or path patterns matched against the field's
+//! location in the document:
+//!
+//! - JSON Pointer style, starting with `/`: `/claims/*/mainsnak/datavalue`
+//!   (`~1` and `~0` escape `/` and `~`)
+//! - Dotted: `claims.*.mainsnak.datavalue`
+//!
+//! A path is the sequence of property names from the root to the field. Array
+//! items do not add a segment, and map values (`additionalProperties`) add a
+//! `*` segment. In patterns, `*` matches any single segment and `**` matches
+//! any number of segments (including none).
+
+use std::borrow::Cow;
+
+/// Segment used for the values of a map schema.
+pub(crate) const MAP_VALUES_SEGMENT: &str = "*";
+
+/// Segment used for array items, in the paths that tell an array apart from the
+/// arrays nested in it (tuple shapes and sparse arrays) rather than field paths.
+pub(crate) const ITEMS_SEGMENT: &str = "[]";
+
+/// Extend `path` with one segment.
+pub(crate) fn child_path(path: &[String], segment: &str) -> Vec<String> {
+    let mut child = Vec::with_capacity(path.len() + 1);
+    child.extend_from_slice(path);
+    child.push(segment.to_string());
+    child
+}
+
+/// Render a path as a JSON Pointer (empty for the root).
+pub(crate) fn pointer(path: &[String]) -> String {
+    path.iter()
+        .map(|seg| format!("/{}", seg.replace('~', "~0").replace('/', "~1")))
+        .collect()
+}
+
+/// Whether `path` matches the JSON Pointer `pattern`, where `*` segments match any
+/// single segment (so a map values path covers every key seen in the data).
+pub(crate) fn pointer_matches(pattern: &str, pointer: &str) -> bool {
+    let path: Vec<String> = pattern_segments(pointer)
+        .into_iter()
+        .map(Cow::into_owned)
+        .collect();
+    segments_match(&pattern_segments(pattern), &path)
+}
+
+/// Whether a config key is a path pattern rather than a bare field name.
+pub(crate) fn is_path_pattern(key: &str) -> bool {
+    key.starts_with('/') || key.contains('.')
+}
+
+fn pattern_segments(pattern: &str) -> Vec<Cow<'_, str>> {
+    if let Some(pointer) = pattern.strip_prefix('/') {
+        pointer
+            .split('/')
+            .map(|seg| {
+                if seg.contains('~') {
+                    Cow::Owned(seg.replace("~1", "/").replace("~0", "~"))
+                } else {
+                    Cow::Borrowed(seg)
+                }
+            })
+            .collect()
+    } else {
+        pattern.split('.').map(Cow::Borrowed).collect()
+    }
+}
+
+fn segments_match(pattern: &[Cow<'_, str>], path: &[String]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((first, rest)) if first == "**" => {
+            (0..=path.len()).any(|skip| segments_match(rest, &path[skip..]))
+        }
+        Some((first, rest)) => match path.split_first() {
+            Some((segment, path_rest)) => {
+                (first == "*" || first == segment) && segments_match(rest, path_rest)
+            }
+            None => false,
+        },
+    }
+}
+
+/// Whether a config key targets the field `name` located at `path`.
+///
+/// Bare keys compare against `name`. Path patterns only apply when `name` is the
+/// last segment of `path`, i.e. to the field itself rather than to intermediate
+/// nodes visited under a different name.
+pub(crate) fn key_matches(key: &str, name: Option<&str>, path: &[String]) -> bool {
+    let Some(name) = name else {
+        return false;
+    };
+    if !is_path_pattern(key) {
+        return key == name;
+    }
+    path.last().is_some_and(|last| last == name) && segments_match(&pattern_segments(key), path)
+}
+
+/// Whether the path pattern `key` could match a field below `path`, making `path`
+/// one of its ancestors. Bare keys never do, as they name no ancestors.
+pub(crate) fn leads_to(key: &str, path: &[String]) -> bool {
+    fn below(pattern: &[Cow<'_, str>], path: &[String]) -> bool {
+        match (pattern.split_first(), path.split_first()) {
+            (Some(_), None) => true,
+            (None, Some(_)) | (None, None) => false,
+            (Some((first, _)), Some(_)) if first == "**" => true,
+            (Some((first, rest)), Some((segment, path_rest))) => {
+                (first == "*" || first == segment) && below(rest, path_rest)
+            }
+        }
+    }
+    is_path_pattern(key) && below(&pattern_segments(key), path)
+}
+
+#[cfg(test)]
+mod tests {
+    include!("../tests/field_path.rs");
+}
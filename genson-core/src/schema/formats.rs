@@ -0,0 +1,296 @@
+//! String format detection (`detect_formats`).
+//!
+//! Genson types every string as `string`. With detection on, the strings seen at
+//! each path are checked against a few well-known formats, and a path whose
+//! checked strings all had one format is annotated with it: `"format"` for UUIDs,
+//! URIs, emails and IP addresses, `"contentEncoding": "base64"` for base64. Avro
+//! schemas give UUIDs the `uuid` logical type.
+
+use crate::schema::field_path::{child_path, pointer, pointer_matches, MAP_VALUES_SEGMENT};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::net::IpAddr;
+
+/// Which string formats to detect, and how many of the strings to check.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FormatDetection {
+    /// `8-4-4-4-12` hex UUIDs, as `"format": "uuid"`
+    pub uuid: bool,
+    /// URIs with an authority (`scheme://...`), `urn:` or `mailto:`, as `"format": "uri"`
+    pub uri: bool,
+    /// Email addresses, as `"format": "email"`
+    pub email: bool,
+    /// Base64 of at least 16 characters with mixed-case letters, as
+    /// `"contentEncoding": "base64"`
+    pub base64: bool,
+    /// IPv4 and IPv6 addresses, as `"format": "ipv4"` or `"ipv6"`
+    pub ip: bool,
+    /// Fraction of the strings at each path to check (0 to 1), evenly spaced and
+    /// always including the first, to cap the cost on huge corpora
+    pub sample_rate: f64,
+}
+
+impl Default for FormatDetection {
+    fn default() -> Self {
+        Self {
+            uuid: true,
+            uri: true,
+            email: true,
+            base64: true,
+            ip: true,
+            sample_rate: 1.0,
+        }
+    }
+}
+
+impl FormatDetection {
+    pub(crate) fn validate(&self) -> Result<(), String> {
+        if self.sample_rate > 0.0 && self.sample_rate <= 1.0 {
+            Ok(())
+        } else {
+            Err(format!(
+                "Invalid detect_formats sample_rate: {} (expected a ratio above 0, up to 1)",
+                self.sample_rate
+            ))
+        }
+    }
+
+    /// The enabled formats, in the order they're preferred when several match.
+    pub(crate) fn enabled(&self) -> Vec<StringFormat> {
+        [
+            (self.uuid, StringFormat::Uuid),
+            (self.ip, StringFormat::Ipv4),
+            (self.ip, StringFormat::Ipv6),
+            (self.email, StringFormat::Email),
+            (self.uri, StringFormat::Uri),
+            (self.base64, StringFormat::Base64),
+        ]
+        .into_iter()
+        .filter_map(|(on, format)| on.then_some(format))
+        .collect()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) enum StringFormat {
+    Uuid,
+    Ipv4,
+    Ipv6,
+    Email,
+    Uri,
+    Base64,
+}
+
+impl StringFormat {
+    fn matches(self, s: &str) -> bool {
+        match self {
+            StringFormat::Uuid => is_uuid(s),
+            StringFormat::Ipv4 => matches!(s.parse::<IpAddr>(), Ok(IpAddr::V4(_))),
+            StringFormat::Ipv6 => matches!(s.parse::<IpAddr>(), Ok(IpAddr::V6(_))),
+            StringFormat::Email => is_email(s),
+            StringFormat::Uri => is_uri(s),
+            StringFormat::Base64 => is_base64(s),
+        }
+    }
+
+    /// The keyword and value annotating a string of this format.
+    fn annotation(self) -> (&'static str, &'static str) {
+        match self {
+            StringFormat::Uuid => ("format", "uuid"),
+            StringFormat::Ipv4 => ("format", "ipv4"),
+            StringFormat::Ipv6 => ("format", "ipv6"),
+            StringFormat::Email => ("format", "email"),
+            StringFormat::Uri => ("format", "uri"),
+            StringFormat::Base64 => ("contentEncoding", "base64"),
+        }
+    }
+}
+
+/// The strings seen at one path.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct FormatCandidates {
+    /// Number of strings seen, checked or not
+    seen: usize,
+    /// Number of strings checked
+    checked: usize,
+    /// Formats every checked string had
+    formats: Vec<StringFormat>,
+}
+
+impl FormatCandidates {
+    fn merge(&mut self, other: FormatCandidates) {
+        self.seen += other.seen;
+        self.checked += other.checked;
+        self.formats.retain(|format| other.formats.contains(format));
+    }
+}
+
+/// Format candidates keyed by field path (array items add no segment).
+pub(crate) type StringFormats = HashMap<String, FormatCandidates>;
+
+/// Add the candidates of `other` to `formats`.
+pub(crate) fn merge_string_formats(
+    mut formats: StringFormats,
+    other: StringFormats,
+) -> StringFormats {
+    for (path, candidates) in other {
+        match formats.entry(path) {
+            Entry::Vacant(entry) => {
+                entry.insert(candidates);
+            }
+            Entry::Occupied(mut entry) => entry.get_mut().merge(candidates),
+        }
+    }
+    formats
+}
+
+/// Check a string at `path` against the formats still enabled there, if the sample
+/// rate picks it. `enabled` is the formats `detection` turns on.
+pub(crate) fn observe_string(
+    formats: &mut StringFormats,
+    path: String,
+    s: &str,
+    detection: &FormatDetection,
+    enabled: &[StringFormat],
+) {
+    let candidates = formats.entry(path).or_insert_with(|| FormatCandidates {
+        seen: 0,
+        checked: 0,
+        formats: enabled.to_vec(),
+    });
+    let n = candidates.seen as f64;
+    let sampled = ((n + 1.0) * detection.sample_rate).ceil() > (n * detection.sample_rate).ceil();
+    candidates.seen += 1;
+    if sampled && !candidates.formats.is_empty() {
+        candidates.checked += 1;
+        candidates.formats.retain(|format| format.matches(s));
+    }
+}
+
+/// Annotate the string schemas whose checked strings all had one format.
+pub(crate) fn apply_string_formats(schema: &mut Value, formats: &StringFormats) {
+    apply_node(schema, formats, &[]);
+}
+
+fn apply_node(schema: &mut Value, formats: &StringFormats, path: &[String]) {
+    let obj = match schema {
+        Value::Object(obj) => obj,
+        Value::Array(items) => {
+            for item in items {
+                apply_node(item, formats, path);
+            }
+            return;
+        }
+        _ => return,
+    };
+
+    let is_string = match obj.get("type") {
+        Some(Value::String(t)) => t == "string",
+        Some(Value::Array(types)) => types.iter().any(|t| t == "string"),
+        _ => false,
+    };
+    if is_string && !obj.contains_key("format") && !obj.contains_key("contentEncoding") {
+        if let Some(format) = detected_format(formats, path) {
+            let (keyword, value) = format.annotation();
+            obj.insert(keyword.to_string(), Value::String(value.to_string()));
+        }
+    }
+
+    for (key, child) in obj.iter_mut() {
+        match key.as_str() {
+            "properties" => {
+                if let Value::Object(props) = child {
+                    for (name, prop) in props.iter_mut() {
+                        apply_node(prop, formats, &child_path(path, name));
+                    }
+                }
+            }
+            "additionalProperties" => {
+                apply_node(child, formats, &child_path(path, MAP_VALUES_SEGMENT))
+            }
+            // Instance values, not schemas
+            "enum" | "const" | "default" | "examples" | "required" => {}
+            _ => apply_node(child, formats, path),
+        }
+    }
+}
+
+/// The format every checked string at `path` had, combining the keys of maps.
+fn detected_format(formats: &StringFormats, path: &[String]) -> Option<StringFormat> {
+    let key = pointer(path);
+    let candidates = if path.iter().any(|seg| seg == MAP_VALUES_SEGMENT) {
+        formats
+            .iter()
+            .filter(|(seen, _)| pointer_matches(&key, seen))
+            .map(|(_, candidates)| candidates.clone())
+            .reduce(|mut merged, candidates| {
+                merged.merge(candidates);
+                merged
+            })?
+    } else {
+        formats.get(&key)?.clone()
+    };
+    (candidates.checked > 0)
+        .then(|| candidates.formats.first().copied())
+        .flatten()
+}
+
+fn is_uuid(s: &str) -> bool {
+    s.len() == 36
+        && s.char_indices().all(|(i, c)| match i {
+            8 | 13 | 18 | 23 => c == '-',
+            _ => c.is_ascii_hexdigit(),
+        })
+}
+
+fn is_email(s: &str) -> bool {
+    let Some((local, domain)) = s.split_once('@') else {
+        return false;
+    };
+    !local.is_empty()
+        && domain.contains('.')
+        && !domain.starts_with('.')
+        && !domain.ends_with('.')
+        && !domain.contains('@')
+        && !s.chars().any(char::is_whitespace)
+}
+
+fn is_uri(s: &str) -> bool {
+    let Some((scheme, rest)) = s.split_once(':') else {
+        return false;
+    };
+    let valid_scheme = scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+        && scheme
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'));
+    let has_target = match rest.strip_prefix("//") {
+        Some(authority) => !authority.is_empty(),
+        None => {
+            matches!(scheme.to_ascii_lowercase().as_str(), "urn" | "mailto") && !rest.is_empty()
+        }
+    };
+    valid_scheme && has_target && !s.chars().any(char::is_whitespace)
+}
+
+/// Base64 (standard alphabet, padded). Short strings, and strings without both
+/// upper- and lower-case letters (words, hex digests), are too often something else.
+fn is_base64(s: &str) -> bool {
+    let body = s.trim_end_matches('=');
+    s.len() >= 16
+        && s.len().is_multiple_of(4)
+        && s.len() - body.len() <= 2
+        && body
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '+' || c == '/')
+        && body.chars().any(|c| c.is_ascii_uppercase())
+        && body.chars().any(|c| c.is_ascii_lowercase())
+        && body.chars().any(|c| !c.is_ascii_alphabetic())
+}
+
+#[cfg(test)]
+mod tests {
+    include!("../tests/formats.rs");
+}
@@ -0,0 +1,95 @@
+//! Errors for documents that fail to parse (`error_context_chars`, `error_dump_dir`).
+//!
+//! Rows such as Wikidata entities run to megabytes, so quoting the start of one
+//! rarely shows what broke it. The error quotes the text around where parsing
+//! failed instead, and can point to a copy of the whole document on disk.
+
+use crate::schema::core::SchemaInferenceConfig;
+use std::path::{Path, PathBuf};
+use xxhash_rust::xxh64::xxh64;
+
+/// The error for `document` (input `index`, or its NDJSON `line`) failing to parse.
+pub(crate) fn invalid_json_error(
+    index: usize,
+    line: Option<usize>,
+    document: &str,
+    error: &serde_json::Error,
+    config: &SchemaInferenceConfig,
+) -> String {
+    let location = match line {
+        Some(line) => format!("index {}, line {}", index + 1, line),
+        None => format!("index {}", index + 1),
+    };
+    let offset = error_offset(document, error);
+    let mut message = format!(
+        "Invalid JSON input at {}: {} - JSON: {}",
+        location,
+        error,
+        excerpt(document, offset, config.error_context_chars)
+    );
+    if let Some(dir) = &config.error_dump_dir {
+        match dump_document(dir, index, document) {
+            Ok(path) => message.push_str(&format!(" - full document: {}", path.display())),
+            Err(e) => message.push_str(&format!(
+                " - failed to write the full document to {}: {}",
+                dir.display(),
+                e
+            )),
+        }
+    }
+    message
+}
+
+/// Byte offset in `document` of the line and column `error` was raised at.
+fn error_offset(document: &str, error: &serde_json::Error) -> usize {
+    // Custom errors have no position
+    if error.line() == 0 {
+        return 0;
+    }
+    let line_start: usize = document
+        .split_inclusive('\n')
+        .take(error.line() - 1)
+        .map(str::len)
+        .sum();
+    let offset = (line_start + error.column().saturating_sub(1)).min(document.len());
+    (0..=offset)
+        .rev()
+        .find(|&i| document.is_char_boundary(i))
+        .unwrap_or(0)
+}
+
+/// Up to `max_chars` of `document`, starting half that before `offset` (or a full
+/// window before the end), with counts of the characters left out on each side.
+fn excerpt(document: &str, offset: usize, max_chars: usize) -> String {
+    let total = document.chars().count();
+    if total <= max_chars {
+        return document.to_string();
+    }
+    let at = document[..offset].chars().count();
+    let start = at.saturating_sub(max_chars / 2).min(total - max_chars);
+    let end = start + max_chars;
+    let mut out = String::new();
+    if start > 0 {
+        out.push_str(&format!("[skipped {} chars] ...", start));
+    }
+    out.extend(document.chars().skip(start).take(max_chars));
+    if end < total {
+        out.push_str(&format!("... [truncated {} chars]", total - end));
+    }
+    out
+}
+
+/// Write `document` to `dir`, named by its input index and a hash of its content so
+/// that several invalid lines of one input don't overwrite each other.
+fn dump_document(dir: &Path, index: usize, document: &str) -> std::io::Result<PathBuf> {
+    std::fs::create_dir_all(dir)?;
+    let hash = xxh64(document.as_bytes(), 0);
+    let path = dir.join(format!("invalid-{}-{:016x}.json", index + 1, hash));
+    std::fs::write(&path, document)?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    include!("../tests/invalid_json.rs");
+}
@@ -0,0 +1,162 @@
+//! Case-insensitive keys (`key_fold`).
+//!
+//! Producers that write `ID`, `Id` and `id` for the same field would otherwise
+//! give a schema with three of them. With `key_fold` set, keys are folded to one
+//! case before inference (and before normalisation, so rows match the schema), and
+//! every spelling seen is recorded so the keys that were merged can be reported.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+
+/// How keys that differ only in case are merged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeyFold {
+    /// Lower-case every key: `ID` becomes `id`
+    Lower,
+    /// Upper-case every key: `id` becomes `ID`
+    Upper,
+    /// Name each field after the spelling of its key seen first. Map keys, and
+    /// the paths in other settings and reports, are lower-cased
+    Preserve,
+}
+
+/// Keys that differ only in case and were merged into one field.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyCollision {
+    /// The field they became
+    pub name: String,
+    /// Every spelling seen, in the order first seen
+    pub keys: Vec<String>,
+}
+
+/// Every spelling of each key, keyed by its lower-case form, in the order seen.
+pub(crate) type KeySpellings = BTreeMap<String, Vec<String>>;
+
+/// `key` in the case `fold` merges it under while inferring.
+pub(crate) fn fold_key(key: &str, fold: KeyFold) -> String {
+    match fold {
+        KeyFold::Upper => key.to_uppercase(),
+        KeyFold::Lower | KeyFold::Preserve => key.to_lowercase(),
+    }
+}
+
+/// Fold the keys of every object in `value`. Where two keys of one object fold
+/// to the same key, the first one's value is kept.
+pub(crate) fn fold_keys(value: &mut Value, fold: KeyFold) {
+    match value {
+        Value::Object(obj) => {
+            if obj.keys().any(|k| fold_key(k, fold) != *k) {
+                let mut folded = Map::new();
+                for (key, child) in std::mem::take(obj) {
+                    folded.entry(fold_key(&key, fold)).or_insert(child);
+                }
+                *obj = folded;
+            }
+            obj.values_mut().for_each(|child| fold_keys(child, fold));
+        }
+        Value::Array(items) => items.iter_mut().for_each(|item| fold_keys(item, fold)),
+        _ => {}
+    }
+}
+
+/// Record the spelling of every key in `value`.
+pub(crate) fn collect_key_spellings(value: &Value, spellings: &mut KeySpellings) {
+    match value {
+        Value::Object(obj) => {
+            for (key, child) in obj {
+                let seen = spellings.entry(key.to_lowercase()).or_default();
+                if !seen.contains(key) {
+                    seen.push(key.clone());
+                }
+                collect_key_spellings(child, spellings);
+            }
+        }
+        Value::Array(items) => items
+            .iter()
+            .for_each(|item| collect_key_spellings(item, spellings)),
+        _ => {}
+    }
+}
+
+/// Combine the spellings of two sets of documents, `spellings` coming first.
+pub(crate) fn merge_key_spellings(
+    mut spellings: KeySpellings,
+    other: KeySpellings,
+) -> KeySpellings {
+    for (lower, keys) in other {
+        let seen = spellings.entry(lower).or_default();
+        for key in keys {
+            if !seen.contains(&key) {
+                seen.push(key);
+            }
+        }
+    }
+    spellings
+}
+
+/// The keys seen with more than one spelling, sorted by the field they became.
+pub(crate) fn key_collisions(spellings: &KeySpellings, fold: KeyFold) -> Vec<KeyCollision> {
+    let mut collisions: Vec<KeyCollision> = spellings
+        .values()
+        .filter(|keys| keys.len() > 1)
+        .map(|keys| KeyCollision {
+            name: match fold {
+                KeyFold::Preserve => keys[0].clone(),
+                fold => fold_key(&keys[0], fold),
+            },
+            keys: keys.clone(),
+        })
+        .collect();
+    collisions.sort_by(|a, b| a.name.cmp(&b.name));
+    collisions
+}
+
+/// Rename the lower-cased properties of `schema` (and its `required` entries) to
+/// the spelling of their key seen first, for [`KeyFold::Preserve`].
+pub(crate) fn restore_spellings(schema: &mut Value, spellings: &KeySpellings) {
+    let Value::Object(obj) = schema else {
+        return;
+    };
+    let spelling = |key: &str| {
+        spellings
+            .get(key)
+            .and_then(|keys| keys.first())
+            .cloned()
+            .unwrap_or_else(|| key.to_string())
+    };
+    if let Some(Value::Object(props)) = obj.get_mut("properties") {
+        *props = std::mem::take(props)
+            .into_iter()
+            .map(|(key, mut child)| {
+                restore_spellings(&mut child, spellings);
+                (spelling(&key), child)
+            })
+            .collect();
+    }
+    if let Some(Value::Array(required)) = obj.get_mut("required") {
+        for name in required.iter_mut() {
+            if let Value::String(key) = name {
+                *key = spelling(key);
+            }
+        }
+    }
+    for key in ["items", "additionalProperties"] {
+        if let Some(child) = obj.get_mut(key) {
+            restore_spellings(child, spellings);
+        }
+    }
+    for key in ["anyOf", "oneOf", "prefixItems", "items"] {
+        if let Some(Value::Array(children)) = obj.get_mut(key) {
+            children
+                .iter_mut()
+                .for_each(|child| restore_spellings(child, spellings));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    include!("../tests/key_fold.rs");
+}
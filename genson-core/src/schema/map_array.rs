@@ -0,0 +1,139 @@
+//! Coercion of arrays of maps into maps of arrays (`coerce_map_array`).
+//!
+//! Scraped data often encodes the same field both as `{"en": ["a"]}` and as
+//! `[{"en": "a"}]`, which genson can only describe as an object/array union.
+//! With the coercion enabled, such a union collapses to the map-of-arrays form:
+//! each key of the array's objects becomes a property holding an array of the
+//! values seen under it, and normalisation folds the array form into it.
+
+use crate::genson_rs::get_builder;
+use serde_json::{json, Map, Value};
+
+/// Collapse every object/array-of-objects union below `schema` to one object
+/// whose properties are arrays.
+pub(crate) fn coerce_map_arrays(schema: &mut Value) {
+    let Value::Object(obj) = schema else {
+        return;
+    };
+    for (key, child) in obj.iter_mut() {
+        match key.as_str() {
+            "properties" => {
+                if let Value::Object(props) = child {
+                    for prop in props.values_mut() {
+                        coerce_map_arrays(prop);
+                    }
+                }
+            }
+            "items" | "additionalProperties" => coerce_map_arrays(child),
+            "anyOf" => {
+                if let Value::Array(branches) = child {
+                    for branch in branches {
+                        coerce_map_arrays(branch);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let Some(Value::Array(branches)) = obj.get_mut("anyOf") else {
+        return;
+    };
+    let map_index = branches.iter().position(is_map_of_arrays);
+    let array_index = branches.iter().position(is_array_of_maps);
+    let (Some(map_index), Some(array_index)) = (map_index, array_index) else {
+        return;
+    };
+    let array_branch = branches.remove(array_index);
+    let map_index = if array_index < map_index {
+        map_index - 1
+    } else {
+        map_index
+    };
+    merge_into_map(&mut branches[map_index], &array_branch["items"]);
+
+    if branches.len() == 1 {
+        let Some(Value::Object(only)) = branches.pop() else {
+            return;
+        };
+        obj.remove("anyOf");
+        obj.extend(only);
+    }
+}
+
+fn is_type(schema: &Value, name: &str) -> bool {
+    schema.get("type").and_then(Value::as_str) == Some(name)
+}
+
+fn properties(schema: &Value) -> Option<&Map<String, Value>> {
+    schema.get("properties").and_then(Value::as_object)
+}
+
+/// An object whose properties are all arrays, e.g. `{"en": ["a"]}`.
+fn is_map_of_arrays(schema: &Value) -> bool {
+    is_type(schema, "object")
+        && properties(schema)
+            .is_some_and(|props| !props.is_empty() && props.values().all(|p| is_type(p, "array")))
+}
+
+/// An array of objects, e.g. `[{"en": "a"}]`.
+fn is_array_of_maps(schema: &Value) -> bool {
+    is_type(schema, "array")
+        && schema
+            .get("items")
+            .is_some_and(|items| is_type(items, "object") && properties(items).is_some())
+}
+
+/// Add the properties of the array form's `items` to the map form, each as an
+/// array of its values. Keys are required only where both forms required them.
+fn merge_into_map(map_schema: &mut Value, items: &Value) {
+    let item_props = properties(items).cloned().unwrap_or_default();
+    let item_required = required(items);
+    let map_required = required(map_schema);
+
+    let Some(Value::Object(props)) = map_schema.get_mut("properties") else {
+        return;
+    };
+    for (name, value_schema) in item_props {
+        let merged = match props.get(&name) {
+            Some(existing) => {
+                let mut builder = get_builder(None);
+                builder.add_schema(existing.get("items").cloned().unwrap_or_default());
+                builder.add_schema(value_schema);
+                builder.to_schema()
+            }
+            None => value_schema,
+        };
+        props.insert(name, json!({"type": "array", "items": merged}));
+    }
+
+    let both: Vec<Value> = map_required
+        .into_iter()
+        .filter(|name| item_required.contains(name))
+        .map(Value::String)
+        .collect();
+    let obj = map_schema.as_object_mut().expect("map form is an object");
+    if both.is_empty() {
+        obj.remove("required");
+    } else {
+        obj.insert("required".to_string(), Value::Array(both));
+    }
+}
+
+fn required(schema: &Value) -> Vec<String> {
+    schema
+        .get("required")
+        .and_then(Value::as_array)
+        .map(|names| {
+            names
+                .iter()
+                .filter_map(|name| name.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    include!("../tests/map_array.rs");
+}
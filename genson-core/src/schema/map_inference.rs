@@ -1,14 +1,29 @@
 // genson-core/src/schema/map_inference.rs
-use crate::schema::core::{make_promoted_scalar_key, SchemaInferenceConfig};
-use crate::{debug, profile_verbose};
+use crate::schema::core::{
+    make_promoted_scalar_key, MapDetection, SchemaInferenceConfig, MAP_KEY_PRESENCE_RATIO,
+};
+use crate::schema::explain::{ExplainRecorder, FieldDecision, FieldExplanation, Unification};
+use crate::schema::field_path::{child_path, pointer, MAP_VALUES_SEGMENT};
+use crate::schema::presence::PresenceCounts;
+use crate::{debug, profile, profile_verbose};
 use rayon::prelude::*;
 use serde_json::Value;
+use std::collections::HashSet;
 mod unification;
 use super::current_time_hms;
 use unification::*;
 
 const PARALLEL_PROP_THRESHOLD: usize = 3;
 
+/// What the build stage observed that map detection takes into account.
+#[derive(Default)]
+pub(crate) struct RewriteHints<'a> {
+    /// Field and object counts, for `MapDetection::PerDocumentVariance`
+    pub(crate) presence: Option<PresenceCounts<'a>>,
+    /// JSON Pointer paths of the `seed_schema` records, which stay records
+    pub(crate) seed_records: HashSet<String>,
+}
+
 /// Process properties in parallel when beneficial
 fn process_properties_parallel<F>(
     props_obj: &mut serde_json::Map<String, Value>,
@@ -152,6 +167,66 @@ fn process_anyof_unions(
     made_changes
 }
 
+/// Key holding the language code in a multi-language label record.
+const LABEL_LANGUAGE_KEY: &str = "language";
+/// Key holding the text in a multi-language label record.
+const LABEL_VALUE_KEY: &str = "value";
+
+/// Whether a schema is a `{language, value}` label record with string fields.
+fn is_label_record(schema: &Value) -> bool {
+    let schema = extract_non_null_schema(schema);
+    if schema.get("type") != Some(&Value::String("object".into())) {
+        return false;
+    }
+    let Some(props) = schema.get("properties").and_then(|p| p.as_object()) else {
+        return false;
+    };
+    props.len() == 2
+        && [LABEL_LANGUAGE_KEY, LABEL_VALUE_KEY].iter().all(|key| {
+            props
+                .get(*key)
+                .map(extract_non_null_schema)
+                .is_some_and(|s| s.get("type") == Some(&Value::String("string".into())))
+        })
+}
+
+/// If every property of an object schema is a label record (labels, descriptions) or
+/// every property is an array of label records (aliases), return the map value schema
+/// for it, simplified to the `value` string when `simplify_labels` is set.
+fn label_map_value_schema(
+    props: &serde_json::Map<String, Value>,
+    config: &SchemaInferenceConfig,
+) -> Option<Value> {
+    if props.is_empty() {
+        return None;
+    }
+    let is_label_array = |schema: &Value| {
+        let schema = extract_non_null_schema(schema);
+        schema.get("type") == Some(&Value::String("array".into()))
+            && schema.get("items").is_some_and(is_label_record)
+    };
+
+    let entry = if config.simplify_labels {
+        serde_json::json!({"type": "string"})
+    } else {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                LABEL_LANGUAGE_KEY: {"type": "string"},
+                LABEL_VALUE_KEY: {"type": "string"}
+            },
+            "required": [LABEL_LANGUAGE_KEY, LABEL_VALUE_KEY]
+        })
+    };
+    if props.values().all(is_label_record) {
+        Some(entry)
+    } else if props.values().all(is_label_array) {
+        Some(serde_json::json!({"type": "array", "items": entry}))
+    } else {
+        None
+    }
+}
+
 /// Check if an object schema contains any fields specified in force_parent_field_types.
 /// Returns Some(forced_type) if a match is found, None otherwise.
 fn check_force_parent_field_types<'a>(
@@ -180,14 +255,22 @@ fn check_force_parent_field_types<'a>(
 ///   - Required key cardinality (`map_max_required_keys`)
 ///   - Value homogeneity (all values must be homogeneous) OR
 ///   - Value unifiability (compatible record schemas when `unify_maps` enabled)
-/// - Recurses into nested objects/arrays, carrying field names down so overrides apply.
+/// - Recurses into nested objects/arrays, carrying field names and the field path
+///   down so name- and path-based overrides apply.
+///
+/// Each map/record decision is recorded in `explain`, when given.
 pub(crate) fn rewrite_objects(
     schema: &mut Value,
     field_name: Option<&str>,
+    path: &[String],
     config: &SchemaInferenceConfig,
     is_root: bool,
+    hints: &RewriteHints,
+    explain: Option<&ExplainRecorder>,
 ) {
-    if config.debug {
+    #[cfg(feature = "trace")]
+    let _span = tracing::debug_span!("rewrite_objects", path = %path.join(".")).entered();
+    if config.debug_enabled() {
         debug!(
             config,
             "rewrite_objects(field_name={:?}, schema={})",
@@ -198,8 +281,8 @@ pub(crate) fn rewrite_objects(
     // GUARD: Skip map conversion if this field was force-promoted to a scalar wrapper
     // BUT: Allow explicit force_field_types to override
     if let Some(name) = field_name {
-        if config.force_scalar_promotion.contains(name)
-            && !config.force_field_types.contains_key(name)
+        if config.is_force_scalar_promotion(field_name, path)
+            && config.forced_field_type(field_name, path).is_none()
         {
             debug!(
                 config,
@@ -214,6 +297,13 @@ pub(crate) fn rewrite_objects(
                             "Force promoting scalar field '{}' of type '{}'", name, type_str
                         );
 
+                        ExplainRecorder::note(explain, || {
+                            FieldExplanation::forced(
+                                path,
+                                FieldDecision::ScalarWrapper,
+                                "force_scalar_promotion",
+                            )
+                        });
                         let wrapped_key = make_promoted_scalar_key(name, type_str);
                         let scalar_schema = schema.clone();
 
@@ -239,6 +329,13 @@ pub(crate) fn rewrite_objects(
                                     inner_type
                                 );
 
+                                ExplainRecorder::note(explain, || {
+                                    FieldExplanation::forced(
+                                        path,
+                                        FieldDecision::ScalarWrapper,
+                                        "force_scalar_promotion",
+                                    )
+                                });
                                 let wrapped_key = make_promoted_scalar_key(name, inner_type);
                                 let scalar_schema = schema.clone();
 
@@ -267,6 +364,13 @@ pub(crate) fn rewrite_objects(
                                     inner_type
                                 );
 
+                                ExplainRecorder::note(explain, || {
+                                    FieldExplanation::forced(
+                                        path,
+                                        FieldDecision::ScalarWrapper,
+                                        "force_scalar_promotion",
+                                    )
+                                });
                                 let wrapped_key = make_promoted_scalar_key(name, inner_type);
                                 let scalar_schema = schema.clone();
 
@@ -285,48 +389,98 @@ pub(crate) fn rewrite_objects(
         }
     }
     if let Value::Object(obj) = schema {
-        // --- Forced overrides by field name ---
-        if let Some(name) = field_name {
-            if let Some(forced) = config.force_field_types.get(name) {
-                if config.debug {
-                    debug!(config, "Hit force field: {}={}", name, forced);
+        // --- Forced overrides by field name, and the records of a seed schema ---
+        let forced = config
+            .forced_field_type(field_name, path)
+            .map(|forced| (forced, "force_field_types"))
+            .or_else(|| {
+                let seeded = !hints.seed_records.is_empty()
+                    && obj.contains_key("properties")
+                    && hints.seed_records.contains(&pointer(path));
+                seeded.then_some(("record", "seed_schema"))
+            });
+        if let Some((forced, setting)) = forced {
+            if config.debug_enabled() {
+                debug!(
+                    config,
+                    "Hit force field: {}={}",
+                    field_name.unwrap_or("root"),
+                    forced
+                );
+            }
+            match forced {
+                "map" => {
+                    ExplainRecorder::note(explain, || {
+                        FieldExplanation::forced(path, FieldDecision::Map, setting)
+                    });
+                    obj.shift_remove("properties");
+                    obj.shift_remove("required");
+                    obj.insert(
+                        "additionalProperties".to_string(),
+                        serde_json::json!({ "type": "string" }),
+                    );
+                    return; // no need to apply heuristics or recurse
                 }
-                match forced.as_str() {
-                    "map" => {
-                        obj.shift_remove("properties");
-                        obj.shift_remove("required");
-                        obj.insert(
-                            "additionalProperties".to_string(),
-                            serde_json::json!({ "type": "string" }),
-                        );
-                        return; // no need to apply heuristics or recurse
+                "record" => {
+                    ExplainRecorder::note(explain, || {
+                        FieldExplanation::forced(path, FieldDecision::Record, setting)
+                    });
+                    if let Some(props) = obj.get_mut("properties").and_then(|p| p.as_object_mut()) {
+                        process_properties_parallel(props, config, |k, v| {
+                            if config.debug_enabled() {
+                                debug!(config, "Force field induced recursion: {}", k);
+                            }
+                            rewrite_objects(
+                                v,
+                                Some(k),
+                                &child_path(path, k),
+                                config,
+                                false,
+                                hints,
+                                explain,
+                            );
+                        });
                     }
-                    "record" => {
-                        if let Some(props) =
-                            obj.get_mut("properties").and_then(|p| p.as_object_mut())
-                        {
-                            process_properties_parallel(props, config, |k, v| {
-                                if config.debug {
-                                    debug!(config, "Force field induced recursion: {}", k);
-                                }
-                                rewrite_objects(v, Some(k), config, false);
-                            });
-                        }
-                        if let Some(items) = obj.get_mut("items") {
-                            debug!(config, "Force field induced recursion: items");
-                            rewrite_objects(items, None, config, false);
-                        }
-                        return;
+                    if let Some(items) = obj.get_mut("items") {
+                        debug!(config, "Force field induced recursion: items");
+                        rewrite_objects(items, None, path, config, false, hints, explain);
                     }
-                    _ => {}
+                    return;
                 }
+                _ => {}
+            }
+        }
+
+        // --- Multi-language label maps (opt-in, ignores thresholds) ---
+        if (config.label_maps || config.simplify_labels) && !(is_root && config.no_root_map) {
+            if let Some(values) = obj
+                .get("properties")
+                .and_then(|p| p.as_object())
+                .and_then(|props| label_map_value_schema(props, config))
+            {
+                debug!(
+                    config,
+                    "Converting field {:?} to a label map",
+                    field_name.unwrap_or("root")
+                );
+                ExplainRecorder::note(explain, || {
+                    FieldExplanation::new(
+                        path,
+                        FieldDecision::Map,
+                        "the values are multi-language labels (label_maps)",
+                    )
+                });
+                obj.shift_remove("properties");
+                obj.shift_remove("required");
+                obj.insert("additionalProperties".to_string(), values);
+                return;
             }
         }
 
         // --- Handle anyOf unions ---
         if let Some(Value::Array(any_of_schemas)) = obj.get("anyOf") {
             if config.unify_maps {
-                if config.debug {
+                if config.debug_enabled() {
                     debug!(
                         config,
                         "Found anyOf union with {} schemas, attempting unification",
@@ -341,21 +495,45 @@ pub(crate) fn rewrite_objects(
                     // Replace the entire schema with the unified result
                     *schema = unified;
                     // Recurse into the unified schema to apply further processing
-                    rewrite_objects(schema, field_name, config, is_root);
+                    rewrite_objects(schema, field_name, path, config, is_root, hints, explain);
                     return;
                 } else {
                     debug!(config, "Failed to unify anyOf schemas, leaving as-is");
+                    ExplainRecorder::note(explain, || FieldExplanation {
+                        unification: Some(Unification::Failed),
+                        ..FieldExplanation::new(
+                            path,
+                            FieldDecision::Union,
+                            "the anyOf branches could not be unified",
+                        )
+                    });
                 }
             }
             // If unification disabled or failed, still recurse into each anyOf branch
             if let Some(any_of_array) = obj.get_mut("anyOf").and_then(|a| a.as_array_mut()) {
                 if any_of_array.len() >= 3 {
                     any_of_array.par_iter_mut().for_each(|any_of_schema| {
-                        rewrite_objects(any_of_schema, field_name, config, false);
+                        rewrite_objects(
+                            any_of_schema,
+                            field_name,
+                            path,
+                            config,
+                            false,
+                            hints,
+                            explain,
+                        );
                     });
                 } else {
                     for any_of_schema in any_of_array {
-                        rewrite_objects(any_of_schema, field_name, config, false);
+                        rewrite_objects(
+                            any_of_schema,
+                            field_name,
+                            path,
+                            config,
+                            false,
+                            hints,
+                            explain,
+                        );
                     }
                 }
             }
@@ -365,7 +543,7 @@ pub(crate) fn rewrite_objects(
         if let Some(props) = obj.get("properties").and_then(|p| p.as_object()) {
             // GUARD: Check if this object contains force_parent_field_types keys
             if let Some(forced_parent_type) = check_force_parent_field_types(obj, config) {
-                if config.debug {
+                if config.debug_enabled() {
                     debug!(
                         config,
                         "Object at field {:?} contains a force_parent_field, forcing parent type to '{}'",
@@ -375,20 +553,35 @@ pub(crate) fn rewrite_objects(
                 }
 
                 if forced_parent_type == "record" {
+                    ExplainRecorder::note(explain, || {
+                        FieldExplanation::forced(
+                            path,
+                            FieldDecision::Record,
+                            "force_parent_field_types",
+                        )
+                    });
                     // Skip map conversion, but still recurse into properties
                     if let Some(props_mut) =
                         obj.get_mut("properties").and_then(|p| p.as_object_mut())
                     {
                         process_properties_parallel(props_mut, config, |k, v| {
-                            if config.debug {
+                            if config.debug_enabled() {
                                 debug!(config, "Force parent field induced recursion: {}", k);
                             }
-                            rewrite_objects(v, Some(k), config, false);
+                            rewrite_objects(
+                                v,
+                                Some(k),
+                                &child_path(path, k),
+                                config,
+                                false,
+                                hints,
+                                explain,
+                            );
                         });
                     }
                     if let Some(items) = obj.get_mut("items") {
                         debug!(config, "Force parent field induced recursion: items");
-                        rewrite_objects(items, None, config, false);
+                        rewrite_objects(items, None, path, config, false, hints, explain);
                     }
                     return;
                 }
@@ -397,17 +590,32 @@ pub(crate) fn rewrite_objects(
 
             // GUARD: Skip map conversion if this field was force-promoted to a scalar wrapper
             if let Some(name) = field_name {
-                if config.force_scalar_promotion.contains(name) {
+                if config.is_force_scalar_promotion(field_name, path) {
                     debug!(
                         config,
                         "Skipping map conversion for force-promoted field '{}'", name
                     );
+                    ExplainRecorder::note(explain, || {
+                        FieldExplanation::forced(
+                            path,
+                            FieldDecision::Record,
+                            "force_scalar_promotion",
+                        )
+                    });
                     // Still need to recurse into properties
                     if let Some(props_mut) =
                         obj.get_mut("properties").and_then(|p| p.as_object_mut())
                     {
                         process_properties_parallel(props_mut, config, |k, v| {
-                            rewrite_objects(v, Some(k), config, false);
+                            rewrite_objects(
+                                v,
+                                Some(k),
+                                &child_path(path, k),
+                                config,
+                                false,
+                                hints,
+                                explain,
+                            );
                         });
                     }
                     return;
@@ -416,7 +624,7 @@ pub(crate) fn rewrite_objects(
             // GUARD: Skip re-processing of already converted map schemas
             if obj.get("additionalProperties").is_some() {
                 if props.is_empty() {
-                    if config.debug {
+                    if config.debug_enabled() {
                         debug!(
                             config,
                             "Skipping re-processing of already converted map schema at field {:?}",
@@ -425,7 +633,7 @@ pub(crate) fn rewrite_objects(
                     }
                     // Just recurse into the additionalProperties value and return
                     if let Some(additional_props) = obj.get_mut("additionalProperties") {
-                        if config.debug {
+                        if config.debug_enabled() {
                             debug!(
                                 config,
                                 "Rewriting already converted map schema at field {:?}",
@@ -433,12 +641,20 @@ pub(crate) fn rewrite_objects(
                             );
                         }
                         // Hmm: shouldn't this be `field_name` not None?
-                        rewrite_objects(additional_props, None, config, false);
+                        rewrite_objects(
+                            additional_props,
+                            None,
+                            &child_path(path, MAP_VALUES_SEGMENT),
+                            config,
+                            false,
+                            hints,
+                            explain,
+                        );
                     }
                     return;
                 } else {
                     // This shouldn't happen - schema shouldn't have both props + additionalProperties
-                    if config.debug {
+                    if config.debug_enabled() {
                         debug!(
                             config,
                             "Warning: schema has both properties and additionalProperties at field {:?}",
@@ -448,12 +664,24 @@ pub(crate) fn rewrite_objects(
                 }
             }
             let key_count = props.len(); // |UK| - total keys observed
-            let above_threshold = key_count >= config.map_threshold;
+                                         // With per-document variance, objects seen at least twice must also have
+                                         // keys that come and go between documents
+            let key_presence = match config.map_detection {
+                MapDetection::Merged => None,
+                MapDetection::PerDocumentVariance => hints
+                    .presence
+                    .as_ref()
+                    .and_then(|counts| counts.key_presence(path, props.keys()))
+                    .filter(|&(objects, _)| objects > 1)
+                    .map(|(_, mean)| mean),
+            };
+            let keys_stable = key_presence.is_some_and(|mean| mean > MAP_KEY_PRESENCE_RATIO);
+            let above_threshold = key_count >= config.map_threshold && !keys_stable;
 
             // Copy out child schema shapes
             let child_schemas: Vec<&Value> = props.values().collect();
-            if config.profile && child_schemas.len() > 50 {
-                anstream::eprintln!("Collected {} schemas", child_schemas.len());
+            if child_schemas.len() > 50 {
+                profile!(config, "Collected {} schemas", child_schemas.len());
             }
 
             // Detect map-of-records only if:
@@ -467,9 +695,29 @@ pub(crate) fn rewrite_objects(
                     {
                         let all_same = child_schemas.par_iter().all(|other| other == first);
                         if all_same {
-                            let first_clone = (*first).clone();
+                            ExplainRecorder::note(explain, || FieldExplanation {
+                                key_count: Some(key_count),
+                                map_threshold: Some(config.map_threshold),
+                                key_presence,
+                                unification: Some(Unification::Homogeneous),
+                                ..FieldExplanation::new(
+                                    path,
+                                    FieldDecision::Map,
+                                    format!("all {} values are the same record", key_count),
+                                )
+                            });
+                            let mut first_clone = (*first).clone();
                             obj.shift_remove("properties");
                             obj.shift_remove("required");
+                            rewrite_objects(
+                                &mut first_clone,
+                                None,
+                                &child_path(path, MAP_VALUES_SEGMENT),
+                                config,
+                                false,
+                                hints,
+                                explain,
+                            );
                             obj.insert("additionalProperties".to_string(), first_clone);
                             return;
                         }
@@ -486,6 +734,7 @@ pub(crate) fn rewrite_objects(
 
             // Check for unifiable schemas
             let mut unified_schema: Option<Value> = None;
+            let mut unification = Unification::NotAttempted;
             if let Some(first_schema) = props.values().next() {
                 // Normalise all schemas for comparison
                 let normalised_schemas: Vec<Value> = if props.len() >= 100 {
@@ -500,7 +749,7 @@ pub(crate) fn rewrite_objects(
                 let first_normalised = extract_non_null_schema(first_schema);
 
                 // Debug output to diagnose the issue
-                if config.debug {
+                if config.debug_enabled() {
                     debug!(
                         config,
                         "Checking homogeneity for field {:?} with {} schemas",
@@ -558,7 +807,7 @@ pub(crate) fn rewrite_objects(
                     }
                 }
 
-                let homog_start = std::time::Instant::now();
+                let homog_start = crate::time::Instant::now();
                 if normalised_schemas
                     .par_iter()
                     .all(|schema| schema == &first_normalised)
@@ -566,26 +815,30 @@ pub(crate) fn rewrite_objects(
                     // All schemas are homogeneous after normalisation
                     debug!(config, "Schemas are homogeneous after normalisation");
                     unified_schema = Some(first_normalised);
+                    unification = Unification::Homogeneous;
                 } else if config.unify_maps {
                     debug!(config, "Schemas not homogeneous, attempting unification");
-                    if config.profile && normalised_schemas.len() > 50 {
-                        anstream::eprintln!(
+                    if normalised_schemas.len() > 50 {
+                        profile!(
+                            config,
                             "Unification of {} heterogeneous schemas beginning...",
                             normalised_schemas.len()
                         );
                     }
 
                     // Check if any of the property keys are in no_unify
-                    let has_excluded_field =
-                        props.keys().any(|k| config.no_unify.contains(k.as_str()));
+                    let has_excluded_field = props
+                        .keys()
+                        .any(|k| config.is_no_unify(k, &child_path(path, k)));
                     if has_excluded_field {
-                        if config.debug {
+                        unification = Unification::NoUnify;
+                        if config.debug_enabled() {
                             debug!(
                                 config,
                                 "Not unifying: one or more fields in no_unify: {:?}",
                                 props
                                     .keys()
-                                    .filter(|k| config.no_unify.contains(k.as_str()))
+                                    .filter(|k| config.is_no_unify(k, &child_path(path, k)))
                                     .collect::<Vec<_>>()
                             );
                         }
@@ -608,7 +861,7 @@ pub(crate) fn rewrite_objects(
                                 }
                             }
                             if all_items_ok {
-                                let unify_start = std::time::Instant::now();
+                                let unify_start = crate::time::Instant::now();
                                 if let Some(unified_items) = check_unifiable_schemas(
                                     &item_schemas,
                                     field_name.unwrap_or(""),
@@ -618,9 +871,13 @@ pub(crate) fn rewrite_objects(
                                         "type": "array",
                                         "items": unified_items
                                     }));
+                                    unification = Unification::Unified;
+                                } else {
+                                    unification = Unification::Failed;
                                 }
-                                if config.profile && child_schemas.len() > 50 {
-                                    anstream::eprintln!(
+                                if child_schemas.len() > 50 {
+                                    profile!(
+                                        config,
                                         "Unification of {} item schemas took {:?}",
                                         child_schemas.len(),
                                         unify_start.elapsed()
@@ -631,14 +888,20 @@ pub(crate) fn rewrite_objects(
                             // Only try record unification if unify_maps is enabled and above threshold
                             // This ensures we only do expensive unification when it would result in map conversion
                             if above_threshold {
-                                let unify_start = std::time::Instant::now();
+                                let unify_start = crate::time::Instant::now();
                                 unified_schema = check_unifiable_schemas(
                                     &child_schemas,
                                     field_name.unwrap_or(""),
                                     config,
                                 );
-                                if config.profile && child_schemas.len() > 50 {
-                                    anstream::eprintln!(
+                                unification = if unified_schema.is_some() {
+                                    Unification::Unified
+                                } else {
+                                    Unification::Failed
+                                };
+                                if child_schemas.len() > 50 {
+                                    profile!(
+                                        config,
                                         "Unification of {} child schemas took {:?}",
                                         child_schemas.len(),
                                         unify_start.elapsed()
@@ -648,8 +911,9 @@ pub(crate) fn rewrite_objects(
                         }
                     }
                 }
-                if config.profile && normalised_schemas.len() > 50 {
-                    anstream::eprintln!(
+                if normalised_schemas.len() > 50 {
+                    profile!(
+                        config,
                         "Homogeneity check on {} schemas took {:?}",
                         normalised_schemas.len(),
                         homog_start.elapsed()
@@ -684,7 +948,7 @@ pub(crate) fn rewrite_objects(
                     false
                 } else if let Some(max_required) = config.map_max_required_keys {
                     let result = required_key_count <= max_required;
-                    if config.debug {
+                    if config.debug_enabled() {
                         debug!(
                             config,
                             "Map conversion decision: required_keys={} <= max_required={} = {}",
@@ -703,7 +967,7 @@ pub(crate) fn rewrite_objects(
                 }
             } else {
                 if !above_threshold {
-                    if config.debug {
+                    if config.debug_enabled() {
                         debug!(
                             config,
                             "Not converting to map: below threshold ({} < {})",
@@ -717,9 +981,53 @@ pub(crate) fn rewrite_objects(
                 false
             };
 
+            ExplainRecorder::note(explain, || {
+                let reason = if key_count < config.map_threshold {
+                    format!(
+                        "{} keys is below map_threshold {}",
+                        key_count, config.map_threshold
+                    )
+                } else if keys_stable {
+                    format!(
+                        "the keys are stable across documents (mean key presence {:.2} is above {})",
+                        key_presence.unwrap_or_default(),
+                        MAP_KEY_PRESENCE_RATIO
+                    )
+                } else if unified_schema.is_none() {
+                    "the values have no common schema".to_string()
+                } else if is_root && config.no_root_map {
+                    "the root is never a map (no_root_map)".to_string()
+                } else if !should_be_map {
+                    format!(
+                        "{} required keys is above map_max_required_keys {}",
+                        required_key_count,
+                        config.map_max_required_keys.unwrap_or_default()
+                    )
+                } else {
+                    format!(
+                        "{} keys reaches map_threshold {} and the values share a schema",
+                        key_count, config.map_threshold
+                    )
+                };
+                let decision = if should_be_map {
+                    FieldDecision::Map
+                } else {
+                    FieldDecision::Record
+                };
+                FieldExplanation {
+                    key_count: Some(key_count),
+                    map_threshold: Some(config.map_threshold),
+                    key_presence,
+                    required_key_count: Some(required_key_count),
+                    map_max_required_keys: config.map_max_required_keys,
+                    unification: Some(unification),
+                    ..FieldExplanation::new(path, decision, reason)
+                }
+            });
+
             if should_be_map {
                 if let Some(schema) = unified_schema {
-                    if config.debug {
+                    if config.debug_enabled() {
                         let pretty_schema =
                             serde_json::to_string_pretty(&schema).unwrap_or_default();
                         let lines: Vec<&str> = pretty_schema.lines().collect();
@@ -744,7 +1052,15 @@ pub(crate) fn rewrite_objects(
 
                     // Process the schema being moved to additionalProperties for nested anyOf
                     let mut processed_schema = schema.clone();
-                    rewrite_objects(&mut processed_schema, None, config, false);
+                    rewrite_objects(
+                        &mut processed_schema,
+                        None,
+                        &child_path(path, MAP_VALUES_SEGMENT),
+                        config,
+                        false,
+                        hints,
+                        explain,
+                    );
                     obj.insert("additionalProperties".to_string(), processed_schema);
 
                     return;
@@ -753,39 +1069,55 @@ pub(crate) fn rewrite_objects(
         }
 
         // Skip recursion if we have a field name that's in the force types map
-        if !matches!(field_name, Some(name) if config.force_field_types.contains_key(name)) {
+        if config.forced_field_type(field_name, path).is_none() {
             // --- Recurse into nested values ---
             if let Some(props) = obj.get_mut("properties").and_then(|p| p.as_object_mut()) {
                 process_properties_parallel(props, config, |k, v| {
-                    if config.debug {
+                    if config.debug_enabled() {
                         debug!(config, "Nested value recursion: {}", k);
                     }
-                    rewrite_objects(v, Some(k), config, false);
+                    rewrite_objects(
+                        v,
+                        Some(k),
+                        &child_path(path, k),
+                        config,
+                        false,
+                        hints,
+                        explain,
+                    );
                 });
             }
             if let Some(items) = obj.get_mut("items") {
                 debug!(config, "Nested value recursion: items");
-                rewrite_objects(items, None, config, false);
+                rewrite_objects(items, None, path, config, false, hints, explain);
             }
             for (k, v) in obj.iter_mut() {
+                // `properties` and `items` were recursed into above
                 if matches!(
                     k.as_str(),
-                    "items" | "type" | "required" | "$schema" | "namespace" | "name"
+                    "properties" | "items" | "type" | "required" | "$schema" | "namespace" | "name"
                 ) {
                     continue;
                 }
                 if let Value::Object(_) = v {
-                    if config.debug {
+                    if config.debug_enabled() {
                         debug!(config, "Other value recursion: {}", k);
                     }
-                    rewrite_objects(v, Some(k), config, false);
+                    // Schema keywords don't name fields: `properties` keeps the path and
+                    // `additionalProperties` descends into the map values
+                    let other_path = match k.as_str() {
+                        "properties" => path.to_vec(),
+                        "additionalProperties" => child_path(path, MAP_VALUES_SEGMENT),
+                        _ => child_path(path, k),
+                    };
+                    rewrite_objects(v, Some(k), &other_path, config, false, hints, explain);
                 }
             }
         }
     } else if let Value::Array(arr) = schema {
         for v in arr {
             debug!(config, "Array value recursion");
-            rewrite_objects(v, None, config, false);
+            rewrite_objects(v, None, path, config, false, hints, explain);
         }
     }
 }
@@ -1,6 +1,6 @@
 // genson-core/src/schema/unification.rs
 use crate::{
-    debug, debug_verbose,
+    debug, debug_verbose, profile,
     schema::core::{make_promoted_scalar_key, SchemaInferenceConfig},
 };
 use rayon::prelude::*;
@@ -367,6 +367,50 @@ fn try_scalar_promotion(
     Some(result)
 }
 
+/// Whether some of `schemas` are scalars and some are records.
+fn mixes_scalars_and_records(schemas: &[&Value]) -> bool {
+    schemas.iter().any(|&s| is_scalar_schema(s)) && schemas.iter().any(|&s| is_object_schema(s))
+}
+
+/// The item schemas of an array schema: the branches of its `items` if they're an
+/// `anyOf`, or else `items` itself.
+fn array_item_schemas(schema: &Value) -> Option<Vec<&Value>> {
+    if !is_array_schema(schema) {
+        return None;
+    }
+    let items = extract_field_from_nullable_schema(schema, "items")?;
+    match items.get("anyOf") {
+        Some(Value::Array(branches)) => Some(branches.iter().collect()),
+        _ => Some(vec![items]),
+    }
+}
+
+/// Attempt to unify arrays of scalars with arrays of records (e.g. `["a"]` and
+/// `[{"x": 1}]`) by wrapping the scalar items under the field's synthetic key, as
+/// [`try_scalar_promotion`] does for the field itself, and unifying the items
+fn try_array_item_promotion(
+    existing: &Value,
+    new: &Value,
+    field_name: &str,
+    path: &str,
+    config: &SchemaInferenceConfig,
+) -> Option<Value> {
+    let mut items = array_item_schemas(existing)?;
+    items.extend(array_item_schemas(new)?);
+    if !mixes_scalars_and_records(&items) {
+        return None;
+    }
+    debug!(
+        config,
+        "{}: Promoting scalar array items for field '{}'", path, field_name
+    );
+    let unified_items = unify_anyof_schemas(&items, field_name, config)?;
+    Some(json!({
+        "type": "array",
+        "items": unified_items
+    }))
+}
+
 /// Recursively unwrap nullable schema wrappers and extract a specific field.
 ///
 /// Handles both legacy format `["null", {...}]` and modern format `{"type": ["null", "..."]}`.
@@ -492,7 +536,7 @@ fn unify_scalar_schemas(
     }
 
     // Multiple incompatible scalar types
-    if config.debug {
+    if config.debug_enabled() {
         let mut sorted_types: Vec<_> = base_types.into_iter().collect();
         sorted_types.sort();
         debug!(
@@ -503,7 +547,9 @@ fn unify_scalar_schemas(
     None
 }
 
-/// Unify map schemas by unifying their additionalProperties
+/// Unify map schemas by unifying their additionalProperties.
+///
+/// Value schemas that are scalars of different types unify to a union of those types.
 fn unify_map_schemas(
     schemas: &[&Value],
     path: &str,
@@ -520,9 +566,12 @@ fn unify_map_schemas(
         return None;
     }
 
-    // Extract all additionalProperties schemas
-    let mut additional_props_schemas = Vec::<&Value>::new();
+    // Extract all additionalProperties schemas (empty records contribute none)
+    let mut value_schemas = Vec::<&Value>::new();
     for (i, &schema) in schemas.iter().enumerate() {
+        if is_empty_record_schema(schema) {
+            continue;
+        }
         if let Some(additional_props) =
             extract_field_from_nullable_schema(schema, "additionalProperties")
         {
@@ -533,7 +582,7 @@ fn unify_map_schemas(
                 i,
                 serde_json::to_string(additional_props).unwrap_or_default()
             );
-            additional_props_schemas.push(additional_props);
+            value_schemas.push(additional_props);
         } else {
             debug!(
                 config,
@@ -543,12 +592,18 @@ fn unify_map_schemas(
         }
     }
 
-    // Recursively unify the additionalProperties
-    if let Some(unified_additional_props) = check_unifiable_schemas(
-        &additional_props_schemas,
-        &format!("{}.additionalProperties", path),
-        config,
-    ) {
+    if value_schemas.is_empty() {
+        return Some(json!({
+            "type": "object",
+            "additionalProperties": {"type": "string"}
+        }));
+    }
+
+    // Recursively unify the values
+    let values_path = format!("{}.additionalProperties", path);
+    let unified = check_unifiable_schemas(&value_schemas, &values_path, config)
+        .or_else(|| unify_scalar_union(&value_schemas, &values_path, config));
+    if let Some(unified_additional_props) = unified {
         debug!(
             config,
             "{}: Successfully unified map additionalProperties", path
@@ -563,6 +618,66 @@ fn unify_map_schemas(
     }
 }
 
+/// The type names of a scalar schema, including `"null"`, or `None` if it isn't one.
+///
+/// Unlike [`is_scalar_schema`] this accepts unions of several scalar types.
+fn scalar_type_names(schema: &Value) -> Option<Vec<&str>> {
+    // Legacy nullable format: ["null", {"type": "string"}]
+    if let Value::Array(arr) = schema {
+        let mut names = Vec::new();
+        for item in arr {
+            match item {
+                Value::String(null) if null == "null" => names.push("null"),
+                other => names.extend(scalar_type_names(other)?),
+            }
+        }
+        return Some(names);
+    }
+
+    let is_scalar = |t: &str| matches!(t, "null" | "string" | "number" | "integer" | "boolean");
+    match schema.get("type")? {
+        Value::String(t) if is_scalar(t) => Some(vec![t.as_str()]),
+        Value::Array(types) => types
+            .iter()
+            .map(|t| t.as_str().filter(|t| is_scalar(t)))
+            .collect(),
+        _ => None,
+    }
+}
+
+/// Unify scalar schemas of different types into a union of those types, e.g. the
+/// integer and string values of two maps into `{"type": ["integer", "string"]}`.
+fn unify_scalar_union(
+    schemas: &[&Value],
+    path: &str,
+    config: &SchemaInferenceConfig,
+) -> Option<Value> {
+    let mut nullable = false;
+    let mut types: Vec<&str> = Vec::new();
+    for &schema in schemas {
+        for name in scalar_type_names(schema)? {
+            if name == "null" {
+                nullable = true;
+            } else if !types.contains(&name) {
+                types.push(name);
+            }
+        }
+    }
+    if types.is_empty() {
+        return None;
+    }
+    if nullable {
+        types.insert(0, "null");
+    }
+
+    debug!(config, "{}: Unified scalars to union {:?}", path, types);
+    if types.len() == 1 {
+        Some(json!({"type": types[0]}))
+    } else {
+        Some(json!({"type": types}))
+    }
+}
+
 /// Sequential pairwise unification with full scalar promotion support
 fn unify_field_schemas_sequential(
     field_name: &str,
@@ -587,9 +702,11 @@ fn unify_field_schemas_sequential(
             continue;
         }
 
+        let is_record = |s: &Value| is_object_schema(s) || is_empty_record_schema(s);
+        let is_map = |s: &Value| is_map_schema(s) || is_empty_record_schema(s);
         if (is_array_schema(&unified) && is_array_schema(new))
-            || ((is_object_schema(&unified) || is_empty_record_schema(&unified))
-                && (is_object_schema(new) || is_empty_record_schema(new)))
+            || (is_record(&unified) && is_record(new))
+            || (is_map(&unified) && is_map(new))
         {
             if let Some(result) = check_unifiable_schemas(
                 &[&unified, new],
@@ -598,9 +715,16 @@ fn unify_field_schemas_sequential(
             ) {
                 unified = result;
                 continue;
-            } else {
-                return (field_name.to_string(), None);
             }
+            if config.wrap_scalars && is_array_schema(&unified) && is_array_schema(new) {
+                if let Some(result) =
+                    try_array_item_promotion(&unified, new, field_name, path, config)
+                {
+                    unified = result;
+                    continue;
+                }
+            }
+            return (field_name.to_string(), None);
         }
 
         if config.wrap_scalars {
@@ -688,11 +812,14 @@ fn unify_field_schemas(
         return (field_name.to_string(), Some(schemas[0].clone()));
     }
 
-    // Check if we need scalar promotion for this field
+    // Check if we need scalar promotion for this field, or for its array items
     let needs_scalar_promo = config.wrap_scalars && {
-        let has_scalars = schemas.iter().any(|&s| is_scalar_schema(s));
-        let has_objects = schemas.iter().any(|&s| is_object_schema(s));
-        has_scalars && has_objects
+        let items: Vec<&Value> = schemas
+            .iter()
+            .filter_map(|&s| array_item_schemas(s))
+            .flatten()
+            .collect();
+        mixes_scalars_and_records(schemas) || mixes_scalars_and_records(&items)
     };
 
     if needs_scalar_promo || schemas.len() < 50 {
@@ -800,7 +927,7 @@ fn unify_record_schemas(
     }
 
     // Step 3: Unify schemas for each field
-    let merge_start = std::time::Instant::now();
+    let merge_start = crate::time::Instant::now();
     let field_names: Vec<_> = field_schemas.keys().cloned().collect();
 
     let unified_fields: Vec<(String, Option<Value>)> = if field_names.len() >= 10 {
@@ -830,8 +957,8 @@ fn unify_record_schemas(
         }
     }
 
-    if config.profile && schemas.len() > 50 {
-        anstream::eprintln!("  Merge loop took {:?}", merge_start.elapsed());
+    if schemas.len() > 50 {
+        profile!(config, "  Merge loop took {:?}", merge_start.elapsed());
     }
 
     let total_schemas = schemas.len();
@@ -948,11 +1075,45 @@ fn try_mixed_scalar_promotion(
     Some(promoted)
 }
 
+/// Unify the branches of an `anyOf` that are all records (besides a `null` branch)
+/// into one record, the fields missing from some branches becoming nullable. The
+/// record is nullable if there was a `null` branch.
+fn unify_record_branches(
+    branches: &[&Value],
+    path: &str,
+    config: &SchemaInferenceConfig,
+) -> Option<Value> {
+    let is_null = |s: &Value| s.get("type") == Some(&Value::String("null".into()));
+    let records: Vec<&Value> = branches.iter().copied().filter(|&s| !is_null(s)).collect();
+    if records.len() < 2
+        || !records
+            .iter()
+            .all(|&s| is_object_schema(s) || is_empty_record_schema(s))
+    {
+        return None;
+    }
+    debug!(
+        config,
+        "{}: Unifying {} record branches of an anyOf",
+        path,
+        records.len()
+    );
+    let mut unified = unify_record_schemas(&records, path, config)?;
+    if records.len() < branches.len() {
+        unified["type"] = json!(["null", "object"]);
+    }
+    Some(unified)
+}
+
 pub(crate) fn unify_anyof_schemas(
     schemas: &[&Value],
     field_name: &str,
     config: &SchemaInferenceConfig,
 ) -> Option<Value> {
+    // Records split across branches collapse into one, before any scalar promotion
+    if let Some(record) = unify_record_branches(schemas, field_name, config) {
+        return Some(record);
+    }
     if !config.wrap_scalars {
         return None;
     }
@@ -1004,9 +1165,13 @@ pub(crate) fn unify_anyof_schemas(
 ///
 /// Supports unifying:
 /// 1. Record schemas (objects with `properties`) - fields become selectively nullable
-/// 2. Map schemas (objects with `additionalProperties`) - by unifying the value schemas  
+/// 2. Map schemas (objects with `additionalProperties`) - by unifying the value schemas;
+///    values that are scalars of different types become a union of those types
 /// 3. Scalar schemas with the same base type - creates nullable version
 ///
+/// An `anyOf` whose branches are all records (or `null`) is first unified into a
+/// single record, so it takes part as a record rather than failing as a mixed type.
+///
 /// When `wrap_scalars` is enabled, scalar types that collide with object types are promoted
 /// to singleton objects under a synthetic key (e.g., `value__string`), allowing unification
 /// to succeed instead of failing.
@@ -1020,6 +1185,8 @@ pub(crate) fn check_unifiable_schemas(
     path: &str,
     config: &SchemaInferenceConfig,
 ) -> Option<Value> {
+    #[cfg(feature = "trace")]
+    let _span = tracing::debug_span!("unify", path).entered();
     debug_verbose!(
         config,
         "=== check_unifiable_schemas called with path='{}' and {} schemas:",
@@ -1040,6 +1207,24 @@ pub(crate) fn check_unifiable_schemas(
         return None;
     }
 
+    // Unions of records are unified into one record first, so they compare as records
+    let collapsed: Vec<Option<Value>> = schemas
+        .iter()
+        .map(|&s| match s.get("anyOf") {
+            Some(Value::Array(branches)) => {
+                let branches: Vec<&Value> = branches.iter().collect();
+                unify_record_branches(&branches, path, config)
+            }
+            _ => None,
+        })
+        .collect();
+    let schemas: Vec<&Value> = schemas
+        .iter()
+        .zip(&collapsed)
+        .map(|(&s, collapsed)| collapsed.as_ref().unwrap_or(s))
+        .collect();
+    let schemas = schemas.as_slice();
+
     // Check if all are array schemas
     if schemas.iter().all(|&s| is_array_schema(s)) {
         debug!(
@@ -1049,47 +1234,32 @@ pub(crate) fn check_unifiable_schemas(
         return unify_array_schemas(schemas, path, config);
     }
 
-    // Check if all are map schemas OR empty records (semantically equivalent to empty maps)
-    let all_maps_or_empty = schemas
-        .iter()
-        .all(|&s| is_map_schema(s) || is_empty_record_schema(s));
-
-    if all_maps_or_empty {
-        // Filter to only map schemas (ignore empty records)
-        let map_schemas: Vec<&Value> = schemas
-            .iter()
-            .filter(|&&s| is_map_schema(s))
-            .copied()
-            .collect();
+    // All empty records are semantically equivalent to an empty map
+    if schemas.iter().all(|&s| is_empty_record_schema(s)) {
+        debug!(
+            config,
+            "{}: All schemas are empty records, treating as empty map", path
+        );
+        return Some(json!({
+            "type": "object",
+            "additionalProperties": {"type": "string"}
+        }));
+    }
 
-        if map_schemas.is_empty() {
-            // All schemas are empty records - treat as an empty map
-            debug!(
-                config,
-                "{}: All schemas are empty records, treating as empty map", path
-            );
-            return Some(json!({
-                "type": "object",
-                "additionalProperties": {"type": "string"}
-            }));
-        } else if map_schemas.len() < schemas.len() {
-            // Some maps, some empty records - unify the maps (empty records contribute nothing)
-            debug!(
-                config,
-                "{}: Mix of {} maps and {} empty records, unifying maps only",
-                path,
-                map_schemas.len(),
-                schemas.len() - map_schemas.len()
-            );
-            return unify_map_schemas(&map_schemas, path, config);
-        } else {
-            // All maps, no empty records
-            debug!(
-                config,
-                "{}: All schemas are maps, attempting map unification", path
-            );
-            return unify_map_schemas(&map_schemas, path, config);
-        }
+    // Maps alongside empty records unify as maps (empty records contribute nothing)
+    if schemas
+        .iter()
+        .all(|&s| is_map_schema(s) || is_empty_record_schema(s))
+    {
+        let map_count = schemas.iter().filter(|&&s| is_map_schema(s)).count();
+        debug!(
+            config,
+            "{}: {} of {} schemas are maps, the rest empty records, attempting map unification",
+            path,
+            map_count,
+            schemas.len()
+        );
+        return unify_map_schemas(schemas, path, config);
     }
 
     // Check if all are record schemas (objects with properties) OR empty records
@@ -0,0 +1,108 @@
+//! Numeric map key detection (`detect_numeric_keys`).
+//!
+//! Maps keyed by IDs (`{"1": ..., "42": ...}`) have integer keys written as JSON
+//! strings. With detection on, a map whose keys were all integers in every
+//! document is annotated `"x-key-type": "integer"`, which carries over to its Avro
+//! map. Normalisation then writes `kv` entries with integer keys, and can
+//! rewrite the keys in canonical form (`canonical_numeric_keys`).
+
+use crate::schema::field_path::{child_path, pointer, pointer_matches, MAP_VALUES_SEGMENT};
+use serde_json::{Map, Value};
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+
+/// Keyword annotating a map whose keys are all integers, e.g.
+/// `{"type": "object", "additionalProperties": {...}, "x-key-type": "integer"}`.
+pub const MAP_KEY_TYPE_KEY: &str = "x-key-type";
+
+/// Whether every key of the objects at each path was an integer, keyed by field
+/// path (array items add no segment). Paths with only empty objects are absent.
+pub(crate) type MapKeyKinds = HashMap<String, bool>;
+
+/// Add the key kinds of `other` to `kinds`.
+pub(crate) fn merge_key_kinds(mut kinds: MapKeyKinds, other: MapKeyKinds) -> MapKeyKinds {
+    for (path, numeric) in other {
+        match kinds.entry(path) {
+            Entry::Vacant(entry) => {
+                entry.insert(numeric);
+            }
+            Entry::Occupied(mut entry) => *entry.get_mut() &= numeric,
+        }
+    }
+    kinds
+}
+
+/// Record whether the keys of an object at `path` are integers.
+pub(crate) fn observe_object(kinds: &mut MapKeyKinds, path: String, obj: &Map<String, Value>) {
+    if !obj.is_empty() {
+        let numeric = obj.keys().all(|key| is_integer_key(key));
+        *kinds.entry(path).or_insert(true) &= numeric;
+    }
+}
+
+/// Whether `key` is an integer (`-` then digits, fitting an `i64`).
+pub(crate) fn is_integer_key(key: &str) -> bool {
+    let digits = key.strip_prefix('-').unwrap_or(key);
+    !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit()) && key.parse::<i64>().is_ok()
+}
+
+/// Annotate the maps whose keys were all integers with `x-key-type`.
+pub(crate) fn apply_numeric_keys(schema: &mut Value, kinds: &MapKeyKinds) {
+    apply_node(schema, kinds, &[]);
+}
+
+fn apply_node(schema: &mut Value, kinds: &MapKeyKinds, path: &[String]) {
+    let obj = match schema {
+        Value::Object(obj) => obj,
+        Value::Array(items) => {
+            for item in items {
+                apply_node(item, kinds, path);
+            }
+            return;
+        }
+        _ => return,
+    };
+
+    let is_map = !obj.contains_key("properties")
+        && matches!(obj.get("additionalProperties"), Some(Value::Object(_)));
+    if is_map && numeric_keys(kinds, path) {
+        obj.insert(MAP_KEY_TYPE_KEY.to_string(), Value::from("integer"));
+    }
+
+    for (key, child) in obj.iter_mut() {
+        match key.as_str() {
+            "properties" => {
+                if let Value::Object(props) = child {
+                    for (name, prop) in props.iter_mut() {
+                        apply_node(prop, kinds, &child_path(path, name));
+                    }
+                }
+            }
+            "additionalProperties" => {
+                apply_node(child, kinds, &child_path(path, MAP_VALUES_SEGMENT))
+            }
+            "enum" | "const" | "default" | "examples" | "required" => {}
+            _ => apply_node(child, kinds, path),
+        }
+    }
+}
+
+/// Whether the objects at `path` had only integer keys, combining the keys of
+/// enclosing maps.
+fn numeric_keys(kinds: &MapKeyKinds, path: &[String]) -> bool {
+    let key = pointer(path);
+    if path.iter().any(|seg| seg == MAP_VALUES_SEGMENT) {
+        let mut matching = kinds
+            .iter()
+            .filter(|(seen, _)| pointer_matches(&key, seen))
+            .peekable();
+        matching.peek().is_some() && matching.all(|(_, numeric)| *numeric)
+    } else {
+        kinds.get(&key).copied().unwrap_or(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    include!("../tests/map_keys.rs");
+}
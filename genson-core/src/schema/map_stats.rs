@@ -0,0 +1,216 @@
+//! Key cardinality of inferred maps (`map_key_stats`).
+//!
+//! For every field inferred as a map, reports how many distinct keys were seen,
+//! the fewest and most keys a single object had, and the most frequent keys. A
+//! map with a handful of keys present in every object was probably a record, and
+//! the key counts decide between the map encodings.
+
+use crate::schema::field_path::{child_path, pointer, pointer_matches, MAP_VALUES_SEGMENT};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeMap, HashMap};
+
+/// Number of most frequent keys reported per map, unless configured otherwise.
+pub const DEFAULT_TOP_KEYS: usize = 10;
+
+/// The keys seen in the objects at one field path.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct KeyObservations {
+    /// Number of objects seen
+    objects: usize,
+    /// Fewest keys in one object
+    min_keys: usize,
+    /// Most keys in one object
+    max_keys: usize,
+    /// Number of objects that had each key
+    key_counts: HashMap<String, usize>,
+}
+
+impl KeyObservations {
+    fn observe(&mut self, keys: impl ExactSizeIterator<Item = String>) {
+        let n = keys.len();
+        self.min_keys = if self.objects == 0 {
+            n
+        } else {
+            self.min_keys.min(n)
+        };
+        self.max_keys = self.max_keys.max(n);
+        self.objects += 1;
+        for key in keys {
+            *self.key_counts.entry(key).or_default() += 1;
+        }
+    }
+
+    fn absorb(&mut self, other: KeyObservations) {
+        if other.objects == 0 {
+            return;
+        }
+        self.min_keys = if self.objects == 0 {
+            other.min_keys
+        } else {
+            self.min_keys.min(other.min_keys)
+        };
+        self.max_keys = self.max_keys.max(other.max_keys);
+        self.objects += other.objects;
+        for (key, count) in other.key_counts {
+            *self.key_counts.entry(key).or_default() += count;
+        }
+    }
+}
+
+/// Key observations keyed by field path (array items add no segment).
+pub(crate) type KeyCardinality = HashMap<String, KeyObservations>;
+
+/// Key cardinality of the objects at the path of one inferred map.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MapKeyStats {
+    /// JSON Pointer path of the map (map values add a `*` segment)
+    pub path: String,
+    /// Number of objects seen at the path
+    pub objects: usize,
+    /// Number of distinct keys across those objects
+    pub distinct_keys: usize,
+    /// Fewest keys in one object
+    pub min_keys: usize,
+    /// Most keys in one object
+    pub max_keys: usize,
+    /// The most frequent keys, most frequent first (ties by key)
+    pub top_keys: Vec<KeyFrequency>,
+}
+
+/// How many objects at a map's path had a key.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyFrequency {
+    pub key: String,
+    pub count: usize,
+}
+
+/// Add the observations of `other` to `cardinality`.
+pub(crate) fn merge_key_cardinality(
+    mut cardinality: KeyCardinality,
+    other: KeyCardinality,
+) -> KeyCardinality {
+    for (path, observations) in other {
+        match cardinality.entry(path) {
+            Entry::Vacant(entry) => {
+                entry.insert(observations);
+            }
+            Entry::Occupied(mut entry) => entry.get_mut().absorb(observations),
+        }
+    }
+    cardinality
+}
+
+/// Record the keys of an object at `path`.
+pub(crate) fn observe_object(
+    cardinality: &mut KeyCardinality,
+    path: String,
+    obj: &Map<String, Value>,
+) {
+    cardinality
+        .entry(path)
+        .or_default()
+        .observe(obj.keys().cloned());
+}
+
+/// The key cardinality of every map in `schema`, sorted by path, with the `top_k`
+/// most frequent keys of each.
+pub(crate) fn map_key_stats(
+    schema: &Value,
+    cardinality: &KeyCardinality,
+    top_k: usize,
+) -> Vec<MapKeyStats> {
+    let mut report = BTreeMap::new();
+    stats_node(schema, cardinality, top_k, &[], &mut report);
+    report.into_values().collect()
+}
+
+fn stats_node(
+    schema: &Value,
+    cardinality: &KeyCardinality,
+    top_k: usize,
+    path: &[String],
+    report: &mut BTreeMap<String, MapKeyStats>,
+) {
+    let obj = match schema {
+        Value::Object(obj) => obj,
+        Value::Array(items) => {
+            for item in items {
+                stats_node(item, cardinality, top_k, path, report);
+            }
+            return;
+        }
+        _ => return,
+    };
+
+    let is_map = !obj.contains_key("properties")
+        && matches!(obj.get("additionalProperties"), Some(Value::Object(_)));
+    if is_map {
+        let key = pointer(path);
+        let observations = observations_at(cardinality, path);
+        if observations.objects > 0 && !report.contains_key(&key) {
+            report.insert(key.clone(), stats(key, observations, top_k));
+        }
+    }
+
+    for (key, child) in obj {
+        match key.as_str() {
+            "properties" => {
+                if let Value::Object(props) = child {
+                    for (name, prop) in props {
+                        stats_node(prop, cardinality, top_k, &child_path(path, name), report);
+                    }
+                }
+            }
+            "additionalProperties" => stats_node(
+                child,
+                cardinality,
+                top_k,
+                &child_path(path, MAP_VALUES_SEGMENT),
+                report,
+            ),
+            "enum" | "const" | "default" | "examples" | "required" => {}
+            _ => stats_node(child, cardinality, top_k, path, report),
+        }
+    }
+}
+
+/// The observations of the objects at `path`, combining the keys of enclosing maps.
+fn observations_at(cardinality: &KeyCardinality, path: &[String]) -> KeyObservations {
+    let key = pointer(path);
+    let mut combined = KeyObservations::default();
+    if path.iter().any(|seg| seg == MAP_VALUES_SEGMENT) {
+        for (seen, observations) in cardinality {
+            if pointer_matches(&key, seen) {
+                combined.absorb(observations.clone());
+            }
+        }
+    } else if let Some(observations) = cardinality.get(&key) {
+        combined.absorb(observations.clone());
+    }
+    combined
+}
+
+fn stats(path: String, observations: KeyObservations, top_k: usize) -> MapKeyStats {
+    let mut keys: Vec<(String, usize)> = observations.key_counts.into_iter().collect();
+    let distinct_keys = keys.len();
+    keys.sort_by(|(a, a_count), (b, b_count)| b_count.cmp(a_count).then_with(|| a.cmp(b)));
+    MapKeyStats {
+        path,
+        objects: observations.objects,
+        distinct_keys,
+        min_keys: observations.min_keys,
+        max_keys: observations.max_keys,
+        top_keys: keys
+            .into_iter()
+            .take(top_k)
+            .map(|(key, count)| KeyFrequency { key, count })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    include!("../tests/map_stats.rs");
+}
@@ -0,0 +1,159 @@
+//! Presence-based required fields (`required_threshold`).
+//!
+//! Genson only marks a field required if every object at its path has it, so a
+//! handful of malformed rows make a field optional for the whole dataset. With a
+//! threshold, fields present in at least that fraction of the objects at their
+//! path stay required, and the ratio of every field is reported.
+
+use crate::schema::field_order::{frequency, FieldCounts};
+use crate::schema::field_path::{child_path, pointer, MAP_VALUES_SEGMENT};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// How often a field was present in the objects at its parent path.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldPresence {
+    /// JSON Pointer path of the field (map values add a `*` segment)
+    pub path: String,
+    /// Number of objects at the parent path that had the field
+    pub present: usize,
+    /// Number of objects at the parent path
+    pub total: usize,
+    /// `present / total`
+    pub ratio: f64,
+    /// Whether the field is required in the output schema
+    pub required: bool,
+}
+
+/// Check that a `required_threshold` is a ratio.
+pub(crate) fn validate_threshold(threshold: f64) -> Result<(), String> {
+    if (0.0..=1.0).contains(&threshold) {
+        Ok(())
+    } else {
+        Err(format!(
+            "Invalid required_threshold: {} (expected a ratio between 0 and 1)",
+            threshold
+        ))
+    }
+}
+
+/// Make the fields present in at least `threshold` of the objects at their path
+/// required, returning the presence of every field, sorted by path.
+pub(crate) fn apply_required_threshold(
+    schema: &mut Value,
+    threshold: f64,
+    field_counts: &FieldCounts,
+    object_counts: &FieldCounts,
+) -> Vec<FieldPresence> {
+    let mut report = BTreeMap::new();
+    let counts = PresenceCounts {
+        fields: field_counts,
+        objects: object_counts,
+    };
+    apply_node(schema, threshold, &counts, &[], &mut report);
+    report.into_values().collect()
+}
+
+/// Field and object counts by path, as collected for `required_threshold`.
+pub(crate) struct PresenceCounts<'a> {
+    pub(crate) fields: &'a FieldCounts,
+    pub(crate) objects: &'a FieldCounts,
+}
+
+impl PresenceCounts<'_> {
+    /// Number of objects seen at `path`, and the mean share of them that had each
+    /// of `keys`. None if no object was seen there (e.g. a wrapped scalar).
+    pub(crate) fn key_presence<'k>(
+        &self,
+        path: &[String],
+        keys: impl Iterator<Item = &'k String>,
+    ) -> Option<(usize, f64)> {
+        let total = frequency(self.objects, path);
+        let (mut key_count, mut present) = (0, 0);
+        for key in keys {
+            key_count += 1;
+            present += frequency(self.fields, &child_path(path, key));
+        }
+        (total > 0 && key_count > 0).then(|| (total, present as f64 / (key_count * total) as f64))
+    }
+}
+
+fn apply_node(
+    schema: &mut Value,
+    threshold: f64,
+    counts: &PresenceCounts,
+    path: &[String],
+    report: &mut BTreeMap<String, FieldPresence>,
+) {
+    let obj = match schema {
+        Value::Object(obj) => obj,
+        Value::Array(items) => {
+            for item in items {
+                apply_node(item, threshold, counts, path, report);
+            }
+            return;
+        }
+        _ => return,
+    };
+
+    if let Some(Value::Object(props)) = obj.get("properties") {
+        let total = frequency(counts.objects, path);
+        let mut required: Vec<Value> = match obj.get("required") {
+            Some(Value::Array(required)) => required.clone(),
+            _ => Vec::new(),
+        };
+        // Synthetic fields (e.g. wrapped scalars) were never seen as objects
+        if total > 0 {
+            for name in props.keys() {
+                let field_path = child_path(path, name);
+                let present = frequency(counts.fields, &field_path);
+                let ratio = present as f64 / total as f64;
+                let was_required = required.iter().any(|r| r == name);
+                if !was_required && ratio >= threshold {
+                    required.push(Value::String(name.clone()));
+                }
+                report.insert(
+                    pointer(&field_path),
+                    FieldPresence {
+                        path: pointer(&field_path),
+                        present,
+                        total,
+                        ratio,
+                        required: was_required || ratio >= threshold,
+                    },
+                );
+            }
+        }
+        if !required.is_empty() {
+            obj.insert("required".to_string(), Value::Array(required));
+        }
+    }
+
+    for (key, child) in obj.iter_mut() {
+        match key.as_str() {
+            "properties" => {
+                if let Value::Object(props) = child {
+                    for (name, prop) in props.iter_mut() {
+                        apply_node(prop, threshold, counts, &child_path(path, name), report);
+                    }
+                }
+            }
+            "additionalProperties" => apply_node(
+                child,
+                threshold,
+                counts,
+                &child_path(path, MAP_VALUES_SEGMENT),
+                report,
+            ),
+            // Instance values, not schemas
+            "enum" | "const" | "default" | "examples" | "required" => {}
+            _ => apply_node(child, threshold, counts, path, report),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    include!("../tests/presence.rs");
+}
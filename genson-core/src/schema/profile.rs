@@ -0,0 +1,104 @@
+//! Structured timings for a schema inference run.
+
+use crate::time::{Duration, Instant};
+use serde::{Deserialize, Serialize};
+
+/// Per-stage timings of a schema inference run, in milliseconds.
+///
+/// Parse and build times are summed over documents, so with parallel processing
+/// they measure CPU time across threads and may exceed `total_ms`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct InferenceProfile {
+    /// Number of documents timed
+    pub documents: usize,
+    /// Preparing the input: validation, root wrapping, NDJSON splitting
+    pub parse_ms: f64,
+    /// Building the schema with genson, including merging per-document schemas
+    pub build_ms: f64,
+    /// Map/record inference, unification and scalar wrapping
+    pub rewrite_objects_ms: f64,
+    /// Conversion of the final schema to Avro (zero unless `avro` is set)
+    pub avro_ms: f64,
+    /// Wall-clock time for the whole run
+    pub total_ms: f64,
+    /// Median parse + build time of a single document
+    pub document_p50_ms: f64,
+    /// 99th percentile parse + build time of a single document
+    pub document_p99_ms: f64,
+}
+
+/// Collects the timings behind an [`InferenceProfile`].
+pub(crate) struct ProfileRecorder {
+    started: Instant,
+    parse: Duration,
+    build: Duration,
+    rewrite_objects: Duration,
+    avro: Duration,
+    documents: Vec<Duration>,
+}
+
+impl ProfileRecorder {
+    pub(crate) fn new() -> Self {
+        Self {
+            started: Instant::now(),
+            parse: Duration::ZERO,
+            build: Duration::ZERO,
+            rewrite_objects: Duration::ZERO,
+            avro: Duration::ZERO,
+            documents: Vec::new(),
+        }
+    }
+
+    /// Record one document's preparation and build times.
+    pub(crate) fn document(&mut self, parse: Duration, build: Duration) {
+        self.parse += parse;
+        self.build += build;
+        self.documents.push(parse + build);
+    }
+
+    /// Add build time not attributable to a single document (merging builders).
+    pub(crate) fn merge(&mut self, elapsed: Duration) {
+        self.build += elapsed;
+    }
+
+    pub(crate) fn rewrite_objects(&mut self, elapsed: Duration) {
+        self.rewrite_objects += elapsed;
+    }
+
+    #[cfg(feature = "avro")]
+    pub(crate) fn avro(&mut self, elapsed: Duration) {
+        self.avro += elapsed;
+    }
+
+    pub(crate) fn finish(mut self) -> InferenceProfile {
+        self.documents.sort_unstable();
+        InferenceProfile {
+            documents: self.documents.len(),
+            parse_ms: ms(self.parse),
+            build_ms: ms(self.build),
+            rewrite_objects_ms: ms(self.rewrite_objects),
+            avro_ms: ms(self.avro),
+            total_ms: ms(self.started.elapsed()),
+            document_p50_ms: percentile(&self.documents, 50),
+            document_p99_ms: percentile(&self.documents, 99),
+        }
+    }
+}
+
+fn ms(d: Duration) -> f64 {
+    d.as_secs_f64() * 1000.0
+}
+
+/// Nearest-rank percentile of sorted durations, 0 when there are none.
+fn percentile(sorted: &[Duration], pct: usize) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let rank = (pct * sorted.len()).div_ceil(100).max(1);
+    ms(sorted[rank - 1])
+}
+
+#[cfg(test)]
+mod tests {
+    include!("../tests/profile.rs");
+}
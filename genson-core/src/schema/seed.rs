@@ -0,0 +1,149 @@
+//! Seed schemas (`seed_schema`): inference that extends an existing schema.
+//!
+//! The seed is merged into the genson builder before any document, so the
+//! documents can only widen it: new fields and types are added, fields missing
+//! from some documents become optional, and the seed's keywords (descriptions,
+//! formats and so on) are kept. Map detection then leaves the seed's own maps and
+//! records as they are, rather than deciding them again from the new data.
+
+use crate::genson_rs::get_builder;
+use crate::schema::core::CONFIG_SNAPSHOT_KEY;
+use crate::schema::field_path::{child_path, pointer, MAP_VALUES_SEGMENT};
+use serde_json::Value;
+use std::collections::HashSet;
+
+/// Check that a seed is a JSON Schema object (not Avro, which has `fields`).
+pub(crate) fn validate_seed(seed: &Value) -> Result<(), String> {
+    match seed {
+        Value::Object(obj) if obj.contains_key("fields") => {
+            Err("Invalid seed_schema: expected a JSON Schema, not an Avro schema".to_string())
+        }
+        Value::Object(_) => Ok(()),
+        _ => Err("Invalid seed_schema: expected a JSON Schema object".to_string()),
+    }
+}
+
+/// The seed as the builder should see it: without the keywords genson adds itself.
+pub(crate) fn builder_seed(seed: &Value) -> Value {
+    let mut seed = seed.clone();
+    if let Value::Object(obj) = &mut seed {
+        obj.remove("$schema");
+        obj.remove(CONFIG_SNAPSHOT_KEY);
+    }
+    seed
+}
+
+/// Merge the fields the documents added under the seed's maps into the map values.
+///
+/// The builder keeps the seed's `additionalProperties` but records the keys seen in
+/// the documents as `properties`, which would otherwise be taken for a record.
+pub(crate) fn fold_seed_maps(schema: &mut Value, seed: &Value) {
+    let Some(seed) = object_node(seed) else {
+        return;
+    };
+    let Some(schema) = matching_node_mut(schema, seed) else {
+        return;
+    };
+
+    let seed_values = match seed.get("additionalProperties") {
+        Some(values @ Value::Object(_)) if seed.get("properties").is_none() => Some(values),
+        _ => None,
+    };
+    if let Some(seed_values) = seed_values {
+        if let Some(Value::Object(props)) = schema.remove("properties") {
+            schema.remove("required");
+            let mut builder = get_builder(None);
+            if let Some(values @ Value::Object(_)) = schema.get("additionalProperties") {
+                builder.add_schema(values.clone());
+            }
+            for prop in props.into_values().filter(Value::is_object) {
+                builder.add_schema(prop);
+            }
+            schema.insert("additionalProperties".to_string(), builder.to_schema());
+        }
+        if let Some(values) = schema.get_mut("additionalProperties") {
+            fold_seed_maps(values, seed_values);
+        }
+        return;
+    }
+
+    if let (Some(Value::Object(props)), Some(Value::Object(seed_props))) =
+        (schema.get_mut("properties"), seed.get("properties"))
+    {
+        for (name, prop) in props.iter_mut() {
+            if let Some(seed_prop) = seed_props.get(name) {
+                fold_seed_maps(prop, seed_prop);
+            }
+        }
+    }
+    if let (Some(items), Some(seed_items)) = (schema.get_mut("items"), seed.get("items")) {
+        fold_seed_maps(items, seed_items);
+    }
+}
+
+/// JSON Pointer paths of the seed's records, which map detection keeps as records
+/// (array items add no segment, map values add `*`).
+pub(crate) fn seed_records(seed: &Value) -> HashSet<String> {
+    let mut records = HashSet::new();
+    collect_records(seed, &[], &mut records);
+    records
+}
+
+fn collect_records(seed: &Value, path: &[String], records: &mut HashSet<String>) {
+    let Some(seed) = object_node(seed) else {
+        return;
+    };
+    if let Some(Value::Object(props)) = seed.get("properties") {
+        records.insert(pointer(path));
+        for (name, prop) in props {
+            collect_records(prop, &child_path(path, name), records);
+        }
+    }
+    if let Some(values) = seed.get("additionalProperties") {
+        collect_records(values, &child_path(path, MAP_VALUES_SEGMENT), records);
+    }
+    if let Some(items) = seed.get("items") {
+        collect_records(items, path, records);
+    }
+}
+
+/// The schema itself, or the first `anyOf` branch that isn't `null`, where an
+/// object or array nullable in the seed or the data is found.
+fn object_node(schema: &Value) -> Option<&serde_json::Map<String, Value>> {
+    let obj = schema.as_object()?;
+    match obj.get("anyOf") {
+        Some(Value::Array(branches)) => branches
+            .iter()
+            .filter_map(Value::as_object)
+            .find(|branch| branch.get("type").is_some_and(|t| t != "null")),
+        _ => Some(obj),
+    }
+}
+
+/// Like [`object_node`], preferring the `anyOf` branch of the same type as `seed`.
+fn matching_node_mut<'a>(
+    schema: &'a mut Value,
+    seed: &serde_json::Map<String, Value>,
+) -> Option<&'a mut serde_json::Map<String, Value>> {
+    let obj = schema.as_object_mut()?;
+    if !matches!(obj.get("anyOf"), Some(Value::Array(_))) {
+        return Some(obj);
+    }
+    let Some(Value::Array(branches)) = obj.get_mut("anyOf") else {
+        return None;
+    };
+    let idx = branches
+        .iter()
+        .position(|branch| branch.get("type") == seed.get("type"))
+        .or_else(|| {
+            branches
+                .iter()
+                .position(|branch| branch.get("type").is_some_and(|t| t != "null"))
+        })?;
+    branches[idx].as_object_mut()
+}
+
+#[cfg(test)]
+mod tests {
+    include!("../tests/seed.rs");
+}
@@ -0,0 +1,50 @@
+//! Pruning documents to a whitelist of fields (`select_paths`).
+//!
+//! Consumers often want a handful of fields out of hundreds. Rather than infer the
+//! whole schema and cut it down afterwards, documents are pruned before inference
+//! (and rows before normalisation), so the fields left out never reach the schema.
+
+use crate::schema::field_path::{key_matches, leads_to};
+use serde_json::Value;
+
+/// Keep only the fields of `document` selected by `paths` (bare field names or path
+/// patterns, see `force_field_types`), with their whole subtrees, and the fields
+/// leading to them. Nothing is pruned when `paths` is empty.
+pub(crate) fn select_fields(document: &mut Value, paths: &[String]) {
+    if !paths.is_empty() {
+        select_within(document, paths, &mut Vec::new());
+    }
+}
+
+/// Prune `value`, found at `path`, returning whether it holds any field that was kept.
+fn select_within(value: &mut Value, paths: &[String], path: &mut Vec<String>) -> bool {
+    match value {
+        Value::Object(obj) => {
+            let mut kept = false;
+            obj.retain(|key, child| {
+                path.push(key.clone());
+                let keep = paths.iter().any(|p| key_matches(p, Some(key), path))
+                    || select_within(child, paths, path)
+                    || paths.iter().any(|p| leads_to(p, path));
+                path.pop();
+                kept |= keep;
+                keep
+            });
+            kept
+        }
+        // Array items add no segment. Every item is pruned, so no short-circuiting
+        Value::Array(items) => {
+            let mut kept = false;
+            for item in items {
+                kept |= select_within(item, paths, path);
+            }
+            kept
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    include!("../tests/select.rs");
+}
@@ -0,0 +1,177 @@
+//! Resumable schema inference.
+//!
+//! A session keeps the raw merged schema (genson's builder output, before map
+//! detection and the other rewrites) with a document count. It can be saved to
+//! disk and resumed later, so a long-lived schema is updated with each new batch
+//! of input rather than re-inferred from scratch.
+
+use super::document_stats::DocumentStats;
+use super::{
+    build_raw_schema, finalise_schema, RawSchema, SchemaInferenceConfig, SchemaInferenceResult,
+};
+use crate::genson_rs::get_builder;
+use crate::limits::LimitExceeded;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+
+/// Version of the saved session format, bumped on incompatible changes.
+const SESSION_FORMAT_VERSION: u32 = 1;
+
+/// Schema inference that can be continued over new input, and saved in between.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SchemaInferenceSession {
+    format_version: u32,
+    config: SchemaInferenceConfig,
+    /// Merged schema of every document so far, `None` before the first
+    raw_schema: Option<Value>,
+    processed_count: usize,
+    limit_reached: Option<LimitExceeded>,
+    /// Statistics of the documents for the rewrites, each empty unless the config
+    /// asks for it
+    #[serde(flatten)]
+    stats: DocumentStats,
+}
+
+impl SchemaInferenceSession {
+    pub fn new(config: SchemaInferenceConfig) -> Self {
+        Self {
+            format_version: SESSION_FORMAT_VERSION,
+            config,
+            raw_schema: None,
+            processed_count: 0,
+            limit_reached: None,
+            stats: DocumentStats::default(),
+        }
+    }
+
+    /// The config documents are added and the schema is finalised with.
+    pub fn config(&self) -> &SchemaInferenceConfig {
+        &self.config
+    }
+
+    /// Number of documents added over the life of the session.
+    pub fn processed_count(&self) -> usize {
+        self.processed_count
+    }
+
+    /// Add documents to the session, returning how many were processed.
+    ///
+    /// Sampling and resource limits in the config apply to each call separately.
+    pub fn add_strings<S: AsRef<str>>(&mut self, json_strings: &[S]) -> Result<usize, String> {
+        if json_strings.is_empty() {
+            return Ok(0);
+        }
+        let json_strings: Vec<&str> = json_strings.iter().map(AsRef::as_ref).collect();
+        let raw = build_raw_schema(&json_strings, &self.config, None)?;
+        let processed_count = raw.processed_count;
+        self.merge_raw(raw)?;
+        Ok(processed_count)
+    }
+
+    /// Add the documents of another session, as if they had been added to this one.
+    ///
+    /// Lets input be split across sessions (e.g. one per file, on separate threads)
+    /// and combined afterwards. Both sessions must use the same config.
+    pub fn merge(&mut self, other: SchemaInferenceSession) -> Result<(), String> {
+        if serde_json::to_value(&self.config).ok() != serde_json::to_value(&other.config).ok() {
+            return Err("Cannot merge sessions with different configs".to_string());
+        }
+        match other.raw_schema {
+            Some(schema) => self.merge_raw(RawSchema {
+                schema,
+                processed_count: other.processed_count,
+                limit_reached: other.limit_reached,
+                stats: other.stats,
+            }),
+            None => Ok(()),
+        }
+    }
+
+    fn merge_raw(&mut self, raw: RawSchema) -> Result<(), String> {
+        let RawSchema {
+            schema: raw_schema,
+            processed_count,
+            limit_reached,
+            stats,
+        } = raw;
+        let existing = self.raw_schema.clone();
+        let schema_uri = self.config.schema_uri.clone();
+        let merged = panic::catch_unwind(AssertUnwindSafe(move || {
+            let mut builder = get_builder(schema_uri.as_deref());
+            if let Some(existing) = existing {
+                builder.add_schema(existing);
+            }
+            builder.add_schema(raw_schema);
+            builder.to_schema()
+        }))
+        .map_err(|_| "Failed to merge documents into the session schema".to_string())?;
+
+        self.raw_schema = Some(merged);
+        self.processed_count += processed_count;
+        self.limit_reached = self.limit_reached.or(limit_reached);
+        self.stats = std::mem::take(&mut self.stats).merge(stats);
+        Ok(())
+    }
+
+    /// The schema inferred from every document added so far.
+    ///
+    /// The session is left as it was, so more documents can be added afterwards.
+    pub fn result(&self) -> Result<SchemaInferenceResult, String> {
+        let raw_schema = self
+            .raw_schema
+            .clone()
+            .ok_or("No documents have been added to the session")?;
+        finalise_schema(
+            RawSchema {
+                schema: raw_schema,
+                processed_count: self.processed_count,
+                limit_reached: self.limit_reached,
+                stats: self.stats.clone(),
+            },
+            &self.config,
+            None,
+        )
+    }
+
+    /// Write the session to `path` as JSON.
+    ///
+    /// The file is written alongside and renamed into place, so an interrupted save
+    /// leaves any previous session file intact.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), String> {
+        let path = path.as_ref();
+        let json =
+            serde_json::to_vec(self).map_err(|e| format!("Failed to serialise session: {}", e))?;
+        let mut tmp_name = path.file_name().unwrap_or_default().to_os_string();
+        tmp_name.push(".tmp");
+        let tmp_path = path.with_file_name(tmp_name);
+        fs::write(&tmp_path, json)
+            .and_then(|()| fs::rename(&tmp_path, path))
+            .map_err(|e| format!("Failed to save session to {}: {}", path.display(), e))
+    }
+
+    /// Load a session saved with [`SchemaInferenceSession::save`].
+    pub fn resume(path: impl AsRef<Path>) -> Result<Self, String> {
+        let path = path.as_ref();
+        let json = fs::read(path)
+            .map_err(|e| format!("Failed to read session {}: {}", path.display(), e))?;
+        let session: Self = serde_json::from_slice(&json)
+            .map_err(|e| format!("Invalid session file {}: {}", path.display(), e))?;
+        if session.format_version != SESSION_FORMAT_VERSION {
+            return Err(format!(
+                "Session file {} has format version {}, expected {}",
+                path.display(),
+                session.format_version,
+                SESSION_FORMAT_VERSION
+            ));
+        }
+        Ok(session)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    include!("../tests/session.rs");
+}
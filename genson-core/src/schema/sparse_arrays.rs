@@ -0,0 +1,161 @@
+//! Sparse array detection (`sparse_array_detection`).
+//!
+//! PHP and some other exporters write lists as objects keyed by position
+//! (`{"0": "a", "1": "b"}`), which genson infers as a record or a map. With
+//! detection on, the objects at a path whose keys were `"0"` to `"n-1"` in every
+//! document are inferred as an array of their values, and normalisation unpacks
+//! them into arrays in key order.
+
+use crate::genson_rs::get_builder;
+use crate::schema::field_path::{
+    child_path, pointer, pointer_matches, ITEMS_SEGMENT, MAP_VALUES_SEGMENT,
+};
+use serde_json::{Map, Value};
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+
+/// Whether every object at each path was keyed `"0"` to `"n-1"`, keyed by field
+/// path. Paths with only empty objects are absent. Array items add an
+/// [`ITEMS_SEGMENT`], so a field seen both as an array of objects and as an
+/// object keyed by position is told apart from its items.
+pub(crate) type SparseArrays = HashMap<String, bool>;
+
+/// Add the observations of `other` to `sparse`.
+pub(crate) fn merge_sparse_arrays(mut sparse: SparseArrays, other: SparseArrays) -> SparseArrays {
+    for (path, positional) in other {
+        match sparse.entry(path) {
+            Entry::Vacant(entry) => {
+                entry.insert(positional);
+            }
+            Entry::Occupied(mut entry) => *entry.get_mut() &= positional,
+        }
+    }
+    sparse
+}
+
+/// Record whether an object at `path` is keyed by position.
+pub(crate) fn observe_object(sparse: &mut SparseArrays, path: String, obj: &Map<String, Value>) {
+    if !obj.is_empty() {
+        *sparse.entry(path).or_insert(true) &= is_sparse_array(obj);
+    }
+}
+
+/// Whether the keys of `obj` are exactly `"0"` to `"n-1"`, in any order.
+pub(crate) fn is_sparse_array(obj: &Map<String, Value>) -> bool {
+    !obj.is_empty() && (0..obj.len()).all(|i| obj.contains_key(&i.to_string()))
+}
+
+/// The values of a sparse array object in key order.
+#[cfg(feature = "avro")]
+pub(crate) fn unpack_sparse_array(mut obj: Map<String, Value>) -> Vec<Value> {
+    (0..obj.len())
+        .filter_map(|i| obj.remove(&i.to_string()))
+        .collect()
+}
+
+/// Turn the object schemas at the paths whose objects were all keyed by position
+/// into arrays of the union of their property (or map value) schemas. Where an
+/// `anyOf` is left with several array branches, they're merged into one.
+pub(crate) fn apply_sparse_arrays(schema: &mut Value, sparse: &SparseArrays) {
+    apply_node(schema, sparse, &[]);
+}
+
+fn apply_node(schema: &mut Value, sparse: &SparseArrays, path: &[String]) {
+    let obj = match schema {
+        Value::Object(obj) => obj,
+        Value::Array(items) => {
+            for item in items {
+                apply_node(item, sparse, path);
+            }
+            return;
+        }
+        _ => return,
+    };
+
+    // Nested sparse arrays are converted first, so their union is the items' type
+    for (key, child) in obj.iter_mut() {
+        match key.as_str() {
+            "properties" => {
+                if let Value::Object(props) = child {
+                    for (name, prop) in props.iter_mut() {
+                        apply_node(prop, sparse, &child_path(path, name));
+                    }
+                }
+            }
+            "additionalProperties" => {
+                apply_node(child, sparse, &child_path(path, MAP_VALUES_SEGMENT))
+            }
+            "items" => apply_node(child, sparse, &child_path(path, ITEMS_SEGMENT)),
+            "enum" | "const" | "default" | "examples" | "required" => {}
+            _ => apply_node(child, sparse, path),
+        }
+    }
+
+    let is_object = obj.get("type").and_then(Value::as_str) == Some("object");
+    if is_object && positional(sparse, path) {
+        let mut builder = get_builder(None);
+        if let Some(Value::Object(props)) = obj.get("properties") {
+            for prop in props.values() {
+                builder.add_schema(prop.clone());
+            }
+        }
+        if let Some(values @ Value::Object(_)) = obj.get("additionalProperties") {
+            builder.add_schema(values.clone());
+        }
+        obj.clear();
+        obj.insert("type".to_string(), Value::from("array"));
+        obj.insert("items".to_string(), builder.to_schema());
+    }
+    merge_array_branches(obj);
+}
+
+/// Merge the array branches of an `anyOf`, unwrapping it if one branch is left.
+fn merge_array_branches(obj: &mut Map<String, Value>) {
+    let Some(Value::Array(branches)) = obj.get_mut("anyOf") else {
+        return;
+    };
+    let is_array = |b: &Value| b.get("type").and_then(Value::as_str) == Some("array");
+    if branches.iter().filter(|b| is_array(b)).count() < 2 {
+        return;
+    }
+    let mut builder = get_builder(None);
+    let first = branches.iter().position(is_array).expect("an array branch");
+    let mut i = 0;
+    branches.retain(|branch| {
+        let keep = i == first || !is_array(branch);
+        if is_array(branch) {
+            builder.add_schema(branch.clone());
+        }
+        i += 1;
+        keep
+    });
+    branches[first] = builder.to_schema();
+
+    if branches.len() == 1 {
+        let Some(Value::Object(only)) = branches.pop() else {
+            return;
+        };
+        obj.remove("anyOf");
+        obj.extend(only);
+    }
+}
+
+/// Whether the objects at `path` were all keyed by position, combining the keys
+/// of enclosing maps.
+fn positional(sparse: &SparseArrays, path: &[String]) -> bool {
+    let key = pointer(path);
+    if path.iter().any(|seg| seg == MAP_VALUES_SEGMENT) {
+        let mut matching = sparse
+            .iter()
+            .filter(|(seen, _)| pointer_matches(&key, seen))
+            .peekable();
+        matching.peek().is_some() && matching.all(|(_, positional)| *positional)
+    } else {
+        sparse.get(&key).copied().unwrap_or(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    include!("../tests/sparse_arrays.rs");
+}
@@ -0,0 +1,138 @@
+//! Strict mode: fail inference on schema smells, for CI gates on a schema contract.
+//!
+//! Nullability is expected, but a field that is sometimes a number and sometimes a
+//! string, an object that was a map in some documents and a record in others, or a
+//! scalar promoted into a record (`wrap_scalars`) usually means the data (or the
+//! settings) need a look before the schema is checked in.
+
+use crate::schema::core::SchemaInferenceConfig;
+use crate::schema::explain::{FieldDecision, FieldExplanation};
+use crate::schema::field_path::{child_path, pointer, MAP_VALUES_SEGMENT};
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Scalar types that `wrap_scalars` names promoted fields after.
+const PROMOTED_TYPES: [&str; 4] = ["string", "integer", "number", "boolean"];
+
+/// Every strict-mode issue in the (JSON Schema) `schema`, as `path: problem` lines
+/// sorted by path. `explanations` are the map/record decisions made on the way.
+pub(crate) fn strict_issues(
+    schema: &Value,
+    explanations: &[FieldExplanation],
+    config: &SchemaInferenceConfig,
+) -> Vec<String> {
+    let mut issues = BTreeSet::new();
+    collect_issues(schema, &[], config, &mut issues);
+
+    let mut decisions: BTreeMap<&str, BTreeSet<&str>> = BTreeMap::new();
+    for explanation in explanations {
+        let kind = match explanation.decision {
+            FieldDecision::Map => "map",
+            FieldDecision::Record => "record",
+            _ => continue,
+        };
+        decisions.entry(&explanation.path).or_default().insert(kind);
+    }
+    for (path, kinds) in decisions {
+        if kinds.len() > 1 {
+            issues.insert((display(path), "both a map and a record".to_string()));
+        }
+    }
+
+    issues
+        .into_iter()
+        .map(|(path, problem)| format!("{}: {}", path, problem))
+        .collect()
+}
+
+/// The error listing `issues`.
+pub(crate) fn strict_error(issues: &[String]) -> String {
+    format!(
+        "Strict mode: {} schema issue(s)\n  {}",
+        issues.len(),
+        issues.join("\n  ")
+    )
+}
+
+fn collect_issues(
+    schema: &Value,
+    path: &[String],
+    config: &SchemaInferenceConfig,
+    issues: &mut BTreeSet<(String, String)>,
+) {
+    let Value::Object(obj) = schema else {
+        return;
+    };
+    let mut branches: Vec<String> = Vec::new();
+    if let Some(Value::Array(types)) = obj.get("type") {
+        branches.extend(types.iter().filter_map(Value::as_str).map(str::to_string));
+    }
+    for key in ["anyOf", "oneOf"] {
+        if let Some(Value::Array(alternatives)) = obj.get(key) {
+            branches.extend(alternatives.iter().map(branch_name));
+        }
+    }
+    branches.retain(|b| b != "null");
+    if branches.len() > 1 {
+        let problem = format!("union of {}", branches.join(" and "));
+        issues.insert((display(&pointer(path)), problem));
+    }
+
+    if let Some(Value::Object(props)) = obj.get("properties") {
+        let field = path.last();
+        for (name, child) in props {
+            let promoted = field.is_some_and(|field| {
+                PROMOTED_TYPES
+                    .iter()
+                    .any(|t| name.strip_prefix(field.as_str()) == Some(&format!("__{}", t)))
+            });
+            let forced = config.is_force_scalar_promotion(field.map(String::as_str), path);
+            if promoted && !forced {
+                let problem = format!("scalar promoted to field {}", name);
+                issues.insert((display(&pointer(path)), problem));
+            }
+            collect_issues(child, &child_path(path, name), config, issues);
+        }
+    }
+    if let Some(values) = obj.get("additionalProperties") {
+        collect_issues(
+            values,
+            &child_path(path, MAP_VALUES_SEGMENT),
+            config,
+            issues,
+        );
+    }
+    if let Some(items) = obj.get("items") {
+        collect_issues(items, path, config, issues);
+    }
+    for key in ["prefixItems", "anyOf", "oneOf"] {
+        if let Some(Value::Array(children)) = obj.get(key) {
+            for child in children {
+                collect_issues(child, path, config, issues);
+            }
+        }
+    }
+}
+
+/// A union branch in words: its type, or `object` for one without.
+fn branch_name(branch: &Value) -> String {
+    match branch.get("type") {
+        Some(Value::String(t)) => t.clone(),
+        Some(other) => other.to_string(),
+        None => "object".to_string(),
+    }
+}
+
+/// The root's pointer is empty, which reads badly in a list.
+fn display(pointer: &str) -> String {
+    if pointer.is_empty() {
+        "/".to_string()
+    } else {
+        pointer.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    include!("../tests/strict.rs");
+}
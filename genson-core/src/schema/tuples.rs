@@ -0,0 +1,256 @@
+//! Tuple detection for positional arrays (`detect_tuples`).
+//!
+//! Genson merges every array item into one `items` schema, so arrays of
+//! `[string, object]` pairs become lists of a string/object union. Tuple
+//! detection records the length of the arrays at each path and the schema of the
+//! values at each position. Where every array had the same length and the
+//! positions differ, the array keeps a schema per position: `prefixItems` in
+//! JSON Schema, or a record of positional fields `_0`, `_1`, ... in Avro.
+
+use crate::genson_rs::get_builder;
+use crate::schema::field_path::{child_path, pointer, ITEMS_SEGMENT};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+
+/// Longest array considered for a tuple: longer arrays are lists.
+pub const MAX_TUPLE_LENGTH: usize = 16;
+
+/// Prefix of the positional field names of a tuple record in Avro.
+#[cfg(feature = "avro")]
+pub(crate) const TUPLE_FIELD_PREFIX: &str = "_";
+
+/// The arrays seen at one path.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct TupleShape {
+    /// Length shared by every array, `None` once two differed (or one was too
+    /// short or too long to be a tuple)
+    length: Option<usize>,
+    /// Merged schema of the values seen at each position
+    positions: Vec<Value>,
+}
+
+/// Tuple shapes keyed by array path (array items add an [`ITEMS_SEGMENT`], so an
+/// array is told apart from the arrays nested in it).
+pub(crate) type TupleShapes = HashMap<String, TupleShape>;
+
+/// The values at each position of the arrays seen at each path, or `None` for a
+/// path whose arrays can't be tuples.
+pub(crate) type TupleValues<'a> = HashMap<String, Option<Vec<Vec<&'a Value>>>>;
+
+impl TupleShape {
+    fn not_a_tuple() -> Self {
+        Self {
+            length: None,
+            positions: Vec::new(),
+        }
+    }
+
+    fn merge(&mut self, other: TupleShape) {
+        if self.length.is_none() || self.length != other.length {
+            *self = Self::not_a_tuple();
+            return;
+        }
+        for (mine, theirs) in self.positions.iter_mut().zip(other.positions) {
+            let mut builder = get_builder(None);
+            builder.add_schema(std::mem::take(mine));
+            builder.add_schema(theirs);
+            *mine = builder.to_schema();
+        }
+    }
+
+    /// The per-position schemas, if the arrays are tuples rather than lists.
+    fn tuple_items(&self) -> Option<&[Value]> {
+        let distinct = self.positions.windows(2).any(|pair| pair[0] != pair[1]);
+        (self.length.is_some() && distinct).then_some(self.positions.as_slice())
+    }
+}
+
+/// Add the shapes of `other` to `shapes`.
+pub(crate) fn merge_tuple_shapes(mut shapes: TupleShapes, other: TupleShapes) -> TupleShapes {
+    for (path, shape) in other {
+        match shapes.entry(path) {
+            Entry::Vacant(entry) => {
+                entry.insert(shape);
+            }
+            Entry::Occupied(mut entry) => entry.get_mut().merge(shape),
+        }
+    }
+    shapes
+}
+
+/// Record the items of an array at `path`.
+pub(crate) fn observe_array<'a>(seen: &mut TupleValues<'a>, path: String, items: &'a [Value]) {
+    let fits = (2..=MAX_TUPLE_LENGTH).contains(&items.len());
+    let entry = seen
+        .entry(path)
+        .or_insert_with(|| fits.then(|| vec![Vec::new(); items.len()]));
+    match entry {
+        Some(positions) if fits && positions.len() == items.len() => {
+            for (position, item) in positions.iter_mut().zip(items) {
+                position.push(item);
+            }
+        }
+        _ => *entry = None,
+    }
+}
+
+/// The shapes of the arrays recorded in `seen`.
+pub(crate) fn tuple_shapes(seen: TupleValues) -> TupleShapes {
+    seen.into_iter()
+        .map(|(path, positions)| {
+            let shape = match positions {
+                Some(positions) => TupleShape {
+                    length: Some(positions.len()),
+                    positions: positions.iter().map(|values| schema_of(values)).collect(),
+                },
+                None => TupleShape::not_a_tuple(),
+            };
+            (path, shape)
+        })
+        .collect()
+}
+
+/// The genson schema of a set of values.
+pub(crate) fn schema_of(values: &[&Value]) -> Value {
+    let mut buffers: Vec<Vec<u8>> = values
+        .iter()
+        .map(|value| serde_json::to_vec(value).unwrap_or_default())
+        .collect();
+    let mut builder = get_builder(None);
+    for bytes in buffers.iter_mut() {
+        if let Ok(object) = simd_json::to_borrowed_value(bytes) {
+            builder.add_object(&object);
+        }
+    }
+    builder.to_schema()
+}
+
+/// Give the arrays found to be tuples one schema per position, as an `items`
+/// array (the draft-07 form, which the later rewrites recurse into).
+pub(crate) fn apply_tuples(schema: &mut Value, shapes: &TupleShapes) {
+    apply_node(schema, shapes, &[]);
+}
+
+fn apply_node(schema: &mut Value, shapes: &TupleShapes, path: &[String]) {
+    let Value::Object(obj) = schema else {
+        return;
+    };
+    if matches!(obj.get("items"), Some(Value::Object(_))) {
+        if let Some(items) = shapes.get(&pointer(path)).and_then(TupleShape::tuple_items) {
+            obj.insert("items".to_string(), Value::Array(items.to_vec()));
+        }
+    }
+    for (key, child) in obj.iter_mut() {
+        match key.as_str() {
+            "properties" => {
+                if let Value::Object(props) = child {
+                    for (name, prop) in props.iter_mut() {
+                        apply_node(prop, shapes, &child_path(path, name));
+                    }
+                }
+            }
+            "items" => {
+                let items_path = child_path(path, ITEMS_SEGMENT);
+                match child {
+                    Value::Array(positions) => {
+                        for position in positions {
+                            apply_node(position, shapes, &items_path);
+                        }
+                    }
+                    _ => apply_node(child, shapes, &items_path),
+                }
+            }
+            "anyOf" => {
+                if let Value::Array(branches) = child {
+                    for branch in branches {
+                        apply_node(branch, shapes, path);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Write tuple `items` arrays as `prefixItems`, the keyword for them since
+/// 2020-12 (`apply_draft` turns them back for earlier drafts).
+pub(crate) fn tuples_to_prefix_items(schema: &mut Value) {
+    match schema {
+        Value::Object(obj) => {
+            if matches!(obj.get("items"), Some(Value::Array(_))) {
+                let prefix = obj.shift_remove("items").unwrap_or_default();
+                obj.insert("prefixItems".to_string(), prefix);
+            }
+            for (key, child) in obj.iter_mut() {
+                // Instance values, not schemas
+                if !matches!(key.as_str(), "enum" | "const" | "default" | "examples") {
+                    tuples_to_prefix_items(child);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(tuples_to_prefix_items),
+        _ => {}
+    }
+}
+
+/// Turn tuple arrays into records of positional fields (`_0`, `_1`, ...), which
+/// Avro can represent where it can't a tuple.
+#[cfg(feature = "avro")]
+pub(crate) fn tuples_to_records(schema: &mut Value) {
+    match schema {
+        Value::Object(obj) => {
+            for (key, child) in obj.iter_mut() {
+                if !matches!(key.as_str(), "enum" | "const" | "default" | "examples") {
+                    tuples_to_records(child);
+                }
+            }
+            if let Some(Value::Array(positions)) = obj.get("items") {
+                let names: Vec<String> = (0..positions.len())
+                    .map(|i| format!("{}{}", TUPLE_FIELD_PREFIX, i))
+                    .collect();
+                let properties: serde_json::Map<String, Value> = names
+                    .iter()
+                    .cloned()
+                    .zip(positions.iter().cloned())
+                    .collect();
+                obj.shift_remove("items");
+                let object_type = match obj.get("type") {
+                    // Nullable arrays stay nullable
+                    Some(Value::Array(types)) => Value::Array(
+                        types
+                            .iter()
+                            .map(|t| {
+                                if t == "array" {
+                                    serde_json::json!("object")
+                                } else {
+                                    t.clone()
+                                }
+                            })
+                            .collect(),
+                    ),
+                    _ => serde_json::json!("object"),
+                };
+                obj.insert("type".to_string(), object_type);
+                obj.insert("properties".to_string(), Value::Object(properties));
+                obj.insert("required".to_string(), serde_json::json!(names));
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(tuples_to_records),
+        _ => {}
+    }
+}
+
+/// The position a tuple record field holds in the array it came from.
+#[cfg(feature = "avro")]
+pub(crate) fn tuple_position(field_name: &str) -> Option<usize> {
+    field_name
+        .strip_prefix(TUPLE_FIELD_PREFIX)
+        .and_then(|i| i.parse().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    include!("../tests/tuples.rs");
+}
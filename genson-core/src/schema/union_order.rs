@@ -0,0 +1,291 @@
+//! Union branch ordering (`union_order`).
+//!
+//! Unions are sorted into a fixed precedence by default. Avro readers take the
+//! default of a union field from its first branch, so a union whose default isn't
+//! in the first branch is invalid for some consumers. The other strategies put
+//! the null branch first, the most often seen type first, or the branch matching
+//! the default first, in both JSON Schema and Avro output.
+
+use crate::schema::field_path::{child_path, pointer, pointer_matches, MAP_VALUES_SEGMENT};
+use crate::schema::reorder_unions;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Order of the branches of a union (a `type` array or `anyOf`, or an Avro union).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UnionOrder {
+    /// Fixed precedence (null, containers, then scalars), keeping `["null", T]`
+    /// and `anyOf` as they are. Avro unions keep the order they're converted in
+    #[default]
+    Canonical,
+    /// The null branch first, the rest in canonical order
+    NullFirst,
+    /// The type seen most often at the path first, ties in canonical order
+    ByFrequency,
+    /// The branch matching the field's `default` first, or null first without one
+    DefaultFirst,
+}
+
+/// How many values of each JSON type were seen at each path, keyed by JSON
+/// Pointer path (array items add no segment) and then type name.
+pub(crate) type TypeCounts = HashMap<String, HashMap<String, usize>>;
+
+/// Add the counts of `other` to `counts`.
+pub(crate) fn merge_type_counts(mut counts: TypeCounts, other: TypeCounts) -> TypeCounts {
+    for (path, types) in other {
+        let entry = counts.entry(path).or_default();
+        for (kind, count) in types {
+            *entry.entry(kind).or_default() += count;
+        }
+    }
+    counts
+}
+
+/// Count the type of a value at `path`.
+pub(crate) fn observe_value(counts: &mut TypeCounts, path: String, value: &Value) {
+    *counts
+        .entry(path)
+        .or_default()
+        .entry(value_kind(value).to_string())
+        .or_default() += 1;
+}
+
+/// The JSON Schema type of a value.
+fn value_kind(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(n) if n.is_f64() => "number",
+        Value::Number(_) => "integer",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+/// Order the unions of a JSON Schema: canonical precedence first, then `order`.
+pub(crate) fn order_unions(schema: &mut Value, order: UnionOrder, counts: &TypeCounts) {
+    reorder_unions(schema);
+    if order != UnionOrder::Canonical {
+        order_node(schema, order, counts, &[]);
+    }
+}
+
+fn order_node(schema: &mut Value, order: UnionOrder, counts: &TypeCounts, path: &[String]) {
+    let obj = match schema {
+        Value::Object(obj) => obj,
+        Value::Array(items) => {
+            for item in items {
+                order_node(item, order, counts, path);
+            }
+            return;
+        }
+        _ => return,
+    };
+
+    let default = obj.get("default").cloned();
+    for key in ["type", "anyOf"] {
+        if let Some(Value::Array(branches)) = obj.get_mut(key) {
+            order_branches(branches, json_kind, order, counts, path, default.as_ref());
+        }
+    }
+
+    for (key, child) in obj.iter_mut() {
+        match key.as_str() {
+            "properties" => {
+                if let Value::Object(props) = child {
+                    for (name, prop) in props.iter_mut() {
+                        order_node(prop, order, counts, &child_path(path, name));
+                    }
+                }
+            }
+            "additionalProperties" => {
+                order_node(child, order, counts, &child_path(path, MAP_VALUES_SEGMENT))
+            }
+            "enum" | "const" | "default" | "examples" | "required" => {}
+            _ => order_node(child, order, counts, path),
+        }
+    }
+}
+
+/// The JSON type of a `type` array entry or `anyOf` branch.
+fn json_kind(branch: &Value) -> Option<&str> {
+    match branch {
+        Value::String(t) => Some(t),
+        Value::Object(obj) => obj.get("type").and_then(Value::as_str),
+        _ => None,
+    }
+}
+
+/// Order the unions of an Avro schema converted from a JSON Schema, by the same
+/// paths. Canonical order leaves them as they were converted. The branch matching
+/// a field's `default` stays first in any order.
+#[cfg(feature = "avro")]
+pub(crate) fn order_avro_unions(avro: &mut Value, order: UnionOrder, counts: &TypeCounts) {
+    if order != UnionOrder::Canonical {
+        order_avro_node(avro, order, counts, &[], None);
+    }
+}
+
+/// `default` is that of the record field whose type `avro` is.
+#[cfg(feature = "avro")]
+fn order_avro_node(
+    avro: &mut Value,
+    order: UnionOrder,
+    counts: &TypeCounts,
+    path: &[String],
+    default: Option<&Value>,
+) {
+    match avro {
+        Value::Array(branches) => {
+            order_branches(branches, avro_kind, order, counts, path, default);
+            // Avro reads a field's default as its first branch, whatever the order
+            if default.is_some() {
+                let pin = UnionOrder::DefaultFirst;
+                order_branches(branches, avro_kind, pin, counts, path, default);
+            }
+            for branch in branches {
+                order_avro_node(branch, order, counts, path, None);
+            }
+        }
+        Value::Object(obj) => match obj.get("type").and_then(Value::as_str) {
+            Some("record") => {
+                if let Some(Value::Array(fields)) = obj.get_mut("fields") {
+                    for field in fields.iter_mut().filter_map(Value::as_object_mut) {
+                        let Some(name) = field.get("name").and_then(Value::as_str) else {
+                            continue;
+                        };
+                        let field_path = child_path(path, name);
+                        let default = field.get("default").cloned();
+                        if let Some(field_type) = field.get_mut("type") {
+                            order_avro_node(
+                                field_type,
+                                order,
+                                counts,
+                                &field_path,
+                                default.as_ref(),
+                            );
+                        }
+                    }
+                }
+            }
+            Some("array") => {
+                if let Some(items) = obj.get_mut("items") {
+                    order_avro_node(items, order, counts, path, None);
+                }
+            }
+            Some("map") => {
+                if let Some(values) = obj.get_mut("values") {
+                    let values_path = child_path(path, MAP_VALUES_SEGMENT);
+                    order_avro_node(values, order, counts, &values_path, None);
+                }
+            }
+            _ => {}
+        },
+        _ => {}
+    }
+}
+
+/// The JSON type an Avro union branch holds. Named type references have none.
+#[cfg(feature = "avro")]
+fn avro_kind(branch: &Value) -> Option<&str> {
+    let name = match branch {
+        Value::String(name) => name.as_str(),
+        Value::Object(obj) => obj.get("type")?.as_str()?,
+        _ => return None,
+    };
+    match name {
+        "null" => Some("null"),
+        "boolean" => Some("boolean"),
+        "int" | "long" => Some("integer"),
+        "float" | "double" => Some("number"),
+        "string" | "bytes" | "enum" | "fixed" => Some("string"),
+        "array" => Some("array"),
+        "record" | "map" => Some("object"),
+        _ => None,
+    }
+}
+
+/// Stable-sort `branches` by `order`, reading each branch's JSON type with `kind`.
+fn order_branches(
+    branches: &mut [Value],
+    kind: fn(&Value) -> Option<&str>,
+    order: UnionOrder,
+    counts: &TypeCounts,
+    path: &[String],
+    default: Option<&Value>,
+) {
+    let kinds: Vec<Option<String>> = branches
+        .iter()
+        .map(|branch| kind(branch).map(str::to_string))
+        .collect();
+    let has_integer = kinds.iter().any(|k| k.as_deref() == Some("integer"));
+    let mut ranked: Vec<(usize, Value)> = match order {
+        UnionOrder::Canonical => return,
+        UnionOrder::NullFirst => kinds
+            .iter()
+            .map(|k| usize::from(k.as_deref() != Some("null")))
+            .zip(branches.iter().cloned())
+            .collect(),
+        UnionOrder::ByFrequency => {
+            let seen = path_counts(counts, path);
+            kinds
+                .iter()
+                .map(|k| {
+                    let count = k.as_deref().map_or(0, |k| {
+                        let mut count = seen.get(k).copied().unwrap_or(0);
+                        // Integers are numbers too, unless they have their own branch
+                        if k == "number" && !has_integer {
+                            count += seen.get("integer").copied().unwrap_or(0);
+                        }
+                        count
+                    });
+                    usize::MAX - count
+                })
+                .zip(branches.iter().cloned())
+                .collect()
+        }
+        UnionOrder::DefaultFirst => {
+            let wanted = match default.map(value_kind) {
+                Some("integer") if !has_integer => "number",
+                Some(wanted) => wanted,
+                None => "null",
+            };
+            let first = kinds.iter().position(|k| k.as_deref() == Some(wanted));
+            (0..branches.len())
+                .map(|i| usize::from(Some(i) != first))
+                .zip(branches.iter().cloned())
+                .collect()
+        }
+    };
+    ranked.sort_by_key(|(rank, _)| *rank);
+    for (branch, (_, ranked)) in branches.iter_mut().zip(ranked) {
+        *branch = ranked;
+    }
+}
+
+/// The type counts at `path`, summed over every key of enclosing maps.
+fn path_counts(counts: &TypeCounts, path: &[String]) -> HashMap<String, usize> {
+    let key = pointer(path);
+    if path.iter().any(|seg| seg == MAP_VALUES_SEGMENT) {
+        counts
+            .iter()
+            .filter(|(seen, _)| pointer_matches(&key, seen))
+            .map(|(_, types)| types.clone())
+            .fold(HashMap::new(), |mut sum, types| {
+                for (kind, count) in types {
+                    *sum.entry(kind).or_default() += count;
+                }
+                sum
+            })
+    } else {
+        counts.get(&key).cloned().unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    include!("../tests/union_order.rs");
+}
@@ -0,0 +1,114 @@
+//! Text serialisations of schema documents besides JSON.
+//!
+//! Schemas kept in config repositories are often YAML (or TOML) rather than JSON.
+//! The document is the same either way: these only change how it is written out.
+//! YAML and TOML need the `yaml` and `toml` features.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt;
+use std::str::FromStr;
+
+/// Text format to write a schema document in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    /// Pretty-printed JSON
+    #[default]
+    Json,
+    /// YAML, with keys in the same order as the JSON
+    Yaml,
+    /// TOML, which has no null, so schemas holding nulls (e.g. `"default": null`)
+    /// can't be written in it
+    Toml,
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "json" => Ok(OutputFormat::Json),
+            "yaml" | "yml" => Ok(OutputFormat::Yaml),
+            "toml" => Ok(OutputFormat::Toml),
+            other => Err(format!(
+                "Invalid output format: {} (expected json|yaml|toml)",
+                other
+            )),
+        }
+    }
+}
+
+impl fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            OutputFormat::Json => "json",
+            OutputFormat::Yaml => "yaml",
+            OutputFormat::Toml => "toml",
+        })
+    }
+}
+
+/// Serialise a schema document in `format`, ending with a newline.
+pub fn to_string(document: &Value, format: OutputFormat) -> Result<String, String> {
+    match format {
+        OutputFormat::Json => serde_json::to_string_pretty(document)
+            .map(|json| json + "\n")
+            .map_err(|e| format!("Failed to write JSON: {}", e)),
+        OutputFormat::Yaml => to_yaml(document),
+        OutputFormat::Toml => to_toml(document),
+    }
+}
+
+#[cfg(feature = "yaml")]
+fn to_yaml(document: &Value) -> Result<String, String> {
+    // serde_yaml already ends the document with a newline
+    serde_yaml::to_string(document).map_err(|e| format!("Failed to write YAML: {}", e))
+}
+
+#[cfg(not(feature = "yaml"))]
+fn to_yaml(_document: &Value) -> Result<String, String> {
+    Err("YAML output needs genson-core's `yaml` feature".to_string())
+}
+
+#[cfg(feature = "toml")]
+fn to_toml(document: &Value) -> Result<String, String> {
+    if !document.is_object() {
+        return Err("TOML output needs a schema that is a JSON object".to_string());
+    }
+    if let Some(path) = find_null(document, &mut Vec::new()) {
+        return Err(format!("TOML can't represent the null at {}", path));
+    }
+    toml::to_string_pretty(document).map_err(|e| format!("Failed to write TOML: {}", e))
+}
+
+#[cfg(not(feature = "toml"))]
+fn to_toml(_document: &Value) -> Result<String, String> {
+    Err("TOML output needs genson-core's `toml` feature".to_string())
+}
+
+/// The JSON Pointer of the first null in `value`, so a TOML failure names it.
+#[cfg(feature = "toml")]
+fn find_null(value: &Value, path: &mut Vec<String>) -> Option<String> {
+    match value {
+        Value::Null => Some(crate::schema::field_path::pointer(path)),
+        Value::Object(obj) => obj.iter().find_map(|(key, child)| {
+            path.push(key.clone());
+            let found = find_null(child, path);
+            path.pop();
+            found
+        }),
+        Value::Array(items) => items.iter().enumerate().find_map(|(i, child)| {
+            path.push(i.to_string());
+            let found = find_null(child, path);
+            path.pop();
+            found
+        }),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    include!("tests/serialise.rs");
+}
@@ -0,0 +1,94 @@
+// genson-core/src/tests/annotate.rs
+use super::*;
+use crate::{infer_json_schema_from_strings, SchemaInferenceConfig};
+use serde_json::json;
+
+fn annotations() -> HashMap<String, FieldAnnotation> {
+    parse_annotations(
+        r#"{
+            "id": {"description": "Primary key"},
+            "owner.name": {"title": "Owner name", "examples": ["Ada"]},
+            "/tags/*/label": {"description": "Tag label"}
+        }"#,
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_annotate_schema() {
+    let mut schema = json!({
+        "type": "object",
+        "properties": {
+            "id": {"type": "integer"},
+            "owner": {"type": "object", "properties": {
+                "id": {"type": "string"},
+                "name": {"type": "string"}
+            }},
+            "tags": {"type": "object", "additionalProperties": {
+                "type": "object",
+                "properties": {"label": {"type": "string"}}
+            }}
+        }
+    });
+    annotate_schema(&mut schema, &annotations());
+    let props = &schema["properties"];
+    assert_eq!(props["id"]["description"], "Primary key");
+    // Bare names apply at any depth
+    assert_eq!(props["owner"]["properties"]["id"]["description"], "Primary key");
+    assert_eq!(
+        props["owner"]["properties"]["name"],
+        json!({"type": "string", "title": "Owner name", "examples": ["Ada"]})
+    );
+    assert_eq!(
+        props["tags"]["additionalProperties"]["properties"]["label"]["description"],
+        "Tag label"
+    );
+    assert!(props["owner"].get("description").is_none());
+}
+
+#[cfg(feature = "yaml")]
+#[test]
+fn test_parse_yaml_annotations() {
+    let parsed = parse_annotations("owner.name:\n  description: Legal name\n").unwrap();
+    assert_eq!(
+        parsed["owner.name"].description.as_deref(),
+        Some("Legal name")
+    );
+}
+
+#[test]
+fn test_parse_annotations_rejects_unknown_keys() {
+    let err = parse_annotations(r#"{"id": {"desc": "x"}}"#).unwrap_err();
+    assert!(err.starts_with("Invalid annotations:"), "{}", err);
+}
+
+#[test]
+fn test_inference_annotations() {
+    let rows = vec![r#"{"id": 1, "owner": {"name": "a"}}"#.to_string()];
+    let config = SchemaInferenceConfig {
+        annotations: annotations(),
+        ..Default::default()
+    };
+    let schema = infer_json_schema_from_strings(&rows, config.clone())
+        .unwrap()
+        .schema;
+    assert_eq!(schema["properties"]["id"]["description"], "Primary key");
+    assert_eq!(
+        schema["properties"]["owner"]["properties"]["name"]["title"],
+        "Owner name"
+    );
+
+    #[cfg(feature = "avro")]
+    {
+        let config = SchemaInferenceConfig {
+            avro: true,
+            avro_docs: HashMap::from([("id".to_string(), "Row id".to_string())]),
+            ..config
+        };
+        let avro = infer_json_schema_from_strings(&rows, config)
+            .unwrap()
+            .schema;
+        // avro_docs wins over an annotated description
+        assert_eq!(avro["fields"][0]["doc"], "Row id");
+    }
+}
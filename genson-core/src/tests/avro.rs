@@ -0,0 +1,331 @@
+// genson-core/src/tests/avro.rs
+use super::*;
+use crate::normalise::{normalise_values, NormaliseConfig};
+
+const ITEM_AVSC: &str = r#"{
+    "type": "record", "name": "Item", "namespace": "org.example",
+    "fields": [
+        {"name": "id", "type": "long"},
+        {"name": "label", "type": ["null", "string"], "default": null},
+        {"name": "tags", "type": {"type": "array", "items": "string"}},
+        {"name": "labels", "type": {"type": "map", "values": "string"}},
+        {"name": "owner", "type": ["null", {"type": "record", "name": "Owner", "fields": [
+            {"name": "name", "type": "string"}
+        ]}]},
+        {"name": "previous_owner", "type": ["null", "org.example.Owner"]},
+        {"name": "kind", "type": {"type": "enum", "name": "Kind", "symbols": ["A", "B"]}},
+        {"name": "created", "type": {"type": "long", "logicalType": "timestamp-millis"}}
+    ]
+}"#;
+
+#[test]
+fn test_avro_to_json_schema_record() {
+    let schema = avro_str_to_json_schema(ITEM_AVSC).unwrap();
+
+    assert_eq!(schema["type"], "object");
+    assert_eq!(schema["properties"]["id"], json!({"type": "integer"}));
+    assert_eq!(
+        schema["properties"]["label"],
+        json!({"type": ["null", "string"]})
+    );
+    assert_eq!(
+        schema["properties"]["tags"],
+        json!({"type": "array", "items": {"type": "string"}})
+    );
+    assert_eq!(
+        schema["properties"]["labels"],
+        json!({"type": "object", "additionalProperties": {"type": "string"}})
+    );
+    assert_eq!(schema["properties"]["kind"], json!({"type": "string"}));
+    assert_eq!(schema["properties"]["created"], json!({"type": "integer"}));
+    assert_eq!(
+        schema["required"],
+        json!(["id", "tags", "labels", "kind", "created"])
+    );
+}
+
+#[test]
+fn test_avro_named_type_reference() {
+    let schema = avro_str_to_json_schema(ITEM_AVSC).unwrap();
+    let owner = json!({
+        "type": "object",
+        "properties": {"name": {"type": "string"}},
+        "required": ["name"]
+    });
+    assert_eq!(
+        schema["properties"]["owner"],
+        json!({"anyOf": [{"type": "null"}, owner.clone()]})
+    );
+    assert_eq!(
+        schema["properties"]["previous_owner"],
+        json!({"anyOf": [{"type": "null"}, owner]})
+    );
+}
+
+#[test]
+fn test_avro_multi_branch_union() {
+    let schema = avro_to_json_schema(&json!(["null", "int", "string"])).unwrap();
+    assert_eq!(
+        schema,
+        json!({"anyOf": [{"type": "null"}, {"type": "integer"}, {"type": "string"}]})
+    );
+}
+
+#[test]
+fn test_avro_recursive_type_rejected() {
+    let avsc = json!({
+        "type": "record", "name": "Node",
+        "fields": [{"name": "next", "type": ["null", "Node"]}]
+    });
+    let err = avro_to_json_schema(&avsc).unwrap_err();
+    assert!(err.contains("Recursive Avro type Node"), "{}", err);
+}
+
+#[test]
+fn test_avro_unknown_type_rejected() {
+    let err = avro_to_json_schema(&json!({"type": "array", "items": "Missing"})).unwrap_err();
+    assert_eq!(err, "Unknown Avro type: Missing");
+}
+
+#[test]
+fn test_normalise_against_avro_schema() {
+    let schema = normalisation_schema_from_avro(ITEM_AVSC).unwrap();
+    assert_eq!(schema["type"], "record");
+
+    let cfg = NormaliseConfig::default();
+    let rows = vec![json!({"id": 1, "tags": "x", "labels": {}, "extra": true})];
+    let normalised = normalise_values(rows, &schema, &cfg);
+    assert_eq!(
+        normalised[0],
+        json!({
+            "id": 1,
+            "label": null,
+            "tags": ["x"],
+            "labels": null,
+            "owner": null,
+            "previous_owner": null,
+            "kind": null,
+            "created": null
+        })
+    );
+}
+
+#[test]
+fn test_json_schema_to_avro_matches_inference() {
+    let rows = vec![r#"{"id": 1, "owner": {"name": "a"}, "pair": ["x", 1]}"#.to_string()];
+    let config = crate::SchemaInferenceConfig {
+        detect_tuples: true,
+        ..Default::default()
+    };
+    let saved = crate::infer_json_schema_from_strings(&rows, config.clone())
+        .unwrap()
+        .schema;
+    let inferred = crate::infer_json_schema_from_strings(
+        &rows,
+        crate::SchemaInferenceConfig {
+            avro: true,
+            ..config
+        },
+    )
+    .unwrap()
+    .schema;
+    assert!(saved["properties"]["pair"]["prefixItems"].is_array());
+    assert_eq!(
+        json_schema_to_avro(&saved, &AvroOptions::default()).unwrap(),
+        inferred
+    );
+}
+
+#[test]
+fn test_json_schema_to_avro_naming() {
+    let schema = json!({
+        "type": "object",
+        "properties": {"owner": {"type": "object", "properties": {"name": {"type": "string"}}}},
+        "required": ["owner"]
+    });
+    let opts = AvroOptions {
+        namespace: "org.example".to_string(),
+        name: "Item".to_string(),
+        ..Default::default()
+    };
+    let avro = json_schema_to_avro(&schema, &opts).unwrap();
+    assert_eq!(avro["name"], "Item");
+    assert_eq!(avro["namespace"], "org.example");
+    assert_eq!(avro["fields"][0]["type"]["namespace"], "org.example.Item_types");
+}
+
+#[test]
+fn test_json_schema_to_avro_invalid() {
+    let opts = AvroOptions {
+        name: "my-record".to_string(),
+        ..Default::default()
+    };
+    let err = json_schema_to_avro(&json!({"type": "object"}), &opts).unwrap_err();
+    assert_eq!(err, "Invalid Avro name: my-record");
+    let err = json_schema_to_avro(&json!(true), &AvroOptions::default()).unwrap_err();
+    assert_eq!(err, "Expected a JSON Schema object");
+}
+
+#[test]
+fn test_avro_docs() {
+    let schema = json!({
+        "type": "object",
+        "properties": {
+            "id": {"type": "integer"},
+            "owner": {
+                "type": "object",
+                "properties": {"id": {"type": "string"}, "name": {"type": "string"}},
+                "required": ["id", "name"]
+            },
+            "tags": {"type": "object", "additionalProperties": {
+                "type": "object",
+                "properties": {"id": {"type": "string"}},
+                "required": ["id"]
+            }}
+        },
+        "required": ["id", "owner", "tags"]
+    });
+    let opts = AvroOptions {
+        docs: HashMap::from([
+            ("name".to_string(), "Display name".to_string()),
+            ("owner.id".to_string(), "Owner key".to_string()),
+            ("/tags/*/id".to_string(), "Tag key".to_string()),
+        ]),
+        ..Default::default()
+    };
+    let avro = json_schema_to_avro(&schema, &opts).unwrap();
+    assert!(avro["fields"][0].get("doc").is_none());
+    let owner = &avro["fields"][1]["type"]["fields"];
+    assert_eq!(owner[0]["doc"], "Owner key");
+    assert_eq!(owner[1]["doc"], "Display name");
+    assert_eq!(
+        avro["fields"][2]["type"]["values"]["fields"][0]["doc"],
+        "Tag key"
+    );
+}
+
+#[test]
+fn test_inference_avro_naming() {
+    let rows = vec![r#"{"id": 1, "owner": {"name": "a"}}"#.to_string()];
+    let config = crate::SchemaInferenceConfig {
+        avro: true,
+        avro_namespace: Some("org.example".to_string()),
+        avro_root_name: Some("Item".to_string()),
+        avro_docs: HashMap::from([("owner.name".to_string(), "Legal name".to_string())]),
+        ..Default::default()
+    };
+    let avro = crate::infer_json_schema_from_strings(&rows, config.clone())
+        .unwrap()
+        .schema;
+    assert_eq!(avro["name"], "Item");
+    assert_eq!(avro["namespace"], "org.example");
+    let owner = &avro["fields"][1]["type"];
+    assert_eq!(owner["namespace"], "org.example.Item_types");
+    assert_eq!(owner["fields"][0]["doc"], "Legal name");
+
+    let invalid = crate::SchemaInferenceConfig {
+        avro_namespace: Some("org..example".to_string()),
+        ..config
+    };
+    let err = crate::infer_json_schema_from_strings(&rows, invalid).unwrap_err();
+    assert_eq!(err, "Invalid Avro namespace: org..example");
+}
+
+#[test]
+fn test_avro_null_defaults() {
+    let rows = vec![
+        r#"{"id": 1, "note": "a", "owner": {"name": "a", "email": "x"}}"#.to_string(),
+        r#"{"id": 2, "owner": {"name": "b"}}"#.to_string(),
+    ];
+    let config = crate::SchemaInferenceConfig {
+        avro: true,
+        ..Default::default()
+    };
+    let avro = crate::infer_json_schema_from_strings(&rows, config.clone())
+        .unwrap()
+        .schema;
+    let fields = &avro["fields"];
+    assert!(fields[0].get("default").is_none());
+    assert_eq!(fields[1]["type"], json!(["null", "string"]));
+    assert_eq!(fields[1]["default"], Value::Null);
+    let owner_fields = &fields[2]["type"]["fields"];
+    assert!(owner_fields[0].get("default").is_none());
+    assert_eq!(owner_fields[1]["default"], Value::Null);
+
+    let config = crate::SchemaInferenceConfig {
+        avro_defaults: false,
+        ..config
+    };
+    let avro = crate::infer_json_schema_from_strings(&rows, config)
+        .unwrap()
+        .schema;
+    assert!(avro["fields"][1].get("default").is_none());
+}
+
+#[test]
+fn test_sanitise_avro_name() {
+    use AvroNameStrategy::*;
+    assert_eq!(sanitise_avro_name("foo-bar", Keep), "foo-bar");
+    assert_eq!(sanitise_avro_name("foo-bar", Replace), "foo_bar");
+    assert_eq!(sanitise_avro_name("foo-bar", Escape), "foo_x2D_bar");
+    assert_eq!(sanitise_avro_name("2023", Replace), "_2023");
+    assert_eq!(sanitise_avro_name("café", Replace), "caf_");
+    assert_eq!(sanitise_avro_name("café", Escape), "caf_xE9_");
+    assert_eq!(sanitise_avro_name("", Replace), "_");
+    assert_eq!(sanitise_avro_name("valid_name", Escape), "valid_name");
+}
+
+#[test]
+fn test_avro_field_names_renamed_and_normalised() {
+    let schema = json!({
+        "type": "object",
+        "properties": {
+            "foo-bar": {"type": "integer"},
+            "foo_bar": {"type": "integer"},
+            "2023": {"type": "string"},
+            "ok-rec": {
+                "type": "object",
+                "properties": {"a b": {"type": "integer"}},
+                "required": ["a b"]
+            }
+        },
+        "required": ["foo-bar", "foo_bar", "2023", "ok-rec"]
+    });
+    let opts = AvroOptions {
+        field_names: AvroNameStrategy::Replace,
+        ..Default::default()
+    };
+    let avro = json_schema_to_avro(&schema, &opts).unwrap();
+    let fields = &avro["fields"];
+    assert_eq!(fields[0]["name"], "foo_bar_2");
+    assert_eq!(fields[0][ORIGINAL_NAME_KEY], "foo-bar");
+    assert_eq!(fields[1]["name"], "foo_bar");
+    assert!(fields[1].get(ORIGINAL_NAME_KEY).is_none());
+    assert_eq!(fields[2]["name"], "_2023");
+    assert_eq!(fields[3]["type"]["name"], "ok_rec");
+    assert_eq!(
+        field_renames(&avro).last(),
+        Some(&FieldRename {
+            path: "/ok-rec/a b".to_string(),
+            original: "a b".to_string(),
+            name: "a_b".to_string(),
+        })
+    );
+
+    let row = json!({"foo-bar": 1, "foo_bar": 2, "2023": "x", "ok-rec": {"a b": 3}});
+    let expected = json!({"foo_bar_2": 1, "foo_bar": 2, "_2023": "x", "ok_rec": {"a_b": 3}});
+    let cfg = NormaliseConfig {
+        unknown_fields: crate::normalise::UnknownFieldPolicy::Keep,
+        ..NormaliseConfig::default()
+    };
+    assert_eq!(normalise_values(vec![row.clone()], &avro, &cfg)[0], expected);
+
+    // A schema saved to disk and read back renames rows the same way
+    let loaded = normalisation_schema_from_avro(&avro.to_string()).unwrap();
+    assert_eq!(field_renames(&loaded), field_renames(&avro));
+    assert_eq!(normalise_values(vec![row], &loaded, &cfg)[0], expected);
+    assert_eq!(
+        avro_to_json_schema(&avro).unwrap()["required"],
+        json!(["foo-bar", "foo_bar", "2023", "ok-rec"])
+    );
+}
@@ -0,0 +1,99 @@
+// genson-core/src/tests/compact.rs
+use super::*;
+use serde_json::json;
+
+#[test]
+fn test_compact_strips_redundant_keywords() {
+    let mut schema = json!({
+        "$schema": "http://json-schema.org/schema#",
+        "type": "object",
+        "properties": {
+            "a": {"$schema": "http://json-schema.org/schema#", "type": ["string"]},
+            "b": {
+                "type": "object",
+                "properties": {"c": {"type": "integer"}},
+                "required": [],
+                "additionalProperties": true
+            }
+        },
+        "required": ["a"]
+    });
+    compact(&mut schema, CompactOptions::default());
+
+    assert_eq!(
+        schema,
+        json!({
+            "$schema": "http://json-schema.org/schema#",
+            "type": "object",
+            "properties": {
+                "a": {"type": "string"},
+                "b": {"type": "object", "properties": {"c": {"type": "integer"}}}
+            },
+            "required": ["a"]
+        })
+    );
+}
+
+#[test]
+fn test_compact_without_root_schema_uri() {
+    let mut schema = json!({"$schema": "http://json-schema.org/schema#", "type": "string"});
+    compact(
+        &mut schema,
+        CompactOptions {
+            keep_schema_uri: false,
+            ..Default::default()
+        },
+    );
+
+    assert_eq!(schema, json!({"type": "string"}));
+}
+
+#[test]
+fn test_compact_collapses_single_branch_unions() {
+    let mut schema = json!({
+        "type": "object",
+        "properties": {
+            "a": {"anyOf": [{"type": "array", "items": {"oneOf": [{"type": "string"}]}}]},
+            "b": {"type": "object", "anyOf": [{"type": "string"}]},
+            "c": {"anyOf": [{"type": "string"}, {"type": "null"}]}
+        }
+    });
+    let untouched = schema.clone();
+    compact(&mut schema, CompactOptions::default());
+
+    assert_eq!(
+        schema["properties"]["a"],
+        json!({"type": "array", "items": {"type": "string"}})
+    );
+    // Collapsing would lose the node's own type
+    assert_eq!(schema["properties"]["b"], untouched["properties"]["b"]);
+    assert_eq!(schema["properties"]["c"], untouched["properties"]["c"]);
+}
+
+#[test]
+fn test_compact_keeps_unions_when_not_collapsing() {
+    let mut schema = json!({"anyOf": [{"type": "string"}]});
+    compact(
+        &mut schema,
+        CompactOptions {
+            collapse_unions: false,
+            ..Default::default()
+        },
+    );
+
+    assert_eq!(schema, json!({"anyOf": [{"type": "string"}]}));
+}
+
+#[test]
+fn test_compact_leaves_property_names_alone() {
+    // A property called `required` is data, not the keyword
+    let mut schema = json!({
+        "type": "object",
+        "properties": {"required": {"type": "boolean"}, "$schema": {"type": "string"}},
+        "additionalProperties": false
+    });
+    let expected = schema.clone();
+    compact(&mut schema, CompactOptions::default());
+
+    assert_eq!(schema, expected);
+}
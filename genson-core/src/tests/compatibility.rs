@@ -0,0 +1,123 @@
+// genson-core/src/tests/compatibility.rs
+use super::*;
+use serde_json::json;
+
+fn old_schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "id": {"type": "integer"},
+            "name": {"type": "string"},
+            "labels": {"type": "object", "additionalProperties": {"type": "string"}}
+        },
+        "required": ["id", "name", "labels"]
+    })
+}
+
+fn messages(issues: &[CompatibilityIssue]) -> Vec<(String, CompatibilityMode, String)> {
+    issues
+        .iter()
+        .map(|i| (i.path.clone(), i.direction, i.message.clone()))
+        .collect()
+}
+
+#[test]
+fn test_same_schema_is_compatible() {
+    let issues = check_compatibility(&old_schema(), &old_schema(), CompatibilityMode::Full);
+    assert!(issues.unwrap().is_empty());
+}
+
+#[test]
+fn test_added_optional_field_is_fully_compatible() {
+    let mut new = old_schema();
+    new["properties"]["email"] = json!({"type": "string"});
+    let issues = check_compatibility(&old_schema(), &new, CompatibilityMode::Full).unwrap();
+    assert!(issues.is_empty());
+}
+
+#[test]
+fn test_added_required_field_breaks_backward() {
+    let mut new = old_schema();
+    new["properties"]["email"] = json!({"type": "string"});
+    new["required"] = json!(["id", "name", "labels", "email"]);
+    let issues = check_compatibility(&old_schema(), &new, CompatibilityMode::Full).unwrap();
+    assert_eq!(
+        messages(&issues),
+        vec![(
+            "email".to_string(),
+            CompatibilityMode::Backward,
+            "field is missing from the writer's schema and has no default".to_string()
+        )]
+    );
+}
+
+#[test]
+fn test_removed_required_field_breaks_forward() {
+    let mut new = old_schema();
+    new["properties"].as_object_mut().unwrap().remove("name");
+    new["required"] = json!(["id", "labels"]);
+    let backward = check_compatibility(&old_schema(), &new, CompatibilityMode::Backward).unwrap();
+    assert!(backward.is_empty());
+    let forward = check_compatibility(&old_schema(), &new, CompatibilityMode::Forward).unwrap();
+    assert_eq!(forward[0].path, "name");
+    assert_eq!(forward[0].direction, CompatibilityMode::Forward);
+}
+
+#[test]
+fn test_type_changes() {
+    // Integers widen to numbers, but not back
+    let mut new = old_schema();
+    new["properties"]["id"] = json!({"type": "number"});
+    let issues = check_compatibility(&old_schema(), &new, CompatibilityMode::Full).unwrap();
+    assert_eq!(
+        messages(&issues),
+        vec![(
+            "id".to_string(),
+            CompatibilityMode::Forward,
+            "number values can't be read as integer".to_string()
+        )]
+    );
+
+    // Map values are checked under `*`
+    let mut new = old_schema();
+    new["properties"]["labels"]["additionalProperties"] = json!({"type": "integer"});
+    let issues = check_compatibility(&old_schema(), &new, CompatibilityMode::Backward).unwrap();
+    assert_eq!(issues[0].path, "labels.*");
+    assert_eq!(issues[0].message, "string values can't be read as integer");
+}
+
+#[test]
+fn test_nullability() {
+    // A field that may now be null can't be read by the old schema
+    let mut new = old_schema();
+    new["properties"]["name"] = json!({"type": ["null", "string"]});
+    let issues = check_compatibility(&old_schema(), &new, CompatibilityMode::Full).unwrap();
+    assert_eq!(
+        messages(&issues),
+        vec![(
+            "name".to_string(),
+            CompatibilityMode::Forward,
+            "null values can't be read as string".to_string()
+        )]
+    );
+}
+
+#[cfg(feature = "avro")]
+#[test]
+fn test_avro_schemas() {
+    let old = json!({"type": "record", "name": "Item", "fields": [
+        {"name": "id", "type": "int"}
+    ]});
+    let new = json!({"type": "record", "name": "Item", "fields": [
+        {"name": "id", "type": "long"},
+        {"name": "note", "type": ["null", "string"], "default": null}
+    ]});
+    let issues = check_compatibility(&old, &new, CompatibilityMode::Full).unwrap();
+    assert!(issues.is_empty());
+
+    let new = json!({"type": "record", "name": "Item", "fields": [
+        {"name": "id", "type": "string"}
+    ]});
+    let issues = check_compatibility(&old, &new, CompatibilityMode::Backward).unwrap();
+    assert_eq!(issues[0].message, "integer values can't be read as string");
+}
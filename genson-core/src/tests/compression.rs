@@ -0,0 +1,78 @@
+// genson-core/src/tests/compression.rs
+use super::*;
+use std::io::Write;
+use tempfile::Builder;
+
+const PAYLOAD: &str = "{\"name\": \"Alice\"}\n{\"name\": \"Bob\"}\n";
+
+fn write_temp(suffix: &str, bytes: &[u8]) -> tempfile::NamedTempFile {
+    let mut file = Builder::new().suffix(suffix).tempfile().unwrap();
+    file.write_all(bytes).unwrap();
+    file
+}
+
+#[test]
+fn test_detect_from_extension() {
+    assert_eq!(Compression::from_path("dump.json.gz"), Compression::Gzip);
+    assert_eq!(Compression::from_path("dump.json.bz2"), Compression::Bzip2);
+    assert_eq!(Compression::from_path("dump.jsonl.zst"), Compression::Zstd);
+    assert_eq!(Compression::from_path("dump.JSON.GZ"), Compression::Gzip);
+    assert_eq!(Compression::from_path("dump.json"), Compression::None);
+    assert_eq!(Compression::from_path("dump"), Compression::None);
+}
+
+#[test]
+fn test_parse_compression_names() {
+    assert_eq!("gzip".parse::<Compression>(), Ok(Compression::Gzip));
+    assert_eq!("bz2".parse::<Compression>(), Ok(Compression::Bzip2));
+    assert_eq!("zstd".parse::<Compression>(), Ok(Compression::Zstd));
+    assert_eq!("none".parse::<Compression>(), Ok(Compression::None));
+    assert!("lzma".parse::<Compression>().is_err());
+}
+
+#[test]
+fn test_read_gzip() {
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(PAYLOAD.as_bytes()).unwrap();
+    let file = write_temp(".json.gz", &encoder.finish().unwrap());
+
+    let text = read_to_string(file.path().to_str().unwrap(), None).unwrap();
+    assert_eq!(text, PAYLOAD);
+}
+
+#[test]
+fn test_read_bzip2() {
+    let mut encoder = bzip2::write::BzEncoder::new(Vec::new(), bzip2::Compression::default());
+    encoder.write_all(PAYLOAD.as_bytes()).unwrap();
+    let file = write_temp(".json.bz2", &encoder.finish().unwrap());
+
+    let text = read_to_string(file.path().to_str().unwrap(), None).unwrap();
+    assert_eq!(text, PAYLOAD);
+}
+
+#[test]
+fn test_read_zstd() {
+    let compressed = zstd::stream::encode_all(PAYLOAD.as_bytes(), 0).unwrap();
+    let file = write_temp(".jsonl.zst", &compressed);
+
+    let text = read_to_string(file.path().to_str().unwrap(), None).unwrap();
+    assert_eq!(text, PAYLOAD);
+}
+
+#[test]
+fn test_explicit_compression_overrides_extension() {
+    let compressed = zstd::stream::encode_all(PAYLOAD.as_bytes(), 0).unwrap();
+    let file = write_temp(".data", &compressed);
+
+    let text =
+        read_to_string(file.path().to_str().unwrap(), Some(Compression::Zstd)).unwrap();
+    assert_eq!(text, PAYLOAD);
+}
+
+#[test]
+fn test_uncompressed_passthrough() {
+    let file = write_temp(".json", PAYLOAD.as_bytes());
+
+    let text = read_to_string(file.path().to_str().unwrap(), None).unwrap();
+    assert_eq!(text, PAYLOAD);
+}
@@ -0,0 +1,66 @@
+// genson-core/src/tests/conformity.rs
+use super::*;
+use serde_json::json;
+
+fn reference() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "id": {"type": "integer"},
+            "name": {"type": ["null", "string"]},
+            "tags": {"type": "array", "items": {"type": "string"}},
+            "owner": {
+                "type": "object",
+                "properties": {"email": {"type": "string"}},
+                "required": ["email"],
+                "additionalProperties": false
+            },
+            "labels": {"type": "object", "additionalProperties": {"type": "string"}},
+            "value": {"anyOf": [{"type": "string"}, {"type": "number"}]}
+        },
+        "required": ["id"]
+    })
+}
+
+#[test]
+fn test_conforming_value_has_no_violations() {
+    let value = json!({
+        "id": 1,
+        "name": null,
+        "tags": ["a"],
+        "owner": {"email": "a@b.c"},
+        "labels": {"en": "Hi"},
+        "value": 2.5,
+        "unlisted": true
+    });
+    assert_eq!(violations(&value, &reference()), Vec::<String>::new());
+}
+
+#[test]
+fn test_violation_paths() {
+    let value = json!({
+        "name": 5,
+        "tags": ["a", 2],
+        "owner": {"phone": "123"},
+        "labels": {"en": {"text": "Hi"}},
+        "value": [1]
+    });
+    assert_eq!(
+        violations(&value, &reference()),
+        vec![
+            "id",
+            "name",
+            "tags[1]",
+            "owner.email",
+            "owner.phone",
+            "labels.en",
+            "value"
+        ]
+    );
+}
+
+#[test]
+fn test_root_violation() {
+    assert_eq!(violations(&json!([1]), &reference()), vec![ROOT_PATH]);
+    assert_eq!(violations(&json!(3), &json!({"enum": [1, 2]})), vec![ROOT_PATH]);
+}
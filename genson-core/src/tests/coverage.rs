@@ -0,0 +1,102 @@
+// genson-core/src/tests/coverage.rs
+use super::*;
+use serde_json::json;
+
+fn reference() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "id": {"type": "integer"},
+            "owner": {
+                "type": "object",
+                "properties": {"name": {"type": "string"}}
+            },
+            "tags": {
+                "type": "array",
+                "items": {"type": "object", "properties": {"k": {"type": "string"}}}
+            },
+            "labels": {
+                "type": "object",
+                "additionalProperties": {
+                    "type": "object",
+                    "properties": {"value": {"type": "string"}}
+                }
+            },
+            "extra": {"type": "object", "additionalProperties": true}
+        }
+    })
+}
+
+fn paths(fields: &[UncoveredField]) -> Vec<&str> {
+    fields.iter().map(|f| f.path.as_str()).collect()
+}
+
+#[test]
+fn test_covered_data_has_no_findings() {
+    let rows = [json!({
+        "id": 1,
+        "owner": {"name": "a"},
+        "tags": [{"k": "x"}],
+        "labels": {"en": {"value": "Hello"}},
+        "extra": {"anything": {"goes": true}}
+    })];
+    assert!(find_uncovered(&reference(), &rows, DEFAULT_MAX_EXAMPLES).is_empty());
+}
+
+#[test]
+fn test_uncovered_fields_at_each_level() {
+    let rows = [
+        json!({"id": 1, "new_top": 1, "owner": {"name": "a", "email": "a@x"}}),
+        json!({"tags": [{"k": "x", "v": 1}, {"v": 2}], "labels": {"en": {"lang": "en"}}}),
+    ];
+    let found = find_uncovered(&reference(), &rows, DEFAULT_MAX_EXAMPLES);
+    assert_eq!(
+        paths(&found),
+        ["new_top", "owner.email", "tags[].v", "labels{}.lang"]
+    );
+    assert_eq!(found[2].count, 2);
+    assert_eq!(found[2].examples, vec![json!(1), json!(2)]);
+}
+
+#[test]
+fn test_only_outermost_uncovered_field_reported() {
+    let rows = [json!({"meta": {"source": {"id": 1}}})];
+    let found = find_uncovered(&reference(), &rows, DEFAULT_MAX_EXAMPLES);
+    assert_eq!(paths(&found), ["meta"]);
+    assert_eq!(found[0].examples, vec![json!({"source": {"id": 1}})]);
+}
+
+#[test]
+fn test_counts_and_distinct_examples() {
+    let rows: Vec<Value> = (0..10).map(|i| json!({"seq": i % 4})).collect();
+    let found = find_uncovered(&reference(), &rows, 2);
+    assert_eq!(found[0].count, 10);
+    assert_eq!(found[0].examples, vec![json!(0), json!(1)]);
+}
+
+#[test]
+fn test_union_branches_cover_keys() {
+    let schema = json!({
+        "type": "object",
+        "properties": {
+            "value": {"anyOf": [
+                {"type": "null"},
+                {"type": "object", "properties": {"a": {"type": "integer"}}},
+                {"type": "object", "properties": {"b": {"type": "integer"}}}
+            ]}
+        }
+    });
+    let rows = [json!({"value": {"a": 1, "b": 2, "c": 3}})];
+    let found = find_uncovered(&schema, &rows, DEFAULT_MAX_EXAMPLES);
+    assert_eq!(paths(&found), ["value.c"]);
+}
+
+#[test]
+fn test_tracker_counts_rows() {
+    let schema = reference();
+    let mut tracker = CoverageTracker::new(&schema, DEFAULT_MAX_EXAMPLES);
+    tracker.observe(&json!({"id": 1}));
+    tracker.observe(&json!({"id": 2}));
+    assert_eq!(tracker.rows(), 2);
+    assert!(tracker.finish().is_empty());
+}
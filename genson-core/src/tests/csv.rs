@@ -0,0 +1,53 @@
+// genson-core/src/tests/csv.rs
+use super::*;
+
+#[test]
+fn test_read_quoted_json_column() {
+    let input = "id,payload\n1,\"{\"\"name\"\": \"\"a, b\"\"}\"\n2,\"{\"\"name\"\": null}\"\n";
+    let strings = read_string_column(input.as_bytes(), "payload", CsvOptions::default()).unwrap();
+    assert_eq!(strings, [r#"{"name": "a, b"}"#, r#"{"name": null}"#]);
+}
+
+#[test]
+fn test_read_tsv_skips_empty_cells() {
+    let input = "payload\tid\n{\"x\": 1}\t1\n\t2\n";
+    let options = CsvOptions::for_path("export.tsv.gz");
+    assert_eq!(options.delimiter, b'\t');
+    let strings = read_string_column(input.as_bytes(), "payload", options).unwrap();
+    assert_eq!(strings, [r#"{"x": 1}"#]);
+}
+
+#[test]
+fn test_custom_quote() {
+    let input = "payload;id\n'{\"a\": \"x;y\"}';1\n";
+    let options = CsvOptions {
+        delimiter: parse_csv_char(";").unwrap(),
+        quote: parse_csv_char("'").unwrap(),
+    };
+    let strings = read_string_column(input.as_bytes(), "payload", options).unwrap();
+    assert_eq!(strings, [r#"{"a": "x;y"}"#]);
+}
+
+#[test]
+fn test_missing_column() {
+    let err = read_string_column("a,b\n1,2\n".as_bytes(), "payload", CsvOptions::default())
+        .unwrap_err();
+    assert_eq!(
+        err,
+        "Column 'payload' not found in CSV header. Available columns: a, b"
+    );
+}
+
+#[test]
+fn test_ragged_row() {
+    let err = read_string_column("a,b\n1,2,3\n".as_bytes(), "a", CsvOptions::default())
+        .unwrap_err();
+    assert!(err.starts_with("Failed to read CSV record"), "{}", err);
+}
+
+#[test]
+fn test_parse_csv_char() {
+    assert_eq!(parse_csv_char("\\t"), Ok(b'\t'));
+    assert_eq!(parse_csv_char("|"), Ok(b'|'));
+    assert!(parse_csv_char("ab").is_err());
+}
@@ -0,0 +1,109 @@
+// genson-core/src/tests/discriminators.rs
+use super::*;
+use crate::{infer_json_schema_from_strings, SchemaInferenceConfig, SchemaInferenceSession};
+
+fn config(discriminators: &[(&str, &str)]) -> SchemaInferenceConfig {
+    SchemaInferenceConfig {
+        discriminators: discriminators
+            .iter()
+            .map(|(path, field)| (path.to_string(), field.to_string()))
+            .collect(),
+        ..SchemaInferenceConfig::default()
+    }
+}
+
+fn rows() -> Vec<String> {
+    [
+        json!({"id": 1, "snak": {"type": "value", "property": "P31", "value": "Q5"}}),
+        json!({"id": 2, "snak": {"type": "novalue", "property": "P21"}}),
+        json!({"id": 3, "snak": {"type": "value", "property": "P27", "value": "Q145"}}),
+    ]
+    .iter()
+    .map(Value::to_string)
+    .collect()
+}
+
+fn branches(schema: &Value) -> &Vec<Value> {
+    schema["properties"]["snak"]["oneOf"].as_array().unwrap()
+}
+
+#[test]
+fn test_branch_per_tag_value() {
+    let schema = infer_json_schema_from_strings(&rows(), config(&[("snak", "type")]))
+        .unwrap()
+        .schema;
+    let branches = branches(&schema);
+    assert_eq!(branches.len(), 2);
+    assert_eq!(
+        branches[0]["properties"]["type"],
+        json!({"type": "string", "const": "novalue"})
+    );
+    assert_eq!(branches[0]["required"], json!(["property", "type"]));
+    assert_eq!(branches[1]["properties"]["type"]["const"], json!("value"));
+    assert_eq!(branches[1]["required"], json!(["property", "type", "value"]));
+
+    // Without the discriminator the fields of one tag are optional in the other
+    let schema = infer_json_schema_from_strings(&rows(), SchemaInferenceConfig::default())
+        .unwrap()
+        .schema;
+    assert_eq!(
+        schema["properties"]["snak"]["required"],
+        json!(["property", "type"])
+    );
+}
+
+#[test]
+fn test_untagged_objects_make_the_last_branch() {
+    let mut rows = rows();
+    rows.push(json!({"id": 4, "snak": {"property": "P1"}}).to_string());
+    let schema = infer_json_schema_from_strings(&rows, config(&[("/snak", "type")]))
+        .unwrap()
+        .schema;
+    let branches = branches(&schema);
+    assert_eq!(branches.len(), 3);
+    assert_eq!(branches[2], json!({"type": "object", "properties": {"property": {"type": "string"}}, "required": ["property"]}));
+}
+
+#[test]
+fn test_root_discriminator() {
+    let rows: Vec<String> = [json!({"kind": "a", "x": 1}), json!({"kind": "b", "y": true})]
+        .iter()
+        .map(Value::to_string)
+        .collect();
+    let schema = infer_json_schema_from_strings(&rows, config(&[("/", "kind")]))
+        .unwrap()
+        .schema;
+    let branches = schema["oneOf"].as_array().unwrap();
+    assert_eq!(branches[0]["properties"]["x"], json!({"type": "integer"}));
+    assert_eq!(branches[1]["properties"]["kind"]["const"], json!("b"));
+}
+
+#[test]
+fn test_sessions_merge_tagged_schemas() {
+    let rows = rows();
+    let mut session = SchemaInferenceSession::new(config(&[("snak", "type")]));
+    session.add_strings(&rows[..1]).unwrap();
+    let mut other = SchemaInferenceSession::new(config(&[("snak", "type")]));
+    other.add_strings(&rows[1..]).unwrap();
+    session.merge(other).unwrap();
+
+    let merged = session.result().unwrap().schema;
+    let whole = infer_json_schema_from_strings(&rows, config(&[("snak", "type")]))
+        .unwrap()
+        .schema;
+    assert_eq!(merged, whole);
+}
+
+#[cfg(feature = "avro")]
+#[test]
+fn test_merge_tagged_unions() {
+    let mut schema = infer_json_schema_from_strings(&rows(), config(&[("snak", "type")]))
+        .unwrap()
+        .schema;
+    merge_tagged_unions(&mut schema);
+    let snak = &schema["properties"]["snak"];
+    assert_eq!(snak["type"], json!("object"));
+    assert_eq!(snak["properties"]["type"], json!({"type": "string"}));
+    assert_eq!(snak["properties"]["value"], json!({"type": "string"}));
+    assert_eq!(snak["required"], json!(["property", "type"]));
+}
@@ -0,0 +1,58 @@
+// genson-core/src/tests/document_stats.rs
+use super::*;
+use crate::schema::key_fold::KeyFold;
+use serde_json::json;
+
+#[test]
+fn test_nothing_collected_unless_asked() {
+    let stats = DocumentStats::collect(&[r#"{"a": [1, "x"]}"#], &SchemaInferenceConfig::default());
+    assert!(stats.field_counts.is_empty());
+    assert!(stats.type_counts.is_empty());
+    assert!(stats.tuple_shapes.is_empty());
+}
+
+#[test]
+fn test_one_walk_fills_every_statistic_asked_for() {
+    let config = SchemaInferenceConfig {
+        delimiter: Some(b'\n'),
+        wrap_root: Some("row".to_string()),
+        key_fold: Some(KeyFold::Lower),
+        sort_fields: FieldOrder::ByFrequency,
+        required_threshold: Some(0.5),
+        union_order: UnionOrder::ByFrequency,
+        detect_tuples: true,
+        sparse_array_detection: true,
+        ..SchemaInferenceConfig::default()
+    };
+    let input = "{\"Id\": 1, \"pair\": [\"a\", 1]}\n{\"id\": \"x\", \"pair\": [\"b\", 2]}";
+    let stats = DocumentStats::collect(&[input], &config);
+
+    // Keyed as the builder sees the documents: wrapped, with their keys folded
+    assert_eq!(stats.field_counts["/row/id"], 2);
+    assert_eq!(stats.object_counts["/row"], 2);
+    assert_eq!(stats.object_counts[""], 2);
+    assert_eq!(stats.type_counts["/row/id"]["integer"], 1);
+    assert_eq!(stats.type_counts["/row/id"]["string"], 1);
+    assert_eq!(stats.type_counts["/row/pair"]["string"], 2);
+    assert!(stats.tuple_shapes.contains_key("/row/pair"));
+    assert_eq!(stats.sparse_arrays.get("/row"), Some(&false));
+    // Spellings are the keys before folding
+    assert_eq!(stats.key_spellings["id"], vec!["Id", "id"]);
+}
+
+#[test]
+fn test_merged_stats_match_one_input() {
+    let config = SchemaInferenceConfig {
+        sort_fields: FieldOrder::ByFrequency,
+        union_order: UnionOrder::ByFrequency,
+        ..SchemaInferenceConfig::default()
+    };
+    let rows = [json!({"a": 1, "b": [true]}).to_string(), json!({"a": "x"}).to_string()];
+    let whole = DocumentStats::collect(&[rows[0].as_str(), rows[1].as_str()], &config);
+    let merged = DocumentStats::collect(&[rows[0].as_str()], &config)
+        .merge(DocumentStats::collect(&[rows[1].as_str()], &config));
+    assert_eq!(merged.field_counts, whole.field_counts);
+    assert_eq!(merged.type_counts, whole.type_counts);
+    assert_eq!(whole.field_counts["/a"], 2);
+    assert_eq!(whole.type_counts["/b"]["boolean"], 1);
+}
@@ -0,0 +1,85 @@
+// genson-core/src/tests/draft.rs
+use super::*;
+use crate::SchemaInferenceConfig;
+use serde_json::json;
+
+#[test]
+fn test_apply_draft_sets_schema_uri() {
+    let mut schema = json!({"$schema": "http://json-schema.org/schema#", "type": "object"});
+    apply_draft(&mut schema, JsonSchemaDraft::Draft202012);
+    assert_eq!(
+        schema["$schema"],
+        "https://json-schema.org/draft/2020-12/schema"
+    );
+
+    let mut schema = json!({"type": "object"});
+    apply_draft(&mut schema, JsonSchemaDraft::Draft07);
+    assert!(schema.get("$schema").is_none());
+}
+
+#[test]
+fn test_apply_draft_renames_definitions() {
+    let mut schema = json!({
+        "type": "object",
+        "properties": {
+            "a": {"$ref": "#/definitions/thing"},
+            "definitions": {"type": "string"}
+        },
+        "definitions": {"thing": {"type": "integer"}}
+    });
+    apply_draft(&mut schema, JsonSchemaDraft::Draft201909);
+    assert_eq!(schema["$defs"]["thing"]["type"], "integer");
+    assert!(schema.get("definitions").is_none());
+    assert_eq!(schema["properties"]["a"]["$ref"], "#/$defs/thing");
+    // A field named like the keyword is left alone
+    assert_eq!(schema["properties"]["definitions"]["type"], "string");
+
+    apply_draft(&mut schema, JsonSchemaDraft::Draft07);
+    assert_eq!(schema["definitions"]["thing"]["type"], "integer");
+    assert_eq!(schema["properties"]["a"]["$ref"], "#/definitions/thing");
+}
+
+#[test]
+fn test_apply_draft_tuple_items() {
+    let mut schema = json!({
+        "type": "array",
+        "items": [{"type": "string"}, {"type": "number"}],
+        "additionalItems": false
+    });
+    apply_draft(&mut schema, JsonSchemaDraft::Draft202012);
+    assert_eq!(
+        schema,
+        json!({
+            "type": "array",
+            "prefixItems": [{"type": "string"}, {"type": "number"}],
+            "items": false
+        })
+    );
+
+    apply_draft(&mut schema, JsonSchemaDraft::Draft07);
+    assert_eq!(schema["items"][1]["type"], "number");
+    assert_eq!(schema["additionalItems"], false);
+    assert!(schema.get("prefixItems").is_none());
+}
+
+#[test]
+fn test_json_schema_draft_config() {
+    let json_strings = vec![r#"{"id": 1, "name": null}"#.to_string()];
+    let config = SchemaInferenceConfig {
+        json_schema_draft: Some(JsonSchemaDraft::Draft07),
+        ..Default::default()
+    };
+    let result = crate::infer_json_schema_from_strings(&json_strings, config).unwrap();
+    assert_eq!(
+        result.schema["$schema"],
+        "http://json-schema.org/draft-07/schema#"
+    );
+
+    let config = SchemaInferenceConfig {
+        schema_uri: None,
+        json_schema_draft: Some(JsonSchemaDraft::Draft202012),
+        ..Default::default()
+    };
+    let result = crate::infer_json_schema_from_strings(&json_strings, config).unwrap();
+    assert!(result.schema.get("$schema").is_none());
+}
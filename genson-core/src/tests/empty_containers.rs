@@ -0,0 +1,93 @@
+// genson-core/src/tests/empty_containers.rs
+use super::*;
+use crate::{infer_json_schema_from_strings, SchemaInferenceConfig};
+
+fn infer(rows: &[Value], policy: Option<EmptyContainerPolicy>) -> Value {
+    let input: Vec<String> = rows.iter().map(Value::to_string).collect();
+    let config = SchemaInferenceConfig {
+        empty_container_policy: policy,
+        ..SchemaInferenceConfig::default()
+    };
+    infer_json_schema_from_strings(&input, config)
+        .unwrap()
+        .schema
+}
+
+fn rows() -> Vec<Value> {
+    vec![
+        json!({"id": 1, "meta": {"x": 1}, "tags": ["a"]}),
+        json!({"id": 2, "meta": {}, "tags": []}),
+    ]
+}
+
+#[test]
+fn test_rewrite_empty_containers() {
+    let mut value = json!({"a": {}, "b": [[], {}, 1], "c": {"d": {}}, "e": 0});
+    rewrite_empty_containers(&mut value, EmptyContainerPolicy::Ignore);
+    assert_eq!(value, json!({"b": [1], "e": 0}));
+
+    let mut value = json!({"a": {}, "b": [[], 1], "c": {"d": {}}});
+    rewrite_empty_containers(&mut value, EmptyContainerPolicy::TypeAsNullable);
+    assert_eq!(value, json!({"a": null, "b": [null, 1], "c": {"d": null}}));
+
+    // The document itself is left alone
+    let mut value = json!({});
+    rewrite_empty_containers(&mut value, EmptyContainerPolicy::Ignore);
+    assert_eq!(value, json!({}));
+}
+
+#[test]
+fn test_empty_containers_by_default() {
+    let schema = infer(&rows(), None);
+    // An empty object has no `required` to merge, so the fields of meta stay required
+    assert_eq!(schema["properties"]["meta"]["required"], json!(["x"]));
+    assert_eq!(schema["properties"]["tags"]["items"], json!({"type": "string"}));
+    assert_eq!(schema["required"], json!(["id", "meta", "tags"]));
+}
+
+#[test]
+fn test_ignore() {
+    let schema = infer(&rows(), Some(EmptyContainerPolicy::Ignore));
+    assert_eq!(schema["properties"]["meta"]["required"], json!(["x"]));
+    assert_eq!(schema["properties"]["tags"]["items"], json!({"type": "string"}));
+    // Missing from the rows where they were empty
+    assert_eq!(schema["required"], json!(["id"]));
+}
+
+#[test]
+fn test_type_as_nullable() {
+    let schema = infer(&rows(), Some(EmptyContainerPolicy::TypeAsNullable));
+    let meta = &schema["properties"]["meta"]["anyOf"];
+    assert!(meta.as_array().unwrap().contains(&json!({"type": "null"})));
+    let record = meta
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|branch| branch["type"] == "object")
+        .unwrap();
+    assert_eq!(record["required"], json!(["x"]));
+    assert_eq!(schema["required"], json!(["id", "meta", "tags"]));
+}
+
+#[test]
+fn test_type_as_map_of_string() {
+    let empty = [json!({"meta": {}, "tags": [], "nested": [{}]})];
+    let schema = infer(&empty, Some(EmptyContainerPolicy::TypeAsMapOfString));
+    assert_eq!(
+        schema["properties"]["meta"],
+        json!({"type": "object", "additionalProperties": {"type": "string"}})
+    );
+    assert_eq!(
+        schema["properties"]["tags"],
+        json!({"type": "array", "items": {"type": "string"}})
+    );
+    assert_eq!(
+        schema["properties"]["nested"]["items"],
+        json!({"type": "object", "additionalProperties": {"type": "string"}})
+    );
+
+    // Containers that were ever non-empty keep their inferred type
+    let schema = infer(&rows(), Some(EmptyContainerPolicy::TypeAsMapOfString));
+    assert_eq!(schema["properties"]["tags"]["items"], json!({"type": "string"}));
+    assert!(schema["properties"]["meta"].get("additionalProperties").is_none());
+}
@@ -0,0 +1,175 @@
+// genson-core/src/tests/explain.rs
+use super::*;
+use crate::{infer_json_schema_from_strings, MapDetection, SchemaInferenceConfig};
+use std::collections::HashMap;
+
+fn explain(json: &str, config: SchemaInferenceConfig) -> Vec<FieldExplanation> {
+    let config = SchemaInferenceConfig {
+        explain: true,
+        delimiter: Some(b'\n'),
+        ..config
+    };
+    infer_json_schema_from_strings(&[json.to_string()], config)
+        .expect("inference")
+        .explanation
+        .expect("explanation")
+}
+
+fn at<'a>(explanations: &'a [FieldExplanation], path: &str) -> &'a FieldExplanation {
+    explanations
+        .iter()
+        .find(|e| e.path == path)
+        .unwrap_or_else(|| panic!("no explanation for {}: {:#?}", path, explanations))
+}
+
+const LABELS: &str = "{\"labels\": {\"en\": \"a\", \"fr\": \"b\", \"de\": \"c\"}, \"meta\": {\"x\": 1, \"y\": \"z\"}}\n{\"labels\": {\"es\": \"d\"}, \"meta\": {\"x\": 2, \"y\": \"w\"}}";
+
+#[test]
+fn test_explain_thresholds() {
+    let explanations = explain(
+        LABELS,
+        SchemaInferenceConfig {
+            map_threshold: 3,
+            map_max_required_keys: Some(1),
+            ..Default::default()
+        },
+    );
+
+    let labels = at(&explanations, "/labels");
+    assert_eq!(labels.decision, FieldDecision::Map);
+    assert_eq!(labels.key_count, Some(4));
+    assert_eq!(labels.required_key_count, Some(0));
+    assert_eq!(labels.unification, Some(Unification::Homogeneous));
+
+    let meta = at(&explanations, "/meta");
+    assert_eq!(meta.decision, FieldDecision::Record);
+    assert_eq!(meta.reason, "2 keys is below map_threshold 3");
+    assert_eq!(meta.map_max_required_keys, Some(1));
+}
+
+#[test]
+fn test_explain_required_keys() {
+    let explanations = explain(
+        "{\"meta\": {\"a\": 1, \"b\": 2, \"c\": 3}}",
+        SchemaInferenceConfig {
+            map_threshold: 3,
+            map_max_required_keys: Some(2),
+            ..Default::default()
+        },
+    );
+
+    let meta = at(&explanations, "/meta");
+    assert_eq!(meta.decision, FieldDecision::Record);
+    assert_eq!(meta.reason, "3 required keys is above map_max_required_keys 2");
+}
+
+#[test]
+fn test_explain_forced() {
+    let explanations = explain(
+        LABELS,
+        SchemaInferenceConfig {
+            force_field_types: HashMap::from([("meta".to_string(), "map".to_string())]),
+            ..Default::default()
+        },
+    );
+
+    let meta = at(&explanations, "/meta");
+    assert_eq!(meta.decision, FieldDecision::Map);
+    assert_eq!(meta.forced_by.as_deref(), Some("force_field_types"));
+}
+
+#[test]
+fn test_explain_off_by_default() {
+    let result = infer_json_schema_from_strings(
+        &["{\"a\": 1}".to_string()],
+        SchemaInferenceConfig::default(),
+    )
+    .unwrap();
+    assert!(result.explanation.is_none());
+}
+
+#[test]
+fn test_per_document_variance_keeps_stable_records() {
+    // Two rows with disjoint keys under "tags", the same keys every time under "meta"
+    let rows = "{\"tags\": {\"a\": 1, \"b\": 2, \"c\": 3}, \"meta\": {\"x\": 1, \"y\": 2, \"z\": 3}}\n{\"tags\": {\"d\": 4, \"e\": 5, \"f\": 6}, \"meta\": {\"x\": 4, \"y\": 5, \"z\": 6}}";
+    let merged = explain(
+        rows,
+        SchemaInferenceConfig {
+            map_threshold: 3,
+            ..Default::default()
+        },
+    );
+    assert_eq!(at(&merged, "/tags").decision, FieldDecision::Map);
+    assert_eq!(at(&merged, "/meta").decision, FieldDecision::Map);
+    assert_eq!(at(&merged, "/meta").key_presence, None);
+
+    let variance = explain(
+        rows,
+        SchemaInferenceConfig {
+            map_threshold: 3,
+            map_detection: MapDetection::PerDocumentVariance,
+            ..Default::default()
+        },
+    );
+    let tags = at(&variance, "/tags");
+    assert_eq!(tags.decision, FieldDecision::Map);
+    assert_eq!(tags.key_presence, Some(0.5));
+    let meta = at(&variance, "/meta");
+    assert_eq!(meta.decision, FieldDecision::Record);
+    assert_eq!(meta.key_presence, Some(1.0));
+    assert_eq!(
+        meta.reason,
+        "the keys are stable across documents (mean key presence 1.00 is above 0.5)"
+    );
+}
+
+#[test]
+fn test_per_document_variance_needs_two_objects() {
+    let explanations = explain(
+        "{\"meta\": {\"x\": 1, \"y\": 2, \"z\": 3}}",
+        SchemaInferenceConfig {
+            map_threshold: 3,
+            map_detection: MapDetection::PerDocumentVariance,
+            ..Default::default()
+        },
+    );
+    let meta = at(&explanations, "/meta");
+    assert_eq!(meta.decision, FieldDecision::Map);
+    assert_eq!(meta.key_presence, None);
+}
+
+#[test]
+fn test_decision_log() {
+    let config = SchemaInferenceConfig {
+        decision_log: true,
+        delimiter: Some(b'\n'),
+        map_threshold: 3,
+        unify_maps: true,
+        force_field_types: HashMap::from([("meta".to_string(), "record".to_string())]),
+        ..Default::default()
+    };
+    let input = "{\"labels\": {\"en\": \"a\", \"fr\": \"b\", \"de\": \"c\"}, \"meta\": {\"x\": 1}, \"mix\": {\"a\": {\"p\": 1}, \"b\": {\"q\": 2}, \"c\": {\"r\": 3}}}";
+    let result = infer_json_schema_from_strings(&[input.to_string()], config).unwrap();
+    assert!(result.explanation.is_none());
+    let log = result.decision_log.expect("decision log");
+
+    let events = |path: &str| -> Vec<DecisionKind> {
+        log.iter()
+            .filter(|e| e.path == path)
+            .map(|e| e.event)
+            .collect()
+    };
+    assert_eq!(events("/labels"), [DecisionKind::MapRewrite]);
+    assert_eq!(events("/meta"), [DecisionKind::ForcedOverride]);
+    assert_eq!(
+        events("/mix"),
+        [DecisionKind::UnificationAttempt, DecisionKind::MapRewrite]
+    );
+
+    let attempt = log.iter().find(|e| e.path == "/mix").unwrap();
+    assert_eq!(attempt.outcome, "unified");
+    assert_eq!(attempt.inputs["key_count"], 3);
+    let forced = log.iter().find(|e| e.path == "/meta").unwrap();
+    assert_eq!(forced.outcome, "record");
+    assert_eq!(forced.inputs["forced_by"], "force_field_types");
+}
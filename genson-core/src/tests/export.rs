@@ -0,0 +1,245 @@
+// genson-core/src/tests/export.rs
+use super::delta::to_delta_schema;
+use super::iceberg::to_iceberg_schema;
+use super::*;
+use serde_json::json;
+
+fn sample_schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "id": {"type": "string"},
+            "count": {"type": ["null", "integer"]},
+            "score": {"type": ["integer", "number"]},
+            "mixed": {"type": ["integer", "string"]},
+            "tags": {"type": "array", "items": {"type": "string"}},
+            "labels": {
+                "type": "object",
+                "additionalProperties": {
+                    "type": "object",
+                    "properties": {"value": {"type": "string"}},
+                    "required": ["value"]
+                }
+            },
+            "meta": {
+                "type": "object",
+                "properties": {"ok": {"type": "boolean"}},
+                "required": ["ok"]
+            }
+        },
+        "required": ["id", "score", "mixed", "tags", "labels"]
+    })
+}
+
+#[test]
+fn test_logical_type_model() {
+    let fields = root_fields(&sample_schema()).unwrap();
+    let summary: Vec<(&str, &str, bool)> = fields
+        .iter()
+        .map(|f| (f.name.as_str(), f.data_type.kind(), f.nullable))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            ("id", "string", false),
+            ("count", "long", true),
+            ("score", "double", false),
+            ("mixed", "string", false),
+            ("tags", "list", false),
+            ("labels", "map", false),
+            ("meta", "struct", true),
+        ]
+    );
+}
+
+#[test]
+fn test_anyof_nullable_branch() {
+    let schema = json!({"anyOf": [{"type": "null"}, {"type": "integer"}]});
+    assert_eq!(logical_type(&schema), (LogicalType::Long, true));
+}
+
+#[test]
+fn test_root_must_be_record() {
+    let err = root_fields(&json!({"type": "array", "items": {"type": "string"}})).unwrap_err();
+    assert!(err.contains("found list"));
+}
+
+#[test]
+fn test_delta_schema() {
+    let delta = to_delta_schema(&sample_schema()).unwrap();
+    assert_eq!(delta["type"], "struct");
+    assert_eq!(
+        delta["fields"][0],
+        json!({"name": "id", "type": "string", "nullable": false, "metadata": {}})
+    );
+    assert_eq!(delta["fields"][1]["type"], "long");
+    assert_eq!(delta["fields"][1]["nullable"], true);
+    assert_eq!(
+        delta["fields"][4]["type"],
+        json!({"type": "array", "elementType": "string", "containsNull": false})
+    );
+    assert_eq!(
+        delta["fields"][5]["type"],
+        json!({
+            "type": "map",
+            "keyType": "string",
+            "valueType": {
+                "type": "struct",
+                "fields": [{"name": "value", "type": "string", "nullable": false, "metadata": {}}]
+            },
+            "valueContainsNull": false
+        })
+    );
+}
+
+#[test]
+fn test_iceberg_schema_assigns_ids() {
+    let iceberg = to_iceberg_schema(&sample_schema()).unwrap();
+    assert_eq!(iceberg["schema-id"], 0);
+    assert_eq!(iceberg["type"], "struct");
+
+    let fields = iceberg["fields"].as_array().unwrap();
+    let ids: Vec<u64> = fields.iter().map(|f| f["id"].as_u64().unwrap()).collect();
+    assert_eq!(ids, vec![1, 2, 3, 4, 5, 6, 7]);
+    assert_eq!(fields[0]["required"], true);
+    assert_eq!(fields[1]["required"], false);
+
+    // Nested IDs follow the top-level fields in document order
+    assert_eq!(
+        fields[4]["type"],
+        json!({"type": "list", "element-id": 8, "element-required": true, "element": "string"})
+    );
+    assert_eq!(
+        fields[5]["type"],
+        json!({
+            "type": "map",
+            "key-id": 9,
+            "key": "string",
+            "value-id": 10,
+            "value-required": true,
+            "value": {
+                "type": "struct",
+                "fields": [{"id": 11, "name": "value", "required": true, "type": "string"}]
+            }
+        })
+    );
+    assert_eq!(fields[6]["type"]["fields"][0]["id"], 12);
+}
+
+#[cfg(feature = "arrow")]
+mod arrow_export {
+    use super::super::arrow::{schema_to_ipc, schema_to_json, to_arrow_schema};
+    use super::sample_schema;
+    use arrow::datatypes::DataType;
+    use serde_json::json;
+
+    #[test]
+    fn test_arrow_schema_types() {
+        let schema = to_arrow_schema(&sample_schema()).unwrap();
+        let types: Vec<(&str, &DataType, bool)> = schema
+            .fields()
+            .iter()
+            .map(|f| (f.name().as_str(), f.data_type(), f.is_nullable()))
+            .collect();
+        assert_eq!(types[0], ("id", &DataType::Utf8, false));
+        assert_eq!(types[1], ("count", &DataType::Int64, true));
+        assert_eq!(types[2], ("score", &DataType::Float64, false));
+        assert!(matches!(types[4].1, DataType::List(_)));
+        assert!(matches!(types[5].1, DataType::Map(_, false)));
+        assert!(matches!(types[6].1, DataType::Struct(_)));
+    }
+
+    #[test]
+    fn test_arrow_schema_json() {
+        let schema = to_arrow_schema(&sample_schema()).unwrap();
+        let json = schema_to_json(&schema).unwrap();
+        assert_eq!(
+            json["fields"][0],
+            json!({"name": "id", "nullable": false, "type": {"name": "utf8"}, "children": []})
+        );
+        let labels = &json["fields"][5];
+        assert_eq!(labels["type"], json!({"name": "map", "keysSorted": false}));
+        let entries = &labels["children"][0];
+        assert_eq!(entries["name"], "entries");
+        assert_eq!(entries["children"][0]["name"], "key");
+        assert_eq!(entries["children"][1]["type"], json!({"name": "struct"}));
+    }
+
+    #[test]
+    fn test_arrow_ipc_round_trip() {
+        use arrow::ipc::reader::StreamReader;
+
+        let schema = to_arrow_schema(&sample_schema()).unwrap();
+        let bytes = schema_to_ipc(&schema).unwrap();
+        let reader = StreamReader::try_new(std::io::Cursor::new(bytes), None).unwrap();
+        assert_eq!(reader.schema().as_ref(), &schema);
+    }
+}
+
+#[cfg(feature = "proto")]
+mod proto_export {
+    use super::super::proto::to_proto_schema;
+    use super::sample_schema;
+    use serde_json::json;
+
+    #[test]
+    fn test_proto_schema() {
+        let proto = to_proto_schema(&sample_schema(), "Entity", Some("example.v1")).unwrap();
+        assert_eq!(
+            proto,
+            r#"syntax = "proto3";
+
+package example.v1;
+
+message Entity {
+  string id = 1;
+  optional int64 count = 2;
+  double score = 3;
+  string mixed = 4;
+  repeated string tags = 5;
+  map<string, Labels> labels = 6;
+  Meta meta = 7;
+
+  message Labels {
+    string value = 1;
+  }
+
+  message Meta {
+    bool ok = 1;
+  }
+}
+"#
+        );
+    }
+
+    #[test]
+    fn test_proto_wraps_nested_collections() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "matrix": {"type": "array", "items": {"type": "array", "items": {"type": "integer"}}},
+                "by-lang": {"type": "object", "additionalProperties": {"type": "array", "items": {"type": "string"}}},
+            },
+            "required": ["matrix", "by-lang"]
+        });
+        let proto = to_proto_schema(&schema, "Root", None).unwrap();
+        assert_eq!(
+            proto,
+            r#"syntax = "proto3";
+
+message Root {
+  repeated MatrixValues matrix = 1;
+  map<string, ByLangValues> by_lang = 2 [json_name = "by-lang"];
+
+  message MatrixValues {
+    repeated int64 values = 1;
+  }
+
+  message ByLangValues {
+    repeated string values = 1;
+  }
+}
+"#
+        );
+    }
+}
@@ -0,0 +1,106 @@
+// genson-core/src/tests/field_order.rs
+use super::*;
+use crate::{infer_json_schema_from_strings, SchemaInferenceConfig, SchemaInferenceSession};
+use serde_json::json;
+
+fn infer(docs: &[&str], sort_fields: FieldOrder) -> Value {
+    let config = SchemaInferenceConfig {
+        sort_fields,
+        ..Default::default()
+    };
+    let docs: Vec<String> = docs.iter().map(|d| d.to_string()).collect();
+    infer_json_schema_from_strings(&docs, config).unwrap().schema
+}
+
+fn keys(value: &Value) -> Vec<&str> {
+    value.as_object().unwrap().keys().map(String::as_str).collect()
+}
+
+#[test]
+fn test_preserve_keeps_first_seen_order() {
+    let schema = infer(&[r#"{"z": 1, "a": 2}"#], FieldOrder::Preserve);
+    assert_eq!(keys(&schema["properties"]), ["z", "a"]);
+}
+
+#[test]
+fn test_alphabetical_ignores_row_order() {
+    let first = infer(
+        &[r#"{"z": 1, "a": {"y": 1, "b": 2}}"#, r#"{"m": true}"#],
+        FieldOrder::Alphabetical,
+    );
+    let second = infer(
+        &[r#"{"m": true}"#, r#"{"a": {"b": 2, "y": 1}, "z": 1}"#],
+        FieldOrder::Alphabetical,
+    );
+    assert_eq!(first, second);
+    assert_eq!(keys(&first["properties"]), ["a", "m", "z"]);
+    assert_eq!(keys(&first["properties"]["a"]["properties"]), ["b", "y"]);
+    assert_eq!(first["properties"]["a"]["required"], json!(["b", "y"]));
+}
+
+#[test]
+fn test_by_frequency() {
+    let schema = infer(
+        &[
+            r#"{"rare": 1, "common": 1, "b": 1}"#,
+            r#"{"common": 2, "a": 1}"#,
+            r#"{"common": 3, "b": 2, "a": 2}"#,
+        ],
+        FieldOrder::ByFrequency,
+    );
+    // Ties (a and b, seen twice) are sorted by name
+    assert_eq!(keys(&schema["properties"]), ["common", "a", "b", "rare"]);
+    assert_eq!(schema["required"], json!(["common"]));
+}
+
+#[test]
+fn test_by_frequency_below_map() {
+    let config = SchemaInferenceConfig {
+        sort_fields: FieldOrder::ByFrequency,
+        map_threshold: 2,
+        unify_maps: true,
+        ..Default::default()
+    };
+    let docs = vec![r#"{"labels": {"en": {"a": 1, "z": "en"}, "fr": {"z": "fr"}}}"#.to_string()];
+    let schema = infer_json_schema_from_strings(&docs, config).unwrap().schema;
+    let values = &schema["properties"]["labels"]["additionalProperties"];
+    // `z` is under both map keys, `a` under one
+    assert_eq!(keys(&values["properties"]), ["z", "a"]);
+}
+
+#[test]
+fn test_by_frequency_across_sessions() {
+    let config = SchemaInferenceConfig {
+        sort_fields: FieldOrder::ByFrequency,
+        ..Default::default()
+    };
+    let mut session = SchemaInferenceSession::new(config.clone());
+    session.add_strings(&[r#"{"a": 1, "b": 1}"#.to_string()]).unwrap();
+    let mut other = SchemaInferenceSession::new(config);
+    other
+        .add_strings(&[r#"{"b": 2}"#.to_string(), r#"{"b": 3}"#.to_string()])
+        .unwrap();
+    session.merge(other).unwrap();
+
+    let schema = session.result().unwrap().schema;
+    assert_eq!(keys(&schema["properties"]), ["b", "a"]);
+}
+
+#[cfg(feature = "avro")]
+#[test]
+fn test_avro_fields_follow_order() {
+    let config = SchemaInferenceConfig {
+        sort_fields: FieldOrder::Alphabetical,
+        avro: true,
+        ..Default::default()
+    };
+    let docs = vec![r#"{"z": 1, "a": "x"}"#.to_string()];
+    let schema = infer_json_schema_from_strings(&docs, config).unwrap().schema;
+    let names: Vec<&str> = schema["fields"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|f| f["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(names, ["a", "z"]);
+}
@@ -0,0 +1,78 @@
+// genson-core/src/tests/field_path.rs
+use super::*;
+
+fn path(segments: &[&str]) -> Vec<String> {
+    segments.iter().map(|s| s.to_string()).collect()
+}
+
+#[test]
+fn test_bare_names_match_anywhere() {
+    assert!(key_matches("value", Some("value"), &path(&["a", "value"])));
+    assert!(key_matches("value", Some("value"), &path(&["value"])));
+    assert!(!key_matches("value", Some("other"), &path(&["other"])));
+    assert!(!key_matches("value", None, &path(&["value"])));
+}
+
+#[test]
+fn test_pointer_and_dotted_patterns() {
+    let p = path(&["claims", "P31", "mainsnak", "datavalue"]);
+    assert!(key_matches("/claims/*/mainsnak/datavalue", Some("datavalue"), &p));
+    assert!(key_matches("claims.*.mainsnak.datavalue", Some("datavalue"), &p));
+    assert!(key_matches("/claims/P31/mainsnak/datavalue", Some("datavalue"), &p));
+    assert!(!key_matches("/claims/P279/mainsnak/datavalue", Some("datavalue"), &p));
+    // Patterns are anchored at both ends
+    assert!(!key_matches("/mainsnak/datavalue", Some("datavalue"), &p));
+    assert!(!key_matches("/claims/*", Some("datavalue"), &p));
+}
+
+#[test]
+fn test_double_star_matches_any_depth() {
+    let p = path(&["claims", "P31", "qualifiers", "P580", "datavalue"]);
+    assert!(key_matches("/claims/**/datavalue", Some("datavalue"), &p));
+    assert!(key_matches("/**/datavalue", Some("datavalue"), &p));
+    assert!(key_matches("**.P580.datavalue", Some("datavalue"), &p));
+    assert!(!key_matches("/claims/**/value", Some("datavalue"), &p));
+}
+
+#[test]
+fn test_pattern_requires_name_to_be_last_segment() {
+    // A node visited under another name at the same path is not the targeted field
+    let p = path(&["claims"]);
+    assert!(key_matches("/claims", Some("claims"), &p));
+    assert!(!key_matches("/claims", Some("properties"), &p));
+}
+
+#[test]
+fn test_pointer_escapes() {
+    let p = path(&["a/b", "c~d"]);
+    assert!(key_matches("/a~1b/c~0d", Some("c~d"), &p));
+}
+
+#[test]
+fn test_pointer() {
+    assert_eq!(pointer(&[]), "");
+    assert_eq!(pointer(&path(&["a/b", "*", "c~d"])), "/a~1b/*/c~0d");
+    assert!(pointer_matches("/labels/*/value", "/labels/en/value"));
+    assert!(!pointer_matches("/labels/*/value", "/labels/en"));
+    assert!(pointer_matches("/a~1b", "/a~1b"));
+}
+
+#[test]
+fn test_child_path() {
+    let p = child_path(&path(&["a"]), MAP_VALUES_SEGMENT);
+    assert_eq!(p, path(&["a", "*"]));
+    assert!(is_path_pattern("/a"));
+    assert!(is_path_pattern("a.b"));
+    assert!(!is_path_pattern("a"));
+}
+
+#[test]
+fn test_leads_to() {
+    assert!(leads_to("/claims/*/mainsnak", &path(&["claims"])));
+    assert!(leads_to("claims.*.mainsnak", &path(&["claims", "P31"])));
+    assert!(!leads_to("/claims/*/mainsnak", &path(&["claims", "P31", "mainsnak"])));
+    assert!(!leads_to("/claims/*/mainsnak", &path(&["labels"])));
+    assert!(leads_to("/claims/**/datavalue", &path(&["claims", "P31", "qualifiers"])));
+    // Bare names can be at any depth, so lead nowhere in particular
+    assert!(!leads_to("mainsnak", &path(&["claims"])));
+}
@@ -0,0 +1,173 @@
+// genson-core/src/tests/formats.rs
+use super::*;
+use crate::schema::document_stats::DocumentStats;
+use crate::{infer_json_schema_from_strings, SchemaInferenceConfig};
+use serde_json::json;
+
+const UUID: &str = "6f1c1d52-8e0a-4a3b-9d55-2f1f9e6a7b10";
+
+fn infer(rows: &[Value], detection: FormatDetection) -> Result<Value, String> {
+    let input: Vec<String> = rows.iter().map(Value::to_string).collect();
+    let config = SchemaInferenceConfig {
+        detect_formats: Some(detection),
+        ..SchemaInferenceConfig::default()
+    };
+    infer_json_schema_from_strings(&input, config).map(|result| result.schema)
+}
+
+/// The formats seen in `rows`, read as one NDJSON input (so sampling runs across them).
+fn string_formats(rows: &[Value], detection: FormatDetection) -> StringFormats {
+    let input: Vec<String> = rows.iter().map(Value::to_string).collect();
+    let config = SchemaInferenceConfig {
+        delimiter: Some(b'\n'),
+        detect_formats: Some(detection),
+        ..SchemaInferenceConfig::default()
+    };
+    DocumentStats::collect(&[input.join("\n").as_str()], &config).string_formats
+}
+
+#[test]
+fn test_detectors() {
+    assert!(is_uuid(UUID));
+    assert!(is_uuid("6F1C1D52-8E0A-4A3B-9D55-2F1F9E6A7B10"));
+    assert!(!is_uuid("6f1c1d528e0a4a3b9d552f1f9e6a7b10"));
+    assert!(is_email("a.b@example.com"));
+    assert!(!is_email("a@localhost"));
+    assert!(!is_email("a b@example.com"));
+    assert!(is_uri("https://example.com/x?y=1"));
+    assert!(is_uri("urn:isbn:0451450523"));
+    assert!(!is_uri("12:30"));
+    assert!(!is_uri("note: see https://example.com"));
+    assert!(is_base64("SGVsbG8sIFdvcmxkIQ=="));
+    assert!(!is_base64("d41d8cd98f00b204e9800998ecf8427e"));
+    assert!(!is_base64("ABCDEFGHIJKLMNOP"));
+}
+
+#[test]
+fn test_formats_annotated() {
+    let rows = [
+        json!({
+            "id": UUID,
+            "site": "https://example.com",
+            "contact": "a@example.com",
+            "host": "10.0.0.1",
+            "host6": "::1",
+            "blob": "SGVsbG8sIFdvcmxkIQ==",
+            "name": "alice"
+        }),
+        json!({
+            "id": "0b7e7dee-87b0-4c7c-8c5e-6b0a8f0b2f11",
+            "site": "http://example.org/a",
+            "contact": "b@example.org",
+            "host": "192.168.1.20",
+            "host6": "fe80::1",
+            "blob": "AAECAwQFBgcICQoLDA0ODw==",
+            "name": "bob"
+        }),
+    ];
+    let schema = infer(&rows, FormatDetection::default()).unwrap();
+    let props = &schema["properties"];
+
+    assert_eq!(props["id"]["format"], "uuid");
+    assert_eq!(props["site"]["format"], "uri");
+    assert_eq!(props["contact"]["format"], "email");
+    assert_eq!(props["host"]["format"], "ipv4");
+    assert_eq!(props["host6"]["format"], "ipv6");
+    assert_eq!(props["blob"]["contentEncoding"], "base64");
+    assert_eq!(props["name"], json!({"type": "string"}));
+}
+
+#[test]
+fn test_one_mismatch_drops_the_format() {
+    let rows = [json!({"id": UUID}), json!({"id": "not-a-uuid"})];
+    let schema = infer(&rows, FormatDetection::default()).unwrap();
+    assert!(schema["properties"]["id"].get("format").is_none());
+}
+
+#[test]
+fn test_disabled_formats_not_detected() {
+    let detection = FormatDetection {
+        uuid: false,
+        ..Default::default()
+    };
+    let schema = infer(&[json!({"id": UUID, "site": "https://a.io"})], detection).unwrap();
+    assert!(schema["properties"]["id"].get("format").is_none());
+    assert_eq!(schema["properties"]["site"]["format"], "uri");
+}
+
+#[test]
+fn test_sample_rate_checks_a_fraction() {
+    let strings: Vec<Value> = (0..10)
+        .map(|i| json!({"id": if i == 5 { "x".to_string() } else { UUID.to_string() }}))
+        .collect();
+    let formats = string_formats(
+        &strings,
+        FormatDetection {
+            sample_rate: 0.5,
+            ..Default::default()
+        },
+    );
+    let candidates = &formats["/id"];
+    assert_eq!(candidates.seen, 10);
+    assert_eq!(candidates.checked, 5);
+    // Every other string from the first is checked, so the mismatch at row 5 is missed
+    assert_eq!(candidates.formats.first(), Some(&StringFormat::Uuid));
+}
+
+#[test]
+fn test_invalid_sample_rate() {
+    let detection = FormatDetection {
+        sample_rate: 0.0,
+        ..Default::default()
+    };
+    let err = infer(&[json!({"id": UUID})], detection).unwrap_err();
+    assert!(err.contains("Invalid detect_formats sample_rate"));
+}
+
+#[test]
+fn test_map_values_combine_keys() {
+    let mut schema = json!({
+        "type": "object",
+        "properties": {
+            "refs": {"type": "object", "additionalProperties": {"type": "string"}}
+        }
+    });
+    let documents = [json!({"refs": {"a": UUID, "b": UUID}})];
+    let formats = string_formats(&documents, FormatDetection::default());
+    apply_string_formats(&mut schema, &formats);
+    assert_eq!(
+        schema["properties"]["refs"]["additionalProperties"]["format"],
+        "uuid"
+    );
+
+    let documents = [json!({"refs": {"a": UUID, "b": "plain"}})];
+    let formats = string_formats(&documents, FormatDetection::default());
+    schema["properties"]["refs"]["additionalProperties"] = json!({"type": "string"});
+    apply_string_formats(&mut schema, &formats);
+    assert!(schema["properties"]["refs"]["additionalProperties"]
+        .get("format")
+        .is_none());
+}
+
+#[test]
+fn test_nullable_strings_annotated() {
+    let rows = [json!({"id": UUID}), json!({"id": null})];
+    let schema = infer(&rows, FormatDetection::default()).unwrap();
+    assert_eq!(schema["properties"]["id"]["format"], "uuid");
+}
+
+#[cfg(feature = "avro")]
+#[test]
+fn test_avro_uuid_logical_type() {
+    let input = vec![json!({"id": UUID}).to_string()];
+    let config = SchemaInferenceConfig {
+        avro: true,
+        detect_formats: Some(FormatDetection::default()),
+        ..SchemaInferenceConfig::default()
+    };
+    let schema = infer_json_schema_from_strings(&input, config).unwrap().schema;
+    assert_eq!(
+        schema["fields"][0]["type"],
+        json!({"type": "string", "logicalType": "uuid"})
+    );
+}
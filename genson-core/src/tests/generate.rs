@@ -0,0 +1,115 @@
+// genson-core/src/tests/generate.rs
+use super::*;
+use crate::conformity::violations;
+use serde_json::json;
+
+fn json_schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "id": {"type": "integer"},
+            "score": {"type": ["null", "number"]},
+            "when": {"type": "string", "format": "date-time"},
+            "kind": {"type": "string", "enum": ["a", "b"]},
+            "tags": {"type": "array", "items": {"type": "string"}},
+            "labels": {
+                "type": "object",
+                "additionalProperties": {"type": "string"}
+            },
+            "owner": {
+                "type": "object",
+                "properties": {"name": {"type": "string"}, "active": {"type": "boolean"}},
+                "required": ["name"]
+            }
+        },
+        "required": ["id", "kind", "tags"]
+    })
+}
+
+#[test]
+fn test_generate_conforms_to_json_schema() {
+    let schema = json_schema();
+    let rows = generate_rows(&schema, &GenerateConfig::default()).unwrap();
+    assert_eq!(rows.len(), 100);
+    for row in &rows {
+        assert!(violations(row, &schema).is_empty(), "{}", row);
+    }
+    assert!(rows.iter().any(|r| r.get("owner").is_none()));
+    assert!(rows.iter().any(|r| r["score"].is_null()));
+}
+
+#[test]
+fn test_generate_is_deterministic_per_seed() {
+    let schema = json_schema();
+    let cfg = GenerateConfig {
+        rows: 20,
+        seed: 7,
+        ..GenerateConfig::default()
+    };
+    let first = generate_rows(&schema, &cfg).unwrap();
+    assert_eq!(first, generate_rows(&schema, &cfg).unwrap());
+    let other = GenerateConfig { seed: 8, ..cfg };
+    assert_ne!(first, generate_rows(&schema, &other).unwrap());
+}
+
+#[test]
+fn test_generate_map_key_cardinality() {
+    let schema = json_schema();
+    let cfg = GenerateConfig {
+        map_key_cardinality: 2,
+        null_probability: 0.0,
+        ..GenerateConfig::default()
+    };
+    let rows = generate_rows(&schema, &cfg).unwrap();
+    let mut keys: Vec<&str> = rows
+        .iter()
+        .flat_map(|r| r["labels"].as_object().unwrap().keys())
+        .map(String::as_str)
+        .collect();
+    keys.sort_unstable();
+    keys.dedup();
+    assert_eq!(keys, ["key_0", "key_1"]);
+    assert!(rows.iter().all(|r| r.get("owner").is_some()));
+    assert!(rows.iter().all(|r| !r["score"].is_null()));
+}
+
+#[test]
+fn test_generate_from_avro() {
+    let schema = json!({
+        "type": "record",
+        "name": "document",
+        "namespace": "genson",
+        "fields": [
+            {"name": "id", "type": "long"},
+            {"name": "kind", "type": {"type": "enum", "name": "Kind", "symbols": ["X", "Y"]}},
+            {"name": "again", "type": ["null", "Kind"]},
+            {"name": "claims", "type": {"type": "map", "values": {
+                "type": "array", "items": "string"
+            }}},
+            {"name": "next", "type": ["null", "document"]}
+        ]
+    });
+    let rows = generate_rows(&schema, &GenerateConfig::default()).unwrap();
+    for row in &rows {
+        assert!(row["id"].is_i64());
+        assert!(["X", "Y"].contains(&row["kind"].as_str().unwrap()));
+        assert!(row["again"].is_null() || row["again"].is_string());
+        assert!(row["claims"].is_object());
+    }
+    assert!(rows.iter().any(|r| r["next"].is_object()));
+}
+
+#[test]
+fn test_generate_rejects_bad_config() {
+    let cfg = GenerateConfig {
+        null_probability: 1.5,
+        ..GenerateConfig::default()
+    };
+    assert!(generate_rows(&json_schema(), &cfg).is_err());
+    assert_eq!(
+        generate_rows(&json!({"type": "record", "name": "r", "fields": [
+            {"name": "x", "type": "Missing"}
+        ]}), &GenerateConfig::default()),
+        Err("Unknown Avro type: Missing".to_string())
+    );
+}
@@ -0,0 +1,71 @@
+// genson-core/src/tests/invalid_json.rs
+use super::*;
+use crate::infer_json_schema_from_strings;
+
+fn parse_error(document: &str) -> serde_json::Error {
+    serde_json::from_str::<serde_json::Value>(document).unwrap_err()
+}
+
+#[test]
+fn test_excerpt_centres_on_the_error() {
+    let document = format!(r#"{{"a": "{}", "b": nope, "c": "{}"}}"#, "x".repeat(500), "y".repeat(500));
+    let offset = error_offset(&document, &parse_error(&document));
+    // Parsing fails on the `o`, expecting `null`
+    assert!(document[..offset].ends_with(r#""b": n"#));
+
+    let quoted = excerpt(&document, offset, 40);
+    assert!(quoted.starts_with("[skipped 496 chars] ..."), "{}", quoted);
+    assert!(quoted.contains(r#""b": nope"#), "{}", quoted);
+    assert!(quoted.ends_with("... [truncated 493 chars]"), "{}", quoted);
+}
+
+#[test]
+fn test_excerpt_at_the_ends() {
+    let document = "é".repeat(30);
+    assert_eq!(excerpt(&document, 0, 30), document);
+    assert_eq!(excerpt(&document, 0, 10), format!("{}... [truncated 20 chars]", "é".repeat(10)));
+    assert_eq!(
+        excerpt(&document, document.len(), 10),
+        format!("[skipped 20 chars] ...{}", "é".repeat(10))
+    );
+}
+
+#[test]
+fn test_error_offset_on_later_lines() {
+    let document = "{\n  \"a\": 1,\n  \"b\": ?\n}";
+    let offset = error_offset(document, &parse_error(document));
+    assert_eq!(&document[offset..offset + 1], "?");
+}
+
+#[test]
+fn test_invalid_json_error_names_the_line_and_dump() {
+    let dir = tempfile::tempdir().unwrap();
+    let config = SchemaInferenceConfig {
+        delimiter: Some(b'\n'),
+        error_context_chars: 20,
+        error_dump_dir: Some(dir.path().to_path_buf()),
+        ..SchemaInferenceConfig::default()
+    };
+    let bad_line = format!(r#"{{"padding": "{}", "broken": }}"#, "p".repeat(100));
+    let input = format!("{{\"ok\": 1}}\n{}\n", bad_line);
+    let error = infer_json_schema_from_strings(&[input], config).unwrap_err();
+
+    assert!(error.starts_with("Invalid JSON input at index 1, line 2: "), "{}", error);
+    assert!(error.contains(r#""broken": }"#), "{}", error);
+    assert!(!error.contains(&"p".repeat(30)), "{}", error);
+
+    let (_, path) = error.split_once(" - full document: ").unwrap();
+    assert_eq!(std::fs::read_to_string(path).unwrap(), bad_line);
+}
+
+#[test]
+fn test_invalid_json_error_reports_failed_dump() {
+    let file = tempfile::NamedTempFile::new().unwrap();
+    let config = SchemaInferenceConfig {
+        error_dump_dir: Some(file.path().join("dumps")),
+        ..SchemaInferenceConfig::default()
+    };
+    let error = invalid_json_error(0, None, "{", &parse_error("{"), &config);
+    assert!(error.starts_with("Invalid JSON input at index 1: "), "{}", error);
+    assert!(error.contains(" - failed to write the full document to "), "{}", error);
+}
@@ -0,0 +1,86 @@
+// genson-core/src/tests/key_fold.rs
+use super::*;
+use crate::{infer_json_schema_from_strings, SchemaInferenceConfig};
+use serde_json::json;
+
+fn infer(fold: KeyFold) -> crate::SchemaInferenceResult {
+    let config = SchemaInferenceConfig {
+        delimiter: Some(b'\n'),
+        key_fold: Some(fold),
+        ..SchemaInferenceConfig::default()
+    };
+    let input = "{\"ID\": 1, \"Name\": \"a\"}\n{\"id\": 2, \"name\": \"b\", \"Id\": 3}\n";
+    infer_json_schema_from_strings(&[input], config).unwrap()
+}
+
+#[test]
+fn test_fold_keys_keeps_the_first_colliding_value() {
+    let mut value = json!({"ID": 1, "id": 2, "Items": [{"Key": "a"}]});
+    fold_keys(&mut value, KeyFold::Lower);
+    assert_eq!(value, json!({"id": 1, "items": [{"key": "a"}]}));
+}
+
+#[test]
+fn test_key_fold_lower_and_upper() {
+    let result = infer(KeyFold::Lower);
+    let props: Vec<&String> = result.schema["properties"].as_object().unwrap().keys().collect();
+    assert_eq!(props, ["id", "name"]);
+    assert_eq!(result.schema["required"], json!(["id", "name"]));
+    assert_eq!(
+        result.key_collisions,
+        [
+            KeyCollision {
+                name: "id".to_string(),
+                keys: vec!["ID".to_string(), "id".to_string(), "Id".to_string()],
+            },
+            KeyCollision {
+                name: "name".to_string(),
+                keys: vec!["Name".to_string(), "name".to_string()],
+            },
+        ]
+    );
+
+    let result = infer(KeyFold::Upper);
+    assert!(result.schema["properties"]["NAME"].is_object());
+    assert_eq!(result.key_collisions[0].name, "ID");
+}
+
+#[test]
+fn test_key_fold_preserve_uses_the_first_spelling() {
+    let result = infer(KeyFold::Preserve);
+    let props: Vec<&String> = result.schema["properties"].as_object().unwrap().keys().collect();
+    assert_eq!(props, ["ID", "Name"]);
+    assert_eq!(result.schema["required"], json!(["ID", "Name"]));
+    assert_eq!(result.key_collisions[1].name, "Name");
+}
+
+#[test]
+fn test_no_key_fold_keeps_keys_apart() {
+    let config = SchemaInferenceConfig {
+        delimiter: Some(b'\n'),
+        ..SchemaInferenceConfig::default()
+    };
+    let result = infer_json_schema_from_strings(&["{\"ID\": 1}\n{\"id\": 2}"], config).unwrap();
+    assert_eq!(result.schema["properties"].as_object().unwrap().len(), 2);
+    assert!(result.key_collisions.is_empty());
+}
+
+#[cfg(feature = "avro")]
+#[test]
+fn test_normalise_with_key_fold() {
+    use crate::normalise::{try_normalise_values, NormaliseConfig, UnknownFieldPolicy};
+    let schema = json!({"type": "record", "name": "document", "fields": [
+        {"name": "ID", "type": "long"},
+        {"name": "Name", "type": ["null", "string"]}
+    ]});
+    let cfg = NormaliseConfig {
+        key_fold: Some(KeyFold::Preserve),
+        unknown_fields: UnknownFieldPolicy::Error,
+        ..NormaliseConfig::default()
+    };
+    let rows = vec![json!({"id": 1, "NAME": "a"}), json!({"Id": 2})];
+    assert_eq!(
+        try_normalise_values(rows, &schema, &cfg).unwrap(),
+        [json!({"ID": 1, "Name": "a"}), json!({"ID": 2, "Name": null})]
+    );
+}
@@ -0,0 +1,68 @@
+// genson-core/src/tests/limits.rs
+use super::*;
+
+#[test]
+fn test_unlimited_admits_everything() {
+    let limits = ResourceLimits::default();
+    assert!(limits.is_unlimited());
+    let mut tracker = LimitTracker::new(&limits);
+    for _ in 0..1000 {
+        tracker.admit(1 << 20).unwrap();
+    }
+}
+
+#[test]
+fn test_max_rows() {
+    let limits = ResourceLimits {
+        max_rows: Some(2),
+        ..Default::default()
+    };
+    let mut tracker = LimitTracker::new(&limits);
+    tracker.admit(10).unwrap();
+    tracker.admit(10).unwrap();
+    assert_eq!(tracker.admit(10), Err(LimitExceeded::MaxRows(2)));
+}
+
+#[test]
+fn test_max_bytes_rejects_row_that_would_overflow() {
+    let limits = ResourceLimits {
+        max_bytes: Some(25),
+        ..Default::default()
+    };
+    let mut tracker = LimitTracker::new(&limits);
+    tracker.admit(10).unwrap();
+    assert_eq!(tracker.admit(20), Err(LimitExceeded::MaxBytes(25)));
+    // The rejected row was not counted, so a smaller one still fits
+    tracker.admit(15).unwrap();
+}
+
+#[test]
+fn test_max_runtime() {
+    let limits = ResourceLimits {
+        max_runtime: Some(Duration::ZERO),
+        ..Default::default()
+    };
+    let tracker = LimitTracker::new(&limits);
+    assert_eq!(
+        tracker.check_runtime(),
+        Err(LimitExceeded::MaxRuntime(Duration::ZERO))
+    );
+}
+
+#[test]
+fn test_resolve_by_action() {
+    let exceeded = LimitExceeded::MaxRows(5);
+
+    let error = LimitTracker::new(&ResourceLimits::default()).resolve(exceeded);
+    assert_eq!(
+        error,
+        Err("Resource limit exceeded: row limit of 5 reached".to_string())
+    );
+
+    let partial = LimitTracker::new(&ResourceLimits {
+        on_limit: LimitAction::Partial,
+        ..Default::default()
+    })
+    .resolve(exceeded);
+    assert_eq!(partial, Ok(exceeded));
+}
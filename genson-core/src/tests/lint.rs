@@ -0,0 +1,120 @@
+// genson-core/src/tests/lint.rs
+use super::*;
+use serde_json::json;
+
+fn rules(findings: &[LintFinding]) -> Vec<(LintRule, &str)> {
+    findings.iter().map(|f| (f.rule, f.path.as_str())).collect()
+}
+
+#[test]
+fn test_clean_avro_schema_has_no_findings() {
+    let schema = json!({
+        "type": "record",
+        "name": "document",
+        "fields": [
+            {"name": "id", "type": "string"},
+            {"name": "note", "type": ["null", "string"], "default": null},
+            {"name": "labels", "type": {
+                "type": "map",
+                "values": {"type": "record", "name": "label", "fields": [
+                    {"name": "value", "type": "string"}
+                ]}
+            }}
+        ]
+    });
+    assert!(lint_schema(&schema, &LintConfig::default()).is_empty());
+}
+
+#[test]
+fn test_avro_findings_for_each_rule() {
+    let schema = json!({
+        "type": "record",
+        "name": "document",
+        "fields": [
+            {"name": "bad-name", "type": "string"},
+            {"name": "note", "type": ["null", "string"]},
+            {"name": "labels", "type": {"type": "map", "values": "string"}},
+            {"name": "outer", "type": {"type": "record", "name": "outer", "fields": [
+                {"name": "inner", "type": {"type": "record", "name": "inner", "fields": [
+                    {"name": "mixed", "type": ["string", "long"]}
+                ]}}
+            ]}}
+        ]
+    });
+    let findings = lint_schema(&schema, &LintConfig::default());
+    assert_eq!(
+        rules(&findings),
+        vec![
+            (LintRule::FieldName, "bad-name"),
+            (LintRule::OptionalDefault, "note"),
+            (LintRule::MapValueRecord, "labels"),
+            (LintRule::UnionDepth, "outer.inner.mixed"),
+        ]
+    );
+}
+
+#[test]
+fn test_union_depth_is_configurable() {
+    let schema = json!({
+        "type": "record",
+        "name": "document",
+        "fields": [{"name": "mixed", "type": ["string", "long"]}]
+    });
+
+    let mut cfg = LintConfig::default();
+    assert!(lint_schema(&schema, &cfg).is_empty());
+
+    cfg.max_union_depth = Some(0);
+    assert_eq!(
+        rules(&lint_schema(&schema, &cfg)),
+        vec![(LintRule::UnionDepth, "mixed")]
+    );
+
+    cfg.disable(LintRule::UnionDepth);
+    assert!(lint_schema(&schema, &cfg).is_empty());
+}
+
+#[test]
+fn test_json_schema_findings() {
+    let schema = json!({
+        "type": "object",
+        "properties": {
+            "id": {"type": "string"},
+            "maybe": {"type": "integer"},
+            "labels": {"type": "object", "additionalProperties": {"type": "string"}},
+            "items": {"type": "array", "items": {
+                "type": "object",
+                "properties": {"x y": {"type": "string"}},
+                "required": ["x y"]
+            }}
+        },
+        "required": ["id", "labels", "items"]
+    });
+    let findings = lint_schema(&schema, &LintConfig::default());
+    assert_eq!(
+        rules(&findings),
+        vec![
+            (LintRule::OptionalDefault, "maybe"),
+            (LintRule::MapValueRecord, "labels"),
+            (LintRule::FieldName, "items[].x y"),
+        ]
+    );
+}
+
+#[test]
+fn test_rule_names_round_trip() {
+    for rule in LintRule::ALL {
+        assert_eq!(rule.name().parse::<LintRule>(), Ok(rule));
+        assert_eq!(serde_json::to_value(rule).unwrap(), json!(rule.name()));
+    }
+    assert!("nonsense".parse::<LintRule>().is_err());
+}
+
+#[test]
+fn test_valid_avro_names() {
+    assert!(is_valid_avro_name("_private"));
+    assert!(is_valid_avro_name("field_2"));
+    assert!(!is_valid_avro_name("2field"));
+    assert!(!is_valid_avro_name("with-dash"));
+    assert!(!is_valid_avro_name(""));
+}
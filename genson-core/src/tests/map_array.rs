@@ -0,0 +1,72 @@
+// genson-core/src/tests/map_array.rs
+use super::*;
+use crate::{infer_json_schema_from_strings, SchemaInferenceConfig};
+
+fn infer(rows: &[Value], coerce_map_array: bool) -> Value {
+    let rows: Vec<String> = rows.iter().map(Value::to_string).collect();
+    let config = SchemaInferenceConfig {
+        coerce_map_array,
+        ..Default::default()
+    };
+    infer_json_schema_from_strings(&rows, config)
+        .expect("inference")
+        .schema
+}
+
+fn mixed_rows() -> Vec<Value> {
+    vec![
+        json!({"l": {"en": ["a"], "fr": ["b"]}}),
+        json!({"l": [{"en": "c"}, {"en": "d", "de": "e"}]}),
+    ]
+}
+
+#[test]
+fn test_union_kept_without_coercion() {
+    let schema = infer(&mixed_rows(), false);
+    assert!(schema["properties"]["l"]["anyOf"].is_array());
+}
+
+#[test]
+fn test_union_collapses_to_map_of_arrays() {
+    let schema = infer(&mixed_rows(), true);
+    assert_eq!(
+        schema["properties"]["l"],
+        json!({
+            "type": "object",
+            "properties": {
+                "en": {"type": "array", "items": {"type": "string"}},
+                "fr": {"type": "array", "items": {"type": "string"}},
+                "de": {"type": "array", "items": {"type": "string"}}
+            },
+            "required": ["en"]
+        })
+    );
+}
+
+#[test]
+fn test_value_types_are_merged() {
+    let rows = vec![
+        json!({"l": {"n": [1]}}),
+        json!({"l": [{"n": "x"}]}),
+        json!({"l": null}),
+    ];
+    let schema = infer(&rows, true);
+    let branches = schema["properties"]["l"]["anyOf"].as_array().unwrap();
+    assert_eq!(branches.len(), 2);
+    let map = branches.iter().find(|b| b["type"] == "object").unwrap();
+    assert_eq!(
+        map["properties"]["n"]["items"]["type"],
+        json!(["integer", "string"])
+    );
+}
+
+#[test]
+fn test_other_unions_untouched() {
+    let mut schema = json!({"anyOf": [
+        {"type": "object", "properties": {"a": {"type": "string"}}},
+        {"type": "array", "items": {"type": "object", "properties": {"a": {"type": "string"}}}}
+    ]});
+    let before = schema.clone();
+    coerce_map_arrays(&mut schema);
+    assert_eq!(schema, before);
+}
@@ -0,0 +1,100 @@
+// genson-core/src/tests/map_keys.rs
+use super::*;
+use crate::{infer_json_schema_from_strings, SchemaInferenceConfig};
+use serde_json::json;
+
+fn infer(rows: &[Value]) -> Value {
+    let input: Vec<String> = rows.iter().map(Value::to_string).collect();
+    let config = SchemaInferenceConfig {
+        detect_numeric_keys: true,
+        map_threshold: 2,
+        ..SchemaInferenceConfig::default()
+    };
+    infer_json_schema_from_strings(&input, config)
+        .unwrap()
+        .schema
+}
+
+#[test]
+fn test_is_integer_key() {
+    assert!(is_integer_key("0"));
+    assert!(is_integer_key("42"));
+    assert!(is_integer_key("007"));
+    assert!(is_integer_key("-3"));
+    assert!(!is_integer_key(""));
+    assert!(!is_integer_key("-"));
+    assert!(!is_integer_key("+3"));
+    assert!(!is_integer_key("1.5"));
+    assert!(!is_integer_key("Q42"));
+    assert!(!is_integer_key("99999999999999999999"));
+}
+
+#[test]
+fn test_numeric_keys_annotated() {
+    let rows = [
+        json!({"scores": {"1": 10, "2": 20}, "labels": {"en": "a", "fr": "b"}}),
+        json!({"scores": {"42": 7, "7": 1}, "labels": {"de": "c", "es": "d"}}),
+    ];
+    let schema = infer(&rows);
+    let props = &schema["properties"];
+    assert_eq!(props["scores"][MAP_KEY_TYPE_KEY], "integer");
+    assert!(props["labels"].get(MAP_KEY_TYPE_KEY).is_none());
+}
+
+#[test]
+fn test_numeric_keys_need_every_document() {
+    let rows = [
+        json!({"scores": {"1": 10, "2": 20}}),
+        json!({"scores": {"3": 7, "x": 1}}),
+    ];
+    let schema = infer(&rows);
+    assert!(schema["properties"]["scores"].get(MAP_KEY_TYPE_KEY).is_none());
+}
+
+#[test]
+fn test_numeric_keys_under_maps() {
+    // The inner maps are found under every key of the outer map
+    let rows = [json!({"by_user": {
+        "alice": {"1": "a", "2": "b"},
+        "bob": {"1": "c", "2": "d"}
+    }})];
+    let schema = infer(&rows);
+    let by_user = &schema["properties"]["by_user"];
+    assert!(by_user.get(MAP_KEY_TYPE_KEY).is_none());
+    assert_eq!(by_user["additionalProperties"][MAP_KEY_TYPE_KEY], "integer");
+}
+
+#[test]
+fn test_numeric_keys_off_by_default() {
+    let input = vec![json!({"scores": {"1": 10, "2": 20}}).to_string()];
+    let config = SchemaInferenceConfig {
+        map_threshold: 2,
+        ..SchemaInferenceConfig::default()
+    };
+    let schema = infer_json_schema_from_strings(&input, config)
+        .unwrap()
+        .schema;
+    assert!(schema["properties"]["scores"].get(MAP_KEY_TYPE_KEY).is_none());
+}
+
+#[cfg(feature = "avro")]
+#[test]
+fn test_numeric_keys_avro() {
+    let input = vec![json!({"scores": {"1": 10, "2": 20}}).to_string()];
+    let config = SchemaInferenceConfig {
+        detect_numeric_keys: true,
+        map_threshold: 2,
+        avro: true,
+        ..SchemaInferenceConfig::default()
+    };
+    let avro = infer_json_schema_from_strings(&input, config)
+        .unwrap()
+        .schema;
+    let scores = &avro["fields"][0]["type"];
+    assert_eq!(scores["type"], "map");
+    assert_eq!(scores[MAP_KEY_TYPE_KEY], "integer");
+
+    // And back again
+    let schema = crate::avro::avro_to_json_schema(&avro).unwrap();
+    assert_eq!(schema["properties"]["scores"][MAP_KEY_TYPE_KEY], "integer");
+}
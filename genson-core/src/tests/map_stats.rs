@@ -0,0 +1,94 @@
+// genson-core/src/tests/map_stats.rs
+use super::*;
+use crate::{infer_json_schema_from_strings, SchemaInferenceConfig, SchemaInferenceSession};
+use serde_json::json;
+
+fn config(top_k: usize) -> SchemaInferenceConfig {
+    SchemaInferenceConfig {
+        map_threshold: 3,
+        map_key_stats: Some(top_k),
+        ..SchemaInferenceConfig::default()
+    }
+}
+
+fn rows() -> Vec<String> {
+    [
+        json!({"id": 1, "labels": {"en": "a", "fr": "b", "de": "c"}}),
+        json!({"id": 2, "labels": {"en": "d"}}),
+        json!({"id": 3, "labels": {"en": "e", "fr": "f", "es": "g", "it": "h"}}),
+    ]
+    .iter()
+    .map(Value::to_string)
+    .collect()
+}
+
+#[test]
+fn test_map_key_stats() {
+    let result = infer_json_schema_from_strings(&rows(), config(2)).unwrap();
+    assert_eq!(
+        result.map_key_stats,
+        Some(vec![MapKeyStats {
+            path: "/labels".to_string(),
+            objects: 3,
+            distinct_keys: 5,
+            min_keys: 1,
+            max_keys: 4,
+            top_keys: vec![
+                KeyFrequency {
+                    key: "en".to_string(),
+                    count: 3
+                },
+                KeyFrequency {
+                    key: "fr".to_string(),
+                    count: 2
+                },
+            ],
+        }])
+    );
+
+    // Records aren't reported, and nothing is without the setting
+    let result = infer_json_schema_from_strings(&rows(), SchemaInferenceConfig::default());
+    assert_eq!(result.unwrap().map_key_stats, None);
+}
+
+#[test]
+fn test_nested_maps_combine_their_keys() {
+    let rows: Vec<String> = [
+        json!({"by_lang": {"en": {"a": 1}, "fr": {"a": 2, "b": 3}, "de": {"c": 4}}}),
+        json!({"by_lang": {"es": {"a": 5, "b": 6, "c": 7}}}),
+    ]
+    .iter()
+    .map(Value::to_string)
+    .collect();
+    let config = SchemaInferenceConfig {
+        map_threshold: 2,
+        unify_maps: true,
+        ..config(DEFAULT_TOP_KEYS)
+    };
+    let stats = infer_json_schema_from_strings(&rows, config)
+        .unwrap()
+        .map_key_stats
+        .unwrap();
+    let paths: Vec<&str> = stats.iter().map(|s| s.path.as_str()).collect();
+    assert_eq!(paths, vec!["/by_lang", "/by_lang/*"]);
+    assert_eq!(
+        (stats[1].objects, stats[1].distinct_keys, stats[1].min_keys, stats[1].max_keys),
+        (4, 3, 1, 3)
+    );
+}
+
+#[test]
+fn test_sessions_merge_key_stats() {
+    let rows = rows();
+    let mut session = SchemaInferenceSession::new(config(DEFAULT_TOP_KEYS));
+    session.add_strings(&rows[..1]).unwrap();
+    let mut other = SchemaInferenceSession::new(config(DEFAULT_TOP_KEYS));
+    other.add_strings(&rows[1..]).unwrap();
+    session.merge(other).unwrap();
+
+    let merged = session.result().unwrap().map_key_stats;
+    let whole = infer_json_schema_from_strings(&rows, config(DEFAULT_TOP_KEYS))
+        .unwrap()
+        .map_key_stats;
+    assert_eq!(merged, whole);
+}
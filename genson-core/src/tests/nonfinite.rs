@@ -0,0 +1,46 @@
+// genson-core/src/tests/nonfinite.rs
+use super::*;
+use crate::{infer_json_schema_from_strings, SchemaInferenceConfig};
+use serde_json::json;
+
+#[test]
+fn test_scrub_literals() {
+    let text = r#"{"a": NaN, "b": [Infinity, -Infinity], "c": 1.5}"#;
+    assert_eq!(
+        scrub_nonfinite(text, NonFinitePolicy::Null),
+        r#"{"a": null, "b": [null, null], "c": 1.5}"#
+    );
+    assert_eq!(
+        scrub_nonfinite(text, NonFinitePolicy::String),
+        r#"{"a": "NaN", "b": ["Infinity", "-Infinity"], "c": 1.5}"#
+    );
+    assert_eq!(
+        nonfinite_as_number(text),
+        r#"{"a": 0.0, "b": [0.0, 0.0], "c": 1.5}"#
+    );
+}
+
+#[test]
+fn test_scrub_leaves_strings_alone() {
+    let text = r#"{"NaN": "Infinity \" NaN", "x": NaNa}"#;
+    assert!(matches!(
+        scrub_nonfinite(text, NonFinitePolicy::Null),
+        Cow::Borrowed(_)
+    ));
+    assert!(is_nonfinite("-Infinity"));
+    assert!(!is_nonfinite("nan"));
+}
+
+#[test]
+fn test_inference_types_literals_as_number() {
+    let input = vec![r#"{"a": 1}"#.to_string(), r#"{"a": NaN}"#.to_string()];
+    let config = SchemaInferenceConfig {
+        nonfinite_policy: Some(NonFinitePolicy::Null),
+        ..SchemaInferenceConfig::default()
+    };
+    let schema = infer_json_schema_from_strings(&input, config).unwrap().schema;
+    assert_eq!(schema["properties"]["a"], json!({"type": "number"}));
+
+    // Without a policy the input is invalid
+    assert!(infer_json_schema_from_strings(&input, SchemaInferenceConfig::default()).is_err());
+}
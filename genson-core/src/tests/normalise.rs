@@ -168,3 +168,747 @@ fn test_normalise_map_of_records_with_null() {
 
     assert_eq!(normalised, expected);
 }
+
+#[test]
+fn test_normalise_values_with_limits() {
+    let schema = json!({
+        "type": "record",
+        "name": "doc",
+        "fields": [{"name": "a", "type": ["null", "long"]}]
+    });
+    let values = vec![json!({"a": 1}), json!({"a": 2}), json!({"a": 3})];
+    let cfg = NormaliseConfig::default();
+
+    let unlimited =
+        normalise_values_with_limits(values.clone(), &schema, &cfg, &ResourceLimits::default())
+            .unwrap();
+    assert_eq!(unlimited, (values.clone(), None));
+
+    let limits = ResourceLimits {
+        max_rows: Some(2),
+        on_limit: crate::LimitAction::Partial,
+        ..Default::default()
+    };
+    let (partial, exceeded) =
+        normalise_values_with_limits(values.clone(), &schema, &cfg, &limits).unwrap();
+    assert_eq!(partial, values[..2]);
+    assert_eq!(exceeded, Some(LimitExceeded::MaxRows(2)));
+
+    let limits = ResourceLimits {
+        max_bytes: Some(10),
+        ..Default::default()
+    };
+    let err = normalise_values_with_limits(values, &schema, &cfg, &limits).unwrap_err();
+    assert!(err.contains("byte limit of 10 reached"));
+}
+
+#[test]
+fn test_normalise_wrap_root_dotted_path() {
+    let schema = json!({
+        "type": "record",
+        "name": "document",
+        "fields": [{"name": "entity", "type": {
+            "type": "record",
+            "name": "entity",
+            "fields": [{"name": "claims", "type": {
+                "type": "record",
+                "name": "claims",
+                "fields": [{"name": "id", "type": "string"}]
+            }}]
+        }}]
+    });
+    let cfg = NormaliseConfig {
+        wrap_root: Some("entity.claims".to_string()),
+        ..Default::default()
+    };
+
+    let wrapped = json!({"entity": {"claims": {"id": "Q6"}}});
+    let normalised = normalise_values(vec![json!({"id": "Q5"}), wrapped.clone()], &schema, &cfg);
+    assert_eq!(
+        normalised,
+        vec![json!({"entity": {"claims": {"id": "Q5"}}}), wrapped]
+    );
+}
+
+#[test]
+fn test_normalise_unknown_fields() {
+    let schema = json!({
+        "type": "record",
+        "name": "document",
+        "fields": [
+            {"name": "id", "type": "string"},
+            {"name": "meta", "type": ["null", {
+                "type": "record",
+                "name": "meta",
+                "fields": [{"name": "source", "type": "string"}]
+            }]}
+        ]
+    });
+    let row = json!({"id": "a", "extra": 1, "meta": {"source": "x"}});
+
+    // Drop (default)
+    let cfg = NormaliseConfig::default();
+    let normalised = try_normalise_values(vec![row.clone()], &schema, &cfg).unwrap();
+    assert_eq!(normalised, vec![json!({"id": "a", "meta": {"source": "x"}})]);
+
+    // Keep
+    let cfg = NormaliseConfig {
+        unknown_fields: UnknownFieldPolicy::Keep,
+        ..Default::default()
+    };
+    let normalised = normalise_values(vec![row.clone()], &schema, &cfg);
+    assert_eq!(
+        normalised,
+        vec![json!({"id": "a", "meta": {"source": "x"}, "extra": 1})]
+    );
+
+    // Error, reporting the nested path
+    let cfg = NormaliseConfig {
+        unknown_fields: UnknownFieldPolicy::Error,
+        ..Default::default()
+    };
+    let nested = json!({"id": "a", "meta": {"source": "x", "lang": "en"}});
+    let err = try_normalise_values(vec![nested], &schema, &cfg).unwrap_err();
+    assert_eq!(err, "Unknown field 'meta.lang' is not in the schema");
+    assert!(try_normalise_values(vec![json!({"id": "b"})], &schema, &cfg).is_ok());
+}
+
+#[test]
+fn test_normalise_values_with_report() {
+    let schema = json!({
+        "type": "record",
+        "name": "document",
+        "fields": [
+            {"name": "id", "type": "int"},
+            {"name": "tags", "type": ["null", {"type": "array", "items": "string"}]},
+            {"name": "labels", "type": ["null", {"type": "map", "values": "string"}]}
+        ]
+    });
+    let cfg = NormaliseConfig {
+        coerce_string: true,
+        ..Default::default()
+    };
+    let values = vec![
+        json!({"id": 1, "tags": ["a"], "labels": {"en": "Hi"}}),
+        json!({"id": "42", "tags": [], "labels": {"en": 5}, "extra": true}),
+    ];
+
+    let (normalised, report) = normalise_values_with_report(values, &schema, &cfg).unwrap();
+    assert_eq!(normalised[1], json!({"id": 42, "tags": null, "labels": {"en": "5"}}));
+
+    let entry = |path: &str, original: &str, action| Coercion {
+        row: 1,
+        path: path.to_string(),
+        original: original.to_string(),
+        action,
+    };
+    assert_eq!(
+        report,
+        vec![
+            entry("id", "string", CoercionAction::CoercedFromString),
+            entry("tags", "array", CoercionAction::EmptyToNull),
+            entry("labels.en", "number", CoercionAction::Stringified),
+            entry("extra", "boolean", CoercionAction::DroppedUnknownField),
+        ]
+    );
+}
+
+#[test]
+fn test_normalise_bare_roots() {
+    let schema = json!({
+        "type": "record",
+        "name": "document",
+        "fields": [
+            {"name": "value", "type": ["null", {"type": "array", "items": "int"}]}
+        ]
+    });
+    let out = normalise_values(
+        vec![json!([1, 2, 3]), json!(null), json!({"value": [4]})],
+        &schema,
+        &NormaliseConfig::default(),
+    );
+    assert_eq!(
+        out,
+        vec![
+            json!({"value": [1, 2, 3]}),
+            json!({"value": null}),
+            json!({"value": [4]}),
+        ]
+    );
+
+    let schema = json!({
+        "type": "record",
+        "name": "document",
+        "fields": [{"name": "text", "type": "string"}]
+    });
+    let cfg = NormaliseConfig {
+        root_value_field: "text".to_string(),
+        ..NormaliseConfig::default()
+    };
+    let out = try_normalise_values(vec![json!("string")], &schema, &cfg).unwrap();
+    assert_eq!(out, vec![json!({"text": "string"})]);
+}
+
+#[test]
+fn test_normalise_folds_array_of_maps() {
+    let schema = json!({
+        "type": "record",
+        "name": "document",
+        "fields": [{
+            "name": "l",
+            "type": ["null", {
+                "type": "map",
+                "values": {"type": "array", "items": "string"}
+            }]
+        }]
+    });
+    let rows = vec![
+        json!({"l": {"en": ["a"]}}),
+        json!({"l": [{"en": "b"}, {"en": "c", "de": "d"}]}),
+    ];
+    let plain = normalise_values(rows.clone(), &schema, &NormaliseConfig::default());
+    assert_eq!(
+        plain[1],
+        json!({"l": {"l__unknown": [r#"{"en":"b"}"#, r#"{"en":"c","de":"d"}"#]}})
+    );
+
+    let cfg = NormaliseConfig {
+        coerce_map_array: true,
+        ..NormaliseConfig::default()
+    };
+    let (out, report) = normalise_values_with_report(rows, &schema, &cfg).unwrap();
+    assert_eq!(out[0], json!({"l": {"en": ["a"]}}));
+    assert_eq!(out[1], json!({"l": {"en": ["b", "c"], "de": ["d"]}}));
+    assert_eq!(report.len(), 1);
+    assert_eq!(report[0].action, CoercionAction::FoldedArrayOfMaps);
+}
+
+#[test]
+fn test_reencode_maps() {
+    let schema = json!({
+        "type": "record",
+        "name": "document",
+        "fields": [
+            {"name": "id", "type": "int"},
+            {"name": "labels", "type": ["null", {"type": "map", "values": "string"}]},
+            {"name": "claims", "type": {"type": "array", "items": {
+                "type": "map", "values": {"type": "array", "items": "int"}
+            }}}
+        ]
+    });
+    let mapping = json!({
+        "id": 1,
+        "labels": {"en": "Hello", "fr": "Bonjour"},
+        "claims": [{"P31": [5]}]
+    });
+    let kv = json!({
+        "id": 1,
+        "labels": [{"key": "en", "value": "Hello"}, {"key": "fr", "value": "Bonjour"}],
+        "claims": [[{"key": "P31", "value": [5]}]]
+    });
+    let kv_out = reencode_maps(
+        mapping.clone(),
+        &schema,
+        MapEncoding::Mapping,
+        MapEncoding::KeyValueEntries,
+    );
+    assert_eq!(kv_out, kv);
+    let entries = reencode_maps(kv, &schema, MapEncoding::KeyValueEntries, MapEncoding::Entries);
+    assert_eq!(
+        entries["labels"],
+        json!([{"en": "Hello"}, {"fr": "Bonjour"}])
+    );
+    assert_eq!(
+        reencode_maps(entries, &schema, MapEncoding::Entries, MapEncoding::Mapping),
+        mapping
+    );
+
+    let null_labels = json!({"id": 2, "labels": null, "claims": []});
+    assert_eq!(
+        reencode_maps(
+            null_labels.clone(),
+            &schema,
+            MapEncoding::Mapping,
+            MapEncoding::KeyValueEntries
+        ),
+        null_labels
+    );
+}
+
+#[test]
+fn test_normalise_threads_keep_row_order() {
+    let schema = json!({
+        "type": "record",
+        "name": "document",
+        "fields": [
+            {"name": "id", "type": "int"},
+            {"name": "tags", "type": ["null", {"type": "array", "items": "string"}]}
+        ]
+    });
+    let values: Vec<Value> = (0..200)
+        .map(|id| {
+            if id % 3 == 0 {
+                json!({"id": id, "tags": "solo"})
+            } else {
+                json!({"id": id, "tags": []})
+            }
+        })
+        .collect();
+    let run = |threads| {
+        let cfg = NormaliseConfig {
+            threads,
+            ..NormaliseConfig::default()
+        };
+        normalise_values_with_report(values.clone(), &schema, &cfg).unwrap()
+    };
+
+    let (serial, serial_report) = run(Some(1));
+    for threads in [None, Some(4)] {
+        let (rows, report) = run(threads);
+        assert_eq!(rows, serial);
+        assert_eq!(report, serial_report);
+    }
+    let ids: Vec<i64> = serial.iter().map(|r| r["id"].as_i64().unwrap()).collect();
+    assert_eq!(ids, (0..200).collect::<Vec<_>>());
+    assert_eq!(serial[3]["tags"], json!(["solo"]));
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn test_thread_pool_is_reused() {
+    use std::sync::Arc;
+    let slot = CachedPool::new(None);
+    let pool = cached_pool(&slot, 3).unwrap();
+    assert_eq!(pool.current_num_threads(), 3);
+    assert!(Arc::ptr_eq(&pool, &cached_pool(&slot, 3).unwrap()));
+    // Another thread count replaces the pool rather than adding one
+    let other = cached_pool(&slot, 2).unwrap();
+    assert_eq!(other.current_num_threads(), 2);
+    assert!(Arc::ptr_eq(&other, slot.lock().unwrap().as_ref().unwrap()));
+}
+
+#[test]
+fn test_normalise_null_only_union() {
+    let schema = json!({
+        "type": "record",
+        "name": "document",
+        "fields": [{"name": "gone", "type": ["null"]}]
+    });
+    let cfg = NormaliseConfig::default();
+    assert_eq!(
+        normalise_value(json!({"gone": 1}), &schema, &cfg, None),
+        json!({"gone": null})
+    );
+}
+
+#[test]
+fn test_normalise_config_from_overrides() {
+    let cfg = NormaliseConfig::from_overrides(&json!({"map_encoding": "kv", "threads": 2}))
+        .unwrap();
+    assert_eq!(cfg.map_encoding, MapEncoding::KeyValueEntries);
+    assert_eq!(cfg.threads, Some(2));
+    assert!(cfg.empty_as_null);
+
+    assert_eq!(
+        NormaliseConfig::from_overrides(&json!({"empty_as_nul": false})).unwrap_err(),
+        "Unknown normalise setting: empty_as_nul"
+    );
+    assert!(NormaliseConfig::from_overrides(&json!({"map_encoding": "list"})).is_err());
+    assert!(NormaliseConfig::from_overrides(&json!([])).is_err());
+}
+
+#[test]
+fn test_encode_map_schemas_avro() {
+    let schema = json!({
+        "type": "record",
+        "name": "document",
+        "fields": [
+            {"name": "labels", "type": ["null", {"type": "map", "values": "string"}]},
+            {"name": "claims", "type": {"type": "map", "values": {
+                "type": "map", "values": "int"
+            }}}
+        ]
+    });
+    assert_eq!(encode_map_schemas(&schema, MapEncoding::Mapping), schema);
+
+    let kv = encode_map_schemas(&schema, MapEncoding::KeyValueEntries);
+    assert_eq!(
+        kv["fields"][0]["type"][1],
+        json!({"type": "array", "items": {
+            "type": "record",
+            "name": "labels_entry",
+            "fields": [{"name": "key", "type": "string"}, {"name": "value", "type": "string"}]
+        }})
+    );
+    let claims = &kv["fields"][1]["type"]["items"];
+    assert_eq!(claims["name"], "claims_entry");
+    assert_eq!(claims["fields"][1]["type"]["items"]["name"], "claims_entry2");
+
+    let entries = encode_map_schemas(&schema, MapEncoding::Entries);
+    assert_eq!(
+        entries["fields"][0]["type"][1],
+        json!({"type": "array", "items": {"type": "map", "values": "string"}})
+    );
+
+    // The rows normalised with kv are valid against the kv schema
+    let row = normalise_value(
+        json!({"labels": {"en": "Hello"}, "claims": {"P31": {"a": 5}}}),
+        &schema,
+        &NormaliseConfig {
+            map_encoding: MapEncoding::KeyValueEntries,
+            ..NormaliseConfig::default()
+        },
+        None,
+    );
+    assert_eq!(
+        row,
+        json!({
+            "labels": [{"key": "en", "value": "Hello"}],
+            "claims": [{"key": "P31", "value": [{"key": "a", "value": 5}]}]
+        })
+    );
+}
+
+#[test]
+fn test_encode_map_schemas_json() {
+    let schema = json!({
+        "type": "object",
+        "properties": {
+            "id": {"type": "string"},
+            "labels": {
+                "type": ["null", "object"],
+                "description": "Localised labels",
+                "additionalProperties": {"type": "string"}
+            }
+        },
+        "required": ["id"]
+    });
+    let kv = encode_map_schemas(&schema, MapEncoding::KeyValueEntries);
+    assert_eq!(kv["properties"]["id"], schema["properties"]["id"]);
+    assert_eq!(
+        kv["properties"]["labels"],
+        json!({
+            "type": ["null", "array"],
+            "description": "Localised labels",
+            "items": {
+                "type": "object",
+                "properties": {"key": {"type": "string"}, "value": {"type": "string"}},
+                "required": ["key", "value"]
+            }
+        })
+    );
+
+    let entries = encode_map_schemas(&schema, MapEncoding::Entries);
+    assert_eq!(
+        entries["properties"]["labels"]["items"],
+        json!({
+            "type": "object",
+            "additionalProperties": {"type": "string"},
+            "minProperties": 1,
+            "maxProperties": 1
+        })
+    );
+}
+
+#[test]
+fn test_value_limits() {
+    let schema = json!({
+        "type": "record",
+        "name": "doc",
+        "fields": [
+            {"name": "name", "type": "string"},
+            {"name": "tags", "type": {"type": "array", "items": "string"}},
+            {"name": "meta", "type": ["null", {
+                "type": "record",
+                "name": "meta",
+                "fields": [{"name": "inner", "type": ["null", {"type": "map", "values": "int"}]}]
+            }]}
+        ]
+    });
+    let row = json!({
+        "name": "élan vital",
+        "tags": ["a", "b", "c"],
+        "meta": {"inner": {"k": 1}}
+    });
+    let cfg = NormaliseConfig {
+        value_limits: ValueLimits {
+            max_string_len: Some(4),
+            max_array_items: Some(2),
+            max_depth: Some(3),
+            marker: "…".to_string(),
+        },
+        ..NormaliseConfig::default()
+    };
+    let (out, report) = normalise_values_with_report(vec![row.clone()], &schema, &cfg).unwrap();
+    assert_eq!(
+        out[0],
+        json!({"name": "élan…", "tags": ["a", "b"], "meta": {"inner": {"k": null}}})
+    );
+    let actions: Vec<(&str, CoercionAction)> =
+        report.iter().map(|c| (c.path.as_str(), c.action)).collect();
+    assert_eq!(
+        actions,
+        [
+            ("name", CoercionAction::TruncatedString),
+            ("tags", CoercionAction::TruncatedArray),
+            ("meta.inner.k", CoercionAction::TruncatedDepth),
+        ]
+    );
+
+    // Values within the limits are left alone
+    let cfg = NormaliseConfig {
+        value_limits: ValueLimits {
+            max_string_len: Some(10),
+            max_array_items: Some(3),
+            max_depth: Some(4),
+            ..ValueLimits::default()
+        },
+        ..NormaliseConfig::default()
+    };
+    let (_, report) = normalise_values_with_report(vec![row], &schema, &cfg).unwrap();
+    assert!(report.is_empty(), "{:?}", report);
+}
+
+#[test]
+fn test_value_limits_default_marker() {
+    let cfg = NormaliseConfig::from_overrides(&json!({
+        "value_limits": {"max_string_len": 2}
+    }))
+    .unwrap();
+    assert_eq!(
+        normalise_value(json!("abc"), &json!("string"), &cfg, None),
+        json!("ab...[truncated]")
+    );
+}
+
+#[test]
+fn test_integer_map_keys() {
+    let schema = json!({
+        "type": "record",
+        "name": "doc",
+        "fields": [
+            {"name": "scores", "type": {"type": "map", "values": "int", "x-key-type": "integer"}}
+        ]
+    });
+    let row = json!({"scores": {"007": 1, "42": 2, "x": 3}});
+
+    // Keys stay strings in a mapping, and are only rewritten when asked
+    let mapping = normalise_value(row.clone(), &schema, &NormaliseConfig::default(), None);
+    assert_eq!(mapping, row);
+    let canonical = NormaliseConfig {
+        canonical_numeric_keys: true,
+        ..NormaliseConfig::default()
+    };
+    assert_eq!(
+        normalise_value(row.clone(), &schema, &canonical, None),
+        json!({"scores": {"7": 1, "42": 2, "x": 3}})
+    );
+
+    // kv entries get integer keys, and drop the entries that can't have one
+    let kv = NormaliseConfig {
+        map_encoding: MapEncoding::KeyValueEntries,
+        canonical_numeric_keys: true,
+        ..NormaliseConfig::default()
+    };
+    let (out, report) = normalise_values_with_report(vec![row], &schema, &kv).unwrap();
+    assert_eq!(
+        out[0],
+        json!({"scores": [{"key": 7, "value": 1}, {"key": 42, "value": 2}]})
+    );
+    let actions: Vec<_> = report.iter().map(|c| (c.path.as_str(), c.action)).collect();
+    assert_eq!(
+        actions,
+        [
+            ("scores", CoercionAction::CanonicalisedKey),
+            ("scores.x", CoercionAction::DroppedMapEntry)
+        ]
+    );
+
+    // Re-encoding reads the integer keys back
+    let back = reencode_maps(
+        out[0].clone(),
+        &schema,
+        MapEncoding::KeyValueEntries,
+        MapEncoding::Mapping,
+    );
+    assert_eq!(back, json!({"scores": {"7": 1, "42": 2}}));
+
+    let kv_schema = encode_map_schemas(&schema, MapEncoding::KeyValueEntries);
+    assert_eq!(
+        kv_schema["fields"][0]["type"]["items"]["fields"][0],
+        json!({"name": "key", "type": "long"})
+    );
+}
+
+#[test]
+fn test_unpack_sparse_arrays() {
+    let schema = json!({
+        "type": "record",
+        "name": "document",
+        "fields": [{"name": "tags", "type": {"type": "array", "items": "string"}}]
+    });
+    let rows = vec![
+        json!({"tags": {"1": "b", "0": "a"}}),
+        json!({"tags": {"0": "a", "2": "c"}}),
+    ];
+
+    // Without detection the object is wrapped as one item
+    let out = normalise_values(rows.clone(), &schema, &NormaliseConfig::default());
+    assert_eq!(out[0], json!({"tags": [r#"{"1":"b","0":"a"}"#]}));
+
+    let cfg = NormaliseConfig {
+        sparse_array_detection: true,
+        ..NormaliseConfig::default()
+    };
+    let (out, report) = normalise_values_with_report(rows, &schema, &cfg).unwrap();
+    assert_eq!(out[0], json!({"tags": ["a", "b"]}));
+    assert_eq!(out[1], json!({"tags": [r#"{"0":"a","2":"c"}"#]}));
+    assert_eq!(report[0].action, CoercionAction::UnpackedSparseArray);
+}
+
+#[test]
+fn test_forced_types_parse_strings() {
+    let schema = json!({
+        "type": "record",
+        "name": "document",
+        "fields": [
+            {"name": "price", "type": "float", "x-forced-type": "number"},
+            {"name": "qty", "type": ["null", "int"], "x-forced-type": "integer"},
+            {"name": "ok", "type": "boolean", "x-forced-type": "boolean"},
+            {"name": "note", "type": "int"}
+        ]
+    });
+    let rows = vec![json!({"price": 12, "qty": "3", "ok": "true", "note": "4"})];
+    let (out, report) =
+        normalise_values_with_report(rows, &schema, &NormaliseConfig::default()).unwrap();
+    // Only the forced fields are parsed without `coerce_string`
+    assert_eq!(
+        out[0],
+        json!({"price": 12.0, "qty": 3, "ok": true, "note": null})
+    );
+    let actions: Vec<_> = report.iter().map(|c| (c.path.as_str(), c.action)).collect();
+    assert_eq!(
+        actions,
+        [
+            ("qty", CoercionAction::CoercedFromString),
+            ("ok", CoercionAction::CoercedFromString),
+            ("note", CoercionAction::Nulled)
+        ]
+    );
+}
+
+#[test]
+fn test_normalise_strict() {
+    let schema = json!({
+        "type": "record",
+        "name": "doc",
+        "fields": [
+            {"name": "id", "type": "int"},
+            {"name": "name", "type": ["null", "string"]},
+            {"name": "score", "type": "double", "default": 0.0},
+            {"name": "value", "type": ["int", "boolean"]},
+        ]
+    });
+    let cfg = NormaliseConfig::default();
+
+    let row = json!({"id": 1, "value": 2});
+    assert_eq!(
+        normalise_value_strict(row, &schema, &cfg),
+        Ok(json!({"id": 1, "name": null, "score": null, "value": 2}))
+    );
+
+    let row = json!({"id": "one", "value": 2});
+    assert_eq!(
+        normalise_value_strict(row, &schema, &cfg),
+        Err(NormaliseError::TypeMismatch {
+            path: "id".to_string(),
+            expected: "int".to_string(),
+            found: "string".to_string(),
+        })
+    );
+
+    let row = json!({"value": 2});
+    let err = normalise_value_strict(row, &schema, &cfg).unwrap_err();
+    assert_eq!(err.to_string(), "Required field 'id' is missing");
+
+    // Unions normalise against their first branch, even where a later one would fit
+    let row = json!({"id": 1, "value": true});
+    assert_eq!(
+        normalise_value_strict(row, &schema, &cfg),
+        Err(NormaliseError::UnionResolution {
+            path: "value".to_string(),
+            found: "boolean".to_string(),
+            branches: vec!["int".to_string(), "boolean".to_string()],
+        })
+    );
+}
+
+#[test]
+fn test_normalise_values_strict() {
+    let schema = json!({
+        "type": "record",
+        "name": "doc",
+        "fields": [
+            {"name": "id", "type": "int"},
+            {"name": "tags", "type": {"type": "array", "items": "long"}},
+            {"name": "meta", "type": {"type": "record", "name": "meta", "fields": [
+                {"name": "ok", "type": "boolean"}
+            ]}},
+        ]
+    });
+    let rows = vec![
+        json!({"id": 1, "tags": [1], "meta": {"ok": true}}),
+        json!({"id": 2.5, "tags": [1, "x"], "meta": "no"}),
+        json!({"id": 3, "tags": 7, "meta": {}}),
+    ];
+    let cfg = NormaliseConfig::default();
+    let errors = normalise_values_strict(rows.clone(), &schema, &cfg).unwrap_err();
+    let mismatch = |path: &str, expected: &str, found: &str| NormaliseError::TypeMismatch {
+        path: path.to_string(),
+        expected: expected.to_string(),
+        found: found.to_string(),
+    };
+    assert_eq!(
+        errors,
+        vec![
+            RowErrors {
+                row: 1,
+                errors: vec![
+                    mismatch("id", "int", "number"),
+                    mismatch("tags", "long", "string"),
+                    mismatch("meta", "record", "string"),
+                ],
+            },
+            RowErrors {
+                row: 2,
+                errors: vec![NormaliseError::MissingRequired {
+                    path: "meta.ok".to_string()
+                }],
+            },
+        ]
+    );
+
+    // Strings that parse aren't errors with `coerce_string`
+    let cfg = NormaliseConfig {
+        coerce_string: true,
+        ..NormaliseConfig::default()
+    };
+    let rows = vec![json!({"id": "4", "tags": ["5"], "meta": {"ok": "true"}})];
+    assert_eq!(
+        normalise_values_strict(rows, &schema, &cfg),
+        Ok(vec![json!({"id": 4, "tags": [5], "meta": {"ok": true}})])
+    );
+
+    let cfg = NormaliseConfig {
+        unknown_fields: UnknownFieldPolicy::Error,
+        ..NormaliseConfig::default()
+    };
+    let rows = vec![json!({"id": 1, "tags": [], "meta": {"ok": true, "x": 1}})];
+    let errors = normalise_values_strict(rows, &schema, &cfg).unwrap_err();
+    assert_eq!(
+        errors[0].errors,
+        vec![NormaliseError::UnknownField {
+            path: "meta.x".to_string()
+        }]
+    );
+}
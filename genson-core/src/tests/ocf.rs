@@ -0,0 +1,65 @@
+// genson-core/src/tests/ocf.rs
+use super::*;
+use crate::normalise::{normalise_values, NormaliseConfig};
+use crate::{infer_json_schema_from_strings, SchemaInferenceConfig};
+use apache_avro::Reader;
+use serde_json::json;
+
+fn inferred_avro_schema(json: &str) -> Value {
+    let config = SchemaInferenceConfig {
+        avro: true,
+        delimiter: Some(b'\n'),
+        ..Default::default()
+    };
+    infer_json_schema_from_strings(&[json.to_string()], config)
+        .expect("inference")
+        .schema
+}
+
+#[test]
+fn test_normalised_rows_round_trip() {
+    let input = "{\"id\": 1, \"name\": \"Alice\", \"tags\": [\"a\"]}\n{\"id\": 2, \"score\": 1.5}";
+    let schema = inferred_avro_schema(input);
+    let rows = input
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    let normalised = normalise_values(rows, &schema, &NormaliseConfig::default());
+
+    let mut buf = Vec::new();
+    let count = write_ocf(&schema, normalised.clone().into_iter().map(Ok), &mut buf).unwrap();
+    assert_eq!(count, 2);
+
+    let read: Vec<Value> = Reader::new(&buf[..])
+        .unwrap()
+        .map(|value| Value::try_from(value.unwrap()).unwrap())
+        .collect();
+    assert_eq!(read.len(), 2);
+    assert_eq!(read[0]["name"], json!("Alice"));
+    assert_eq!(read[1]["name"], Value::Null);
+    assert_eq!(read[1]["score"], json!(1.5));
+}
+
+#[test]
+fn test_row_not_matching_schema() {
+    let schema = json!({
+        "type": "record",
+        "name": "doc",
+        "fields": [{"name": "id", "type": "long"}]
+    });
+    let rows = vec![Ok(json!({"id": 1})), Ok(json!({"id": "one"}))];
+
+    let err = write_ocf(&schema, rows, Vec::new()).unwrap_err();
+    assert!(err.starts_with("Row 2 does not match the Avro schema"), "{}", err);
+}
+
+#[test]
+fn test_row_error_is_passed_through() {
+    let schema = json!({"type": "record", "name": "doc", "fields": []});
+    let rows = vec![Err("Row 1: bad input".to_string())];
+
+    assert_eq!(
+        write_ocf(&schema, rows, Vec::new()).unwrap_err(),
+        "Row 1: bad input"
+    );
+}
@@ -116,3 +116,159 @@ fn test_metadata_with_complex_json_schema() {
     let parsed: serde_json::Value = serde_json::from_str(schema_json).unwrap();
     assert_eq!(parsed["type"], "object");
 }
+
+#[test]
+fn test_write_string_rows_in_batches() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let path = temp_file.path().to_str().unwrap();
+
+    let rows: Vec<String> = (0..10).map(|i| format!(r#"{{"id": {}}}"#, i)).collect();
+
+    let mut metadata = HashMap::new();
+    metadata.insert("origin".to_string(), "stream".to_string());
+
+    // Batch size smaller than the row count forces several row groups
+    let written = write_string_rows(path, "data", rows.clone(), Some(metadata), 3).unwrap();
+    assert_eq!(written, 10);
+
+    assert_eq!(read_string_column(path, "data").unwrap(), rows);
+    assert_eq!(
+        read_parquet_metadata(path).unwrap().get("origin"),
+        Some(&"stream".to_string())
+    );
+
+    let file = File::open(path).unwrap();
+    let builder = ParquetRecordBatchReaderBuilder::try_new(file).unwrap();
+    assert_eq!(builder.metadata().num_row_groups(), 4);
+}
+
+#[test]
+fn test_write_string_rows_empty() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let path = temp_file.path().to_str().unwrap();
+
+    let written = write_string_rows(path, "data", Vec::<String>::new(), None, 100).unwrap();
+    assert_eq!(written, 0);
+    assert!(read_string_column(path, "data").unwrap().is_empty());
+}
+
+#[test]
+fn test_read_string_column_batches() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let path = temp_file.path().to_str().unwrap();
+
+    let rows: Vec<String> = (0..25).map(|i| format!(r#"{{"id": {}}}"#, i)).collect();
+    // Row groups of 10 rows
+    write_string_rows(path, "data", rows.clone(), None, 10).unwrap();
+
+    let batches: Vec<Vec<String>> = read_string_column_batches(path, "data", 4)
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert!(batches.iter().all(|batch| batch.len() <= 4));
+    assert_eq!(batches.concat(), rows);
+}
+
+#[test]
+fn test_read_string_column_batches_skips_nulls() {
+    use arrow::array::StringArray;
+
+    let temp_file = NamedTempFile::new().unwrap();
+    let path = temp_file.path().to_str().unwrap();
+
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("id", DataType::Utf8, false),
+        Field::new("data", DataType::Utf8, true),
+    ]));
+    let batch = RecordBatch::try_new(
+        schema.clone(),
+        vec![
+            Arc::new(StringArray::from(vec!["a", "b", "c"])),
+            Arc::new(StringArray::from(vec![Some("{}"), None, Some("[]")])),
+        ],
+    )
+    .unwrap();
+    let mut writer = ArrowWriter::try_new(File::create(path).unwrap(), schema, None).unwrap();
+    writer.write(&batch).unwrap();
+    writer.close().unwrap();
+
+    let strings = read_string_column(path, "data").unwrap();
+    assert_eq!(strings, vec!["{}".to_string(), "[]".to_string()]);
+}
+
+#[test]
+fn test_read_string_column_parallel_keeps_order() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let path = temp_file.path().to_str().unwrap();
+
+    let rows: Vec<String> = (0..100).map(|i| i.to_string()).collect();
+    write_string_rows(path, "data", rows.clone(), None, 7).unwrap();
+
+    assert_eq!(read_string_column_parallel(path, "data").unwrap(), rows);
+    assert!(read_string_column_parallel(path, "missing")
+        .unwrap_err()
+        .contains("not found"));
+}
+
+fn artifact_schema() -> serde_json::Value {
+    serde_json::json!({
+        "type": "object",
+        "properties": {
+            "id": {"type": "integer"},
+            "name": {"type": ["null", "string"]},
+            "tags": {"type": "array", "items": {"type": "string"}},
+            "labels": {"type": "object", "additionalProperties": {"type": "string"}},
+            "owner": {
+                "type": "object",
+                "properties": {"active": {"type": "boolean"}},
+                "required": ["active"]
+            }
+        },
+        "required": ["id", "tags", "labels", "owner"]
+    })
+}
+
+#[test]
+fn test_write_schema_parquet_empty_table() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let path = temp_file.path().to_str().unwrap();
+    let avro = serde_json::json!({"type": "record", "name": "document", "fields": []});
+    write_schema_parquet(path, &artifact_schema(), Some(&avro), &[]).unwrap();
+
+    let builder = ParquetRecordBatchReaderBuilder::try_new(File::open(path).unwrap()).unwrap();
+    assert_eq!(builder.metadata().file_metadata().num_rows(), 0);
+    let schema = builder.schema();
+    let expected = crate::export::arrow::to_arrow_schema(&artifact_schema()).unwrap();
+    assert_eq!(schema.fields(), expected.fields());
+
+    let metadata = read_parquet_metadata(path).unwrap();
+    assert_eq!(metadata[JSON_SCHEMA_METADATA_KEY], artifact_schema().to_string());
+    assert_eq!(metadata[AVRO_SCHEMA_METADATA_KEY], avro.to_string());
+}
+
+#[test]
+fn test_write_schema_parquet_with_rows() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let path = temp_file.path().to_str().unwrap();
+    let rows = vec![
+        serde_json::json!({"id": 1, "name": "a", "tags": ["x"], "labels": {"en": "A"},
+            "owner": {"active": true}}),
+        serde_json::json!({"id": 2, "name": null, "tags": [], "labels": {},
+            "owner": {"active": false}}),
+    ];
+    write_schema_parquet(path, &artifact_schema(), None, &rows).unwrap();
+
+    let reader = ParquetRecordBatchReaderBuilder::try_new(File::open(path).unwrap())
+        .unwrap()
+        .build()
+        .unwrap();
+    let batches: Vec<RecordBatch> = reader.map(Result::unwrap).collect();
+    assert_eq!(batches.iter().map(RecordBatch::num_rows).sum::<usize>(), 2);
+    assert!(!read_parquet_metadata(path)
+        .unwrap()
+        .contains_key(AVRO_SCHEMA_METADATA_KEY));
+
+    let bad = vec![serde_json::json!({"id": "one"})];
+    let err = write_schema_parquet(path, &artifact_schema(), None, &bad).unwrap_err();
+    assert!(err.starts_with("Row doesn't fit the Arrow schema"), "{}", err);
+}
@@ -0,0 +1,126 @@
+// genson-core/src/tests/presence.rs
+use super::*;
+use crate::{infer_json_schema_from_strings, SchemaInferenceConfig, SchemaInferenceSession};
+use serde_json::json;
+
+fn docs(lines: &[&str]) -> Vec<String> {
+    lines.iter().map(|d| d.to_string()).collect()
+}
+
+fn threshold_config(threshold: f64) -> SchemaInferenceConfig {
+    SchemaInferenceConfig {
+        required_threshold: Some(threshold),
+        ..Default::default()
+    }
+}
+
+/// Nine documents with `id` and `name`, and one malformed one without `name`.
+fn noisy_docs() -> Vec<String> {
+    let mut lines = vec![r#"{"id": 1, "name": "a"}"#; 9];
+    lines.push(r#"{"id": 2}"#);
+    docs(&lines)
+}
+
+#[test]
+fn test_without_threshold_missing_field_is_optional() {
+    let result = infer_json_schema_from_strings(&noisy_docs(), Default::default()).unwrap();
+    assert_eq!(result.schema["required"], json!(["id"]));
+    assert!(result.presence.is_none());
+}
+
+#[test]
+fn test_threshold_keeps_mostly_present_field_required() {
+    let result = infer_json_schema_from_strings(&noisy_docs(), threshold_config(0.9)).unwrap();
+    assert_eq!(result.schema["required"], json!(["id", "name"]));
+
+    let strict = infer_json_schema_from_strings(&noisy_docs(), threshold_config(0.95)).unwrap();
+    assert_eq!(strict.schema["required"], json!(["id"]));
+}
+
+#[test]
+fn test_presence_report() {
+    let result = infer_json_schema_from_strings(&noisy_docs(), threshold_config(0.95)).unwrap();
+    assert_eq!(
+        result.presence.unwrap(),
+        vec![
+            FieldPresence {
+                path: "/id".to_string(),
+                present: 10,
+                total: 10,
+                ratio: 1.0,
+                required: true,
+            },
+            FieldPresence {
+                path: "/name".to_string(),
+                present: 9,
+                total: 10,
+                ratio: 0.9,
+                required: false,
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_ratio_is_relative_to_parent_objects() {
+    // `meta` is in two of four documents, and `meta.v` in both of those
+    let lines = docs(&[
+        r#"{"meta": {"v": 1}}"#,
+        r#"{"meta": {"v": 2}}"#,
+        r#"{"x": 1}"#,
+        r#"{"x": 2}"#,
+    ]);
+    let result = infer_json_schema_from_strings(&lines, threshold_config(0.9)).unwrap();
+    assert!(result.schema.get("required").is_none());
+    assert_eq!(result.schema["properties"]["meta"]["required"], json!(["v"]));
+
+    let presence = result.presence.unwrap();
+    let meta = presence.iter().find(|p| p.path == "/meta").unwrap();
+    assert_eq!((meta.present, meta.total), (2, 4));
+}
+
+#[test]
+fn test_threshold_counts_array_items() {
+    let lines = docs(&[r#"{"items": [{"a": 1, "b": 1}, {"a": 2, "b": 2}, {"a": 3}]}"#]);
+    let result = infer_json_schema_from_strings(&lines, threshold_config(0.6)).unwrap();
+    assert_eq!(
+        result.schema["properties"]["items"]["items"]["required"],
+        json!(["a", "b"])
+    );
+}
+
+#[test]
+fn test_threshold_below_map() {
+    let lines = docs(&[
+        r#"{"labels": {"en": {"v": "a", "x": 1}, "fr": {"v": "b", "x": 2}}}"#,
+        r#"{"labels": {"de": {"v": "c", "x": 3}, "es": {"v": "d"}}}"#,
+    ]);
+    let config = SchemaInferenceConfig {
+        map_threshold: 2,
+        unify_maps: true,
+        required_threshold: Some(0.75),
+        ..Default::default()
+    };
+    let result = infer_json_schema_from_strings(&lines, config).unwrap();
+    let values = &result.schema["properties"]["labels"]["additionalProperties"];
+    assert_eq!(values["required"], json!(["v", "x"]));
+    let presence = result.presence.unwrap();
+    let x = presence.iter().find(|p| p.path == "/labels/*/x").unwrap();
+    assert_eq!((x.present, x.total), (3, 4));
+}
+
+#[test]
+fn test_invalid_threshold() {
+    let err = infer_json_schema_from_strings(&noisy_docs(), threshold_config(1.5)).unwrap_err();
+    assert!(err.contains("Invalid required_threshold: 1.5"), "{}", err);
+}
+
+#[test]
+fn test_threshold_across_session() {
+    let mut session = SchemaInferenceSession::new(threshold_config(0.9));
+    let all = noisy_docs();
+    session.add_strings(&all[..5]).unwrap();
+    session.add_strings(&all[5..]).unwrap();
+    let result = session.result().unwrap();
+    assert_eq!(result.schema["required"], json!(["id", "name"]));
+}
@@ -0,0 +1,33 @@
+// genson-core/src/tests/profile.rs
+use super::*;
+
+fn ms_durations(values: &[u64]) -> Vec<Duration> {
+    values.iter().map(|&v| Duration::from_millis(v)).collect()
+}
+
+#[test]
+fn test_percentile_nearest_rank() {
+    let sorted = ms_durations(&(1..=100).collect::<Vec<_>>());
+    assert_eq!(percentile(&sorted, 50), 50.0);
+    assert_eq!(percentile(&sorted, 99), 99.0);
+    assert_eq!(percentile(&ms_durations(&[7]), 99), 7.0);
+    assert_eq!(percentile(&[], 50), 0.0);
+}
+
+#[test]
+fn test_recorder_sums_stages() {
+    let mut recorder = ProfileRecorder::new();
+    recorder.document(Duration::from_millis(1), Duration::from_millis(2));
+    recorder.document(Duration::from_millis(3), Duration::from_millis(4));
+    recorder.merge(Duration::from_millis(5));
+    recorder.rewrite_objects(Duration::from_millis(6));
+    let profile = recorder.finish();
+
+    assert_eq!(profile.documents, 2);
+    assert_eq!(profile.parse_ms, 4.0);
+    assert_eq!(profile.build_ms, 11.0);
+    assert_eq!(profile.rewrite_objects_ms, 6.0);
+    assert_eq!(profile.avro_ms, 0.0);
+    assert_eq!(profile.document_p50_ms, 3.0);
+    assert_eq!(profile.document_p99_ms, 7.0);
+}
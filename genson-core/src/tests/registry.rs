@@ -0,0 +1,109 @@
+// genson-core/src/tests/registry.rs
+use super::*;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::thread::{self, JoinHandle};
+
+/// Serve one canned `(status, body)` response per connection, returning the
+/// request lines received.
+fn serve(responses: Vec<(u16, &'static str)>) -> (String, JoinHandle<Vec<String>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let handle = thread::spawn(move || {
+        responses
+            .into_iter()
+            .map(|(status, body)| {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                let mut content_length = 0;
+                loop {
+                    let mut header = String::new();
+                    reader.read_line(&mut header).unwrap();
+                    if header == "\r\n" {
+                        break;
+                    }
+                    if let Some(len) = header.to_ascii_lowercase().strip_prefix("content-length:")
+                    {
+                        content_length = len.trim().parse().unwrap();
+                    }
+                }
+                let mut payload = vec![0; content_length];
+                reader.read_exact(&mut payload).unwrap();
+                write!(
+                    stream,
+                    "HTTP/1.1 {} Status\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                )
+                .unwrap();
+                request_line.trim_end().to_string()
+            })
+            .collect()
+    });
+    (url, handle)
+}
+
+fn schema() -> Value {
+    json!({"type": "record", "name": "order", "namespace": "shop", "fields": []})
+}
+
+#[test]
+fn test_subject_name_strategies() {
+    let schema = schema();
+    let topic = Some("orders");
+    assert_eq!(
+        SubjectNameStrategy::TopicName.subject(topic, &schema),
+        Ok("orders-value".to_string())
+    );
+    assert_eq!(
+        SubjectNameStrategy::RecordName.subject(None, &schema),
+        Ok("shop.order".to_string())
+    );
+    assert_eq!(
+        SubjectNameStrategy::TopicRecordName.subject(topic, &schema),
+        Ok("orders-shop.order".to_string())
+    );
+    assert!(SubjectNameStrategy::TopicName
+        .subject(None, &schema)
+        .is_err());
+}
+
+#[test]
+fn test_check_compatibility_then_register() {
+    let (url, server) = serve(vec![(200, r#"{"is_compatible": true}"#), (200, r#"{"id": 7}"#)]);
+    let client = SchemaRegistryClient::new(&url).unwrap();
+
+    assert_eq!(client.check_compatibility("orders-value", &schema()), Ok(true));
+    assert_eq!(client.register("orders-value", &schema()), Ok(7));
+    assert_eq!(
+        server.join().unwrap(),
+        [
+            "POST /compatibility/subjects/orders-value/versions/latest HTTP/1.1",
+            "POST /subjects/orders-value/versions HTTP/1.1",
+        ]
+    );
+}
+
+#[test]
+fn test_new_subject_is_compatible() {
+    let (url, _server) = serve(vec![(
+        404,
+        r#"{"error_code": 40401, "message": "Subject 'orders-value' not found."}"#,
+    )]);
+    let client = SchemaRegistryClient::new(&url).unwrap();
+    assert_eq!(client.check_compatibility("orders-value", &schema()), Ok(true));
+}
+
+#[test]
+fn test_registry_error_message() {
+    let (url, _server) = serve(vec![(
+        409,
+        r#"{"error_code": 409, "message": "Schema being registered is incompatible"}"#,
+    )]);
+    let client = SchemaRegistryClient::new(&url).unwrap();
+    let err = client.register("orders-value", &schema()).unwrap_err();
+    assert!(err.contains("(error 409): Schema being registered is incompatible"), "{}", err);
+}
@@ -0,0 +1,74 @@
+// genson-core/src/tests/repair.rs
+use super::*;
+
+fn repaired(text: &str) -> (String, Vec<Repair>) {
+    let (out, repairs) = repair_json(text);
+    (out.into_owned(), repairs)
+}
+
+#[test]
+fn test_valid_json_is_borrowed() {
+    let text = r#"{"a": [1, 2], "b": "it's, }", "c": null}"#;
+    let (out, repairs) = repair_json(text);
+    assert!(matches!(out, Cow::Borrowed(_)));
+    assert!(repairs.is_empty());
+}
+
+#[test]
+fn test_trailing_commas() {
+    assert_eq!(
+        repaired(r#"{"a": [1, 2, ], "b": {"c": 1,},}"#),
+        (
+            r#"{"a": [1, 2 ], "b": {"c": 1}}"#.to_string(),
+            vec![Repair::TrailingComma]
+        )
+    );
+}
+
+#[test]
+fn test_single_quotes() {
+    let (out, repairs) = repaired(r#"{'name': 'O\'Brien "Bob"'}"#);
+    assert_eq!(out, r#"{"name": "O'Brien \"Bob\""}"#);
+    assert_eq!(repairs, vec![Repair::SingleQuotes]);
+}
+
+#[test]
+fn test_unquoted_keys() {
+    let (out, repairs) = repaired(r#"{id: 1, $ref: "x", ok: true, tags: [null]}"#);
+    assert_eq!(out, r#"{"id": 1, "$ref": "x", "ok": true, "tags": [null]}"#);
+    assert_eq!(repairs, vec![Repair::UnquotedKey]);
+}
+
+#[test]
+fn test_bom_and_control_characters() {
+    let (out, repairs) = repaired("\u{FEFF}{\"a\": \"x\ty\u{1}\"}\u{0}");
+    assert_eq!(out, r#"{"a": "x\ty\u0001"}"#);
+    assert_eq!(
+        repairs,
+        vec![Repair::ByteOrderMark, Repair::ControlCharacter]
+    );
+    assert!(serde_json::from_str::<serde_json::Value>(&out).is_ok());
+}
+
+#[test]
+fn test_repair_lines() {
+    let text = "{\"a\": 1}\n\n{a: 2,}\n{\"a\": 3}\n{'a': 4}\n";
+    let (out, rows) = repair_lines(text);
+    assert_eq!(out, "{\"a\": 1}\n\n{\"a\": 2}\n{\"a\": 3}\n{\"a\": 4}\n");
+    assert_eq!(
+        rows,
+        vec![
+            RowRepairs {
+                row: 1,
+                repairs: vec![Repair::UnquotedKey, Repair::TrailingComma]
+            },
+            RowRepairs {
+                row: 3,
+                repairs: vec![Repair::SingleQuotes]
+            },
+        ]
+    );
+
+    let clean = "{\"a\": 1}\n{\"a\": 2}\n";
+    assert!(matches!(repair_lines(clean).0, Cow::Borrowed(_)));
+}
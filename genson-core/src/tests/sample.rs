@@ -0,0 +1,55 @@
+// genson-core/src/tests/sample.rs
+use super::*;
+
+fn docs(n: usize) -> Vec<String> {
+    (0..n).map(|i| i.to_string()).collect()
+}
+
+#[test]
+fn test_first_and_every_nth() {
+    let docs = docs(10);
+    assert_eq!(
+        sample_documents(&docs, SampleSpec::First(3), false),
+        ["0", "1", "2"]
+    );
+    assert_eq!(
+        sample_documents(&docs, SampleSpec::EveryNth(4), false),
+        ["0", "4", "8"]
+    );
+    assert_eq!(sample_documents(&docs, SampleSpec::First(50), false).len(), 10);
+}
+
+#[test]
+fn test_random_is_seeded_and_ordered() {
+    let docs = docs(1000);
+    let spec = SampleSpec::Random { n: 20, seed: 42 };
+    let a = sample_documents(&docs, spec, false);
+    let b = sample_documents(&docs, spec, false);
+    assert_eq!(a, b);
+    assert_eq!(a.len(), 20);
+
+    let indices: Vec<usize> = a.iter().map(|s| s.parse().unwrap()).collect();
+    assert!(indices.windows(2).all(|w| w[0] < w[1]));
+
+    let other = sample_documents(&docs, SampleSpec::Random { n: 20, seed: 7 }, false);
+    assert_ne!(a, other);
+}
+
+#[test]
+fn test_ndjson_sampled_by_line() {
+    let input = vec!["a\nb\n\nc".to_string(), "d\ne".to_string()];
+    assert_eq!(
+        sample_documents(&input, SampleSpec::EveryNth(2), true),
+        ["a", "c", "e"]
+    );
+}
+
+#[test]
+fn test_parse_random_spec() {
+    assert_eq!(
+        "100:42".parse::<SampleSpec>(),
+        Ok(SampleSpec::Random { n: 100, seed: 42 })
+    );
+    assert!("100".parse::<SampleSpec>().is_err());
+    assert!("x:1".parse::<SampleSpec>().is_err());
+}
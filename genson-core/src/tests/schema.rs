@@ -2,6 +2,7 @@
 use super::*;
 use predicates::prelude::*;
 use serde_json::json;
+use crate::limits::{LimitAction, ResourceLimits};
 
 #[test]
 fn test_reorder_unions_string_float_null() {
@@ -54,9 +55,25 @@ fn test_basic_schema_inference() {
     );
 }
 
+#[test]
+fn test_borrowed_slices_match_owned_strings() {
+    let column = r#"{"a": 1}{"a": 2, "b": "x"}"#;
+    let strs = [&column[..8], &column[8..]];
+    let strings: Vec<String> = strs.iter().map(|s| s.to_string()).collect();
+    let config = SchemaInferenceConfig {
+        sample: Some(crate::SampleSpec::First(2)),
+        ..Default::default()
+    };
+
+    let borrowed = infer_json_schema_from_strings(&strs, config.clone()).unwrap();
+    let owned = infer_json_schema_from_strings(&strings, config).unwrap();
+    assert_eq!(borrowed.schema, owned.schema);
+    assert_eq!(borrowed.processed_count, 2);
+}
+
 #[test]
 fn test_empty_input() {
-    let json_strings = vec![];
+    let json_strings: Vec<String> = vec![];
     let result = infer_json_schema_from_strings(&json_strings, SchemaInferenceConfig::default());
 
     assert!(result.is_err());
@@ -112,7 +129,7 @@ fn test_invalid_json_variants() {
         predicate::str::contains("Invalid JSON input at position").eval(&error_msg);
 
         // For short JSON strings, verify the content is included
-        if invalid_json.len() <= MAX_JSON_ERROR_LENGTH {
+        if invalid_json.len() <= DEFAULT_ERROR_CONTEXT_CHARS {
             predicate::str::contains(invalid_json).eval(&error_msg);
         } else {
             // For long JSON, just check that truncation happened
@@ -367,7 +384,7 @@ fn test_invalid_ndjson_line() {
     let err_msg = result.unwrap_err();
     eprintln!("Got error: {}", err_msg);
     assert!(
-        err_msg.contains("Invalid JSON input at index 1: expected value at line 1 column 13"),
+        err_msg.contains("Invalid JSON input at index 1, line 3: expected value at line 1 column 13"),
         "Error message should report the failing line"
     );
     println!("✅ Correctly rejected malformed NDJSON: {}", err_msg);
@@ -429,6 +446,62 @@ fn test_wrap_root_inserts_single_required_field() {
     assert!(sch["properties"]["labels"].is_object());
 }
 
+#[test]
+fn test_wrap_root_dotted_path_nests_each_level() {
+    let json_strings = vec![
+        r#"{"P31":[{"id":"Q5"}]}"#.to_string(),
+        r#"{"P31":[{"id":"Q6"}]}"#.to_string(),
+    ];
+
+    let cfg = SchemaInferenceConfig {
+        wrap_root: Some("entity.claims".to_string()),
+        ..Default::default()
+    };
+
+    let sch = infer_json_schema_from_strings(&json_strings, cfg)
+        .unwrap()
+        .schema;
+
+    assert_eq!(sch["required"], serde_json::json!(["entity"]));
+    let entity = &sch["properties"]["entity"];
+    assert_eq!(entity["required"], serde_json::json!(["claims"]));
+    assert_eq!(
+        entity["properties"]["claims"]["properties"]["P31"]["type"],
+        "array"
+    );
+}
+
+#[test]
+fn test_wrap_root_skips_wrapped_rows() {
+    let json_strings = vec![
+        r#"{"P31":[{"id":"Q5"}]}"#.to_string(),
+        r#"{"entity":{"claims":{"P31":[{"id":"Q6"}]}}}"#.to_string(),
+    ];
+    let cfg = SchemaInferenceConfig {
+        wrap_root: Some("entity.claims".to_string()),
+        ..Default::default()
+    };
+    let sch = infer_json_schema_from_strings(&json_strings, cfg.clone())
+        .unwrap()
+        .schema;
+    let claims = &sch["properties"]["entity"]["properties"]["claims"];
+    assert_eq!(claims["properties"].as_object().unwrap().len(), 1);
+    assert_eq!(claims["required"], serde_json::json!(["P31"]));
+
+    // A row with other fields beside the root isn't wrapped already
+    assert!(!is_wrapped("entity", &serde_json::json!({"entity": {}, "id": 1})));
+
+    let strict = SchemaInferenceConfig {
+        wrap_root_strict: true,
+        ..cfg
+    };
+    let err = infer_json_schema_from_strings(&json_strings, strict).unwrap_err();
+    assert_eq!(
+        err,
+        "Row 2 is already wrapped under 'entity.claims' but row 1 isn't (wrap_root_strict)"
+    );
+}
+
 #[test]
 fn test_rewrite_objects_map_of_records() {
     use serde_json::json;
@@ -462,7 +535,7 @@ fn test_rewrite_objects_map_of_records() {
         ..Default::default()
     };
 
-    rewrite_objects(&mut schema, None, &cfg, true);
+    rewrite_objects(&mut schema, None, &[], &cfg, true, &RewriteHints::default(), None);
 
     println!("Generated schema:\n{}", schema);
 
@@ -471,14 +544,16 @@ fn test_rewrite_objects_map_of_records() {
     assert!(schema.get("properties").is_none());
     assert!(schema.get("required").is_none());
 
-    // additionalProperties should carry the inner record shape
+    // additionalProperties carries the inner record, itself rewritten: its two string
+    // fields meet the threshold of 2, so it is a map of strings too
     let ap = schema
         .get("additionalProperties")
         .expect("should insert additionalProperties");
 
-    assert_eq!(ap["type"], "object");
-    assert_eq!(ap["properties"]["language"], json!({ "type": "string" }));
-    assert_eq!(ap["properties"]["value"], json!({ "type": "string" }));
+    assert_eq!(
+        ap,
+        &json!({ "type": "object", "additionalProperties": { "type": "string" } })
+    );
 }
 
 #[test]
@@ -503,7 +578,7 @@ fn test_map_of_strings_not_promoted_to_records() {
         map_threshold: 2,
         ..Default::default()
     };
-    rewrite_objects(&mut sch, None, &cfg, true);
+    rewrite_objects(&mut sch, None, &[], &cfg, true, &RewriteHints::default(), None);
 
     assert_eq!(
         sch["properties"]["labels"]["additionalProperties"]["type"],
@@ -528,7 +603,7 @@ fn test_rewrite_objects_respects_map_max_required_keys() {
         ..Default::default()
     };
 
-    rewrite_objects(&mut schema, None, &config, true);
+    rewrite_objects(&mut schema, None, &[], &config, true, &RewriteHints::default(), None);
 
     // Should remain as record because 2 required keys > 1
     assert_eq!(schema["type"], "object");
@@ -554,7 +629,7 @@ fn test_rewrite_objects_allows_map_with_few_required_keys() {
         ..Default::default()
     };
 
-    rewrite_objects(&mut schema, None, &config, true);
+    rewrite_objects(&mut schema, None, &[], &config, true, &RewriteHints::default(), None);
 
     // Should become map because 1 required key ≤ 1
     assert_eq!(schema["type"], "object");
@@ -581,7 +656,7 @@ fn test_rewrite_objects_none_max_required_keys_preserves_behavior() {
         ..Default::default()
     };
 
-    rewrite_objects(&mut schema, None, &config, true);
+    rewrite_objects(&mut schema, None, &[], &config, true, &RewriteHints::default(), None);
 
     // Should become map because None means no gating (old behavior)
     assert_eq!(schema["type"], "object");
@@ -606,7 +681,7 @@ fn test_rewrite_objects_zero_max_required_keys() {
         ..Default::default()
     };
 
-    rewrite_objects(&mut schema, None, &config, true);
+    rewrite_objects(&mut schema, None, &[], &config, true, &RewriteHints::default(), None);
 
     // Should remain as record because 1 required key > 0
     assert_eq!(schema["type"], "object");
@@ -632,7 +707,7 @@ fn test_rewrite_objects_zero_required_keys_allowed() {
         ..Default::default()
     };
 
-    rewrite_objects(&mut schema, None, &config, true);
+    rewrite_objects(&mut schema, None, &[], &config, true, &RewriteHints::default(), None);
 
     // Should become map because 0 required keys ≤ 0
     assert_eq!(schema["type"], "object");
@@ -839,7 +914,7 @@ fn test_rewrite_objects_force_override_wins() {
     };
 
     // Apply with field name that matches force override
-    rewrite_objects(&mut schema, Some("test_field"), &config, true);
+    rewrite_objects(&mut schema, Some("test_field"), &[], &config, true, &RewriteHints::default(), None);
 
     // Should become map despite having required keys due to force override
     assert_eq!(schema["type"], "object");
@@ -864,7 +939,7 @@ fn test_rewrite_objects_non_homogeneous_values_not_rewritten() {
         ..Default::default()
     };
 
-    rewrite_objects(&mut schema, None, &config, true);
+    rewrite_objects(&mut schema, None, &[], &config, true, &RewriteHints::default(), None);
 
     // Should remain as record because values are not homogeneous
     assert_eq!(schema["type"], "object");
@@ -888,7 +963,7 @@ fn test_rewrite_objects_below_threshold_not_rewritten() {
         ..Default::default()
     };
 
-    rewrite_objects(&mut schema, None, &config, true);
+    rewrite_objects(&mut schema, None, &[], &config, true, &RewriteHints::default(), None);
 
     // Should remain as record because below threshold
     assert_eq!(schema["type"], "object");
@@ -992,7 +1067,7 @@ fn test_no_root_map_prevents_root_becoming_map() {
         ..Default::default()
     };
 
-    rewrite_objects(&mut schema, None, &config, true);
+    rewrite_objects(&mut schema, None, &[], &config, true, &RewriteHints::default(), None);
 
     // Should remain as record despite meeting map criteria
     assert_eq!(schema["type"], "object");
@@ -1020,7 +1095,7 @@ fn test_no_root_map_false_allows_root_becoming_map() {
         ..Default::default()
     };
 
-    rewrite_objects(&mut schema, None, &config, true);
+    rewrite_objects(&mut schema, None, &[], &config, true, &RewriteHints::default(), None);
 
     // Should become map because no_root_map=false allows it
     assert_eq!(schema["type"], "object");
@@ -1211,3 +1286,555 @@ fn test_simd_json_iteration_determinism() {
         panic!("Not an object");
     }
 }
+
+#[test]
+fn test_limits_max_rows_error() {
+    let json_strings: Vec<String> = (0..5).map(|i| format!(r#"{{"a": {}}}"#, i)).collect();
+    let config = SchemaInferenceConfig {
+        limits: ResourceLimits {
+            max_rows: Some(3),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let err = infer_json_schema_from_strings(&json_strings, config).unwrap_err();
+    assert_eq!(err, "Resource limit exceeded: row limit of 3 reached");
+}
+
+#[test]
+fn test_limits_partial_result_counts_ndjson_lines() {
+    // The third line (the only one with "b") is beyond the row limit
+    let json_strings = vec!["{\"a\": 1}\n{\"a\": 2}\n{\"b\": true}\n".to_string()];
+    let config = SchemaInferenceConfig {
+        delimiter: Some(b'\n'),
+        limits: ResourceLimits {
+            max_rows: Some(2),
+            on_limit: LimitAction::Partial,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let result = infer_json_schema_from_strings(&json_strings, config).unwrap();
+    assert_eq!(result.limit_reached, Some(LimitExceeded::MaxRows(2)));
+    assert!(result.schema["properties"].get("a").is_some());
+    assert!(result.schema["properties"].get("b").is_none());
+}
+
+#[test]
+fn test_limits_partial_max_bytes() {
+    let json_strings = vec![
+        r#"{"a": 1}"#.to_string(),
+        r#"{"b": "a much longer value"}"#.to_string(),
+    ];
+    let config = SchemaInferenceConfig {
+        limits: ResourceLimits {
+            max_bytes: Some(20),
+            on_limit: LimitAction::Partial,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let result = infer_json_schema_from_strings(&json_strings, config).unwrap();
+    assert_eq!(result.processed_count, 1);
+    assert_eq!(result.limit_reached, Some(LimitExceeded::MaxBytes(20)));
+}
+
+#[test]
+fn test_limits_runtime_partial() {
+    let json_strings: Vec<String> = (0..20).map(|i| format!(r#"{{"a": {}}}"#, i)).collect();
+    let config = SchemaInferenceConfig {
+        limits: ResourceLimits {
+            max_runtime: Some(std::time::Duration::ZERO),
+            on_limit: LimitAction::Partial,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let result = infer_json_schema_from_strings(&json_strings, config).unwrap();
+    assert_eq!(result.processed_count, 0);
+    assert_eq!(
+        result.limit_reached,
+        Some(LimitExceeded::MaxRuntime(std::time::Duration::ZERO))
+    );
+}
+
+#[test]
+fn test_force_field_types_by_path() {
+    // Two different "value" fields: only the one under "labels" should become a map
+    let json_strings = vec![
+        r#"{"labels": {"value": {"en": "a", "fr": "b"}}, "meta": {"value": {"x": "1", "y": "2"}}}"#
+            .to_string(),
+    ];
+
+    let mut force_types = std::collections::HashMap::new();
+    force_types.insert("/labels/value".to_string(), "map".to_string());
+
+    let config = SchemaInferenceConfig {
+        force_field_types: force_types,
+        ..Default::default()
+    };
+    let result = infer_json_schema_from_strings(&json_strings, config).unwrap();
+    let props = &result.schema["properties"];
+
+    let labels_value = &props["labels"]["properties"]["value"];
+    assert!(labels_value.get("additionalProperties").is_some());
+    assert!(labels_value.get("properties").is_none());
+
+    let meta_value = &props["meta"]["properties"]["value"];
+    assert!(meta_value.get("properties").is_some());
+    assert!(meta_value.get("additionalProperties").is_none());
+}
+
+#[test]
+fn test_force_field_types_path_wildcard_through_map() {
+    // Once "claims" becomes a map, its values are addressed by the "*" segment
+    let json_strings = vec![
+        r#"{"claims": {"P1": {"qual": {"a": "x", "b": "y"}}, "P2": {"qual": {"c": "z"}}}}"#
+            .to_string(),
+    ];
+
+    let mut force_types = std::collections::HashMap::new();
+    force_types.insert("claims".to_string(), "record".to_string());
+    force_types.insert("claims.*.qual".to_string(), "map".to_string());
+
+    let config = SchemaInferenceConfig {
+        map_threshold: 2,
+        force_field_types: force_types,
+        ..Default::default()
+    };
+    let result = infer_json_schema_from_strings(&json_strings, config).unwrap();
+    let claims = &result.schema["properties"]["claims"]["properties"];
+    for key in ["P1", "P2"] {
+        assert!(
+            claims[key]["properties"]["qual"]
+                .get("additionalProperties")
+                .is_some(),
+            "{} qual should be a map: {}",
+            key,
+            result.schema
+        );
+    }
+}
+
+#[test]
+fn test_force_scalar_promotion_by_path() {
+    let json_strings = vec![r#"{"a": {"value": "x"}, "b": {"value": "y"}}"#.to_string()];
+
+    let mut promo = std::collections::HashSet::new();
+    promo.insert("/a/value".to_string());
+
+    let config = SchemaInferenceConfig {
+        force_scalar_promotion: promo,
+        ..Default::default()
+    };
+    let result = infer_json_schema_from_strings(&json_strings, config).unwrap();
+    let props = &result.schema["properties"];
+    assert_eq!(
+        props["a"]["properties"]["value"]["properties"]["value__string"]["type"],
+        "string"
+    );
+    assert_eq!(props["b"]["properties"]["value"]["type"], "string");
+}
+
+#[test]
+fn test_collect_profile() {
+    let json_strings: Vec<String> = (0..20).map(|i| format!(r#"{{"id": {}}}"#, i)).collect();
+
+    let result =
+        infer_json_schema_from_strings(&json_strings, SchemaInferenceConfig::default()).unwrap();
+    assert!(result.profile.is_none());
+
+    let config = SchemaInferenceConfig {
+        collect_profile: true,
+        ..Default::default()
+    };
+    let result = infer_json_schema_from_strings(&json_strings, config).unwrap();
+    let profile = result.profile.expect("profile collected");
+    assert_eq!(profile.documents, 20);
+    assert!(profile.document_p50_ms <= profile.document_p99_ms);
+    assert_eq!(profile.avro_ms, 0.0);
+}
+
+#[test]
+fn test_ignore_paths_treats_subtrees_as_strings() {
+    let json_strings = vec![
+        r#"{"id": 1, "metadata": {"a": 1, "b": {"c": true}}, "items": [{"extra": {"x": 1}, "n": 1}]}"#
+            .to_string(),
+        r#"{"id": 2, "metadata": null, "items": [{"extra": [1, 2], "n": 2}]}"#.to_string(),
+    ];
+
+    let config = SchemaInferenceConfig {
+        ignore_paths: vec!["metadata".to_string(), "/items/extra".to_string()],
+        ..Default::default()
+    };
+    let result = infer_json_schema_from_strings(&json_strings, config).unwrap();
+    let props = &result.schema["properties"];
+    assert_eq!(props["metadata"]["type"], json!(["null", "string"]));
+    let item = &props["items"]["items"];
+    assert_eq!(item["properties"]["extra"]["type"], "string");
+    assert_eq!(item["properties"]["n"]["type"], "integer");
+}
+
+#[test]
+fn test_ignore_paths_with_wrap_root_and_ndjson() {
+    let json_strings = vec!["{\"blob\": {\"k\": 1}, \"keep\": {\"k\": 1}}\n{\"blob\": {\"j\": 2}, \"keep\": {\"k\": 2}}".to_string()];
+
+    let config = SchemaInferenceConfig {
+        delimiter: Some(b'\n'),
+        wrap_root: Some("doc".to_string()),
+        ignore_paths: vec!["doc.blob".to_string()],
+        ..Default::default()
+    };
+    let result = infer_json_schema_from_strings(&json_strings, config).unwrap();
+    let doc = &result.schema["properties"]["doc"]["properties"];
+    assert_eq!(doc["blob"]["type"], "string");
+    assert_eq!(doc["keep"]["properties"]["k"]["type"], "integer");
+}
+
+#[test]
+fn test_max_depth_collapses_deep_subtrees() {
+    let json_strings = vec![r#"{"a": {"b": {"c": 1}}, "deep": [[[[1]]]], "n": 1}"#.to_string()];
+
+    let config = SchemaInferenceConfig {
+        max_depth: Some(2),
+        ..Default::default()
+    };
+    let result = infer_json_schema_from_strings(&json_strings, config).unwrap();
+    let props = &result.schema["properties"];
+    assert_eq!(props["a"]["properties"]["b"]["type"], "string");
+    assert_eq!(props["deep"]["items"]["type"], "string");
+    assert_eq!(props["n"]["type"], "integer");
+}
+
+#[test]
+fn test_max_object_keys_collapses_wide_objects() {
+    let json_strings = vec![
+        r#"{"blob": {"a": 1, "b": 2, "c": 3}, "small": {"a": 1}}"#.to_string(),
+        r#"[{"blob": {"d": 1, "e": 2, "f": 3}, "small": {"a": 2}}]"#.to_string(),
+    ];
+
+    let config = SchemaInferenceConfig {
+        max_object_keys: Some(2),
+        ..Default::default()
+    };
+    let result = infer_json_schema_from_strings(&json_strings, config).unwrap();
+    let props = &result.schema["properties"];
+    assert_eq!(props["blob"]["type"], "string");
+    assert_eq!(props["small"]["properties"]["a"]["type"], "integer");
+}
+
+#[test]
+fn test_null_fallback_types_null_only_fields() {
+    let json_strings = vec![
+        r#"{"id": 1, "note": null, "tags": [null], "meta": {"deleted_at": null}}"#.to_string(),
+        r#"{"id": 2, "note": null, "tags": [], "meta": {"deleted_at": null}}"#.to_string(),
+    ];
+
+    let config = SchemaInferenceConfig {
+        null_fallback: Some("string".to_string()),
+        ..Default::default()
+    };
+    let result = infer_json_schema_from_strings(&json_strings, config).unwrap();
+    let props = &result.schema["properties"];
+    assert_eq!(props["note"]["type"], json!(["null", "string"]));
+    assert_eq!(props["tags"]["items"]["type"], json!(["null", "string"]));
+    assert_eq!(
+        props["meta"]["properties"]["deleted_at"]["type"],
+        json!(["null", "string"])
+    );
+    assert_eq!(props["id"]["type"], "integer");
+    assert_eq!(result.schema["required"], json!(["id", "meta", "tags"]));
+    assert_eq!(
+        result.defaulted_fields,
+        vec!["/meta/deleted_at", "/note", "/tags"]
+    );
+}
+
+#[test]
+fn test_null_fallback_unset_keeps_null_type() {
+    let json_strings = vec![r#"{"note": null}"#.to_string()];
+
+    let result =
+        infer_json_schema_from_strings(&json_strings, SchemaInferenceConfig::default()).unwrap();
+    assert_eq!(result.schema["properties"]["note"]["type"], "null");
+    assert!(result.defaulted_fields.is_empty());
+}
+
+#[cfg(feature = "avro")]
+#[test]
+fn test_null_fallback_avro() {
+    let json_strings = vec![r#"{"note": null}"#.to_string()];
+
+    let config = SchemaInferenceConfig {
+        null_fallback: Some("integer".to_string()),
+        avro: true,
+        ..Default::default()
+    };
+    let result = infer_json_schema_from_strings(&json_strings, config).unwrap();
+    let fields = result.schema["fields"].as_array().unwrap();
+    assert_eq!(fields[0]["type"], json!(["null", "int"]));
+    assert_eq!(result.defaulted_fields, vec!["/note"]);
+}
+
+#[test]
+fn test_null_fallback_rejects_unknown_type() {
+    let json_strings = vec![r#"{"note": null}"#.to_string()];
+
+    let config = SchemaInferenceConfig {
+        null_fallback: Some("object".to_string()),
+        ..Default::default()
+    };
+    let err = infer_json_schema_from_strings(&json_strings, config).unwrap_err();
+    assert_eq!(
+        err,
+        "Invalid null_fallback: object (expected string|integer|number|boolean)"
+    );
+}
+
+#[test]
+fn test_bare_roots_are_wrapped_under_value_field() {
+    let config = SchemaInferenceConfig {
+        delimiter: Some(b'\n'),
+        ..Default::default()
+    };
+    let json_strings = vec!["[1, 2, 3]\n[4]".to_string()];
+    let result = infer_json_schema_from_strings(&json_strings, config.clone()).unwrap();
+    assert_eq!(
+        result.schema["properties"]["value"],
+        json!({"type": "array", "items": {"type": "integer"}})
+    );
+    assert_eq!(result.schema["required"], json!(["value"]));
+
+    let json_strings = vec!["\"string\"\n\"another\"".to_string()];
+    let result = infer_json_schema_from_strings(&json_strings, config).unwrap();
+    assert_eq!(
+        result.schema["properties"]["value"],
+        json!({"type": "string"})
+    );
+}
+
+#[test]
+fn test_bare_roots_mixed_with_objects() {
+    let json_strings = vec![
+        r#"{"value": "a"}"#.to_string(),
+        r#""b""#.to_string(),
+        r#"[{"value": "c"}]"#.to_string(),
+    ];
+
+    let config = SchemaInferenceConfig {
+        root_value_field: "value".to_string(),
+        ..Default::default()
+    };
+    let result = infer_json_schema_from_strings(&json_strings, config).unwrap();
+    assert_eq!(result.schema["properties"]["value"]["type"], "string");
+    assert_eq!(result.processed_count, 3);
+}
+
+#[test]
+fn test_outer_array_with_bare_items_is_wrapped() {
+    let json_strings = vec![r#"[{"a": "x"}, [1], "y"]"#.to_string()];
+    let result =
+        infer_json_schema_from_strings(&json_strings, SchemaInferenceConfig::default()).unwrap();
+    assert_eq!(result.schema["properties"]["value"]["type"], "array");
+
+    let json_strings = vec![r#"[{"a": "[x,"}, {"a": "]"}]"#.to_string()];
+    let result =
+        infer_json_schema_from_strings(&json_strings, SchemaInferenceConfig::default()).unwrap();
+    assert_eq!(result.schema["properties"]["a"]["type"], "string");
+}
+
+#[test]
+fn test_outer_scalar_policy_array_keeps_one_document() {
+    let json_strings = vec!["[1, 2, 3]".to_string()];
+    let config = SchemaInferenceConfig {
+        outer_scalar_policy: OuterScalarPolicy::Array,
+        ..Default::default()
+    };
+    let result = infer_json_schema_from_strings(&json_strings, config).unwrap();
+    assert_eq!(
+        result.schema["properties"]["value"],
+        json!({"type": "array", "items": {"type": "integer"}})
+    );
+    assert_eq!(result.schema["required"], json!(["value"]));
+}
+
+#[test]
+fn test_outer_scalar_policy_rows_wraps_each_item() {
+    let config = SchemaInferenceConfig {
+        outer_scalar_policy: OuterScalarPolicy::Rows,
+        ..Default::default()
+    };
+    let json_strings = vec!["[1, 2, 3]".to_string()];
+    let result = infer_json_schema_from_strings(&json_strings, config.clone()).unwrap();
+    assert_eq!(result.schema["properties"]["value"], json!({"type": "integer"}));
+    assert_eq!(result.schema["required"], json!(["value"]));
+
+    // Objects stay rows of their own; a null item makes the wrapped field nullable
+    let json_strings = vec![r#"[{"a": "x"}, 1, null]"#.to_string()];
+    let result = infer_json_schema_from_strings(&json_strings, config.clone()).unwrap();
+    assert_eq!(result.schema["properties"]["a"]["type"], "string");
+    assert_eq!(result.schema["properties"]["value"]["type"], json!(["integer", "null"]));
+    assert!(result.schema.get("required").is_none());
+
+    // Without ignore_outer_array the array is a document as before
+    let config = SchemaInferenceConfig {
+        ignore_outer_array: false,
+        ..config
+    };
+    let result = infer_json_schema_from_strings(&["[1, 2]".to_string()], config).unwrap();
+    assert_eq!(result.schema["properties"]["value"]["type"], "array");
+}
+
+#[test]
+fn test_builder_documents_follow_outer_scalar_policy() {
+    let mixed = r#"[{"a": 1}, 2]"#;
+    let config = SchemaInferenceConfig {
+        outer_scalar_policy: OuterScalarPolicy::Rows,
+        ..Default::default()
+    };
+    let mixed: Value = serde_json::from_str(mixed).unwrap();
+    assert_eq!(
+        builder_documents(mixed.clone(), &config),
+        vec![json!({"a": 1}), json!({"value": 2})]
+    );
+
+    let config = SchemaInferenceConfig {
+        outer_scalar_policy: OuterScalarPolicy::Array,
+        ..Default::default()
+    };
+    assert_eq!(
+        builder_documents(mixed, &config),
+        vec![json!({"value": [{"a": 1}, 2]})]
+    );
+}
+
+#[test]
+fn test_null_and_empty_documents() {
+    let config = SchemaInferenceConfig {
+        delimiter: Some(b'\n'),
+        ..Default::default()
+    };
+    let json_strings = vec!["null\n{}\n{\"a\": 1}".to_string()];
+    let result = infer_json_schema_from_strings(&json_strings, config).unwrap();
+    assert_eq!(result.schema["properties"]["a"]["type"], "integer");
+}
+
+#[test]
+fn test_wrap_root_takes_precedence_over_root_value_field() {
+    let json_strings = vec![r#"[1, 2]"#.to_string()];
+
+    let config = SchemaInferenceConfig {
+        wrap_root: Some("numbers".to_string()),
+        ..Default::default()
+    };
+    let result = infer_json_schema_from_strings(&json_strings, config).unwrap();
+    assert_eq!(result.schema["properties"]["numbers"]["type"], "array");
+    assert!(result.schema["properties"].get("value").is_none());
+}
+
+#[test]
+fn test_deeply_nested_array_is_rejected() {
+    let json = format!("{}1{}", "[".repeat(10_000), "]".repeat(10_000));
+    let err = infer_json_schema_from_strings(&[json], SchemaInferenceConfig::default())
+        .unwrap_err();
+    assert_eq!(
+        err,
+        "Invalid JSON input at index 1: nested more than 128 levels deep"
+    );
+
+    // Even when max_depth would collapse the deeper containers
+    let json = format!("{}1{}", "{\"a\": ".repeat(200), "}".repeat(200));
+    let config = SchemaInferenceConfig {
+        max_depth: Some(2),
+        ..SchemaInferenceConfig::default()
+    };
+    let err = infer_json_schema_from_strings(&[json], config).unwrap_err();
+    assert_eq!(
+        err,
+        "Invalid JSON input at index 1: nested more than 128 levels deep"
+    );
+}
+
+#[test]
+fn test_nesting_depth_ignores_brackets_in_strings() {
+    assert!(!exceeds_nesting_depth(r#"{"a": "[[[[\"[[["}"#, 2));
+    assert!(exceeds_nesting_depth(r#"{"a": [[1]]}"#, 2));
+    assert!(!exceeds_nesting_depth("{\"a\": [1]}\n{\"b\": [2]}", 2));
+}
+
+#[test]
+fn test_deep_nesting_within_limit() {
+    // Each level used to be rewritten twice (through `properties` too), doubling
+    // the work per level
+    let depth = MAX_NESTING_DEPTH - 1;
+    let json = format!("{}1{}", "{\"a\": ".repeat(depth), "}".repeat(depth));
+    let result =
+        infer_json_schema_from_strings(&[json], SchemaInferenceConfig::default()).unwrap();
+
+    let mut node = &result.schema;
+    for _ in 0..depth {
+        node = &node["properties"]["a"];
+    }
+    assert_eq!(node["type"], "integer");
+}
+
+#[test]
+fn test_nesting_limit_fits_default_thread_stack() {
+    // Objects and arrays alternating to the limit (the records in each array one
+    // level deeper), with map detection at every level, called from a default-sized
+    // thread stack whatever RUST_MIN_STACK is (this holds under the `trace` feature too)
+    let levels = (MAX_NESTING_DEPTH - 1) / 2;
+    let json = format!(
+        "{}1{}",
+        "{\"a\": [{\"b\": 2, \"c\": 3}, ".repeat(levels),
+        "]}".repeat(levels)
+    );
+    let config = SchemaInferenceConfig {
+        map_threshold: 2,
+        unify_maps: true,
+        ..SchemaInferenceConfig::default()
+    };
+    let result = std::thread::Builder::new()
+        .stack_size(2 * 1024 * 1024)
+        .spawn(move || infer_json_schema_from_strings(&[json], config))
+        .unwrap()
+        .join()
+        .unwrap();
+    result.unwrap();
+}
+
+#[test]
+fn test_force_scalar_types() {
+    let json_strings = vec![
+        r#"{"price": 12, "ts": "2024-01-01T00:00:00Z", "meta": {"n": 1}}"#.to_string(),
+        r#"{"price": "12.5", "ts": null, "meta": {"n": 2}}"#.to_string(),
+    ];
+    let config = SchemaInferenceConfig {
+        force_field_types: std::collections::HashMap::from([
+            ("price".to_string(), "number".to_string()),
+            ("ts".to_string(), "date-time".to_string()),
+            ("meta.n".to_string(), "string".to_string()),
+        ]),
+        ..Default::default()
+    };
+    let schema = infer_json_schema_from_strings(&json_strings, config)
+        .unwrap()
+        .schema;
+    let props = &schema["properties"];
+    assert_eq!(
+        props["price"],
+        json!({"type": "number", "x-forced-type": "number"})
+    );
+    assert_eq!(
+        props["ts"],
+        json!({"type": ["null", "string"], "format": "date-time", "x-forced-type": "date-time"})
+    );
+    assert_eq!(props["meta"]["properties"]["n"]["type"], "string");
+
+    let config = SchemaInferenceConfig {
+        force_field_types: std::collections::HashMap::from([("price".to_string(), "float".to_string())]),
+        ..Default::default()
+    };
+    let err = infer_json_schema_from_strings(&json_strings, config).unwrap_err();
+    assert!(err.starts_with("Invalid force_field_types value for price: float"));
+}
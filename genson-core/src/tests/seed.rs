@@ -0,0 +1,143 @@
+// genson-core/src/tests/seed.rs
+use super::*;
+use crate::{infer_json_schema_from_strings, SchemaInferenceConfig};
+use serde_json::json;
+
+fn infer(seed: Value, rows: &str, config: SchemaInferenceConfig) -> Result<Value, String> {
+    let config = SchemaInferenceConfig {
+        delimiter: Some(b'\n'),
+        seed_schema: Some(seed),
+        ..config
+    };
+    infer_json_schema_from_strings(&[rows.to_string()], config).map(|result| result.schema)
+}
+
+#[test]
+fn test_seed_is_widened() {
+    let seed = json!({
+        "$schema": "http://json-schema.org/schema#",
+        "type": "object",
+        "properties": {
+            "id": {"type": "integer", "description": "Row id"},
+            "name": {"type": "string", "format": "email"}
+        },
+        "required": ["id", "name"]
+    });
+    let schema = infer(
+        seed,
+        "{\"id\": \"a1\", \"score\": 0.5}",
+        SchemaInferenceConfig::default(),
+    )
+    .unwrap();
+
+    let props = &schema["properties"];
+    assert_eq!(
+        props["id"]["anyOf"],
+        json!([{"type": "integer", "description": "Row id"}, {"type": "string"}])
+    );
+    // A field the documents never had is kept, and made optional
+    assert_eq!(props["name"], json!({"type": "string", "format": "email"}));
+    assert_eq!(props["score"]["type"], "number");
+    assert_eq!(schema["required"], json!(["id"]));
+}
+
+#[test]
+fn test_seed_maps_absorb_new_keys() {
+    let seed = json!({
+        "type": "object",
+        "properties": {
+            "labels": {"type": "object", "additionalProperties": {"type": "string"}}
+        }
+    });
+    let schema = infer(
+        seed,
+        "{\"labels\": {\"en\": \"a\", \"fr\": 1}}",
+        SchemaInferenceConfig::default(),
+    )
+    .unwrap();
+
+    let labels = &schema["properties"]["labels"];
+    assert!(labels.get("properties").is_none());
+    assert_eq!(
+        labels["additionalProperties"]["type"],
+        json!(["integer", "string"])
+    );
+}
+
+#[test]
+fn test_seed_records_stay_records() {
+    let seed = json!({
+        "type": "object",
+        "properties": {
+            "meta": {"type": "object", "properties": {"a": {"type": "string"}}}
+        }
+    });
+    let rows = "{\"meta\": {\"a\": \"x\", \"b\": \"y\", \"c\": \"z\"}}";
+    let config = SchemaInferenceConfig {
+        map_threshold: 2,
+        ..Default::default()
+    };
+
+    let unseeded = infer_json_schema_from_strings(
+        &[rows.to_string()],
+        SchemaInferenceConfig {
+            map_threshold: 2,
+            ..Default::default()
+        },
+    )
+    .unwrap()
+    .schema;
+    assert!(unseeded["properties"]["meta"]["additionalProperties"].is_object());
+
+    let schema = infer(seed, rows, config).unwrap();
+    let meta = &schema["properties"]["meta"];
+    assert_eq!(
+        meta["properties"].as_object().unwrap().len(),
+        3,
+        "{:#}",
+        meta
+    );
+}
+
+#[test]
+fn test_seed_without_documents() {
+    let seed = json!({
+        "type": "object",
+        "properties": {"tags": {"type": "array", "items": {"type": "string"}}},
+        "required": ["tags"]
+    });
+    let mut schema = infer(seed.clone(), "", SchemaInferenceConfig::default()).unwrap();
+    schema.as_object_mut().unwrap().remove("$schema");
+    assert_eq!(schema, seed);
+}
+
+#[test]
+fn test_seed_records_paths() {
+    let seed = json!({
+        "type": "object",
+        "properties": {
+            "items": {
+                "type": "array",
+                "items": {"type": "object", "properties": {"sku": {"type": "string"}}}
+            },
+            "labels": {
+                "type": "object",
+                "additionalProperties": {"type": "object", "properties": {"v": {"type": "string"}}}
+            },
+            "maybe": {"anyOf": [{"type": "null"}, {"type": "object", "properties": {}}]}
+        }
+    });
+    let mut records: Vec<String> = seed_records(&seed).into_iter().collect();
+    records.sort();
+    assert_eq!(records, ["", "/items", "/labels/*", "/maybe"]);
+}
+
+#[test]
+fn test_invalid_seed() {
+    let err = infer(json!([1]), "{}", SchemaInferenceConfig::default()).unwrap_err();
+    assert_eq!(err, "Invalid seed_schema: expected a JSON Schema object");
+
+    let avro = json!({"type": "record", "name": "r", "fields": []});
+    let err = infer(avro, "{}", SchemaInferenceConfig::default()).unwrap_err();
+    assert!(err.contains("not an Avro schema"));
+}
@@ -0,0 +1,88 @@
+// genson-core/src/tests/select.rs
+use super::*;
+use crate::{infer_json_schema_from_strings, SchemaInferenceConfig};
+use serde_json::json;
+
+fn paths(paths: &[&str]) -> Vec<String> {
+    paths.iter().map(|p| p.to_string()).collect()
+}
+
+#[test]
+fn test_select_fields() {
+    let mut doc = json!({
+        "id": 1,
+        "name": "a",
+        "claims": {"P31": [{"mainsnak": {"datavalue": 5, "hash": "x"}, "rank": "normal"}]},
+        "meta": {"id": 2, "other": true}
+    });
+    select_fields(&mut doc, &paths(&["id", "/claims/*/mainsnak/datavalue"]));
+    assert_eq!(
+        doc,
+        json!({
+            "id": 1,
+            "claims": {"P31": [{"mainsnak": {"datavalue": 5}}]},
+            // Bare names match at any depth
+            "meta": {"id": 2}
+        })
+    );
+
+    // Selected fields keep their whole subtree
+    let mut doc = json!({"meta": {"a": {"b": 1}}, "x": 1});
+    select_fields(&mut doc, &paths(&["meta"]));
+    assert_eq!(doc, json!({"meta": {"a": {"b": 1}}}));
+
+    // Nothing is pruned without paths
+    let mut doc = json!({"x": 1});
+    select_fields(&mut doc, &[]);
+    assert_eq!(doc, json!({"x": 1}));
+}
+
+#[test]
+fn test_ancestors_of_missing_fields_are_kept() {
+    // `a` leads to `/a/b`, so is kept (and stays required) in rows without `b`, where it
+    // is left empty and so has no `required` to merge
+    let rows: Vec<String> = [json!({"a": {"b": 1, "c": 2}}), json!({"a": {"c": 3}, "d": 4})]
+        .iter()
+        .map(Value::to_string)
+        .collect();
+    let config = SchemaInferenceConfig {
+        select_paths: paths(&["/a/b"]),
+        ..SchemaInferenceConfig::default()
+    };
+    let schema = infer_json_schema_from_strings(&rows, config)
+        .unwrap()
+        .schema;
+    assert_eq!(schema["required"], json!(["a"]));
+    let a = &schema["properties"]["a"];
+    assert_eq!(a["properties"], json!({"b": {"type": "integer"}}));
+    assert_eq!(a["required"], json!(["b"]));
+}
+
+#[cfg(feature = "avro")]
+#[test]
+fn test_normalise_selected_fields() {
+    use crate::normalise::{try_normalise_values, NormaliseConfig, UnknownFieldPolicy};
+
+    let rows = vec![json!({"id": 1, "tags": ["x"], "meta": {"lang": "en", "n": 2}})];
+    let select = paths(&["id", "meta.lang"]);
+    let config = SchemaInferenceConfig {
+        avro: true,
+        select_paths: select.clone(),
+        ..SchemaInferenceConfig::default()
+    };
+    let input: Vec<String> = rows.iter().map(Value::to_string).collect();
+    let schema = infer_json_schema_from_strings(&input, config)
+        .unwrap()
+        .schema;
+
+    // Pruned fields aren't kept, nor reported as unknown
+    for unknown_fields in [UnknownFieldPolicy::Keep, UnknownFieldPolicy::Error] {
+        let cfg = NormaliseConfig {
+            unknown_fields,
+            select_paths: select.clone(),
+            ..NormaliseConfig::default()
+        };
+        let out = try_normalise_values(rows.clone(), &schema, &cfg).unwrap();
+        assert_eq!(out, vec![json!({"id": 1, "meta": {"lang": "en"}})]);
+    }
+}
@@ -0,0 +1,60 @@
+// genson-core/src/tests/serialise.rs
+use super::*;
+use serde_json::json;
+
+fn schema() -> Value {
+    json!({
+        "$schema": "http://json-schema.org/schema#",
+        "type": "object",
+        "properties": {
+            "id": {"type": "integer"},
+            "tags": {"type": "array", "items": {"type": "string"}}
+        },
+        "required": ["id"]
+    })
+}
+
+#[test]
+fn test_parse_output_format() {
+    assert_eq!("json".parse(), Ok(OutputFormat::Json));
+    assert_eq!("yml".parse(), Ok(OutputFormat::Yaml));
+    assert_eq!("toml".parse(), Ok(OutputFormat::Toml));
+    assert_eq!(
+        "xml".parse::<OutputFormat>(),
+        Err("Invalid output format: xml (expected json|yaml|toml)".to_string())
+    );
+}
+
+#[test]
+fn test_json_matches_pretty_print() {
+    let out = to_string(&schema(), OutputFormat::Json).unwrap();
+    assert_eq!(out, serde_json::to_string_pretty(&schema()).unwrap() + "\n");
+}
+
+#[cfg(feature = "yaml")]
+#[test]
+fn test_yaml_round_trips() {
+    let out = to_string(&schema(), OutputFormat::Yaml).unwrap();
+    assert!(out.starts_with("$schema: http://json-schema.org/schema#\ntype: object\n"));
+    let back: Value = serde_yaml::from_str(&out).unwrap();
+    assert_eq!(back, schema());
+}
+
+#[cfg(feature = "toml")]
+#[test]
+fn test_toml_round_trips() {
+    let out = to_string(&schema(), OutputFormat::Toml).unwrap();
+    assert!(out.contains("required = [\"id\"]"), "{}", out);
+    let back: Value = toml::from_str(&out).unwrap();
+    assert_eq!(back, schema());
+}
+
+#[cfg(feature = "toml")]
+#[test]
+fn test_toml_rejects_null() {
+    let document = json!({"properties": {"a": {"default": null}}});
+    assert_eq!(
+        to_string(&document, OutputFormat::Toml).unwrap_err(),
+        "TOML can't represent the null at /properties/a/default"
+    );
+}
@@ -0,0 +1,76 @@
+// genson-core/src/tests/session.rs
+use super::*;
+use crate::infer_json_schema_from_strings;
+
+fn strings(docs: &[&str]) -> Vec<String> {
+    docs.iter().map(|s| s.to_string()).collect()
+}
+
+#[test]
+fn test_session_matches_one_shot_inference() {
+    let first = strings(&[r#"{"id": 1, "name": "a"}"#, r#"{"id": 2}"#]);
+    let second = strings(&[r#"{"id": 3, "tags": ["x"]}"#]);
+    let config = SchemaInferenceConfig::default();
+
+    let mut session = SchemaInferenceSession::new(config.clone());
+    assert_eq!(session.add_strings(&first), Ok(2));
+    assert_eq!(session.add_strings(&second), Ok(1));
+
+    let all: Vec<String> = first.iter().chain(&second).cloned().collect();
+    let expected = infer_json_schema_from_strings(&all, config).unwrap();
+    let result = session.result().unwrap();
+    assert_eq!(result.schema, expected.schema);
+    assert_eq!(result.processed_count, 3);
+}
+
+#[test]
+fn test_session_save_and_resume() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("session.json");
+
+    let mut session = SchemaInferenceSession::new(SchemaInferenceConfig::default());
+    session.add_strings(&strings(&[r#"{"id": 1}"#])).unwrap();
+    session.save(&path).unwrap();
+    assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+
+    let mut resumed = SchemaInferenceSession::resume(&path).unwrap();
+    assert_eq!(resumed.processed_count(), 1);
+    resumed
+        .add_strings(&strings(&[r#"{"id": 2, "name": "b"}"#]))
+        .unwrap();
+
+    let schema = resumed.result().unwrap().schema;
+    assert_eq!(schema["properties"]["name"]["type"], "string");
+    assert_eq!(schema["required"], serde_json::json!(["id"]));
+    assert_eq!(resumed.processed_count(), 2);
+}
+
+#[test]
+fn test_empty_session_has_no_result() {
+    let session = SchemaInferenceSession::new(SchemaInferenceConfig::default());
+    assert!(session.result().is_err());
+}
+
+#[test]
+fn test_merge_sessions() {
+    let config = SchemaInferenceConfig::default();
+    let mut left = SchemaInferenceSession::new(config.clone());
+    left.add_strings(&strings(&[r#"{"id": 1}"#])).unwrap();
+    let mut right = SchemaInferenceSession::new(config.clone());
+    right
+        .add_strings(&strings(&[r#"{"id": 2, "name": "b"}"#, r#"{"id": 3}"#]))
+        .unwrap();
+
+    left.merge(right).unwrap();
+    left.merge(SchemaInferenceSession::new(config)).unwrap();
+    let result = left.result().unwrap();
+    assert_eq!(result.processed_count, 3);
+    assert_eq!(result.schema["properties"]["name"]["type"], "string");
+    assert_eq!(result.schema["required"], serde_json::json!(["id"]));
+
+    let other = SchemaInferenceSession::new(SchemaInferenceConfig {
+        map_threshold: 3,
+        ..Default::default()
+    });
+    assert!(left.merge(other).is_err());
+}
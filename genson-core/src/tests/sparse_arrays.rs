@@ -0,0 +1,85 @@
+// genson-core/src/tests/sparse_arrays.rs
+use super::*;
+use crate::schema::document_stats::DocumentStats;
+use crate::{infer_json_schema_from_strings, SchemaInferenceConfig};
+use serde_json::json;
+
+fn infer(rows: &[Value]) -> Value {
+    let input: Vec<String> = rows.iter().map(Value::to_string).collect();
+    let config = SchemaInferenceConfig {
+        sparse_array_detection: true,
+        ..SchemaInferenceConfig::default()
+    };
+    infer_json_schema_from_strings(&input, config)
+        .unwrap()
+        .schema
+}
+
+#[test]
+fn test_is_sparse_array() {
+    let obj = |v: Value| v.as_object().unwrap().clone();
+    assert!(is_sparse_array(&obj(json!({"1": "b", "0": "a"}))));
+    assert!(!is_sparse_array(&obj(json!({"0": "a", "2": "c"}))));
+    assert!(!is_sparse_array(&obj(json!({"00": "a"}))));
+    assert!(!is_sparse_array(&obj(json!({}))));
+    #[cfg(feature = "avro")]
+    assert_eq!(
+        unpack_sparse_array(obj(json!({"1": "b", "0": "a"}))),
+        vec![json!("a"), json!("b")]
+    );
+}
+
+#[test]
+fn test_positional_objects_become_arrays() {
+    let rows = [
+        json!({"tags": {"0": "a", "1": "b"}}),
+        json!({"tags": {"0": 1}}),
+    ];
+    let schema = infer(&rows);
+    assert_eq!(
+        schema["properties"]["tags"],
+        json!({"type": "array", "items": {"type": ["integer", "string"]}})
+    );
+}
+
+#[test]
+fn test_other_keys_keep_the_object() {
+    let rows = [
+        json!({"tags": {"0": "a", "1": "b"}}),
+        json!({"tags": {"0": "a", "2": "c"}}),
+    ];
+    let schema = infer(&rows);
+    assert_eq!(schema["properties"]["tags"]["type"], "object");
+}
+
+#[test]
+fn test_merges_with_real_arrays() {
+    let rows = [
+        json!({"pts": {"0": {"x": 1}, "1": {"x": 2}}}),
+        json!({"pts": [{"x": 3, "y": 4}]}),
+    ];
+    let schema = infer(&rows);
+    let pts = &schema["properties"]["pts"];
+    assert_eq!(pts["type"], "array");
+    assert!(pts.get("anyOf").is_none());
+    assert_eq!(pts["items"]["required"], json!(["x"]));
+}
+
+#[test]
+fn test_nested_and_merged_observations() {
+    let config = SchemaInferenceConfig {
+        sparse_array_detection: true,
+        ..SchemaInferenceConfig::default()
+    };
+    let a = DocumentStats::collect(&[r#"{"m": {"0": {"0": true}}}"#], &config).sparse_arrays;
+    let b = DocumentStats::collect(&[r#"{"m": {"k": {"0": true}}}"#], &config).sparse_arrays;
+    let sparse = merge_sparse_arrays(a, b);
+    assert!(!sparse["/m"]);
+    assert!(sparse["/m/0"]);
+
+    let schema = infer(&[json!({"grid": {"0": {"0": 1, "1": 2}}})]);
+    assert_eq!(
+        schema["properties"]["grid"],
+        json!({"type": "array", "items": {"type": "array", "items": {"type": "integer"}}})
+    );
+}
@@ -0,0 +1,70 @@
+// genson-core/src/tests/strict.rs
+use super::*;
+use crate::infer_json_schema_from_strings;
+use serde_json::json;
+
+fn strict() -> SchemaInferenceConfig {
+    SchemaInferenceConfig {
+        strict: true,
+        ..SchemaInferenceConfig::default()
+    }
+}
+
+fn infer(docs: &[&str], config: SchemaInferenceConfig) -> Result<Value, String> {
+    infer_json_schema_from_strings(docs, config).map(|r| r.schema)
+}
+
+#[test]
+fn test_strict_allows_nullable_fields() {
+    let docs = [r#"{"a": 1, "b": {"c": "x"}}"#, r#"{"a": null, "b": null}"#];
+    assert!(infer(&docs, strict()).is_ok());
+}
+
+#[test]
+fn test_strict_rejects_unions_and_promotions() {
+    let docs = [
+        r#"{"id": 1, "m": {"k1": {"bar": "x"}, "k2": {"bar": true}, "k3": {"bar": "y"}}}"#,
+        r#"{"id": "2"}"#,
+    ];
+    let config = SchemaInferenceConfig {
+        map_threshold: 2,
+        unify_maps: true,
+        ..strict()
+    };
+    assert!(infer(&docs, SchemaInferenceConfig { strict: false, ..config.clone() }).is_ok());
+    assert_eq!(
+        infer(&docs, config).unwrap_err(),
+        "Strict mode: 3 schema issue(s)\n  \
+         /id: union of integer and string\n  \
+         /m/*/bar: scalar promoted to field bar__boolean\n  \
+         /m/*/bar: scalar promoted to field bar__string"
+    );
+}
+
+#[test]
+fn test_strict_allows_forced_promotion() {
+    let mut config = strict();
+    config.force_scalar_promotion.insert("owner".to_string());
+    assert!(infer(&[r#"{"owner": "y"}"#], config).is_ok());
+}
+
+#[test]
+fn test_strict_issues_mixed_map_and_record() {
+    let path = ["labels".to_string()];
+    let explanations = [
+        FieldExplanation::new(&path, FieldDecision::Map, "above map_threshold"),
+        FieldExplanation::new(&path, FieldDecision::Record, "below map_threshold"),
+        FieldExplanation::new(&[], FieldDecision::Record, "no_root_map"),
+    ];
+    let schema = json!({"type": "object", "properties": {"labels": {"anyOf": [
+        {"type": "object", "additionalProperties": {"type": "string"}},
+        {"type": "object", "properties": {"en": {"type": "string"}}}
+    ]}}});
+    assert_eq!(
+        strict_issues(&schema, &explanations, &SchemaInferenceConfig::default()),
+        [
+            "/labels: both a map and a record",
+            "/labels: union of object and object"
+        ]
+    );
+}
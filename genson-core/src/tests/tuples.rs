@@ -0,0 +1,142 @@
+// genson-core/src/tests/tuples.rs
+use super::*;
+use crate::schema::document_stats::DocumentStats;
+use crate::{infer_json_schema_from_strings, JsonSchemaDraft, SchemaInferenceConfig};
+use serde_json::json;
+
+fn infer(rows: &[Value], detect_tuples: bool) -> Value {
+    let json = rows
+        .iter()
+        .map(Value::to_string)
+        .collect::<Vec<_>>()
+        .join("\n");
+    let config = SchemaInferenceConfig {
+        delimiter: Some(b'\n'),
+        detect_tuples,
+        ..Default::default()
+    };
+    infer_json_schema_from_strings(&[json], config)
+        .expect("inference")
+        .schema
+}
+
+#[test]
+fn test_positional_pairs_become_prefix_items() {
+    let rows = [
+        json!({"pair": ["a", {"x": 1}]}),
+        json!({"pair": ["b", {"x": 2}]}),
+    ];
+    let schema = infer(&rows, true);
+
+    let pair = &schema["properties"]["pair"];
+    assert_eq!(pair["type"], "array");
+    assert!(pair.get("items").is_none());
+    assert_eq!(pair["prefixItems"][0], json!({"type": "string"}));
+    assert_eq!(pair["prefixItems"][1]["type"], "object");
+    assert_eq!(pair["prefixItems"][1]["properties"]["x"]["type"], "integer");
+}
+
+#[test]
+fn test_tuples_not_detected_by_default() {
+    let rows = [json!({"pair": ["a", 1]}), json!({"pair": ["b", 2]})];
+    let schema = infer(&rows, false);
+
+    assert!(schema["properties"]["pair"]["items"].is_object());
+    assert!(schema["properties"]["pair"].get("prefixItems").is_none());
+}
+
+#[test]
+fn test_varying_lengths_stay_lists() {
+    let rows = [json!({"pair": ["a", 1]}), json!({"pair": ["b", 2, "c"]})];
+    let schema = infer(&rows, true);
+
+    assert!(schema["properties"]["pair"]["items"].is_object());
+}
+
+#[test]
+fn test_same_schema_at_every_position_stays_a_list() {
+    let rows = [json!({"point": [1, 2]}), json!({"point": [3, 4]})];
+    let schema = infer(&rows, true);
+
+    assert_eq!(
+        schema["properties"]["point"]["items"],
+        json!({"type": "integer"})
+    );
+}
+
+#[test]
+fn test_tuples_nested_in_lists() {
+    // A list of pairs: the outer arrays vary in length, the inner ones don't
+    let rows = [
+        json!({"pairs": [["a", 1], ["b", 2], ["c", 3]]}),
+        json!({"pairs": [["d", 4]]}),
+    ];
+    let schema = infer(&rows, true);
+
+    let items = &schema["properties"]["pairs"]["items"];
+    assert_eq!(
+        items["prefixItems"],
+        json!([{"type": "string"}, {"type": "integer"}])
+    );
+}
+
+#[test]
+fn test_draft_07_uses_items_array() {
+    let json = r#"{"pair": ["a", 1]}"#.to_string();
+    let config = SchemaInferenceConfig {
+        detect_tuples: true,
+        json_schema_draft: Some(JsonSchemaDraft::Draft07),
+        ..Default::default()
+    };
+    let schema = infer_json_schema_from_strings(&[json], config)
+        .unwrap()
+        .schema;
+
+    assert_eq!(
+        schema["properties"]["pair"]["items"],
+        json!([{"type": "string"}, {"type": "integer"}])
+    );
+}
+
+#[test]
+fn test_shapes_of_different_lengths_do_not_merge() {
+    let config = SchemaInferenceConfig {
+        detect_tuples: true,
+        ..Default::default()
+    };
+    let short = DocumentStats::collect(&[r#"{"pair": ["a", 1]}"#], &config).tuple_shapes;
+    let long = DocumentStats::collect(&[r#"{"pair": ["a", 1, true]}"#], &config).tuple_shapes;
+
+    let merged = merge_tuple_shapes(short.clone(), long);
+    assert_eq!(merged["/pair"].tuple_items(), None);
+
+    let merged = merge_tuple_shapes(short.clone(), short);
+    assert_eq!(merged["/pair"].tuple_items().map(<[Value]>::len), Some(2));
+}
+
+#[cfg(feature = "avro")]
+#[test]
+fn test_avro_tuple_is_positional_record() {
+    use crate::normalise::{normalise_values, NormaliseConfig};
+
+    let config = SchemaInferenceConfig {
+        avro: true,
+        detect_tuples: true,
+        ..Default::default()
+    };
+    let schema = infer_json_schema_from_strings(&[r#"{"pair": ["a", 1]}"#.to_string()], config)
+        .unwrap()
+        .schema;
+
+    let pair = &schema["fields"][0]["type"];
+    assert_eq!(pair["type"], "record");
+    assert_eq!(pair["fields"][0]["name"], "_0");
+    assert_eq!(pair["fields"][1]["name"], "_1");
+
+    let normalised = normalise_values(
+        vec![json!({"pair": ["b", 2]})],
+        &schema,
+        &NormaliseConfig::default(),
+    );
+    assert_eq!(normalised[0], json!({"pair": {"_0": "b", "_1": 2}}));
+}
@@ -1,6 +1,6 @@
 use super::*;
 use serde_json::json;
-use crate::{infer_json_schema_from_strings, schema::rewrite_objects};
+use crate::{infer_json_schema_from_strings, schema::{rewrite_objects, RewriteHints}};
 
 #[test]
 fn test_scalar_unification_ndjson_mixed_nullable_formats() {
@@ -92,7 +92,7 @@ fn test_anyof_unification() {
         ..Default::default()
     };
 
-    rewrite_objects(&mut anyof_schema, Some("datavalue"), &config, false);
+    rewrite_objects(&mut anyof_schema, Some("datavalue"), &[], &config, false, &RewriteHints::default(), None);
     println!("Generated schema: {}", serde_json::to_string_pretty(&anyof_schema).unwrap());
 
     // Should be unified to a single object, not anyOf
@@ -147,7 +147,7 @@ fn test_anyof_rewrite_objects_root() {
     });
 
     let config = SchemaInferenceConfig { unify_maps: true, wrap_scalars: true, ..Default::default() };
-    rewrite_objects(&mut nested_schema, None, &config, true);
+    rewrite_objects(&mut nested_schema, None, &[], &config, true, &RewriteHints::default(), None);
 
     println!("{}", nested_schema);
 
@@ -176,7 +176,7 @@ fn test_anyof_rewrite_objects_nested() {
     });
 
     let config = SchemaInferenceConfig { unify_maps: true, wrap_scalars: true, ..Default::default() };
-    rewrite_objects(&mut nested_schema, None, &config, true);
+    rewrite_objects(&mut nested_schema, None, &[], &config, true, &RewriteHints::default(), None);
 
     println!("{}", nested_schema);
 
@@ -587,3 +587,167 @@ fn test_nullable_union_from_null_and_typed() {
     let result = try_make_nullable_union(&null_schema, &null_schema);
     assert_eq!(result, None);
 }
+
+#[test]
+fn test_map_values_of_different_scalar_types_unify_to_union() {
+    let config = SchemaInferenceConfig::default();
+    let ints = json!({"type": "object", "additionalProperties": {"type": "integer"}});
+    let strings = json!({"type": "object", "additionalProperties": {"type": ["null", "string"]}});
+
+    let unified = check_unifiable_schemas(&[&ints, &strings], "m", &config).unwrap();
+    assert_eq!(
+        unified,
+        json!({
+            "type": "object",
+            "additionalProperties": {"type": ["null", "integer", "string"]}
+        })
+    );
+
+    // A union already formed unifies again, e.g. when merging halves in parallel
+    let bools = json!({"type": "object", "additionalProperties": {"type": "boolean"}});
+    let again = check_unifiable_schemas(&[&unified, &bools], "m", &config).unwrap();
+    assert_eq!(
+        again["additionalProperties"],
+        json!({"type": ["null", "integer", "string", "boolean"]})
+    );
+}
+
+#[test]
+fn test_map_values_union_inside_arrays() {
+    let config = SchemaInferenceConfig::default();
+    let a = json!({"type": "array", "items": {"type": "object", "additionalProperties": {"type": "integer"}}});
+    let b = json!({"type": "array", "items": {"type": "object", "additionalProperties": {"type": "string"}}});
+
+    let unified = check_unifiable_schemas(&[&a, &b], "claims", &config).unwrap();
+    assert_eq!(
+        unified["items"]["additionalProperties"],
+        json!({"type": ["integer", "string"]})
+    );
+}
+
+#[test]
+fn test_map_fields_of_records_unify() {
+    // Record fields holding maps with different value types no longer block unification
+    let config = SchemaInferenceConfig::default();
+    let a = json!({"type": "object", "properties": {
+        "id": {"type": "string"},
+        "counts": {"type": "object", "additionalProperties": {"type": "integer"}}
+    }, "required": ["id", "counts"]});
+    let b = json!({"type": "object", "properties": {
+        "id": {"type": "string"},
+        "counts": {"type": "object", "additionalProperties": {"type": "number"}}
+    }, "required": ["id", "counts"]});
+
+    let unified = check_unifiable_schemas(&[&a, &b], "root", &config).unwrap();
+    assert_eq!(
+        unified["properties"]["counts"]["additionalProperties"],
+        json!({"type": ["integer", "number"]})
+    );
+}
+
+#[test]
+fn test_map_values_mixing_scalars_and_records_still_fail() {
+    let config = SchemaInferenceConfig {
+        wrap_scalars: false,
+        ..Default::default()
+    };
+    let a = json!({"type": "object", "additionalProperties": {"type": "integer"}});
+    let b = json!({"type": "object", "additionalProperties": {"type": "object", "properties": {"x": {"type": "string"}}}});
+    assert!(check_unifiable_schemas(&[&a, &b], "m", &config).is_none());
+}
+
+#[test]
+fn test_record_union_branches_unify() {
+    let config = SchemaInferenceConfig::default();
+    let union = json!({"anyOf": [
+        {"type": "object", "properties": {"id": {"type": "string"}, "n": {"type": "integer"}}, "required": ["id", "n"]},
+        {"type": "object", "properties": {"id": {"type": "string"}}, "required": ["id"]}
+    ]});
+    let record = json!({"type": "object", "properties": {"id": {"type": "string"}}, "required": ["id"]});
+
+    let unified = check_unifiable_schemas(&[&union, &record], "root", &config).unwrap();
+    assert_eq!(
+        unified,
+        json!({
+            "type": "object",
+            "properties": {"id": {"type": "string"}, "n": {"type": ["null", "integer"]}},
+            "required": ["id"]
+        })
+    );
+}
+
+#[test]
+fn test_nullable_record_union_collapses_in_rewrite() {
+    let mut schema = json!({"anyOf": [
+        {"type": "null"},
+        {"type": "object", "properties": {"a": {"type": "string"}}, "required": ["a"]},
+        {"type": "object", "properties": {"b": {"type": "integer"}}, "required": ["b"]}
+    ]});
+    let config = SchemaInferenceConfig {
+        unify_maps: true,
+        wrap_scalars: false,
+        ..Default::default()
+    };
+
+    rewrite_objects(&mut schema, Some("value"), &[], &config, false, &RewriteHints::default(), None);
+    assert_eq!(schema["type"], json!(["null", "object"]));
+    assert_eq!(schema["properties"]["a"]["type"], json!(["null", "string"]));
+    assert_eq!(schema["properties"]["b"]["type"], json!(["null", "integer"]));
+}
+
+#[test]
+fn test_incompatible_record_branches_stay_a_union() {
+    let config = SchemaInferenceConfig {
+        wrap_scalars: false,
+        ..Default::default()
+    };
+    let union = json!({"anyOf": [
+        {"type": "object", "properties": {"a": {"type": "string"}}},
+        {"type": "object", "properties": {"a": {"type": "object", "properties": {"x": {"type": "integer"}}}}}
+    ]});
+    let record = json!({"type": "object", "properties": {"a": {"type": "string"}}});
+
+    assert!(check_unifiable_schemas(&[&union, &record], "root", &config).is_none());
+}
+
+#[test]
+fn test_array_of_scalars_vs_array_of_records_promotion() {
+    let config = SchemaInferenceConfig {
+        wrap_scalars: true,
+        ..Default::default()
+    };
+    let a = json!({"type": "object", "properties": {"refs": {"type": "array", "items": {"type": "string"}}}, "required": ["refs"]});
+    let b = json!({"type": "object", "properties": {"refs": {"type": "array", "items": {"type": "object", "properties": {"x": {"type": "integer"}}, "required": ["x"]}}}, "required": ["refs"]});
+
+    let unified = check_unifiable_schemas(&[&a, &b], "root", &config).unwrap();
+    assert_eq!(
+        unified["properties"]["refs"],
+        json!({"type": "array", "items": {"type": "object", "properties": {
+            "refs__string": {"type": ["null", "string"]},
+            "x": {"type": ["null", "integer"]}
+        }}})
+    );
+
+    // Without scalar promotion the item types still clash
+    let config = SchemaInferenceConfig {
+        wrap_scalars: false,
+        ..Default::default()
+    };
+    assert!(check_unifiable_schemas(&[&a, &b], "root", &config).is_none());
+}
+
+#[test]
+fn test_array_item_promotion_in_map_values() {
+    let input = r#"{"claims": {"P1": {"references": ["a"]}, "P2": {"references": [{"x": 1}]}, "P3": {"references": [2, {"y": true}]}}}"#;
+    let config = SchemaInferenceConfig {
+        map_threshold: 2,
+        unify_maps: true,
+        wrap_scalars: true,
+        ..Default::default()
+    };
+    let result = infer_json_schema_from_strings(&[input.to_string()], config).unwrap();
+    let items = &result.schema["properties"]["claims"]["additionalProperties"]["properties"]["references"]["items"];
+    let mut keys: Vec<&String> = items["properties"].as_object().unwrap().keys().collect();
+    keys.sort();
+    assert_eq!(keys, vec!["references__integer", "references__string", "x", "y"]);
+}
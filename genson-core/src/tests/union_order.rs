@@ -0,0 +1,166 @@
+// genson-core/src/tests/union_order.rs
+use super::*;
+use crate::schema::document_stats::DocumentStats;
+use crate::{infer_json_schema_from_strings, SchemaInferenceConfig};
+use serde_json::json;
+
+fn infer(rows: &[Value], union_order: UnionOrder) -> Value {
+    let input: Vec<String> = rows.iter().map(Value::to_string).collect();
+    let config = SchemaInferenceConfig {
+        union_order,
+        ..SchemaInferenceConfig::default()
+    };
+    infer_json_schema_from_strings(&input, config)
+        .unwrap()
+        .schema
+}
+
+#[test]
+fn test_canonical_keeps_reorder_unions() {
+    let rows = [json!({"a": "x"}), json!({"a": 1}), json!({"a": true})];
+    let schema = infer(&rows, UnionOrder::Canonical);
+    assert_eq!(
+        schema["properties"]["a"]["type"],
+        json!(["boolean", "integer", "string"])
+    );
+}
+
+#[test]
+fn test_null_first() {
+    let mut schema = json!({"anyOf": [{"type": "string"}, {"type": "null"}]});
+    order_unions(&mut schema, UnionOrder::NullFirst, &TypeCounts::new());
+    assert_eq!(schema["anyOf"], json!([{"type": "null"}, {"type": "string"}]));
+
+    let mut schema = json!({"type": ["string", "null"]});
+    order_unions(&mut schema, UnionOrder::NullFirst, &TypeCounts::new());
+    assert_eq!(schema["type"], json!(["null", "string"]));
+}
+
+#[test]
+fn test_by_frequency() {
+    let rows = [json!({"a": 1}), json!({"a": "x"}), json!({"a": "y"})];
+    let schema = infer(&rows, UnionOrder::ByFrequency);
+    assert_eq!(schema["properties"]["a"]["type"], json!(["string", "integer"]));
+}
+
+#[test]
+fn test_by_frequency_sums_map_values() {
+    let mut counts = TypeCounts::new();
+    counts.insert("/m/k1".to_string(), HashMap::from([("integer".into(), 1)]));
+    counts.insert("/m/k2".to_string(), HashMap::from([("string".into(), 1)]));
+    counts.insert("/m/k3".to_string(), HashMap::from([("string".into(), 1)]));
+    let mut schema = json!({
+        "type": "object",
+        "properties": {"m": {
+            "type": "object",
+            "additionalProperties": {"type": ["integer", "string"]}
+        }}
+    });
+    order_unions(&mut schema, UnionOrder::ByFrequency, &counts);
+    assert_eq!(
+        schema["properties"]["m"]["additionalProperties"]["type"],
+        json!(["string", "integer"])
+    );
+}
+
+#[test]
+fn test_by_frequency_number_counts_integers() {
+    let rows = [
+        json!({"a": 1}),
+        json!({"a": 2}),
+        json!({"a": 0.5}),
+        json!({"a": "x"}),
+        json!({"a": "y"}),
+    ];
+    let schema = infer(&rows, UnionOrder::ByFrequency);
+    assert_eq!(schema["properties"]["a"]["type"], json!(["number", "string"]));
+}
+
+#[test]
+fn test_default_first() {
+    let mut schema = json!({"type": ["null", "string"], "default": "n/a"});
+    order_unions(&mut schema, UnionOrder::DefaultFirst, &TypeCounts::new());
+    assert_eq!(schema["type"], json!(["string", "null"]));
+
+    // Without a default, null comes first
+    let mut schema = json!({"anyOf": [{"type": "integer"}, {"type": "null"}]});
+    order_unions(&mut schema, UnionOrder::DefaultFirst, &TypeCounts::new());
+    assert_eq!(schema["anyOf"][0], json!({"type": "null"}));
+}
+
+#[test]
+fn test_type_counts_merge() {
+    let config = SchemaInferenceConfig {
+        union_order: UnionOrder::ByFrequency,
+        ..SchemaInferenceConfig::default()
+    };
+    let a = DocumentStats::collect(&[r#"{"a": 1}"#], &config).type_counts;
+    let b = DocumentStats::collect(&[r#"{"a": "x"}"#, r#"{"a": 2}"#], &config).type_counts;
+    let counts = merge_type_counts(a, b);
+    assert_eq!(counts["/a"]["integer"], 2);
+    assert_eq!(counts["/a"]["string"], 1);
+}
+
+#[cfg(feature = "avro")]
+#[test]
+fn test_avro_default_first() {
+    let mut avro = json!({
+        "type": "record",
+        "name": "document",
+        "fields": [
+            {"name": "a", "type": ["null", "string"], "default": "n/a"},
+            {"name": "b", "type": ["long", "null"]},
+            {"name": "c", "type": {"type": "map", "values": ["null", "double"]}, "default": {}}
+        ]
+    });
+    order_avro_unions(&mut avro, UnionOrder::DefaultFirst, &TypeCounts::new());
+    assert_eq!(avro["fields"][0]["type"], json!(["string", "null"]));
+    assert_eq!(avro["fields"][1]["type"], json!(["null", "long"]));
+    assert_eq!(avro["fields"][2]["type"]["values"], json!(["null", "double"]));
+}
+
+#[cfg(feature = "avro")]
+#[test]
+fn test_avro_by_frequency() {
+    let rows = [
+        json!({"a": 1}),
+        json!({"a": 2}),
+        json!({"a": "x"}),
+        json!({"a": null}),
+    ];
+    let input: Vec<String> = rows.iter().map(Value::to_string).collect();
+    let config = SchemaInferenceConfig {
+        avro: true,
+        avro_defaults: false,
+        union_order: UnionOrder::ByFrequency,
+        ..SchemaInferenceConfig::default()
+    };
+    let avro = infer_json_schema_from_strings(&input, config)
+        .unwrap()
+        .schema;
+    let a = avro["fields"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|f| f["name"] == "a")
+        .unwrap();
+    assert_eq!(a["type"][0], "int");
+}
+
+#[cfg(feature = "avro")]
+#[test]
+fn test_avro_default_stays_first() {
+    // Null is seen least, but the optional field defaults to it
+    let rows = [json!({"a": 1}), json!({"a": 2}), json!({"b": null})];
+    let input: Vec<String> = rows.iter().map(Value::to_string).collect();
+    let config = SchemaInferenceConfig {
+        avro: true,
+        union_order: UnionOrder::ByFrequency,
+        ..SchemaInferenceConfig::default()
+    };
+    let avro = infer_json_schema_from_strings(&input, config)
+        .unwrap()
+        .schema;
+    assert_eq!(avro["fields"][0]["type"], json!(["null", "int"]));
+    assert_eq!(avro["fields"][0]["default"], Value::Null);
+}
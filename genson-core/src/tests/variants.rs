@@ -0,0 +1,90 @@
+// genson-core/src/tests/variants.rs
+use super::*;
+use serde_json::json;
+
+#[test]
+fn test_documents_grouped_by_signature() {
+    let rows = [
+        json!({"id": 1, "user": {"name": "a"}}),
+        json!({"order": {"id": "x", "items": [{"sku": "A", "qty": 2}]}}),
+        json!({"id": 2, "user": {"name": "b"}}),
+        json!({"id": 3, "user": {"name": "c"}}),
+        json!({"order": {"id": "y", "items": []}}),
+    ];
+    let variants = find_variants(&rows, None, 2);
+
+    assert_eq!(variants.len(), 3);
+    assert_eq!(variants[0].count, 3);
+    assert_eq!(
+        variants[0].paths,
+        BTreeMap::from([
+            ("id".to_string(), "integer".to_string()),
+            ("user".to_string(), "object".to_string()),
+            ("user.name".to_string(), "string".to_string()),
+        ])
+    );
+    assert_eq!(variants[0].examples, vec![rows[0].clone(), rows[2].clone()]);
+    // An empty array has no item paths, so it is a variant of its own
+    assert_eq!(variants[1].paths["order.items[]"], "object");
+    assert_eq!(variants[1].paths["order.items[].qty"], "integer");
+    assert!(!variants[2].paths.contains_key("order.items[]"));
+}
+
+#[test]
+fn test_values_do_not_affect_signature() {
+    let rows = [json!({"a": 1, "b": "x"}), json!({"b": "y", "a": 2})];
+    let variants = find_variants(&rows, None, 1);
+
+    assert_eq!(variants.len(), 1);
+    assert_eq!(variants[0].count, 2);
+    assert_eq!(variants[0].examples.len(), 1);
+}
+
+#[test]
+fn test_types_distinguish_variants() {
+    let rows = [json!({"a": 1}), json!({"a": 1.5}), json!({"a": null})];
+    let variants = find_variants(&rows, None, 0);
+
+    let types: Vec<&str> = variants.iter().map(|v| v.paths["a"].as_str()).collect();
+    assert_eq!(types, ["integer", "number", "null"]);
+    assert!(variants.iter().all(|v| v.examples.is_empty()));
+}
+
+#[test]
+fn test_mixed_array_items_list_every_type() {
+    let variants = find_variants(&[json!({"tags": ["a", 1, "b"]})], None, 1);
+
+    assert_eq!(variants[0].paths["tags[]"], "integer|string");
+}
+
+#[test]
+fn test_top_keeps_most_common_first_seen_on_ties() {
+    let rows = [json!({"a": 1}), json!({"b": 1}), json!({"c": 1}), json!({"c": 2})];
+    let variants = find_variants(&rows, Some(2), 1);
+
+    let first_keys: Vec<&str> = variants
+        .iter()
+        .map(|v| v.paths.keys().next().unwrap().as_str())
+        .collect();
+    assert_eq!(first_keys, ["c", "a"]);
+}
+
+#[test]
+fn test_scalar_document_uses_root_path() {
+    let variants = find_variants(&[json!("x"), json!({})], None, 1);
+
+    assert_eq!(variants[0].paths[ROOT_PATH], "string");
+    assert!(variants[1].paths.is_empty());
+}
+
+#[test]
+fn test_tracker_counts_rows_and_variants() {
+    let mut tracker = VariantTracker::new(1);
+    assert!(tracker.is_empty());
+    for row in [json!({"a": 1}), json!({"a": 2}), json!({"b": 1})] {
+        tracker.observe(&row);
+    }
+    assert_eq!(tracker.rows(), 3);
+    assert_eq!(tracker.len(), 2);
+    assert_eq!(tracker.finish(Some(1))[0].count, 2);
+}
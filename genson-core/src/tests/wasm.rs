@@ -0,0 +1,27 @@
+// genson-core/src/tests/wasm.rs
+use super::*;
+use serde_json::json;
+
+#[test]
+fn test_snake_case() {
+    assert_eq!(snake_case("mapThreshold"), "map_threshold");
+    assert_eq!(snake_case("map_threshold"), "map_threshold");
+    assert_eq!(snake_case("avro"), "avro");
+}
+
+#[test]
+fn test_config_from_js() {
+    let config = config_from_js(json!({"mapThreshold": 5, "ndjson": true})).unwrap();
+    assert_eq!(config.map_threshold, 5);
+    assert_eq!(config.delimiter, Some(b'\n'));
+
+    let config = config_from_js(Value::Null).unwrap();
+    assert_eq!(config.map_threshold, SchemaInferenceConfig::default().map_threshold);
+    assert_eq!(config.delimiter, None);
+
+    assert_eq!(
+        config_from_js(json!({"mapTreshold": 5})).unwrap_err(),
+        "Unknown config setting: map_treshold"
+    );
+    assert!(config_from_js(json!([1])).is_err());
+}
@@ -0,0 +1,169 @@
+//! Structural variants: documents grouped by the paths and types they contain.
+//!
+//! A merged schema hides how many different shapes went into it. Grouping the
+//! documents by their structural signature shows, for instance, that a mixed-source
+//! NDJSON file holds three payload shapes, and how many rows have each.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+/// Default number of variants reported.
+pub const DEFAULT_TOP_VARIANTS: usize = 10;
+
+/// Default number of example documents kept per variant.
+pub const DEFAULT_VARIANT_EXAMPLES: usize = 1;
+
+/// The path of the document itself in a signature, when it isn't an object.
+pub const ROOT_PATH: &str = "$";
+
+/// Documents that share a structural signature.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Variant {
+    /// Type of the value at each dotted path, `[]` marking array items. Paths seen
+    /// with several types (e.g. mixed array items) list them joined by `|`.
+    pub paths: BTreeMap<String, String>,
+    /// Number of documents with this signature
+    pub count: usize,
+    /// The first documents seen with this signature
+    pub examples: Vec<Value>,
+}
+
+/// Groups a stream of JSON documents by structural signature.
+pub struct VariantTracker {
+    max_examples: usize,
+    variants: Vec<Variant>,
+    index: HashMap<BTreeMap<String, String>, usize>,
+    rows: usize,
+}
+
+impl VariantTracker {
+    pub fn new(max_examples: usize) -> Self {
+        Self {
+            max_examples,
+            variants: Vec::new(),
+            index: HashMap::new(),
+            rows: 0,
+        }
+    }
+
+    /// Add one document to its variant.
+    pub fn observe(&mut self, value: &Value) {
+        self.rows += 1;
+        let paths = signature(value);
+        let idx = match self.index.get(&paths) {
+            Some(&idx) => idx,
+            None => {
+                self.index.insert(paths.clone(), self.variants.len());
+                self.variants.push(Variant {
+                    paths,
+                    count: 0,
+                    examples: Vec::new(),
+                });
+                self.variants.len() - 1
+            }
+        };
+        let variant = &mut self.variants[idx];
+        variant.count += 1;
+        if variant.examples.len() < self.max_examples {
+            variant.examples.push(value.clone());
+        }
+    }
+
+    /// Number of documents observed.
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Number of distinct variants seen so far.
+    pub fn len(&self) -> usize {
+        self.variants.len()
+    }
+
+    /// Whether no documents have been observed.
+    pub fn is_empty(&self) -> bool {
+        self.variants.is_empty()
+    }
+
+    /// The `top` most common variants, most common first (ties in the order first
+    /// seen), or all of them when `top` is `None`.
+    pub fn finish(self, top: Option<usize>) -> Vec<Variant> {
+        let mut variants = self.variants;
+        // Stable sort keeps first-seen order among equal counts
+        variants.sort_by_key(|v| std::cmp::Reverse(v.count));
+        if let Some(top) = top {
+            variants.truncate(top);
+        }
+        variants
+    }
+}
+
+/// Group `values` by structural signature and report the `top` most common variants.
+pub fn find_variants<'v>(
+    values: impl IntoIterator<Item = &'v Value>,
+    top: Option<usize>,
+    max_examples: usize,
+) -> Vec<Variant> {
+    let mut tracker = VariantTracker::new(max_examples);
+    for value in values {
+        tracker.observe(value);
+    }
+    tracker.finish(top)
+}
+
+/// The type at every path of `value`. An object document contributes only its
+/// fields, so the signature of `{}` is empty.
+fn signature(value: &Value) -> BTreeMap<String, String> {
+    let mut types: BTreeMap<String, BTreeSet<&'static str>> = BTreeMap::new();
+    match value {
+        Value::Object(obj) => {
+            for (key, child) in obj {
+                collect_types(child, key.clone(), &mut types);
+            }
+        }
+        value => collect_types(value, ROOT_PATH.to_string(), &mut types),
+    }
+    types
+        .into_iter()
+        .map(|(path, types)| (path, types.into_iter().collect::<Vec<_>>().join("|")))
+        .collect()
+}
+
+fn collect_types(
+    value: &Value,
+    path: String,
+    types: &mut BTreeMap<String, BTreeSet<&'static str>>,
+) {
+    match value {
+        Value::Object(obj) => {
+            for (key, child) in obj {
+                collect_types(child, format!("{}.{}", path, key), types);
+            }
+        }
+        Value::Array(items) => {
+            let item_path = format!("{}[]", path);
+            for item in items {
+                collect_types(item, item_path.clone(), types);
+            }
+        }
+        _ => {}
+    }
+    types.entry(path).or_default().insert(type_name(value));
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(n) if n.is_f64() => "number",
+        Value::Number(_) => "integer",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    include!("tests/variants.rs");
+}
@@ -0,0 +1,84 @@
+//! JavaScript bindings for schema inference, with the `wasm` feature.
+//!
+//! Build for `wasm32-unknown-unknown` without the default features (there are no
+//! threads to normalise on), then generate the JS glue with `wasm-bindgen`:
+//!
+//! ```sh
+//! cargo rustc -p genson-core --lib --crate-type cdylib --release \
+//!     --target wasm32-unknown-unknown --no-default-features --features wasm
+//! wasm-bindgen --target web --out-dir pkg \
+//!     target/wasm32-unknown-unknown/release/genson_core.wasm
+//! ```
+//!
+//! A panic inside inference aborts the module rather than being caught, as
+//! wasm32 has no unwinding.
+
+use crate::{infer_json_schema_from_strings, SchemaInferenceConfig};
+use serde::Serialize;
+use serde_json::{Map, Value};
+use wasm_bindgen::prelude::*;
+
+/// Infer a JSON Schema from `json`: one document, an array of documents, or NDJSON
+/// with `{ndjson: true}`.
+///
+/// `config` is an object of [`SchemaInferenceConfig`] settings, keyed in
+/// camelCase or snake_case (`{mapThreshold: 5}`), or `undefined` for the defaults.
+/// Errors are thrown as strings.
+#[wasm_bindgen(js_name = inferSchema)]
+pub fn infer_schema(json: &str, config: JsValue) -> Result<JsValue, JsValue> {
+    let overrides: Value = if config.is_undefined() || config.is_null() {
+        Value::Null
+    } else {
+        serde_wasm_bindgen::from_value(config).map_err(|e| JsValue::from_str(&e.to_string()))?
+    };
+    let config = config_from_js(overrides).map_err(|e| JsValue::from_str(&e))?;
+    let result = infer_json_schema_from_strings(&[json], config)
+        .map_err(|e| JsValue::from_str(&format!("Schema inference failed: {}", e)))?;
+    result
+        .schema
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+/// The inference config for the settings object passed from JavaScript.
+fn config_from_js(overrides: Value) -> Result<SchemaInferenceConfig, String> {
+    let overrides = match overrides {
+        Value::Null => Map::new(),
+        Value::Object(obj) => obj,
+        _ => return Err("Expected a config object".to_string()),
+    };
+    let mut settings = Map::new();
+    for (key, value) in overrides {
+        let key = snake_case(&key);
+        if key == "ndjson" {
+            let ndjson = value.as_bool().ok_or("Expected a boolean for ndjson")?;
+            settings.insert(
+                "delimiter".to_string(),
+                Value::from(ndjson.then_some(b'\n')),
+            );
+        } else {
+            settings.insert(key, value);
+        }
+    }
+    // Settings of features left out of the build (e.g. `avro`) are unknown
+    SchemaInferenceConfig::from_overrides(&Value::Object(settings))
+}
+
+/// `mapThreshold` as `map_threshold`; snake_case keys are unchanged.
+fn snake_case(key: &str) -> String {
+    let mut out = String::with_capacity(key.len() + 4);
+    for c in key.chars() {
+        if c.is_ascii_uppercase() {
+            out.push('_');
+            out.push(c.to_ascii_lowercase());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    include!("tests/wasm.rs");
+}
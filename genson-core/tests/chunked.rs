@@ -0,0 +1,79 @@
+use genson_core::{infer_json_schema_from_strings, SchemaInferenceConfig};
+
+fn rows() -> Vec<String> {
+    (0..50)
+        .map(|i| match i % 3 {
+            0 => format!(r#"{{"id": {}, "name": "n{}", "tags": ["a"]}}"#, i, i),
+            1 => format!(r#"{{"id": {}, "score": 1.5, "meta": {{"k": "v"}}}}"#, i),
+            _ => format!(r#"{{"id": "{}", "tags": [], "extra": null}}"#, i),
+        })
+        .collect()
+}
+
+fn chunked(chunk_size: usize) -> SchemaInferenceConfig {
+    SchemaInferenceConfig {
+        chunk_size: Some(chunk_size),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_chunked_matches_unchunked() {
+    let rows = rows();
+    let expected = infer_json_schema_from_strings(&rows, SchemaInferenceConfig::default())
+        .unwrap()
+        .schema;
+    for chunk_size in [1, 7, 50, 1000] {
+        let result = infer_json_schema_from_strings(&rows, chunked(chunk_size)).unwrap();
+        assert_eq!(result.schema, expected, "chunk_size {}", chunk_size);
+        assert_eq!(result.processed_count, 50);
+    }
+}
+
+#[test]
+fn test_chunked_splits_ndjson_lines() {
+    let ndjson = vec![rows().join("\n")];
+    let base = SchemaInferenceConfig {
+        delimiter: Some(b'\n'),
+        ..Default::default()
+    };
+    let expected = infer_json_schema_from_strings(&ndjson, base.clone())
+        .unwrap()
+        .schema;
+
+    let config = SchemaInferenceConfig {
+        chunk_size: Some(8),
+        ..base
+    };
+    let result = infer_json_schema_from_strings(&ndjson, config).unwrap();
+    assert_eq!(result.schema, expected);
+    // Lines are the documents in chunked mode
+    assert_eq!(result.processed_count, 50);
+}
+
+#[test]
+fn test_chunked_wrap_root() {
+    let rows = rows();
+    let config = SchemaInferenceConfig {
+        wrap_root: Some("doc".to_string()),
+        ..Default::default()
+    };
+    let expected = infer_json_schema_from_strings(&rows, config.clone())
+        .unwrap()
+        .schema;
+    let config = SchemaInferenceConfig {
+        chunk_size: Some(4),
+        ..config
+    };
+    let result = infer_json_schema_from_strings(&rows, config).unwrap();
+    assert_eq!(result.schema, expected);
+    assert!(result.schema["properties"]["doc"]["properties"]["id"].is_object());
+}
+
+#[test]
+fn test_chunked_reports_invalid_document() {
+    let mut rows = rows();
+    rows[12] = r#"{"id": 12,"#.to_string();
+    let err = infer_json_schema_from_strings(&rows, chunked(5)).unwrap_err();
+    assert!(err.contains("Invalid JSON input at index 13"), "{}", err);
+}
@@ -0,0 +1,86 @@
+use genson_core::{infer_json_schema_from_strings, SchemaInferenceConfig, CONFIG_SNAPSHOT_KEY};
+use serde_json::json;
+
+fn rows() -> Vec<String> {
+    vec![r#"{"id": 1, "labels": {"en": "x"}}"#.to_string()]
+}
+
+#[test]
+fn test_no_snapshot_by_default() {
+    let result = infer_json_schema_from_strings(&rows(), SchemaInferenceConfig::default()).unwrap();
+    assert!(result.schema.get(CONFIG_SNAPSHOT_KEY).is_none());
+}
+
+#[test]
+fn test_snapshot_lists_only_non_default_settings() {
+    let config = SchemaInferenceConfig {
+        embed_config: true,
+        map_threshold: 5,
+        no_unify: ["b", "a", "c"].iter().map(|s| s.to_string()).collect(),
+        force_field_types: [("labels", "map"), ("claims", "record")]
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect(),
+        max_builders: Some(2),
+        ..Default::default()
+    };
+    let result = infer_json_schema_from_strings(&rows(), config).unwrap();
+    let snapshot = &result.schema[CONFIG_SNAPSHOT_KEY];
+
+    assert_eq!(
+        snapshot,
+        &json!({
+            "map_threshold": 5,
+            "no_unify": ["a", "b", "c"],
+            "force_field_types": {"claims": "record", "labels": "map"}
+        })
+    );
+    // Keys are sorted, whatever order the hash map iterates in
+    let keys: Vec<_> = snapshot["force_field_types"]
+        .as_object()
+        .unwrap()
+        .keys()
+        .collect();
+    assert_eq!(keys, ["claims", "labels"]);
+}
+
+#[test]
+fn test_snapshot_of_defaults_is_empty() {
+    let config = SchemaInferenceConfig {
+        embed_config: true,
+        profile: true,
+        ..Default::default()
+    };
+    assert_eq!(config.config_snapshot(), json!({}));
+}
+
+#[cfg(feature = "avro")]
+#[test]
+fn test_snapshot_in_avro_schema() {
+    let config = SchemaInferenceConfig {
+        embed_config: true,
+        avro: true,
+        ..Default::default()
+    };
+    let result = infer_json_schema_from_strings(&rows(), config).unwrap();
+    assert_eq!(result.schema["type"], "record");
+    assert_eq!(result.schema[CONFIG_SNAPSHOT_KEY], json!({"avro": true}));
+}
+
+#[test]
+fn test_snapshot_round_trips_through_from_overrides() {
+    let config = SchemaInferenceConfig {
+        map_threshold: 5,
+        no_unify: ["a"].iter().map(|s| s.to_string()).collect(),
+        ..Default::default()
+    };
+    let restored = SchemaInferenceConfig::from_overrides(&config.config_snapshot()).unwrap();
+    assert_eq!(restored.config_snapshot(), config.config_snapshot());
+    assert_eq!(restored.map_threshold, 5);
+
+    assert_eq!(
+        SchemaInferenceConfig::from_overrides(&json!({"map_treshold": 5})).unwrap_err(),
+        "Unknown config setting: map_treshold"
+    );
+    assert!(SchemaInferenceConfig::from_overrides(&json!({"map_threshold": "5"})).is_err());
+}
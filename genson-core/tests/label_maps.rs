@@ -0,0 +1,127 @@
+use genson_core::{infer_json_schema_from_strings, SchemaInferenceConfig};
+use serde_json::json;
+
+fn wikidata_rows() -> Vec<String> {
+    vec![
+        r#"{"id": "Q1", "labels": {"en": {"language": "en", "value": "universe"}, "fr": {"language": "fr", "value": "univers"}}, "aliases": {"en": [{"language": "en", "value": "cosmos"}]}}"#.to_string(),
+        r#"{"id": "Q2", "labels": {"de": {"language": "de", "value": "Erde"}}, "aliases": {"de": [{"language": "de", "value": "Welt"}, {"language": "de", "value": "Terra"}]}}"#.to_string(),
+    ]
+}
+
+fn label_record() -> serde_json::Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "language": {"type": "string"},
+            "value": {"type": "string"}
+        },
+        "required": ["language", "value"]
+    })
+}
+
+#[test]
+fn test_label_maps_off_by_default() {
+    let result =
+        infer_json_schema_from_strings(&wikidata_rows(), SchemaInferenceConfig::default()).unwrap();
+    // Only 3 language keys, far below the map threshold: stays a record
+    assert!(result.schema["properties"]["labels"]["properties"].is_object());
+}
+
+#[test]
+fn test_label_maps_ignore_threshold() {
+    let config = SchemaInferenceConfig {
+        label_maps: true,
+        ..Default::default()
+    };
+    let result = infer_json_schema_from_strings(&wikidata_rows(), config).unwrap();
+    let props = &result.schema["properties"];
+
+    assert_eq!(
+        props["labels"],
+        json!({"type": "object", "additionalProperties": label_record()})
+    );
+    assert_eq!(
+        props["aliases"],
+        json!({"type": "object", "additionalProperties": {"type": "array", "items": label_record()}})
+    );
+    assert_eq!(props["id"], json!({"type": "string"}));
+}
+
+#[test]
+fn test_simplify_labels() {
+    let config = SchemaInferenceConfig {
+        simplify_labels: true,
+        ..Default::default()
+    };
+    let result = infer_json_schema_from_strings(&wikidata_rows(), config).unwrap();
+    let props = &result.schema["properties"];
+
+    assert_eq!(
+        props["labels"],
+        json!({"type": "object", "additionalProperties": {"type": "string"}})
+    );
+    assert_eq!(
+        props["aliases"],
+        json!({"type": "object", "additionalProperties": {"type": "array", "items": {"type": "string"}}})
+    );
+}
+
+#[test]
+fn test_label_maps_require_exact_shape() {
+    // An extra key means this isn't a label record
+    let rows =
+        vec![r#"{"labels": {"en": {"language": "en", "value": "x", "source": "y"}}}"#.to_string()];
+    let config = SchemaInferenceConfig {
+        label_maps: true,
+        ..Default::default()
+    };
+    let result = infer_json_schema_from_strings(&rows, config).unwrap();
+    assert!(result.schema["properties"]["labels"]["properties"]["en"].is_object());
+}
+
+#[test]
+fn test_forced_record_beats_label_maps() {
+    let config = SchemaInferenceConfig {
+        label_maps: true,
+        force_field_types: [("labels".to_string(), "record".to_string())]
+            .into_iter()
+            .collect(),
+        ..Default::default()
+    };
+    let result = infer_json_schema_from_strings(&wikidata_rows(), config).unwrap();
+    assert!(result.schema["properties"]["labels"]["properties"].is_object());
+    assert!(result.schema["properties"]["aliases"]["additionalProperties"].is_object());
+}
+
+#[cfg(feature = "avro")]
+#[test]
+fn test_simplify_labels_normalise() {
+    use genson_core::normalise::{normalise_values, NormaliseConfig};
+
+    let config = SchemaInferenceConfig {
+        simplify_labels: true,
+        avro: true,
+        ..Default::default()
+    };
+    let rows = wikidata_rows();
+    let result = infer_json_schema_from_strings(&rows, config).unwrap();
+
+    let cfg = NormaliseConfig {
+        simplify_labels: true,
+        ..NormaliseConfig::default()
+    };
+    let values = rows
+        .iter()
+        .map(|r| serde_json::from_str(r).unwrap())
+        .collect();
+    let normalised = normalise_values(values, &result.schema, &cfg);
+
+    assert_eq!(
+        normalised[1],
+        json!({
+            "id": "Q2",
+            "labels": {"de": "Erde"},
+            "aliases": {"de": ["Welt", "Terra"]}
+        })
+    );
+}
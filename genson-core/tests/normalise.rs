@@ -1,6 +1,8 @@
 #![cfg(feature = "avro")]
 
+use genson_core::generate::{generate_rows, GenerateConfig};
 use genson_core::normalise::{normalise_value, normalise_values, MapEncoding, NormaliseConfig};
+use genson_core::{infer_json_schema, SchemaInferenceConfig};
 use serde_json::json;
 
 /// Arrays: empty → null (with flag), empty → [] (without flag).
@@ -165,3 +167,29 @@ fn test_map_scalar_fallback_encodings() {
     let val = normalise_value(json!("foo"), &schema, &cfg, None);
     assert_eq!(val, json!([{"key": "__string", "value": "foo"}]));
 }
+
+/// Rows generated from an inferred Avro schema come back from normalisation unchanged.
+#[test]
+fn test_generated_rows_normalise_unchanged() {
+    let docs = [
+        r#"{"id": 1, "name": "a", "tags": ["x"], "meta": {"score": 1.5}}"#,
+        r#"{"id": 2, "tags": [], "meta": {"score": null}}"#,
+    ];
+    let config = SchemaInferenceConfig {
+        avro: true,
+        ..SchemaInferenceConfig::default()
+    };
+    let schema = infer_json_schema(&docs, Some(config)).unwrap().schema;
+
+    let gen_cfg = GenerateConfig {
+        rows: 200,
+        seed: 42,
+        ..GenerateConfig::default()
+    };
+    let rows = generate_rows(&schema, &gen_cfg).unwrap();
+    let cfg = NormaliseConfig {
+        empty_as_null: false,
+        ..NormaliseConfig::default()
+    };
+    assert_eq!(normalise_values(rows.clone(), &schema, &cfg), rows);
+}
@@ -0,0 +1,132 @@
+//! Property tests for inference and normalisation over arbitrary JSON documents.
+//!
+//! Set `PROPTEST_CASES` to run more cases than the default when hunting crashes.
+#![cfg(feature = "avro")]
+
+use genson_core::avro::avro_to_json_schema;
+use genson_core::conformity::violations;
+use genson_core::normalise::{normalise_values, NormaliseConfig};
+use genson_core::{infer_json_schema, SchemaInferenceConfig};
+use proptest::prelude::*;
+use serde_json::{Map, Value};
+
+/// Any JSON value, nested a few levels, with keys from a small alphabet so that
+/// documents share fields and disagree on their types.
+fn json_value() -> impl Strategy<Value = Value> {
+    let leaf = prop_oneof![
+        Just(Value::Null),
+        any::<bool>().prop_map(Value::from),
+        any::<i32>().prop_map(Value::from),
+        (-1e6f64..1e6).prop_map(Value::from),
+        "[a-z]{0,6}".prop_map(Value::from),
+    ];
+    leaf.prop_recursive(4, 32, 4, |inner| {
+        prop_oneof![
+            prop::collection::vec(inner.clone(), 0..4).prop_map(Value::Array),
+            prop::collection::btree_map("[a-d]{1,2}", inner, 0..4)
+                .prop_map(|m| Value::Object(m.into_iter().collect())),
+        ]
+    })
+}
+
+/// A batch of object documents, serialised.
+fn documents() -> impl Strategy<Value = Vec<String>> {
+    let doc = prop::collection::btree_map("[a-d]{1,2}", json_value(), 0..5)
+        .prop_map(|m| Value::Object(m.into_iter().collect()).to_string());
+    prop::collection::vec(doc, 1..6)
+}
+
+/// Like [`documents`], but each key always holds one type, decided by its last
+/// letter. Normalisation resolves a union of several types by its first branch,
+/// which is lossy by design, so only these documents must come back fitting the
+/// schema exactly. They have no nulls either, and every object has a `b` field:
+/// the Avro converter drops `null` from required fields, and genson's merge ignores
+/// an object with no required fields when deciding which fields are required.
+fn typed_documents() -> impl Strategy<Value = Vec<String>> {
+    let doc = prop::collection::btree_map("[a-f]{1,2}", json_value(), 0..5)
+        .prop_map(|m| Value::Object(typed_object(Value::Object(m.into_iter().collect()))));
+    prop::collection::vec(doc.prop_map(|d| d.to_string()), 1..6)
+}
+
+/// The fields of `value` retyped by key (none if it isn't an object), with an
+/// integer `b` field added if it has none.
+fn typed_object(value: Value) -> Map<String, Value> {
+    let size = value.to_string().len();
+    let mut obj: Map<String, Value> = match value {
+        Value::Object(obj) => obj
+            .into_iter()
+            .map(|(key, child)| {
+                let child = retype(child, &key);
+                (key, child)
+            })
+            .collect(),
+        _ => Map::new(),
+    };
+    obj.entry("b").or_insert_with(|| Value::from(size as i64));
+    obj
+}
+
+fn retype(value: Value, key: &str) -> Value {
+    let size = value.to_string().len();
+    match key.as_bytes().last() {
+        Some(b'a') => Value::Bool(size.is_multiple_of(2)),
+        Some(b'b') => Value::from(size as i64),
+        Some(b'c') => Value::from(size as f64 + 0.5),
+        Some(b'd') => Value::String(value.to_string()),
+        Some(b'e') => Value::Object(typed_object(value)),
+        _ => {
+            let items = match value {
+                Value::Array(items) => items,
+                other => vec![other],
+            };
+            let objects = items.into_iter().map(|v| Value::Object(typed_object(v)));
+            Value::Array(objects.collect())
+        }
+    }
+}
+
+fn avro_config() -> SchemaInferenceConfig {
+    SchemaInferenceConfig {
+        avro: true,
+        map_threshold: 3,
+        ..SchemaInferenceConfig::default()
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn inference_never_panics(docs in prop::collection::vec(json_value(), 1..6)) {
+        let strings: Vec<String> = docs.iter().map(Value::to_string).collect();
+        prop_assert!(infer_json_schema(&strings, None).is_ok());
+        prop_assert!(infer_json_schema(&strings, Some(avro_config())).is_ok());
+    }
+
+    #[test]
+    fn normalised_rows_fit_the_schema(docs in typed_documents()) {
+        let schema = infer_json_schema(&docs, Some(avro_config())).unwrap().schema;
+        let json_schema = avro_to_json_schema(&schema).unwrap();
+        let values: Vec<Value> = docs.iter().map(|d| serde_json::from_str(d).unwrap()).collect();
+        // `empty_as_null` writes null for empty containers whether or not the schema
+        // allows it, by design
+        let cfg = NormaliseConfig {
+            empty_as_null: false,
+            ..NormaliseConfig::default()
+        };
+        for row in normalise_values(values, &schema, &cfg) {
+            let bad = violations(&row, &json_schema);
+            prop_assert!(bad.is_empty(), "{} violates {} at {:?}", row, json_schema, bad);
+        }
+    }
+
+    #[test]
+    fn normalisation_is_idempotent(docs in documents()) {
+        let schema = infer_json_schema(&docs, Some(avro_config())).unwrap().schema;
+        let cfg = NormaliseConfig::default();
+        let values: Vec<Value> = docs.iter().map(|d| serde_json::from_str(d).unwrap()).collect();
+        let once = normalise_values(values, &schema, &cfg);
+        let twice = normalise_values(once.clone(), &schema, &cfg);
+        prop_assert_eq!(once, twice);
+    }
+}
@@ -0,0 +1,55 @@
+use genson_core::{infer_json_schema_from_strings, SampleSpec, SchemaInferenceConfig};
+
+fn rows() -> Vec<String> {
+    (0..20)
+        .map(|i| {
+            if i < 5 {
+                format!(r#"{{"id": {}}}"#, i)
+            } else {
+                format!(r#"{{"id": {}, "late": true}}"#, i)
+            }
+        })
+        .collect()
+}
+
+fn sampled(spec: SampleSpec) -> SchemaInferenceConfig {
+    SchemaInferenceConfig {
+        sample: Some(spec),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_sample_first() {
+    let result = infer_json_schema_from_strings(&rows(), sampled(SampleSpec::First(5))).unwrap();
+    assert_eq!(result.processed_count, 5);
+    assert!(result.schema["properties"].get("late").is_none());
+}
+
+#[test]
+fn test_sample_every_nth() {
+    let result = infer_json_schema_from_strings(&rows(), sampled(SampleSpec::EveryNth(3))).unwrap();
+    assert_eq!(result.processed_count, 7);
+    assert!(result.schema["properties"]["late"].is_object());
+}
+
+#[test]
+fn test_sample_random_is_reproducible() {
+    let spec = SampleSpec::Random { n: 4, seed: 1 };
+    let a = infer_json_schema_from_strings(&rows(), sampled(spec)).unwrap();
+    let b = infer_json_schema_from_strings(&rows(), sampled(spec)).unwrap();
+    assert_eq!(a.processed_count, 4);
+    assert_eq!(a.schema, b.schema);
+}
+
+#[test]
+fn test_sample_ndjson_lines() {
+    let ndjson = vec![rows().join("\n")];
+    let config = SchemaInferenceConfig {
+        delimiter: Some(b'\n'),
+        ..sampled(SampleSpec::First(5))
+    };
+    let result = infer_json_schema_from_strings(&ndjson, config).unwrap();
+    assert_eq!(result.processed_count, 5);
+    assert!(result.schema["properties"].get("late").is_none());
+}
@@ -0,0 +1,52 @@
+#ifndef GENSON_H
+#define GENSON_H
+
+/* Generated by cbindgen from genson-ffi: regenerate with `just ffi-header` */
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Infer a JSON Schema (or an Avro schema, with `{"avro": true}`) from a buffer of
+// JSON: one document, an array of documents, or NDJSON with `{"delimiter": 10}`.
+//
+// `config` is a JSON object of `SchemaInferenceConfig` settings, keyed by their
+// snake_case names. Returns the schema as compact JSON.
+//
+// # Safety
+// `json` must point to `json_len` readable bytes (it may be `NULL` if `json_len`
+// is 0). `config` must be `NULL` or a NUL-terminated string. `error` must be
+// `NULL` or point to writable storage for a pointer.
+char *genson_infer_schema(const uint8_t *json, size_t json_len, const char *config, char **error);
+
+// Normalise NDJSON rows against an Avro schema, so that every row has the same
+// shape. Blank lines are skipped.
+//
+// `config` is a JSON object of `NormaliseConfig` settings, keyed by their
+// snake_case names (e.g. `{"map_encoding": "kv"}`). Returns NDJSON, one
+// normalised row per input row.
+//
+// # Safety
+// `rows` must point to `rows_len` readable bytes (it may be `NULL` if `rows_len`
+// is 0). `schema` must be a NUL-terminated string, and `config` `NULL` or one.
+// `error` must be `NULL` or point to writable storage for a pointer.
+char *genson_normalise(const uint8_t *rows,
+                       size_t rows_len,
+                       const char *schema,
+                       const char *config,
+                       char **error);
+
+// Release a string returned by this library. `NULL` is ignored.
+//
+// # Safety
+// `s` must be `NULL` or a pointer returned by this library that hasn't been freed.
+void genson_string_free(char *s);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* GENSON_H */
@@ -0,0 +1,172 @@
+//! C ABI for schema inference and normalisation, for services that embed
+//! genson-core rather than shelling out to `genson-cli`.
+//!
+//! Documents are passed as UTF-8 buffers (a pointer and a length, not
+//! NUL-terminated); schemas and configs as NUL-terminated JSON strings, where a
+//! `NULL` config means the defaults. Each function returns a NUL-terminated string
+//! owned by the caller, to be released with [`genson_string_free`]. On failure it
+//! returns `NULL` and, if `error` isn't `NULL`, sets `*error` to a message that must
+//! be released the same way.
+//!
+//! The functions are safe to call from several threads at once. The header is
+//! `include/genson.h`, generated by cbindgen (`just ffi-header`).
+
+use genson_core::normalise::{try_normalise_values, NormaliseConfig};
+use genson_core::{infer_json_schema_from_strings, SchemaInferenceConfig};
+use serde_json::Value;
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::ptr;
+
+/// Infer a JSON Schema (or an Avro schema, with `{"avro": true}`) from a buffer of
+/// JSON: one document, an array of documents, or NDJSON with `{"delimiter": 10}`.
+///
+/// `config` is a JSON object of `SchemaInferenceConfig` settings, keyed by their
+/// snake_case names. Returns the schema as compact JSON.
+///
+/// # Safety
+/// `json` must point to `json_len` readable bytes (it may be `NULL` if `json_len`
+/// is 0). `config` must be `NULL` or a NUL-terminated string. `error` must be
+/// `NULL` or point to writable storage for a pointer.
+#[no_mangle]
+pub unsafe extern "C" fn genson_infer_schema(
+    json: *const u8,
+    json_len: usize,
+    config: *const c_char,
+    error: *mut *mut c_char,
+) -> *mut c_char {
+    let result = guard(|| {
+        let json = unsafe { buffer(json, json_len) }?;
+        let overrides = unsafe { json_arg(config, "config") }?.unwrap_or(Value::Null);
+        let config = match overrides {
+            Value::Null => SchemaInferenceConfig::default(),
+            overrides => SchemaInferenceConfig::from_overrides(&overrides)?,
+        };
+        let result = infer_json_schema_from_strings(&[json], config)
+            .map_err(|e| format!("Schema inference failed: {}", e))?;
+        Ok(result.schema.to_string())
+    });
+    unsafe { respond(result, error) }
+}
+
+/// Normalise NDJSON rows against an Avro schema, so that every row has the same
+/// shape. Blank lines are skipped.
+///
+/// `config` is a JSON object of `NormaliseConfig` settings, keyed by their
+/// snake_case names (e.g. `{"map_encoding": "kv"}`). Returns NDJSON, one
+/// normalised row per input row.
+///
+/// # Safety
+/// `rows` must point to `rows_len` readable bytes (it may be `NULL` if `rows_len`
+/// is 0). `schema` must be a NUL-terminated string, and `config` `NULL` or one.
+/// `error` must be `NULL` or point to writable storage for a pointer.
+#[no_mangle]
+pub unsafe extern "C" fn genson_normalise(
+    rows: *const u8,
+    rows_len: usize,
+    schema: *const c_char,
+    config: *const c_char,
+    error: *mut *mut c_char,
+) -> *mut c_char {
+    let result = guard(|| {
+        let rows = unsafe { buffer(rows, rows_len) }?;
+        let schema = unsafe { json_arg(schema, "schema") }?.ok_or("Missing schema")?;
+        let cfg = match unsafe { json_arg(config, "config") }? {
+            None | Some(Value::Null) => NormaliseConfig::default(),
+            Some(overrides) => NormaliseConfig::from_overrides(&overrides)?,
+        };
+        let values = rows
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .enumerate()
+            .map(|(i, line)| {
+                serde_json::from_str(line)
+                    .map_err(|e| format!("Row {}: invalid JSON: {}", i + 1, e))
+            })
+            .collect::<Result<Vec<Value>, String>>()?;
+        let normalised = try_normalise_values(values, &schema, &cfg)?;
+        let mut out = String::new();
+        for row in normalised {
+            out.push_str(&row.to_string());
+            out.push('\n');
+        }
+        Ok(out)
+    });
+    unsafe { respond(result, error) }
+}
+
+/// Release a string returned by this library. `NULL` is ignored.
+///
+/// # Safety
+/// `s` must be `NULL` or a pointer returned by this library that hasn't been freed.
+#[no_mangle]
+pub unsafe extern "C" fn genson_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        drop(unsafe { CString::from_raw(s) });
+    }
+}
+
+/// Run `f`, turning a panic into an error rather than unwinding into the caller,
+/// which is undefined behaviour across `extern "C"`.
+fn guard(f: impl FnOnce() -> Result<String, String>) -> Result<String, String> {
+    catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|panic| {
+        let msg = panic
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| panic.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic".to_string());
+        Err(format!("genson panicked: {}", msg))
+    })
+}
+
+/// The UTF-8 text in a pointer and length.
+unsafe fn buffer<'a>(data: *const u8, len: usize) -> Result<&'a str, String> {
+    if len == 0 {
+        return Ok("");
+    }
+    if data.is_null() {
+        return Err("Null buffer with a non-zero length".to_string());
+    }
+    let bytes = unsafe { std::slice::from_raw_parts(data, len) };
+    std::str::from_utf8(bytes).map_err(|e| format!("Buffer isn't UTF-8: {}", e))
+}
+
+/// The JSON in a NUL-terminated string, or `None` for `NULL`.
+unsafe fn json_arg(s: *const c_char, name: &str) -> Result<Option<Value>, String> {
+    if s.is_null() {
+        return Ok(None);
+    }
+    let s = unsafe { CStr::from_ptr(s) }
+        .to_str()
+        .map_err(|e| format!("The {} isn't UTF-8: {}", name, e))?;
+    serde_json::from_str(s)
+        .map(Some)
+        .map_err(|e| format!("The {} isn't valid JSON: {}", name, e))
+}
+
+/// Hand `result` to the caller: the string on success, else `NULL` with the
+/// message in `*error`.
+unsafe fn respond(result: Result<String, String>, error: *mut *mut c_char) -> *mut c_char {
+    let (ok, err) = match result {
+        Ok(s) => (to_c_string(s), ptr::null_mut()),
+        Err(e) => (ptr::null_mut(), to_c_string(e)),
+    };
+    if !error.is_null() {
+        unsafe { *error = err };
+    } else if !err.is_null() {
+        unsafe { genson_string_free(err) };
+    }
+    ok
+}
+
+/// An owned C string; JSON has no raw NULs, but a message might.
+fn to_c_string(s: String) -> *mut c_char {
+    CString::new(s)
+        .unwrap_or_else(|e| {
+            let end = e.nul_position();
+            let mut bytes = e.into_vec();
+            bytes.truncate(end);
+            CString::new(bytes).expect("truncated at the first NUL")
+        })
+        .into_raw()
+}
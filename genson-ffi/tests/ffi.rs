@@ -0,0 +1,128 @@
+//! The C functions, called as a C caller would.
+use genson_ffi::{genson_infer_schema, genson_normalise, genson_string_free};
+use serde_json::{json, Value};
+use std::ffi::{c_char, CStr, CString};
+use std::ptr;
+
+/// Take ownership of a returned string (or error) as a Rust string.
+fn take(s: *mut c_char) -> Option<String> {
+    if s.is_null() {
+        return None;
+    }
+    let owned = unsafe { CStr::from_ptr(s) }.to_str().unwrap().to_string();
+    unsafe { genson_string_free(s) };
+    Some(owned)
+}
+
+fn infer(json: &str, config: Option<&str>) -> Result<Value, String> {
+    let config = config.map(|c| CString::new(c).unwrap());
+    let config_ptr = config.as_ref().map_or(ptr::null(), |c| c.as_ptr());
+    let mut error = ptr::null_mut();
+    let out = unsafe { genson_infer_schema(json.as_ptr(), json.len(), config_ptr, &mut error) };
+    match take(out) {
+        Some(schema) => {
+            assert!(error.is_null());
+            Ok(serde_json::from_str(&schema).unwrap())
+        }
+        None => Err(take(error).unwrap()),
+    }
+}
+
+fn normalise(rows: &str, schema: &Value, config: Option<&str>) -> Result<String, String> {
+    let schema = CString::new(schema.to_string()).unwrap();
+    let config = config.map(|c| CString::new(c).unwrap());
+    let config_ptr = config.as_ref().map_or(ptr::null(), |c| c.as_ptr());
+    let mut error = ptr::null_mut();
+    let out = unsafe {
+        genson_normalise(
+            rows.as_ptr(),
+            rows.len(),
+            schema.as_ptr(),
+            config_ptr,
+            &mut error,
+        )
+    };
+    take(out).ok_or_else(|| take(error).unwrap())
+}
+
+#[test]
+fn test_infer_schema() {
+    let schema = infer(r#"{"a": 1, "b": "x"}"#, None).unwrap();
+    assert_eq!(schema["properties"]["a"]["type"], "integer");
+
+    let ndjson = "{\"a\": 1}\n{\"b\": true}\n";
+    let schema = infer(ndjson, Some(r#"{"delimiter": 10, "avro": true}"#)).unwrap();
+    assert_eq!(schema["type"], "record");
+    assert_eq!(schema["fields"].as_array().unwrap().len(), 2);
+}
+
+#[test]
+fn test_infer_schema_errors() {
+    assert_eq!(
+        infer("{}", Some(r#"{"map_treshold": 5}"#)).unwrap_err(),
+        "Unknown config setting: map_treshold"
+    );
+    assert!(infer("{}", Some("not json"))
+        .unwrap_err()
+        .starts_with("The config isn't valid JSON"));
+    assert!(infer("{\"a\":", None)
+        .unwrap_err()
+        .starts_with("Schema inference failed"));
+
+    // The error out-pointer is optional
+    let out = unsafe { genson_infer_schema(b"{".as_ptr(), 1, ptr::null(), ptr::null_mut()) };
+    assert!(out.is_null());
+    let mut error = ptr::null_mut();
+    let out = unsafe { genson_infer_schema(ptr::null(), 3, ptr::null(), &mut error) };
+    assert!(out.is_null());
+    assert_eq!(take(error).unwrap(), "Null buffer with a non-zero length");
+}
+
+#[test]
+fn test_normalise() {
+    let schema = json!({
+        "type": "record",
+        "name": "document",
+        "fields": [
+            {"name": "id", "type": "string"},
+            {"name": "labels", "type": {"type": "map", "values": "string"}}
+        ]
+    });
+    let rows = "{\"id\": 1}\n\n{\"id\": \"2\", \"labels\": {\"en\": \"x\"}}\n";
+    assert_eq!(
+        normalise(rows, &schema, None).unwrap(),
+        "{\"id\":\"1\",\"labels\":null}\n{\"id\":\"2\",\"labels\":{\"en\":\"x\"}}\n"
+    );
+    assert_eq!(
+        normalise(rows, &schema, Some(r#"{"map_encoding": "kv"}"#)).unwrap(),
+        "{\"id\":\"1\",\"labels\":null}\n\
+         {\"id\":\"2\",\"labels\":[{\"key\":\"en\",\"value\":\"x\"}]}\n"
+    );
+    assert_eq!(normalise("", &schema, None).unwrap(), "");
+}
+
+#[test]
+fn test_normalise_errors() {
+    let schema = json!({"type": "record", "name": "r", "fields": [{"name": "a", "type": "long"}]});
+    assert!(normalise("{\"a\": 1}\n{", &schema, None)
+        .unwrap_err()
+        .starts_with("Row 2: invalid JSON"));
+    assert_eq!(
+        normalise(
+            "{\"b\": 1}",
+            &schema,
+            Some(r#"{"unknown_fields": "error"}"#)
+        )
+        .unwrap_err(),
+        "Unknown field 'b' is not in the schema"
+    );
+    assert_eq!(
+        normalise("{}", &schema, Some(r#"{"threds": 2}"#)).unwrap_err(),
+        "Unknown normalise setting: threds"
+    );
+
+    let mut error = ptr::null_mut();
+    let out = unsafe { genson_normalise(b"{}".as_ptr(), 2, ptr::null(), ptr::null(), &mut error) };
+    assert!(out.is_null());
+    assert_eq!(take(error).unwrap(), "Missing schema");
+}
@@ -0,0 +1,28 @@
+//! The checked-in C header matches what cbindgen generates from the crate.
+//!
+//! Run with `GENSON_BLESS_HEADER=1` (`just ffi-header`) to rewrite it.
+use std::path::Path;
+
+#[test]
+fn test_header_is_up_to_date() {
+    let crate_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let config = cbindgen::Config::from_file(crate_dir.join("cbindgen.toml")).unwrap();
+    let mut generated = Vec::new();
+    cbindgen::Builder::new()
+        .with_config(config)
+        .with_src(crate_dir.join("src/lib.rs"))
+        .generate()
+        .unwrap()
+        .write(&mut generated);
+    let generated = String::from_utf8(generated).unwrap();
+
+    let header = crate_dir.join("include/genson.h");
+    if std::env::var_os("GENSON_BLESS_HEADER").is_some() {
+        std::fs::write(&header, &generated).unwrap();
+    }
+    let checked_in = std::fs::read_to_string(&header).unwrap_or_default();
+    assert!(
+        checked_in == generated,
+        "include/genson.h is stale: regenerate it with `just ffi-header`"
+    );
+}
@@ -9,14 +9,26 @@ from typing import Literal
 import orjson
 import polars as pl
 from polars.api import register_dataframe_namespace
+from polars.io.plugins import register_io_source
 from polars.plugins import register_plugin_function
 
-from ._polars_genson import avro_to_polars_fields as _rust_avro_to_polars_fields
+from ._polars_genson import clear_schema_cache as _rust_clear_schema_cache
+from ._polars_genson import effective_config as _rust_effective_config
+from ._polars_genson import format_schema as _rust_format_schema
 from ._polars_genson import infer_from_parquet as _rust_infer_from_parquet
+from ._polars_genson import infer_ndjson_schema as _rust_infer_ndjson_schema
+from ._polars_genson import json_schema_to_polars as _rust_json_schema_to_polars
 from ._polars_genson import json_to_schema as _rust_json_to_schema
 from ._polars_genson import normalise_from_parquet as _rust_normalise_from_parquet
+from ._polars_genson import SchemaBuilder as _RustSchemaBuilder
+from ._polars_genson import (
+    normalise_json_with_schema as _rust_normalise_json_with_schema,
+)
+from ._polars_genson import normalise_lines as _rust_normalise_lines
 from ._polars_genson import read_parquet_metadata as _rust_read_parquet_metadata
 from ._polars_genson import schema_to_json as _rust_schema_to_json
+from ._polars_genson import unnest_fields as _rust_unnest_fields
+from ._polars_genson import write_normalised as _rust_write_normalised
 from .dtypes import _parse_polars_dtype
 from .utils import parse_into_expr, parse_version  # noqa: F401
 
@@ -31,11 +43,17 @@ else:
 __all__ = [
     "infer_json_schema",
     "json_to_schema",
+    "json_schema_to_polars_schema",
     "schema_to_json",
     "infer_from_parquet",
     "normalise_from_parquet",
     "read_parquet_metadata",
     "schema_to_dict",
+    "effective_config",
+    "unnest_json",
+    "clear_schema_cache",
+    "SchemaBuilder",
+    "infer_json_schema_lazy",
 ]
 
 
@@ -99,11 +117,39 @@ def json_to_schema(json_str: str, *, debug: bool = False) -> pl.Schema:
     return schema
 
 
+def _sample_spec(
+    sample: int | tuple[int, int] | None, sample_every: int | None
+) -> dict | None:
+    """Convert the sampling options to the genson-core ``SampleSpec`` form."""
+    if sample is not None and sample_every is not None:
+        raise ValueError("sample and sample_every cannot be combined")
+    if sample_every is not None:
+        return {"every_nth": sample_every}
+    if isinstance(sample, tuple):
+        n, seed = sample
+        return {"random": {"n": n, "seed": seed}}
+    if sample is not None:
+        return {"first": sample}
+    return None
+
+
+def _wrap_root_for(
+    column: str, wrap_root: bool | str | dict[str, str] | None
+) -> str | None:
+    """The root to wrap ``column`` under: ``True`` means the column name."""
+    if wrap_root is True:
+        return column
+    if isinstance(wrap_root, dict):
+        return wrap_root.get(column)
+    return wrap_root or None
+
+
 def plug(expr: pl.Expr, changes_length: bool, **kwargs) -> pl.Expr:
     """Wrap Polars' `register_plugin_function` helper to always pass the same `lib`.
 
-    Pass `changes_length` when using the `merge_schemas` (per-row) inference, as we only
-    build a single schema in that case (so it'd be a waste to make more than one row).
+    Pass `changes_length` when using `merge_schemas` inference, as we only build a
+    single schema in that case (so it'd be a waste to make more than one row). Without
+    it there is one schema per input row.
     """
     func_name = inspect.stack()[1].function
     return register_plugin_function(
@@ -122,6 +168,9 @@ def infer_json_schema(
     ignore_outer_array: bool = True,
     ndjson: bool = False,
     schema_uri: str | None = "http://json-schema.org/schema#",
+    json_schema_draft: Literal["draft-07", "2019-09", "2020-12"] | None = None,
+    sort_fields: Literal["preserve", "alphabetical", "by_frequency"] = "preserve",
+    null_fallback: Literal["string", "integer", "number", "boolean"] | None = None,
     merge_schemas: bool = True,
     debug: bool = False,
     profile: bool = False,
@@ -129,15 +178,31 @@ def infer_json_schema(
     map_threshold: int = 20,
     map_max_required_keys: int | None = None,
     unify_maps: bool = False,
-    no_unify: set[str] | None = None,
+    detect_tuples: bool = False,
+    required_threshold: float | None = None,
+    coerce_map_array: bool = False,
+    no_unify: list[str] | set[str] | None = None,
     force_field_types: dict[str, str] | None = None,
     force_parent_field_types: dict[str, str] | None = None,
-    force_scalar_promotion: set[str] | None = None,
+    force_scalar_promotion: list[str] | set[str] | None = None,
+    ignore_paths: list[str] | None = None,
+    max_depth: int | None = None,
+    max_object_keys: int | None = None,
     wrap_scalars: bool = True,
     avro: bool = False,
-    wrap_root: str | None = None,
+    wrap_root: str | dict[str, str] | None = None,
     no_root_map: bool = True,
     max_builders: int | None = None,
+    chunk_size: int | None = None,
+    error_context_chars: int = 100,
+    error_dump_dir: str | None = None,
+    sample: int | tuple[int, int] | None = None,
+    sample_every: int | None = None,
+    cache: bool = False,
+    cache_dir: str | None = None,
+    output_name: str | None = None,
+    profile_json: bool = False,
+    embed_config: bool = False,
 ) -> pl.Expr:
     """Infer JSON schema from a string column containing JSON data.
 
@@ -151,8 +216,20 @@ def infer_json_schema(
         Whether to treat input as newline-delimited JSON
     schema_uri : str or None, default "http://json-schema.org/schema#"
         Schema URI to use for the generated schema
+    json_schema_draft : {"draft-07", "2019-09", "2020-12"}, optional
+        JSON Schema draft to write the schema for: sets ``$schema`` to the draft's
+        URI and uses its keywords (``definitions`` or ``$defs``, ``prefixItems``).
+    sort_fields : {"preserve", "alphabetical", "by_frequency"}, default "preserve"
+        Order of the properties (and Avro fields) at every level: as first seen in
+        the input, by name, or most often seen first (ties by name).
+    null_fallback : {"string", "integer", "number", "boolean"}, optional
+        Type given to fields that were null in every row, which otherwise get the
+        ``null`` type that Avro consumers can't use. They become nullable fields of
+        that type.
     merge_schemas : bool, default True
-        Whether to merge schemas from all rows (True) or return individual schemas (False)
+        Whether to merge schemas from all rows into one (True), or output each row's
+        own schema in a column aligned with the input rows (False), with null for
+        null or blank rows, so schema variants can be grouped and compared.
     debug : bool, default False
         Whether to print debug information
     profile : bool, default False
@@ -170,19 +247,46 @@ def infer_json_schema(
         Enable unification of compatible but non-homogeneous record schemas into maps.
         When True, record schemas with compatible field types can be merged into a single
         map schema with selective nullable fields.
-    no_unify: set[str] | None, default None
-        Prevent unification of keys under these field names with their sibling record fields.
+    detect_tuples : bool, default False
+        Keep a schema per position for arrays that had the same length in every
+        row and different values at each position (e.g. ``[string, object]``
+        pairs), instead of one union for all the items. Avro schemas get a
+        record with fields ``_0``, ``_1``, ...
+    required_threshold : float, optional
+        Fraction of the objects at a path (0 to 1) that must have a field for it
+        to be required, rather than all of them, so a few malformed rows don't
+        make the field optional.
+    coerce_map_array : bool, default False
+        Collapse a field seen both as a map of arrays (``{"en": ["a"]}``)
+        and as an array of maps (``[{"en": "a"}]``) to the map-of-arrays
+        form, and fold the array form into it when normalising.
+    no_unify : list[str] | set[str], optional
+        Prevent unification of keys under these fields with their sibling record fields,
+        e.g. volatile fields like ``references``. Accepts names or paths like
+        ``force_field_types``. Example: ``["references", "/claims/*/qualifiers"]``.
     force_field_types : dict[str, str], optional
-        Explicit overrides for specific fields. Values must be `"map"` or `"record"`.
-        Example: ``{"labels": "map", "claims": "record"}``.
+        Explicit overrides for specific fields. Values must be `"map"` or `"record"`,
+        or a scalar type (`"string"`, `"integer"`, `"number"`, `"boolean"`, `"date"`,
+        `"date-time"`). Example: ``{"labels": "map", "price": "number"}``.
     force_parent_field_types : dict[str, str], optional
         Explicit overrides for fields based on their parent field name. Values must be `"map"` or `"record"`.
         Example: ``{"labels": "map", "claims": "record"}``.
-    force_scalar_promotion : set[str], optional
-        Set of field names that should always be promoted to wrapped scalars,
+    force_scalar_promotion : list[str] | set[str], optional
+        Fields that should always be promoted to wrapped scalars (names, or paths
+        like ``force_field_types``),
         even when they appear as simple scalars. Ensures schema stability for
         fields known to have heterogeneous types across chunks.
         Example: ``{"precision", "datavalue"}``.
+    ignore_paths : list[str], optional
+        Fields whose subtrees are treated as opaque strings during inference,
+        e.g. freeform metadata blobs. Accepts names or paths like ``force_field_types``.
+        Example: ``["metadata", "/items/extra"]``.
+    max_depth : int, optional
+        Treat objects and arrays nested deeper than this (the root is depth 1)
+        as opaque strings during inference.
+    max_object_keys : int, optional
+        Treat objects with more than this many keys (below the root) as opaque
+        strings during inference.
     wrap_scalars : bool, default True
         Whether to promote scalar values into singleton objects when they appear
         in contexts where other rows provide objects. This avoids unification
@@ -191,8 +295,10 @@ def infer_json_schema(
         ``"value"`` becomes ``{"value__string": "..."}``.
     avro: bool, default False
         Whether to output an Avro schema instead of JSON schema.
-    wrap_root : str | None, default None
-        If a string, wrap each JSON row under that key before inference.
+    wrap_root : str | dict[str, str] | None, default None
+        If a string, wrap each JSON row under that key before inference, or under
+        nested keys for a dotted path like ``"entity.claims"``. A dict maps input
+        column names to their own root, for expressions over several columns.
         If ``None``, leave rows unchanged.
     no_root_map : bool, default True
         Prevent document root from becoming a map type, even if it meets map inference criteria
@@ -200,6 +306,38 @@ def infer_json_schema(
         Maximum number of schema builders to create in parallel at once.
         Lower values reduce peak memory usage during schema inference.
         If None, processes all strings at once. Default is None.
+    chunk_size : int, optional
+        Infer in batches of this many rows (NDJSON lines), merging each batch
+        before the next so peak memory stays bounded however large the input.
+    error_context_chars : int, default 100
+        Characters of an invalid row to quote in its parse error, around
+        where parsing failed.
+    error_dump_dir : str, optional
+        Directory to write invalid rows to in full, with each file's path
+        given in the parse error, for rows too long to quote.
+    sample : int | tuple[int, int], optional
+        Infer from a sample of the rows (NDJSON lines): an int ``n`` takes the
+        first ``n``, a tuple ``(n, seed)`` takes ``n`` chosen at random with ``seed``.
+    sample_every : int, optional
+        Infer from every ``k``th row only. Cannot be combined with ``sample``.
+    cache : bool, default False
+        Look the inferred schema up by a hash of the column's rows and these
+        settings, and keep it for later calls on the same data, which then skip
+        inference. Needs the ``schema-cache`` build feature. Clear it with
+        :func:`clear_schema_cache`.
+    cache_dir : str, optional
+        Directory to also keep cached schemas in, so they outlast the session.
+        Implies ``cache``.
+    output_name : str, optional
+        Name of the output column. Defaults to ``"schema"``. Use
+        :func:`effective_config` to inspect the settings behind the output.
+    profile_json : bool, default False
+        With ``merge_schemas=True``, output ``{"schema": ..., "profile": ...}``
+        where ``profile`` holds the stage timings in milliseconds (parse, build,
+        rewrite_objects, avro, total, per-document p50/p99).
+    embed_config : bool, default False
+        Record the settings that differ from the library defaults in the schema,
+        under an ``"x-genson-config"`` key, so it can be reproduced.
 
     Returns:
     -------
@@ -216,15 +354,33 @@ def infer_json_schema(
         "map_threshold": map_threshold,
         "map_max_required_keys": map_max_required_keys,
         "unify_maps": unify_maps,
+        "detect_tuples": detect_tuples,
+        "required_threshold": required_threshold,
+        "coerce_map_array": coerce_map_array,
         "no_unify": list(no_unify) if no_unify else [],
         "force_scalar_promotion": (
             list(force_scalar_promotion) if force_scalar_promotion else []
         ),
+        "ignore_paths": list(ignore_paths) if ignore_paths else [],
+        "max_depth": max_depth,
+        "max_object_keys": max_object_keys,
         "wrap_scalars": wrap_scalars,
+        "json_schema_draft": json_schema_draft,
+        "sort_fields": sort_fields,
+        "null_fallback": null_fallback,
         "avro": avro,
         "wrap_root": wrap_root,
         "no_root_map": no_root_map,
         "max_builders": max_builders,
+        "chunk_size": chunk_size,
+        "error_context_chars": error_context_chars,
+        "error_dump_dir": error_dump_dir,
+        "sample": _sample_spec(sample, sample_every),
+        "cache": cache,
+        "cache_dir": cache_dir,
+        "output_name": output_name,
+        "profile_json": profile_json,
+        "embed_config": embed_config,
     }
     if schema_uri is not None:
         kwargs["schema_uri"] = schema_uri
@@ -248,15 +404,29 @@ def infer_polars_schema(
     map_threshold: int = 20,
     map_max_required_keys: int | None = None,
     unify_maps: bool = False,
-    no_unify: set[str] | None = None,
+    detect_tuples: bool = False,
+    required_threshold: float | None = None,
+    coerce_map_array: bool = False,
+    no_unify: list[str] | set[str] | None = None,
     force_field_types: dict[str, str] | None = None,
     force_parent_field_types: dict[str, str] | None = None,
-    force_scalar_promotion: set[str] | None = None,
+    force_scalar_promotion: list[str] | set[str] | None = None,
+    ignore_paths: list[str] | None = None,
+    max_depth: int | None = None,
+    max_object_keys: int | None = None,
     wrap_scalars: bool = True,
     avro: bool = False,
-    wrap_root: str | None = None,
+    wrap_root: str | dict[str, str] | None = None,
     no_root_map: bool = True,
     max_builders: int | None = None,
+    chunk_size: int | None = None,
+    error_context_chars: int = 100,
+    error_dump_dir: str | None = None,
+    sample: int | tuple[int, int] | None = None,
+    sample_every: int | None = None,
+    cache: bool = False,
+    cache_dir: str | None = None,
+    output_name: str | None = None,
 ) -> pl.Expr:
     """Infer Polars schema from a string column containing JSON data.
 
@@ -287,19 +457,46 @@ def infer_polars_schema(
         Enable unification of compatible but non-homogeneous record schemas into maps.
         When True, record schemas with compatible field types can be merged into a single
         map schema with selective nullable fields.
-    no_unify: set[str] | None, default None
-        Prevent unification of keys under these field names with their sibling record fields.
+    detect_tuples : bool, default False
+        Keep a schema per position for arrays that had the same length in every
+        row and different values at each position (e.g. ``[string, object]``
+        pairs), instead of one union for all the items. Avro schemas get a
+        record with fields ``_0``, ``_1``, ...
+    required_threshold : float, optional
+        Fraction of the objects at a path (0 to 1) that must have a field for it
+        to be required, rather than all of them, so a few malformed rows don't
+        make the field optional.
+    coerce_map_array : bool, default False
+        Collapse a field seen both as a map of arrays (``{"en": ["a"]}``)
+        and as an array of maps (``[{"en": "a"}]``) to the map-of-arrays
+        form, and fold the array form into it when normalising.
+    no_unify : list[str] | set[str], optional
+        Prevent unification of keys under these fields with their sibling record fields,
+        e.g. volatile fields like ``references``. Accepts names or paths like
+        ``force_field_types``. Example: ``["references", "/claims/*/qualifiers"]``.
     force_field_types : dict[str, str], optional
-        Explicit overrides for specific fields. Values must be `"map"` or `"record"`.
-        Example: ``{"labels": "map", "claims": "record"}``.
+        Explicit overrides for specific fields. Values must be `"map"` or `"record"`,
+        or a scalar type (`"string"`, `"integer"`, `"number"`, `"boolean"`, `"date"`,
+        `"date-time"`). Example: ``{"labels": "map", "price": "number"}``.
     force_parent_field_types : dict[str, str], optional
         Explicit overrides for fields based on their parent field name. Values must be `"map"` or `"record"`.
         Example: ``{"labels": "map", "claims": "record"}``.
-    force_scalar_promotion : set[str], optional
-        Set of field names that should always be promoted to wrapped scalars,
+    force_scalar_promotion : list[str] | set[str], optional
+        Fields that should always be promoted to wrapped scalars (names, or paths
+        like ``force_field_types``),
         even when they appear as simple scalars. Ensures schema stability for
         fields known to have heterogeneous types across chunks.
         Example: ``{"precision", "datavalue"}``.
+    ignore_paths : list[str], optional
+        Fields whose subtrees are treated as opaque strings during inference,
+        e.g. freeform metadata blobs. Accepts names or paths like ``force_field_types``.
+        Example: ``["metadata", "/items/extra"]``.
+    max_depth : int, optional
+        Treat objects and arrays nested deeper than this (the root is depth 1)
+        as opaque strings during inference.
+    max_object_keys : int, optional
+        Treat objects with more than this many keys (below the root) as opaque
+        strings during inference.
     wrap_scalars : bool, default True
         Whether to promote scalar values into singleton objects when they appear
         in contexts where other rows provide objects. This avoids unification
@@ -308,8 +505,10 @@ def infer_polars_schema(
         ``"value"`` becomes ``{"value__string": "..."}``.
     avro: bool, default False
         Whether to read the input as an Avro schema instead of JSON schema.
-    wrap_root : str | None, default None
-        If a string, wrap each JSON row under that key before inference.
+    wrap_root : str | dict[str, str] | None, default None
+        If a string, wrap each JSON row under that key before inference, or under
+        nested keys for a dotted path like ``"entity.claims"``. A dict maps input
+        column names to their own root, for expressions over several columns.
         If ``None``, leave rows unchanged.
     no_root_map : bool, default True
         Prevent document root from becoming a map type, even if it meets map inference criteria
@@ -317,6 +516,31 @@ def infer_polars_schema(
         Maximum number of schema builders to create in parallel at once.
         Lower values reduce peak memory usage during schema inference.
         If None, processes all strings at once. Default is None.
+    chunk_size : int, optional
+        Infer in batches of this many rows (NDJSON lines), merging each batch
+        before the next so peak memory stays bounded however large the input.
+    error_context_chars : int, default 100
+        Characters of an invalid row to quote in its parse error, around
+        where parsing failed.
+    error_dump_dir : str, optional
+        Directory to write invalid rows to in full, with each file's path
+        given in the parse error, for rows too long to quote.
+    sample : int | tuple[int, int], optional
+        Infer from a sample of the rows (NDJSON lines): an int ``n`` takes the
+        first ``n``, a tuple ``(n, seed)`` takes ``n`` chosen at random with ``seed``.
+    sample_every : int, optional
+        Infer from every ``k``th row only. Cannot be combined with ``sample``.
+    cache : bool, default False
+        Look the inferred schema up by a hash of the column's rows and these
+        settings, and keep it for later calls on the same data, which then skip
+        inference. Needs the ``schema-cache`` build feature. Clear it with
+        :func:`clear_schema_cache`.
+    cache_dir : str, optional
+        Directory to also keep cached schemas in, so they outlast the session.
+        Implies ``cache``.
+    output_name : str, optional
+        Name of the output column. Defaults to ``"schema"``. Use
+        :func:`effective_config` to inspect the settings behind the output.
 
     Returns:
     -------
@@ -333,15 +557,28 @@ def infer_polars_schema(
         "map_threshold": map_threshold,
         "map_max_required_keys": map_max_required_keys,
         "unify_maps": unify_maps,
+        "detect_tuples": detect_tuples,
+        "required_threshold": required_threshold,
+        "coerce_map_array": coerce_map_array,
         "no_unify": list(no_unify) if no_unify else [],
         "force_scalar_promotion": (
             list(force_scalar_promotion) if force_scalar_promotion else []
         ),
+        "ignore_paths": list(ignore_paths) if ignore_paths else [],
+        "max_depth": max_depth,
+        "max_object_keys": max_object_keys,
         "wrap_scalars": wrap_scalars,
         "avro": avro,
         "wrap_root": wrap_root,
         "no_root_map": no_root_map,
         "max_builders": max_builders,
+        "chunk_size": chunk_size,
+        "error_context_chars": error_context_chars,
+        "error_dump_dir": error_dump_dir,
+        "sample": _sample_spec(sample, sample_every),
+        "cache": cache,
+        "cache_dir": cache_dir,
+        "output_name": output_name,
     }
     if not merge_schemas:
         url = "https://github.com/lmmx/polars-genson/issues/37"
@@ -362,18 +599,34 @@ def normalise_json(
     empty_as_null: bool = True,
     coerce_strings: bool = False,
     map_encoding: Literal["entries", "mapping", "kv"] = "kv",
+    unknown_fields: Literal["drop", "keep", "error"] = "drop",
+    value_limits: dict | None = None,
     profile: bool = False,
     map_threshold: int = 20,
     map_max_required_keys: int | None = None,
     unify_maps: bool = False,
-    no_unify: set[str] | None = None,
+    detect_tuples: bool = False,
+    required_threshold: float | None = None,
+    coerce_map_array: bool = False,
+    no_unify: list[str] | set[str] | None = None,
     force_field_types: dict[str, str] | None = None,
     force_parent_field_types: dict[str, str] | None = None,
-    force_scalar_promotion: set[str] | None = None,
+    force_scalar_promotion: list[str] | set[str] | None = None,
+    ignore_paths: list[str] | None = None,
+    max_depth: int | None = None,
+    max_object_keys: int | None = None,
     wrap_scalars: bool = True,
-    wrap_root: str | None = None,
+    wrap_root: str | dict[str, str] | None = None,
     no_root_map: bool = True,
     max_builders: int | None = None,
+    chunk_size: int | None = None,
+    error_context_chars: int = 100,
+    error_dump_dir: str | None = None,
+    sample: int | tuple[int, int] | None = None,
+    sample_every: int | None = None,
+    cache: bool = False,
+    cache_dir: str | None = None,
+    output_name: str | None = None,
 ) -> pl.Expr:
     """Normalise a JSON string column against an inferred Avro schema.
 
@@ -400,6 +653,16 @@ def normalise_json(
         - "mapping": plain JSON object ({"en":"Hello"})
         - "entries": list of single-entry objects ([{"en":"Hello"}])
         - "kv":      list of {key,value} dicts ([{"key":"en","value":"Hello"}])
+    unknown_fields : {"drop", "keep", "error"}, default "drop"
+        What to do with input fields the schema doesn't declare, which can happen
+        when inferring from a sample: leave them out, pass them through unchanged,
+        or raise an error naming the first one.
+    value_limits : dict, optional
+        Caps on the normalised values, so adversarial rows can't produce values too
+        large for downstream writers: ``max_string_len`` cuts longer strings and ends
+        them with ``marker`` (default ``"...[truncated]"``), ``max_array_items`` keeps
+        the first items of arrays, and ``max_depth`` replaces values nested deeper
+        (the row is depth 1) with null.
     profile : bool, default False
         Whether to show timing profile output
     map_threshold : int, default 20
@@ -413,27 +676,55 @@ def normalise_json(
         Enable unification of compatible but non-homogeneous record schemas into maps.
         When True, record schemas with compatible field types can be merged into a single
         map schema with selective nullable fields.
-    no_unify: set[str] | None, default None
-        Prevent unification of keys under these field names with their sibling record fields.
+    detect_tuples : bool, default False
+        Keep a schema per position for arrays that had the same length in every
+        row and different values at each position (e.g. ``[string, object]``
+        pairs), instead of one union for all the items. Avro schemas get a
+        record with fields ``_0``, ``_1``, ...
+    required_threshold : float, optional
+        Fraction of the objects at a path (0 to 1) that must have a field for it
+        to be required, rather than all of them, so a few malformed rows don't
+        make the field optional.
+    coerce_map_array : bool, default False
+        Collapse a field seen both as a map of arrays (``{"en": ["a"]}``)
+        and as an array of maps (``[{"en": "a"}]``) to the map-of-arrays
+        form, and fold the array form into it when normalising.
+    no_unify : list[str] | set[str], optional
+        Prevent unification of keys under these fields with their sibling record fields,
+        e.g. volatile fields like ``references``. Accepts names or paths like
+        ``force_field_types``. Example: ``["references", "/claims/*/qualifiers"]``.
     force_field_types : dict[str, str], optional
         Override the inferred type for specific fields. Keys are field names,
         values must be either ``"map"`` or ``"record"``.
     force_parent_field_types : dict[str, str], optional
         Override the inferred type for specific fields based on their parent field name.
         Keys are field names, values must be either ``"map"`` or ``"record"``.
-    force_scalar_promotion : set[str], optional
-        Set of field names that should always be promoted to wrapped scalars,
+    force_scalar_promotion : list[str] | set[str], optional
+        Fields that should always be promoted to wrapped scalars (names, or paths
+        like ``force_field_types``),
         even when they appear as simple scalars. Ensures schema stability for
         fields known to have heterogeneous types across chunks.
         Example: ``{"precision", "datavalue"}``.
+    ignore_paths : list[str], optional
+        Fields whose subtrees are treated as opaque strings during inference,
+        e.g. freeform metadata blobs. Accepts names or paths like ``force_field_types``.
+        Example: ``["metadata", "/items/extra"]``.
+    max_depth : int, optional
+        Treat objects and arrays nested deeper than this (the root is depth 1)
+        as opaque strings during inference.
+    max_object_keys : int, optional
+        Treat objects with more than this many keys (below the root) as opaque
+        strings during inference.
     wrap_scalars : bool, default True
         Whether to promote scalar values into singleton objects when they appear
         in contexts where other rows provide objects. This avoids unification
         failures between scalars and objects. The promoted field name defaults
         to the parent key with a ``__{type}`` suffix, e.g. a string under
         ``"value"`` becomes ``{"value__string": "..."}``.
-    wrap_root : str | None, default None
-        Wrap each JSON row under that key before normalisation.
+    wrap_root : str | dict[str, str] | None, default None
+        Wrap each JSON row under that key before normalisation, or under nested
+        keys for a dotted path like ``"entity.claims"``. A dict maps input column
+        names to their own root, for expressions over several columns.
         If ``None``, leave rows unchanged.
     no_root_map : bool, default True
         Prevent document root from becoming a map type, even if it meets map inference criteria
@@ -441,6 +732,31 @@ def normalise_json(
         Maximum number of schema builders to create in parallel at once.
         Lower values reduce peak memory usage during schema inference.
         If None, processes all strings at once. Default is None.
+    chunk_size : int, optional
+        Infer in batches of this many rows (NDJSON lines), merging each batch
+        before the next so peak memory stays bounded however large the input.
+    error_context_chars : int, default 100
+        Characters of an invalid row to quote in its parse error, around
+        where parsing failed.
+    error_dump_dir : str, optional
+        Directory to write invalid rows to in full, with each file's path
+        given in the parse error, for rows too long to quote.
+    sample : int | tuple[int, int], optional
+        Infer from a sample of the rows (NDJSON lines): an int ``n`` takes the
+        first ``n``, a tuple ``(n, seed)`` takes ``n`` chosen at random with ``seed``.
+    sample_every : int, optional
+        Infer from every ``k``th row only. Cannot be combined with ``sample``.
+    cache : bool, default False
+        Look the inferred schema up by a hash of the column's rows and these
+        settings, and keep it for later calls on the same data, which then skip
+        inference. Needs the ``schema-cache`` build feature. Clear it with
+        :func:`clear_schema_cache`.
+    cache_dir : str, optional
+        Directory to also keep cached schemas in, so they outlast the session.
+        Implies ``cache``.
+    output_name : str, optional
+        Name of the output column. Defaults to ``"normalised"``. Use
+        :func:`effective_config` to inspect the settings behind the output.
 
     Returns:
     -------
@@ -473,18 +789,33 @@ def normalise_json(
         "empty_as_null": empty_as_null,
         "coerce_string": coerce_strings,
         "map_encoding": map_encoding,
+        "unknown_fields": unknown_fields,
+        "value_limits": value_limits,
         "profile": profile,
         "map_threshold": map_threshold,
         "map_max_required_keys": map_max_required_keys,
         "unify_maps": unify_maps,
+        "detect_tuples": detect_tuples,
+        "required_threshold": required_threshold,
+        "coerce_map_array": coerce_map_array,
         "no_unify": list(no_unify) if no_unify else [],
         "force_scalar_promotion": (
             list(force_scalar_promotion) if force_scalar_promotion else []
         ),
+        "ignore_paths": list(ignore_paths) if ignore_paths else [],
+        "max_depth": max_depth,
+        "max_object_keys": max_object_keys,
         "wrap_scalars": wrap_scalars,
         "wrap_root": wrap_root,
         "no_root_map": no_root_map,
         "max_builders": max_builders,
+        "chunk_size": chunk_size,
+        "error_context_chars": error_context_chars,
+        "error_dump_dir": error_dump_dir,
+        "sample": _sample_spec(sample, sample_every),
+        "cache": cache,
+        "cache_dir": cache_dir,
+        "output_name": output_name,
     }
     if force_field_types is not None:
         kwargs["force_field_types"] = force_field_types
@@ -494,6 +825,263 @@ def normalise_json(
     return plug(expr, changes_length=True, **kwargs)
 
 
+def schema_conformity(
+    expr: pl.Expr,
+    *,
+    schema: dict | str | None = None,
+    map_threshold: int = 20,
+    map_max_required_keys: int | None = None,
+    unify_maps: bool = False,
+    force_field_types: dict[str, str] | None = None,
+    output_name: str | None = None,
+) -> pl.Expr:
+    """Validate each row of a JSON string column against a JSON Schema.
+
+    Parameters
+    ----------
+    expr : pl.Expr
+        Expression representing a string column of JSON data.
+    schema : dict | str, optional
+        JSON Schema to validate against, as a dict or JSON text. If None, a schema
+        is inferred from the whole column first (with the inference options below).
+    map_threshold : int, default 20
+        Maximum number of keys before an object is treated as a map when inferring.
+    map_max_required_keys : int, optional
+        Maximum number of required keys allowed for Map inference.
+    unify_maps : bool, default False
+        Enable unification of compatible but non-homogeneous record schemas into maps.
+    force_field_types : dict[str, str], optional
+        Override the inferred type for specific fields (``"map"`` or ``"record"``).
+    output_name : str, optional
+        Name of the output column. Defaults to ``"conformity"``.
+
+    Returns:
+    -------
+    pl.Expr
+        A struct column with a boolean ``valid`` and a list of ``violations``: the
+        paths that didn't conform, such as ``"owner.email"`` or ``"tags[1]"``
+        (``"$"`` for the row itself, including rows that aren't valid JSON).
+        Null rows give null.
+    """
+    if isinstance(schema, dict):
+        schema = orjson.dumps(schema).decode()
+    kwargs = {
+        "schema": schema,
+        "map_threshold": map_threshold,
+        "map_max_required_keys": map_max_required_keys,
+        "unify_maps": unify_maps,
+        "output_name": output_name,
+    }
+    if force_field_types is not None:
+        kwargs["force_field_types"] = force_field_types
+
+    return plug(expr, changes_length=False, **kwargs)
+
+
+def schema_compatible(
+    old: pl.Expr,
+    new: pl.Expr,
+    *,
+    mode: Literal["backward", "forward", "full"] = "backward",
+    output_name: str | None = None,
+) -> pl.Expr:
+    """Check that the schemas in two string columns can read each other's data.
+
+    Each row compares an old schema with a new one by Avro's schema resolution
+    rules, so a schema change can be gated before it reaches consumers. Either
+    schema may be a JSON Schema (as inferred) or an Avro schema.
+
+    Parameters
+    ----------
+    old : pl.Expr
+        Expression representing a string column of the old schemas.
+    new : pl.Expr
+        Expression representing a string column of the new schemas.
+    mode : {"backward", "forward", "full"}, default "backward"
+        ``"backward"``: the new schema reads data written with the old one.
+        ``"forward"``: the old schema reads data written with the new one.
+        ``"full"``: both.
+    output_name : str, optional
+        Name of the output column. Defaults to ``"compatibility"``.
+
+    Returns:
+    -------
+    pl.Expr
+        A struct column with a boolean ``compatible`` and a list of ``issues``,
+        each a struct of the field ``path``, the ``direction`` that failed
+        (``"backward"`` or ``"forward"``) and a ``message``. Rows where either
+        schema is null give null.
+    """
+    kwargs = {"compatibility_mode": mode, "output_name": output_name}
+    return register_plugin_function(
+        plugin_path=lib,
+        function_name="schema_compatible",
+        args=[old, new],
+        is_elementwise=True,
+        kwargs=kwargs,
+    )
+
+
+def unnest_json(
+    expr: pl.Expr,
+    schema: dict | str,
+    *,
+    map_encoding: Literal["entries", "mapping", "kv"] = "kv",
+) -> pl.Expr:
+    """Decode normalised JSON strings into Polars types, following the Avro schema.
+
+    Records decode to structs and maps to lists of ``{key, value}`` structs,
+    however the rows' maps were encoded, so map fields never turn into a struct
+    field per key (as they would decoding mapping-encoded JSON without a schema).
+
+    Parameters
+    ----------
+    expr : pl.Expr
+        Expression representing a string column of normalised JSON.
+    schema : dict | str
+        Avro schema the rows were normalised against, as a dict or JSON text (e.g.
+        the one returned by ``normalise_json(..., with_schema=True)``).
+    map_encoding : {"mapping", "entries", "kv"}, default "kv"
+        Map encoding the rows were normalised with.
+
+    Returns:
+    -------
+    pl.Expr
+        A struct column, with one field per field of the schema's root record.
+    """
+    if isinstance(schema, dict):
+        schema = orjson.dumps(schema).decode()
+    dtype = pl.Struct(avro_to_polars_schema(schema))
+    if map_encoding != "kv":
+        expr = plug(
+            expr, changes_length=False, schema=schema, map_encoding=map_encoding
+        )
+    return expr.str.json_decode(dtype=dtype)
+
+
+def effective_config(**options) -> dict:
+    """Resolve expression options to the configuration genson-core runs with.
+
+    Accepts the same keyword arguments as :func:`infer_json_schema`,
+    :func:`infer_polars_schema` and :func:`normalise_json`. Omitted options take
+    the plugin's own defaults, so pass the same options as the expression call
+    to record alongside an output column how it was produced.
+
+    Returns:
+    -------
+    dict
+        A dict with ``output_name``, ``inference`` (the schema inference config)
+        and ``normalisation`` (the normalisation config) entries.
+    """
+    return orjson.loads(_rust_effective_config(_kwargs_json(options)))
+
+
+def clear_schema_cache() -> None:
+    """Drop the schemas cached in memory by calls with ``cache=True``.
+
+    Schemas kept in a ``cache_dir`` are left in place: delete the files to drop them.
+    """
+    _rust_clear_schema_cache()
+
+
+class SchemaBuilder:
+    """Accumulate one JSON schema over batches of a JSON string column.
+
+    Use it where the column arrives in pieces, such as the batches of a
+    LazyFrame query, rather than collecting it first to call
+    :func:`infer_json_schema`. Accepts the inference options of
+    :func:`infer_json_schema` (``wrap_root`` as a string only). Sampling options
+    apply to each batch separately.
+
+    Examples:
+    --------
+    >>> builder = SchemaBuilder(map_threshold=10)
+    >>> for batch in batches:
+    ...     builder.add_batch(batch)
+    >>> schema = builder.schema()
+    """
+
+    def __init__(self, **options):
+        self._builder = _RustSchemaBuilder(_kwargs_json(options))
+
+    def add_batch(self, batch: pl.Series) -> int:
+        """Merge a batch of rows into the schema, returning how many were added.
+
+        Null and blank rows are skipped. Struct and list columns are serialised
+        to JSON first, as in :func:`infer_json_schema`.
+        """
+        return self._builder.add_batch(batch)
+
+    @property
+    def processed_count(self) -> int:
+        """Number of documents added so far."""
+        return self._builder.processed_count
+
+    def schema(self) -> dict:
+        """The schema of every batch added so far."""
+        return orjson.loads(self._builder.schema())
+
+
+def infer_json_schema_lazy(
+    lf: pl.LazyFrame,
+    column: str,
+    *,
+    wrap_root: bool | str | dict[str, str] | None = None,
+    **options,
+) -> dict:
+    """Infer the JSON schema of a LazyFrame column without collecting it.
+
+    The query runs on the streaming engine, and each batch of ``column`` is
+    merged into the schema (with a :class:`SchemaBuilder`) as it arrives, so
+    memory holds one batch at a time rather than the whole column.
+
+    Parameters
+    ----------
+    lf : pl.LazyFrame
+        The query giving the column.
+    column : str
+        Name of the column containing JSON strings.
+    wrap_root : str | bool | dict[str, str] | None, default None
+        Wrap each row under that key before inference, as in
+        :func:`infer_json_schema`: ``True`` wraps under the column name.
+    **options
+        Other inference options of :func:`infer_json_schema`.
+
+    Returns:
+    -------
+    dict
+        The inferred schema, the same as collecting the column first would give.
+    """
+    builder = SchemaBuilder(wrap_root=_wrap_root_for(column, wrap_root), **options)
+
+    def add_batch(batch: pl.Series) -> pl.Series:
+        builder.add_batch(batch)
+        return batch.is_null()
+
+    lf.select(
+        pl.col(column)
+        .map_batches(add_batch, return_dtype=pl.Boolean, is_elementwise=True)
+        .any()
+    ).collect(engine="streaming")
+    return builder.schema()
+
+
+def _kwargs_json(options: dict) -> str:
+    """Serialise Python-side options to the plugin's kwargs, as JSON."""
+    if "coerce_strings" in options:
+        options["coerce_string"] = options.pop("coerce_strings")
+    if "sample" in options or "sample_every" in options:
+        options["sample"] = _sample_spec(
+            options.pop("sample", None), options.pop("sample_every", None)
+        )
+    kwargs = {
+        key: sorted(value) if isinstance(value, set) else value
+        for key, value in options.items()
+        if value is not None
+    }
+    return orjson.dumps(kwargs).decode()
+
+
 def infer_from_parquet(
     input_path: str | Path,
     column: str,
@@ -502,21 +1090,31 @@ def infer_from_parquet(
     ignore_outer_array: bool = True,
     ndjson: bool = False,
     schema_uri: str | None = "http://json-schema.org/schema#",
+    json_schema_draft: Literal["draft-07", "2019-09", "2020-12"] | None = None,
+    sort_fields: Literal["preserve", "alphabetical", "by_frequency"] = "preserve",
+    null_fallback: Literal["string", "integer", "number", "boolean"] | None = None,
     debug: bool = False,
     profile: bool = False,
     verbosity: Literal["Normal", "Verbose"] = "Normal",
     map_threshold: int = 20,
     map_max_required_keys: int | None = None,
     unify_maps: bool = False,
-    no_unify: set[str] | None = None,
+    detect_tuples: bool = False,
+    required_threshold: float | None = None,
+    coerce_map_array: bool = False,
+    no_unify: list[str] | set[str] | None = None,
     force_field_types: dict[str, str] | None = None,
     force_parent_field_types: dict[str, str] | None = None,
-    force_scalar_promotion: set[str] | None = None,
+    force_scalar_promotion: list[str] | set[str] | None = None,
+    ignore_paths: list[str] | None = None,
+    max_depth: int | None = None,
+    max_object_keys: int | None = None,
     wrap_scalars: bool = True,
     avro: bool = False,
     wrap_root: str | None = None,
     no_root_map: bool = True,
     max_builders: int | None = None,
+    format: Literal["json", "yaml", "toml"] = "json",
 ) -> str | dict:
     """Infer JSON schema from a Parquet column.
 
@@ -534,6 +1132,16 @@ def infer_from_parquet(
         Whether to treat input as newline-delimited JSON
     schema_uri : str or None, default "http://json-schema.org/schema#"
         Schema URI to use for the generated schema
+    json_schema_draft : {"draft-07", "2019-09", "2020-12"}, optional
+        JSON Schema draft to write the schema for: sets ``$schema`` to the draft's
+        URI and uses its keywords (``definitions`` or ``$defs``, ``prefixItems``).
+    sort_fields : {"preserve", "alphabetical", "by_frequency"}, default "preserve"
+        Order of the properties (and Avro fields) at every level: as first seen in
+        the input, by name, or most often seen first (ties by name).
+    null_fallback : {"string", "integer", "number", "boolean"}, optional
+        Type given to fields that were null in every row, which otherwise get the
+        ``null`` type that Avro consumers can't use. They become nullable fields of
+        that type.
     debug : bool, default False
         Whether to print debug information
     profile : bool, default False
@@ -551,26 +1159,54 @@ def infer_from_parquet(
         Enable unification of compatible but non-homogeneous record schemas into maps.
         When True, record schemas with compatible field types can be merged into a single
         map schema with selective nullable fields.
-    no_unify: set[str] | None, default None
-        Prevent unification of keys under these field names with their sibling record fields.
+    detect_tuples : bool, default False
+        Keep a schema per position for arrays that had the same length in every
+        row and different values at each position (e.g. ``[string, object]``
+        pairs), instead of one union for all the items. Avro schemas get a
+        record with fields ``_0``, ``_1``, ...
+    required_threshold : float, optional
+        Fraction of the objects at a path (0 to 1) that must have a field for it
+        to be required, rather than all of them, so a few malformed rows don't
+        make the field optional.
+    coerce_map_array : bool, default False
+        Collapse a field seen both as a map of arrays (``{"en": ["a"]}``)
+        and as an array of maps (``[{"en": "a"}]``) to the map-of-arrays
+        form, and fold the array form into it when normalising.
+    no_unify : list[str] | set[str], optional
+        Prevent unification of keys under these fields with their sibling record fields,
+        e.g. volatile fields like ``references``. Accepts names or paths like
+        ``force_field_types``. Example: ``["references", "/claims/*/qualifiers"]``.
     force_field_types : dict[str, str], optional
-        Explicit overrides for specific fields. Values must be `"map"` or `"record"`.
-        Example: ``{"labels": "map", "claims": "record"}``.
+        Explicit overrides for specific fields. Values must be `"map"` or `"record"`,
+        or a scalar type (`"string"`, `"integer"`, `"number"`, `"boolean"`, `"date"`,
+        `"date-time"`). Example: ``{"labels": "map", "price": "number"}``.
     force_parent_field_types : dict[str, str], optional
         Explicit overrides for fields based on their parent field name. Values must be `"map"` or `"record"`.
         Example: ``{"labels": "map", "claims": "record"}``.
-    force_scalar_promotion : set[str], optional
-        Set of field names that should always be promoted to wrapped scalars,
+    force_scalar_promotion : list[str] | set[str], optional
+        Fields that should always be promoted to wrapped scalars (names, or paths
+        like ``force_field_types``),
         even when they appear as simple scalars. Ensures schema stability for
         fields known to have heterogeneous types across chunks.
         Example: ``{"precision", "datavalue"}``.
+    ignore_paths : list[str], optional
+        Fields whose subtrees are treated as opaque strings during inference,
+        e.g. freeform metadata blobs. Accepts names or paths like ``force_field_types``.
+        Example: ``["metadata", "/items/extra"]``.
+    max_depth : int, optional
+        Treat objects and arrays nested deeper than this (the root is depth 1)
+        as opaque strings during inference.
+    max_object_keys : int, optional
+        Treat objects with more than this many keys (below the root) as opaque
+        strings during inference.
     wrap_scalars : bool, default True
         Whether to promote scalar values into singleton objects when they appear
         in contexts where other rows provide objects.
     avro: bool, default False
         Whether to output an Avro schema instead of JSON schema.
     wrap_root : str | None, default None
-        If a string, wrap each JSON row under that key before inference.
+        If a string, wrap each JSON row under that key before inference, or under
+        nested keys for a dotted path like ``"entity.claims"``.
         If ``None``, leave rows unchanged.
     no_root_map : bool, default True
         Prevent document root from becoming a map type, even if it meets map inference criteria
@@ -578,12 +1214,16 @@ def infer_from_parquet(
         Maximum number of schema builders to create in parallel at once.
         Lower values reduce peak memory usage during schema inference.
         If None, processes all strings at once. Default is None.
+    format : {"json", "yaml", "toml"}, default "json"
+        Text format to write the schema in. With ``"yaml"`` or ``"toml"`` the
+        schema is returned (or written to ``output_path``) as text in that format.
 
     Returns:
     -------
     str | dict
         If output_path is given, returns success message.
-        If output_path is None, returns schema as dict.
+        If output_path is None, returns schema as dict (or as text for a
+        ``format`` other than ``"json"``).
 
     Examples:
     --------
@@ -599,16 +1239,25 @@ def infer_from_parquet(
         ignore_outer_array=ignore_outer_array,
         ndjson=ndjson,
         schema_uri=schema_uri,
+        json_schema_draft=json_schema_draft,
+        sort_fields=sort_fields,
+        null_fallback=null_fallback,
         debug=debug,
         profile=profile,
         verbosity=verbosity,
         map_threshold=map_threshold,
         map_max_required_keys=map_max_required_keys,
         unify_maps=unify_maps,
+        detect_tuples=detect_tuples,
+        required_threshold=required_threshold,
+        coerce_map_array=coerce_map_array,
         no_unify=list(no_unify) if no_unify else None,
         force_scalar_promotion=(
             list(force_scalar_promotion) if force_scalar_promotion else []
         ),
+        ignore_paths=ignore_paths,
+        max_depth=max_depth,
+        max_object_keys=max_object_keys,
         force_field_types=force_field_types,
         force_parent_field_types=force_parent_field_types,
         wrap_scalars=wrap_scalars,
@@ -616,10 +1265,11 @@ def infer_from_parquet(
         wrap_root=wrap_root,
         no_root_map=no_root_map,
         max_builders=max_builders,
+        format=format,
     )
 
-    if output_path:
-        return result  # Success message
+    if output_path or format != "json":
+        return result  # Success message, or the schema as text
     else:
         return orjson.loads(result)  # Parse and return dict
 
@@ -640,10 +1290,13 @@ def normalise_from_parquet(
     map_threshold: int = 20,
     map_max_required_keys: int | None = None,
     unify_maps: bool = False,
-    no_unify: set[str] | None = None,
+    no_unify: list[str] | set[str] | None = None,
     force_field_types: dict[str, str] | None = None,
     force_parent_field_types: dict[str, str] | None = None,
-    force_scalar_promotion: set[str] | None = None,
+    force_scalar_promotion: list[str] | set[str] | None = None,
+    ignore_paths: list[str] | None = None,
+    max_depth: int | None = None,
+    max_object_keys: int | None = None,
     wrap_scalars: bool = True,
     wrap_root: str | None = None,
     no_root_map: bool = True,
@@ -692,22 +1345,36 @@ def normalise_from_parquet(
         Enable unification of compatible but non-homogeneous record schemas into maps.
         When True, record schemas with compatible field types can be merged into a single
         map schema with selective nullable fields.
-    no_unify: set[str] | None, default None
-        Prevent unification of keys under these field names with their sibling record fields.
+    no_unify : list[str] | set[str], optional
+        Prevent unification of keys under these fields with their sibling record fields,
+        e.g. volatile fields like ``references``. Accepts names or paths like
+        ``force_field_types``. Example: ``["references", "/claims/*/qualifiers"]``.
     force_field_types : dict[str, str], optional
         Per-field overrides for schema inference (e.g. ``{"labels": "map"}``).
     force_parent_field_types : dict[str, str], optional
         Per-field overrides for schema inference based on their parent field name (e.g. ``{"labels": "map"}``).
-    force_scalar_promotion : set[str], optional
-        Set of field names that should always be promoted to wrapped scalars,
+    force_scalar_promotion : list[str] | set[str], optional
+        Fields that should always be promoted to wrapped scalars (names, or paths
+        like ``force_field_types``),
         even when they appear as simple scalars. Ensures schema stability for
         fields known to have heterogeneous types across chunks.
         Example: ``{"precision", "datavalue"}``.
+    ignore_paths : list[str], optional
+        Fields whose subtrees are treated as opaque strings during inference,
+        e.g. freeform metadata blobs. Accepts names or paths like ``force_field_types``.
+        Example: ``["metadata", "/items/extra"]``.
+    max_depth : int, optional
+        Treat objects and arrays nested deeper than this (the root is depth 1)
+        as opaque strings during inference.
+    max_object_keys : int, optional
+        Treat objects with more than this many keys (below the root) as opaque
+        strings during inference.
     wrap_scalars : bool, default True
         Whether to promote scalar values into singleton objects when they appear
         in contexts where other rows provide objects.
     wrap_root : str | None, default None
-        If a string, wrap each JSON row under that key before normalisation.
+        If a string, wrap each JSON row under that key before normalisation, or under
+        nested keys for a dotted path like ``"entity.claims"``.
         If ``None``, leave rows unchanged.
     no_root_map : bool, default True
         Prevent document root from becoming a map type, even if it meets map inference criteria
@@ -754,6 +1421,9 @@ def normalise_from_parquet(
         force_scalar_promotion=(
             list(force_scalar_promotion) if force_scalar_promotion else []
         ),
+        ignore_paths=ignore_paths,
+        max_depth=max_depth,
+        max_object_keys=max_object_keys,
         wrap_scalars=wrap_scalars,
         wrap_root=wrap_root,
         no_root_map=no_root_map,
@@ -761,6 +1431,186 @@ def normalise_from_parquet(
     )
 
 
+def scan_json_normalised(
+    source: str | Path,
+    *,
+    batch_size: int = 10_000,
+    empty_as_null: bool = True,
+    coerce_strings: bool = False,
+    unknown_fields: Literal["drop", "keep", "error"] = "drop",
+    value_limits: dict | None = None,
+    map_threshold: int = 20,
+    map_max_required_keys: int | None = None,
+    unify_maps: bool = False,
+    no_unify: list[str] | set[str] | None = None,
+    force_field_types: dict[str, str] | None = None,
+    force_parent_field_types: dict[str, str] | None = None,
+    force_scalar_promotion: list[str] | set[str] | None = None,
+    ignore_paths: list[str] | None = None,
+    max_depth: int | None = None,
+    max_object_keys: int | None = None,
+    wrap_scalars: bool = True,
+    wrap_root: str | None = None,
+    no_root_map: bool = True,
+    max_builders: int | None = None,
+    chunk_size: int | None = None,
+    error_context_chars: int = 100,
+    error_dump_dir: str | None = None,
+    sample: int | tuple[int, int] | None = None,
+    sample_every: int | None = None,
+) -> pl.LazyFrame:
+    """Lazily read an NDJSON file, normalised to one schema inferred from it.
+
+    The schema is inferred over the whole file (or a sample of it) when this is
+    called, so the LazyFrame has its final dtypes up front. Collecting it then
+    reads the file in batches, normalising each line to the schema as with
+    :func:`normalise_json` (using the ``kv`` map encoding).
+
+    Parameters
+    ----------
+    source : str | Path
+        Path to a newline-delimited JSON file.
+    batch_size : int, default 10_000
+        Number of lines to normalise per batch, unless Polars asks for another size.
+    empty_as_null : bool, default True
+        If True, normalise empty arrays and empty maps to ``null``.
+    coerce_strings : bool, default False
+        If True, attempt to parse numeric/boolean values from strings.
+    unknown_fields : {"drop", "keep", "error"}, default "drop"
+        What to do with input fields the schema doesn't declare, which can happen
+        when inferring from a sample: leave them out, pass them through unchanged,
+        or raise an error naming the first one.
+    value_limits : dict, optional
+        Caps on the normalised values, so adversarial rows can't produce values too
+        large for downstream writers: ``max_string_len`` cuts longer strings and ends
+        them with ``marker`` (default ``"...[truncated]"``), ``max_array_items`` keeps
+        the first items of arrays, and ``max_depth`` replaces values nested deeper
+        (the row is depth 1) with null.
+    map_threshold : int, default 20
+        Maximum number of keys before an object is treated as a map
+        (unless overridden).
+    map_max_required_keys : int, optional
+        Maximum number of required keys allowed for Map inference.
+    unify_maps : bool, default False
+        Enable unification of compatible but non-homogeneous record schemas into maps.
+    no_unify : list[str] | set[str], optional
+        Prevent unification of keys under these fields with their sibling record fields,
+        e.g. volatile fields like ``references``. Accepts names or paths like
+        ``force_field_types``. Example: ``["references", "/claims/*/qualifiers"]``.
+    force_field_types : dict[str, str], optional
+        Override the inferred type for specific fields (``"map"`` or ``"record"``).
+    force_parent_field_types : dict[str, str], optional
+        Override the type of fields based on their parent field name.
+    force_scalar_promotion : set[str], optional
+        Field names that should always be promoted to wrapped scalars.
+    ignore_paths : list[str], optional
+        Fields whose subtrees are treated as opaque strings during inference,
+        e.g. freeform metadata blobs. Accepts names or paths like ``force_field_types``.
+        Example: ``["metadata", "/items/extra"]``.
+    max_depth : int, optional
+        Treat objects and arrays nested deeper than this (the root is depth 1)
+        as opaque strings during inference.
+    max_object_keys : int, optional
+        Treat objects with more than this many keys (below the root) as opaque
+        strings during inference.
+    wrap_scalars : bool, default True
+        Whether to promote scalar values into singleton objects when they appear
+        in contexts where other rows provide objects.
+    wrap_root : str | None, default None
+        Wrap each line under that key (or nested keys for a dotted path).
+    no_root_map : bool, default True
+        Prevent document root from becoming a map type.
+    max_builders : int, optional
+        Maximum number of schema builders to create in parallel at once.
+    chunk_size : int, optional
+        Infer in batches of this many lines, merging each batch before the next.
+    error_context_chars : int, default 100
+        Characters of an invalid row to quote in its parse error, around
+        where parsing failed.
+    error_dump_dir : str, optional
+        Directory to write invalid rows to in full, with each file's path
+        given in the parse error, for rows too long to quote.
+    sample : int | tuple[int, int], optional
+        Infer from a sample of the lines: an int ``n`` takes the first ``n``, a
+        tuple ``(n, seed)`` takes ``n`` chosen at random with ``seed``.
+    sample_every : int, optional
+        Infer from every ``k``th line only. Cannot be combined with ``sample``.
+
+    Returns:
+    -------
+    pl.LazyFrame
+        One column per top-level field of the inferred schema.
+    """
+    path = str(source)
+    kwargs_json = _kwargs_json(
+        {
+            "ndjson": True,
+            "empty_as_null": empty_as_null,
+            "coerce_strings": coerce_strings,
+            "map_encoding": "kv",
+            "unknown_fields": unknown_fields,
+            "value_limits": value_limits,
+            "map_threshold": map_threshold,
+            "map_max_required_keys": map_max_required_keys,
+            "unify_maps": unify_maps,
+            "no_unify": no_unify,
+            "force_field_types": force_field_types,
+            "force_parent_field_types": force_parent_field_types,
+            "force_scalar_promotion": force_scalar_promotion,
+            "ignore_paths": ignore_paths,
+            "max_depth": max_depth,
+            "max_object_keys": max_object_keys,
+            "wrap_scalars": wrap_scalars,
+            "wrap_root": wrap_root,
+            "no_root_map": no_root_map,
+            "max_builders": max_builders,
+            "chunk_size": chunk_size,
+            "error_context_chars": error_context_chars,
+            "error_dump_dir": error_dump_dir,
+            "sample": sample,
+            "sample_every": sample_every,
+        }
+    )
+    avro_schema = _rust_infer_ndjson_schema(path, kwargs_json)
+    schema = avro_to_polars_schema(avro_schema)
+    dtype = pl.Struct(schema)
+
+    def read_batches(size: int):
+        with open(path, encoding="utf-8") as f:
+            lines = []
+            for line in f:
+                if line.strip():
+                    lines.append(line)
+                if len(lines) >= size:
+                    yield lines
+                    lines = []
+            if lines:
+                yield lines
+
+    def source_generator(
+        with_columns: list[str] | None,
+        predicate: pl.Expr | None,
+        n_rows: int | None,
+        requested_batch_size: int | None,
+    ):
+        for lines in read_batches(requested_batch_size or batch_size):
+            if n_rows is not None and n_rows <= 0:
+                break
+            normalised = _rust_normalise_lines(lines, avro_schema, kwargs_json)
+            df = pl.Series(normalised, dtype=pl.String).str.json_decode(dtype)
+            df = df.struct.unnest()
+            if predicate is not None:
+                df = df.filter(predicate)
+            if with_columns is not None:
+                df = df.select(with_columns)
+            if n_rows is not None:
+                df = df.head(n_rows)
+                n_rows -= df.height
+            yield df
+
+    return register_io_source(source_generator, schema=schema)
+
+
 @register_dataframe_namespace("genson")
 class GensonNamespace:
     """Namespace for JSON schema inference operations."""
@@ -791,17 +1641,30 @@ class GensonNamespace:
         map_threshold: int = 20,
         map_max_required_keys: int | None = None,
         unify_maps: bool = False,
-        no_unify: set[str] | None = None,
+        detect_tuples: bool = False,
+        required_threshold: float | None = None,
+        coerce_map_array: bool = False,
+        no_unify: list[str] | set[str] | None = None,
         force_field_types: dict[str, str] | None = None,
         force_parent_field_types: dict[str, str] | None = None,
-        force_scalar_promotion: set[str] | None = None,
+        force_scalar_promotion: list[str] | set[str] | None = None,
+        ignore_paths: list[str] | None = None,
+        max_depth: int | None = None,
+        max_object_keys: int | None = None,
         wrap_scalars: bool = True,
         avro: bool = False,
-        wrap_root: bool | str | None = None,
+        wrap_root: bool | str | dict[str, str] | None = None,
         no_root_map: bool = True,
         max_builders: int | None = None,
-    ) -> pl.Schema:
-        # ) -> pl.Schema | list[pl.Schema]:
+        chunk_size: int | None = None,
+        error_context_chars: int = 100,
+        error_dump_dir: str | None = None,
+        sample: int | tuple[int, int] | None = None,
+        sample_every: int | None = None,
+        cache: bool = False,
+        cache_dir: str | None = None,
+        return_nullable: bool = False,
+    ) -> pl.Schema | tuple[pl.Schema, list[str]]:
         """Infer Polars schema from a string column containing JSON data.
 
         Parameters
@@ -831,19 +1694,46 @@ class GensonNamespace:
             Enable unification of compatible but non-homogeneous record schemas into maps.
             When True, record schemas with compatible field types can be merged into a single
             map schema with selective nullable fields.
-        no_unify: set[str] | None, default None
-            Prevent unification of keys under these field names with their sibling record fields.
+        detect_tuples : bool, default False
+            Keep a schema per position for arrays that had the same length in every
+            row and different values at each position (e.g. ``[string, object]``
+            pairs), instead of one union for all the items. Avro schemas get a
+            record with fields ``_0``, ``_1``, ...
+        required_threshold : float, optional
+            Fraction of the objects at a path (0 to 1) that must have a field for it
+            to be required, rather than all of them, so a few malformed rows don't
+            make the field optional.
+        coerce_map_array : bool, default False
+            Collapse a field seen both as a map of arrays (``{"en": ["a"]}``)
+            and as an array of maps (``[{"en": "a"}]``) to the map-of-arrays
+            form, and fold the array form into it when normalising.
+        no_unify : list[str] | set[str], optional
+            Prevent unification of keys under these fields with their sibling record fields,
+            e.g. volatile fields like ``references``. Accepts names or paths like
+            ``force_field_types``. Example: ``["references", "/claims/*/qualifiers"]``.
         force_field_types : dict[str, str], optional
-            Explicit overrides for specific fields. Values must be `"map"` or `"record"`.
-            Example: ``{"labels": "map", "claims": "record"}``.
+            Explicit overrides for specific fields. Values must be `"map"` or `"record"`,
+            or a scalar type (`"string"`, `"integer"`, `"number"`, `"boolean"`, `"date"`,
+            `"date-time"`). Example: ``{"labels": "map", "price": "number"}``.
         force_parent_field_types : dict[str, str], optional
             Explicit overrides for fields based on their parent field name. Values must be `"map"` or `"record"`.
             Example: ``{"labels": "map", "claims": "record"}``.
-        force_scalar_promotion : set[str], optional
-            Set of field names that should always be promoted to wrapped scalars,
+        force_scalar_promotion : list[str] | set[str], optional
+            Fields that should always be promoted to wrapped scalars (names, or paths
+            like ``force_field_types``),
             even when they appear as simple scalars. Ensures schema stability for
             fields known to have heterogeneous types across chunks.
             Example: ``{"precision", "datavalue"}``.
+        ignore_paths : list[str], optional
+            Fields whose subtrees are treated as opaque strings during inference,
+            e.g. freeform metadata blobs. Accepts names or paths like ``force_field_types``.
+            Example: ``["metadata", "/items/extra"]``.
+        max_depth : int, optional
+            Treat objects and arrays nested deeper than this (the root is depth 1)
+            as opaque strings during inference.
+        max_object_keys : int, optional
+            Treat objects with more than this many keys (below the root) as opaque
+            strings during inference.
         wrap_scalars : bool, default True
             Whether to promote scalar values into singleton objects when they appear
             in contexts where other rows provide objects. This avoids unification
@@ -853,20 +1743,51 @@ class GensonNamespace:
         avro : bool, default False
             Whether to infer using Avro schema semantics (unions, maps, nullability).
             By default (`False`), JSON Schema mode is used.
-        wrap_root : str | bool | None, default None
-            If a string, wrap each JSON row under that key before inference.
-            If ``True``, wrap under the column name. If ``None``, leave rows unchanged.
+        wrap_root : str | bool | dict[str, str] | None, default None
+            If a string, wrap each JSON row under that key before inference, or
+            under nested keys for a dotted path like ``"entity.claims"``.
+            If ``True``, wrap under the column name. A dict maps column names to
+            their own root, so one mapping can serve every column of a table
+            (columns it doesn't list are left unwrapped). If ``None``, leave rows
+            unchanged.
         no_root_map : bool, default True
             Prevent document root from becoming a map type, even if it meets map inference criteria
         max_builders : int, optional
             Maximum number of schema builders to create in parallel at once.
             Lower values reduce peak memory usage during schema inference.
             If None, processes all strings at once. Default is None.
+        chunk_size : int, optional
+            Infer in batches of this many rows (NDJSON lines), merging each batch
+            before the next so peak memory stays bounded however large the input.
+        error_context_chars : int, default 100
+            Characters of an invalid row to quote in its parse error, around
+            where parsing failed.
+        error_dump_dir : str, optional
+            Directory to write invalid rows to in full, with each file's path
+            given in the parse error, for rows too long to quote.
+        sample : int | tuple[int, int], optional
+            Infer from a sample of the rows (NDJSON lines): an int ``n`` takes the
+            first ``n``, a tuple ``(n, seed)`` takes ``n`` chosen at random with ``seed``.
+        sample_every : int, optional
+            Infer from every ``k``th row only. Cannot be combined with ``sample``.
+        cache : bool, default False
+            Look the inferred schema up by a hash of the column's rows and these
+            settings, and keep it for later calls on the same data, which then skip
+            inference. Needs the ``schema-cache`` build feature. Clear it with
+            :func:`clear_schema_cache`.
+        cache_dir : str, optional
+            Directory to also keep cached schemas in, so they outlast the session.
+            Implies ``cache``.
+        return_nullable : bool, default False
+            Also return the names of the nullable columns: those not ``required`` in
+            the inferred JSON Schema, or whose type admits ``null`` (for Avro, a
+            union with ``null``). The others can be validated as non-null.
 
         Returns:
         -------
-        pl.Schema | list[pl.Schema]
-            The inferred schema (if merge_schemas=True) or list of schemas (if merge_schemas=False)
+        pl.Schema | tuple[pl.Schema, list[str]]
+            The inferred schema, or with ``return_nullable=True`` a
+            ``(schema, nullable_columns)`` tuple.
         """
         if not merge_schemas:
             raise NotImplementedError("Only merge schemas is implemented")
@@ -880,7 +1801,7 @@ class GensonNamespace:
             if force_parent_field_types is None
             else {"force_parent_field_types": force_parent_field_types}
         )
-        wrap_root_field = column if wrap_root is True else wrap_root
+        wrap_root_field = _wrap_root_for(column, wrap_root)
         result = self._df.select(
             infer_polars_schema(
                 pl.col(column),
@@ -893,27 +1814,45 @@ class GensonNamespace:
                 map_threshold=map_threshold,
                 map_max_required_keys=map_max_required_keys,
                 unify_maps=unify_maps,
+                detect_tuples=detect_tuples,
+                required_threshold=required_threshold,
+                coerce_map_array=coerce_map_array,
                 **fft,
                 **fpft,
+                no_unify=no_unify,
                 force_scalar_promotion=(
                     list(force_scalar_promotion) if force_scalar_promotion else []
                 ),
+                ignore_paths=ignore_paths,
+                max_depth=max_depth,
+                max_object_keys=max_object_keys,
                 wrap_scalars=wrap_scalars,
                 avro=avro,
                 wrap_root=wrap_root_field,
                 no_root_map=no_root_map,
                 max_builders=max_builders,
+                chunk_size=chunk_size,
+                error_context_chars=error_context_chars,
+                error_dump_dir=error_dump_dir,
+                sample=sample,
+                sample_every=sample_every,
+                cache=cache,
+                cache_dir=cache_dir,
             ).first()
         )
 
         # Extract the schema from the first column, which is the struct
         schema_fields = result.to_series().item()
-        return pl.Schema(
+        schema = pl.Schema(
             {
                 field["name"]: _parse_polars_dtype(field["dtype"])
                 for field in schema_fields
             }
         )
+        if return_nullable:
+            nullable = [field["name"] for field in schema_fields if field["nullable"]]
+            return schema, nullable
+        return schema
 
     def infer_json_schema(
         self,
@@ -922,6 +1861,9 @@ class GensonNamespace:
         ignore_outer_array: bool = True,
         ndjson: bool = False,
         schema_uri: str | None = "http://json-schema.org/schema#",
+        json_schema_draft: Literal["draft-07", "2019-09", "2020-12"] | None = None,
+        sort_fields: Literal["preserve", "alphabetical", "by_frequency"] = "preserve",
+        null_fallback: Literal["string", "integer", "number", "boolean"] | None = None,
         merge_schemas: bool = True,
         debug: bool = False,
         profile: bool = False,
@@ -929,16 +1871,32 @@ class GensonNamespace:
         map_threshold: int = 20,
         map_max_required_keys: int | None = None,
         unify_maps: bool = False,
-        no_unify: set[str] | None = None,
+        detect_tuples: bool = False,
+        required_threshold: float | None = None,
+        coerce_map_array: bool = False,
+        no_unify: list[str] | set[str] | None = None,
         force_field_types: dict[str, str] | None = None,
         force_parent_field_types: dict[str, str] | None = None,
-        force_scalar_promotion: set[str] | None = None,
+        force_scalar_promotion: list[str] | set[str] | None = None,
+        ignore_paths: list[str] | None = None,
+        max_depth: int | None = None,
+        max_object_keys: int | None = None,
         wrap_scalars: bool = True,
         avro: bool = False,
-        wrap_root: bool | str | None = None,
+        wrap_root: bool | str | dict[str, str] | None = None,
         no_root_map: bool = True,
         max_builders: int | None = None,
-    ) -> dict | list[dict]:
+        chunk_size: int | None = None,
+        error_context_chars: int = 100,
+        error_dump_dir: str | None = None,
+        sample: int | tuple[int, int] | None = None,
+        sample_every: int | None = None,
+        cache: bool = False,
+        cache_dir: str | None = None,
+        profile_json: bool = False,
+        embed_config: bool = False,
+        format: Literal["json", "yaml", "toml"] = "json",
+    ) -> dict | list[dict] | tuple[dict, dict] | str:
         """Infer JSON schema from a string column containing JSON data.
 
         Parameters
@@ -951,8 +1909,19 @@ class GensonNamespace:
             Whether to treat input as newline-delimited JSON
         schema_uri : str or None, default "http://json-schema.org/schema#"
             Schema URI to use for the generated schema
+        json_schema_draft : {"draft-07", "2019-09", "2020-12"}, optional
+            JSON Schema draft to write the schema for: sets ``$schema`` to the draft's
+            URI and uses its keywords (``definitions`` or ``$defs``, ``prefixItems``).
+        sort_fields : {"preserve", "alphabetical", "by_frequency"}, default "preserve"
+            Order of the properties (and Avro fields) at every level: as first seen in
+            the input, by name, or most often seen first (ties by name).
+        null_fallback : {"string", "integer", "number", "boolean"}, optional
+            Type given to fields that were null in every row, which otherwise get the
+            ``null`` type that Avro consumers can't use. They become nullable fields of
+            that type.
         merge_schemas : bool, default True
-            Whether to merge schemas from all rows (True) or return individual schemas (False)
+            Whether to merge schemas from all rows (True) or return each row's own
+            schema (False), as a list aligned with the rows.
         debug : bool, default False
             Whether to print debug information
         profile : bool, default False
@@ -970,19 +1939,46 @@ class GensonNamespace:
             Enable unification of compatible but non-homogeneous record schemas into maps.
             When True, record schemas with compatible field types can be merged into a single
             map schema with selective nullable fields.
-        no_unify: set[str] | None, default None
-            Prevent unification of keys under these field names with their sibling record fields.
+        detect_tuples : bool, default False
+            Keep a schema per position for arrays that had the same length in every
+            row and different values at each position (e.g. ``[string, object]``
+            pairs), instead of one union for all the items. Avro schemas get a
+            record with fields ``_0``, ``_1``, ...
+        required_threshold : float, optional
+            Fraction of the objects at a path (0 to 1) that must have a field for it
+            to be required, rather than all of them, so a few malformed rows don't
+            make the field optional.
+        coerce_map_array : bool, default False
+            Collapse a field seen both as a map of arrays (``{"en": ["a"]}``)
+            and as an array of maps (``[{"en": "a"}]``) to the map-of-arrays
+            form, and fold the array form into it when normalising.
+        no_unify : list[str] | set[str], optional
+            Prevent unification of keys under these fields with their sibling record fields,
+            e.g. volatile fields like ``references``. Accepts names or paths like
+            ``force_field_types``. Example: ``["references", "/claims/*/qualifiers"]``.
         force_field_types : dict[str, str], optional
-            Explicit overrides for specific fields. Values must be `"map"` or `"record"`.
-            Example: ``{"labels": "map", "claims": "record"}``.
+            Explicit overrides for specific fields. Values must be `"map"` or `"record"`,
+            or a scalar type (`"string"`, `"integer"`, `"number"`, `"boolean"`, `"date"`,
+            `"date-time"`). Example: ``{"labels": "map", "price": "number"}``.
         force_parent_field_types : dict[str, str], optional
             Explicit overrides for fields based on their parent field name. Values must be `"map"` or `"record"`.
             Example: ``{"labels": "map", "claims": "record"}``.
-        force_scalar_promotion : set[str], optional
-            Set of field names that should always be promoted to wrapped scalars,
+        force_scalar_promotion : list[str] | set[str], optional
+            Fields that should always be promoted to wrapped scalars (names, or paths
+            like ``force_field_types``),
             even when they appear as simple scalars. Ensures schema stability for
             fields known to have heterogeneous types across chunks.
             Example: ``{"precision", "datavalue"}``.
+        ignore_paths : list[str], optional
+            Fields whose subtrees are treated as opaque strings during inference,
+            e.g. freeform metadata blobs. Accepts names or paths like ``force_field_types``.
+            Example: ``["metadata", "/items/extra"]``.
+        max_depth : int, optional
+            Treat objects and arrays nested deeper than this (the root is depth 1)
+            as opaque strings during inference.
+        max_object_keys : int, optional
+            Treat objects with more than this many keys (below the root) as opaque
+            strings during inference.
         wrap_scalars : bool, default True
             Whether to promote scalar values into singleton objects when they appear
             in contexts where other rows provide objects. This avoids unification
@@ -991,29 +1987,68 @@ class GensonNamespace:
             ``"value"`` becomes ``{"value__string": "..."}``.
         avro: bool, default False
             Whether to read the input as an Avro schema instead of JSON schema.
-        wrap_root : str | bool | None, default None
-            If a string, wrap each JSON row under that key before inference.
-            If ``True``, wrap under the column name. If ``None``, leave rows unchanged.
+        wrap_root : str | bool | dict[str, str] | None, default None
+            If a string, wrap each JSON row under that key before inference, or
+            under nested keys for a dotted path like ``"entity.claims"``.
+            If ``True``, wrap under the column name. A dict maps column names to
+            their own root, so one mapping can serve every column of a table
+            (columns it doesn't list are left unwrapped). If ``None``, leave rows
+            unchanged.
         no_root_map : bool, default True
             Prevent document root from becoming a map type, even if it meets map inference criteria
         max_builders : int, optional
             Maximum number of schema builders to create in parallel at once.
             Lower values reduce peak memory usage during schema inference.
             If None, processes all strings at once. Default is None.
+        chunk_size : int, optional
+            Infer in batches of this many rows (NDJSON lines), merging each batch
+            before the next so peak memory stays bounded however large the input.
+        error_context_chars : int, default 100
+            Characters of an invalid row to quote in its parse error, around
+            where parsing failed.
+        error_dump_dir : str, optional
+            Directory to write invalid rows to in full, with each file's path
+            given in the parse error, for rows too long to quote.
+        sample : int | tuple[int, int], optional
+            Infer from a sample of the rows (NDJSON lines): an int ``n`` takes the
+            first ``n``, a tuple ``(n, seed)`` takes ``n`` chosen at random with ``seed``.
+        sample_every : int, optional
+            Infer from every ``k``th row only. Cannot be combined with ``sample``.
+        cache : bool, default False
+            Look the inferred schema up by a hash of the column's rows and these
+            settings, and keep it for later calls on the same data, which then skip
+            inference. Needs the ``schema-cache`` build feature. Clear it with
+            :func:`clear_schema_cache`.
+        cache_dir : str, optional
+            Directory to also keep cached schemas in, so they outlast the session.
+            Implies ``cache``.
+        profile_json : bool, default False
+            Also return the stage timings of the run (merged schemas only).
+        embed_config : bool, default False
+            Record the non-default settings in the schema under ``"x-genson-config"``.
+        format : {"json", "yaml", "toml"}, default "json"
+            Return the merged schema as YAML or TOML text instead of a dictionary.
 
         Returns:
         -------
-        dict | list[dict]
+        dict | list[dict] | tuple[dict, dict] | str
             The inferred JSON schema as a dictionary (if merge_schemas=True) or
-            list of schemas (if merge_schemas=False)
+            a list of each row's schema, None for null rows (if merge_schemas=False).
+            With ``profile_json=True``, a ``(schema, profile)`` tuple where
+            ``profile`` holds the timings in milliseconds. With ``format="yaml"`` or ``"toml"``, the schema as text.
         """
-        wrap_root_field = column if wrap_root is True else wrap_root
+        if format != "json" and not merge_schemas:
+            raise ValueError("format only applies to a merged schema")
+        wrap_root_field = _wrap_root_for(column, wrap_root)
         result = self._df.select(
             infer_json_schema(
                 pl.col(column),
                 ignore_outer_array=ignore_outer_array,
                 ndjson=ndjson,
                 schema_uri=schema_uri,
+                json_schema_draft=json_schema_draft,
+                sort_fields=sort_fields,
+                null_fallback=null_fallback,
                 merge_schemas=merge_schemas,
                 debug=debug,
                 profile=profile,
@@ -1021,18 +2056,39 @@ class GensonNamespace:
                 map_threshold=map_threshold,
                 map_max_required_keys=map_max_required_keys,
                 unify_maps=unify_maps,
+                detect_tuples=detect_tuples,
+                required_threshold=required_threshold,
+                coerce_map_array=coerce_map_array,
                 force_field_types=force_field_types,
                 force_parent_field_types=force_parent_field_types,
+                no_unify=no_unify,
                 force_scalar_promotion=(
                     list(force_scalar_promotion) if force_scalar_promotion else []
                 ),
+                ignore_paths=ignore_paths,
+                max_depth=max_depth,
+                max_object_keys=max_object_keys,
                 wrap_scalars=wrap_scalars,
                 avro=avro,
                 wrap_root=wrap_root_field,
                 no_root_map=no_root_map,
                 max_builders=max_builders,
-            ).first()
+                chunk_size=chunk_size,
+                error_context_chars=error_context_chars,
+                error_dump_dir=error_dump_dir,
+                sample=sample,
+                sample_every=sample_every,
+                cache=cache,
+                cache_dir=cache_dir,
+                profile_json=profile_json and merge_schemas,
+                embed_config=embed_config,
+            )
         )
+        if not merge_schemas:
+            return [
+                None if row is None else orjson.loads(row)
+                for row in result.to_series().to_list()
+            ]
 
         # Extract the schema from the first column (whatever it's named)
         schema_json = result.to_series().item()
@@ -1040,9 +2096,17 @@ class GensonNamespace:
             raise ValueError(f"Expected string schema, got {type(schema_json)}")
 
         try:
-            return orjson.loads(schema_json)
+            parsed = orjson.loads(schema_json)
         except orjson.JSONDecodeError as e:
             raise ValueError(f"Failed to parse schema JSON: {e}") from e
+        if profile_json and merge_schemas:
+            schema, profile_timings = parsed["schema"], parsed["profile"]
+            if format != "json":
+                schema = _rust_format_schema(orjson.dumps(schema).decode(), format)
+            return schema, profile_timings
+        if format != "json":
+            return _rust_format_schema(schema_json, format)
+        return parsed
 
     def normalise_json(
         self,
@@ -1050,24 +2114,41 @@ class GensonNamespace:
         *,
         decode: bool | pl.Schema = True,
         unnest: bool = True,
+        column_template: str | None = None,
         ignore_outer_array: bool = True,
         ndjson: bool = False,
         empty_as_null: bool = True,
         coerce_strings: bool = False,
         map_encoding: Literal["entries", "mapping", "kv"] = "kv",
+        unknown_fields: Literal["drop", "keep", "error"] = "drop",
+        value_limits: dict | None = None,
         profile: bool = False,
         map_threshold: int = 20,
         map_max_required_keys: int | None = None,
         unify_maps: bool = False,
-        no_unify: set[str] | None = None,
+        detect_tuples: bool = False,
+        required_threshold: float | None = None,
+        coerce_map_array: bool = False,
+        no_unify: list[str] | set[str] | None = None,
         force_field_types: dict[str, str] | None = None,
         force_parent_field_types: dict[str, str] | None = None,
-        force_scalar_promotion: set[str] | None = None,
+        force_scalar_promotion: list[str] | set[str] | None = None,
+        ignore_paths: list[str] | None = None,
+        max_depth: int | None = None,
+        max_object_keys: int | None = None,
         wrap_scalars: bool = True,
-        wrap_root: bool | str | None = None,
+        wrap_root: bool | str | dict[str, str] | None = None,
         no_root_map: bool = True,
         max_builders: int | None = None,
-    ) -> pl.Series:
+        chunk_size: int | None = None,
+        error_context_chars: int = 100,
+        error_dump_dir: str | None = None,
+        sample: int | tuple[int, int] | None = None,
+        sample_every: int | None = None,
+        cache: bool = False,
+        cache_dir: str | None = None,
+        with_schema: bool = False,
+    ) -> pl.Series | pl.DataFrame | tuple[pl.Series | pl.DataFrame, dict]:
         """Normalise a JSON string column to conform to an inferred Avro schema.
 
         This is a higher-level wrapper around :func:`normalise_json`, returning the
@@ -1090,6 +2171,11 @@ class GensonNamespace:
             Only applies if `decode=True`. If True, expand the decoded struct
             into separate columns for each schema field. If False, keep a
             single Series of structs.
+        column_template : str, optional
+            Only applies if `decode=True` and `unnest=True`. Name the unnested
+            columns with this template, where ``{column}`` is the input column's
+            name and ``{field}`` the field's, e.g. ``"{column}__{field}"`` gives
+            ``labels__en``.
         ignore_outer_array : bool, default True
             Whether to treat a top-level JSON array as a stream of objects instead
             of a single array value.
@@ -1106,6 +2192,16 @@ class GensonNamespace:
             - "mapping": plain JSON object ({"en":"Hello"})
             - "entries": list of single-entry objects ([{"en":"Hello"}])
             - "kv":      list of {key,value} dicts ([{"key":"en","value":"Hello"}])
+        unknown_fields : {"drop", "keep", "error"}, default "drop"
+            What to do with input fields the schema doesn't declare, which can happen
+            when inferring from a sample: leave them out, pass them through unchanged,
+            or raise an error naming the first one.
+        value_limits : dict, optional
+            Caps on the normalised values, so adversarial rows can't produce values too
+            large for downstream writers: ``max_string_len`` cuts longer strings and ends
+            them with ``marker`` (default ``"...[truncated]"``), ``max_array_items`` keeps
+            the first items of arrays, and ``max_depth`` replaces values nested deeper
+            (the row is depth 1) with null.
         profile : bool, default False
             Whether to display timing profile information
         map_threshold : int, default 20
@@ -1119,70 +2215,156 @@ class GensonNamespace:
             Enable unification of compatible but non-homogeneous record schemas into maps.
             When True, record schemas with compatible field types can be merged into a single
             map schema with selective nullable fields.
-        no_unify: set[str] | None, default None
-            Prevent unification of keys under these field names with their sibling record fields.
+        detect_tuples : bool, default False
+            Keep a schema per position for arrays that had the same length in every
+            row and different values at each position (e.g. ``[string, object]``
+            pairs), instead of one union for all the items. Avro schemas get a
+            record with fields ``_0``, ``_1``, ...
+        required_threshold : float, optional
+            Fraction of the objects at a path (0 to 1) that must have a field for it
+            to be required, rather than all of them, so a few malformed rows don't
+            make the field optional.
+        coerce_map_array : bool, default False
+            Collapse a field seen both as a map of arrays (``{"en": ["a"]}``)
+            and as an array of maps (``[{"en": "a"}]``) to the map-of-arrays
+            form, and fold the array form into it when normalising.
+        no_unify : list[str] | set[str], optional
+            Prevent unification of keys under these fields with their sibling record fields,
+            e.g. volatile fields like ``references``. Accepts names or paths like
+            ``force_field_types``. Example: ``["references", "/claims/*/qualifiers"]``.
         force_field_types : dict[str, str], optional
             Per-field overrides for schema inference (e.g. ``{"labels": "map"}``).
         force_parent_field_types : dict[str, str], optional
             Per-field overrides for schema inference based on their parent field name (e.g. ``{"labels": "map"}``).
-        force_scalar_promotion : set[str], optional
-            Set of field names that should always be promoted to wrapped scalars,
+        force_scalar_promotion : list[str] | set[str], optional
+            Fields that should always be promoted to wrapped scalars (names, or paths
+            like ``force_field_types``),
             even when they appear as simple scalars. Ensures schema stability for
             fields known to have heterogeneous types across chunks.
             Example: ``{"precision", "datavalue"}``.
+        ignore_paths : list[str], optional
+            Fields whose subtrees are treated as opaque strings during inference,
+            e.g. freeform metadata blobs. Accepts names or paths like ``force_field_types``.
+            Example: ``["metadata", "/items/extra"]``.
+        max_depth : int, optional
+            Treat objects and arrays nested deeper than this (the root is depth 1)
+            as opaque strings during inference.
+        max_object_keys : int, optional
+            Treat objects with more than this many keys (below the root) as opaque
+            strings during inference.
         wrap_scalars : bool, default True
             Whether to promote scalar values into singleton objects when they appear
             in contexts where other rows provide objects. This avoids unification
             failures between scalars and objects. The promoted field name defaults
             to the parent key with a ``__{type}`` suffix, e.g. a string under
             ``"value"`` becomes ``{"value__string": "..."}``.
-        wrap_root : str | bool | None, default None
-            If a string, wrap each JSON row under that key before normalisation.
-            If ``True``, wrap under the column name. If ``None``, leave rows unchanged.
+        wrap_root : str | bool | dict[str, str] | None, default None
+            If a string, wrap each JSON row under that key before normalisation, or
+            under nested keys for a dotted path like ``"entity.claims"``.
+            If ``True``, wrap under the column name. A dict maps column names to
+            their own root, so one mapping can serve every column of a table
+            (columns it doesn't list are left unwrapped). If ``None``, leave rows
+            unchanged.
         no_root_map : bool, default True
             Prevent document root from becoming a map type, even if it meets map inference criteria
         max_builders : int, optional
             Maximum number of schema builders to create in parallel at once.
             Lower values reduce peak memory usage during schema inference.
             If None, processes all strings at once. Default is None.
+        chunk_size : int, optional
+            Infer in batches of this many rows (NDJSON lines), merging each batch
+            before the next so peak memory stays bounded however large the input.
+        error_context_chars : int, default 100
+            Characters of an invalid row to quote in its parse error, around
+            where parsing failed.
+        error_dump_dir : str, optional
+            Directory to write invalid rows to in full, with each file's path
+            given in the parse error, for rows too long to quote.
+        sample : int | tuple[int, int], optional
+            Infer from a sample of the rows (NDJSON lines): an int ``n`` takes the
+            first ``n``, a tuple ``(n, seed)`` takes ``n`` chosen at random with ``seed``.
+        sample_every : int, optional
+            Infer from every ``k``th row only. Cannot be combined with ``sample``.
+        cache : bool, default False
+            Look the inferred schema up by a hash of the column's rows and these
+            settings, and keep it for later calls on the same data, which then skip
+            inference. Needs the ``schema-cache`` build feature. Clear it with
+            :func:`clear_schema_cache`.
+        cache_dir : str, optional
+            Directory to also keep cached schemas in, so they outlast the session.
+            Implies ``cache``.
+        with_schema : bool, default False
+            Also return the Avro schema the rows were normalised against, as a dict.
+            The schema comes from the same inference pass as the normalised rows, so
+            with ``decode=True`` the column is only scanned once.
 
         Returns:
         -------
-        pl.Series
+        pl.Series | pl.DataFrame | tuple[pl.Series | pl.DataFrame, dict]
             A Series of normalised JSON data. Each row is rewritten to match the
             same Avro schema, with consistent shape across the column.
             If ``unnest=True``, the Series is expanded into multiple columns
-            corresponding to schema fields.
+            corresponding to schema fields. With ``with_schema=True``, a tuple of
+            the result and the Avro schema.
         """
-        wrap_root_field = column if wrap_root is True else wrap_root
-        expr = normalise_json(
-            pl.col(column),
-            ignore_outer_array=ignore_outer_array,
-            ndjson=ndjson,
-            empty_as_null=empty_as_null,
-            coerce_strings=coerce_strings,
-            map_encoding=map_encoding,
-            profile=profile,
-            map_threshold=map_threshold,
-            map_max_required_keys=map_max_required_keys,
-            unify_maps=unify_maps,
-            force_field_types=force_field_types,
-            force_parent_field_types=force_parent_field_types,
-            force_scalar_promotion=(
+        wrap_root_field = _wrap_root_for(column, wrap_root)
+        options = {
+            "ignore_outer_array": ignore_outer_array,
+            "ndjson": ndjson,
+            "empty_as_null": empty_as_null,
+            "coerce_strings": coerce_strings,
+            "map_encoding": map_encoding,
+            "unknown_fields": unknown_fields,
+            "value_limits": value_limits,
+            "profile": profile,
+            "map_threshold": map_threshold,
+            "map_max_required_keys": map_max_required_keys,
+            "unify_maps": unify_maps,
+            "detect_tuples": detect_tuples,
+            "required_threshold": required_threshold,
+            "coerce_map_array": coerce_map_array,
+            "force_field_types": force_field_types,
+            "force_parent_field_types": force_parent_field_types,
+            "no_unify": no_unify,
+            "force_scalar_promotion": (
                 list(force_scalar_promotion) if force_scalar_promotion else []
             ),
-            wrap_scalars=wrap_scalars,
-            wrap_root=wrap_root_field,
-            no_root_map=no_root_map,
-            max_builders=max_builders,
-        )
+            "ignore_paths": ignore_paths,
+            "max_depth": max_depth,
+            "max_object_keys": max_object_keys,
+            "wrap_scalars": wrap_scalars,
+            "wrap_root": wrap_root_field,
+            "no_root_map": no_root_map,
+            "max_builders": max_builders,
+            "chunk_size": chunk_size,
+            "error_context_chars": error_context_chars,
+            "error_dump_dir": error_dump_dir,
+            "sample": sample,
+            "sample_every": sample_every,
+            "cache": cache,
+            "cache_dir": cache_dir,
+        }
+        expr = normalise_json(pl.col(column), **options)
+        frame = self._df
+        avro_schema = None
+        if with_schema:
+            # One Rust call infers the schema and normalises against it
+            avro_schema, normalised = _rust_normalise_json_with_schema(
+                self._df.get_column(column), _kwargs_json(dict(options))
+            )
+            name = expr.meta.output_name()
+            frame = normalised.alias(name).to_frame()
+            expr = pl.col(name)
         if decode:
             if map_encoding != "kv":
                 # Map type fields must be k:v encoded as infer_polars_schema assumes it
                 # This could be done, it would always make record fields, ...but why?
                 raise NotImplementedError("map_encoding must be kv to decode to Polars")
 
-            if decode is True:
+            if decode is True and avro_schema is not None:
+                # Reuse the schema the rows were normalised against
+                dtype = pl.Struct(avro_to_polars_schema(avro_schema))
+            elif decode is True:
                 # Infer Avro schema and convert it to Polars Schema
                 schema = self.infer_polars_schema(
                     column,
@@ -1193,29 +2375,267 @@ class GensonNamespace:
                     map_threshold=map_threshold,
                     map_max_required_keys=map_max_required_keys,
                     unify_maps=unify_maps,
+                    detect_tuples=detect_tuples,
+                    required_threshold=required_threshold,
+                    coerce_map_array=coerce_map_array,
                     force_field_types=force_field_types,
                     force_parent_field_types=force_parent_field_types,
+                    no_unify=no_unify,
                     force_scalar_promotion=(
                         list(force_scalar_promotion) if force_scalar_promotion else []
                     ),
+                    ignore_paths=ignore_paths,
+                    max_depth=max_depth,
+                    max_object_keys=max_object_keys,
                     wrap_scalars=wrap_scalars,
                     avro=True,
                     wrap_root=wrap_root_field,
                     no_root_map=no_root_map,
                     max_builders=max_builders,
+                    chunk_size=chunk_size,
+                    error_context_chars=error_context_chars,
+                    error_dump_dir=error_dump_dir,
+                    sample=sample,
+                    sample_every=sample_every,
+                    cache=cache,
+                    cache_dir=cache_dir,
                 )
                 dtype = pl.Struct(schema)
             else:
                 # decode was passed as a Polars Schema directly
                 dtype = decode
 
-            result = self._df.select(expr.str.json_decode(dtype=dtype))
-            if unnest:
+            result = frame.select(expr.str.json_decode(dtype=dtype))
+            if unnest and column_template is not None:
+                result = _rust_unnest_fields(result.to_series(), column_template)
+            elif unnest:
                 result = result.unnest(expr.meta.output_name())
         else:
-            result = self._df.select(expr).to_series()
+            if column_template is not None:
+                raise ValueError("column_template requires decode")
+            result = frame.select(expr).to_series()
+        if with_schema:
+            return result, orjson.loads(avro_schema)
         return result
 
+    def schema_conformity(
+        self,
+        column: str,
+        *,
+        schema: dict | str | None = None,
+        map_threshold: int = 20,
+        map_max_required_keys: int | None = None,
+        unify_maps: bool = False,
+        force_field_types: dict[str, str] | None = None,
+    ) -> pl.Series:
+        """Validate each row of a JSON column against a JSON Schema.
+
+        Parameters
+        ----------
+        column : str
+            Name of the column containing JSON strings.
+        schema : dict | str, optional
+            JSON Schema to validate against, as a dict or JSON text. If None, a
+            schema is inferred from the column first.
+        map_threshold : int, default 20
+            Maximum number of keys before an object is treated as a map when inferring.
+        map_max_required_keys : int, optional
+            Maximum number of required keys allowed for Map inference.
+        unify_maps : bool, default False
+            Enable unification of compatible record schemas into maps.
+        force_field_types : dict[str, str], optional
+            Override the inferred type for specific fields (``"map"`` or ``"record"``).
+
+        Returns:
+        -------
+        pl.Series
+            A struct Series named ``"conformity"`` with a boolean ``valid`` and a
+            list of ``violations`` paths per row.
+        """
+        return self._df.select(
+            schema_conformity(
+                pl.col(column),
+                schema=schema,
+                map_threshold=map_threshold,
+                map_max_required_keys=map_max_required_keys,
+                unify_maps=unify_maps,
+                force_field_types=force_field_types,
+            )
+        ).to_series()
+
+    def schema_compatible(
+        self,
+        old: str,
+        new: str,
+        *,
+        mode: Literal["backward", "forward", "full"] = "backward",
+    ) -> pl.Series:
+        """Check that the schemas in two columns can read each other's data.
+
+        Parameters
+        ----------
+        old : str
+            Name of the column containing the old schemas.
+        new : str
+            Name of the column containing the new schemas.
+        mode : {"backward", "forward", "full"}, default "backward"
+            Which way data must be readable (see ``schema_compatible``).
+
+        Returns:
+        -------
+        pl.Series
+            A struct Series named ``"compatibility"`` with a boolean ``compatible``
+            and a list of ``issues`` per row.
+        """
+        return self._df.select(
+            schema_compatible(pl.col(old), pl.col(new), mode=mode)
+        ).to_series()
+
+    def unnest_json(
+        self,
+        column: str,
+        schema: dict | str,
+        *,
+        map_encoding: Literal["entries", "mapping", "kv"] = "kv",
+        unnest: bool = True,
+        column_template: str | None = None,
+    ) -> pl.Series | pl.DataFrame:
+        """Decode a column of normalised JSON into Polars types by its Avro schema.
+
+        Unlike decoding the JSON and calling ``struct.unnest`` yourself, map fields
+        come out as lists of ``{key, value}`` structs, not a struct field per key.
+
+        Parameters
+        ----------
+        column : str
+            Name of the column containing normalised JSON strings.
+        schema : dict | str
+            Avro schema the rows were normalised against, as a dict or JSON text.
+        map_encoding : {"mapping", "entries", "kv"}, default "kv"
+            Map encoding the rows were normalised with.
+        unnest : bool, default True
+            If True, expand the decoded struct into a column per schema field. If
+            False, return a single Series of structs.
+        column_template : str, optional
+            Only applies if `unnest=True`. Name the unnested columns with this
+            template, where ``{column}`` is the input column's name and ``{field}``
+            the field's, e.g. ``"{column}__{field}"``.
+
+        Returns:
+        -------
+        pl.DataFrame | pl.Series
+            A DataFrame with a column per field, or a struct Series if
+            ``unnest=False``.
+        """
+        decoded = self._df.select(
+            unnest_json(pl.col(column), schema, map_encoding=map_encoding)
+        ).to_series()
+        if not unnest:
+            return decoded
+        if column_template is not None:
+            return _rust_unnest_fields(decoded, column_template)
+        return decoded.struct.unnest()
+
+    def write_normalised(
+        self,
+        path: str | Path,
+        *,
+        column: str | None = None,
+        format: Literal["parquet", "ndjson"] = "parquet",
+        output_column: str | None = None,
+        ignore_outer_array: bool = True,
+        ndjson: bool = False,
+        empty_as_null: bool = True,
+        coerce_strings: bool = False,
+        map_encoding: Literal["entries", "mapping", "kv"] = "kv",
+        debug: bool = False,
+        profile: bool = False,
+        map_threshold: int = 20,
+        map_max_required_keys: int | None = None,
+        unify_maps: bool = False,
+        no_unify: list[str] | set[str] | None = None,
+        force_field_types: dict[str, str] | None = None,
+        force_parent_field_types: dict[str, str] | None = None,
+        force_scalar_promotion: list[str] | set[str] | None = None,
+        ignore_paths: list[str] | None = None,
+        max_depth: int | None = None,
+        max_object_keys: int | None = None,
+        wrap_scalars: bool = True,
+        wrap_root: bool | str | dict[str, str] | None = None,
+        no_root_map: bool = True,
+        max_builders: int | None = None,
+        batch_size: int | None = None,
+    ) -> int:
+        """Normalise a JSON string column and write it straight to a file.
+
+        Unlike :meth:`normalise_json`, the normalised rows are never collected into a
+        DataFrame column: each row is normalised and handed to the file writer in Rust,
+        so only one write batch is held in memory at a time.
+
+        Parameters
+        ----------
+        path : str | Path
+            Path of the file to write.
+        column : str, optional
+            Name of the column containing JSON strings. May be omitted if the
+            DataFrame has a single column.
+        format : {"parquet", "ndjson"}, default "parquet"
+            Output file format:
+            - "parquet": a single string column of normalised JSON, with the Avro
+              schema and normalisation config stored in the file metadata (as
+              written by :func:`normalise_from_parquet`).
+            - "ndjson": one normalised JSON document per line.
+        output_column : str, optional
+            Name of the Parquet output column. Defaults to the input column name.
+        batch_size : int, optional
+            Rows per Parquet row group. Defaults to 8192.
+
+        The remaining parameters are as for :meth:`normalise_json`.
+
+        Returns:
+        -------
+        int
+            The number of rows written.
+        """
+        if column is None:
+            if self._df.width != 1:
+                raise ValueError(
+                    f"column must be given for a DataFrame with {self._df.width} columns"
+                )
+            column = self._df.columns[0]
+        wrap_root_field = _wrap_root_for(column, wrap_root)
+        return _rust_write_normalised(
+            self._df.get_column(column),
+            output_path=str(path),
+            format=format,
+            output_column=output_column,
+            ignore_outer_array=ignore_outer_array,
+            ndjson=ndjson,
+            empty_as_null=empty_as_null,
+            coerce_strings=coerce_strings,
+            map_encoding=map_encoding,
+            debug=debug,
+            profile=profile,
+            map_threshold=map_threshold,
+            map_max_required_keys=map_max_required_keys,
+            unify_maps=unify_maps,
+            no_unify=list(no_unify) if no_unify else None,
+            force_field_types=force_field_types,
+            force_parent_field_types=force_parent_field_types,
+            force_scalar_promotion=(
+                list(force_scalar_promotion) if force_scalar_promotion else []
+            ),
+            ignore_paths=ignore_paths,
+            max_depth=max_depth,
+            max_object_keys=max_object_keys,
+            wrap_scalars=wrap_scalars,
+            wrap_root=wrap_root_field,
+            no_root_map=no_root_map,
+            max_builders=max_builders,
+            batch_size=batch_size,
+        )
+
+
 
 def read_parquet_metadata(path: str | Path) -> dict[str, str]:
     """Read metadata from a Parquet file.
@@ -1248,13 +2668,36 @@ def avro_to_polars_schema(avro_schema_json: str, debug: bool = False) -> pl.Sche
     pl.Schema
         Polars schema representation
     """
-    # Get field name/type string pairs from Rust
-    fields = _rust_avro_to_polars_fields(avro_schema_json, debug)
+    return _rust_json_schema_to_polars(avro_schema_json, True, debug).schema
 
-    # Convert type strings to actual DataType objects
-    return pl.Schema(
-        {name: _parse_polars_dtype(dtype_str) for name, dtype_str in fields}
-    )
+
+def json_schema_to_polars_schema(
+    schema: str | dict, *, avro: bool = False, debug: bool = False
+) -> pl.Schema:
+    """Convert a JSON Schema to a Polars Schema of dtype objects.
+
+    Each top-level property becomes a column, with the dtype
+    ``str.json_decode`` needs to decode it: objects become ``pl.Struct``,
+    arrays ``pl.List``, string enums ``pl.Enum`` and maps lists of
+    ``{key, value}`` structs. Use ``pl.Struct(schema)`` for the whole document.
+
+    Parameters
+    ----------
+    schema : str | dict
+        The JSON Schema, as a JSON string or a dict
+    avro : bool, default False
+        Whether the schema is an Avro schema (a record) rather than JSON Schema
+    debug : bool, default False
+        Whether to print debug information
+
+    Returns:
+    -------
+    pl.Schema
+        Polars schema representation
+    """
+    if isinstance(schema, dict):
+        schema = orjson.dumps(schema).decode()
+    return _rust_json_schema_to_polars(schema, avro, debug).schema
 
 
 def _dtype_to_dict(dtype: pl.datatypes.DataType):
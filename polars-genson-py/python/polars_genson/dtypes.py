@@ -1,5 +1,6 @@
 """Dtype parsing from concise string format used to serialise across Rust to Python."""
 
+import json
 import re
 
 import polars as pl
@@ -49,6 +50,10 @@ def _parse_polars_dtype(dtype_str: str) -> pl.DataType:
     if dtype_str in simple_types:
         return simple_types[dtype_str]
 
+    # Handle Enum["a","b"] (the categories as a JSON array)
+    if dtype_str.startswith("Enum[") and dtype_str.endswith("]"):
+        return pl.Enum(json.loads(dtype_str[4:]))
+
     # Handle List[ItemType]
     if dtype_str.startswith("List[") and dtype_str.endswith("]"):
         inner_type_str = dtype_str[5:-1]  # Remove "List[" and "]"
@@ -106,9 +111,22 @@ def _split_struct_fields(fields_str: str) -> list[str]:
     current_field = ""
     bracket_depth = 0
     paren_depth = 0
+    in_string = False  # Enum categories are JSON strings
+    escaped = False
 
     for char in fields_str:
-        if char == "[":
+        if in_string:
+            if escaped:
+                escaped = False
+            elif char == "\\":
+                escaped = True
+            elif char == '"':
+                in_string = False
+            current_field += char
+            continue
+        if char == '"':
+            in_string = True
+        elif char == "[":
             bracket_depth += 1
         elif char == "]":
             bracket_depth -= 1
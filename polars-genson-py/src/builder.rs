@@ -0,0 +1,81 @@
+//! Schema inference over batches of a column, for LazyFrames.
+//!
+//! A `SchemaBuilder` holds a [`SchemaInferenceSession`], so each batch a query
+//! hands to `map_batches` (or a sink) is merged into the schema as it arrives,
+//! and the whole column never has to be collected at once.
+
+use crate::expressions::GensonKwargs;
+use crate::input::{json_rows, json_strs};
+use crate::scan::parse_kwargs;
+use genson_core::SchemaInferenceSession;
+use pyo3::exceptions::{PyRuntimeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3_polars::PySeries;
+use std::sync::{Mutex, MutexGuard};
+
+/// Accumulates the schema of batches of JSON strings.
+#[pyclass(frozen)]
+pub struct SchemaBuilder {
+    kwargs: GensonKwargs,
+    session: Mutex<SchemaInferenceSession>,
+}
+
+impl SchemaBuilder {
+    /// The session, still usable if a thread panicked while holding it.
+    fn session(&self) -> MutexGuard<'_, SchemaInferenceSession> {
+        self.session.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[pymethods]
+impl SchemaBuilder {
+    #[new]
+    fn new(kwargs_json: &str) -> PyResult<Self> {
+        let kwargs = parse_kwargs(kwargs_json)?;
+        let session = SchemaInferenceSession::new(kwargs.inference_config());
+        Ok(Self {
+            kwargs,
+            session: Mutex::new(session),
+        })
+    }
+
+    /// Add a batch of a column (strings, or structs/lists to serialise), returning
+    /// how many documents it held. Null and blank rows are skipped.
+    fn add_batch(&self, py: Python<'_>, batch: PySeries) -> PyResult<usize> {
+        let series = batch.0;
+        py.allow_threads(|| {
+            let rows = json_rows(&series).map_err(|e| PyValueError::new_err(e.to_string()))?;
+            let json_strings = json_strs(&rows);
+            self.session()
+                .add_strings(&json_strings)
+                .map_err(|e| PyRuntimeError::new_err(format!("Genson error: {}", e)))
+        })
+    }
+
+    /// Number of documents added so far.
+    #[getter]
+    fn processed_count(&self) -> usize {
+        self.session().processed_count()
+    }
+
+    /// The schema of every batch added so far, as JSON.
+    fn schema(&self) -> PyResult<String> {
+        if self.processed_count() == 0 {
+            return Err(PyValueError::new_err(
+                "No valid JSON strings found in column",
+            ));
+        }
+        let result = self
+            .session()
+            .result()
+            .map_err(|e| PyRuntimeError::new_err(format!("Genson error: {}", e)))?;
+        if self.kwargs.debug {
+            anstream::eprintln!(
+                "DEBUG: Built schema from {} documents",
+                result.processed_count
+            );
+        }
+        serde_json::to_string_pretty(&result.schema)
+            .map_err(|e| PyRuntimeError::new_err(format!("JSON serialization error: {}", e)))
+    }
+}
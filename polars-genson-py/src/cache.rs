@@ -0,0 +1,142 @@
+//! Inferred schemas cached by column content (the `schema-cache` feature).
+//!
+//! Interactive sessions often infer the same column again and again. With `cache`
+//! set, the schema is looked up by a hash of the column's JSON strings and the
+//! inference config before inferring, so repeated calls on unchanged data skip
+//! inference. Schemas are kept in memory, and in `cache_dir` as `<hash>.json`
+//! files when given, so they also survive the session.
+
+#[cfg(feature = "schema-cache")]
+use genson_core::infer_json_schema_from_strings;
+use genson_core::SchemaInferenceConfig;
+use pyo3::prelude::*;
+use serde_json::Value;
+use std::path::Path;
+
+/// Schemas kept in memory before the oldest is evicted.
+#[cfg(feature = "schema-cache")]
+pub const MAX_CACHED_SCHEMAS: usize = 64;
+
+#[cfg(feature = "schema-cache")]
+mod store {
+    use super::MAX_CACHED_SCHEMAS;
+    use serde_json::Value;
+    use std::collections::{HashMap, VecDeque};
+    use std::sync::{Mutex, MutexGuard};
+
+    #[derive(Default)]
+    pub struct Store {
+        schemas: HashMap<u64, Value>,
+        /// Keys, oldest first
+        order: VecDeque<u64>,
+    }
+
+    impl Store {
+        pub fn get(&self, key: u64) -> Option<Value> {
+            self.schemas.get(&key).cloned()
+        }
+
+        pub fn insert(&mut self, key: u64, schema: Value) {
+            if self.schemas.insert(key, schema).is_none() {
+                self.order.push_back(key);
+            }
+            while self.order.len() > MAX_CACHED_SCHEMAS {
+                if let Some(oldest) = self.order.pop_front() {
+                    self.schemas.remove(&oldest);
+                }
+            }
+        }
+
+        pub fn clear(&mut self) {
+            self.schemas.clear();
+            self.order.clear();
+        }
+    }
+
+    static STORE: Mutex<Option<Store>> = Mutex::new(None);
+
+    /// The in-memory store, still usable if a thread panicked while holding it.
+    pub fn lock() -> MutexGuard<'static, Option<Store>> {
+        STORE.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// The cache key of `json_strings` inferred with `config`.
+#[cfg(feature = "schema-cache")]
+fn cache_key<S: AsRef<str>>(json_strings: &[S], config: &SchemaInferenceConfig) -> u64 {
+    use std::hash::{DefaultHasher, Hash, Hasher};
+
+    let mut hasher = DefaultHasher::new();
+    // The config serialises in field order, so equal configs hash the same
+    serde_json::to_string(config)
+        .unwrap_or_default()
+        .hash(&mut hasher);
+    json_strings.len().hash(&mut hasher);
+    for s in json_strings {
+        // Hashing a str includes its length, so rows can't run into each other
+        s.as_ref().hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+/// The schema of `json_strings` inferred with `config`, from the cache if it was
+/// inferred before, and otherwise inferred and cached.
+///
+/// # Errors
+/// Returns error if inference fails, or a schema can't be written to `cache_dir`.
+#[cfg(feature = "schema-cache")]
+pub fn cached_schema<S: AsRef<str>>(
+    json_strings: &[S],
+    config: SchemaInferenceConfig,
+    cache_dir: Option<&Path>,
+) -> Result<Value, String> {
+    let key = cache_key(json_strings, &config);
+    if let Some(schema) = store::lock().as_ref().and_then(|store| store.get(key)) {
+        return Ok(schema);
+    }
+
+    let path = cache_dir.map(|dir| dir.join(format!("{:016x}.json", key)));
+    // A file that can't be read or parsed is inferred again and overwritten
+    let on_disk = path
+        .as_ref()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|text| serde_json::from_str::<Value>(&text).ok());
+    let schema = match on_disk {
+        Some(schema) => schema,
+        None => {
+            let schema = infer_json_schema_from_strings(json_strings, config)?.schema;
+            if let (Some(dir), Some(path)) = (cache_dir, &path) {
+                std::fs::create_dir_all(dir)
+                    .and_then(|_| std::fs::write(path, schema.to_string()))
+                    .map_err(|e| {
+                        format!("Failed to write schema cache {}: {}", path.display(), e)
+                    })?;
+            }
+            schema
+        }
+    };
+
+    store::lock()
+        .get_or_insert_with(Default::default)
+        .insert(key, schema.clone());
+    Ok(schema)
+}
+
+/// Without the `schema-cache` feature, asking for the cache is an error.
+#[cfg(not(feature = "schema-cache"))]
+pub fn cached_schema<S: AsRef<str>>(
+    _json_strings: &[S],
+    _config: SchemaInferenceConfig,
+    _cache_dir: Option<&Path>,
+) -> Result<Value, String> {
+    Err("Schema caching needs polars-genson built with the `schema-cache` feature".to_string())
+}
+
+/// Drop the schemas cached in memory (files in a `cache_dir` are left alone).
+#[pyfunction]
+pub fn clear_schema_cache() {
+    #[cfg(feature = "schema-cache")]
+    if let Some(store) = store::lock().as_mut() {
+        store.clear();
+    }
+}
@@ -0,0 +1,55 @@
+use polars::prelude::*;
+use pyo3::prelude::*;
+use pyo3_polars::types::PyDataFrame;
+use pyo3_polars::PySeries;
+use std::collections::HashSet;
+
+/// Render a column name from `template`, substituting `{column}` and `{field}`.
+fn render_name(template: &str, column: &str, field: &str) -> String {
+    template
+        .replace("{column}", column)
+        .replace("{field}", field)
+}
+
+/// Split a decoded struct column into one column per field, named by `template`.
+///
+/// `{column}` in the template is replaced with the struct column's name and
+/// `{field}` with the field name, so `"{column}__{field}"` turns the `en` field
+/// of a `labels` column into `labels__en`.
+#[pyfunction]
+#[pyo3(signature = (series, template))]
+pub fn unnest_fields(series: PySeries, template: String) -> PyResult<PyDataFrame> {
+    let series = series.0;
+    if !template.contains("{field}") {
+        return Err(pyo3::exceptions::PyValueError::new_err(format!(
+            "Invalid column template: {} (must contain {{field}})",
+            template
+        )));
+    }
+    let ca = series.struct_().map_err(|_| {
+        pyo3::exceptions::PyTypeError::new_err(format!(
+            "Expected a struct column to unnest, got {}",
+            series.dtype()
+        ))
+    })?;
+
+    let mut seen = HashSet::new();
+    let columns = ca
+        .fields_as_series()
+        .into_iter()
+        .map(|field| {
+            let name = render_name(&template, series.name(), field.name());
+            if !seen.insert(name.clone()) {
+                return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                    "Column template {} gives duplicate column name {}",
+                    template, name
+                )));
+            }
+            Ok(field.with_name(name.into()).into_column())
+        })
+        .collect::<PyResult<Vec<_>>>()?;
+
+    let df = DataFrame::new(columns)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    Ok(PyDataFrame(df))
+}
@@ -1,12 +1,30 @@
-use genson_core::normalise::{normalise_values, MapEncoding, NormaliseConfig};
-use genson_core::{infer_json_schema_from_strings, DebugVerbosity, SchemaInferenceConfig};
+use crate::cache::cached_schema;
+use crate::input::{json_rows, json_strs, parse_rows};
+use genson_core::compatibility::{check_compatibility, CompatibilityMode};
+use genson_core::conformity;
+use genson_core::normalise::{
+    reencode_maps, try_normalise_values, MapEncoding, NormaliseConfig, UnknownFieldPolicy,
+    ValueLimits,
+};
+use genson_core::{
+    infer_json_schema_from_strings, DebugVerbosity, FieldOrder, JsonSchemaDraft, SampleSpec,
+    SchemaInferenceConfig, UnionOrder, DEFAULT_ERROR_CONTEXT_CHARS, DEFAULT_ROOT_VALUE_FIELD,
+};
 use polars::prelude::*;
-use polars_jsonschema_bridge::deserialise::{schema_to_polars_fields, SchemaFormat};
+use polars_jsonschema_bridge::deserialise::{
+    schema_to_polars_fields_with_nullability, PolarsField, SchemaFormat,
+};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
 use pyo3_polars::derive::polars_expr;
+use pyo3_polars::PySeries;
 use serde::Deserialize;
 use std::panic;
 use std::slice::from_ref;
 
+/// Rows parsed and normalised together, bounding the parsed values held at once.
+const NORMALISE_BATCH_ROWS: usize = 4096;
+
 // Mimalloc purge function - forces allocator to return memory to OS
 extern "C" {
     fn mi_collect(force: bool);
@@ -31,12 +49,32 @@ pub struct GensonKwargs {
     #[serde(default)]
     pub schema_uri: Option<String>,
 
+    /// JSON Schema draft to write the schema for
+    #[serde(default)]
+    pub json_schema_draft: Option<JsonSchemaDraft>,
+
+    /// Order of the properties in the output schema
+    #[serde(default)]
+    pub sort_fields: FieldOrder,
+
+    /// Type given to fields that were null in every row
+    #[serde(default)]
+    pub null_fallback: Option<String>,
+
     #[serde(default)]
     pub debug: bool,
 
     #[serde(default)]
     pub profile: bool,
 
+    /// Return stage timings alongside the merged schema
+    #[serde(default)]
+    pub profile_json: bool,
+
+    /// Record the non-default settings in the schema under `x-genson-config`
+    #[serde(default)]
+    pub embed_config: bool,
+
     #[serde(default)]
     pub verbosity: DebugVerbosity,
 
@@ -57,6 +95,18 @@ pub struct GensonKwargs {
     #[serde(default)]
     pub unify_maps: bool,
 
+    /// Keep a schema per position for fixed-length arrays
+    #[serde(default)]
+    pub detect_tuples: bool,
+
+    /// Fraction of objects that must have a field for it to stay required
+    #[serde(default)]
+    pub required_threshold: Option<f64>,
+
+    /// Collapse map-of-arrays/array-of-maps unions to the map-of-arrays form
+    #[serde(default)]
+    pub coerce_map_array: bool,
+
     #[serde(default)]
     pub no_unify: Vec<String>,
 
@@ -69,6 +119,18 @@ pub struct GensonKwargs {
     #[serde(default)]
     pub force_scalar_promotion: Vec<String>,
 
+    /// Subtrees to treat as opaque strings during inference
+    #[serde(default)]
+    pub ignore_paths: Vec<String>,
+
+    /// Nesting depth beyond which subtrees are treated as opaque strings
+    #[serde(default)]
+    pub max_depth: Option<usize>,
+
+    /// Key count above which objects are treated as opaque strings
+    #[serde(default)]
+    pub max_object_keys: Option<usize>,
+
     #[serde(default = "default_wrap_scalars")]
     pub wrap_scalars: bool,
 
@@ -86,12 +148,21 @@ pub struct GensonKwargs {
     #[serde(default = "default_map_encoding")]
     pub map_encoding: MapEncoding,
 
+    /// What to do with input fields the schema doesn't declare (default: drop)
+    #[serde(default)]
+    pub unknown_fields: UnknownFieldPolicy,
+
+    /// Caps on normalised string length, array length and depth (default: none)
+    #[serde(default)]
+    pub value_limits: ValueLimits,
+
     /// Wrap the root object under a single field.
     ///
     /// - If set to `Some("field")`, all input JSON objects are wrapped inside
-    ///   an object with key `"field"`.
+    ///   an object with key `"field"` (or nested objects for a dotted path).
+    /// - A mapping gives each input column its own root, by column name.
     #[serde(default)]
-    pub wrap_root: Option<String>,
+    pub wrap_root: Option<WrapRoot>,
 
     #[serde(default = "default_no_root_map")]
     pub no_root_map: bool,
@@ -101,6 +172,181 @@ pub struct GensonKwargs {
     /// If None, processes all strings at once. Default is None.
     #[serde(default)]
     pub max_builders: Option<usize>,
+
+    /// Infer in batches of this many rows, merging between batches
+    #[serde(default)]
+    pub chunk_size: Option<usize>,
+
+    /// Characters of an invalid row to quote in its parse error
+    #[serde(default = "default_error_context_chars")]
+    pub error_context_chars: usize,
+
+    /// Directory to write invalid rows to in full
+    #[serde(default)]
+    pub error_dump_dir: Option<std::path::PathBuf>,
+
+    /// Infer from a sample of the rows
+    #[serde(default)]
+    pub sample: Option<SampleSpec>,
+
+    /// Look the inferred schema up by the column's content and config, and keep
+    /// it for later calls (needs the `schema-cache` feature)
+    #[serde(default)]
+    pub cache: bool,
+
+    /// Directory to also keep cached schemas in, across sessions (implies `cache`)
+    #[serde(default)]
+    pub cache_dir: Option<std::path::PathBuf>,
+
+    /// JSON Schema (as JSON text) for `schema_conformity` to validate against,
+    /// instead of one inferred from the column, or the Avro schema that rows given
+    /// to `unnest_json` were normalised against
+    #[serde(default)]
+    pub schema: Option<String>,
+
+    /// Name of the output column. Defaults to `schema` for inference,
+    /// `normalised` for normalisation, `conformity` for conformity checks and
+    /// `compatibility` for compatibility checks.
+    #[serde(default)]
+    pub output_name: Option<String>,
+
+    /// Which way `schema_compatible` checks the schemas can read each other's data
+    #[serde(default)]
+    pub compatibility_mode: CompatibilityMode,
+}
+
+/// Root to wrap rows under: one path for every column, or one per column.
+#[derive(Deserialize, Clone)]
+#[serde(untagged)]
+pub enum WrapRoot {
+    Path(String),
+    /// Paths by input column name; columns not listed are left unwrapped
+    PerColumn(std::collections::HashMap<String, String>),
+}
+
+impl GensonKwargs {
+    /// These kwargs for the input column `name`, picking its root when the roots are
+    /// given per column.
+    fn for_column(mut self, name: &str) -> Self {
+        if let Some(WrapRoot::PerColumn(roots)) = &self.wrap_root {
+            self.wrap_root = roots.get(name).cloned().map(WrapRoot::Path);
+        }
+        self
+    }
+
+    /// The single root path, `None` when unset or not yet resolved per column.
+    fn wrap_root_path(&self) -> Option<String> {
+        match &self.wrap_root {
+            Some(WrapRoot::Path(path)) => Some(path.clone()),
+            _ => None,
+        }
+    }
+
+    /// The schema inference config these kwargs resolve to.
+    pub fn inference_config(&self) -> SchemaInferenceConfig {
+        SchemaInferenceConfig {
+            ignore_outer_array: self.ignore_outer_array,
+            delimiter: if self.ndjson { Some(b'\n') } else { None },
+            schema_uri: self.schema_uri.clone(),
+            json_schema_draft: self.json_schema_draft,
+            sort_fields: self.sort_fields,
+            union_order: UnionOrder::Canonical,
+            select_paths: Vec::new(),
+            discriminators: std::collections::HashMap::new(),
+            nonfinite_policy: None,
+            repair: false,
+            null_fallback: self.null_fallback.clone(),
+            map_threshold: self.map_threshold,
+            map_max_required_keys: self.map_max_required_keys,
+            map_detection: Default::default(),
+            unify_maps: self.unify_maps,
+            label_maps: false,
+            simplify_labels: false,
+            detect_tuples: self.detect_tuples,
+            required_threshold: self.required_threshold,
+            coerce_map_array: self.coerce_map_array,
+            no_unify: self.no_unify.iter().cloned().collect(),
+            force_field_types: self.force_field_types.clone(),
+            force_parent_field_types: self.force_parent_field_types.clone(),
+            force_scalar_promotion: self.force_scalar_promotion.iter().cloned().collect(),
+            ignore_paths: self.ignore_paths.clone(),
+            annotations: std::collections::HashMap::new(),
+            max_depth: self.max_depth,
+            max_object_keys: self.max_object_keys,
+            empty_container_policy: None,
+            wrap_scalars: self.wrap_scalars,
+            avro: self.avro,
+            avro_namespace: None,
+            avro_root_name: None,
+            avro_docs: std::collections::HashMap::new(),
+            avro_field_names: Default::default(),
+            avro_defaults: true,
+            wrap_root: self.wrap_root_path(),
+            wrap_root_strict: false,
+            root_value_field: DEFAULT_ROOT_VALUE_FIELD.to_string(),
+            outer_scalar_policy: Default::default(),
+            key_fold: None,
+            detect_formats: None,
+            detect_numeric_keys: false,
+            sparse_array_detection: false,
+            seed_schema: None,
+            no_root_map: self.no_root_map,
+            max_builders: self.max_builders,
+            chunk_size: self.chunk_size,
+            sample: self.sample,
+            limits: Default::default(),
+            error_context_chars: self.error_context_chars,
+            error_dump_dir: self.error_dump_dir.clone(),
+            debug: self.debug,
+            profile: self.profile,
+            collect_profile: self.profile_json,
+            explain: false,
+            map_key_stats: None,
+            decision_log: false,
+            embed_config: self.embed_config,
+            strict: false,
+            verbosity: self.verbosity,
+        }
+    }
+
+    /// The normalisation config these kwargs resolve to.
+    pub fn normalise_config(&self) -> NormaliseConfig {
+        NormaliseConfig {
+            empty_as_null: self.empty_as_null,
+            coerce_string: self.coerce_string,
+            map_encoding: self.map_encoding,
+            wrap_root: self.wrap_root_path(),
+            root_value_field: DEFAULT_ROOT_VALUE_FIELD.to_string(),
+            simplify_labels: false,
+            coerce_map_array: self.coerce_map_array,
+            sparse_array_detection: false,
+            unknown_fields: self.unknown_fields,
+            threads: None,
+            key_fold: None,
+            value_limits: self.value_limits.clone(),
+            canonical_numeric_keys: false,
+            nonfinite_policy: None,
+            select_paths: Vec::new(),
+        }
+    }
+
+    /// Infer the schema of `json_strings` with `config`, through the schema cache
+    /// when it was asked for.
+    fn infer_schema(
+        &self,
+        json_strings: &[&str],
+        config: SchemaInferenceConfig,
+    ) -> Result<serde_json::Value, String> {
+        if self.cache || self.cache_dir.is_some() {
+            cached_schema(json_strings, config, self.cache_dir.as_deref())
+        } else {
+            infer_json_schema_from_strings(json_strings, config).map(|result| result.schema)
+        }
+    }
+
+    fn output_name(&self, default: &str) -> PlSmallStr {
+        self.output_name.as_deref().unwrap_or(default).into()
+    }
 }
 
 fn default_map_threshold() -> usize {
@@ -123,6 +369,10 @@ fn default_wrap_scalars() -> bool {
     true
 }
 
+fn default_error_context_chars() -> usize {
+    DEFAULT_ERROR_CONTEXT_CHARS
+}
+
 fn default_no_root_map() -> bool {
     true
 }
@@ -131,49 +381,119 @@ fn default_map_encoding() -> MapEncoding {
     MapEncoding::KeyValueEntries
 }
 
+/// Effective configuration for a set of expression kwargs, as a JSON object with
+/// `inference` and `normalisation` entries. Defaults are filled in the same way
+/// as when the kwargs are passed to an expression.
+#[pyfunction]
+pub fn effective_config(kwargs_json: String) -> PyResult<String> {
+    let kwargs: GensonKwargs = serde_json::from_str(&kwargs_json)
+        .map_err(|e| PyValueError::new_err(format!("Invalid kwargs: {}", e)))?;
+    let config = serde_json::json!({
+        "output_name": kwargs.output_name,
+        "inference": kwargs.inference_config(),
+        "normalisation": kwargs.normalise_config(),
+    });
+    serde_json::to_string(&config)
+        .map_err(|e| PyValueError::new_err(format!("JSON serialization error: {}", e)))
+}
+
 /// JSON Schema is a String
-fn infer_json_schema_output_type(_input_fields: &[Field]) -> PolarsResult<Field> {
-    Ok(Field::new("schema".into(), DataType::String))
+fn infer_json_schema_output_type(
+    _input_fields: &[Field],
+    kwargs: GensonKwargs,
+) -> PolarsResult<Field> {
+    Ok(Field::new(kwargs.output_name("schema"), DataType::String))
 }
 
 /// Polars schema is serialised to String
-fn infer_polars_schema_output_type(_input_fields: &[Field]) -> PolarsResult<Field> {
+fn infer_polars_schema_output_type(
+    _input_fields: &[Field],
+    kwargs: GensonKwargs,
+) -> PolarsResult<Field> {
     let schema_field_struct = DataType::Struct(vec![
         Field::new("name".into(), DataType::String),
         Field::new("dtype".into(), DataType::String),
+        Field::new("nullable".into(), DataType::Boolean),
     ]);
     Ok(Field::new(
-        "schema".into(),
+        kwargs.output_name("schema"),
         DataType::List(Box::new(schema_field_struct)),
     ))
 }
 
 /// Normalised JSON is still a JSON string
-fn normalise_json_output_type(_input_fields: &[Field]) -> PolarsResult<Field> {
-    Ok(Field::new("normalised".into(), DataType::String))
+fn normalise_json_output_type(
+    _input_fields: &[Field],
+    kwargs: GensonKwargs,
+) -> PolarsResult<Field> {
+    Ok(Field::new(
+        kwargs.output_name("normalised"),
+        DataType::String,
+    ))
+}
+
+/// Re-encoded JSON keeps the input column's name
+fn unnest_json_output_type(input_fields: &[Field], _kwargs: GensonKwargs) -> PolarsResult<Field> {
+    Ok(Field::new(input_fields[0].name().clone(), DataType::String))
+}
+
+/// Conformity is a struct of whether the row is valid and where it isn't
+fn schema_conformity_output_type(
+    _input_fields: &[Field],
+    kwargs: GensonKwargs,
+) -> PolarsResult<Field> {
+    Ok(Field::new(
+        kwargs.output_name("conformity"),
+        DataType::Struct(vec![
+            Field::new("valid".into(), DataType::Boolean),
+            Field::new(
+                "violations".into(),
+                DataType::List(Box::new(DataType::String)),
+            ),
+        ]),
+    ))
+}
+
+/// One compatibility issue: where, which direction failed, and why
+fn compatibility_issue_dtype() -> DataType {
+    DataType::Struct(vec![
+        Field::new("path".into(), DataType::String),
+        Field::new("direction".into(), DataType::String),
+        Field::new("message".into(), DataType::String),
+    ])
+}
+
+/// Compatibility is a struct of whether the schemas are compatible and the issues
+fn schema_compatible_output_type(
+    _input_fields: &[Field],
+    kwargs: GensonKwargs,
+) -> PolarsResult<Field> {
+    Ok(Field::new(
+        kwargs.output_name("compatibility"),
+        DataType::Struct(vec![
+            Field::new("compatible".into(), DataType::Boolean),
+            Field::new(
+                "issues".into(),
+                DataType::List(Box::new(compatibility_issue_dtype())),
+            ),
+        ]),
+    ))
 }
 
 /// Polars expression that infers JSON schema from string column
-#[polars_expr(output_type_func=infer_json_schema_output_type)]
+#[polars_expr(output_type_func_with_kwargs=infer_json_schema_output_type)]
 pub fn infer_json_schema(inputs: &[Series], kwargs: GensonKwargs) -> PolarsResult<Series> {
     if inputs.is_empty() {
         return Err(PolarsError::ComputeError("No input series provided".into()));
     }
 
     let series = &inputs[0];
+    let kwargs = kwargs.for_column(series.name());
 
-    // Ensure we have a string column
-    let string_chunked = series.str().map_err(|_| {
-        PolarsError::ComputeError("Expected a string column for JSON schema inference".into())
-    })?;
+    let rows = json_rows(series)?;
 
-    // Collect all non-null string values from ALL rows
-    let mut json_strings = Vec::new();
-    for s in string_chunked.iter().flatten() {
-        if !s.trim().is_empty() {
-            json_strings.push(s.to_string());
-        }
-    }
+    // Borrow all non-null string values from ALL rows
+    let json_strings = json_strs(&rows);
 
     if json_strings.is_empty() {
         return Err(PolarsError::ComputeError(
@@ -193,40 +513,33 @@ pub fn infer_json_schema(inputs: &[Series], kwargs: GensonKwargs) -> PolarsResul
         }
     }
 
-    let wrap_root_field = kwargs.wrap_root.clone();
-
     if kwargs.merge_schemas {
         // Original behavior: merge all schemas into one
         // We only need a single row, and we are allowed to change the length
         // Wrap EVERYTHING in panic catching, including config creation
+        let kwargs = &kwargs;
         let result = panic::catch_unwind(move || -> Result<String, String> {
-            let config = SchemaInferenceConfig {
-                ignore_outer_array: kwargs.ignore_outer_array,
-                delimiter: if kwargs.ndjson { Some(b'\n') } else { None },
-                schema_uri: kwargs.schema_uri.clone(),
-                map_threshold: kwargs.map_threshold,
-                map_max_required_keys: kwargs.map_max_required_keys,
-                unify_maps: kwargs.unify_maps,
-                no_unify: kwargs.no_unify.iter().cloned().collect(),
-                force_field_types: kwargs.force_field_types.clone(),
-                force_parent_field_types: kwargs.force_parent_field_types.clone(),
-                force_scalar_promotion: kwargs.force_scalar_promotion.iter().cloned().collect(),
-                wrap_scalars: kwargs.wrap_scalars,
-                avro: kwargs.avro,
-                wrap_root: wrap_root_field.clone(),
-                no_root_map: kwargs.no_root_map,
-                max_builders: kwargs.max_builders,
-                debug: kwargs.debug,
-                profile: kwargs.profile,
-                verbosity: kwargs.verbosity,
-            };
+            let config = kwargs.inference_config();
+
+            if kwargs.profile_json {
+                // Profiles time the inference itself, so they're never cached
+                let schema_result = infer_json_schema_from_strings(&json_strings, config)
+                    .map_err(|e| format!("Genson error: {}", e))?;
+                let output = serde_json::json!({
+                    "schema": schema_result.schema,
+                    "profile": schema_result.profile,
+                });
+                return serde_json::to_string_pretty(&output)
+                    .map_err(|e| format!("JSON serialization error: {}", e));
+            }
 
-            let schema_result = infer_json_schema_from_strings(&json_strings, config)
+            let schema = kwargs
+                .infer_schema(&json_strings, config)
                 .map_err(|e| format!("Genson error: {}", e))?;
 
             drop(json_strings);
 
-            serde_json::to_string_pretty(&schema_result.schema)
+            serde_json::to_string_pretty(&schema)
                 .map_err(|e| format!("JSON serialization error: {}", e))
         });
 
@@ -235,7 +548,10 @@ pub fn infer_json_schema(inputs: &[Series], kwargs: GensonKwargs) -> PolarsResul
                 if kwargs.debug {
                     anstream::eprintln!("DEBUG: Successfully generated merged schema");
                 }
-                Ok(Series::new("schema".into(), vec![schema_json; 1]))
+                Ok(Series::new(
+                    kwargs.output_name("schema"),
+                    vec![schema_json; 1],
+                ))
             }
             Ok(Err(e)) => Err(PolarsError::ComputeError(
                 format!("Merged schema processing failed: {}", e).into(),
@@ -245,37 +561,28 @@ pub fn infer_json_schema(inputs: &[Series], kwargs: GensonKwargs) -> PolarsResul
             )),
         }
     } else {
-        // New behavior: infer schema for each row individually
-        let result = panic::catch_unwind(move || -> Result<Vec<serde_json::Value>, String> {
-            let mut individual_schemas = Vec::new();
-            for json_str in &json_strings {
-                let config = SchemaInferenceConfig {
-                    ignore_outer_array: kwargs.ignore_outer_array,
-                    delimiter: if kwargs.ndjson { Some(b'\n') } else { None },
-                    schema_uri: kwargs.schema_uri.clone(),
-                    map_threshold: kwargs.map_threshold,
-                    map_max_required_keys: kwargs.map_max_required_keys,
-                    unify_maps: kwargs.unify_maps,
-                    no_unify: kwargs.no_unify.iter().cloned().collect(),
-                    force_field_types: kwargs.force_field_types.clone(),
-                    force_parent_field_types: kwargs.force_parent_field_types.clone(),
-                    force_scalar_promotion: kwargs.force_scalar_promotion.iter().cloned().collect(),
-                    wrap_scalars: kwargs.wrap_scalars,
-                    avro: kwargs.avro,
-                    wrap_root: wrap_root_field.clone(),
-                    no_root_map: kwargs.no_root_map,
-                    max_builders: kwargs.max_builders,
-                    debug: kwargs.debug,
-                    profile: kwargs.profile,
-                    verbosity: kwargs.verbosity,
-                };
-
-                let single_result = infer_json_schema_from_strings(from_ref(json_str), config)
+        // Infer a schema for each row on its own, keeping the rows aligned with the
+        // input so schema variants can be grouped and compared. Null and blank rows
+        // get a null schema.
+        drop(json_strings);
+        let kwargs = &kwargs;
+        let rows = &rows;
+        let result = panic::catch_unwind(move || -> Result<Vec<Option<String>>, String> {
+            rows.iter()
+                .map(|row| {
+                    let Some(json_str) = row.as_deref().filter(|s| !s.trim().is_empty()) else {
+                        return Ok(None);
+                    };
+                    let single_result = infer_json_schema_from_strings(
+                        from_ref(&json_str),
+                        kwargs.inference_config(),
+                    )
                     .map_err(|e| format!("Individual genson error: {}", e))?;
-                individual_schemas.push(single_result.schema);
-            }
-            drop(json_strings);
-            Ok(individual_schemas)
+                    serde_json::to_string_pretty(&single_result.schema)
+                        .map(Some)
+                        .map_err(|e| format!("JSON serialization error: {}", e))
+                })
+                .collect()
         });
 
         match result {
@@ -283,21 +590,12 @@ pub fn infer_json_schema(inputs: &[Series], kwargs: GensonKwargs) -> PolarsResul
                 if kwargs.debug {
                     anstream::eprintln!(
                         "DEBUG: Generated {} individual schemas",
-                        individual_schemas.len()
+                        individual_schemas.iter().flatten().count()
                     );
                 }
-
-                // Return array of schemas as JSON
-                let schemas_json =
-                    serde_json::to_string_pretty(&individual_schemas).map_err(|e| {
-                        PolarsError::ComputeError(
-                            format!("Failed to serialize individual schemas: {}", e).into(),
-                        )
-                    })?;
-
                 Ok(Series::new(
-                    "schema".into(),
-                    vec![schemas_json; series.len()],
+                    kwargs.output_name("schema"),
+                    individual_schemas,
                 ))
             }
             Ok(Err(e)) => Err(PolarsError::ComputeError(
@@ -311,24 +609,18 @@ pub fn infer_json_schema(inputs: &[Series], kwargs: GensonKwargs) -> PolarsResul
 }
 
 /// Polars expression that infers Polars schema from string column
-#[polars_expr(output_type_func=infer_polars_schema_output_type)]
+#[polars_expr(output_type_func_with_kwargs=infer_polars_schema_output_type)]
 pub fn infer_polars_schema(inputs: &[Series], kwargs: GensonKwargs) -> PolarsResult<Series> {
     if inputs.is_empty() {
         return Err(PolarsError::ComputeError("No input series provided".into()));
     }
 
     let series = &inputs[0];
-    let string_chunked = series.str().map_err(|_| {
-        PolarsError::ComputeError("Expected a string column for Polars schema inference".into())
-    })?;
-
-    // Collect all non-null string values from ALL rows
-    let mut json_strings = Vec::new();
-    for s in string_chunked.iter().flatten() {
-        if !s.trim().is_empty() {
-            json_strings.push(s.to_string());
-        }
-    }
+    let kwargs = kwargs.for_column(series.name());
+    let rows = json_rows(series)?;
+
+    // Borrow all non-null string values from ALL rows
+    let json_strings = json_strs(&rows);
 
     if json_strings.is_empty() {
         return Err(PolarsError::ComputeError(
@@ -336,32 +628,13 @@ pub fn infer_polars_schema(inputs: &[Series], kwargs: GensonKwargs) -> PolarsRes
         ));
     }
 
-    let wrap_root_field = kwargs.wrap_root.clone();
-
     // Use genson to infer JSON schema, then convert to Polars schema fields
-    let result = panic::catch_unwind(move || -> Result<Vec<(String, String)>, String> {
-        let config = SchemaInferenceConfig {
-            ignore_outer_array: kwargs.ignore_outer_array,
-            delimiter: if kwargs.ndjson { Some(b'\n') } else { None },
-            schema_uri: kwargs.schema_uri.clone(),
-            map_threshold: kwargs.map_threshold,
-            map_max_required_keys: kwargs.map_max_required_keys,
-            unify_maps: kwargs.unify_maps,
-            no_unify: kwargs.no_unify.iter().cloned().collect(),
-            force_field_types: kwargs.force_field_types.clone(),
-            force_parent_field_types: kwargs.force_parent_field_types.clone(),
-            force_scalar_promotion: kwargs.force_scalar_promotion.iter().cloned().collect(),
-            wrap_scalars: kwargs.wrap_scalars,
-            avro: kwargs.avro,
-            wrap_root: wrap_root_field,
-            no_root_map: kwargs.no_root_map,
-            max_builders: kwargs.max_builders,
-            debug: kwargs.debug,
-            profile: kwargs.profile,
-            verbosity: kwargs.verbosity,
-        };
+    let kwargs = &kwargs;
+    let result = panic::catch_unwind(move || -> Result<Vec<PolarsField>, String> {
+        let config = kwargs.inference_config();
 
-        let schema_result = infer_json_schema_from_strings(&json_strings, config)
+        let schema = kwargs
+            .infer_schema(&json_strings, config)
             .map_err(|e| format!("Genson error: {}", e))?;
 
         drop(json_strings);
@@ -373,29 +646,27 @@ pub fn infer_polars_schema(inputs: &[Series], kwargs: GensonKwargs) -> PolarsRes
         };
 
         // Convert JSON schema to Polars field mappings
-        let polars_fields = schema_to_polars_fields(&schema_result.schema, format, kwargs.debug)
+        let polars_fields = schema_to_polars_fields_with_nullability(&schema, format, kwargs.debug)
             .map_err(|e| e.to_string())?;
         Ok(polars_fields)
     });
 
     match result {
         Ok(Ok(polars_fields)) => {
-            // Convert field mappings to name/dtype series
-            let field_names: Vec<String> =
-                polars_fields.iter().map(|(name, _)| name.clone()).collect();
-            let field_dtypes: Vec<String> = polars_fields
-                .iter()
-                .map(|(_, dtype)| dtype.clone())
-                .collect();
+            // Convert fields to name/dtype/nullable series
+            let field_names: Vec<&str> = polars_fields.iter().map(|f| f.name.as_str()).collect();
+            let field_dtypes: Vec<&str> = polars_fields.iter().map(|f| f.dtype.as_str()).collect();
+            let field_nullable: Vec<bool> = polars_fields.iter().map(|f| f.nullable).collect();
 
             let names = Series::new("name".into(), field_names);
             let dtypes = Series::new("dtype".into(), field_dtypes);
+            let nullable = Series::new("nullable".into(), field_nullable);
 
             // Create struct series
             let struct_series = StructChunked::from_series(
                 "schema_field".into(),
                 names.len(),
-                [&names, &dtypes].iter().cloned(),
+                [&names, &dtypes, &nullable].iter().cloned(),
             )?
             .into_series();
 
@@ -403,7 +674,7 @@ pub fn infer_polars_schema(inputs: &[Series], kwargs: GensonKwargs) -> PolarsRes
             let list_values: Vec<Series> =
                 (0..series.len()).map(|_| struct_series.clone()).collect();
 
-            let list_series = Series::new("schema".into(), list_values);
+            let list_series = Series::new(kwargs.output_name("schema"), list_values);
             Ok(list_series)
         }
         Ok(Err(e)) => Err(PolarsError::ComputeError(
@@ -463,79 +734,256 @@ pub fn infer_polars_schema(inputs: &[Series], kwargs: GensonKwargs) -> PolarsRes
 ///   {"id": "1", "labels": null}
 ///   {"id": "2", "labels": {"en": "Hello"}}
 /// ```
-#[polars_expr(output_type_func=normalise_json_output_type)]
+#[polars_expr(output_type_func_with_kwargs=normalise_json_output_type)]
 pub fn normalise_json(inputs: &[Series], kwargs: GensonKwargs) -> PolarsResult<Series> {
     if inputs.is_empty() {
         return Err(PolarsError::ComputeError("No input series provided".into()));
     }
 
-    let series = &inputs[0];
-    let string_chunked = series.str().map_err(|_| {
-        PolarsError::ComputeError("Expected string column for JSON normalisation".into())
-    })?;
-
-    let out = {
-        // Collect all JSON strings
-        let mut json_strings = Vec::new();
-        for s in string_chunked.iter().flatten() {
-            if !s.trim().is_empty() {
-                json_strings.push(s.to_string());
-            }
-        }
+    let (_, normalised) = normalise_series(&inputs[0], kwargs)?;
+    Ok(normalised)
+}
+
+/// Infer the Avro schema of a JSON column and normalise every row against it,
+/// returning the schema along with the normalised rows.
+fn normalise_series(
+    series: &Series,
+    kwargs: GensonKwargs,
+) -> PolarsResult<(serde_json::Value, Series)> {
+    let kwargs = kwargs.for_column(series.name());
+    let rows = json_rows(series)?;
 
-        let wrap_root_field = kwargs.wrap_root.clone();
+    let (schema, out) = {
+        // Borrow all JSON strings
+        let json_strings = json_strs(&rows);
 
         // Infer schema ONCE
         let config = SchemaInferenceConfig {
-            ignore_outer_array: kwargs.ignore_outer_array,
-            delimiter: if kwargs.ndjson { Some(b'\n') } else { None },
-            schema_uri: kwargs.schema_uri.clone(),
-            map_threshold: kwargs.map_threshold,
-            map_max_required_keys: kwargs.map_max_required_keys,
-            unify_maps: kwargs.unify_maps,
-            no_unify: kwargs.no_unify.iter().cloned().collect(),
-            force_field_types: kwargs.force_field_types.clone(),
-            force_parent_field_types: kwargs.force_parent_field_types.clone(),
-            force_scalar_promotion: kwargs.force_scalar_promotion.iter().cloned().collect(),
-            wrap_scalars: kwargs.wrap_scalars,
             avro: true, // normalisation implies Avro
-            wrap_root: wrap_root_field.clone(),
-            no_root_map: kwargs.no_root_map,
-            max_builders: kwargs.max_builders,
-            debug: kwargs.debug,
-            profile: kwargs.profile,
-            verbosity: kwargs.verbosity,
+            ..kwargs.inference_config()
         };
 
-        let schema_result = infer_json_schema_from_strings(&json_strings, config).map_err(|e| {
+        let schema = kwargs.infer_schema(&json_strings, config).map_err(|e| {
             PolarsError::ComputeError(format!("Schema inference failed: {e}").into())
         })?;
 
         drop(json_strings);
 
-        let schema = &schema_result.schema;
-
         // Parse each row and normalise
-        let cfg = NormaliseConfig {
-            empty_as_null: kwargs.empty_as_null,
-            coerce_string: kwargs.coerce_string,
-            map_encoding: kwargs.map_encoding,
-            wrap_root: wrap_root_field.clone(),
-        };
+        let cfg = kwargs.normalise_config();
+
+        // Rows are normalised a batch at a time, in parallel within each batch
+        let mut out = Vec::with_capacity(rows.len());
+        for (b, batch) in rows.chunks(NORMALISE_BATCH_ROWS).enumerate() {
+            let normed = try_normalise_values(parse_rows(batch), &schema, &cfg).map_err(|_| {
+                // Rerun the failed batch row by row to say which row it was
+                let offset = b * NORMALISE_BATCH_ROWS;
+                let (i, e) = parse_rows(batch)
+                    .into_iter()
+                    .enumerate()
+                    .find_map(|(i, val)| {
+                        try_normalise_values(vec![val], &schema, &cfg)
+                            .err()
+                            .map(|e| (i, e))
+                    })
+                    .expect("a row of the batch failed");
+                PolarsError::ComputeError(format!("Row {}: {}", offset + i + 1, e).into())
+            })?;
+            out.extend(normed.iter().map(|v| serde_json::to_string(v).unwrap()));
+        }
+        (schema, out)
+    };
 
-        let mut out = Vec::with_capacity(string_chunked.len());
-        for s in string_chunked {
-            let val = s
-                .and_then(|st| serde_json::from_str::<serde_json::Value>(st).ok())
-                .unwrap_or(serde_json::Value::Null);
+    force_memory_release();
 
-            let normed = normalise_values(vec![val], schema, &cfg).pop().unwrap();
-            out.push(serde_json::to_string(&normed).unwrap());
+    Ok((schema, Series::new(kwargs.output_name("normalised"), out)))
+}
+
+/// Normalise a JSON column like the `normalise_json` expression, also returning
+/// the Avro schema (as JSON) the rows were normalised against, so callers don't
+/// need to infer it a second time.
+#[pyfunction]
+pub fn normalise_json_with_schema(
+    series: PySeries,
+    kwargs_json: String,
+) -> PyResult<(String, PySeries)> {
+    let kwargs: GensonKwargs = serde_json::from_str(&kwargs_json)
+        .map_err(|e| PyValueError::new_err(format!("Invalid kwargs: {}", e)))?;
+    let (schema, normalised) =
+        normalise_series(&series.0, kwargs).map_err(|e| PyValueError::new_err(e.to_string()))?;
+    let schema = serde_json::to_string(&schema)
+        .map_err(|e| PyValueError::new_err(format!("JSON serialization error: {}", e)))?;
+    Ok((schema, PySeries(normalised)))
+}
+
+/// Validate each row of a JSON column against a JSON Schema.
+///
+/// The schema is the `schema` kwarg if given, otherwise one inferred from the whole
+/// column. Each row gets a struct of `valid` and the `violations` paths from
+/// [`genson_core::conformity::violations`]; rows that aren't valid JSON fail at the
+/// root, and null rows stay null.
+#[polars_expr(output_type_func_with_kwargs=schema_conformity_output_type)]
+pub fn schema_conformity(inputs: &[Series], kwargs: GensonKwargs) -> PolarsResult<Series> {
+    if inputs.is_empty() {
+        return Err(PolarsError::ComputeError("No input series provided".into()));
+    }
+
+    let series = &inputs[0];
+    let kwargs = kwargs.for_column(series.name());
+    let rows = json_rows(series)?;
+
+    let schema: serde_json::Value = match &kwargs.schema {
+        Some(schema_json) => serde_json::from_str(schema_json)
+            .map_err(|e| PolarsError::ComputeError(format!("Invalid schema JSON: {}", e).into()))?,
+        None => {
+            let json_strings = json_strs(&rows);
+            let config = SchemaInferenceConfig {
+                avro: false, // validation is against JSON Schema
+                ..kwargs.inference_config()
+            };
+            infer_json_schema_from_strings(&json_strings, config)
+                .map_err(|e| {
+                    PolarsError::ComputeError(format!("Schema inference failed: {e}").into())
+                })?
+                .schema
         }
-        out
     };
 
-    force_memory_release();
+    let mut valid = Vec::with_capacity(rows.len());
+    let mut violations = Vec::with_capacity(rows.len());
+    for row in &rows {
+        let paths = row
+            .as_deref()
+            .map(|s| match serde_json::from_str::<serde_json::Value>(s) {
+                Ok(value) => conformity::violations(&value, &schema),
+                Err(_) => vec![conformity::ROOT_PATH.to_string()],
+            });
+        valid.push(paths.as_ref().map(Vec::is_empty));
+        violations.push(paths.map(|p| Series::new(PlSmallStr::EMPTY, p)));
+    }
+
+    let valid = Series::new("valid".into(), valid);
+    // An all-null column would otherwise collect as List(Null)
+    let violations = violations
+        .into_iter()
+        .collect::<ListChunked>()
+        .with_name("violations".into())
+        .into_series()
+        .cast(&DataType::List(Box::new(DataType::String)))?;
+    Ok(StructChunked::from_series(
+        kwargs.output_name("conformity"),
+        series.len(),
+        [&valid, &violations].iter().cloned(),
+    )?
+    .into_series())
+}
 
-    Ok(Series::new("normalised".into(), out))
+/// Polars expression checking that the schemas in one string column (the old
+/// schemas) and another (the new ones) can read each other's data, row by row,
+/// with [`genson_core::compatibility::check_compatibility`]. Each row gets a struct
+/// of `compatible` and the `issues` found; rows with a null schema stay null.
+#[polars_expr(output_type_func_with_kwargs=schema_compatible_output_type)]
+pub fn schema_compatible(inputs: &[Series], kwargs: GensonKwargs) -> PolarsResult<Series> {
+    if inputs.len() != 2 {
+        return Err(PolarsError::ComputeError(
+            "Expected an old and a new schema column".into(),
+        ));
+    }
+    let old = inputs[0].str()?;
+    let new = inputs[1].str()?;
+    let parse = |text: &str| {
+        serde_json::from_str::<serde_json::Value>(text)
+            .map_err(|e| PolarsError::ComputeError(format!("Invalid schema JSON: {}", e).into()))
+    };
+
+    let mut compatible = Vec::with_capacity(old.len());
+    let mut issues = Vec::with_capacity(old.len());
+    for (old, new) in old.into_iter().zip(new) {
+        let (Some(old), Some(new)) = (old, new) else {
+            compatible.push(None);
+            issues.push(None);
+            continue;
+        };
+        let found = check_compatibility(&parse(old)?, &parse(new)?, kwargs.compatibility_mode)
+            .map_err(|e| PolarsError::ComputeError(e.into()))?;
+        let direction = |mode: CompatibilityMode| match mode {
+            CompatibilityMode::Backward => "backward",
+            CompatibilityMode::Forward => "forward",
+            CompatibilityMode::Full => "full",
+        };
+        let paths: Vec<&str> = found.iter().map(|i| i.path.as_str()).collect();
+        let directions: Vec<&str> = found.iter().map(|i| direction(i.direction)).collect();
+        let messages: Vec<&str> = found.iter().map(|i| i.message.as_str()).collect();
+        let row = StructChunked::from_series(
+            PlSmallStr::EMPTY,
+            found.len(),
+            [
+                Series::new("path".into(), paths),
+                Series::new("direction".into(), directions),
+                Series::new("message".into(), messages),
+            ]
+            .iter(),
+        )?;
+        compatible.push(Some(found.is_empty()));
+        issues.push(Some(row.into_series()));
+    }
+
+    let compatible = Series::new("compatible".into(), compatible);
+    // An all-null column would otherwise collect as List(Null)
+    let issues = issues
+        .into_iter()
+        .collect::<ListChunked>()
+        .with_name("issues".into())
+        .into_series()
+        .cast(&DataType::List(Box::new(compatibility_issue_dtype())))?;
+    Ok(StructChunked::from_series(
+        kwargs.output_name("compatibility"),
+        inputs[0].len(),
+        [&compatible, &issues].iter().cloned(),
+    )?
+    .into_series())
+}
+
+/// Re-encode the maps in normalised JSON rows as `{key, value}` lists, the form
+/// Polars decodes a map type from, whichever `map_encoding` they were normalised
+/// with. Records keep their fields, so decoding gives structs for records and
+/// lists of key/value structs for maps.
+#[polars_expr(output_type_func_with_kwargs=unnest_json_output_type)]
+pub fn unnest_json(inputs: &[Series], kwargs: GensonKwargs) -> PolarsResult<Series> {
+    if inputs.is_empty() {
+        return Err(PolarsError::ComputeError("No input series provided".into()));
+    }
+
+    let series = &inputs[0];
+    let schema: serde_json::Value = match &kwargs.schema {
+        Some(schema_json) => serde_json::from_str(schema_json)
+            .map_err(|e| PolarsError::ComputeError(format!("Invalid schema JSON: {}", e).into()))?,
+        None => {
+            return Err(PolarsError::ComputeError(
+                "unnest_json needs the Avro schema the rows were normalised against".into(),
+            ))
+        }
+    };
+
+    let rows = json_rows(series)?;
+    let mut out = Vec::with_capacity(rows.len());
+    for (i, row) in rows.iter().enumerate() {
+        let reencoded = match row.as_deref() {
+            Some(s) => {
+                let value = serde_json::from_str::<serde_json::Value>(s).map_err(|e| {
+                    PolarsError::ComputeError(format!("Row {}: invalid JSON: {}", i + 1, e).into())
+                })?;
+                let value = reencode_maps(
+                    value,
+                    &schema,
+                    kwargs.map_encoding,
+                    MapEncoding::KeyValueEntries,
+                );
+                Some(serde_json::to_string(&value).unwrap())
+            }
+            None => None,
+        };
+        out.push(reencoded);
+    }
+    Ok(Series::new(series.name().clone(), out))
 }
@@ -0,0 +1,137 @@
+//! Plugin input columns as JSON rows.
+//!
+//! String columns hold JSON text already. Struct, List and Array columns (JSON that
+//! was decoded earlier) are serialised back to JSON row by row, so they go through
+//! the same inference and normalisation as text.
+
+use polars::prelude::*;
+use serde_json::{Map, Number, Value};
+use std::borrow::Cow;
+
+/// The JSON text of each row of `series`, `None` for null rows.
+pub fn json_rows(series: &Series) -> PolarsResult<Vec<Option<Cow<'_, str>>>> {
+    if let Ok(ca) = series.str() {
+        return Ok(ca.iter().map(|s| s.map(Cow::Borrowed)).collect());
+    }
+    match series.dtype() {
+        DataType::Struct(_) | DataType::List(_) | DataType::Array(_, _) => {
+            let series = series.cast(&json_friendly_dtype(series.dtype()))?.rechunk();
+            series
+                .iter()
+                .map(|av| {
+                    if av.is_null() {
+                        return Ok(None);
+                    }
+                    serde_json::to_string(&any_value_to_json(&av))
+                        .map(|s| Some(Cow::Owned(s)))
+                        .map_err(|e| {
+                            PolarsError::ComputeError(
+                                format!("JSON serialization error: {}", e).into(),
+                            )
+                        })
+                })
+                .collect()
+        }
+        other => Err(PolarsError::ComputeError(
+            format!(
+                "Expected a String, Struct, List or Array column of JSON data, got {}",
+                other
+            )
+            .into(),
+        )),
+    }
+}
+
+/// The non-blank rows as string slices, for inference. For String columns these
+/// point into the column's Arrow buffers, so the JSON text is never copied.
+pub fn json_strs<'a>(rows: &'a [Option<Cow<'_, str>>]) -> Vec<&'a str> {
+    rows.iter()
+        .flatten()
+        .map(|s| s.as_ref())
+        .filter(|s| !s.trim().is_empty())
+        .collect()
+}
+
+/// Parse each row for normalisation; null, blank and invalid rows become `null`.
+pub fn parse_rows(rows: &[Option<Cow<'_, str>>]) -> Vec<Value> {
+    rows.iter()
+        .map(|s| {
+            s.as_deref()
+                .and_then(|st| serde_json::from_str::<Value>(st).ok())
+                .unwrap_or(Value::Null)
+        })
+        .collect()
+}
+
+/// The dtype to cast to before serialising: temporal, categorical and binary leaves
+/// become strings, decimals become floats.
+fn json_friendly_dtype(dtype: &DataType) -> DataType {
+    match dtype {
+        DataType::Struct(fields) => DataType::Struct(
+            fields
+                .iter()
+                .map(|f| Field::new(f.name().clone(), json_friendly_dtype(f.dtype())))
+                .collect(),
+        ),
+        DataType::List(inner) => DataType::List(Box::new(json_friendly_dtype(inner))),
+        DataType::Array(inner, width) => {
+            DataType::Array(Box::new(json_friendly_dtype(inner)), *width)
+        }
+        DataType::Decimal(_, _) => DataType::Float64,
+        DataType::Date
+        | DataType::Datetime(_, _)
+        | DataType::Duration(_)
+        | DataType::Time
+        | DataType::Categorical(_, _)
+        | DataType::Enum(_, _) => DataType::String,
+        other => other.clone(),
+    }
+}
+
+fn any_value_to_json(av: &AnyValue) -> Value {
+    match av {
+        AnyValue::Null => Value::Null,
+        AnyValue::Boolean(b) => Value::Bool(*b),
+        AnyValue::String(s) => Value::String(s.to_string()),
+        AnyValue::StringOwned(s) => Value::String(s.to_string()),
+        AnyValue::Binary(b) => Value::String(String::from_utf8_lossy(b).into_owned()),
+        AnyValue::BinaryOwned(b) => Value::String(String::from_utf8_lossy(b).into_owned()),
+        AnyValue::List(s) | AnyValue::Array(s, _) => {
+            let s = s.rechunk();
+            Value::Array(s.iter().map(|v| any_value_to_json(&v)).collect())
+        }
+        AnyValue::Struct(_, _, fields) => {
+            let obj: Map<String, Value> = fields
+                .iter()
+                .zip(av._iter_struct_av())
+                .map(|(f, v)| (f.name().to_string(), any_value_to_json(&v)))
+                .collect();
+            Value::Object(obj)
+        }
+        AnyValue::StructOwned(payload) => {
+            let (values, fields) = &**payload;
+            let obj: Map<String, Value> = fields
+                .iter()
+                .zip(values)
+                .map(|(f, v)| (f.name().to_string(), any_value_to_json(v)))
+                .collect();
+            Value::Object(obj)
+        }
+        other => {
+            let dtype = other.dtype();
+            if dtype.is_float() {
+                other
+                    .extract::<f64>()
+                    .and_then(Number::from_f64)
+                    .map_or(Value::Null, Value::Number)
+            } else if dtype.is_unsigned_integer() {
+                other.extract::<u64>().map_or(Value::Null, Value::from)
+            } else if dtype.is_integer() {
+                other.extract::<i64>().map_or(Value::Null, Value::from)
+            } else {
+                // Anything else left after the cast (e.g. objects) has no JSON form
+                Value::Null
+            }
+        }
+    }
+}
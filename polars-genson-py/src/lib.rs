@@ -1,35 +1,42 @@
 use pyo3::prelude::*;
 
+mod builder;
+mod cache;
+mod columns;
 mod expressions;
+mod input;
 mod parquet_io;
+mod scan;
 mod schema;
 
-use parquet_io::{infer_from_parquet, normalise_from_parquet, read_parquet_metadata};
-use schema::{json_to_schema, schema_to_json};
-
-#[pyfunction]
-fn avro_to_polars_fields(schema_json: String, debug: bool) -> PyResult<Vec<(String, String)>> {
-    use polars_jsonschema_bridge::{schema_to_polars_fields, SchemaFormat};
-
-    let schema: serde_json::Value = serde_json::from_str(&schema_json)
-        .map_err(|e| pyo3::exceptions::PyValueError::new_err(format!("Invalid JSON: {}", e)))?;
-
-    let fields = schema_to_polars_fields(&schema, SchemaFormat::Avro, debug).map_err(|e| {
-        pyo3::exceptions::PyRuntimeError::new_err(format!("Conversion failed: {}", e))
-    })?;
-
-    Ok(fields)
-}
+use builder::SchemaBuilder;
+use cache::clear_schema_cache;
+use columns::unnest_fields;
+use expressions::{effective_config, normalise_json_with_schema};
+use parquet_io::{
+    infer_from_parquet, normalise_from_parquet, read_parquet_metadata, write_normalised,
+};
+use scan::{infer_ndjson_schema, normalise_lines};
+use schema::{format_schema, json_schema_to_polars, json_to_schema, schema_to_json};
 
 #[pymodule]
 fn _polars_genson(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add("__version__", env!("CARGO_PKG_VERSION"))?;
     m.add_function(wrap_pyfunction!(json_to_schema, m)?)?;
     m.add_function(wrap_pyfunction!(schema_to_json, m)?)?;
+    m.add_function(wrap_pyfunction!(format_schema, m)?)?;
     m.add_function(wrap_pyfunction!(infer_from_parquet, m)?)?;
     m.add_function(wrap_pyfunction!(normalise_from_parquet, m)?)?;
     m.add_function(wrap_pyfunction!(read_parquet_metadata, m)?)?;
-    m.add_function(wrap_pyfunction!(avro_to_polars_fields, m)?)?;
+    m.add_function(wrap_pyfunction!(write_normalised, m)?)?;
+    m.add_function(wrap_pyfunction!(json_schema_to_polars, m)?)?;
+    m.add_function(wrap_pyfunction!(effective_config, m)?)?;
+    m.add_function(wrap_pyfunction!(normalise_json_with_schema, m)?)?;
+    m.add_function(wrap_pyfunction!(infer_ndjson_schema, m)?)?;
+    m.add_function(wrap_pyfunction!(normalise_lines, m)?)?;
+    m.add_function(wrap_pyfunction!(unnest_fields, m)?)?;
+    m.add_function(wrap_pyfunction!(clear_schema_cache, m)?)?;
+    m.add_class::<SchemaBuilder>()?;
     Ok(())
 }
 
@@ -1,8 +1,16 @@
-use genson_core::normalise::{normalise_values, MapEncoding, NormaliseConfig};
-use genson_core::parquet::{read_string_column, write_string_column};
-use genson_core::{infer_json_schema_from_strings, DebugVerbosity, SchemaInferenceConfig};
+use genson_core::normalise::{normalise_values, MapEncoding, NormaliseConfig, UnknownFieldPolicy};
+use genson_core::parquet::{
+    read_string_column, write_string_column, write_string_rows, DEFAULT_WRITE_BATCH_SIZE,
+};
+use genson_core::serialise::{self, OutputFormat};
+use genson_core::{
+    infer_json_schema_from_strings, DebugVerbosity, FieldOrder, JsonSchemaDraft,
+    SchemaInferenceConfig, UnionOrder, DEFAULT_ERROR_CONTEXT_CHARS, DEFAULT_ROOT_VALUE_FIELD,
+};
 use pyo3::prelude::*;
+use pyo3_polars::PySeries;
 use std::collections::HashMap;
+use std::io::{BufWriter, Write};
 
 #[pyfunction]
 #[pyo3(signature = (
@@ -12,21 +20,31 @@ use std::collections::HashMap;
     ignore_outer_array=true,
     ndjson=false,
     schema_uri=Some("http://json-schema.org/schema#".to_string()),
+    json_schema_draft=None,
+    sort_fields="preserve".to_string(),
+    null_fallback=None,
     debug=false,
     profile=false,
     verbosity="Normal".to_string(),
     map_threshold=20,
     map_max_required_keys=None,
     unify_maps=false,
+    detect_tuples=false,
+    required_threshold=None,
+    coerce_map_array=false,
     no_unify=None,
     force_field_types=None,
     force_parent_field_types=None,
     force_scalar_promotion=None,
+    ignore_paths=None,
+    max_depth=None,
+    max_object_keys=None,
     wrap_scalars=true,
     avro=false,
     wrap_root=None,
     no_root_map=true,
     max_builders=None,
+    format="json".to_string(),
 ))]
 #[allow(clippy::too_many_arguments)]
 pub fn infer_from_parquet(
@@ -36,22 +54,36 @@ pub fn infer_from_parquet(
     ignore_outer_array: bool,
     ndjson: bool,
     schema_uri: Option<String>,
+    json_schema_draft: Option<String>,
+    sort_fields: String,
+    null_fallback: Option<String>,
     debug: bool,
     profile: bool,
     verbosity: String,
     map_threshold: usize,
     map_max_required_keys: Option<usize>,
     unify_maps: bool,
+    detect_tuples: bool,
+    required_threshold: Option<f64>,
+    coerce_map_array: bool,
     no_unify: Option<Vec<String>>,
     force_field_types: Option<HashMap<String, String>>,
     force_parent_field_types: Option<HashMap<String, String>>,
     force_scalar_promotion: Option<Vec<String>>,
+    ignore_paths: Option<Vec<String>>,
+    max_depth: Option<usize>,
+    max_object_keys: Option<usize>,
     wrap_scalars: bool,
     avro: bool,
     wrap_root: Option<String>,
     no_root_map: bool,
     max_builders: Option<usize>,
+    format: String,
 ) -> PyResult<String> {
+    let output_format: OutputFormat = format
+        .parse()
+        .map_err(pyo3::exceptions::PyValueError::new_err)?;
+
     // Read from Parquet
     let json_strings = read_string_column(&input_path, &column).map_err(|e| {
         pyo3::exceptions::PyIOError::new_err(format!("Failed to read Parquet: {}", e))
@@ -71,14 +103,49 @@ pub fn infer_from_parquet(
         _ => DebugVerbosity::Normal,
     };
 
+    let json_schema_draft = json_schema_draft
+        .map(|draft| {
+            serde_json::from_value::<JsonSchemaDraft>(serde_json::Value::String(draft.clone()))
+                .map_err(|_| {
+                    pyo3::exceptions::PyValueError::new_err(format!(
+                        "Invalid JSON Schema draft: {} (expected draft-07|2019-09|2020-12)",
+                        draft
+                    ))
+                })
+        })
+        .transpose()?;
+    let sort_fields =
+        serde_json::from_value::<FieldOrder>(serde_json::Value::String(sort_fields.clone()))
+            .map_err(|_| {
+                pyo3::exceptions::PyValueError::new_err(format!(
+                    "Invalid field order: {} (expected preserve|alphabetical|by_frequency)",
+                    sort_fields
+                ))
+            })?;
+
     // Build config
     let config = SchemaInferenceConfig {
         ignore_outer_array,
         delimiter: if ndjson { Some(b'\n') } else { None },
         schema_uri,
+        json_schema_draft,
+        sort_fields,
+        union_order: UnionOrder::Canonical,
+        select_paths: Vec::new(),
+        discriminators: HashMap::new(),
+        empty_container_policy: None,
+        nonfinite_policy: None,
+        repair: false,
+        null_fallback,
         map_threshold,
         map_max_required_keys,
+        map_detection: Default::default(),
         unify_maps,
+        label_maps: false,
+        simplify_labels: false,
+        detect_tuples,
+        required_threshold,
+        coerce_map_array,
         no_unify: no_unify.unwrap_or_default().into_iter().collect(),
         force_field_types: force_field_types.unwrap_or_default(),
         force_parent_field_types: force_parent_field_types.unwrap_or_default(),
@@ -86,13 +153,41 @@ pub fn infer_from_parquet(
             .unwrap_or_default()
             .into_iter()
             .collect(),
+        ignore_paths: ignore_paths.unwrap_or_default(),
+        annotations: HashMap::new(),
+        max_depth,
+        max_object_keys,
         wrap_scalars,
         avro,
+        avro_namespace: None,
+        avro_root_name: None,
+        avro_docs: HashMap::new(),
+        avro_field_names: Default::default(),
+        avro_defaults: true,
         wrap_root,
+        wrap_root_strict: false,
+        root_value_field: DEFAULT_ROOT_VALUE_FIELD.to_string(),
+        outer_scalar_policy: Default::default(),
+        key_fold: None,
+        detect_formats: None,
+        detect_numeric_keys: false,
+        sparse_array_detection: false,
+        seed_schema: None,
         no_root_map,
         max_builders,
+        chunk_size: None,
+        sample: None,
+        limits: Default::default(),
+        error_context_chars: DEFAULT_ERROR_CONTEXT_CHARS,
+        error_dump_dir: None,
         debug,
         profile,
+        collect_profile: false,
+        explain: false,
+        map_key_stats: None,
+        decision_log: false,
+        embed_config: false,
+        strict: false,
         verbosity: verbosity_enum,
     };
 
@@ -102,9 +197,13 @@ pub fn infer_from_parquet(
     })?;
 
     // Serialize schema
-    let schema_json = serde_json::to_string_pretty(&result.schema).map_err(|e| {
-        pyo3::exceptions::PyValueError::new_err(format!("JSON serialization failed: {}", e))
-    })?;
+    let schema_json = match output_format {
+        OutputFormat::Json => serde_json::to_string_pretty(&result.schema).map_err(|e| {
+            pyo3::exceptions::PyValueError::new_err(format!("JSON serialization failed: {}", e))
+        })?,
+        _ => serialise::to_string(&result.schema, output_format)
+            .map_err(pyo3::exceptions::PyValueError::new_err)?,
+    };
 
     if debug {
         anstream::eprintln!("Processed {} JSON object(s)", result.processed_count);
@@ -144,6 +243,9 @@ pub fn infer_from_parquet(
     force_field_types=None,
     force_parent_field_types=None,
     force_scalar_promotion=None,
+    ignore_paths=None,
+    max_depth=None,
+    max_object_keys=None,
     wrap_scalars=true,
     wrap_root=None,
     no_root_map=true,
@@ -169,6 +271,9 @@ pub fn normalise_from_parquet(
     force_field_types: Option<HashMap<String, String>>,
     force_parent_field_types: Option<HashMap<String, String>>,
     force_scalar_promotion: Option<Vec<String>>,
+    ignore_paths: Option<Vec<String>>,
+    max_depth: Option<usize>,
+    max_object_keys: Option<usize>,
     wrap_scalars: bool,
     wrap_root: Option<String>,
     no_root_map: bool,
@@ -192,9 +297,24 @@ pub fn normalise_from_parquet(
         ignore_outer_array,
         delimiter: if ndjson { Some(b'\n') } else { None },
         schema_uri: None,
+        json_schema_draft: None,
+        sort_fields: FieldOrder::Preserve,
+        union_order: UnionOrder::Canonical,
+        select_paths: Vec::new(),
+        discriminators: HashMap::new(),
+        empty_container_policy: None,
+        nonfinite_policy: None,
+        repair: false,
+        null_fallback: None,
         map_threshold,
         map_max_required_keys,
+        map_detection: Default::default(),
         unify_maps,
+        label_maps: false,
+        simplify_labels: false,
+        detect_tuples: false,
+        required_threshold: None,
+        coerce_map_array: false,
         no_unify: no_unify.unwrap_or_default().into_iter().collect(),
         force_field_types: force_field_types.unwrap_or_default(),
         force_parent_field_types: force_parent_field_types.unwrap_or_default(),
@@ -202,13 +322,41 @@ pub fn normalise_from_parquet(
             .unwrap_or_default()
             .into_iter()
             .collect(),
+        ignore_paths: ignore_paths.unwrap_or_default(),
+        annotations: HashMap::new(),
+        max_depth,
+        max_object_keys,
         wrap_scalars,
         avro: true,
+        avro_namespace: None,
+        avro_root_name: None,
+        avro_docs: HashMap::new(),
+        avro_field_names: Default::default(),
+        avro_defaults: true,
         wrap_root: wrap_root.clone(),
+        wrap_root_strict: false,
+        root_value_field: DEFAULT_ROOT_VALUE_FIELD.to_string(),
+        outer_scalar_policy: Default::default(),
+        key_fold: None,
+        detect_formats: None,
+        detect_numeric_keys: false,
+        sparse_array_detection: false,
+        seed_schema: None,
         no_root_map,
         max_builders,
+        chunk_size: None,
+        sample: None,
+        limits: Default::default(),
+        error_context_chars: DEFAULT_ERROR_CONTEXT_CHARS,
+        error_dump_dir: None,
         debug,
         profile,
+        collect_profile: false,
+        explain: false,
+        map_key_stats: None,
+        decision_log: false,
+        embed_config: false,
+        strict: false,
         verbosity: DebugVerbosity::Normal,
     };
 
@@ -245,6 +393,17 @@ pub fn normalise_from_parquet(
         coerce_string: coerce_strings,
         map_encoding: map_enc,
         wrap_root: wrap_root.clone(),
+        root_value_field: DEFAULT_ROOT_VALUE_FIELD.to_string(),
+        simplify_labels: false,
+        coerce_map_array: false,
+        sparse_array_detection: false,
+        unknown_fields: UnknownFieldPolicy::Drop,
+        threads: None,
+        key_fold: None,
+        value_limits: Default::default(),
+        canonical_numeric_keys: false,
+        nonfinite_policy: None,
+        select_paths: Vec::new(),
     };
 
     let normalised = normalise_values(values, &result.schema, &norm_config);
@@ -287,6 +446,269 @@ pub fn normalise_from_parquet(
     Ok(())
 }
 
+#[pyfunction]
+#[pyo3(signature = (
+    series,
+    output_path,
+    format="parquet".to_string(),
+    output_column=None,
+    ignore_outer_array=true,
+    ndjson=false,
+    empty_as_null=true,
+    coerce_strings=false,
+    map_encoding="kv".to_string(),
+    debug=false,
+    profile=false,
+    map_threshold=20,
+    map_max_required_keys=None,
+    unify_maps=false,
+    no_unify=None,
+    force_field_types=None,
+    force_parent_field_types=None,
+    force_scalar_promotion=None,
+    ignore_paths=None,
+    max_depth=None,
+    max_object_keys=None,
+    wrap_scalars=true,
+    wrap_root=None,
+    no_root_map=true,
+    max_builders=None,
+    batch_size=None,
+))]
+#[allow(clippy::too_many_arguments)]
+pub fn write_normalised(
+    series: PySeries,
+    output_path: String,
+    format: String,
+    output_column: Option<String>,
+    ignore_outer_array: bool,
+    ndjson: bool,
+    empty_as_null: bool,
+    coerce_strings: bool,
+    map_encoding: String,
+    debug: bool,
+    profile: bool,
+    map_threshold: usize,
+    map_max_required_keys: Option<usize>,
+    unify_maps: bool,
+    no_unify: Option<Vec<String>>,
+    force_field_types: Option<HashMap<String, String>>,
+    force_parent_field_types: Option<HashMap<String, String>>,
+    force_scalar_promotion: Option<Vec<String>>,
+    ignore_paths: Option<Vec<String>>,
+    max_depth: Option<usize>,
+    max_object_keys: Option<usize>,
+    wrap_scalars: bool,
+    wrap_root: Option<String>,
+    no_root_map: bool,
+    max_builders: Option<usize>,
+    batch_size: Option<usize>,
+) -> PyResult<usize> {
+    let series = series.0;
+    let string_chunked = series.str().map_err(|_| {
+        pyo3::exceptions::PyTypeError::new_err(format!(
+            "Expected a string column for JSON normalisation, got {}",
+            series.dtype()
+        ))
+    })?;
+
+    if format != "parquet" && format != "ndjson" {
+        return Err(pyo3::exceptions::PyValueError::new_err(format!(
+            "Invalid format: {} (expected 'parquet' or 'ndjson')",
+            format
+        )));
+    }
+
+    let map_enc = match map_encoding.as_str() {
+        "mapping" => MapEncoding::Mapping,
+        "entries" => MapEncoding::Entries,
+        "kv" => MapEncoding::KeyValueEntries,
+        _ => {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "Invalid map_encoding: {}",
+                map_encoding
+            )))
+        }
+    };
+
+    // Only the inference pass needs owned strings; normalisation reads the column directly
+    let json_strings: Vec<String> = string_chunked
+        .iter()
+        .flatten()
+        .filter(|s| !s.trim().is_empty())
+        .map(str::to_string)
+        .collect();
+
+    let config = SchemaInferenceConfig {
+        ignore_outer_array,
+        delimiter: if ndjson { Some(b'\n') } else { None },
+        schema_uri: None,
+        json_schema_draft: None,
+        sort_fields: FieldOrder::Preserve,
+        union_order: UnionOrder::Canonical,
+        select_paths: Vec::new(),
+        discriminators: HashMap::new(),
+        empty_container_policy: None,
+        nonfinite_policy: None,
+        repair: false,
+        null_fallback: None,
+        map_threshold,
+        map_max_required_keys,
+        map_detection: Default::default(),
+        unify_maps,
+        label_maps: false,
+        simplify_labels: false,
+        detect_tuples: false,
+        required_threshold: None,
+        coerce_map_array: false,
+        no_unify: no_unify.unwrap_or_default().into_iter().collect(),
+        force_field_types: force_field_types.unwrap_or_default(),
+        force_parent_field_types: force_parent_field_types.unwrap_or_default(),
+        force_scalar_promotion: force_scalar_promotion
+            .unwrap_or_default()
+            .into_iter()
+            .collect(),
+        ignore_paths: ignore_paths.unwrap_or_default(),
+        annotations: HashMap::new(),
+        max_depth,
+        max_object_keys,
+        wrap_scalars,
+        avro: true,
+        avro_namespace: None,
+        avro_root_name: None,
+        avro_docs: HashMap::new(),
+        avro_field_names: Default::default(),
+        avro_defaults: true,
+        wrap_root: wrap_root.clone(),
+        wrap_root_strict: false,
+        root_value_field: DEFAULT_ROOT_VALUE_FIELD.to_string(),
+        outer_scalar_policy: Default::default(),
+        key_fold: None,
+        detect_formats: None,
+        detect_numeric_keys: false,
+        sparse_array_detection: false,
+        seed_schema: None,
+        no_root_map,
+        max_builders,
+        chunk_size: None,
+        sample: None,
+        limits: Default::default(),
+        error_context_chars: DEFAULT_ERROR_CONTEXT_CHARS,
+        error_dump_dir: None,
+        debug,
+        profile,
+        collect_profile: false,
+        explain: false,
+        map_key_stats: None,
+        decision_log: false,
+        embed_config: false,
+        strict: false,
+        verbosity: DebugVerbosity::Normal,
+    };
+
+    let result = infer_json_schema_from_strings(&json_strings, config).map_err(|e| {
+        pyo3::exceptions::PyRuntimeError::new_err(format!("Schema inference failed: {}", e))
+    })?;
+    drop(json_strings);
+
+    if debug {
+        anstream::eprintln!("Processed {} JSON object(s)", result.processed_count);
+    }
+
+    let norm_config = NormaliseConfig {
+        empty_as_null,
+        coerce_string: coerce_strings,
+        map_encoding: map_enc,
+        wrap_root,
+        root_value_field: DEFAULT_ROOT_VALUE_FIELD.to_string(),
+        simplify_labels: false,
+        coerce_map_array: false,
+        sparse_array_detection: false,
+        unknown_fields: UnknownFieldPolicy::Drop,
+        threads: None,
+        key_fold: None,
+        value_limits: Default::default(),
+        canonical_numeric_keys: false,
+        nonfinite_policy: None,
+        select_paths: Vec::new(),
+    };
+
+    // Each row is normalised as the writer pulls it, so only one batch is ever materialised
+    let schema = &result.schema;
+    let normalised_rows = string_chunked.iter().map(|s| {
+        let value = s
+            .and_then(|st| serde_json::from_str::<serde_json::Value>(st).ok())
+            .unwrap_or(serde_json::Value::Null);
+        let normed = normalise_values(vec![value], schema, &norm_config)
+            .pop()
+            .unwrap_or(serde_json::Value::Null);
+        serde_json::to_string(&normed).unwrap()
+    });
+
+    let written = if format == "parquet" {
+        let col_name = output_column.unwrap_or_else(|| series.name().to_string());
+
+        let mut metadata = HashMap::new();
+        metadata.insert(
+            "genson_avro_schema".to_string(),
+            serde_json::to_string(schema).map_err(|e| {
+                pyo3::exceptions::PyValueError::new_err(format!(
+                    "Failed to serialize schema: {}",
+                    e
+                ))
+            })?,
+        );
+        metadata.insert(
+            "genson_normalise_config".to_string(),
+            serde_json::to_string(&norm_config).map_err(|e| {
+                pyo3::exceptions::PyValueError::new_err(format!(
+                    "Failed to serialize config: {}",
+                    e
+                ))
+            })?,
+        );
+
+        write_string_rows(
+            &output_path,
+            &col_name,
+            normalised_rows,
+            Some(metadata),
+            batch_size.unwrap_or(DEFAULT_WRITE_BATCH_SIZE),
+        )
+        .map_err(|e| {
+            pyo3::exceptions::PyIOError::new_err(format!("Failed to write Parquet: {}", e))
+        })?
+    } else {
+        let io_err = |e: std::io::Error| {
+            pyo3::exceptions::PyIOError::new_err(format!(
+                "Failed to write to {}: {}",
+                output_path, e
+            ))
+        };
+        let file = std::fs::File::create(&output_path).map_err(io_err)?;
+        let mut out = BufWriter::new(file);
+        let mut written = 0;
+        for row in normalised_rows {
+            out.write_all(row.as_bytes()).map_err(io_err)?;
+            out.write_all(b"\n").map_err(io_err)?;
+            written += 1;
+        }
+        out.flush().map_err(io_err)?;
+        written
+    };
+
+    if debug {
+        anstream::eprintln!(
+            "Normalised {} row(s) written to: {} ({})",
+            written,
+            output_path,
+            format
+        );
+    }
+
+    Ok(written)
+}
+
 #[pyfunction]
 pub fn read_parquet_metadata(path: String) -> PyResult<HashMap<String, String>> {
     genson_core::parquet::read_parquet_metadata(&path).map_err(|e| {
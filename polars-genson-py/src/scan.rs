@@ -0,0 +1,64 @@
+//! Support for `scan_json_normalised`, the NDJSON IO plugin: the schema is inferred
+//! over the whole file up front, then batches of lines are normalised against it as
+//! the plugin yields them.
+
+use crate::expressions::GensonKwargs;
+use genson_core::normalise::try_normalise_values;
+use genson_core::{infer_json_schema_from_strings, SchemaInferenceConfig};
+use pyo3::exceptions::{PyIOError, PyRuntimeError, PyValueError};
+use pyo3::prelude::*;
+use serde_json::Value;
+use std::fs;
+
+pub(crate) fn parse_kwargs(kwargs_json: &str) -> PyResult<GensonKwargs> {
+    serde_json::from_str(kwargs_json)
+        .map_err(|e| PyValueError::new_err(format!("Invalid kwargs: {}", e)))
+}
+
+/// Infer the Avro schema of an NDJSON file, returned as JSON.
+#[pyfunction]
+pub fn infer_ndjson_schema(path: String, kwargs_json: String) -> PyResult<String> {
+    let kwargs = parse_kwargs(&kwargs_json)?;
+    let input = fs::read_to_string(&path)
+        .map_err(|e| PyIOError::new_err(format!("Failed to read {}: {}", path, e)))?;
+
+    let config = SchemaInferenceConfig {
+        delimiter: Some(b'\n'),
+        avro: true, // normalisation implies Avro
+        ..kwargs.inference_config()
+    };
+    let result = infer_json_schema_from_strings(&[input], config)
+        .map_err(|e| PyRuntimeError::new_err(format!("Schema inference failed: {}", e)))?;
+
+    serde_json::to_string(&result.schema)
+        .map_err(|e| PyRuntimeError::new_err(format!("JSON serialization error: {}", e)))
+}
+
+/// Normalise NDJSON lines against an Avro schema (as JSON), one JSON string per line.
+///
+/// Lines that aren't valid JSON normalise as `null`, as in the `normalise_json`
+/// expression.
+#[pyfunction]
+pub fn normalise_lines(
+    lines: Vec<String>,
+    schema_json: String,
+    kwargs_json: String,
+) -> PyResult<Vec<String>> {
+    let kwargs = parse_kwargs(&kwargs_json)?;
+    let schema: Value = serde_json::from_str(&schema_json)
+        .map_err(|e| PyValueError::new_err(format!("Invalid schema JSON: {}", e)))?;
+    let cfg = kwargs.normalise_config();
+
+    let values = lines
+        .iter()
+        .map(|line| serde_json::from_str(line).unwrap_or(Value::Null))
+        .collect();
+    try_normalise_values(values, &schema, &cfg)
+        .map_err(|e| PyValueError::new_err(format!("Normalisation failed: {}", e)))?
+        .iter()
+        .map(|v| {
+            serde_json::to_string(v)
+                .map_err(|e| PyRuntimeError::new_err(format!("JSON serialization error: {}", e)))
+        })
+        .collect()
+}
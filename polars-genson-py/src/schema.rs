@@ -13,6 +13,19 @@ macro_rules! eprintln_orange {
     }};
 }
 
+/// Serialise a JSON schema in another text format (json, yaml or toml)
+#[pyfunction]
+pub fn format_schema(schema_json: String, format: String) -> PyResult<String> {
+    use genson_core::serialise::{to_string, OutputFormat};
+
+    let output_format: OutputFormat = format
+        .parse()
+        .map_err(pyo3::exceptions::PyValueError::new_err)?;
+    let schema: Value = serde_json::from_str(&schema_json)
+        .map_err(|e| pyo3::exceptions::PyValueError::new_err(format!("Invalid JSON: {}", e)))?;
+    to_string(&schema, output_format).map_err(pyo3::exceptions::PyValueError::new_err)
+}
+
 /// Convert a Polars schema to JSON string representation
 #[pyfunction]
 #[pyo3(signature = (df, debug=false))]
@@ -93,3 +106,29 @@ pub fn json_to_schema(json_str: &str, debug: bool) -> PyResult<PyDataFrame> {
 
     Ok(PyDataFrame(df))
 }
+
+/// Convert a JSON Schema (or Avro schema) to Polars dtypes, returned as an empty
+/// DataFrame whose schema Python reads back as real dtype objects.
+#[pyfunction]
+#[pyo3(signature = (schema_json, avro=false, debug=false))]
+pub fn json_schema_to_polars(schema_json: &str, avro: bool, debug: bool) -> PyResult<PyDataFrame> {
+    use polars_jsonschema_bridge::{schema_to_polars_schema, SchemaFormat};
+
+    let schema: Value = serde_json::from_str(schema_json)
+        .map_err(|e| pyo3::exceptions::PyValueError::new_err(format!("Invalid JSON: {}", e)))?;
+    let format = if avro {
+        SchemaFormat::Avro
+    } else {
+        SchemaFormat::JsonSchema
+    };
+
+    let polars_schema = schema_to_polars_schema(&schema, format).map_err(|e| {
+        pyo3::exceptions::PyValueError::new_err(format!("Conversion failed: {}", e))
+    })?;
+
+    if debug {
+        eprintln_orange!("Converted schema: {:?}", polars_schema);
+    }
+
+    Ok(PyDataFrame(DataFrame::empty_with_schema(&polars_schema)))
+}
@@ -0,0 +1,26 @@
+"""Tests for documents whose root is a scalar or an array rather than an object."""
+
+import polars as pl
+import polars_genson  # noqa: F401
+
+
+def test_scalar_roots_wrapped_under_value():
+    """String roots are inferred and normalised as a record with a value field."""
+    df = pl.DataFrame({"json_col": ['"string"', '"another"']})
+
+    schema = df.genson.infer_json_schema("json_col")
+    assert schema["properties"] == {"value": {"type": "string"}}
+
+    normalised = df.genson.normalise_json("json_col", decode=False).to_list()
+    assert normalised == ['{"value":"string"}', '{"value":"another"}']
+
+
+def test_array_of_scalars_root_wrapped_under_value():
+    """A root array of scalars is one document, not a stream of them."""
+    df = pl.DataFrame({"json_col": ["[1, 2, 3]", "[4]"]})
+
+    schema = df.genson.infer_json_schema("json_col")
+    assert schema["properties"]["value"] == {
+        "type": "array",
+        "items": {"type": "integer"},
+    }
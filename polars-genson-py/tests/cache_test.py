@@ -0,0 +1,55 @@
+# tests/cache_test.py
+"""Tests for caching inferred schemas by column content."""
+
+import polars as pl
+import polars_genson
+from polars_genson import infer_json_schema
+
+
+def make_df():
+    return pl.DataFrame({"json_data": ['{"id": 1}', '{"id": 2, "name": "a"}']})
+
+
+def test_cache_repeated_inference():
+    """A cached schema is the one inference gives."""
+    polars_genson.clear_schema_cache()
+    df = make_df()
+    uncached = df.genson.infer_json_schema("json_data")
+    assert df.genson.infer_json_schema("json_data", cache=True) == uncached
+    assert df.genson.infer_json_schema("json_data", cache=True) == uncached
+
+
+def test_cache_keyed_by_content_and_config():
+    """Other rows or other settings don't get a cached schema."""
+    polars_genson.clear_schema_cache()
+    df = make_df()
+    df.genson.infer_json_schema("json_data", cache=True)
+
+    avro = df.genson.infer_json_schema("json_data", cache=True, avro=True)
+    assert avro["type"] == "record"
+
+    other = pl.DataFrame({"json_data": ['{"id": "x"}']})
+    schema = other.genson.infer_json_schema("json_data", cache=True)
+    assert schema["properties"]["id"]["type"] == "string"
+
+
+def test_cache_dir(tmp_path):
+    """Schemas written to the cache directory are read back after clearing."""
+    polars_genson.clear_schema_cache()
+    df = make_df()
+    schema = df.genson.infer_json_schema("json_data", cache_dir=str(tmp_path))
+    files = list(tmp_path.glob("*.json"))
+    assert len(files) == 1
+
+    polars_genson.clear_schema_cache()
+    out = df.select(infer_json_schema(pl.col("json_data"), cache_dir=str(tmp_path)))
+    assert out.height == 1
+    assert df.genson.infer_json_schema("json_data", cache_dir=str(tmp_path)) == schema
+
+
+def test_cache_normalise():
+    """Normalisation against a cached schema gives the same rows."""
+    polars_genson.clear_schema_cache()
+    df = make_df()
+    first = df.genson.normalise_json("json_data", cache=True).to_list()
+    assert df.genson.normalise_json("json_data", cache=True).to_list() == first
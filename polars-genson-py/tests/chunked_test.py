@@ -0,0 +1,24 @@
+# tests/chunked_test.py
+"""Tests for memory-bounded chunked inference."""
+
+import polars as pl
+
+
+def make_df():
+    rows = [f'{{"id": {i}, "v{i % 4}": "x"}}' for i in range(30)]
+    return pl.DataFrame({"json_data": rows})
+
+
+def test_chunked_schema_matches_unchunked():
+    """Batching the rows doesn't change the merged schema."""
+    df = make_df()
+    expected = df.genson.infer_json_schema("json_data")
+    for chunk_size in [1, 4, 100]:
+        assert df.genson.infer_json_schema("json_data", chunk_size=chunk_size) == expected
+
+
+def test_chunked_normalise():
+    """Normalisation accepts chunk_size for its inference pass."""
+    df = make_df()
+    expected = df.genson.normalise_json("json_data")
+    assert df.genson.normalise_json("json_data", chunk_size=7).equals(expected)
@@ -0,0 +1,36 @@
+"""Tests for merging maps of arrays and arrays of maps with coerce_map_array."""
+
+import polars as pl
+import polars_genson  # noqa: F401
+
+ROWS = ['{"l": {"en": ["a"]}}', '{"l": [{"en": "b"}, {"en": "c", "de": "d"}]}']
+
+
+def test_coerce_map_array_schema():
+    """The object/array union collapses to an object of arrays."""
+    df = pl.DataFrame({"json_col": ROWS})
+
+    schema = df.genson.infer_json_schema("json_col", coerce_map_array=True)
+    l_schema = schema["properties"]["l"]
+    assert "anyOf" not in l_schema
+    de = l_schema["properties"]["de"]
+    assert de == {"type": "array", "items": {"type": "string"}}
+
+
+def test_coerce_map_array_off_by_default():
+    """Without coerce_map_array the two forms stay a union."""
+    df = pl.DataFrame({"json_col": ROWS})
+
+    schema = df.genson.infer_json_schema("json_col")
+    assert "anyOf" in schema["properties"]["l"]
+
+
+def test_coerce_map_array_normalise():
+    """Arrays of maps are folded into maps of arrays."""
+    df = pl.DataFrame({"json_col": ROWS})
+
+    result = df.genson.normalise_json("json_col", coerce_map_array=True)
+    assert result.to_dicts() == [
+        {"l": {"en": ["a"], "de": None}},
+        {"l": {"en": ["b", "c"], "de": ["d"]}},
+    ]
@@ -0,0 +1,72 @@
+"""Tests for the schema_compatible expression."""
+
+import orjson
+import polars as pl
+import polars_genson
+
+
+OLD = {
+    "type": "object",
+    "properties": {"id": {"type": "integer"}, "name": {"type": "string"}},
+    "required": ["id", "name"],
+}
+
+
+def _frame(new: dict | None) -> pl.DataFrame:
+    return pl.DataFrame(
+        {
+            "old": [orjson.dumps(OLD).decode()],
+            "new": [None if new is None else orjson.dumps(new).decode()],
+        },
+        schema={"old": pl.String, "new": pl.String},
+    )
+
+
+def test_added_optional_field_is_compatible():
+    """A new field that isn't required keeps both directions compatible."""
+    new = {**OLD, "properties": {**OLD["properties"], "email": {"type": "string"}}}
+    out = _frame(new).genson.schema_compatible("old", "new", mode="full")
+    assert out.struct.field("compatible").to_list() == [True]
+    assert out.struct.field("issues").to_list() == [[]]
+
+
+def test_changed_type_reports_both_directions():
+    """A type change is reported once per direction that fails."""
+    new = {**OLD, "properties": {**OLD["properties"], "id": {"type": "string"}}}
+    out = _frame(new).select(
+        polars_genson.schema_compatible(pl.col("old"), pl.col("new"), mode="full")
+    )
+    row = out.to_series().to_list()[0]
+    assert row["compatible"] is False
+    assert row["issues"] == [
+        {
+            "path": "id",
+            "direction": "backward",
+            "message": "integer values can't be read as string",
+        },
+        {
+            "path": "id",
+            "direction": "forward",
+            "message": "string values can't be read as integer",
+        },
+    ]
+
+
+def test_added_required_field_breaks_backward_only():
+    """A new required field can't be read from old data, but old readers skip it."""
+    new = {
+        "type": "object",
+        "properties": {**OLD["properties"], "email": {"type": "string"}},
+        "required": ["id", "name", "email"],
+    }
+    frame = _frame(new)
+    backward = frame.genson.schema_compatible("old", "new")
+    assert backward.struct.field("compatible").to_list() == [False]
+    forward = frame.genson.schema_compatible("old", "new", mode="forward")
+    assert forward.struct.field("compatible").to_list() == [True]
+
+
+def test_null_schema_gives_null():
+    """Rows without a schema to compare are null."""
+    out = _frame(None).genson.schema_compatible("old", "new")
+    assert out.to_list() == [None]
@@ -0,0 +1,48 @@
+"""Tests for the per-row schema_conformity expression."""
+
+import polars as pl
+import polars_genson  # noqa: F401
+
+
+SCHEMA = {
+    "type": "object",
+    "properties": {
+        "id": {"type": "integer"},
+        "tags": {"type": "array", "items": {"type": "string"}},
+    },
+    "required": ["id"],
+}
+
+
+def test_conformity_against_supplied_schema():
+    """Each row reports whether it is valid and which paths are not."""
+    df = pl.DataFrame(
+        {
+            "json_data": [
+                '{"id": 1, "tags": ["a"]}',
+                '{"tags": ["a", 2]}',
+                "not json",
+                None,
+            ]
+        }
+    )
+    out = df.genson.schema_conformity("json_data", schema=SCHEMA)
+
+    assert out.name == "conformity"
+    assert out.to_list() == [
+        {"valid": True, "violations": []},
+        {"valid": False, "violations": ["id", "tags[1]"]},
+        {"valid": False, "violations": ["$"]},
+        {"valid": None, "violations": None},
+    ]
+
+
+def test_conformity_against_inferred_schema():
+    """Without a schema, rows are checked against one inferred from the column."""
+    df = pl.DataFrame({"json_data": ['{"id": 1}', '{"id": 2}']})
+    out = df.select(
+        polars_genson.schema_conformity(pl.col("json_data"), output_name="ok")
+    ).to_series()
+
+    assert out.name == "ok"
+    assert out.struct.field("valid").to_list() == [True, True]
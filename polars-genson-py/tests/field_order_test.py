@@ -134,3 +134,34 @@ def test_required_fields_order():
 
     # Required fields should be in the same order as they appear: z, b, a
     assert required_fields == ["a", "b", "z"]
+
+
+def test_sort_fields_alphabetical():
+    """Sorted fields don't depend on the order of the input rows."""
+    import polars as pl
+    import polars_genson
+
+    rows = ['{"z": 1, "b": {"y": 1, "a": 2}}', '{"m": true}']
+    forward = pl.DataFrame({"json_col": rows}).genson.infer_json_schema(
+        "json_col", sort_fields="alphabetical"
+    )
+    backward = pl.DataFrame({"json_col": rows[::-1]}).genson.infer_json_schema(
+        "json_col", sort_fields="alphabetical"
+    )
+
+    assert list(forward["properties"]) == ["b", "m", "z"]
+    assert list(forward["properties"]["b"]["properties"]) == ["a", "y"]
+    assert forward == backward
+
+
+def test_sort_fields_by_frequency():
+    """The most common fields come first, ties sorted by name."""
+    import polars as pl
+    import polars_genson
+
+    df = pl.DataFrame(
+        {"json_col": ['{"rare": 1, "b": 1}', '{"b": 2, "a": 1}', '{"a": 2, "b": 3}']}
+    )
+    schema = df.genson.infer_json_schema("json_col", sort_fields="by_frequency")
+
+    assert list(schema["properties"]) == ["b", "a", "rare"]
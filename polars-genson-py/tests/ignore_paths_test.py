@@ -0,0 +1,62 @@
+# tests/ignore_paths_test.py
+"""Tests for leaving subtrees out of inference: ignore_paths and depth/width limits."""
+
+import orjson
+import polars as pl
+import polars_genson
+
+
+def make_df():
+    return pl.DataFrame(
+        {
+            "json_data": [
+                '{"id": 1, "metadata": {"a": 1, "b": {"c": true}}}',
+                '{"id": 2, "metadata": {"d": [1, 2]}}',
+            ]
+        }
+    )
+
+
+def test_ignored_subtree_is_a_string():
+    """The ignored field's contents never reach the schema."""
+    schema = make_df().genson.infer_json_schema("json_data", ignore_paths=["metadata"])
+    assert schema["properties"]["metadata"] == {"type": "string"}
+    assert schema["properties"]["id"] == {"type": "integer"}
+
+
+def test_ignored_subtree_path():
+    """Paths only match the field at that location."""
+    df = pl.DataFrame(
+        {"json_data": ['{"metadata": {"x": 1}, "inner": {"metadata": {"y": 2}}}']}
+    )
+    schema = df.genson.infer_json_schema("json_data", ignore_paths=["/inner/metadata"])
+    props = schema["properties"]
+    assert props["inner"]["properties"]["metadata"] == {"type": "string"}
+    assert "properties" in props["metadata"]
+
+
+def test_ignored_subtree_normalises_to_json_string():
+    """Normalised rows carry the subtree as serialised JSON."""
+    out = make_df().genson.normalise_json(
+        "json_data", ignore_paths=["metadata"], decode=False
+    )
+    first = orjson.loads(out.to_list()[0])
+    assert orjson.loads(first["metadata"]) == {"a": 1, "b": {"c": True}}
+
+
+def test_max_depth_and_object_keys():
+    """Containers beyond the limits collapse to strings."""
+    df = pl.DataFrame(
+        {
+            "json_data": [
+                '{"nested": {"a": {"b": 1}}, "wide": {"a": 1, "b": 2, "c": 3}, "n": 1}'
+            ]
+        }
+    )
+    schema = df.genson.infer_json_schema(
+        "json_data", max_depth=2, max_object_keys=2
+    )
+    props = schema["properties"]
+    assert props["nested"]["properties"]["a"] == {"type": "string"}
+    assert props["wide"] == {"type": "string"}
+    assert props["n"] == {"type": "integer"}
@@ -0,0 +1,62 @@
+"""Tests for converting JSON Schema to Polars dtype objects."""
+
+import polars as pl
+import pytest
+from polars_genson import avro_to_polars_schema, json_schema_to_polars_schema
+
+
+def test_json_schema_to_polars_schema():
+    """Each property converts to a Polars dtype object."""
+    schema = {
+        "type": "object",
+        "properties": {
+            "id": {"type": "integer"},
+            "score": {"type": ["integer", "number", "null"]},
+            "tags": {"type": "array", "items": {"type": "string"}},
+            "level": {"enum": ["low", "high"]},
+            "meta": {"type": "object", "properties": {"a": {"type": "boolean"}}},
+            "labels": {"type": "object", "additionalProperties": {"type": "string"}},
+        },
+    }
+    assert json_schema_to_polars_schema(schema) == pl.Schema(
+        {
+            "id": pl.Int64,
+            "score": pl.Float64,
+            "tags": pl.List(pl.String),
+            "level": pl.Enum(["low", "high"]),
+            "meta": pl.Struct({"a": pl.Boolean}),
+            "labels": pl.List(pl.Struct({"key": pl.String, "value": pl.String})),
+        }
+    )
+
+
+def test_json_schema_string_decodes():
+    """The converted schema decodes the JSON it describes."""
+    schema = '{"type": "object", "properties": {"a": {"type": "object", "properties": {"b": {"type": "integer"}}}}}'
+    dtype = pl.Struct(json_schema_to_polars_schema(schema))
+    df = pl.DataFrame({"j": ['{"a": {"b": 1}}']})
+    decoded = df.select(pl.col("j").str.json_decode(dtype=dtype)).unnest("j")
+    assert decoded.to_dicts() == [{"a": {"b": 1}}]
+
+
+def test_avro_schema_to_polars():
+    """Avro records convert too."""
+    avro = {
+        "type": "record",
+        "name": "document",
+        "fields": [
+            {"name": "id", "type": "long"},
+            {"name": "name", "type": ["null", "string"]},
+        ],
+    }
+    expected = pl.Schema({"id": pl.Int64, "name": pl.String})
+    assert json_schema_to_polars_schema(avro, avro=True) == expected
+    assert avro_to_polars_schema('{"type": "record", "name": "r", "fields": []}') == (
+        pl.Schema({})
+    )
+
+
+def test_unsupported_type_raises():
+    """A type with no Polars equivalent is an error."""
+    with pytest.raises(ValueError, match="Unsupported JSON Schema type"):
+        json_schema_to_polars_schema({"properties": {"x": {"type": "date"}}})
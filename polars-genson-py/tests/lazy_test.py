@@ -0,0 +1,41 @@
+# tests/lazy_test.py
+"""Tests for inferring schemas from LazyFrame batches."""
+
+import polars as pl
+import pytest
+from polars_genson import SchemaBuilder, infer_json_schema_lazy
+
+
+def make_df():
+    rows = [f'{{"id": {i}, "v{i % 4}": "x"}}' for i in range(30)]
+    return pl.DataFrame({"json_data": rows})
+
+
+def test_lazy_schema_matches_collected():
+    """Streaming the column gives the schema of the collected column."""
+    df = make_df()
+    expected = df.genson.infer_json_schema("json_data")
+    assert infer_json_schema_lazy(df.lazy(), "json_data") == expected
+
+
+def test_lazy_options_apply():
+    """Inference options pass through to every batch."""
+    df = pl.DataFrame({"json_data": ['{"a": {"x": 1, "y": 2, "z": 3}}']})
+    schema = infer_json_schema_lazy(df.lazy(), "json_data", map_threshold=2)
+    assert schema["properties"]["a"]["additionalProperties"] == {"type": "integer"}
+
+
+def test_builder_accumulates_batches():
+    """A builder merges batches added one at a time."""
+    builder = SchemaBuilder()
+    assert builder.add_batch(pl.Series(['{"a": 1}', None, ""])) == 1
+    assert builder.add_batch(pl.Series(['{"b": "x"}'])) == 1
+    assert builder.processed_count == 2
+    schema = builder.schema()
+    assert schema["properties"] == {"a": {"type": "integer"}, "b": {"type": "string"}}
+
+
+def test_builder_without_documents():
+    """A builder given no documents has no schema."""
+    with pytest.raises(ValueError, match="No valid JSON strings"):
+        SchemaBuilder().schema()
@@ -141,9 +141,41 @@ def test_expression_with_merge_schemas():
             "schemas"
         )
     )
-    individual_schemas = orjson.loads(individual_result.get_column("schemas").first())
+    individual_schemas = [
+        orjson.loads(s) for s in individual_result.get_column("schemas")
+    ]
 
     # Verify types
     assert isinstance(merged_schema, dict)
-    assert isinstance(individual_schemas, list)
-    assert len(individual_schemas) == 2
+    assert individual_result.height == 2
+    assert all(isinstance(s, dict) for s in individual_schemas)
+
+
+def test_individual_schemas_align_with_rows():
+    """Test merge_schemas=False gives each row its own schema, null for null rows."""
+    df = pl.DataFrame(
+        {
+            "json_data": [
+                '{"a": 1, "b": 2}',
+                None,
+                '{"a": 1, "c": true}',
+                '{"a": 5, "b": 6}',
+            ]
+        }
+    )
+
+    result = df.with_columns(
+        polars_genson.infer_json_schema(
+            pl.col("json_data"), merge_schemas=False
+        ).alias("schema")
+    )
+    schemas = result.get_column("schema").to_list()
+    assert schemas[1] is None
+    assert orjson.loads(schemas[0])["required"] == ["a", "b"]
+    assert orjson.loads(schemas[2])["required"] == ["a", "c"]
+
+    # Rows with the same shape share a schema, so the variants can be grouped
+    variants = result.drop_nulls("schema").group_by("schema").len()
+    assert sorted(variants.get_column("len").to_list()) == [1, 2]
+
+    assert df.genson.infer_json_schema("json_data", merge_schemas=False)[1] is None
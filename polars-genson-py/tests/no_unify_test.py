@@ -0,0 +1,58 @@
+"""Tests for excluding fields from unification in the DataFrame workflow."""
+
+import polars as pl
+import polars_genson  # noqa: F401
+import pytest
+
+DOC = '{"claims": {"a": {"x": 1}, "references": {"y": "s"}}}'
+
+
+def test_unified_without_no_unify():
+    """Without no_unify the sibling records are unified into a map."""
+    df = pl.DataFrame({"json_data": [DOC]})
+
+    schema = df.genson.infer_json_schema(
+        "json_data", unify_maps=True, map_threshold=1
+    )
+    assert "additionalProperties" in schema["properties"]["claims"]
+
+
+@pytest.mark.parametrize(
+    "no_unify", [["references"], {"references"}, ["/claims/references"]]
+)
+def test_no_unify_by_name_or_path(no_unify):
+    """A field listed by name or by path keeps its parent a record."""
+    df = pl.DataFrame({"json_data": [DOC]})
+
+    schema = df.genson.infer_json_schema(
+        "json_data", unify_maps=True, map_threshold=1, no_unify=no_unify
+    )
+    claims = schema["properties"]["claims"]
+    assert list(claims["properties"]) == ["a", "references"]
+
+
+def test_no_unify_in_normalise_json():
+    """normalise_json passes no_unify on to inference."""
+    df = pl.DataFrame({"json_data": [DOC]})
+
+    _, schema = df.genson.normalise_json(
+        "json_data",
+        decode=False,
+        unify_maps=True,
+        map_threshold=1,
+        no_unify=["references"],
+        with_schema=True,
+    )
+    claims = schema["fields"][0]["type"]
+    assert claims["type"] == "record"
+
+
+def test_force_scalar_promotion_by_path():
+    """Scalar promotion can be forced for a field given by path."""
+    df = pl.DataFrame({"json_data": ['{"value": {"amount": 1}}']})
+
+    schema = df.genson.infer_json_schema(
+        "json_data", force_scalar_promotion=["/value/amount"]
+    )
+    amount = schema["properties"]["value"]["properties"]["amount"]
+    assert amount["type"] == "object"
@@ -4,6 +4,7 @@
 import orjson
 import polars as pl
 import polars_genson
+import pytest
 
 
 def test_empty_array_becomes_null_by_default():
@@ -374,3 +375,75 @@ def test_normalise_with_wrap_root_true_uses_column_name():
     out = df.genson.normalise_json("json_data", decode=False, wrap_root=True).to_list()
 
     assert out == ['{"json_data":{"foo":"bar"}}']
+
+
+def test_normalise_with_wrap_root_dotted_path():
+    """A dotted wrap_root nests each row one level per segment."""
+    df = pl.DataFrame({"json_data": ['{"foo": "bar"}']})
+    out = df.genson.normalise_json(
+        "json_data", decode=False, wrap_root="entity.claims"
+    ).to_list()
+
+    assert out == ['{"entity":{"claims":{"foo":"bar"}}}']
+
+
+def test_wrap_root_per_column():
+    """A wrap_root mapping gives each column its own root, by column name."""
+    df = pl.DataFrame(
+        {
+            "labels_json": ['{"en": "Hello"}'],
+            "claims_json": ['{"P31": "Q5"}'],
+            "other_json": ['{"x": 1}'],
+        }
+    )
+    roots = {"labels_json": "entity.labels", "claims_json": "entity.claims"}
+
+    labels = df.genson.normalise_json(
+        "labels_json", decode=False, wrap_root=roots
+    ).to_list()
+    claims = df.select(
+        polars_genson.normalise_json(pl.col("claims_json"), wrap_root=roots)
+    ).to_series()
+    other = df.genson.normalise_json(
+        "other_json", decode=False, wrap_root=roots
+    ).to_list()
+
+    assert labels == ['{"entity":{"labels":{"en":"Hello"}}}']
+    assert claims.to_list() == ['{"entity":{"claims":{"P31":"Q5"}}}']
+    assert other == ['{"x":1}']
+
+
+def test_normalise_unknown_fields():
+    """Fields missing from a sampled schema are dropped, kept or raise an error."""
+    df = pl.DataFrame({"json_data": ['{"id": 1}', '{"id": 2, "extra": "x"}']})
+
+    dropped = df.genson.normalise_json("json_data", decode=False, sample=1).to_list()
+    kept = df.genson.normalise_json(
+        "json_data", decode=False, sample=1, unknown_fields="keep"
+    ).to_list()
+
+    assert dropped == ['{"id":1}', '{"id":2}']
+    assert kept == ['{"id":1}', '{"id":2,"extra":"x"}']
+
+    with pytest.raises(pl.exceptions.ComputeError, match="Unknown field 'extra'"):
+        df.genson.normalise_json(
+            "json_data", decode=False, sample=1, unknown_fields="error"
+        )
+
+
+def test_column_template_names_unnested_fields():
+    """Unnested fields are named by the template."""
+    df = pl.DataFrame({"labels": ['{"en": "cat", "fr": "chat"}', '{"en": "dog"}']})
+
+    out = df.genson.normalise_json("labels", column_template="{column}__{field}")
+
+    assert out.columns == ["labels__en", "labels__fr"]
+    assert out["labels__fr"].to_list() == ["chat", None]
+
+
+def test_column_template_requires_field():
+    """A template without {field} would give every column the same name."""
+    df = pl.DataFrame({"labels": ['{"en": "cat"}']})
+
+    with pytest.raises(ValueError, match="must contain"):
+        df.genson.normalise_json("labels", column_template="{column}")
@@ -0,0 +1,27 @@
+"""Tests for returning the Avro schema alongside normalised data."""
+
+import polars as pl
+import polars_genson  # noqa: F401
+
+
+def test_with_schema_returns_avro_schema():
+    """The schema returned is the Avro schema the rows were normalised against."""
+    df = pl.DataFrame({"json_data": ['{"id": 1, "tags": ["a"]}', '{"id": 2}']})
+
+    out, schema = df.genson.normalise_json(
+        "json_data", decode=False, with_schema=True
+    )
+    assert out.to_list() == ['{"id":1,"tags":["a"]}', '{"id":2,"tags":null}']
+    assert schema["type"] == "record"
+    assert [field["name"] for field in schema["fields"]] == ["id", "tags"]
+
+
+def test_with_schema_decodes_with_returned_schema():
+    """Decoding uses the returned schema, matching the plain decoded result."""
+    df = pl.DataFrame({"json_data": ['{"id": 1, "name": "x"}', '{"id": 2}']})
+
+    decoded, schema = df.genson.normalise_json("json_data", with_schema=True)
+    expected = df.genson.normalise_json("json_data")
+    assert decoded.equals(expected)
+    assert decoded.columns == ["id", "name"]
+    assert schema["fields"][0]["name"] == "id"
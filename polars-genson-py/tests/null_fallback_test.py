@@ -0,0 +1,31 @@
+"""Tests for typing null-only fields with null_fallback."""
+
+import polars as pl
+import polars_genson  # noqa: F401
+
+
+def test_null_fallback_types_null_only_field():
+    """A field that is always null gets the fallback type, made nullable."""
+    df = pl.DataFrame(
+        {"json_col": ['{"id": 1, "note": null}', '{"id": 2, "note": null}']}
+    )
+
+    schema = df.genson.infer_json_schema("json_col", null_fallback="string")
+    assert schema["properties"]["note"]["type"] == ["null", "string"]
+    assert "note" not in schema.get("required", [])
+
+
+def test_null_fallback_unset_keeps_null():
+    """Without null_fallback the field stays typed null."""
+    df = pl.DataFrame({"json_col": ['{"note": null}']})
+
+    schema = df.genson.infer_json_schema("json_col")
+    assert schema["properties"]["note"]["type"] == "null"
+
+
+def test_null_fallback_avro():
+    """The fallback type carries through to the Avro schema."""
+    df = pl.DataFrame({"json_col": ['{"note": null}']})
+
+    schema = df.genson.infer_json_schema("json_col", null_fallback="boolean", avro=True)
+    assert schema["fields"][0]["type"] == ["null", "boolean"]
@@ -0,0 +1,42 @@
+"""Tests for writing inferred schemas as YAML or TOML."""
+
+import polars as pl
+import polars_genson  # noqa: F401
+import pytest
+from polars_genson import infer_from_parquet
+
+
+def test_yaml_format():
+    """The schema comes back as YAML text."""
+    df = pl.DataFrame({"json_col": ['{"id": 1, "name": "Alice"}']})
+
+    schema = df.genson.infer_json_schema("json_col", format="yaml")
+    assert isinstance(schema, str)
+    assert schema.startswith("$schema:")
+    assert "type: integer" in schema
+
+
+def test_toml_format():
+    """The schema comes back as TOML text."""
+    df = pl.DataFrame({"json_col": ['{"id": 1}']})
+
+    schema = df.genson.infer_json_schema("json_col", format="toml")
+    assert "[properties.id]" in schema
+
+
+def test_format_needs_merged_schema():
+    """Per-row schemas are only returned as dictionaries."""
+    df = pl.DataFrame({"json_col": ['{"id": 1}']})
+
+    with pytest.raises(ValueError, match="merged schema"):
+        df.genson.infer_json_schema("json_col", merge_schemas=False, format="yaml")
+
+
+def test_parquet_yaml_output(tmp_path):
+    """infer_from_parquet writes the schema file in the requested format."""
+    parquet_path = tmp_path / "data.parquet"
+    pl.DataFrame({"claims": ['{"id": 1}']}).write_parquet(parquet_path)
+    output_path = tmp_path / "schema.yaml"
+
+    infer_from_parquet(parquet_path, "claims", output_path, format="yaml")
+    assert "type: integer" in output_path.read_text()
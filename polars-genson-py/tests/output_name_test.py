@@ -0,0 +1,83 @@
+# tests/output_name_test.py
+"""Tests for output column naming and effective config introspection."""
+
+import polars as pl
+import polars_genson
+from polars_genson import infer_json_schema, infer_polars_schema, normalise_json
+
+
+def make_df():
+    return pl.DataFrame({"json_data": ['{"id": 1}', '{"id": "2"}']})
+
+
+def test_default_output_names():
+    """Without output_name the historic names are kept."""
+    df = make_df()
+    assert df.select(infer_json_schema(pl.col("json_data"))).columns == ["schema"]
+    assert df.select(normalise_json(pl.col("json_data"))).columns == ["normalised"]
+
+
+def test_output_name_json_schema():
+    """The inferred schema column takes the requested name, also in lazy plans."""
+    lf = make_df().lazy().select(
+        infer_json_schema(pl.col("json_data"), output_name="json_schema")
+    )
+    assert lf.collect_schema().names() == ["json_schema"]
+    assert lf.collect().columns == ["json_schema"]
+
+
+def test_output_name_polars_schema():
+    """The Polars schema column takes the requested name."""
+    df = make_df().select(
+        infer_polars_schema(pl.col("json_data"), output_name="fields")
+    )
+    assert df.columns == ["fields"]
+
+
+def test_output_name_normalise():
+    """Normalised output can keep the source column name."""
+    df = make_df().with_columns(
+        normalise_json(pl.col("json_data"), output_name="json_data")
+    )
+    assert df.columns == ["json_data"]
+    assert df.height == 2
+
+
+def test_effective_config():
+    """Options resolve to the inference and normalisation configs."""
+    config = polars_genson.effective_config(
+        map_threshold=5,
+        no_unify={"b", "a"},
+        coerce_strings=True,
+        wrap_root="doc",
+        output_name="out",
+    )
+    assert config["output_name"] == "out"
+    assert config["inference"]["map_threshold"] == 5
+    assert sorted(config["inference"]["no_unify"]) == ["a", "b"]
+    assert config["inference"]["wrap_root"] == "doc"
+    assert config["normalisation"]["coerce_string"] is True
+    assert config["normalisation"]["wrap_root"] == "doc"
+
+
+def test_effective_config_defaults():
+    """Omitted options take the plugin defaults."""
+    config = polars_genson.effective_config()
+    assert config["output_name"] is None
+    assert config["inference"]["map_threshold"] == 20
+    assert config["inference"]["ignore_outer_array"] is True
+    assert config["normalisation"]["empty_as_null"] is True
+
+
+def test_embed_config():
+    """Non-default settings are recorded in the schema."""
+    schema = make_df().genson.infer_json_schema(
+        "json_data", map_threshold=5, embed_config=True
+    )
+    assert schema["x-genson-config"]["map_threshold"] == 5
+    assert "debug" not in schema["x-genson-config"]
+
+
+def test_no_embedded_config_by_default():
+    schema = make_df().genson.infer_json_schema("json_data")
+    assert "x-genson-config" not in schema
@@ -85,6 +85,16 @@ class TestDtypeParsing:
         assert isinstance(struct_type, pl.Struct)
         assert len(struct_type.fields) == 0
 
+    def test_enum_types(self):
+        """Test parsing of Enum with JSON-encoded categories."""
+        assert _parse_polars_dtype('Enum["low","mid","high"]') == pl.Enum(
+            ["low", "mid", "high"]
+        )
+        struct_type = _parse_polars_dtype('Struct[level:Enum["a,b","c]"],n:Int64]')
+        assert struct_type == pl.Struct(
+            {"level": pl.Enum(["a,b", "c]"]), "n": pl.Int64}
+        )
+
 
 # Integration tests for the full infer_polars_schema pipeline
 class TestPolarsSchemaInference:
@@ -112,6 +122,24 @@ class TestPolarsSchemaInference:
             }
         )
 
+    def test_return_nullable(self):
+        """Test the nullable columns are those missing or null in some row."""
+        df = pl.DataFrame(
+            {
+                "json_col": [
+                    '{"id": 1, "name": "Alice", "email": "a@x"}',
+                    '{"id": 2, "name": null}',
+                ]
+            }
+        )
+
+        schema, nullable = df.genson.infer_polars_schema(
+            "json_col", return_nullable=True
+        )
+
+        assert schema.names() == ["id", "name", "email"]
+        assert nullable == ["name", "email"]
+
     def test_mixed_types(self):
         """Test with mixed JSON types including floats and booleans."""
         df = pl.DataFrame(
@@ -373,3 +401,23 @@ class TestPolarsSchemaInference:
         )
 
         assert schema == expected
+
+    def test_map_fields(self):
+        """Map fields become lists of key/value structs, as in the kv encoding."""
+        df = pl.DataFrame(
+            {
+                "json_col": [
+                    '{"id": 1, "scores": {"a": 1, "b": 2}}',
+                    '{"id": 2, "scores": {"c": 3}}',
+                ]
+            }
+        )
+
+        schema = df.genson.infer_polars_schema("json_col", map_threshold=1)
+
+        assert schema == pl.Schema(
+            {
+                "id": pl.Int64,
+                "scores": pl.List(pl.Struct({"key": pl.String, "value": pl.Int64})),
+            }
+        )
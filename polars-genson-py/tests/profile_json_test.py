@@ -0,0 +1,46 @@
+# tests/profile_json_test.py
+"""Tests for structured profiling output."""
+
+import orjson
+import polars as pl
+from polars_genson import infer_json_schema
+
+STAGES = [
+    "parse_ms",
+    "build_ms",
+    "rewrite_objects_ms",
+    "avro_ms",
+    "total_ms",
+    "document_p50_ms",
+    "document_p99_ms",
+]
+
+
+def make_df():
+    return pl.DataFrame({"json_data": [f'{{"id": {i}}}' for i in range(20)]})
+
+
+def test_profile_json_namespace():
+    """The namespace method returns the schema and a timings dict."""
+    schema, profile = make_df().genson.infer_json_schema("json_data", profile_json=True)
+    assert schema["properties"]["id"] == {"type": "integer"}
+    assert profile["documents"] == 20
+    for stage in STAGES:
+        assert profile[stage] >= 0
+    assert profile["document_p50_ms"] <= profile["document_p99_ms"]
+
+
+def test_profile_json_expression():
+    """The expression outputs both parts as one JSON object."""
+    out = make_df().select(
+        infer_json_schema(pl.col("json_data"), profile_json=True, avro=True)
+    )
+    parsed = orjson.loads(out.item())
+    assert parsed["schema"]["type"] == "record"
+    assert parsed["profile"]["avro_ms"] >= 0
+
+
+def test_no_profile_by_default():
+    """Without profile_json only the schema is returned."""
+    schema = make_df().genson.infer_json_schema("json_data")
+    assert "profile" not in schema
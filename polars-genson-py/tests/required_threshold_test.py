@@ -0,0 +1,31 @@
+"""Tests for presence-based required fields with required_threshold."""
+
+import polars as pl
+import polars_genson  # noqa: F401
+
+
+def noisy_df() -> pl.DataFrame:
+    """Nine rows with id and name, and one without name."""
+    rows = ['{"id": 1, "name": "a"}'] * 9 + ['{"id": 2}']
+    return pl.DataFrame({"json_col": rows})
+
+
+def test_missing_field_is_optional_by_default():
+    """A field missing from one row is optional without a threshold."""
+    schema = noisy_df().genson.infer_json_schema("json_col")
+    assert schema["required"] == ["id"]
+
+
+def test_required_threshold_keeps_field_required():
+    """A field in 90% of rows stays required with a 0.9 threshold."""
+    schema = noisy_df().genson.infer_json_schema("json_col", required_threshold=0.9)
+    assert schema["required"] == ["id", "name"]
+
+
+def test_required_threshold_avro():
+    """Required fields aren't nullable in Avro."""
+    schema = noisy_df().genson.infer_json_schema(
+        "json_col", avro=True, required_threshold=0.9
+    )
+    fields = {field["name"]: field["type"] for field in schema["fields"]}
+    assert fields["name"] == "string"
@@ -0,0 +1,47 @@
+# tests/sample_test.py
+"""Tests for sampled schema inference."""
+
+import polars as pl
+import polars_genson
+import pytest
+
+
+def make_df():
+    rows = ['{"id": 1}'] * 5 + ['{"id": 2, "late": true}'] * 5
+    return pl.DataFrame({"json_data": rows})
+
+
+def test_sample_first():
+    """Only the first rows are seen."""
+    schema = make_df().genson.infer_json_schema("json_data", sample=5)
+    assert "late" not in schema["properties"]
+
+
+def test_sample_every():
+    """Every kth row is seen."""
+    schema = make_df().genson.infer_json_schema("json_data", sample_every=3)
+    assert "late" in schema["properties"]
+
+
+def test_sample_random_reproducible():
+    """The same seed gives the same schema."""
+    df = make_df()
+    a = df.genson.infer_json_schema("json_data", sample=(3, 42))
+    b = df.genson.infer_json_schema("json_data", sample=(3, 42))
+    assert a == b
+
+
+def test_sample_normalises_all_rows():
+    """Sampling only affects inference: every row is still normalised."""
+    out = make_df().genson.normalise_json("json_data", sample=5)
+    assert len(out) == 10
+
+
+def test_sample_options_exclusive():
+    with pytest.raises(ValueError, match="cannot be combined"):
+        make_df().genson.infer_json_schema("json_data", sample=2, sample_every=2)
+
+
+def test_effective_config_sample():
+    config = polars_genson.effective_config(sample=(10, 1))
+    assert config["inference"]["sample"] == {"random": {"n": 10, "seed": 1}}
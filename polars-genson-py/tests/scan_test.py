@@ -0,0 +1,50 @@
+"""Test lazily reading NDJSON files with scan_json_normalised."""
+
+import polars as pl
+import pytest
+from polars_genson import scan_json_normalised
+
+
+@pytest.fixture
+def ndjson_path(tmp_path):
+    path = tmp_path / "data.jsonl"
+    path.write_text(
+        "\n".join(
+            [
+                '{"id": 1, "name": "a", "tags": ["x"]}',
+                '{"id": 2, "tags": []}',
+                "",
+                '{"id": 3, "name": "c", "tags": ["y", "z"]}',
+            ]
+        )
+        + "\n"
+    )
+    return path
+
+
+def test_scan_schema_is_known_up_front(ndjson_path):
+    """The inferred schema is available without collecting."""
+    lf = scan_json_normalised(ndjson_path)
+    assert lf.collect_schema() == pl.Schema(
+        {"id": pl.Int64, "name": pl.String, "tags": pl.List(pl.String)}
+    )
+
+
+def test_scan_collects_normalised_rows(ndjson_path):
+    """Rows are normalised: missing fields and empty arrays become null."""
+    df = scan_json_normalised(ndjson_path, batch_size=2).collect()
+    assert df.to_dicts() == [
+        {"id": 1, "name": "a", "tags": ["x"]},
+        {"id": 2, "name": None, "tags": None},
+        {"id": 3, "name": "c", "tags": ["y", "z"]},
+    ]
+
+
+def test_scan_pushdown(ndjson_path):
+    """Projections, filters and row limits apply to the scanned batches."""
+    lf = scan_json_normalised(ndjson_path, batch_size=1)
+    assert lf.filter(pl.col("id") > 1).select("id").collect()["id"].to_list() == [
+        2,
+        3,
+    ]
+    assert lf.head(2).collect().height == 2
@@ -35,3 +35,32 @@ def test_schema_uri_custom_string():
     schema = df.genson.infer_json_schema("json_data", schema_uri=custom_uri)
 
     assert schema["$schema"] == custom_uri
+
+
+@mark.parametrize(
+    "draft,uri",
+    [
+        ("draft-07", "http://json-schema.org/draft-07/schema#"),
+        ("2019-09", "https://json-schema.org/draft/2019-09/schema"),
+        ("2020-12", "https://json-schema.org/draft/2020-12/schema"),
+    ],
+)
+def test_json_schema_draft_sets_uri(draft, uri):
+    """Choosing a draft replaces the generic URI with the draft's."""
+    df = pl.DataFrame({"json_data": ['{"name": "Alice", "age": 30}']})
+
+    schema = df.genson.infer_json_schema("json_data", json_schema_draft=draft)
+
+    assert schema["$schema"] == uri
+    assert schema["properties"]["age"] == {"type": "integer"}
+
+
+def test_json_schema_draft_without_uri():
+    """A draft with schema_uri=None still leaves out $schema."""
+    df = pl.DataFrame({"json_data": ['{"name": "Alice"}']})
+
+    schema = df.genson.infer_json_schema(
+        "json_data", schema_uri=None, json_schema_draft="2020-12"
+    )
+
+    assert "$schema" not in schema
@@ -0,0 +1,63 @@
+# tests/struct_input_test.py
+"""Tests for schema inference and normalisation from already-decoded columns."""
+
+import json
+
+import polars as pl
+from polars_genson import infer_json_schema, normalise_json
+
+
+def test_struct_column_matches_string_column():
+    """A Struct column infers the same schema as its JSON text."""
+    rows = ['{"id": 1, "name": "a"}', '{"id": 2, "name": null}']
+    text = pl.DataFrame({"data": rows})
+    decoded = text.select(pl.col("data").str.json_decode())
+    assert decoded.schema["data"] == pl.Struct({"id": pl.Int64, "name": pl.String})
+
+    assert decoded.genson.infer_json_schema("data") == text.genson.infer_json_schema(
+        "data"
+    )
+
+
+def test_list_column_schema():
+    """Each row of a List column is an array document."""
+    df = pl.DataFrame({"data": [[1, 2], [3]]})
+    schema = df.genson.infer_json_schema("data", ignore_outer_array=False)
+    assert schema["type"] == "array"
+    assert schema["items"] == {"type": "integer"}
+
+
+def test_struct_with_nested_list():
+    """Nested lists inside structs become arrays."""
+    df = pl.DataFrame({"data": [{"id": 1, "tags": ["a", "b"]}, {"id": 2, "tags": []}]})
+    schema = df.genson.infer_json_schema("data")
+    assert schema["properties"]["tags"] == {
+        "type": "array",
+        "items": {"type": "string"},
+    }
+
+
+def test_temporal_fields_become_strings():
+    """Dates are serialised as ISO strings."""
+    from datetime import date
+
+    df = pl.DataFrame({"data": [{"day": date(2024, 1, 2)}]})
+    schema = df.genson.infer_json_schema("data")
+    assert schema["properties"]["day"] == {"type": "string"}
+
+
+def test_normalise_struct_column():
+    """Normalisation works from a Struct column, null rows stay null."""
+    df = pl.DataFrame({"data": [{"id": 1, "name": "a"}, None]})
+    out = df.select(normalise_json(pl.col("data")))
+    rows = [json.loads(r) for r in out.to_series()]
+    assert rows[0] == {"id": 1, "name": "a"}
+    assert rows[1] is None
+
+
+def test_expression_accepts_struct():
+    """The plain expression functions accept Struct columns too."""
+    df = pl.DataFrame({"data": [{"id": 1}]})
+    out = df.select(infer_json_schema(pl.col("data")))
+    schema = json.loads(out.item())
+    assert schema["properties"]["id"] == {"type": "integer"}
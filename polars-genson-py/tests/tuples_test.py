@@ -0,0 +1,36 @@
+"""Tests for detecting positional tuples with detect_tuples."""
+
+import polars as pl
+import polars_genson  # noqa: F401
+
+
+def test_detect_tuples_prefix_items():
+    """Fixed-length arrays with a type per position get prefixItems."""
+    df = pl.DataFrame(
+        {"json_col": ['{"pair": ["a", {"x": 1}]}', '{"pair": ["b", {"x": 2}]}']}
+    )
+
+    schema = df.genson.infer_json_schema("json_col", detect_tuples=True)
+    pair = schema["properties"]["pair"]
+    assert "items" not in pair
+    assert pair["prefixItems"][0] == {"type": "string"}
+    assert pair["prefixItems"][1]["properties"]["x"] == {"type": "integer"}
+
+
+def test_detect_tuples_off_by_default():
+    """Without detect_tuples the items are merged into one schema."""
+    df = pl.DataFrame({"json_col": ['{"pair": ["a", 1]}']})
+
+    schema = df.genson.infer_json_schema("json_col")
+    assert "prefixItems" not in schema["properties"]["pair"]
+
+
+def test_detect_tuples_normalise():
+    """Tuples normalise to structs of positional fields."""
+    df = pl.DataFrame({"json_col": ['{"pair": ["a", 1]}', '{"pair": ["b", 2]}']})
+
+    result = df.genson.normalise_json("json_col", detect_tuples=True)
+    assert result.to_dicts() == [
+        {"pair": {"_0": "a", "_1": 1}},
+        {"pair": {"_0": "b", "_1": 2}},
+    ]
@@ -0,0 +1,45 @@
+"""Tests for decoding normalised JSON by its Avro schema with unnest_json."""
+
+import polars as pl
+import polars_genson  # noqa: F401
+import pytest
+
+ROWS = [
+    '{"id": 1, "labels": {"en": "Hello", "fr": "Bonjour"}}',
+    '{"id": 2, "labels": {"de": "Hallo"}}',
+]
+
+MAP_DTYPE = pl.List(pl.Struct({"key": pl.String, "value": pl.String}))
+
+
+@pytest.mark.parametrize("map_encoding", ["kv", "mapping", "entries"])
+def test_unnest_json_maps_decode_to_key_value_lists(map_encoding):
+    """Maps decode to key/value lists whichever encoding they were written in."""
+    df = pl.DataFrame({"json_data": ROWS})
+    normalised, schema = df.genson.normalise_json(
+        "json_data",
+        decode=False,
+        with_schema=True,
+        map_threshold=1,
+        map_encoding=map_encoding,
+    )
+
+    result = normalised.to_frame().genson.unnest_json(
+        normalised.name, schema, map_encoding=map_encoding
+    )
+    assert result.columns == ["id", "labels"]
+    assert result.schema["labels"] == MAP_DTYPE
+    assert result["labels"].to_list()[1] == [{"key": "de", "value": "Hallo"}]
+
+
+def test_unnest_json_expression_keeps_struct():
+    """The expression gives one struct column, records as struct fields."""
+    df = pl.DataFrame({"json_data": ['{"id": 1, "owner": {"name": "a"}}']})
+    normalised, schema = df.genson.normalise_json(
+        "json_data", decode=False, with_schema=True
+    )
+
+    result = normalised.to_frame().select(
+        polars_genson.unnest_json(pl.col(normalised.name), schema)
+    )
+    assert result.to_series().to_list() == [{"id": 1, "owner": {"name": "a"}}]
@@ -0,0 +1,28 @@
+# tests/value_limits_test.py
+"""Tests for capping normalised values with value_limits."""
+
+import polars as pl
+
+
+def test_value_limits_truncate():
+    """Long strings and arrays are cut, deep values nulled."""
+    df = pl.DataFrame(
+        {"json_data": ['{"s": "abcdefghij", "a": [1, 2, 3, 4], "o": {"p": {"q": 1}}}']}
+    )
+    out = df.genson.normalise_json(
+        "json_data",
+        decode=False,
+        value_limits={"max_string_len": 4, "max_array_items": 2, "max_depth": 3},
+    ).to_list()
+    assert out == ['{"s":"abcd...[truncated]","a":[1,2],"o":{"p":{"q":null}}}']
+
+
+def test_value_limits_marker():
+    """The marker is configurable."""
+    df = pl.DataFrame({"json_data": ['{"s": "abcdefghij"}']})
+    out = df.genson.normalise_json(
+        "json_data",
+        decode=False,
+        value_limits={"max_string_len": 2, "marker": ""},
+    ).to_list()
+    assert out == ['{"s":"ab"}']
@@ -0,0 +1,67 @@
+"""Test writing normalised output directly to files."""
+
+import json
+
+import polars as pl
+import pytest
+from polars_genson import read_parquet_metadata
+
+
+@pytest.fixture
+def df():
+    return pl.DataFrame(
+        {
+            "json_data": [
+                '{"id": 1, "tags": ["a"], "labels": {"en": "one"}}',
+                '{"id": 2, "tags": [], "labels": {"fr": "deux"}}',
+                None,
+            ]
+        }
+    )
+
+
+def test_write_normalised_parquet(df, tmp_path):
+    """Parquet output holds the same rows as normalise_json, plus schema metadata."""
+    path = tmp_path / "out.parquet"
+    written = df.genson.write_normalised(path, map_threshold=1)
+    assert written == 3
+
+    expected = df.genson.normalise_json("json_data", decode=False, map_threshold=1)
+    result = pl.read_parquet(path)
+    assert result.columns == ["json_data"]
+    assert result.to_series().to_list() == expected.to_list()
+
+    metadata = read_parquet_metadata(path)
+    assert json.loads(metadata["genson_avro_schema"])["type"] == "record"
+
+
+def test_write_normalised_ndjson(df, tmp_path):
+    """NDJSON output writes one normalised document per input row."""
+    path = tmp_path / "out.ndjson"
+    df.genson.write_normalised(path, format="ndjson", empty_as_null=False)
+
+    rows = [json.loads(line) for line in path.read_text().splitlines()]
+    assert len(rows) == 3
+    assert rows[0]["tags"] == ["a"]
+    assert rows[1]["tags"] == []
+    assert rows[2] is None
+
+
+def test_write_normalised_output_column(df, tmp_path):
+    """The Parquet column can be renamed and written in small row groups."""
+    path = tmp_path / "out.parquet"
+    df.genson.write_normalised(path, output_column="normalised", batch_size=1)
+    assert pl.read_parquet(path).columns == ["normalised"]
+
+
+def test_write_normalised_requires_column_for_wide_frames(df, tmp_path):
+    """The column must be named when the DataFrame has several."""
+    wide = df.with_columns(pl.lit(1).alias("other"))
+    with pytest.raises(ValueError, match="column must be given"):
+        wide.genson.write_normalised(tmp_path / "out.parquet")
+
+
+def test_write_normalised_rejects_unknown_format(df, tmp_path):
+    """Unknown formats are rejected before anything is written."""
+    with pytest.raises(ValueError, match="Invalid format"):
+        df.genson.write_normalised(tmp_path / "out.csv", format="csv")
@@ -1,8 +1,9 @@
 //! Convert JSON Schema to Polars types.
 
-use crate::types::conversion_error;
+use crate::types::{conversion_error, MAP_KEY_TYPE_KEY, NUMERIC_DTYPES, POLARS_DTYPE_KEY};
 use polars::prelude::*;
-use serde_json::Value;
+use serde_json::{Map, Value};
+use std::borrow::Cow;
 
 /// The type of schema to be deserialised to Polars schema.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -11,6 +12,18 @@ pub enum SchemaFormat {
     Avro,
 }
 
+/// A top-level field converted to Polars, with whether its column may hold nulls.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PolarsField {
+    pub name: String,
+    /// The Polars DataType string representation, as [`json_type_to_polars_type`] gives
+    pub dtype: String,
+    /// Whether the field may be missing or null. A JSON Schema field is non-nullable
+    /// when it is listed in `required` and its type doesn't admit `null`; an Avro
+    /// field when its type isn't `null` or a union with `null`.
+    pub nullable: bool,
+}
+
 /// Convert JSON schema to Polars field mappings.
 ///
 /// Returns a vector of (field_name, dtype_string) pairs that can be used
@@ -23,6 +36,21 @@ pub fn schema_to_polars_fields(
     format: SchemaFormat,
     debug: bool,
 ) -> Result<Vec<(String, String)>, PolarsError> {
+    Ok(
+        schema_to_polars_fields_with_nullability(schema, format, debug)?
+            .into_iter()
+            .map(|field| (field.name, field.dtype))
+            .collect(),
+    )
+}
+
+/// Convert JSON schema to Polars fields, like [`schema_to_polars_fields`], along
+/// with whether each field is nullable, so non-null columns can be enforced.
+pub fn schema_to_polars_fields_with_nullability(
+    schema: &Value,
+    format: SchemaFormat,
+    debug: bool,
+) -> Result<Vec<PolarsField>, PolarsError> {
     if debug {
         anstream::eprintln!("=== Generated Schema ({:?}) ===", format);
         anstream::eprintln!(
@@ -40,37 +68,125 @@ pub fn schema_to_polars_fields(
 }
 
 /// Convert JSON Schema object to Polars field mappings.
-fn json_schema_to_polars_fields(json_schema: &Value) -> Result<Vec<(String, String)>, PolarsError> {
+fn json_schema_to_polars_fields(json_schema: &Value) -> Result<Vec<PolarsField>, PolarsError> {
+    let json_schema = merge_all_of(json_schema);
     let mut fields = Vec::new();
+    let required: Vec<&str> = json_schema
+        .get("required")
+        .and_then(|r| r.as_array())
+        .map(|r| r.iter().filter_map(|name| name.as_str()).collect())
+        .unwrap_or_default();
     if let Some(properties) = json_schema.get("properties").and_then(|p| p.as_object()) {
         for (field_name, field_schema) in properties {
             let polars_type = json_type_to_polars_type(field_schema)?;
-            fields.push((field_name.clone(), polars_type));
+            fields.push(PolarsField {
+                name: field_name.clone(),
+                dtype: polars_type,
+                nullable: !required.contains(&field_name.as_str())
+                    || json_type_admits_null(field_schema),
+            });
         }
     }
     Ok(fields)
 }
 
 /// Convert Avro record schema to Polars field mappings.
-fn avro_schema_to_polars_fields(avro_schema: &Value) -> Result<Vec<(String, String)>, PolarsError> {
+fn avro_schema_to_polars_fields(avro_schema: &Value) -> Result<Vec<PolarsField>, PolarsError> {
     let mut fields = Vec::new();
     if let Some(avro_fields) = avro_schema.get("fields").and_then(|f| f.as_array()) {
         for f in avro_fields {
             if let (Some(name), Some(field_type)) = (f.get("name"), f.get("type")) {
                 let fname = name.as_str().unwrap_or("").to_string();
                 let ftype = avro_type_to_polars_type(field_type)?;
-                fields.push((fname, ftype));
+                fields.push(PolarsField {
+                    name: fname,
+                    dtype: ftype,
+                    nullable: avro_type_admits_null(field_type),
+                });
             }
         }
     }
     Ok(fields)
 }
 
+/// Whether a JSON Schema type admits `null`: as its type, one of its type names,
+/// a union branch, or an `enum` value.
+fn json_type_admits_null(json_schema: &Value) -> bool {
+    let in_union = ["anyOf", "oneOf"].iter().any(|key| {
+        json_schema
+            .get(*key)
+            .and_then(|u| u.as_array())
+            .is_some_and(|branches| branches.iter().any(json_type_admits_null))
+    });
+    let in_enum = json_schema
+        .get("enum")
+        .and_then(|e| e.as_array())
+        .is_some_and(|values| values.iter().any(Value::is_null));
+    in_union
+        || in_enum
+        || match json_schema.get("type") {
+            Some(Value::String(name)) => name == "null",
+            Some(Value::Array(names)) => names.iter().any(|name| name == "null"),
+            _ => false,
+        }
+}
+
+/// Whether an Avro type is `null` or a union with `null`.
+fn avro_type_admits_null(avro_type: &Value) -> bool {
+    match avro_type {
+        Value::String(name) => name == "null",
+        Value::Array(branches) => branches.iter().any(avro_type_admits_null),
+        Value::Object(obj) => obj.get("type").is_some_and(|t| t == "null"),
+        _ => false,
+    }
+}
+
 /// Convert a JSON Schema type definition to Polars DataType string representation.
+///
+/// Unions (`anyOf`, `oneOf` or a list of `type` names) convert as described in
+/// [`union_to_polars_type`]. Integers and numbers take the dtype recorded under
+/// `x-polars-dtype` if there is one. An `enum` of strings becomes an `Enum` of those
+/// categories, written `Enum["a","b"]` (the categories as a JSON array). The members
+/// of an `allOf` are merged first, as described in [`merge_all_of`].
 pub fn json_type_to_polars_type(json_schema: &Value) -> Result<String, PolarsError> {
+    let json_schema = &*merge_all_of(json_schema);
+    if let Some(categories) = string_enum(json_schema) {
+        let categories = serde_json::to_string(&categories)
+            .map_err(|e| conversion_error(format!("Invalid enum: {}", e)))?;
+        return Ok(format!("Enum{}", categories));
+    }
+
+    let union = json_schema
+        .get("anyOf")
+        .or_else(|| json_schema.get("oneOf"))
+        .and_then(|u| u.as_array());
+    if let Some(branches) = union {
+        let types = branches
+            .iter()
+            .map(json_type_to_polars_type)
+            .collect::<Result<Vec<_>, _>>()?;
+        return Ok(union_to_polars_type(types));
+    }
+
+    if let Some(Value::Array(type_names)) = json_schema.get("type") {
+        // Each branch keeps the rest of the schema, e.g. `items` for "array"
+        let types = type_names
+            .iter()
+            .map(|name| {
+                let mut branch = json_schema.clone();
+                branch["type"] = name.clone();
+                json_type_to_polars_type(&branch)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        return Ok(union_to_polars_type(types));
+    }
+
     if let Some(type_value) = json_schema.get("type") {
         match type_value.as_str() {
             Some("string") => Ok("String".to_string()),
+            Some("integer" | "number") if json_schema.get(POLARS_DTYPE_KEY).is_some() => {
+                polars_dtype_extension(json_schema)
+            }
             Some("integer") => Ok("Int64".to_string()),
             Some("number") => Ok("Float64".to_string()),
             Some("boolean") => Ok("Boolean".to_string()),
@@ -85,6 +201,21 @@ pub fn json_type_to_polars_type(json_schema: &Value) -> Result<String, PolarsErr
                 }
             }
             Some("object") => {
+                // Maps → list of {key,value} structs, matching the kv map encoding
+                if let Some(values @ Value::Object(_)) = json_schema.get("additionalProperties") {
+                    let has_properties = json_schema
+                        .get("properties")
+                        .and_then(|p| p.as_object())
+                        .is_some_and(|p| !p.is_empty());
+                    if !has_properties {
+                        let value_type = json_type_to_polars_type(values)?;
+                        return Ok(format!(
+                            "List[Struct[key:{},value:{}]]",
+                            map_key_type(json_schema),
+                            value_type
+                        ));
+                    }
+                }
                 // Handle nested objects/structs
                 if let Some(properties) = json_schema.get("properties").and_then(|p| p.as_object())
                 {
@@ -109,6 +240,125 @@ pub fn json_type_to_polars_type(json_schema: &Value) -> Result<String, PolarsErr
     }
 }
 
+/// Merge the members of an `allOf` into the schema that holds it, so an object
+/// composed from several parts converts to one Struct.
+///
+/// The merge is shallow: `properties` are combined (a property in several members
+/// becomes an `allOf` of its schemas, merged in turn when it converts), `required`
+/// lists are joined, and any other keyword is taken from the first member with it.
+/// Members without a `type` but with `properties` are objects.
+pub fn merge_all_of(json_schema: &Value) -> Cow<'_, Value> {
+    let Some(Value::Array(members)) = json_schema.get("allOf") else {
+        return Cow::Borrowed(json_schema);
+    };
+    let mut merged = json_schema.as_object().cloned().unwrap_or_default();
+    merged.remove("allOf");
+    let mut parts: Vec<Map<String, Value>> = vec![std::mem::take(&mut merged)];
+    parts.extend(
+        members
+            .iter()
+            .filter_map(|member| merge_all_of(member).as_object().cloned()),
+    );
+
+    let mut properties: Map<String, Value> = Map::new();
+    let mut required: Vec<Value> = Vec::new();
+    for part in parts {
+        for (key, value) in part {
+            match (key.as_str(), value) {
+                ("properties", Value::Object(props)) => {
+                    for (name, prop) in props {
+                        match properties.get_mut(&name) {
+                            Some(Value::Object(existing)) if existing.contains_key("allOf") => {
+                                if let Some(Value::Array(all_of)) = existing.get_mut("allOf") {
+                                    all_of.push(prop);
+                                }
+                            }
+                            Some(existing) => {
+                                *existing = serde_json::json!({"allOf": [existing.take(), prop]});
+                            }
+                            None => {
+                                properties.insert(name, prop);
+                            }
+                        }
+                    }
+                }
+                ("required", Value::Array(names)) => {
+                    for name in names {
+                        if !required.contains(&name) {
+                            required.push(name);
+                        }
+                    }
+                }
+                (_, value) => {
+                    merged.entry(key).or_insert(value);
+                }
+            }
+        }
+    }
+    if !properties.is_empty() {
+        merged
+            .entry("type")
+            .or_insert_with(|| Value::String("object".to_string()));
+        merged.insert("properties".to_string(), Value::Object(properties));
+    }
+    if !required.is_empty() {
+        merged.insert("required".to_string(), Value::Array(required));
+    }
+    Cow::Owned(Value::Object(merged))
+}
+
+/// The values of an `enum` made of strings (besides `null`, for a nullable enum).
+fn string_enum(json_schema: &Value) -> Option<Vec<&str>> {
+    let values = json_schema.get("enum")?.as_array()?;
+    let categories: Vec<&str> = values
+        .iter()
+        .filter(|v| !v.is_null())
+        .map(|v| v.as_str())
+        .collect::<Option<_>>()?;
+    (!categories.is_empty()).then_some(categories)
+}
+
+/// The key type of a kv-encoded map: `Int64` if annotated under
+/// [`MAP_KEY_TYPE_KEY`] as integer, otherwise `String`.
+fn map_key_type(schema: &Value) -> &'static str {
+    if schema.get(MAP_KEY_TYPE_KEY).is_some_and(|t| t == "integer") {
+        "Int64"
+    } else {
+        "String"
+    }
+}
+
+/// The numeric dtype recorded under [`POLARS_DTYPE_KEY`].
+fn polars_dtype_extension(json_schema: &Value) -> Result<String, PolarsError> {
+    match json_schema[POLARS_DTYPE_KEY].as_str() {
+        Some(name) if NUMERIC_DTYPES.contains(&name) => Ok(name.to_string()),
+        _ => Err(conversion_error(format!(
+            "Unsupported {}: {}",
+            POLARS_DTYPE_KEY, json_schema[POLARS_DTYPE_KEY]
+        ))),
+    }
+}
+
+/// The Polars type for a union of branch types.
+///
+/// Null branches are dropped, since every Polars type is nullable. What's left
+/// converts to its single type, `Float64` for a mix of integers and numbers, or
+/// `String` for any other mix.
+fn union_to_polars_type(branch_types: Vec<String>) -> String {
+    let mut types: Vec<String> = Vec::new();
+    for t in branch_types {
+        if t != "Null" && !types.contains(&t) {
+            types.push(t);
+        }
+    }
+    match types.as_slice() {
+        [] => "Null".to_string(),
+        [single] => single.clone(),
+        _ if types.iter().all(|t| t == "Int64" || t == "Float64") => "Float64".to_string(),
+        _ => "String".to_string(),
+    }
+}
+
 /// Convert an Avro type definition to Polars DataType string representation.
 pub fn avro_type_to_polars_type(avro_schema: &Value) -> Result<String, PolarsError> {
     match avro_schema {
@@ -139,7 +389,11 @@ pub fn avro_type_to_polars_type(avro_schema: &Value) -> Result<String, PolarsErr
         Value::Object(obj) if obj.get("type") == Some(&Value::String("map".into())) => {
             if let Some(values) = obj.get("values") {
                 let value_type = avro_type_to_polars_type(values)?;
-                Ok(format!("List[Struct[key:String,value:{}]]", value_type))
+                Ok(format!(
+                    "List[Struct[key:{},value:{}]]",
+                    map_key_type(avro_schema),
+                    value_type
+                ))
             } else {
                 Ok("List[Struct[key:String,value:String]]".to_string()) // fallback default
             }
@@ -178,6 +432,234 @@ pub fn avro_type_to_polars_type(avro_schema: &Value) -> Result<String, PolarsErr
     }
 }
 
+/// Convert JSON schema to a Polars [`Schema`] of DataTypes, like
+/// [`schema_to_polars_fields`] but with dtype objects rather than their strings.
+pub fn schema_to_polars_schema(
+    schema: &Value,
+    format: SchemaFormat,
+) -> Result<Schema, PolarsError> {
+    let mut polars_schema = Schema::default();
+    match format {
+        SchemaFormat::JsonSchema => {
+            let schema = merge_all_of(schema);
+            if let Some(properties) = schema.get("properties").and_then(|p| p.as_object()) {
+                for (field_name, field_schema) in properties {
+                    let dtype = json_type_to_polars_dtype(field_schema)?;
+                    polars_schema.with_column(field_name.into(), dtype);
+                }
+            }
+        }
+        SchemaFormat::Avro => {
+            for (name, field_type) in avro_record_fields(schema) {
+                polars_schema.with_column(name.into(), avro_type_to_polars_dtype(field_type)?);
+            }
+        }
+    }
+    Ok(polars_schema)
+}
+
+/// Convert a JSON Schema type definition to a Polars DataType.
+///
+/// Converts as [`json_type_to_polars_type`] does, except that arrays without
+/// `items` and objects without `properties` become `String`.
+pub fn json_type_to_polars_dtype(json_schema: &Value) -> Result<DataType, PolarsError> {
+    let json_schema = &*merge_all_of(json_schema);
+    if let Some(categories) = string_enum(json_schema) {
+        return Ok(DataType::from_frozen_categories(FrozenCategories::new(
+            categories,
+        )?));
+    }
+
+    let union = json_schema
+        .get("anyOf")
+        .or_else(|| json_schema.get("oneOf"))
+        .and_then(|u| u.as_array());
+    if let Some(branches) = union {
+        let dtypes = branches
+            .iter()
+            .map(json_type_to_polars_dtype)
+            .collect::<Result<Vec<_>, _>>()?;
+        return Ok(union_to_polars_dtype(dtypes));
+    }
+
+    if let Some(Value::Array(type_names)) = json_schema.get("type") {
+        let dtypes = type_names
+            .iter()
+            .map(|name| {
+                let mut branch = json_schema.clone();
+                branch["type"] = name.clone();
+                json_type_to_polars_dtype(&branch)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        return Ok(union_to_polars_dtype(dtypes));
+    }
+
+    match json_schema.get("type").and_then(|t| t.as_str()) {
+        Some("integer" | "number") if json_schema.get(POLARS_DTYPE_KEY).is_some() => {
+            numeric_dtype(&polars_dtype_extension(json_schema)?)
+        }
+        Some("integer") => Ok(DataType::Int64),
+        Some("number") => Ok(DataType::Float64),
+        Some("boolean") => Ok(DataType::Boolean),
+        Some("null") => Ok(DataType::Null),
+        Some("array") => match json_schema.get("items") {
+            Some(items) => Ok(DataType::List(Box::new(json_type_to_polars_dtype(items)?))),
+            None => Ok(DataType::String),
+        },
+        Some("object") => {
+            if let Some(values @ Value::Object(_)) = json_schema.get("additionalProperties") {
+                let has_properties = json_schema
+                    .get("properties")
+                    .and_then(|p| p.as_object())
+                    .is_some_and(|p| !p.is_empty());
+                if !has_properties {
+                    let value_type = json_type_to_polars_dtype(values)?;
+                    return Ok(map_dtype(map_key_dtype(json_schema), value_type));
+                }
+            }
+            match json_schema.get("properties").and_then(|p| p.as_object()) {
+                Some(properties) => {
+                    let fields = properties
+                        .iter()
+                        .map(|(name, field_schema)| {
+                            Ok(Field::new(
+                                name.into(),
+                                json_type_to_polars_dtype(field_schema)?,
+                            ))
+                        })
+                        .collect::<Result<Vec<_>, PolarsError>>()?;
+                    Ok(DataType::Struct(fields))
+                }
+                None => Ok(DataType::String),
+            }
+        }
+        Some("string") | None => Ok(DataType::String),
+        Some(other) => Err(conversion_error(format!(
+            "Unsupported JSON Schema type: {}",
+            other
+        ))),
+    }
+}
+
+/// Convert an Avro type definition to a Polars DataType.
+///
+/// Converts as [`avro_type_to_polars_type`] does, except that an array without
+/// `items` becomes `String`.
+pub fn avro_type_to_polars_dtype(avro_schema: &Value) -> Result<DataType, PolarsError> {
+    match avro_schema {
+        Value::String(s) => match s.as_str() {
+            "string" => Ok(DataType::String),
+            "int" | "long" => Ok(DataType::Int64),
+            "float" | "double" => Ok(DataType::Float64),
+            "boolean" => Ok(DataType::Boolean),
+            "null" => Ok(DataType::Null),
+            other => Err(conversion_error(format!(
+                "Unsupported Avro type: {}",
+                other
+            ))),
+        },
+        Value::Object(obj) if obj.get("type") == Some(&Value::String("array".into())) => {
+            match obj.get("items") {
+                Some(items) => Ok(DataType::List(Box::new(avro_type_to_polars_dtype(items)?))),
+                None => Ok(DataType::String),
+            }
+        }
+        Value::Object(obj) if obj.get("type") == Some(&Value::String("map".into())) => {
+            let value_type = match obj.get("values") {
+                Some(values) => avro_type_to_polars_dtype(values)?,
+                None => DataType::String,
+            };
+            Ok(map_dtype(map_key_dtype(avro_schema), value_type))
+        }
+        Value::Object(obj) if obj.get("type") == Some(&Value::String("record".into())) => {
+            let fields = avro_record_fields(avro_schema)
+                .map(|(name, ftype)| Ok(Field::new(name.into(), avro_type_to_polars_dtype(ftype)?)))
+                .collect::<Result<Vec<_>, PolarsError>>()?;
+            Ok(DataType::Struct(fields))
+        }
+        Value::Array(types) => {
+            let non_null = types.iter().find(|t| *t != &Value::String("null".into()));
+            match non_null {
+                Some(branch) => avro_type_to_polars_dtype(branch),
+                None => Ok(DataType::Null),
+            }
+        }
+        _ => Err(conversion_error(format!(
+            "Unsupported Avro schema element: {}",
+            avro_schema
+        ))),
+    }
+}
+
+/// The (name, type) of each field of an Avro record.
+fn avro_record_fields(record: &Value) -> impl Iterator<Item = (&str, &Value)> {
+    record
+        .get("fields")
+        .and_then(|f| f.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|f| Some((f.get("name")?.as_str().unwrap_or(""), f.get("type")?)))
+}
+
+/// A kv-encoded map: a list of `{key, value}` structs.
+fn map_dtype(key_type: DataType, value_type: DataType) -> DataType {
+    DataType::List(Box::new(DataType::Struct(vec![
+        Field::new("key".into(), key_type),
+        Field::new("value".into(), value_type),
+    ])))
+}
+
+/// The key DataType of a kv-encoded map, as [`map_key_type`] names it.
+fn map_key_dtype(schema: &Value) -> DataType {
+    match map_key_type(schema) {
+        "Int64" => DataType::Int64,
+        _ => DataType::String,
+    }
+}
+
+/// The DataType of one of the [`NUMERIC_DTYPES`].
+fn numeric_dtype(name: &str) -> Result<DataType, PolarsError> {
+    Ok(match name {
+        "Int8" => DataType::Int8,
+        "Int16" => DataType::Int16,
+        "Int32" => DataType::Int32,
+        "Int64" => DataType::Int64,
+        "UInt8" => DataType::UInt8,
+        "UInt16" => DataType::UInt16,
+        "UInt32" => DataType::UInt32,
+        "UInt64" => DataType::UInt64,
+        "Float32" => DataType::Float32,
+        "Float64" => DataType::Float64,
+        other => {
+            return Err(conversion_error(format!(
+                "Unsupported {}: {}",
+                POLARS_DTYPE_KEY, other
+            )))
+        }
+    })
+}
+
+/// The Polars DataType for a union of branch DataTypes, as [`union_to_polars_type`].
+fn union_to_polars_dtype(branch_dtypes: Vec<DataType>) -> DataType {
+    let mut dtypes: Vec<DataType> = Vec::new();
+    for dtype in branch_dtypes {
+        if dtype != DataType::Null && !dtypes.contains(&dtype) {
+            dtypes.push(dtype);
+        }
+    }
+    match dtypes.as_slice() {
+        [] => DataType::Null,
+        [single] => single.clone(),
+        _ if dtypes
+            .iter()
+            .all(|t| matches!(t, DataType::Int64 | DataType::Float64)) =>
+        {
+            DataType::Float64
+        }
+        _ => DataType::String,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -211,6 +693,112 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_nullable_union() {
+        assert_eq!(
+            json_type_to_polars_type(&json!({"type": ["null", "string"]})).unwrap(),
+            "String"
+        );
+        let schema = json!({"type": ["array", "null"], "items": {"type": "integer"}});
+        assert_eq!(json_type_to_polars_type(&schema).unwrap(), "List[Int64]");
+        let schema = json!({"anyOf": [{"type": "null"}, {"type": "boolean"}]});
+        assert_eq!(json_type_to_polars_type(&schema).unwrap(), "Boolean");
+    }
+
+    #[test]
+    fn test_numeric_union_widens() {
+        assert_eq!(
+            json_type_to_polars_type(&json!({"type": ["integer", "number", "null"]})).unwrap(),
+            "Float64"
+        );
+        let schema = json!({"oneOf": [{"type": "integer"}, {"type": "number"}]});
+        assert_eq!(json_type_to_polars_type(&schema).unwrap(), "Float64");
+    }
+
+    #[test]
+    fn test_heterogeneous_union_falls_back_to_string() {
+        assert_eq!(
+            json_type_to_polars_type(&json!({"type": ["string", "integer"]})).unwrap(),
+            "String"
+        );
+        let schema = json!({"anyOf": [
+            {"type": "object", "properties": {"a": {"type": "string"}}},
+            {"type": "array", "items": {"type": "string"}}
+        ]});
+        assert_eq!(json_type_to_polars_type(&schema).unwrap(), "String");
+        assert_eq!(
+            json_type_to_polars_type(&json!({"anyOf": []})).unwrap(),
+            "Null"
+        );
+    }
+
+    #[test]
+    fn test_polars_dtype_extension() {
+        let schema = json!({"type": "integer", "minimum": 0, "x-polars-dtype": "UInt8"});
+        assert_eq!(json_type_to_polars_type(&schema).unwrap(), "UInt8");
+        let schema = json!({"type": ["null", "number"], "x-polars-dtype": "Float32"});
+        assert_eq!(json_type_to_polars_type(&schema).unwrap(), "Float32");
+        let schema = json!({"type": "integer", "x-polars-dtype": "Int256"});
+        assert!(json_type_to_polars_type(&schema).is_err());
+    }
+
+    #[test]
+    fn test_string_enum() {
+        let schema = json!({"type": "string", "enum": ["low", "mid", "high"]});
+        assert_eq!(
+            json_type_to_polars_type(&schema).unwrap(),
+            r#"Enum["low","mid","high"]"#
+        );
+        let schema = json!({"type": ["string", "null"], "enum": ["a", null]});
+        assert_eq!(json_type_to_polars_type(&schema).unwrap(), r#"Enum["a"]"#);
+        // Enums of other values stay their plain type
+        let schema = json!({"type": "integer", "enum": [1, 2]});
+        assert_eq!(json_type_to_polars_type(&schema).unwrap(), "Int64");
+    }
+
+    #[test]
+    fn test_map_type() {
+        let map_schema = json!({
+            "type": "object",
+            "additionalProperties": {"type": "integer"}
+        });
+        assert_eq!(
+            json_type_to_polars_type(&map_schema).unwrap(),
+            "List[Struct[key:String,value:Int64]]"
+        );
+
+        // Maps of records, as inferred by genson-core
+        let map_schema = json!({
+            "type": "object",
+            "additionalProperties": {
+                "type": "object",
+                "properties": {"language": {"type": "string"}}
+            }
+        });
+        assert_eq!(
+            json_type_to_polars_type(&map_schema).unwrap(),
+            "List[Struct[key:String,value:Struct[language:String]]]"
+        );
+    }
+
+    #[test]
+    fn test_integer_key_map_type() {
+        let map_schema = json!({
+            "type": "object",
+            "additionalProperties": {"type": "string"},
+            "x-key-type": "integer"
+        });
+        assert_eq!(
+            json_type_to_polars_type(&map_schema).unwrap(),
+            "List[Struct[key:Int64,value:String]]"
+        );
+        let avro = json!({"type": "map", "values": "long", "x-key-type": "integer"});
+        assert_eq!(
+            avro_type_to_polars_type(&avro).unwrap(),
+            "List[Struct[key:Int64,value:Int64]]"
+        );
+    }
+
     #[test]
     fn test_struct_type() {
         let struct_schema = json!({
@@ -226,4 +814,193 @@ mod tests {
         assert!(result.contains("name:String"));
         assert!(result.contains("age:Int64"));
     }
+
+    #[test]
+    fn test_json_schema_nullability() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "id": {"type": "integer"},
+                "name": {"type": ["string", "null"]},
+                "tag": {"anyOf": [{"type": "null"}, {"type": "string"}]},
+                "note": {"type": "string"}
+            },
+            "required": ["id", "name", "tag"]
+        });
+        let fields =
+            schema_to_polars_fields_with_nullability(&schema, SchemaFormat::JsonSchema, false)
+                .unwrap();
+        let nullable: Vec<(&str, bool)> = fields
+            .iter()
+            .map(|f| (f.name.as_str(), f.nullable))
+            .collect();
+        assert_eq!(
+            nullable,
+            [("id", false), ("name", true), ("tag", true), ("note", true)]
+        );
+        assert_eq!(fields[0].dtype, "Int64");
+    }
+
+    #[test]
+    fn test_avro_nullability() {
+        let schema = json!({
+            "type": "record",
+            "name": "doc",
+            "fields": [
+                {"name": "id", "type": "long"},
+                {"name": "name", "type": ["null", "string"]},
+                {"name": "tags", "type": {"type": "array", "items": "string"}}
+            ]
+        });
+        let fields =
+            schema_to_polars_fields_with_nullability(&schema, SchemaFormat::Avro, false).unwrap();
+        let nullable: Vec<bool> = fields.iter().map(|f| f.nullable).collect();
+        assert_eq!(nullable, [false, true, false]);
+    }
+
+    #[test]
+    fn test_all_of_merges_object_members() {
+        let schema = json!({
+            "allOf": [
+                {
+                    "type": "object",
+                    "properties": {"id": {"type": "integer"}},
+                    "required": ["id"]
+                },
+                {
+                    "properties": {
+                        "meta": {
+                            "type": "object",
+                            "properties": {"source": {"type": "string"}}
+                        }
+                    },
+                    "required": ["meta"]
+                }
+            ]
+        });
+        assert_eq!(
+            json_type_to_polars_type(&schema).unwrap(),
+            "Struct[id:Int64,meta:Struct[source:String]]"
+        );
+        let merged = merge_all_of(&schema);
+        assert_eq!(merged["required"], json!(["id", "meta"]));
+        assert!(merged.get("allOf").is_none());
+    }
+
+    #[test]
+    fn test_all_of_property_in_several_members() {
+        let schema = json!({
+            "type": "object",
+            "properties": {"a": {"type": "object", "properties": {"x": {"type": "string"}}}},
+            "allOf": [
+                {"properties": {"a": {"properties": {"y": {"type": "boolean"}}}}},
+                {"allOf": [{"properties": {"b": {"type": "number"}}}]}
+            ]
+        });
+        assert_eq!(
+            json_type_to_polars_type(&schema).unwrap(),
+            "Struct[a:Struct[x:String,y:Boolean],b:Float64]"
+        );
+    }
+
+    #[test]
+    fn test_all_of_top_level_fields() {
+        let schema = json!({
+            "allOf": [
+                {"properties": {"id": {"type": "integer"}}, "required": ["id"]},
+                {"properties": {"name": {"type": "string"}}}
+            ]
+        });
+        let fields =
+            schema_to_polars_fields_with_nullability(&schema, SchemaFormat::JsonSchema, false)
+                .unwrap();
+        let converted: Vec<(&str, &str, bool)> = fields
+            .iter()
+            .map(|f| (f.name.as_str(), f.dtype.as_str(), f.nullable))
+            .collect();
+        assert_eq!(
+            converted,
+            [("id", "Int64", false), ("name", "String", true)]
+        );
+    }
+
+    #[test]
+    fn test_all_of_keeps_non_object_keywords() {
+        let schema = json!({"allOf": [{"type": "integer"}, {"minimum": 0}]});
+        assert_eq!(json_type_to_polars_type(&schema).unwrap(), "Int64");
+    }
+
+    #[test]
+    fn test_json_schema_to_polars_schema() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "id": {"type": "integer"},
+                "score": {"type": ["integer", "number", "null"]},
+                "tags": {"type": "array", "items": {"type": "string"}},
+                "level": {"enum": ["low", "high"]},
+                "small": {"type": "integer", "x-polars-dtype": "Int8"},
+                "counts": {
+                    "type": "object",
+                    "additionalProperties": {"type": "integer"},
+                    "x-key-type": "integer"
+                },
+                "meta": {"type": "object", "properties": {"a": {"type": "boolean"}}},
+                "loose": {"type": "object"}
+            }
+        });
+        let converted = schema_to_polars_schema(&schema, SchemaFormat::JsonSchema).unwrap();
+        let dtype = |name: &str| converted.get(name).unwrap().clone();
+        assert_eq!(dtype("id"), DataType::Int64);
+        assert_eq!(dtype("score"), DataType::Float64);
+        assert_eq!(dtype("tags"), DataType::List(Box::new(DataType::String)));
+        assert!(matches!(dtype("level"), DataType::Enum(..)));
+        assert_eq!(dtype("small"), DataType::Int8);
+        assert_eq!(
+            dtype("counts"),
+            DataType::List(Box::new(DataType::Struct(vec![
+                Field::new("key".into(), DataType::Int64),
+                Field::new("value".into(), DataType::Int64),
+            ])))
+        );
+        assert_eq!(
+            dtype("meta"),
+            DataType::Struct(vec![Field::new("a".into(), DataType::Boolean)])
+        );
+        assert_eq!(dtype("loose"), DataType::String);
+    }
+
+    #[test]
+    fn test_avro_to_polars_schema() {
+        let schema = json!({
+            "type": "record",
+            "name": "document",
+            "fields": [
+                {"name": "id", "type": "long"},
+                {"name": "name", "type": ["null", "string"]},
+                {"name": "labels", "type": {"type": "map", "values": "string"}},
+                {"name": "point", "type": {
+                    "type": "record",
+                    "name": "point",
+                    "fields": [{"name": "x", "type": "double"}]
+                }}
+            ]
+        });
+        let converted = schema_to_polars_schema(&schema, SchemaFormat::Avro).unwrap();
+        let names: Vec<&str> = converted.iter_names().map(|n| n.as_str()).collect();
+        assert_eq!(names, ["id", "name", "labels", "point"]);
+        assert_eq!(converted.get("name"), Some(&DataType::String));
+        assert_eq!(
+            converted.get("point"),
+            Some(&DataType::Struct(vec![Field::new(
+                "x".into(),
+                DataType::Float64
+            )]))
+        );
+        assert!(schema_to_polars_schema(
+            &json!({"fields": [{"name": "d", "type": "bytes"}]}),
+            SchemaFormat::Avro
+        )
+        .is_err());
+    }
 }
@@ -7,7 +7,7 @@
 //! # Examples
 //!
 //! ```rust
-//! use polars_jsonschema_bridge::{schema_to_polars_fields, polars_schema_to_json_schema, JsonSchemaOptions};
+//! use polars_jsonschema_bridge::{schema_to_polars_fields, polars_schema_to_json_schema, JsonSchemaOptions, SchemaFormat};
 //! use polars::prelude::*;
 //! use serde_json::json;
 //!
@@ -19,7 +19,7 @@
 //!         "age": {"type": "integer"}
 //!     }
 //! });
-//! let fields = schema_to_polars_fields(&json_schema, false).unwrap();
+//! let fields = schema_to_polars_fields(&json_schema, SchemaFormat::JsonSchema, false).unwrap();
 //!
 //! // Polars → JSON Schema  
 //! let mut schema = Schema::default();
@@ -32,6 +32,10 @@ pub mod serialise;
 pub mod types;
 
 // Re-export main functions
-pub use deserialise::{json_type_to_polars_type, schema_to_polars_fields, SchemaFormat};
+pub use deserialise::{
+    avro_type_to_polars_dtype, json_type_to_polars_dtype, json_type_to_polars_type,
+    schema_to_polars_fields, schema_to_polars_fields_with_nullability, schema_to_polars_schema,
+    PolarsField, SchemaFormat,
+};
 pub use serialise::{polars_dtype_to_json_schema, polars_schema_to_json_schema, JsonSchemaOptions};
-pub use types::conversion_error;
+pub use types::{conversion_error, MAP_KEY_TYPE_KEY, POLARS_DTYPE_KEY};
@@ -1,6 +1,6 @@
 //! Convert Polars types to JSON Schema.
 
-use crate::types::conversion_error;
+use crate::types::{conversion_error, MAP_KEY_TYPE_KEY, POLARS_DTYPE_KEY};
 use polars::prelude::*;
 use serde_json::{json, Map, Value};
 
@@ -12,6 +12,13 @@ pub struct JsonSchemaOptions {
     pub description: Option<String>,
     pub optional_fields: std::collections::HashSet<String>,
     pub additional_properties: bool,
+    /// Convert `List[Struct[key:String,value:T]]` (the kv map encoding) to a map
+    /// schema with `additionalProperties: T` rather than an array of objects.
+    /// An `Int64` key marks the map `"x-key-type": "integer"`.
+    pub kv_maps: bool,
+    /// Record sized and unsigned numeric dtypes under `x-polars-dtype`, so that
+    /// converting back doesn't widen them to Int64/Float64.
+    pub polars_dtypes: bool,
 }
 
 impl Default for JsonSchemaOptions {
@@ -22,6 +29,8 @@ impl Default for JsonSchemaOptions {
             description: None,
             optional_fields: std::collections::HashSet::new(),
             additional_properties: false,
+            kv_maps: false,
+            polars_dtypes: false,
         }
     }
 }
@@ -59,6 +68,16 @@ impl JsonSchemaOptions {
         self.additional_properties = allow;
         self
     }
+
+    pub fn with_kv_maps(mut self, kv_maps: bool) -> Self {
+        self.kv_maps = kv_maps;
+        self
+    }
+
+    pub fn with_polars_dtypes(mut self, polars_dtypes: bool) -> Self {
+        self.polars_dtypes = polars_dtypes;
+        self
+    }
 }
 
 /// Convert a Polars Schema to JSON Schema with preserved insertion order.
@@ -111,6 +130,44 @@ pub fn polars_schema_to_json_schema(
     Ok(Value::Object(schema_obj))
 }
 
+/// Add the numeric `dtype` under [`POLARS_DTYPE_KEY`] if `polars_dtypes` is set and
+/// it isn't the one converting back picks anyway (Int64 or Float64).
+fn with_polars_dtype(mut schema: Value, dtype: &DataType, options: &JsonSchemaOptions) -> Value {
+    let name = match dtype {
+        DataType::Int8 => "Int8",
+        DataType::Int16 => "Int16",
+        DataType::Int32 => "Int32",
+        DataType::UInt8 => "UInt8",
+        DataType::UInt16 => "UInt16",
+        DataType::UInt32 => "UInt32",
+        DataType::UInt64 => "UInt64",
+        DataType::Float32 => "Float32",
+        _ => return schema,
+    };
+    if options.polars_dtypes {
+        schema[POLARS_DTYPE_KEY] = json!(name);
+    }
+    schema
+}
+
+/// The key and value types of a kv-encoded map entry, `Struct[key:K,value:T]`
+/// with a `String` or `Int64` key.
+fn kv_map_entry(entry: &DataType) -> Option<(&DataType, &DataType)> {
+    match entry {
+        DataType::Struct(fields) => match fields.as_slice() {
+            [key, value]
+                if key.name() == "key"
+                    && matches!(key.dtype(), DataType::String | DataType::Int64)
+                    && value.name() == "value" =>
+            {
+                Some((key.dtype(), value.dtype()))
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
 /// Convert a Polars DataType to JSON Schema type definition.
 pub fn polars_dtype_to_json_schema(
     dtype: &DataType,
@@ -119,16 +176,21 @@ pub fn polars_dtype_to_json_schema(
     match dtype {
         DataType::Boolean => Ok(json!({"type": "boolean"})),
 
-        DataType::Int8 | DataType::Int16 | DataType::Int32 | DataType::Int64 => {
-            Ok(json!({"type": "integer"}))
-        }
+        DataType::Int8 | DataType::Int16 | DataType::Int32 | DataType::Int64 => Ok(
+            with_polars_dtype(json!({"type": "integer"}), dtype, options),
+        ),
 
-        DataType::UInt8 | DataType::UInt16 | DataType::UInt32 | DataType::UInt64 => Ok(json!({
-            "type": "integer",
-            "minimum": 0
-        })),
+        DataType::UInt8 | DataType::UInt16 | DataType::UInt32 | DataType::UInt64 => {
+            let schema = json!({
+                "type": "integer",
+                "minimum": 0
+            });
+            Ok(with_polars_dtype(schema, dtype, options))
+        }
 
-        DataType::Float32 | DataType::Float64 => Ok(json!({"type": "number"})),
+        DataType::Float32 | DataType::Float64 => {
+            Ok(with_polars_dtype(json!({"type": "number"}), dtype, options))
+        }
 
         DataType::String => Ok(json!({"type": "string"})),
 
@@ -165,6 +227,17 @@ pub fn polars_dtype_to_json_schema(
         })),
 
         DataType::List(inner) => {
+            if let Some((key, values)) = kv_map_entry(inner).filter(|_| options.kv_maps) {
+                let values_schema = polars_dtype_to_json_schema(values, options)?;
+                let mut map = json!({
+                    "type": "object",
+                    "additionalProperties": values_schema
+                });
+                if key == &DataType::Int64 {
+                    map[MAP_KEY_TYPE_KEY] = json!("integer");
+                }
+                return Ok(map);
+            }
             let items_schema = polars_dtype_to_json_schema(inner, options)?;
             Ok(json!({
                 "type": "array",
@@ -241,13 +314,16 @@ pub fn polars_dtype_to_json_schema(
             Ok(schema)
         }
 
-        DataType::Enum(_, _) => {
-            // For enums, we treat them as string types
-            // Note: Extracting specific enum values requires more complex handling
-            Ok(json!({
+        DataType::Enum(categories, _) => {
+            let mut schema = json!({
                 "type": "string",
                 "description": "Enumerated string values"
-            }))
+            });
+            let values: Vec<&str> = categories.categories().values_iter().collect();
+            if !values.is_empty() {
+                schema["enum"] = json!(values);
+            }
+            Ok(schema)
         }
 
         // Handle newer Polars types
@@ -314,6 +390,72 @@ mod tests {
         assert_eq!(result, expected);
     }
 
+    #[test]
+    fn test_polars_dtypes_extension() {
+        let options = &JsonSchemaOptions::new().with_polars_dtypes(true);
+
+        assert_eq!(
+            polars_dtype_to_json_schema(&DataType::UInt8, options).unwrap(),
+            json!({"type": "integer", "minimum": 0, "x-polars-dtype": "UInt8"})
+        );
+        assert_eq!(
+            polars_dtype_to_json_schema(&DataType::Float32, options).unwrap(),
+            json!({"type": "number", "x-polars-dtype": "Float32"})
+        );
+        // The default widths need no annotation
+        assert_eq!(
+            polars_dtype_to_json_schema(&DataType::Int64, options).unwrap(),
+            json!({"type": "integer"})
+        );
+        // Off by default
+        assert_eq!(
+            polars_dtype_to_json_schema(&DataType::Int16, &JsonSchemaOptions::new()).unwrap(),
+            json!({"type": "integer"})
+        );
+    }
+
+    #[test]
+    fn test_kv_map_type() {
+        let entry = DataType::Struct(vec![
+            Field::new("key".into(), DataType::String),
+            Field::new("value".into(), DataType::Int64),
+        ]);
+        let list_dtype = DataType::List(Box::new(entry));
+
+        let result =
+            polars_dtype_to_json_schema(&list_dtype, &JsonSchemaOptions::new().with_kv_maps(true))
+                .unwrap();
+        assert_eq!(
+            result,
+            json!({"type": "object", "additionalProperties": {"type": "integer"}})
+        );
+
+        // Off by default: a list of key/value objects
+        let result = polars_dtype_to_json_schema(&list_dtype, &JsonSchemaOptions::new()).unwrap();
+        assert_eq!(result["type"], "array");
+    }
+
+    #[test]
+    fn test_kv_map_integer_keys() {
+        let entry = DataType::Struct(vec![
+            Field::new("key".into(), DataType::Int64),
+            Field::new("value".into(), DataType::String),
+        ]);
+        let list_dtype = DataType::List(Box::new(entry));
+
+        let result =
+            polars_dtype_to_json_schema(&list_dtype, &JsonSchemaOptions::new().with_kv_maps(true))
+                .unwrap();
+        assert_eq!(
+            result,
+            json!({
+                "type": "object",
+                "additionalProperties": {"type": "string"},
+                "x-key-type": "integer"
+            })
+        );
+    }
+
     #[test]
     fn test_struct_type() {
         let options = &JsonSchemaOptions::default();
@@ -395,6 +537,18 @@ mod tests {
         assert_eq!(result["description"], "Categorical data");
     }
 
+    #[test]
+    fn test_enum_type_lists_categories() {
+        let options = &JsonSchemaOptions::default();
+        let categories = FrozenCategories::new(["low", "mid", "high"]).unwrap();
+        let enum_dtype = DataType::from_frozen_categories(categories);
+
+        let result = polars_dtype_to_json_schema(&enum_dtype, options).unwrap();
+
+        assert_eq!(result["type"], "string");
+        assert_eq!(result["enum"], json!(["low", "mid", "high"]));
+    }
+
     #[test]
     fn test_required_fields_are_sorted() {
         let options = &JsonSchemaOptions::default();
@@ -6,3 +6,17 @@ use polars::prelude::PolarsError;
 pub fn conversion_error(msg: impl Into<String>) -> PolarsError {
     PolarsError::ComputeError(msg.into().into())
 }
+
+/// Vendor extension keyword recording the exact Polars numeric dtype of a schema,
+/// e.g. `{"type": "integer", "x-polars-dtype": "UInt8"}`.
+pub const POLARS_DTYPE_KEY: &str = "x-polars-dtype";
+
+/// Keyword marking a map whose keys are integers, e.g.
+/// `{"type": "object", "additionalProperties": {...}, "x-key-type": "integer"}`.
+/// Such maps convert to `List[Struct[key:Int64,value:T]]`.
+pub const MAP_KEY_TYPE_KEY: &str = "x-key-type";
+
+/// The numeric dtypes that can be recorded under [`POLARS_DTYPE_KEY`].
+pub(crate) const NUMERIC_DTYPES: [&str; 10] = [
+    "Int8", "Int16", "Int32", "Int64", "UInt8", "UInt16", "UInt32", "UInt64", "Float32", "Float64",
+];
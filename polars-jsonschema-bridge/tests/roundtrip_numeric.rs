@@ -0,0 +1,46 @@
+use polars::prelude::*;
+use polars_jsonschema_bridge::{
+    polars_schema_to_json_schema, schema_to_polars_fields, JsonSchemaOptions, SchemaFormat,
+};
+
+#[test]
+fn test_numeric_dtypes_round_trip() {
+    let dtypes = [
+        DataType::Int8,
+        DataType::Int16,
+        DataType::Int32,
+        DataType::Int64,
+        DataType::UInt8,
+        DataType::UInt16,
+        DataType::UInt32,
+        DataType::UInt64,
+        DataType::Float32,
+        DataType::Float64,
+    ];
+    let schema = Schema::from_iter(
+        dtypes
+            .iter()
+            .enumerate()
+            .map(|(i, dtype)| Field::new(format!("col{}", i).into(), dtype.clone())),
+    );
+
+    let options = JsonSchemaOptions::new().with_polars_dtypes(true);
+    let json_schema = polars_schema_to_json_schema(&schema, &options).unwrap();
+    let fields = schema_to_polars_fields(&json_schema, SchemaFormat::JsonSchema, false).unwrap();
+
+    let names: Vec<&str> = fields.iter().map(|(_, dtype)| dtype.as_str()).collect();
+    assert_eq!(
+        names,
+        [
+            "Int8", "Int16", "Int32", "Int64", "UInt8", "UInt16", "UInt32", "UInt64", "Float32",
+            "Float64"
+        ]
+    );
+
+    // Without the extension every column widens
+    let json_schema = polars_schema_to_json_schema(&schema, &JsonSchemaOptions::new()).unwrap();
+    let fields = schema_to_polars_fields(&json_schema, SchemaFormat::JsonSchema, false).unwrap();
+    assert!(fields
+        .iter()
+        .all(|(_, dtype)| dtype == "Int64" || dtype == "Float64"));
+}